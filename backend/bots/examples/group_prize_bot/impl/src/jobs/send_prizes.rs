@@ -7,7 +7,7 @@ use std::{cmp, time::Duration};
 use tracing::{error, trace};
 use types::{
     CanisterId, CompletedCryptoTransaction, CryptoTransaction, Cryptocurrency, MessageContentInitial, MessageId,
-    PrizeContentInitial, TimestampMillis, TimestampNanos,
+    PrizeContentInitial, PrizeDistribution, TimestampMillis, TimestampNanos,
 };
 
 pub(crate) fn start_job(state: &mut RuntimeState) {
@@ -184,6 +184,8 @@ async fn send_prize_message_to_group(
         end_date,
         caption: None,
         diamond_only: false,
+        distribution: PrizeDistribution::FirstComeFirstServed,
+        eligibility: Vec::new(),
     });
 
     let c2c_args = group_canister::send_message_v2::Args {
@@ -2,6 +2,7 @@ use candid::Principal;
 use canister_agent_utils::{build_ic_agent, get_canister_wasm, install_wasm, set_controllers, CanisterIds, CanisterName};
 use ic_agent::{Agent, Identity};
 use ic_utils::interfaces::ManagementCanister;
+use std::collections::HashMap;
 use types::{BuildVersion, CanisterWasm, Cycles};
 
 const T: Cycles = 1_000_000_000_000;
@@ -63,6 +64,7 @@ async fn install_service_canisters_impl(
         storage_index_canister_id: canister_ids.storage_index,
         cycles_dispenser_canister_id: canister_ids.cycles_dispenser,
         internet_identity_canister_id: canister_ids.nns_internet_identity,
+        online_users_canister_id: canister_ids.online_users,
         wasm_version: version,
         test_mode,
     };
@@ -170,6 +172,7 @@ async fn install_service_canisters_impl(
         cycles_dispenser_canister_id: canister_ids.cycles_dispenser,
         icp_ledger_canister_id: canister_ids.nns_ledger,
         chat_ledger_canister_id: canister_ids.nns_ledger, // TODO This should be the CHAT ledger
+        exchange_canister_ids: HashMap::new(),
         wasm_version: version,
         test_mode,
     };
@@ -5,6 +5,8 @@ use ic_utils::call::AsyncCall;
 use ic_utils::interfaces::management_canister::builders::InstallMode;
 use ic_utils::interfaces::management_canister::CanisterStatus;
 use ic_utils::interfaces::ManagementCanister;
+use market_maker_canister::ExchangeId;
+use std::collections::HashMap;
 use types::{BuildVersion, CanisterId, CanisterWasm, UpgradeCanisterWasmArgs};
 
 pub async fn upgrade_group_index_canister(
@@ -30,6 +32,7 @@ pub async fn upgrade_user_index_canister(
     identity: Box<dyn Identity>,
     url: String,
     user_index_canister_id: CanisterId,
+    online_users_canister_id: CanisterId,
     version: BuildVersion,
 ) {
     upgrade_top_level_canister(
@@ -37,7 +40,10 @@ pub async fn upgrade_user_index_canister(
         url,
         user_index_canister_id,
         version,
-        user_index_canister::post_upgrade::Args { wasm_version: version },
+        user_index_canister::post_upgrade::Args {
+            wasm_version: version,
+            online_users_canister_id: Some(online_users_canister_id),
+        },
         CanisterName::UserIndex,
     )
     .await;
@@ -163,6 +169,7 @@ pub async fn upgrade_market_maker_canister(
     identity: Box<dyn Identity>,
     url: String,
     market_maker_canister_id: CanisterId,
+    exchange_canister_ids: HashMap<ExchangeId, CanisterId>,
     version: BuildVersion,
 ) {
     upgrade_top_level_canister(
@@ -170,7 +177,10 @@ pub async fn upgrade_market_maker_canister(
         url,
         market_maker_canister_id,
         version,
-        market_maker_canister::post_upgrade::Args { wasm_version: version },
+        market_maker_canister::post_upgrade::Args {
+            wasm_version: version,
+            exchange_canister_ids,
+        },
         CanisterName::MarketMaker,
     )
     .await;
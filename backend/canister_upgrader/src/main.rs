@@ -1,6 +1,7 @@
 use canister_agent_utils::{get_dfx_identity, CanisterName};
 use canister_upgrader::*;
 use clap::Parser;
+use market_maker_canister::{ICDEX_EXCHANGE_ID, ICPSWAP_EXCHANGE_ID, SONIC_EXCHANGE_ID};
 use types::{BuildVersion, CanisterId};
 
 #[tokio::main]
@@ -19,7 +20,18 @@ async fn main() {
             upgrade_local_group_index_canister(identity, opts.url, opts.group_index, opts.version).await
         }
         CanisterName::GroupIndex => upgrade_group_index_canister(identity, opts.url, opts.group_index, opts.version).await,
-        CanisterName::MarketMaker => upgrade_market_maker_canister(identity, opts.url, opts.market_maker, opts.version).await,
+        CanisterName::MarketMaker => {
+            let exchange_canister_ids = [
+                (ICDEX_EXCHANGE_ID, opts.icdex),
+                (ICPSWAP_EXCHANGE_ID, opts.icpswap),
+                (SONIC_EXCHANGE_ID, opts.sonic),
+            ]
+            .into_iter()
+            .filter_map(|(exchange_id, canister_id)| canister_id.map(|c| (exchange_id, c)))
+            .collect();
+
+            upgrade_market_maker_canister(identity, opts.url, opts.market_maker, exchange_canister_ids, opts.version).await
+        }
         CanisterName::NeuronController => {
             upgrade_neuron_controller_canister(identity, opts.url, opts.neuron_controller, opts.version).await
         }
@@ -44,7 +56,9 @@ async fn main() {
         CanisterName::LocalUserIndex => {
             upgrade_local_user_index_canister(identity, opts.url, opts.user_index, opts.version).await
         }
-        CanisterName::UserIndex => upgrade_user_index_canister(identity, opts.url, opts.user_index, opts.version).await,
+        CanisterName::UserIndex => {
+            upgrade_user_index_canister(identity, opts.url, opts.user_index, opts.online_users, opts.version).await
+        }
     };
 }
 
@@ -86,6 +100,16 @@ struct Opts {
     #[arg(long)]
     neuron_controller: CanisterId,
 
+    // The exchange canister ids used by the market maker, any which are set replace the existing ids
+    #[arg(long)]
+    icdex: Option<CanisterId>,
+
+    #[arg(long)]
+    icpswap: Option<CanisterId>,
+
+    #[arg(long)]
+    sonic: Option<CanisterId>,
+
     #[arg(long)]
     canister_to_upgrade: CanisterName,
 
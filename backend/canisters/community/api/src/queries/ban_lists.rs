@@ -0,0 +1,23 @@
+use candid::CandidType;
+use serde::{Deserialize, Serialize};
+use types::{BanListAuditRecord, BanListSubscriptionSummary};
+
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub struct Args {
+    pub max_audit_records: u32,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub enum Response {
+    Success(SuccessResult),
+    UserNotInCommunity,
+    NotAuthorized,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub struct SuccessResult {
+    pub published: bool,
+    pub subscribers: u32,
+    pub subscriptions: Vec<BanListSubscriptionSummary>,
+    pub audit_log: Vec<BanListAuditRecord>,
+}
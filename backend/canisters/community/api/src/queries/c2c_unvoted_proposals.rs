@@ -0,0 +1,16 @@
+use candid::CandidType;
+use serde::{Deserialize, Serialize};
+use types::{TimestampMillis, UnvotedProposal};
+
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub struct Args {
+    pub closing_before: TimestampMillis,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub enum Response {
+    Success(Vec<UnvotedProposal>),
+    // The user isn't a member of any proposals channels
+    NoProposals,
+    UserNotInCommunity,
+}
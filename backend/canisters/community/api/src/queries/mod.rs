@@ -1,14 +1,20 @@
+pub mod ban_lists;
+pub mod c2c_is_member;
 pub mod c2c_summary;
+pub mod c2c_unvoted_proposals;
 pub mod channel_summary;
 pub mod channel_summary_updates;
+pub mod chat_reports;
 pub mod deleted_message;
 pub mod events;
 pub mod events_by_index;
 pub mod events_window;
 pub mod explore_channels;
+pub mod forum_posts;
 pub mod invite_code;
 pub mod local_user_index;
 pub mod messages_by_message_index;
+pub mod proposal_vote_delegation;
 pub mod search_channel;
 pub mod selected_channel_initial;
 pub mod selected_channel_updates;
@@ -19,3 +25,5 @@ pub mod selected_updates_v2;
 pub mod summary;
 pub mod summary_updates;
 pub mod thread_previews;
+pub mod typing;
+pub mod upcoming_calendar_events;
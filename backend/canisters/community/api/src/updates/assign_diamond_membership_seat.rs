@@ -0,0 +1,28 @@
+use candid::CandidType;
+use serde::{Deserialize, Serialize};
+use types::{TimestampMillis, UserId};
+
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub struct Args {
+    pub user_id: UserId,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub enum Response {
+    Success(SuccessResult),
+    UserNotInCommunity,
+    NotAuthorized,
+    TargetUserNotInCommunity,
+    NoSeatsAvailable,
+    MembershipCannotBeExtended(TimestampMillis), // Returns the time at which the membership can next be extended
+    PaymentAlreadyInProgress,
+    UserSuspended,
+    CommunityFrozen,
+    InternalError(String),
+}
+
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub struct SuccessResult {
+    pub expires_at: TimestampMillis,
+    pub seats_remaining: u32,
+}
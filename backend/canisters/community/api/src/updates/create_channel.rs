@@ -1,8 +1,8 @@
 use candid::CandidType;
 use serde::{Deserialize, Serialize};
 use types::{
-    AccessGate, ChannelId, Document, FieldTooLongResult, FieldTooShortResult, GroupPermissions, GroupSubtype, Milliseconds,
-    Rules,
+    AccessGate, ChannelId, Document, FieldTooLongResult, FieldTooShortResult, ForumTagsValidationError, GroupPermissions,
+    GroupSubtype, Milliseconds, Rules,
 };
 
 #[derive(CandidType, Serialize, Deserialize, Debug)]
@@ -17,6 +17,9 @@ pub struct Args {
     pub permissions_v2: Option<GroupPermissions>,
     pub events_ttl: Option<Milliseconds>,
     pub gate: Option<AccessGate>,
+    // Setting this makes the channel a forum, with the tags being those that posts can be labelled with
+    #[serde(default)]
+    pub forum_tags: Option<Vec<String>>,
 }
 
 #[derive(CandidType, Serialize, Deserialize, Debug)]
@@ -31,6 +34,8 @@ pub enum Response {
     AvatarTooBig(FieldTooLongResult),
     MaxChannelsCreated(u32),
     NameTaken,
+    InvalidForumTags(ForumTagsValidationError),
+    UserGroupNotFound(u32),
     UserSuspended,
     NotAuthorized,
     CommunityFrozen,
@@ -1,6 +1,8 @@
 pub mod add_members_to_channel;
 pub mod add_reaction;
+pub mod assign_diamond_membership_seat;
 pub mod block_user;
+pub mod c2c_ban_list_updated;
 pub mod c2c_create_proposals_channel;
 pub mod c2c_delete_community;
 pub mod c2c_freeze_community;
@@ -11,16 +13,22 @@ pub mod c2c_join_channel;
 pub mod c2c_join_community;
 pub mod c2c_leave_community;
 pub mod c2c_set_user_suspended;
+pub mod c2c_subscribe_to_ban_list;
 pub mod c2c_tip_message;
+pub mod c2c_undelete_reported_message;
 pub mod c2c_unfreeze_community;
+pub mod c2c_unsubscribe_from_ban_list;
 pub mod c2c_update_proposals;
 pub mod change_channel_role;
 pub mod change_role;
 pub mod claim_prize;
 pub mod create_channel;
+pub mod create_channel_category;
+pub mod create_referral_campaign;
 pub mod create_user_group;
 pub mod decline_invitation;
 pub mod delete_channel;
+pub mod delete_channel_category;
 pub mod delete_messages;
 pub mod delete_user_groups;
 pub mod disable_invite_code;
@@ -29,22 +37,35 @@ pub mod enable_invite_code;
 pub mod follow_thread;
 pub mod import_group;
 pub mod leave_channel;
+pub mod opt_out_of_delegated_proposal_vote;
 pub mod pin_message;
+pub mod publish_ban_list;
 pub mod register_poll_vote;
 pub mod register_proposal_vote;
 pub mod register_proposal_vote_v2;
 pub mod remove_member;
 pub mod remove_member_from_channel;
 pub mod remove_reaction;
+pub mod reorder_channel_categories;
 pub mod report_message;
 pub mod reset_invite_code;
+pub mod resolve_report;
+pub mod rsvp_calendar_event;
 pub mod send_message;
 pub mod set_member_display_name;
+pub mod set_onboarding;
+pub mod set_proposal_vote_delegation;
+pub mod set_typing;
+pub mod submit_onboarding_answers;
+pub mod subscribe_to_ban_list;
 pub mod toggle_mute_notifications;
 pub mod unblock_user;
 pub mod undelete_messages;
 pub mod unfollow_thread;
 pub mod unpin_message;
+pub mod unsubscribe_from_ban_list;
 pub mod update_channel;
+pub mod update_channel_category;
 pub mod update_community;
+pub mod update_forum_post;
 pub mod update_user_group;
@@ -0,0 +1,22 @@
+use candid::CandidType;
+use serde::{Deserialize, Serialize};
+use types::{ChannelId, MessageIndex};
+
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub struct Args {
+    pub channel_id: ChannelId,
+    pub message_index: MessageIndex,
+    pub opt_out: bool,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub enum Response {
+    Success,
+    UserNotInCommunity,
+    ChannelNotFound,
+    UserNotInChannel,
+    NoDelegation,
+    ProposalMessageNotFound,
+    UserSuspended,
+    CommunityFrozen,
+}
@@ -8,6 +8,9 @@ pub struct Args {
     pub thread_root_message_index: Option<MessageIndex>,
     pub message_index: MessageIndex,
     pub poll_option: u32,
+    // For ranked choice polls, the user's full ranking of the options, starting with `poll_option`
+    #[serde(default)]
+    pub ranked_options: Option<Vec<u32>>,
     pub operation: VoteOperation,
 }
 
@@ -23,4 +26,7 @@ pub enum Response {
     PollNotFound,
     PollEnded,
     OptionIndexOutOfRange,
+    InvalidRanking,
+    NoVotingPower,
+    InternalError(String),
 }
@@ -1,8 +1,8 @@
 use candid::CandidType;
 use serde::{Deserialize, Serialize};
 use types::{
-    ChannelId, EventIndex, GroupReplyContext, InvalidPollReason, MessageContentInitial, MessageId, MessageIndex,
-    TimestampMillis, User, Version,
+    ChannelId, EventIndex, ForumPostDetails, GroupReplyContext, InvalidPollReason, MessageContentInitial, MessageId,
+    MessageIndex, TimestampMillis, User, Version,
 };
 
 #[derive(CandidType, Serialize, Deserialize, Debug)]
@@ -18,6 +18,9 @@ pub struct Args {
     pub forwarding: bool,
     pub community_rules_accepted: Option<Version>,
     pub channel_rules_accepted: Option<Version>,
+    // Required for top level messages in forum channels
+    #[serde(default)]
+    pub forum_post: Option<ForumPostDetails>,
 }
 
 #[derive(CandidType, Serialize, Deserialize, Debug)]
@@ -36,6 +39,7 @@ pub enum Response {
     CommunityFrozen,
     RulesNotAccepted,
     CommunityRulesNotAccepted,
+    ForumPostLocked,
 }
 
 #[derive(CandidType, Serialize, Deserialize, Debug)]
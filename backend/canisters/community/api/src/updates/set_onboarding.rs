@@ -0,0 +1,19 @@
+use candid::CandidType;
+use serde::{Deserialize, Serialize};
+use types::{ChannelId, CommunityOnboarding, InvalidOnboardingReason};
+
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub struct Args {
+    pub onboarding: Option<CommunityOnboarding>,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub enum Response {
+    Success,
+    InvalidOnboarding(InvalidOnboardingReason),
+    UserGroupNotFound(u32),
+    ChannelNotFound(ChannelId),
+    NotAuthorized,
+    CommunityFrozen,
+    UserSuspended,
+}
@@ -0,0 +1,25 @@
+use candid::CandidType;
+use serde::{Deserialize, Serialize};
+use types::{ChannelId, UserId};
+
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub struct Args {
+    pub channel_id: ChannelId,
+    // Set to `None` to stop following the delegate's votes
+    pub delegate: Option<UserId>,
+    // If set, only votes on proposals with these topics are followed
+    pub topics: Option<Vec<u64>>,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub enum Response {
+    Success,
+    UserNotInCommunity,
+    ChannelNotFound,
+    UserNotInChannel,
+    DelegateNotInChannel,
+    CannotDelegateToSelf,
+    NotProposalsChannel,
+    UserSuspended,
+    CommunityFrozen,
+}
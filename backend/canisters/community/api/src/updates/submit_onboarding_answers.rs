@@ -0,0 +1,19 @@
+use candid::CandidType;
+use serde::{Deserialize, Serialize};
+use types::OnboardingAnswer;
+
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub struct Args {
+    pub answers: Vec<OnboardingAnswer>,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub enum Response {
+    Success,
+    NoOnboarding,
+    InvalidAnswers,
+    AlreadyAnswered,
+    UserNotInCommunity,
+    UserSuspended,
+    CommunityFrozen,
+}
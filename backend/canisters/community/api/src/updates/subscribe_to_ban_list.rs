@@ -0,0 +1,23 @@
+use candid::CandidType;
+use serde::{Deserialize, Serialize};
+use types::CommunityId;
+
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub struct Args {
+    pub community_id: CommunityId,
+    pub remove_existing_members: bool,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub enum Response {
+    Success,
+    AlreadySubscribed,
+    CannotSubscribeToSelf,
+    BanListNotPublished,
+    TooManySubscriptions(u32),
+    TooManySubscribers(u32),
+    NotAuthorized,
+    CommunityFrozen,
+    UserSuspended,
+    InternalError(String),
+}
@@ -1,8 +1,8 @@
 use candid::CandidType;
 use serde::{Deserialize, Serialize};
 use types::{
-    AccessGate, ChannelId, Document, FieldTooLongResult, FieldTooShortResult, Milliseconds, OptionUpdate,
-    OptionalGroupPermissions, UpdatedRules, Version,
+    AccessGate, ChannelId, ChannelUserGroupPermissions, Document, FieldTooLongResult, FieldTooShortResult,
+    ForumTagsValidationError, Milliseconds, OptionUpdate, OptionalGroupPermissions, UpdatedRules, Version,
 };
 
 #[derive(CandidType, Serialize, Deserialize, Debug)]
@@ -16,6 +16,12 @@ pub struct Args {
     pub events_ttl: OptionUpdate<Milliseconds>,
     pub gate: OptionUpdate<AccessGate>,
     pub public: Option<bool>,
+    #[serde(default)]
+    pub forum_tags: Option<Vec<String>>,
+    #[serde(default)]
+    pub user_group_permissions: Option<ChannelUserGroupPermissions>,
+    #[serde(default)]
+    pub audience_user_groups: Option<Vec<u32>>,
 }
 
 #[derive(CandidType, Serialize, Deserialize, Debug)]
@@ -33,6 +39,10 @@ pub enum Response {
     NameTaken,
     RulesTooLong(FieldTooLongResult),
     RulesTooShort(FieldTooShortResult),
+    ChannelNotForum,
+    InvalidForumTags(ForumTagsValidationError),
+    UserGroupNotFound(u32),
+    AudienceRequiresPrivateChannel,
     UserSuspended,
     CommunityFrozen,
 }
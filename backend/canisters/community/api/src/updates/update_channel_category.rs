@@ -0,0 +1,25 @@
+use candid::CandidType;
+use serde::{Deserialize, Serialize};
+use types::{ChannelId, FieldTooLongResult, FieldTooShortResult};
+
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub struct Args {
+    pub category_id: u32,
+    pub name: Option<String>,
+    pub collapsed_by_default: Option<bool>,
+    // If set, replaces the category's channels, in display order
+    pub channels: Option<Vec<ChannelId>>,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub enum Response {
+    Success,
+    CategoryNotFound,
+    ChannelNotFound(ChannelId),
+    NameTooShort(FieldTooShortResult),
+    NameTooLong(FieldTooLongResult),
+    NameTaken,
+    NotAuthorized,
+    CommunityFrozen,
+    UserSuspended,
+}
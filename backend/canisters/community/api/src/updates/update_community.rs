@@ -36,6 +36,7 @@ pub enum Response {
     UserSuspended,
     CommunityFrozen,
     InvalidLanguage,
+    InvalidGate,
 }
 
 #[derive(CandidType, Serialize, Deserialize, Debug)]
@@ -2,8 +2,11 @@ use canister_client::generate_c2c_call;
 use community_canister::*;
 
 // Queries
+generate_c2c_call!(c2c_is_member);
+generate_c2c_call!(c2c_unvoted_proposals);
 
 // Updates
+generate_c2c_call!(c2c_ban_list_updated);
 generate_c2c_call!(c2c_create_proposals_channel);
 generate_c2c_call!(c2c_delete_community);
 generate_c2c_call!(c2c_freeze_community);
@@ -14,8 +17,10 @@ generate_c2c_call!(c2c_join_channel);
 generate_c2c_call!(c2c_join_community);
 generate_c2c_call!(c2c_leave_community);
 generate_c2c_call!(c2c_set_user_suspended);
+generate_c2c_call!(c2c_subscribe_to_ban_list);
 generate_c2c_call!(c2c_tip_message);
 generate_c2c_call!(c2c_unfreeze_community);
+generate_c2c_call!(c2c_unsubscribe_from_ban_list);
 generate_c2c_call!(c2c_update_proposals);
 generate_c2c_call!(change_channel_role);
 generate_c2c_call!(delete_channel);
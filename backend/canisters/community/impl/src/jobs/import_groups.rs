@@ -13,7 +13,7 @@ use std::cell::Cell;
 use std::collections::HashMap;
 use std::time::Duration;
 use tracing::{info, trace};
-use types::{ChannelId, ChannelLatestMessageIndex, ChatId, Empty, UserId};
+use types::{ChannelId, ChannelLatestMessageIndex, ChatId, Empty, Timestamped, UserId};
 use utils::consts::OPENCHAT_BOT_USER_ID;
 
 const PAGE_SIZE: u32 = 19 * 102 * 1024; // Roughly 1.9MB (1.9 * 1024 * 1024)
@@ -116,6 +116,9 @@ pub(crate) fn finalize_group_import(group_id: ChatId) {
                 id: channel_id,
                 chat,
                 date_imported: None, // This is only set once everything is complete
+                forum: None,
+                user_group_permissions: Timestamped::default(),
+                audience_user_groups: Timestamped::default(),
             });
 
             state.data.timer_jobs.enqueue_job(
@@ -1,15 +1,21 @@
 use crate::memory::{get_instruction_counts_data_memory, get_instruction_counts_index_memory};
+use crate::model::ban_lists::BanLists;
+use crate::model::calendar_events::CalendarEvents;
+use crate::model::channel_categories::ChannelCategories;
 use crate::model::channels::Channels;
+use crate::model::events::CommunityEventInternal;
 use crate::model::groups_being_imported::{GroupBeingImportedSummary, GroupsBeingImported};
 use crate::model::members::CommunityMembers;
-use crate::timer_job_types::{RemoveExpiredEventsJob, TimerJob};
+use crate::timer_job_types::{MakeTransferJob, RemoveExpiredEventsJob, TimerJob};
+use crate::updates::c2c_join_channel::join_channel_unchecked;
+use crate::updates::remove_member::remove_membership_from_user_canister;
 use activity_notification_state::ActivityNotificationState;
 use candid::Principal;
 use canister_state_macros::canister_state;
 use canister_timer_jobs::TimerJobs;
 use chat_events::ChatMetricsInternal;
 use fire_and_forget_handler::FireAndForgetHandler;
-use group_chat_core::AccessRulesInternal;
+use group_chat_core::{AccessRulesInternal, LeaveResult};
 use group_community_common::{PaymentReceipts, PaymentRecipient, PendingPayment, PendingPaymentReason, PendingPaymentsQueue};
 use instruction_counts_log::{InstructionCountEntry, InstructionCountFunctionId, InstructionCountsLog};
 use model::{events::CommunityEvents, invited_users::InvitedUsers, members::CommunityMemberInternal};
@@ -18,12 +24,15 @@ use notifications_canister::c2c_push_notification;
 use serde::{Deserialize, Serialize};
 use serde_bytes::ByteBuf;
 use std::cell::RefCell;
+use std::collections::HashSet;
 use std::ops::Deref;
 use types::{
-    AccessGate, BuildVersion, CanisterId, ChannelId, ChatMetrics, CommunityCanisterCommunitySummary, CommunityMembership,
-    CommunityPermissions, CommunityRole, Cryptocurrency, Cycles, Document, Empty, FrozenGroupInfo, Milliseconds, Notification,
+    AccessGate, BanListAction, BuildVersion, CanisterId, ChannelCategory, ChannelId, ChatMetrics,
+    CommunityCanisterCommunitySummary, CommunityId, CommunityMembership, CommunityOnboarding, CommunityPermissions,
+    CommunityRole, Cryptocurrency, Cycles, Document, Empty, FrozenGroupInfo, MembersRemoved, Milliseconds, Notification,
     PaymentGate, Rules, TimestampMillis, Timestamped, UserId,
 };
+use utils::consts::{MEMO_POLL_VOTE_REFUND, OPENCHAT_BOT_USER_ID};
 use utils::env::Environment;
 use utils::regular_jobs::RegularJobs;
 
@@ -90,6 +99,21 @@ impl RuntimeState {
         }
     }
 
+    // Records the joining fee in the user's wallet transaction history. The user canister looks up
+    // the transfer on the ledger, so only the block index is passed.
+    pub fn notify_joining_fee_paid(&mut self, user_id: UserId, gate: &PaymentGate, block_index: u64) {
+        let args = user_canister::c2c_notify_joining_fee_paid::Args {
+            ledger: gate.ledger_canister_id,
+            block_index,
+            fee: gate.fee,
+        };
+        self.data.fire_and_forget_handler.send(
+            user_id.into(),
+            "c2c_notify_joining_fee_paid_msgpack".to_string(),
+            serialize_then_unwrap(args),
+        );
+    }
+
     pub fn queue_access_gate_payments(&mut self, gate: PaymentGate) {
         // The amount available is the gate amount less the approval fee and the transfer_from fee
         let amount_available = gate.amount - 2 * gate.fee;
@@ -173,6 +197,8 @@ impl RuntimeState {
             member.map(|m| m.last_updated()).unwrap_or_default(),
             self.data.events.latest_event_timestamp(),
             self.data.members.user_groups_last_updated(),
+            self.data.channel_categories.last_updated(),
+            self.data.onboarding.timestamp,
         ]
         .into_iter()
         .chain(channels.iter().map(|c| c.last_updated))
@@ -196,15 +222,24 @@ impl RuntimeState {
             channels,
             membership,
             user_groups: data.members.iter_user_groups().map(|u| u.into()).collect(),
+            channel_categories: data.channel_categories_layout(member),
+            onboarding: data.onboarding.value.clone(),
             metrics: data.cached_chat_metrics.value.clone(),
         }
     }
 
     pub fn run_event_expiry_job(&mut self) {
         let now = self.env.now();
+        let now_nanos = self.env.now_nanos();
         let mut next_event_expiry = None;
         for channel in self.data.channels.iter_mut() {
-            channel.chat.remove_expired_events(now);
+            for (user_id, lock) in channel.chat.remove_expired_events(now) {
+                if let Some(pending_transaction) = lock.refund(user_id, &MEMO_POLL_VOTE_REFUND, now_nanos) {
+                    self.data
+                        .timer_jobs
+                        .enqueue_job(TimerJob::MakeTransfer(MakeTransferJob { pending_transaction }), now, now);
+                }
+            }
             if let Some(expiry) = channel.chat.events.next_event_expiry() {
                 if next_event_expiry.map_or(true, |current| expiry < current) {
                     next_event_expiry = Some(expiry);
@@ -294,6 +329,14 @@ struct Data {
     pub pending_payments_queue: PendingPaymentsQueue,
     #[serde(default)]
     pub total_payment_receipts: PaymentReceipts,
+    #[serde(default)]
+    calendar_events: CalendarEvents,
+    #[serde(default)]
+    channel_categories: ChannelCategories,
+    #[serde(default)]
+    onboarding: Timestamped<Option<CommunityOnboarding>>,
+    #[serde(default)]
+    ban_lists: BanLists,
 }
 
 impl Data {
@@ -361,6 +404,10 @@ impl Data {
             rng_seed: [0; 32],
             pending_payments_queue: PendingPaymentsQueue::default(),
             total_payment_receipts: PaymentReceipts::default(),
+            calendar_events: CalendarEvents::default(),
+            channel_categories: ChannelCategories::default(),
+            onboarding: Timestamped::default(),
+            ban_lists: BanLists::default(),
         }
     }
 
@@ -414,6 +461,121 @@ impl Data {
         );
     }
 
+    // Adds the members of the channels' audience user groups to those channels, and removes any
+    // channel members who are no longer in one of the groups (other than moderators and above).
+    // Deleted user groups are dropped from the audience, and if that leaves a channel with no
+    // audience groups its members are left as they are.
+    pub fn sync_channel_audiences(&mut self, user_group_ids: &[u32], now: TimestampMillis) {
+        for channel in self.channels.iter_mut() {
+            if !channel.audience_user_groups.iter().any(|id| user_group_ids.contains(id)) {
+                continue;
+            }
+
+            channel
+                .audience_user_groups
+                .retain(|id| self.members.get_user_group(*id).is_some());
+
+            if channel.audience_user_groups.is_empty() {
+                continue;
+            }
+
+            let audience: HashSet<UserId> = channel
+                .audience_user_groups
+                .iter()
+                .filter_map(|id| self.members.get_user_group(*id))
+                .flat_map(|g| g.members.iter().copied())
+                .collect();
+
+            for user_id in audience.iter() {
+                if channel.chat.members.get(user_id).is_none() {
+                    if let Some(member) = self.members.get_by_user_id_mut(user_id) {
+                        join_channel_unchecked(channel, member, self.is_public, now);
+                    }
+                }
+            }
+
+            let to_remove: Vec<_> = channel
+                .chat
+                .members
+                .iter()
+                .filter(|m| !audience.contains(&m.user_id))
+                .filter(|m| !(m.role.is_owner() || m.role.is_admin() || m.role.is_moderator()))
+                .map(|m| m.user_id)
+                .collect();
+
+            for user_id in to_remove {
+                if matches!(channel.chat.leave(user_id, now), LeaveResult::Success(_)) {
+                    if let Some(member) = self.members.get_by_user_id_mut(&user_id) {
+                        member.leave(channel.id, now);
+                    }
+                }
+            }
+        }
+    }
+
+    // Only channels which are public or which the user is a member of are included in the layout
+    pub fn channel_categories_layout(&self, member: Option<&CommunityMemberInternal>) -> Vec<ChannelCategory> {
+        self.channel_categories.layout(|channel_id| {
+            member.map_or(false, |m| m.channels.contains(channel_id))
+                || self.channels.get(channel_id).map_or(false, |c| c.chat.is_public.value)
+        })
+    }
+
+    // Owners and admins are trusted locally so are never removed due to another community's ban list
+    pub fn remove_members_banned_by_list(&mut self, ban_list: CommunityId, user_ids: Vec<UserId>, now: TimestampMillis) {
+        let mut removed = Vec::new();
+
+        for user_id in user_ids {
+            if self
+                .members
+                .get_by_user_id(&user_id)
+                .map_or(false, |m| !m.role.is_owner() && !m.role.is_admin())
+            {
+                self.members.remove(&user_id, now);
+                self.channels.leave_all_channels(user_id, now);
+                self.ban_lists.record(user_id, ban_list, BanListAction::MemberRemoved, now);
+
+                remove_membership_from_user_canister(
+                    user_id,
+                    OPENCHAT_BOT_USER_ID,
+                    true,
+                    self.name.clone(),
+                    self.is_public,
+                    &mut self.fire_and_forget_handler,
+                );
+                removed.push(user_id);
+            }
+        }
+
+        if !removed.is_empty() {
+            let event = MembersRemoved {
+                user_ids: removed,
+                removed_by: OPENCHAT_BOT_USER_ID,
+            };
+            self.events
+                .push_event(CommunityEventInternal::MembersRemoved(Box::new(event)), now);
+        }
+    }
+
+    pub fn notify_ban_list_subscribers(&mut self, blocked: Vec<UserId>, unblocked: Vec<UserId>) {
+        if !self.ban_lists.is_published() {
+            return;
+        }
+
+        let args = community_canister::c2c_ban_list_updated::Args {
+            blocked,
+            unblocked,
+            unpublished: false,
+        };
+        for community_id in self.ban_lists.subscribers() {
+            self.fire_and_forget_handler.send(
+                community_id.into(),
+                "c2c_ban_list_updated_msgpack".to_string(),
+                serialize_then_unwrap(&args),
+            );
+        }
+    }
+
     pub fn details_last_updated(&self) -> TimestampMillis {
         [
             self.invited_users.last_updated(),
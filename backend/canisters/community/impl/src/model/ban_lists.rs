@@ -0,0 +1,143 @@
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet, VecDeque};
+use types::{BanListAction, BanListAuditRecord, BanListSubscriptionSummary, CommunityId, TimestampMillis, UserId};
+
+const MAX_AUDIT_RECORDS: usize = 1000;
+
+// A community can publish its list of blocked users so that other communities can subscribe to
+// it. Subscribers are sent each change to the list and hold their own copy of each list.
+#[derive(Serialize, Deserialize, Default)]
+pub struct BanLists {
+    published: bool,
+    subscribers: HashSet<CommunityId>,
+    subscriptions: HashMap<CommunityId, BanListSubscription>,
+    audit_log: VecDeque<BanListAuditRecord>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct BanListSubscription {
+    pub remove_existing_members: bool,
+    pub subscribed: TimestampMillis,
+    pub blocked_users: HashSet<UserId>,
+}
+
+impl BanLists {
+    pub fn is_published(&self) -> bool {
+        self.published
+    }
+
+    // Returns the subscribers which have been dropped as a result of unpublishing the list
+    pub fn set_published(&mut self, published: bool) -> Vec<CommunityId> {
+        self.published = published;
+
+        if published {
+            Vec::new()
+        } else {
+            self.subscribers.drain().collect()
+        }
+    }
+
+    pub fn add_subscriber(&mut self, community_id: CommunityId) -> bool {
+        self.published && self.subscribers.insert(community_id)
+    }
+
+    pub fn remove_subscriber(&mut self, community_id: &CommunityId) -> bool {
+        self.subscribers.remove(community_id)
+    }
+
+    pub fn is_subscriber(&self, community_id: &CommunityId) -> bool {
+        self.subscribers.contains(community_id)
+    }
+
+    pub fn subscriber_count(&self) -> usize {
+        self.subscribers.len()
+    }
+
+    pub fn subscribers(&self) -> Vec<CommunityId> {
+        self.subscribers.iter().copied().collect()
+    }
+
+    pub fn subscribe(
+        &mut self,
+        community_id: CommunityId,
+        remove_existing_members: bool,
+        blocked_users: Vec<UserId>,
+        now: TimestampMillis,
+    ) {
+        self.subscriptions.insert(
+            community_id,
+            BanListSubscription {
+                remove_existing_members,
+                subscribed: now,
+                blocked_users: blocked_users.into_iter().collect(),
+            },
+        );
+    }
+
+    pub fn unsubscribe(&mut self, community_id: &CommunityId) -> bool {
+        self.subscriptions.remove(community_id).is_some()
+    }
+
+    pub fn is_subscribed(&self, community_id: &CommunityId) -> bool {
+        self.subscriptions.contains_key(community_id)
+    }
+
+    pub fn subscription_count(&self) -> usize {
+        self.subscriptions.len()
+    }
+
+    // Returns the subscription, updated to reflect the changes to the list, or None if this
+    // community is not subscribed to the list
+    pub fn update_subscription(
+        &mut self,
+        community_id: &CommunityId,
+        blocked: &[UserId],
+        unblocked: &[UserId],
+    ) -> Option<&BanListSubscription> {
+        let subscription = self.subscriptions.get_mut(community_id)?;
+
+        for user_id in unblocked {
+            subscription.blocked_users.remove(user_id);
+        }
+        subscription.blocked_users.extend(blocked.iter().copied());
+
+        Some(subscription)
+    }
+
+    // Returns the first subscribed list which blocks the user
+    pub fn banning_list(&self, user_id: &UserId) -> Option<CommunityId> {
+        self.subscriptions
+            .iter()
+            .find(|(_, s)| s.blocked_users.contains(user_id))
+            .map(|(id, _)| *id)
+    }
+
+    pub fn record(&mut self, user_id: UserId, ban_list: CommunityId, action: BanListAction, now: TimestampMillis) {
+        if self.audit_log.len() >= MAX_AUDIT_RECORDS {
+            self.audit_log.pop_front();
+        }
+        self.audit_log.push_back(BanListAuditRecord {
+            user_id,
+            ban_list,
+            action,
+            timestamp: now,
+        });
+    }
+
+    pub fn subscriptions(&self) -> Vec<BanListSubscriptionSummary> {
+        self.subscriptions
+            .iter()
+            .map(|(id, s)| BanListSubscriptionSummary {
+                community_id: *id,
+                remove_existing_members: s.remove_existing_members,
+                subscribed: s.subscribed,
+                blocked_users: s.blocked_users.len() as u32,
+            })
+            .collect()
+    }
+
+    // Returns the most recent records first
+    pub fn audit_log(&self, max_results: usize) -> Vec<BanListAuditRecord> {
+        self.audit_log.iter().rev().take(max_results).cloned().collect()
+    }
+}
@@ -0,0 +1,81 @@
+use serde::{Deserialize, Serialize};
+use types::{ChannelId, MessageIndex, TimestampMillis};
+
+// An index of the calendar events sent to the community's channels, so that upcoming events can
+// be listed without scanning each channel's messages
+#[derive(Serialize, Deserialize, Default)]
+pub struct CalendarEvents {
+    events: Vec<CalendarEventEntry>,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct CalendarEventEntry {
+    pub channel_id: ChannelId,
+    pub thread_root_message_index: Option<MessageIndex>,
+    pub message_index: MessageIndex,
+    pub start: TimestampMillis,
+    pub end: TimestampMillis,
+}
+
+impl CalendarEvents {
+    pub fn add(&mut self, entry: CalendarEventEntry, now: TimestampMillis) {
+        self.events.retain(|e| e.end > now);
+        self.events.push(entry);
+    }
+
+    pub fn remove_channel(&mut self, channel_id: ChannelId) {
+        self.events.retain(|e| e.channel_id != channel_id);
+    }
+
+    pub fn upcoming(&self, now: TimestampMillis) -> impl Iterator<Item = &CalendarEventEntry> {
+        self.events.iter().filter(move |e| e.end > now)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn upcoming_excludes_finished_events() {
+        let mut events = CalendarEvents::default();
+        events.add(entry(1, 1, 100, 200), 0);
+        events.add(entry(1, 2, 300, 400), 0);
+
+        let upcoming: Vec<_> = events.upcoming(250).map(|e| e.message_index).collect();
+
+        assert_eq!(upcoming, vec![2.into()]);
+    }
+
+    #[test]
+    fn finished_events_pruned_when_adding() {
+        let mut events = CalendarEvents::default();
+        events.add(entry(1, 1, 100, 200), 0);
+        events.add(entry(1, 2, 300, 400), 250);
+
+        assert_eq!(events.events.len(), 1);
+        assert_eq!(events.events[0].message_index, 2.into());
+    }
+
+    #[test]
+    fn remove_channel_removes_its_events() {
+        let mut events = CalendarEvents::default();
+        events.add(entry(1, 1, 100, 200), 0);
+        events.add(entry(2, 2, 100, 200), 0);
+
+        events.remove_channel(1);
+
+        let upcoming: Vec<_> = events.upcoming(0).map(|e| e.channel_id).collect();
+        assert_eq!(upcoming, vec![2]);
+    }
+
+    fn entry(channel_id: ChannelId, message_index: u32, start: TimestampMillis, end: TimestampMillis) -> CalendarEventEntry {
+        CalendarEventEntry {
+            channel_id,
+            thread_root_message_index: None,
+            message_index: message_index.into(),
+            start,
+            end,
+        }
+    }
+}
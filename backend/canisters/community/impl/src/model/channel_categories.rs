@@ -0,0 +1,261 @@
+use rand::{Rng, RngCore};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use types::{ChannelCategory, ChannelId, TimestampMillis};
+
+// The categories are stored in display order, each holding its channels in display order
+#[derive(Serialize, Deserialize, Default)]
+pub struct ChannelCategories {
+    categories: Vec<ChannelCategoryInternal>,
+    last_updated: TimestampMillis,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct ChannelCategoryInternal {
+    pub id: u32,
+    pub name: String,
+    pub collapsed_by_default: bool,
+    pub channels: Vec<ChannelId>,
+}
+
+impl ChannelCategories {
+    pub fn create<R: RngCore>(
+        &mut self,
+        name: String,
+        collapsed_by_default: bool,
+        rng: &mut R,
+        now: TimestampMillis,
+    ) -> Option<u32> {
+        if self.is_name_taken(&name, None) {
+            None
+        } else {
+            let id = self.generate_id(rng);
+
+            self.categories.push(ChannelCategoryInternal {
+                id,
+                name,
+                collapsed_by_default,
+                channels: Vec::new(),
+            });
+            self.last_updated = now;
+
+            Some(id)
+        }
+    }
+
+    // Channels assigned to a category are moved out of whichever category they were in previously
+    pub fn update(
+        &mut self,
+        id: u32,
+        name: Option<String>,
+        collapsed_by_default: Option<bool>,
+        channels: Option<Vec<ChannelId>>,
+        now: TimestampMillis,
+    ) -> UpdateChannelCategoryResult {
+        if !self.categories.iter().any(|c| c.id == id) {
+            return UpdateChannelCategoryResult::NotFound;
+        }
+        if name.as_ref().map_or(false, |n| self.is_name_taken(n, Some(id))) {
+            return UpdateChannelCategoryResult::NameTaken;
+        }
+
+        if let Some(channels) = channels {
+            let mut unique = HashSet::new();
+            let channels: Vec<_> = channels.into_iter().filter(|c| unique.insert(*c)).collect();
+
+            for category in self.categories.iter_mut().filter(|c| c.id != id) {
+                category.channels.retain(|c| !unique.contains(c));
+            }
+            if let Some(category) = self.categories.iter_mut().find(|c| c.id == id) {
+                category.channels = channels;
+            }
+        }
+
+        if let Some(category) = self.categories.iter_mut().find(|c| c.id == id) {
+            if let Some(name) = name {
+                category.name = name;
+            }
+            if let Some(collapsed_by_default) = collapsed_by_default {
+                category.collapsed_by_default = collapsed_by_default;
+            }
+        }
+        self.last_updated = now;
+        UpdateChannelCategoryResult::Success
+    }
+
+    // The channels in a deleted category become uncategorised
+    pub fn delete(&mut self, id: u32, now: TimestampMillis) -> bool {
+        let original_len = self.categories.len();
+        self.categories.retain(|c| c.id != id);
+
+        if self.categories.len() != original_len {
+            self.last_updated = now;
+            true
+        } else {
+            false
+        }
+    }
+
+    // The new order must contain each existing category exactly once
+    pub fn reorder(&mut self, category_ids: &[u32], now: TimestampMillis) -> bool {
+        let existing: HashSet<_> = self.categories.iter().map(|c| c.id).collect();
+        let requested: HashSet<_> = category_ids.iter().copied().collect();
+
+        if category_ids.len() != self.categories.len() || requested != existing {
+            return false;
+        }
+
+        self.categories
+            .sort_by_key(|c| category_ids.iter().position(|id| *id == c.id).unwrap_or_default());
+        self.last_updated = now;
+        true
+    }
+
+    pub fn remove_channel(&mut self, channel_id: &ChannelId, now: TimestampMillis) {
+        for category in self.categories.iter_mut() {
+            let original_len = category.channels.len();
+            category.channels.retain(|c| c != channel_id);
+            if category.channels.len() != original_len {
+                self.last_updated = now;
+            }
+        }
+    }
+
+    pub fn layout<F: Fn(&ChannelId) -> bool>(&self, is_channel_visible: F) -> Vec<ChannelCategory> {
+        self.categories
+            .iter()
+            .map(|c| ChannelCategory {
+                category_id: c.id,
+                name: c.name.clone(),
+                collapsed_by_default: c.collapsed_by_default,
+                channels: c.channels.iter().filter(|id| is_channel_visible(id)).copied().collect(),
+            })
+            .collect()
+    }
+
+    pub fn count(&self) -> usize {
+        self.categories.len()
+    }
+
+    pub fn last_updated(&self) -> TimestampMillis {
+        self.last_updated
+    }
+
+    fn is_name_taken(&self, name: &str, excluding: Option<u32>) -> bool {
+        let name_upper = name.to_uppercase();
+        self.categories
+            .iter()
+            .any(|c| Some(c.id) != excluding && c.name.to_uppercase() == name_upper)
+    }
+
+    fn generate_id<R: RngCore>(&self, rng: &mut R) -> u32 {
+        let ids: HashSet<_> = self.categories.iter().map(|c| c.id).collect();
+
+        loop {
+            let id: u32 = rng.gen();
+            if !ids.contains(&id) {
+                return id;
+            }
+        }
+    }
+}
+
+pub enum UpdateChannelCategoryResult {
+    Success,
+    NotFound,
+    NameTaken,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn names_must_be_unique_ignoring_case() {
+        let mut categories = ChannelCategories::default();
+        let mut rng = StdRng::seed_from_u64(0);
+
+        assert!(categories.create("General".to_string(), false, &mut rng, 1).is_some());
+        assert!(categories.create("GENERAL".to_string(), false, &mut rng, 2).is_none());
+        assert_eq!(categories.count(), 1);
+        assert_eq!(categories.last_updated(), 1);
+    }
+
+    #[test]
+    fn rename_to_taken_name_rejected() {
+        let (mut categories, a, b) = setup();
+
+        assert!(matches!(
+            categories.update(b, Some("a".to_string()), None, None, 10),
+            UpdateChannelCategoryResult::NameTaken
+        ));
+        // Renaming a category to its own name (with different casing) is allowed
+        assert!(matches!(
+            categories.update(a, Some("a".to_string()), None, None, 10),
+            UpdateChannelCategoryResult::Success
+        ));
+        assert!(matches!(
+            categories.update(u32::MAX, None, None, None, 10),
+            UpdateChannelCategoryResult::NotFound
+        ));
+    }
+
+    #[test]
+    fn assigning_channels_moves_them_between_categories() {
+        let (mut categories, a, b) = setup();
+        categories.update(a, None, None, Some(vec![1, 2, 3]), 10);
+
+        categories.update(b, None, None, Some(vec![2, 4, 2]), 20);
+
+        let layout = categories.layout(|_| true);
+        assert_eq!(layout[0].channels, vec![1, 3]);
+        assert_eq!(layout[1].channels, vec![2, 4]);
+        assert_eq!(categories.last_updated(), 20);
+    }
+
+    #[test]
+    fn reorder_requires_each_category_exactly_once() {
+        let (mut categories, a, b) = setup();
+
+        assert!(!categories.reorder(&[a], 10));
+        assert!(!categories.reorder(&[a, a], 10));
+        assert!(categories.reorder(&[b, a], 10));
+
+        let ids: Vec<_> = categories.layout(|_| true).iter().map(|c| c.category_id).collect();
+        assert_eq!(ids, vec![b, a]);
+    }
+
+    #[test]
+    fn deleted_and_hidden_channels_excluded_from_layout() {
+        let (mut categories, a, _) = setup();
+        categories.update(a, None, None, Some(vec![1, 2, 3]), 10);
+
+        categories.remove_channel(&2, 20);
+
+        let layout = categories.layout(|c| *c != 3);
+        assert_eq!(layout[0].channels, vec![1]);
+        assert_eq!(categories.last_updated(), 20);
+    }
+
+    #[test]
+    fn delete_category() {
+        let (mut categories, a, b) = setup();
+
+        assert!(categories.delete(a, 10));
+        assert!(!categories.delete(a, 20));
+
+        let ids: Vec<_> = categories.layout(|_| true).iter().map(|c| c.category_id).collect();
+        assert_eq!(ids, vec![b]);
+        assert_eq!(categories.last_updated(), 10);
+    }
+
+    fn setup() -> (ChannelCategories, u32, u32) {
+        let mut categories = ChannelCategories::default();
+        let mut rng = StdRng::seed_from_u64(0);
+        let a = categories.create("A".to_string(), false, &mut rng, 1).unwrap();
+        let b = categories.create("B".to_string(), true, &mut rng, 2).unwrap();
+        (categories, a, b)
+    }
+}
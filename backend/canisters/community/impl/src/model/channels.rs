@@ -2,15 +2,16 @@ use chat_events::Reader;
 use group_chat_core::{CanLeaveResult, GroupChatCore, GroupMemberInternal, LeaveResult};
 use search::*;
 use serde::{Deserialize, Serialize};
-use std::cmp::{max, Reverse};
+use std::cmp::Reverse;
 use std::collections::hash_map::Entry::Vacant;
 use std::collections::HashMap;
 use types::{
-    ChannelId, ChannelMatch, CommunityCanisterChannelSummary, CommunityCanisterChannelSummaryUpdates, GroupMembership,
-    GroupMembershipUpdates, GroupPermissionRole, GroupPermissions, Rules, TimestampMillis, Timestamped, UserId,
-    MAX_THREADS_IN_SUMMARY,
+    ChannelId, ChannelMatch, ChannelUserGroupPermissions, CommunityCanisterChannelSummary,
+    CommunityCanisterChannelSummaryUpdates, GroupMembership, GroupMembershipUpdates, GroupPermissionRole, GroupPermissions,
+    Rules, TimestampMillis, Timestamped, UserId, MAX_THREADS_IN_SUMMARY,
 };
 
+use super::forum::Forum;
 use super::members::CommunityMembers;
 
 #[derive(Serialize, Deserialize, Default)]
@@ -23,6 +24,12 @@ pub struct Channel {
     pub id: ChannelId,
     pub chat: GroupChatCore,
     pub date_imported: Option<TimestampMillis>,
+    #[serde(default)]
+    pub forum: Option<Forum>,
+    #[serde(default)]
+    pub user_group_permissions: Timestamped<ChannelUserGroupPermissions>,
+    #[serde(default)]
+    pub audience_user_groups: Timestamped<Vec<u32>>,
 }
 
 impl Channels {
@@ -190,6 +197,9 @@ impl Channel {
                 now,
             ),
             date_imported: None,
+            forum: None,
+            user_group_permissions: Timestamped::default(),
+            audience_user_groups: Timestamped::default(),
         }
     }
 
@@ -269,12 +279,40 @@ impl Channel {
             events_ttl: events_ttl.value,
             events_ttl_last_updated: events_ttl.timestamp,
             gate: chat.gate.value.clone(),
+            forum_tags: self.forum.as_ref().map(|f| f.tags().value.clone()),
+            user_group_permissions: self.user_group_permissions.value.clone(),
+            audience_user_groups: self.audience_user_groups.value.clone(),
             membership,
         })
     }
 
     pub fn last_updated(&self, user_id: Option<UserId>) -> TimestampMillis {
-        max(self.chat.last_updated(user_id), self.date_imported.unwrap_or_default())
+        [
+            self.chat.last_updated(user_id),
+            self.date_imported.unwrap_or_default(),
+            self.forum.as_ref().map_or(0, |f| f.tags().timestamp),
+            self.user_group_permissions.timestamp,
+            self.audience_user_groups.timestamp,
+        ]
+        .into_iter()
+        .max()
+        .unwrap()
+    }
+
+    // Restrictions based on user groups don't apply to the channel's moderators and above
+    pub fn is_permitted_by_user_groups(
+        &self,
+        user_group_ids: &[u32],
+        user_id: UserId,
+        community_members: &CommunityMembers,
+    ) -> bool {
+        user_group_ids.is_empty()
+            || self
+                .chat
+                .members
+                .get(&user_id)
+                .map_or(false, |m| m.role.is_owner() || m.role.is_admin() || m.role.is_moderator())
+            || community_members.is_in_any_user_group(&user_id, user_group_ids)
     }
 
     pub fn summary_updates(
@@ -350,6 +388,9 @@ impl Channel {
             events_ttl: updates.events_ttl,
             events_ttl_last_updated: updates.events_ttl_last_updated,
             gate: updates.gate,
+            forum_tags: self.forum.as_ref().and_then(|f| f.tags().if_set_after(since)).cloned(),
+            user_group_permissions: self.user_group_permissions.if_set_after(since).cloned(),
+            audience_user_groups: self.audience_user_groups.if_set_after(since).cloned(),
             membership,
         })
     }
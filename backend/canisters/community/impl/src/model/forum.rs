@@ -0,0 +1,139 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use types::{
+    ForumPostDetails, MessageIndex, TimestampMillis, Timestamped, MAX_FORUM_POST_TITLE_LENGTH, MAX_TAGS_PER_FORUM_POST,
+};
+
+// Forum channels treat each top level message as a post, with its thread holding the replies
+#[derive(Serialize, Deserialize)]
+pub struct Forum {
+    tags: Timestamped<Vec<String>>,
+    posts: BTreeMap<MessageIndex, ForumPostInternal>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct ForumPostInternal {
+    pub title: String,
+    pub tags: Vec<String>,
+    pub resolved: bool,
+    pub locked: bool,
+}
+
+impl Forum {
+    pub fn new(tags: Vec<String>, now: TimestampMillis) -> Forum {
+        Forum {
+            tags: Timestamped::new(tags, now),
+            posts: BTreeMap::new(),
+        }
+    }
+
+    pub fn tags(&self) -> &Timestamped<Vec<String>> {
+        &self.tags
+    }
+
+    // Tags which are no longer available are removed from existing posts
+    pub fn set_tags(&mut self, tags: Vec<String>, now: TimestampMillis) {
+        for post in self.posts.values_mut() {
+            post.tags.retain(|t| tags.contains(t));
+        }
+        self.tags = Timestamped::new(tags, now);
+    }
+
+    pub fn validate_post(&self, details: &ForumPostDetails) -> Result<(), String> {
+        let title_length = details.title.trim().chars().count();
+        if title_length == 0 {
+            Err("Post title cannot be empty".to_string())
+        } else if title_length > MAX_FORUM_POST_TITLE_LENGTH {
+            Err(format!("Post title cannot exceed {MAX_FORUM_POST_TITLE_LENGTH} characters"))
+        } else if details.tags.len() > MAX_TAGS_PER_FORUM_POST {
+            Err(format!("Posts cannot have more than {MAX_TAGS_PER_FORUM_POST} tags"))
+        } else if let Some(tag) = details.tags.iter().find(|t| !self.tags.value.contains(t)) {
+            Err(format!("Tag not found: {tag}"))
+        } else {
+            Ok(())
+        }
+    }
+
+    pub fn add_post(&mut self, message_index: MessageIndex, details: ForumPostDetails) {
+        let mut tags = details.tags;
+        tags.sort_unstable();
+        tags.dedup();
+
+        self.posts.insert(
+            message_index,
+            ForumPostInternal {
+                title: details.title.trim().to_string(),
+                tags,
+                resolved: false,
+                locked: false,
+            },
+        );
+    }
+
+    pub fn get(&self, message_index: &MessageIndex) -> Option<&ForumPostInternal> {
+        self.posts.get(message_index)
+    }
+
+    pub fn get_mut(&mut self, message_index: &MessageIndex) -> Option<&mut ForumPostInternal> {
+        self.posts.get_mut(message_index)
+    }
+
+    pub fn is_locked(&self, message_index: &MessageIndex) -> bool {
+        self.posts.get(message_index).map_or(false, |p| p.locked)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&MessageIndex, &ForumPostInternal)> {
+        self.posts.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn duplicate_tags_are_removed() {
+        let mut forum = Forum::new(tags(&["a", "b", "c"]), 0);
+
+        forum.add_post(1.into(), details("Title", &["b", "a", "b", "c", "a"]));
+
+        assert_eq!(forum.get(&1.into()).unwrap().tags, tags(&["a", "b", "c"]));
+    }
+
+    #[test]
+    fn post_validation() {
+        let forum = Forum::new(tags(&["a", "b", "c", "d", "e", "f"]), 0);
+
+        assert!(forum.validate_post(&details("Title", &["a", "b"])).is_ok());
+        assert!(forum.validate_post(&details("  ", &[])).is_err());
+        assert!(forum
+            .validate_post(&details(&"x".repeat(MAX_FORUM_POST_TITLE_LENGTH + 1), &[]))
+            .is_err());
+        assert!(forum
+            .validate_post(&details("Title", &["a", "b", "c", "d", "e", "f"]))
+            .is_err());
+        assert!(forum.validate_post(&details("Title", &["z"])).is_err());
+    }
+
+    #[test]
+    fn removed_tags_are_removed_from_posts() {
+        let mut forum = Forum::new(tags(&["a", "b"]), 0);
+        forum.add_post(1.into(), details("Title", &["a", "b"]));
+
+        forum.set_tags(tags(&["b", "c"]), 1);
+
+        assert_eq!(forum.get(&1.into()).unwrap().tags, tags(&["b"]));
+        assert_eq!(forum.tags().value, tags(&["b", "c"]));
+    }
+
+    fn details(title: &str, tags: &[&str]) -> ForumPostDetails {
+        ForumPostDetails {
+            title: title.to_string(),
+            tags: self::tags(tags),
+        }
+    }
+
+    fn tags(tags: &[&str]) -> Vec<String> {
+        tags.iter().map(|t| t.to_string()).collect()
+    }
+}
@@ -35,6 +35,7 @@ impl CommunityMembers {
             channels: public_channels.into_iter().collect(),
             channels_removed: Vec::new(),
             rules_accepted: Some(Timestamped::new(Version::zero(), now)),
+            onboarding_answered: None,
             is_bot: false,
             display_name: Timestamped::default(),
         };
@@ -64,6 +65,7 @@ impl CommunityMembers {
                         channels: HashSet::new(),
                         channels_removed: Vec::new(),
                         rules_accepted: None,
+                        onboarding_answered: None,
                         is_bot,
                         display_name: Timestamped::default(),
                     };
@@ -215,6 +217,13 @@ impl CommunityMembers {
         self.user_groups.deleted_since(since)
     }
 
+    pub fn is_in_any_user_group(&self, user_id: &UserId, user_group_ids: &[u32]) -> bool {
+        user_group_ids
+            .iter()
+            .filter_map(|id| self.user_groups.get(*id))
+            .any(|g| g.members.contains(user_id))
+    }
+
     pub fn user_groups_last_updated(&self) -> TimestampMillis {
         self.user_groups.last_updated()
     }
@@ -335,6 +344,8 @@ pub struct CommunityMemberInternal {
     pub channels: HashSet<ChannelId>,
     pub channels_removed: Vec<Timestamped<ChannelId>>,
     pub rules_accepted: Option<Timestamped<Version>>,
+    #[serde(default)]
+    pub onboarding_answered: Option<TimestampMillis>,
     pub is_bot: bool,
     display_name: Timestamped<Option<String>>,
 }
@@ -357,6 +368,19 @@ impl CommunityMemberInternal {
         }
     }
 
+    // Returns false if the member has already answered the onboarding questions since they were last updated
+    pub fn answer_onboarding(&mut self, onboarding_updated: TimestampMillis, now: TimestampMillis) -> bool {
+        if self
+            .onboarding_answered
+            .map_or(false, |answered| answered >= onboarding_updated)
+        {
+            false
+        } else {
+            self.onboarding_answered = Some(now);
+            true
+        }
+    }
+
     pub fn channels_removed_since(&self, since: TimestampMillis) -> Vec<ChannelId> {
         self.channels_removed
             .iter()
@@ -427,3 +451,22 @@ impl From<&CommunityMemberInternal> for CommunityMember {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn onboarding_can_only_be_answered_once_per_update() {
+        let principal = Principal::from_slice(&[1]);
+        let mut members = CommunityMembers::new(principal, principal.into(), Vec::new(), 0);
+        let member = members.get_mut(principal).unwrap();
+
+        assert!(member.answer_onboarding(10, 20));
+        assert!(!member.answer_onboarding(10, 30));
+
+        // Once the onboarding questions are updated they can be answered again
+        assert!(member.answer_onboarding(40, 50));
+        assert!(!member.answer_onboarding(40, 60));
+    }
+}
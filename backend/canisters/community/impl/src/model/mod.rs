@@ -1,5 +1,9 @@
+pub mod ban_lists;
+pub mod calendar_events;
+pub mod channel_categories;
 pub mod channels;
 pub mod events;
+pub mod forum;
 pub mod groups_being_imported;
 pub mod invited_users;
 pub mod members;
@@ -0,0 +1,28 @@
+use crate::read_state;
+use crate::RuntimeState;
+use community_canister::ban_lists::{Response::*, *};
+use ic_cdk_macros::query;
+
+#[query]
+fn ban_lists(args: Args) -> Response {
+    read_state(|state| ban_lists_impl(args, state))
+}
+
+fn ban_lists_impl(args: Args, state: &RuntimeState) -> Response {
+    let caller = state.env.caller();
+
+    if let Some(member) = state.data.members.get(caller) {
+        if member.role.can_update_details(&state.data.permissions) {
+            Success(SuccessResult {
+                published: state.data.ban_lists.is_published(),
+                subscribers: state.data.ban_lists.subscribers().len() as u32,
+                subscriptions: state.data.ban_lists.subscriptions(),
+                audit_log: state.data.ban_lists.audit_log(args.max_audit_records as usize),
+            })
+        } else {
+            NotAuthorized
+        }
+    } else {
+        UserNotInCommunity
+    }
+}
@@ -0,0 +1,49 @@
+use crate::read_state;
+use crate::RuntimeState;
+use canister_api_macros::query_msgpack;
+use community_canister::c2c_unvoted_proposals::{Response::*, *};
+use types::MultiUserChat;
+
+#[query_msgpack]
+fn c2c_unvoted_proposals(args: Args) -> Response {
+    read_state(|state| c2c_unvoted_proposals_impl(args, state))
+}
+
+fn c2c_unvoted_proposals_impl(args: Args, state: &RuntimeState) -> Response {
+    let caller = state.env.caller();
+    let Some(member) = state.data.members.get(caller) else {
+        return UserNotInCommunity;
+    };
+
+    let community_id = state.env.canister_id().into();
+    let now = state.env.now();
+
+    let proposals_channels: Vec<_> = member
+        .channels
+        .iter()
+        .filter_map(|channel_id| state.data.channels.get(channel_id))
+        .filter(|channel| channel.chat.subtype.value.is_some())
+        .collect();
+
+    if proposals_channels.is_empty() {
+        return NoProposals;
+    }
+
+    let proposals = proposals_channels
+        .into_iter()
+        .filter_map(|channel| {
+            channel.chat.members.get(&member.user_id).map(|channel_member| {
+                channel.chat.events.unvoted_proposals(
+                    MultiUserChat::Channel(community_id, channel.id),
+                    member.user_id,
+                    channel_member.min_visible_event_index(),
+                    args.closing_before,
+                    now,
+                )
+            })
+        })
+        .flatten()
+        .collect();
+
+    Success(proposals)
+}
@@ -0,0 +1,34 @@
+use crate::{read_state, RuntimeState};
+use community_canister::chat_reports::{Response::*, *};
+use ic_cdk_macros::query;
+
+#[query]
+fn chat_reports(args: Args) -> Response {
+    read_state(|state| chat_reports_impl(args, state))
+}
+
+fn chat_reports_impl(args: Args, state: &RuntimeState) -> Response {
+    let caller = state.env.caller();
+    let user_id = match state.data.members.get(caller) {
+        Some(m) => m.user_id,
+        None => return UserNotInCommunity,
+    };
+
+    let channel = match state.data.channels.get(&args.channel_id) {
+        Some(c) => c,
+        None => return ChannelNotFound,
+    };
+
+    match channel.chat.members.get(&user_id) {
+        Some(m) if !m.role.can_handle_reports() => NotAuthorized,
+        Some(_) => {
+            let reports = &channel.chat.reports;
+
+            Success(SuccessResult {
+                reports: reports.list(args.include_resolved),
+                member_report_counts: reports.member_report_counts(),
+            })
+        }
+        None => UserNotInChannel,
+    }
+}
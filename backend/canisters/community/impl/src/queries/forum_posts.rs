@@ -0,0 +1,79 @@
+use crate::{read_state, RuntimeState};
+use chat_events::Reader;
+use community_canister::forum_posts::{Response::*, *};
+use ic_cdk_macros::query;
+use std::cmp::Reverse;
+use types::{ForumPost, ForumPostSortOrder};
+
+#[query]
+fn forum_posts(args: Args) -> Response {
+    read_state(|state| forum_posts_impl(args, state))
+}
+
+fn forum_posts_impl(args: Args, state: &RuntimeState) -> Response {
+    let caller = state.env.caller();
+    let user_id = state.data.members.get(caller).map(|m| m.user_id);
+
+    if !state.data.is_public && user_id.is_none() {
+        return UserNotInCommunity;
+    }
+
+    let channel = match state.data.channels.get(&args.channel_id) {
+        Some(c) => c,
+        None => return ChannelNotFound,
+    };
+
+    let forum = match &channel.forum {
+        Some(f) => f,
+        None => return ChannelNotForum,
+    };
+
+    let min_visible_event_index = match channel.chat.min_visible_event_index(user_id) {
+        Some(i) => i,
+        None => return UserNotInChannel,
+    };
+
+    let events_reader = channel.chat.events.visible_main_events_reader(min_visible_event_index);
+
+    let mut posts: Vec<_> = forum
+        .iter()
+        .filter(|(_, p)| args.tag.as_ref().map_or(true, |t| p.tags.contains(t)))
+        .filter_map(|(message_index, p)| {
+            let message_event = events_reader.message_event_internal((*message_index).into())?;
+            let message = message_event.event;
+            if message.deleted_by.is_some() {
+                return None;
+            }
+
+            let thread_summary = message.thread_summary.as_ref();
+
+            Some(ForumPost {
+                message_index: *message_index,
+                event_index: message_event.index,
+                title: p.title.clone(),
+                tags: p.tags.clone(),
+                created_by: message.sender,
+                created: message_event.timestamp,
+                latest_activity: thread_summary.map_or(message_event.timestamp, |t| t.latest_event_timestamp),
+                reply_count: thread_summary.map_or(0, |t| t.reply_count),
+                resolved: p.resolved,
+                locked: p.locked,
+            })
+        })
+        .collect();
+
+    let total = posts.len() as u32;
+
+    match args.sort_order {
+        ForumPostSortOrder::LatestActivity => posts.sort_by_key(|p| Reverse((p.latest_activity, p.message_index))),
+        ForumPostSortOrder::Created => posts.sort_by_key(|p| Reverse(p.message_index)),
+    }
+
+    let posts = posts
+        .into_iter()
+        .skip(args.page_index as usize * args.page_size as usize)
+        .take(args.page_size as usize)
+        .collect();
+
+    Success(SuccessResult { posts, total })
+}
@@ -1,17 +1,23 @@
 use crate::RuntimeState;
 use types::TimestampMillis;
 
+mod ban_lists;
+mod c2c_is_member;
+mod c2c_unvoted_proposals;
 mod channel_summary;
 mod channel_summary_updates;
+mod chat_reports;
 mod deleted_message;
 mod events;
 mod events_by_index;
 mod events_window;
 mod explore_channels;
+mod forum_posts;
 mod http_request;
 mod invite_code;
 mod local_user_index;
 mod messages_by_message_index;
+mod proposal_vote_delegation;
 mod search_channel;
 mod selected_channel_initial;
 mod selected_channel_updates;
@@ -20,6 +26,8 @@ mod selected_updates;
 mod summary;
 mod summary_updates;
 mod thread_previews;
+mod typing;
+mod upcoming_calendar_events;
 
 fn check_replica_up_to_date(latest_known_update: Option<TimestampMillis>, state: &RuntimeState) -> Result<(), TimestampMillis> {
     if let Some(ts) = latest_known_update {
@@ -0,0 +1,30 @@
+use crate::{read_state, RuntimeState};
+use community_canister::proposal_vote_delegation::{Response::*, *};
+use ic_cdk_macros::query;
+
+#[query]
+fn proposal_vote_delegation(args: Args) -> Response {
+    read_state(|state| proposal_vote_delegation_impl(args, state))
+}
+
+fn proposal_vote_delegation_impl(args: Args, state: &RuntimeState) -> Response {
+    let caller = state.env.caller();
+    let Some(member) = state.data.members.get(caller) else {
+        return UserNotInCommunity;
+    };
+
+    let Some(channel) = state.data.channels.get(&args.channel_id) else {
+        return ChannelNotFound;
+    };
+
+    if channel.chat.members.get(&member.user_id).is_none() {
+        return UserNotInChannel;
+    }
+
+    let delegations = &channel.chat.proposal_vote_delegations;
+
+    Success(SuccessResult {
+        delegation: delegations.get(&member.user_id),
+        followers: delegations.follower_count(&member.user_id) as u32,
+    })
+}
@@ -58,6 +58,8 @@ fn summary_updates_impl(args: Args, state: &RuntimeState) -> Response {
         && channels_removed.is_empty()
         && state.data.events.latest_event_timestamp() <= args.updates_since
         && state.data.members.user_groups_last_updated() <= args.updates_since
+        && state.data.channel_categories.last_updated() <= args.updates_since
+        && state.data.onboarding.timestamp <= args.updates_since
         && member_last_updated <= args.updates_since
     {
         return SuccessNoUpdates;
@@ -106,10 +108,19 @@ fn summary_updates_impl(args: Args, state: &RuntimeState) -> Response {
             }),
     });
 
+    // The layout is filtered to the channels visible to the user, so it must also be returned
+    // whenever the user's set of channels changes
+    let channel_categories = (state.data.channel_categories.last_updated() > args.updates_since
+        || !channels_added.is_empty()
+        || !channels_removed.is_empty())
+    .then(|| state.data.channel_categories_layout(member));
+
     let last_updated = [
         member_last_updated,
         state.data.events.latest_event_timestamp(),
         state.data.members.user_groups_last_updated(),
+        state.data.channel_categories.last_updated(),
+        state.data.onboarding.timestamp,
     ]
     .into_iter()
     .chain(channels_added.iter().map(|c| c.last_updated))
@@ -143,6 +154,12 @@ fn summary_updates_impl(args: Args, state: &RuntimeState) -> Response {
             .map(|u| u.into())
             .collect(),
         user_groups_deleted: state.data.members.user_groups_deleted_since(args.updates_since),
+        channel_categories,
+        onboarding: state
+            .data
+            .onboarding
+            .if_set_after(args.updates_since)
+            .map_or(OptionUpdate::NoChange, |o| OptionUpdate::from_update(o.clone())),
         metrics: state.data.cached_chat_metrics.if_set_after(args.updates_since).cloned(),
     })
 }
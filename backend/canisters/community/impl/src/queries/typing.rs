@@ -0,0 +1,28 @@
+use crate::{read_state, RuntimeState};
+use community_canister::typing::{Response::*, *};
+use ic_cdk_macros::query;
+
+#[query]
+fn typing(args: Args) -> Response {
+    read_state(|state| typing_impl(args, state))
+}
+
+fn typing_impl(args: Args, state: &RuntimeState) -> Response {
+    let caller = state.env.caller();
+    let user_id = match state.data.members.get(caller) {
+        Some(m) => m.user_id,
+        None => return UserNotInCommunity,
+    };
+
+    let channel = match state.data.channels.get(&args.channel_id) {
+        Some(c) => c,
+        None => return ChannelNotFound,
+    };
+
+    let now = state.env.now();
+
+    channel
+        .chat
+        .typing(user_id, args.thread_root_message_index, now)
+        .map_or(UserNotInChannel, Success)
+}
@@ -0,0 +1,47 @@
+use crate::{read_state, RuntimeState};
+use community_canister::upcoming_calendar_events::{Response::*, *};
+use ic_cdk_macros::query;
+use types::UpcomingCalendarEvent;
+
+#[query]
+fn upcoming_calendar_events(_args: Args) -> Response {
+    read_state(upcoming_calendar_events_impl)
+}
+
+fn upcoming_calendar_events_impl(state: &RuntimeState) -> Response {
+    let caller = state.env.caller();
+    let user_id = state.data.members.get(caller).map(|m| m.user_id);
+
+    if !state.data.is_public && user_id.is_none() {
+        return UserNotInCommunity;
+    }
+
+    let now = state.env.now();
+    let mut events: Vec<_> = state
+        .data
+        .calendar_events
+        .upcoming(now)
+        .filter_map(|e| {
+            let channel = state.data.channels.get(&e.channel_id)?;
+            let min_visible_event_index = channel.chat.min_visible_event_index(user_id)?;
+            let (event, event_index) =
+                channel
+                    .chat
+                    .events
+                    .calendar_event(min_visible_event_index, e.thread_root_message_index, e.message_index)?;
+
+            Some(UpcomingCalendarEvent {
+                channel_id: e.channel_id,
+                thread_root_message_index: e.thread_root_message_index,
+                message_index: e.message_index,
+                event_index,
+                details: event.details.clone(),
+                rsvps: event.rsvp_counts(user_id),
+            })
+        })
+        .collect();
+
+    events.sort_by_key(|e| e.details.start);
+
+    Success(events)
+}
@@ -1,15 +1,22 @@
 use crate::activity_notifications::handle_activity_notification;
 use crate::jobs::import_groups::{finalize_group_import, mark_import_complete, process_channel_members};
+use crate::updates::claim_prize::commit_prize_claim;
 use crate::{mutate_state, read_state};
 use canister_timer_jobs::Job;
 use chat_events::MessageContentInternal;
-use ledger_utils::process_transaction;
+use group_index_canister::c2c_report_message;
+use ledger_utils::{process_transaction, try_process_transaction};
 use serde::{Deserialize, Serialize};
 use tracing::error;
-use types::{BlobReference, CanisterId, ChannelId, ChatId, MessageId, MessageIndex, PendingCryptoTransaction};
-use utils::consts::MEMO_PRIZE_REFUND;
+use types::{
+    BlobReference, CanisterId, ChannelCalendarEventReminder, ChannelId, ChatId, ChatReportAction, EventIndex, MessageId,
+    MessageIndex, Notification, PendingCryptoTransaction, UserId,
+};
+use utils::consts::{MEMO_POLL_VOTE_REFUND, MEMO_PRIZE_CLAIM, MEMO_PRIZE_REFUND};
 use utils::time::MINUTE_IN_MS;
 
+const MAX_PAY_PRIZE_WINNER_ATTEMPTS: u32 = 10;
+
 #[derive(Serialize, Deserialize, Clone)]
 pub enum TimerJob {
     HardDeleteMessageContent(HardDeleteMessageContentJob),
@@ -21,6 +28,10 @@ pub enum TimerJob {
     MarkGroupImportComplete(MarkGroupImportCompleteJob),
     RefundPrize(RefundPrizeJob),
     MakeTransfer(MakeTransferJob),
+    PayPrizeWinner(PayPrizeWinnerJob),
+    SendCalendarEventReminder(SendCalendarEventReminderJob),
+    EscalateChatReport(EscalateChatReportJob),
+    CastDelegatedProposalVote(CastDelegatedProposalVoteJob),
 }
 
 #[derive(Serialize, Deserialize, Clone)]
@@ -75,6 +86,38 @@ pub struct MakeTransferJob {
     pub pending_transaction: PendingCryptoTransaction,
 }
 
+#[derive(Serialize, Deserialize, Clone)]
+pub struct PayPrizeWinnerJob {
+    pub channel_id: ChannelId,
+    pub message_id: MessageId,
+    pub winner: UserId,
+    pub pending_transaction: PendingCryptoTransaction,
+    #[serde(default)]
+    pub attempt: u32,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct SendCalendarEventReminderJob {
+    pub channel_id: ChannelId,
+    pub thread_root_message_index: Option<MessageIndex>,
+    pub message_index: MessageIndex,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct EscalateChatReportJob {
+    pub channel_id: ChannelId,
+    pub report_id: u32,
+    pub c2c_args: c2c_report_message::Args,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct CastDelegatedProposalVoteJob {
+    pub channel_id: ChannelId,
+    pub user_id: UserId,
+    pub message_index: MessageIndex,
+    pub adopt: bool,
+}
+
 impl Job for TimerJob {
     fn execute(self) {
         match self {
@@ -87,6 +130,10 @@ impl Job for TimerJob {
             TimerJob::MarkGroupImportComplete(job) => job.execute(),
             TimerJob::RefundPrize(job) => job.execute(),
             TimerJob::MakeTransfer(job) => job.execute(),
+            TimerJob::PayPrizeWinner(job) => job.execute(),
+            TimerJob::SendCalendarEventReminder(job) => job.execute(),
+            TimerJob::EscalateChatReport(job) => job.execute(),
+            TimerJob::CastDelegatedProposalVote(job) => job.execute(),
         }
     }
 }
@@ -141,6 +188,13 @@ impl Job for HardDeleteMessageContentJob {
                                 }
                             }
                         }
+                    } else if let MessageContentInternal::Poll(poll) = content {
+                        let now_nanos = state.env.now_nanos();
+                        for (user_id, lock) in poll.token_locks {
+                            if let Some(pending_transaction) = lock.refund(user_id, &MEMO_POLL_VOTE_REFUND, now_nanos) {
+                                follow_on_jobs.push(TimerJob::MakeTransfer(MakeTransferJob { pending_transaction }));
+                            }
+                        }
                     }
                 }
             }
@@ -160,17 +214,35 @@ impl Job for DeleteFileReferencesJob {
 
 impl Job for EndPollJob {
     fn execute(self) {
-        mutate_state(|state| {
+        let refunds = mutate_state(|state| {
             let now = state.env.now();
+            let now_nanos = state.env.now_nanos();
             if let Some(channel) = state.data.channels.get_mut(&self.channel_id) {
                 channel
                     .chat
                     .events
                     .end_poll(self.thread_root_message_index, self.message_index, now);
 
+                // Return any tokens which were locked to weight votes in the poll
+                let refunds: Vec<_> = channel
+                    .chat
+                    .events
+                    .take_poll_token_locks(self.thread_root_message_index, self.message_index)
+                    .into_iter()
+                    .filter_map(|(user_id, lock)| lock.refund(user_id, &MEMO_POLL_VOTE_REFUND, now_nanos))
+                    .collect();
+
                 handle_activity_notification(state);
+                refunds
+            } else {
+                Vec::new()
             }
         });
+
+        for pending_transaction in refunds {
+            let make_transfer_job = MakeTransferJob { pending_transaction };
+            make_transfer_job.execute();
+        }
     }
 }
 
@@ -200,6 +272,35 @@ impl Job for MarkGroupImportCompleteJob {
 
 impl Job for RefundPrizeJob {
     fn execute(self) {
+        // For prize draws, first pick the winners and pay them their prizes
+        if let Some((message_id, payouts)) = mutate_state(|state| {
+            let now = state.env.now();
+            let now_nanos = state.env.now_nanos();
+            if let Some(channel) = state.data.channels.get_mut(&self.channel_id) {
+                channel.chat.events.draw_prize(
+                    self.thread_root_message_index,
+                    self.message_index,
+                    &MEMO_PRIZE_CLAIM,
+                    state.env.rng(),
+                    now_nanos,
+                    now,
+                )
+            } else {
+                None
+            }
+        }) {
+            for (winner, pending_transaction) in payouts {
+                let pay_prize_winner_job = PayPrizeWinnerJob {
+                    channel_id: self.channel_id,
+                    message_id,
+                    winner,
+                    pending_transaction,
+                    attempt: 0,
+                };
+                pay_prize_winner_job.execute();
+            }
+        }
+
         if let Some(pending_transaction) = read_state(|state| {
             if let Some(channel) = state.data.channels.get(&self.channel_id) {
                 channel.chat.events.prize_refund(
@@ -239,3 +340,158 @@ impl Job for MakeTransferJob {
         }
     }
 }
+
+impl Job for PayPrizeWinnerJob {
+    fn execute(self) {
+        let sender = read_state(|state| state.env.canister_id());
+        ic_cdk::spawn(pay_prize_winner(self, sender));
+
+        async fn pay_prize_winner(job: PayPrizeWinnerJob, sender: CanisterId) {
+            match try_process_transaction(job.pending_transaction.clone(), sender).await {
+                Ok(transaction) => {
+                    if let Some(error) =
+                        mutate_state(|state| commit_prize_claim(job.channel_id, job.message_id, job.winner, transaction, state))
+                    {
+                        error!(%error, "Failed to record prize draw winner");
+                    }
+                }
+                Err(failure) if failure.transient && job.attempt + 1 < MAX_PAY_PRIZE_WINNER_ATTEMPTS => {
+                    // The pending transaction is reused so that the ledger will deduplicate the
+                    // transfer if the previous attempt actually succeeded
+                    mutate_state(|state| {
+                        let now = state.env.now();
+                        state.data.timer_jobs.enqueue_job(
+                            TimerJob::PayPrizeWinner(PayPrizeWinnerJob {
+                                attempt: job.attempt + 1,
+                                ..job
+                            }),
+                            now + MINUTE_IN_MS,
+                            now,
+                        );
+                    });
+                }
+                Err(failure) => {
+                    error!(
+                        error = failure.transaction.error_message(),
+                        winner = %job.winner,
+                        attempts = job.attempt + 1,
+                        "Failed to pay prize draw winner"
+                    );
+                    // Release the winner's reservation and refund their prize to the prize's sender
+                    if let Some(pending_transaction) = mutate_state(|state| {
+                        let now = state.env.now();
+                        let now_nanos = state.env.now_nanos();
+                        state.data.channels.get_mut(&job.channel_id).and_then(|channel| {
+                            channel.chat.events.refund_prize_reservation(
+                                job.message_id,
+                                job.winner,
+                                job.pending_transaction.units(),
+                                &MEMO_PRIZE_REFUND,
+                                now_nanos,
+                                now,
+                            )
+                        })
+                    }) {
+                        let make_transfer_job = MakeTransferJob { pending_transaction };
+                        make_transfer_job.execute();
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl Job for SendCalendarEventReminderJob {
+    fn execute(self) {
+        mutate_state(|state| {
+            if let Some(channel) = state.data.channels.get(&self.channel_id) {
+                if let Some((event, event_index)) = channel.chat.events.calendar_event(
+                    EventIndex::default(),
+                    self.thread_root_message_index,
+                    self.message_index,
+                ) {
+                    // Only remind attendees who are still in the channel and not suspended
+                    let recipients: Vec<UserId> = event
+                        .attendees()
+                        .into_iter()
+                        .filter(|u| channel.chat.members.get(u).is_some())
+                        .filter(|u| state.data.members.get_by_user_id(u).map_or(false, |m| !m.suspended.value))
+                        .collect();
+
+                    let notification = Notification::ChannelCalendarEventReminder(ChannelCalendarEventReminder {
+                        community_id: state.env.canister_id().into(),
+                        channel_id: self.channel_id,
+                        thread_root_message_index: self.thread_root_message_index,
+                        message_index: self.message_index,
+                        message_event_index: event_index,
+                        community_name: state.data.name.clone(),
+                        channel_name: channel.chat.name.value.clone(),
+                        title: event.details.title.clone(),
+                        start: event.details.start,
+                        location: event.details.location.clone(),
+                        community_avatar_id: state.data.avatar.as_ref().map(|d| d.id),
+                        channel_avatar_id: channel.chat.avatar.as_ref().map(|d| d.id),
+                    });
+                    state.push_notification(recipients, notification);
+                }
+            }
+        });
+    }
+}
+
+impl Job for EscalateChatReportJob {
+    fn execute(self) {
+        // Skip reports which have already been handled by the channel's moderators
+        let Some(group_index_canister_id) = read_state(|state| {
+            state
+                .data
+                .channels
+                .get(&self.channel_id)
+                .and_then(|c| c.chat.reports.get(self.report_id))
+                .filter(|r| r.resolution.is_none())
+                .map(|_| state.data.group_index_canister_id)
+        }) else {
+            return;
+        };
+
+        ic_cdk::spawn(escalate_chat_report(self, group_index_canister_id));
+
+        async fn escalate_chat_report(job: EscalateChatReportJob, group_index_canister_id: CanisterId) {
+            match group_index_canister_c2c_client::c2c_report_message(group_index_canister_id, &job.c2c_args).await {
+                Ok(_) => mutate_state(|state| {
+                    let now = state.env.now();
+                    if let Some(channel) = state.data.channels.get_mut(&job.channel_id) {
+                        // Automatic escalations are attributed to the user who reported the message
+                        channel.chat.reports.resolve(
+                            job.report_id,
+                            ChatReportAction::EscalateToPlatform,
+                            job.c2c_args.reporter,
+                            now,
+                        );
+                    }
+                }),
+                Err(error) => {
+                    error!(?error, "Failed to escalate chat report");
+                    mutate_state(|state| {
+                        let now = state.env.now();
+                        state
+                            .data
+                            .timer_jobs
+                            .enqueue_job(TimerJob::EscalateChatReport(job), now + MINUTE_IN_MS, now);
+                    });
+                }
+            }
+        }
+    }
+}
+
+impl Job for CastDelegatedProposalVoteJob {
+    fn execute(self) {
+        ic_cdk::spawn(crate::updates::register_proposal_vote::cast_delegated_vote(
+            self.channel_id,
+            self.user_id,
+            self.message_index,
+            self.adopt,
+        ));
+    }
+}
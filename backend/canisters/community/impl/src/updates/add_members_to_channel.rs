@@ -44,7 +44,7 @@ async fn add_members_to_channel(args: Args) -> Response {
 
         for (user_id, result) in zip(prepare_result.users_to_add, results) {
             match result {
-                CheckIfPassesGateResult::Success => users_to_add.push(user_id),
+                CheckIfPassesGateResult::Success | CheckIfPassesGateResult::PaymentTaken(_) => users_to_add.push(user_id),
                 CheckIfPassesGateResult::Failed(reason) => {
                     users_failed_gate_check.push(UserFailedGateCheck { user_id, reason })
                 }
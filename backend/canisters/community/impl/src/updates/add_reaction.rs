@@ -29,6 +29,14 @@ fn add_reaction_impl(args: Args, state: &mut RuntimeState) -> Response {
         let user_id = member.user_id;
 
         if let Some(channel) = state.data.channels.get_mut(&args.channel_id) {
+            if !channel.is_permitted_by_user_groups(
+                &channel.user_group_permissions.react_to_messages,
+                user_id,
+                &state.data.members,
+            ) {
+                return NotAuthorized;
+            }
+
             let now = state.env.now();
             match channel.chat.add_reaction(
                 user_id,
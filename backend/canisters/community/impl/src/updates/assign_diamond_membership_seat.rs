@@ -0,0 +1,69 @@
+use crate::{read_state, run_regular_jobs, RuntimeState};
+use canister_tracing_macros::trace;
+use community_canister::assign_diamond_membership_seat::{Response::*, *};
+use ic_cdk_macros::update;
+use types::{CanisterId, UserId};
+
+#[update]
+#[trace]
+async fn assign_diamond_membership_seat(args: Args) -> Response {
+    run_regular_jobs();
+
+    let PrepareResult {
+        caller_id,
+        user_index_canister_id,
+    } = match read_state(|state| prepare(args.user_id, state)) {
+        Ok(result) => result,
+        Err(response) => return response,
+    };
+
+    let c2c_args = user_index_canister::c2c_assign_diamond_membership_seat::Args {
+        user_id: args.user_id,
+        assigned_by: caller_id,
+    };
+    match user_index_canister_c2c_client::c2c_assign_diamond_membership_seat(user_index_canister_id, &c2c_args).await {
+        Ok(response) => match response {
+            user_index_canister::c2c_assign_diamond_membership_seat::Response::Success(result) => Success(SuccessResult {
+                expires_at: result.expires_at,
+                seats_remaining: result.seats_remaining,
+            }),
+            user_index_canister::c2c_assign_diamond_membership_seat::Response::NoSeatsAvailable => NoSeatsAvailable,
+            user_index_canister::c2c_assign_diamond_membership_seat::Response::UserNotFound => TargetUserNotInCommunity,
+            user_index_canister::c2c_assign_diamond_membership_seat::Response::CannotExtend(result) => {
+                MembershipCannotBeExtended(result.can_extend_at)
+            }
+            user_index_canister::c2c_assign_diamond_membership_seat::Response::PaymentAlreadyInProgress => {
+                PaymentAlreadyInProgress
+            }
+        },
+        Err(error) => InternalError(format!("{error:?}")),
+    }
+}
+
+struct PrepareResult {
+    caller_id: UserId,
+    user_index_canister_id: CanisterId,
+}
+
+fn prepare(user_id: UserId, state: &RuntimeState) -> Result<PrepareResult, Response> {
+    if state.data.is_frozen() {
+        return Err(CommunityFrozen);
+    }
+
+    let caller = state.env.caller();
+    let Some(member) = state.data.members.get(caller) else {
+        return Err(UserNotInCommunity);
+    };
+    if member.suspended.value {
+        Err(UserSuspended)
+    } else if !member.role.is_owner() && !member.role.is_admin() {
+        Err(NotAuthorized)
+    } else if state.data.members.get_by_user_id(&user_id).is_none() {
+        Err(TargetUserNotInCommunity)
+    } else {
+        Ok(PrepareResult {
+            caller_id: member.user_id,
+            user_index_canister_id: state.data.user_index_canister_id,
+        })
+    }
+}
@@ -0,0 +1,39 @@
+use crate::activity_notifications::handle_activity_notification;
+use crate::{mutate_state, run_regular_jobs, RuntimeState};
+use canister_api_macros::update_msgpack;
+use canister_tracing_macros::trace;
+use community_canister::c2c_ban_list_updated::{Response::*, *};
+use types::CommunityId;
+
+// Called by the community canister which published the ban list
+#[update_msgpack]
+#[trace]
+fn c2c_ban_list_updated(args: Args) -> Response {
+    run_regular_jobs();
+
+    mutate_state(|state| c2c_ban_list_updated_impl(args, state))
+}
+
+fn c2c_ban_list_updated_impl(args: Args, state: &mut RuntimeState) -> Response {
+    let ban_list: CommunityId = state.env.caller().into();
+    let now = state.env.now();
+
+    if args.unpublished {
+        return if state.data.ban_lists.unsubscribe(&ban_list) { Success } else { NotSubscribed };
+    }
+
+    match state
+        .data
+        .ban_lists
+        .update_subscription(&ban_list, &args.blocked, &args.unblocked)
+    {
+        Some(subscription) => {
+            if subscription.remove_existing_members && !args.blocked.is_empty() {
+                state.data.remove_members_banned_by_list(ban_list, args.blocked, now);
+                handle_activity_notification(state);
+            }
+            Success
+        }
+        None => NotSubscribed,
+    }
+}
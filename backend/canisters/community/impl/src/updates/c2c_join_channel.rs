@@ -11,7 +11,7 @@ use chat_events::ChatEventInternal;
 use community_canister::c2c_join_channel::{Response::*, *};
 use gated_groups::{check_if_passes_gate, CheckGateArgs, CheckIfPassesGateResult};
 use group_chat_core::AddResult;
-use types::{AccessGate, ChannelId, MemberJoined, TimestampMillis};
+use types::{AccessGate, ChannelId, GateCheckFailedReason, MemberJoined, TimestampMillis};
 
 #[update_msgpack(guard = "caller_is_user_index_or_local_user_index")]
 #[trace]
@@ -57,21 +57,22 @@ pub(crate) fn join_channel_auto(channel_id: ChannelId, user_principal: Principal
         _ => return,
     };
 
-    mutate_state(|state| commit(channel_id, user_principal, state));
+    mutate_state(|state| commit(channel_id, user_principal, None, state));
 }
 
 async fn check_gate_then_join_channel(channel_id: ChannelId, user_principal: Principal) -> Response {
-    match read_state(|state| is_permitted_to_join(channel_id, user_principal, state)) {
+    let payment_block_index = match read_state(|state| is_permitted_to_join(channel_id, user_principal, state)) {
         Ok(Some(check_gate_args)) => match check_if_passes_gate(check_gate_args).await {
-            CheckIfPassesGateResult::Success => {}
+            CheckIfPassesGateResult::Success => None,
+            CheckIfPassesGateResult::PaymentTaken(block_index) => Some(block_index),
             CheckIfPassesGateResult::Failed(reason) => return GateCheckFailed(reason),
             CheckIfPassesGateResult::InternalError(error) => return InternalError(error),
         },
-        Ok(None) => {}
+        Ok(None) => None,
         Err(response) => return response,
     };
 
-    mutate_state(|state| commit(channel_id, user_principal, state))
+    mutate_state(|state| commit(channel_id, user_principal, payment_block_index, state))
 }
 
 fn is_permitted_to_join(
@@ -101,6 +102,17 @@ fn is_permitted_to_join(
                 Err(MemberLimitReached(limit))
             } else if channel.chat.members.is_blocked(&member.user_id) {
                 Err(UserBlocked)
+            } else if let Some(AccessGate::UserGroup(gate)) = &channel.chat.gate.value {
+                // User group gates can be checked locally since the user groups live in this canister
+                if state
+                    .data
+                    .members
+                    .is_in_any_user_group(&member.user_id, &[gate.user_group_id])
+                {
+                    Ok(None)
+                } else {
+                    Err(GateCheckFailed(GateCheckFailedReason::NotInUserGroup))
+                }
             } else {
                 Ok(channel.chat.gate.as_ref().map(|g| CheckGateArgs {
                     gate: g.clone(),
@@ -118,7 +130,12 @@ fn is_permitted_to_join(
     }
 }
 
-fn commit(channel_id: ChannelId, user_principal: Principal, state: &mut RuntimeState) -> Response {
+fn commit(
+    channel_id: ChannelId,
+    user_principal: Principal,
+    payment_block_index: Option<u64>,
+    state: &mut RuntimeState,
+) -> Response {
     if let Some(member) = state.data.members.get_mut(user_principal) {
         if let Some(channel) = state.data.channels.get_mut(&channel_id) {
             let now = state.env.now();
@@ -130,6 +147,10 @@ fn commit(channel_id: ChannelId, user_principal: Principal, state: &mut RuntimeS
 
                     // If there is a payment gate on this channel then queue payments to *community* owner(s) and treasury
                     if let Some(AccessGate::Payment(gate)) = channel.chat.gate.value.as_ref().cloned() {
+                        if let Some(block_index) = payment_block_index {
+                            let user_id = member.user_id;
+                            state.notify_joining_fee_paid(user_id, &gate, block_index);
+                        }
                         state.queue_access_gate_payments(gate);
                     }
 
@@ -6,9 +6,12 @@ use crate::updates::c2c_join_channel::join_channel_auto;
 use crate::{mutate_state, read_state, run_regular_jobs, RuntimeState};
 use canister_api_macros::update_msgpack;
 use canister_tracing_macros::trace;
+use chat_events::{MessageContentInternal, PushMessageArgs, TextContentInternal};
 use community_canister::c2c_join_community::{Response::*, *};
 use gated_groups::{check_if_passes_gate, CheckGateArgs, CheckIfPassesGateResult};
-use types::{AccessGate, ChannelId, MemberJoined, UsersUnblocked};
+use rand::Rng;
+use types::{AccessGate, BanListAction, ChannelId, MemberJoined, UserId, UsersUnblocked};
+use utils::consts::OPENCHAT_BOT_USER_ID;
 
 #[update_msgpack(guard = "caller_is_user_index_or_local_user_index")]
 #[trace]
@@ -19,21 +22,27 @@ async fn c2c_join_community(args: Args) -> Response {
 }
 
 pub(crate) async fn join_community(args: Args) -> Response {
-    match read_state(|state| is_permitted_to_join(&args, state)) {
+    if let Err(response) = mutate_state(|state| check_ban_lists(&args, state)) {
+        return response;
+    }
+
+    let payment_block_index = match read_state(|state| is_permitted_to_join(&args, state)) {
         Ok(Some(check_gate_args)) => match check_if_passes_gate(check_gate_args).await {
-            CheckIfPassesGateResult::Success => {}
+            CheckIfPassesGateResult::Success => None,
+            CheckIfPassesGateResult::PaymentTaken(block_index) => Some(block_index),
             CheckIfPassesGateResult::Failed(reason) => return GateCheckFailed(reason),
             CheckIfPassesGateResult::InternalError(error) => return InternalError(error),
         },
-        Ok(None) => {}
+        Ok(None) => None,
         Err(response) => return response,
     };
 
-    match mutate_state(|state| join_community_impl(&args, state)) {
+    match mutate_state(|state| join_community_impl(&args, payment_block_index, state)) {
         Ok(public_channel_ids) => {
             for c in public_channel_ids {
                 join_channel_auto(c, args.principal);
             }
+            mutate_state(|state| send_welcome_message(args.user_id, state));
             read_state(|state| {
                 if let Some(member) = state.data.members.get_by_user_id(&args.user_id) {
                     Success(Box::new(state.summary(Some(member))))
@@ -46,6 +55,21 @@ pub(crate) async fn join_community(args: Args) -> Response {
     }
 }
 
+// This is checked before the access gate so that banned users aren't charged for payment gates
+fn check_ban_lists(args: &Args, state: &mut RuntimeState) -> Result<(), Response> {
+    if !args.is_platform_moderator {
+        if let Some(ban_list) = state.data.ban_lists.banning_list(&args.user_id) {
+            let now = state.env.now();
+            state
+                .data
+                .ban_lists
+                .record(args.user_id, ban_list, BanListAction::JoinPrevented, now);
+            return Err(UserBlocked);
+        }
+    }
+    Ok(())
+}
+
 fn is_permitted_to_join(args: &Args, state: &RuntimeState) -> Result<Option<CheckGateArgs>, Response> {
     let caller = state.env.caller();
 
@@ -69,12 +93,17 @@ fn is_permitted_to_join(args: &Args, state: &RuntimeState) -> Result<Option<Chec
     }
 }
 
-pub(crate) fn join_community_impl(args: &Args, state: &mut RuntimeState) -> Result<Vec<ChannelId>, Response> {
+pub(crate) fn join_community_impl(
+    args: &Args,
+    payment_block_index: Option<u64>,
+    state: &mut RuntimeState,
+) -> Result<Vec<ChannelId>, Response> {
     let now = state.env.now();
 
     // Unblock "platform moderator" if necessary
     if args.is_platform_moderator && state.data.members.is_blocked(&args.user_id) {
         state.data.members.unblock(&args.user_id);
+        state.data.notify_ban_list_subscribers(Vec::new(), vec![args.user_id]);
 
         let event = UsersUnblocked {
             user_ids: vec![args.user_id],
@@ -100,8 +129,11 @@ pub(crate) fn join_community_impl(args: &Args, state: &mut RuntimeState) -> Resu
             );
 
             // If there is a payment gate on this community then queue payments to owner(s) and treasury
-            if let Some(AccessGate::Payment(gate)) = state.data.gate.value.as_ref() {
-                state.queue_access_gate_payments(gate.clone());
+            if let Some(AccessGate::Payment(gate)) = state.data.gate.value.as_ref().cloned() {
+                if let Some(block_index) = payment_block_index {
+                    state.notify_joining_fee_paid(args.user_id, &gate, block_index);
+                }
+                state.queue_access_gate_payments(gate);
             }
 
             handle_activity_notification(state);
@@ -116,3 +148,33 @@ pub(crate) fn join_community_impl(args: &Args, state: &mut RuntimeState) -> Resu
         AddResult::Blocked => Err(UserBlocked),
     }
 }
+
+fn send_welcome_message(user_id: UserId, state: &mut RuntimeState) {
+    let welcome_message = match state.data.onboarding.value.as_ref().and_then(|o| o.welcome_message.as_ref()) {
+        Some(m) => m,
+        None => return,
+    };
+
+    if let Some(channel) = state.data.channels.get_mut(&welcome_message.channel_id) {
+        let now = state.env.now();
+        let message_event = channel.chat.events.push_message(PushMessageArgs {
+            sender: OPENCHAT_BOT_USER_ID,
+            thread_root_message_index: None,
+            message_id: state.env.rng().gen(),
+            content: MessageContentInternal::Text(TextContentInternal {
+                text: welcome_message.text_for_member(user_id),
+            }),
+            mentioned: vec![user_id],
+            replies_to: None,
+            forwarded: false,
+            correlation_id: 0,
+            now,
+        });
+
+        if let Some(member) = channel.chat.members.get_mut(&user_id) {
+            member.mentions.add(None, message_event.event.message_index, now);
+        }
+
+        handle_activity_notification(state);
+    }
+}
@@ -0,0 +1,75 @@
+use crate::{mutate_state, read_state, run_regular_jobs, RuntimeState};
+use canister_api_macros::update_msgpack;
+use canister_tracing_macros::trace;
+use community_canister::c2c_subscribe_to_ban_list::{Response::*, *};
+use group_index_canister::c2c_active_groups;
+use types::{CanisterId, CommunityId, MAX_BAN_LIST_SUBSCRIBERS};
+
+// Called by the subscribing community canister
+#[update_msgpack]
+#[trace]
+async fn c2c_subscribe_to_ban_list(_args: Args) -> Response {
+    run_regular_jobs();
+
+    let PrepareResult {
+        caller,
+        group_index_canister_id,
+    } = match read_state(prepare) {
+        Ok(ok) => ok,
+        Err(response) => return response,
+    };
+
+    // Only communities registered with the GroupIndex can subscribe
+    let args = c2c_active_groups::Args {
+        group_ids: Vec::new(),
+        community_ids: vec![caller],
+        active_since: Some(0),
+    };
+    match group_index_canister_c2c_client::c2c_active_groups(group_index_canister_id, &args).await {
+        Ok(c2c_active_groups::Response::Success(result)) if result.active_communities.contains(&caller) => {}
+        Ok(_) => return NotAuthorized,
+        Err(error) => return InternalError(format!("Failed to call 'c2c_active_groups': {error:?}")),
+    }
+
+    mutate_state(|state| commit(caller, state))
+}
+
+struct PrepareResult {
+    caller: CommunityId,
+    group_index_canister_id: CanisterId,
+}
+
+fn prepare(state: &RuntimeState) -> Result<PrepareResult, Response> {
+    check(state)?;
+
+    Ok(PrepareResult {
+        caller: state.env.caller().into(),
+        group_index_canister_id: state.data.group_index_canister_id,
+    })
+}
+
+fn commit(caller: CommunityId, state: &mut RuntimeState) -> Response {
+    // Re-check now that the call to the GroupIndex has completed
+    if let Err(response) = check(state) {
+        return response;
+    }
+
+    state.data.ban_lists.add_subscriber(caller);
+    Success(SuccessResult {
+        blocked_users: state.data.members.blocked(),
+    })
+}
+
+fn check(state: &RuntimeState) -> Result<(), Response> {
+    if state.data.is_frozen() {
+        Err(CommunityFrozen)
+    } else if !state.data.ban_lists.is_published() {
+        Err(BanListNotPublished)
+    } else if !state.data.ban_lists.is_subscriber(&state.env.caller().into())
+        && state.data.ban_lists.subscriber_count() >= MAX_BAN_LIST_SUBSCRIBERS
+    {
+        Err(TooManySubscribers(MAX_BAN_LIST_SUBSCRIBERS as u32))
+    } else {
+        Ok(())
+    }
+}
@@ -0,0 +1,45 @@
+use crate::activity_notifications::handle_activity_notification;
+use crate::guards::caller_is_user_index;
+use crate::{mutate_state, run_regular_jobs, RuntimeState};
+use canister_api_macros::update_msgpack;
+use canister_tracing_macros::trace;
+use chat_events::{DeleteUndeleteMessagesArgs, UndeleteMessageResult};
+use community_canister::c2c_undelete_reported_message::{Response::*, *};
+use types::EventIndex;
+use utils::consts::OPENCHAT_BOT_USER_ID;
+
+// Used by the user_index to restore a message which was deleted for breaking the platform rules once
+// the sender's appeal against that decision has been accepted
+#[update_msgpack(guard = "caller_is_user_index")]
+#[trace]
+fn c2c_undelete_reported_message(args: Args) -> Response {
+    run_regular_jobs();
+
+    mutate_state(|state| c2c_undelete_reported_message_impl(args, state))
+}
+
+fn c2c_undelete_reported_message_impl(args: Args, state: &mut RuntimeState) -> Response {
+    let channel = match state.data.channels.get_mut(&args.channel_id) {
+        Some(c) => c,
+        None => return ChannelNotFound,
+    };
+
+    // Messages deleted by the platform moderators are marked as deleted by the OpenChat bot, so only
+    // those messages can be restored here
+    let now = state.env.now();
+    let results = channel.chat.events.undelete_messages(DeleteUndeleteMessagesArgs {
+        caller: OPENCHAT_BOT_USER_ID,
+        is_admin: false,
+        min_visible_event_index: EventIndex::default(),
+        thread_root_message_index: args.thread_root_message_index,
+        message_ids: vec![args.message_id],
+        now,
+    });
+
+    if results.iter().any(|(_, r)| matches!(r, UndeleteMessageResult::Success)) {
+        handle_activity_notification(state);
+        Success
+    } else {
+        MessageNotFound
+    }
+}
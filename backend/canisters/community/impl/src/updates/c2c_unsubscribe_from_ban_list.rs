@@ -0,0 +1,19 @@
+use crate::{mutate_state, run_regular_jobs, RuntimeState};
+use canister_api_macros::update_msgpack;
+use canister_tracing_macros::trace;
+use community_canister::c2c_unsubscribe_from_ban_list::{Response::*, *};
+
+// Called by the subscribing community canister
+#[update_msgpack]
+#[trace]
+fn c2c_unsubscribe_from_ban_list(_args: Args) -> Response {
+    run_regular_jobs();
+
+    mutate_state(c2c_unsubscribe_from_ban_list_impl)
+}
+
+fn c2c_unsubscribe_from_ban_list_impl(state: &mut RuntimeState) -> Response {
+    let caller = state.env.caller();
+    state.data.ban_lists.remove_subscriber(&caller.into());
+    Success
+}
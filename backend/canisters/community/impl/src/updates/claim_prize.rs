@@ -1,14 +1,18 @@
 use crate::activity_notifications::handle_activity_notification;
-use crate::{mutate_state, run_regular_jobs, RuntimeState};
+use crate::{mutate_state, read_state, run_regular_jobs, RuntimeState};
 use canister_tracing_macros::trace;
 use chat_events::ReservePrizeResult;
 use community_canister::claim_prize::{Response::*, *};
+use gated_groups::{check_if_passes_gate, CheckGateArgs, CheckIfPassesGateResult};
 use ic_cdk_macros::update;
 use ic_ledger_types::Tokens;
 use ledger_utils::{create_pending_transaction, process_transaction};
+use msgpack::serialize_then_unwrap;
 use types::{
-    CanisterId, ChannelMessageNotification, CompletedCryptoTransaction, Notification, PendingCryptoTransaction, UserId,
+    AccessGate, CanisterId, ChannelId, ChannelMessageNotification, CompletedCryptoTransaction, GateCheckFailedReason,
+    MessageId, Notification, PendingCryptoTransaction, PrizeEligibility, PrizeIneligibilityReason, UserId,
 };
+use user_canister::c2c_notify_prize_claimed;
 use utils::consts::{MEMO_PRIZE_CLAIM, OPENCHAT_BOT_USERNAME, OPENCHAT_BOT_USER_ID};
 
 #[update]
@@ -16,7 +20,22 @@ use utils::consts::{MEMO_PRIZE_CLAIM, OPENCHAT_BOT_USERNAME, OPENCHAT_BOT_USER_I
 async fn claim_prize(args: Args) -> Response {
     run_regular_jobs();
 
-    // Validate the request and reserve a prize
+    // Check the user meets the prize's eligibility rules, leaving any access gates until last since
+    // they require c2c calls
+    let gate_checks = match read_state(|state| check_eligibility(&args, state)) {
+        Ok(g) => g,
+        Err(response) => return *response,
+    };
+
+    for check_gate_args in gate_checks {
+        match check_if_passes_gate(check_gate_args).await {
+            CheckIfPassesGateResult::Success | CheckIfPassesGateResult::PaymentTaken(_) => {}
+            CheckIfPassesGateResult::Failed(reason) => return NotEligible(PrizeIneligibilityReason::GateCheckFailed(reason)),
+            CheckIfPassesGateResult::InternalError(error) => return InternalError(error),
+        }
+    }
+
+    // Validate the request and reserve a prize, or enter the user into the draw
     let prepare_result = match mutate_state(|state| prepare(&args, state)) {
         Ok(c) => c,
         Err(response) => return *response,
@@ -28,9 +47,15 @@ async fn claim_prize(args: Args) -> Response {
     match result {
         Ok(completed_transaction) => {
             // Claim the prize and send a message to the group
-            if let Some(error_message) =
-                mutate_state(|state| commit(args, prepare_result.user_id, completed_transaction.clone(), state))
-            {
+            if let Some(error_message) = mutate_state(|state| {
+                commit_prize_claim(
+                    args.channel_id,
+                    args.message_id,
+                    prepare_result.user_id,
+                    completed_transaction.clone(),
+                    state,
+                )
+            }) {
                 FailedAfterTransfer(error_message, completed_transaction)
             } else {
                 Success
@@ -45,6 +70,85 @@ async fn claim_prize(args: Args) -> Response {
     }
 }
 
+fn check_eligibility(args: &Args, state: &RuntimeState) -> Result<Vec<CheckGateArgs>, Box<Response>> {
+    if state.data.is_frozen() {
+        return Err(Box::new(CommunityFrozen));
+    }
+
+    let caller = state.env.caller();
+
+    let member = match state.data.members.get(caller) {
+        Some(m) => m,
+        None => return Err(Box::new(UserNotInCommunity)),
+    };
+
+    if member.suspended.value {
+        return Err(Box::new(UserSuspended));
+    }
+
+    let channel = match state.data.channels.get(&args.channel_id) {
+        Some(c) => c,
+        None => return Err(Box::new(ChannelNotFound)),
+    };
+
+    let channel_member = match channel.chat.members.get(&member.user_id) {
+        Some(m) => m,
+        None => return Err(Box::new(UserNotInChannel)),
+    };
+
+    let now = state.env.now();
+    let min_visible_event_index = channel_member.min_visible_event_index();
+    let eligibility = match channel
+        .chat
+        .events
+        .prize_eligibility(args.message_id, min_visible_event_index)
+    {
+        Some(e) => e,
+        None => return Err(Box::new(MessageNotFound)),
+    };
+
+    let mut gate_checks = Vec::new();
+    for rule in eligibility {
+        match rule {
+            PrizeEligibility::MinMembershipDuration(duration) => {
+                if now.saturating_sub(channel_member.date_added) < *duration {
+                    return Err(Box::new(NotEligible(PrizeIneligibilityReason::MembershipTooRecent)));
+                }
+            }
+            PrizeEligibility::EngagedWithMessage(message_index) => {
+                if !channel
+                    .chat
+                    .events
+                    .has_engaged_with_message(member.user_id, min_visible_event_index, *message_index)
+                {
+                    return Err(Box::new(NotEligible(PrizeIneligibilityReason::NotEngagedWithMessage)));
+                }
+            }
+            // User group gates can be checked locally since the user groups live in this canister
+            PrizeEligibility::AccessGate(AccessGate::UserGroup(gate)) => {
+                if !state
+                    .data
+                    .members
+                    .is_in_any_user_group(&member.user_id, &[gate.user_group_id])
+                {
+                    return Err(Box::new(NotEligible(PrizeIneligibilityReason::GateCheckFailed(
+                        GateCheckFailedReason::NotInUserGroup,
+                    ))));
+                }
+            }
+            PrizeEligibility::AccessGate(gate) => gate_checks.push(CheckGateArgs {
+                gate: gate.clone(),
+                user_index_canister: state.data.user_index_canister_id,
+                user_id: member.user_id,
+                this_canister: state.env.canister_id(),
+                now_nanos: state.env.now_nanos(),
+            }),
+        }
+    }
+
+    Ok(gate_checks)
+}
+
 struct PrepareResult {
     pub transaction: PendingCryptoTransaction,
     pub group: CanisterId,
@@ -90,6 +194,8 @@ fn prepare(args: &Args, state: &mut RuntimeState) -> Result<PrepareResult, Box<R
         {
             ReservePrizeResult::AlreadyClaimed => return Err(Box::new(AlreadyClaimed)),
             ReservePrizeResult::Success(t, l, a, f) => (t, l, a, f),
+            // There is nothing to transfer until the draw is made
+            ReservePrizeResult::EnteredDraw => return Err(Box::new(EnteredDraw)),
             ReservePrizeResult::MessageNotFound => return Err(Box::new(MessageNotFound)),
             ReservePrizeResult::PrizeFullyClaimed => return Err(Box::new(PrizeFullyClaimed)),
             ReservePrizeResult::PrizeEnded => return Err(Box::new(PrizeEnded)),
@@ -104,10 +210,22 @@ fn prepare(args: &Args, state: &mut RuntimeState) -> Result<PrepareResult, Box<R
     })
 }
 
-fn commit(args: Args, winner: UserId, transaction: CompletedCryptoTransaction, state: &mut RuntimeState) -> Option<String> {
+// Also used to pay out the winners of prize draws
+pub(crate) fn commit_prize_claim(
+    channel_id: ChannelId,
+    message_id: MessageId,
+    winner: UserId,
+    transaction: CompletedCryptoTransaction,
+    state: &mut RuntimeState,
+) -> Option<String> {
     let now = state.env.now();
+    let prize_claimed_args = c2c_notify_prize_claimed::Args {
+        ledger: transaction.ledger_canister_id(),
+        amount: transaction.units(),
+        block_index: transaction.block_index(),
+    };
 
-    let channel = match state.data.channels.get_mut(&args.channel_id) {
+    let channel = match state.data.channels.get_mut(&channel_id) {
         Some(c) => c,
         None => return Some("ChannelNotFound".to_string()),
     };
@@ -115,7 +233,7 @@ fn commit(args: Args, winner: UserId, transaction: CompletedCryptoTransaction, s
     match channel
         .chat
         .events
-        .claim_prize(args.message_id, winner, transaction, state.env.rng(), now)
+        .claim_prize(message_id, winner, transaction, state.env.rng(), now)
     {
         chat_events::ClaimPrizeResult::Success(message_event) => {
             // Send a notification to group participants
@@ -130,7 +248,7 @@ fn commit(args: Args, winner: UserId, transaction: CompletedCryptoTransaction, s
 
             let notification = Notification::ChannelMessage(ChannelMessageNotification {
                 community_id: state.env.canister_id().into(),
-                channel_id: args.channel_id,
+                channel_id: channel_id,
                 thread_root_message_index: None,
                 message_index: message_event.event.message_index,
                 event_index: message_event.index,
@@ -148,6 +266,13 @@ fn commit(args: Args, winner: UserId, transaction: CompletedCryptoTransaction, s
             });
             state.push_notification(notification_recipients, notification);
 
+            // Fire-and-forget call to notify the winner's user canister
+            state.data.fire_and_forget_handler.send(
+                winner.into(),
+                "c2c_notify_prize_claimed_msgpack".to_string(),
+                serialize_then_unwrap(prize_claimed_args),
+            );
+
             handle_activity_notification(state);
             None
         }
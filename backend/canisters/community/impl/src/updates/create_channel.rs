@@ -2,6 +2,7 @@ use super::c2c_join_community::join_community_impl;
 use crate::activity_notifications::handle_activity_notification;
 use crate::guards::caller_is_proposals_bot;
 use crate::model::channels::Channel;
+use crate::model::forum::Forum;
 use crate::updates::c2c_join_channel::join_channel_unchecked;
 use crate::{mutate_state, run_regular_jobs, RuntimeState};
 use canister_api_macros::update_msgpack;
@@ -11,7 +12,7 @@ use community_canister::create_channel::{Response::*, *};
 use group_chat_core::GroupChatCore;
 use ic_cdk_macros::update;
 use rand::Rng;
-use types::ChannelId;
+use types::{validate_forum_tags, AccessGate, ChannelId, Timestamped};
 use utils::document_validation::validate_avatar;
 use utils::text_validation::{
     validate_description, validate_group_name, validate_rules, NameValidationError, RulesValidationError,
@@ -41,6 +42,7 @@ fn c2c_create_proposals_channel(args: Args) -> Response {
                 is_platform_moderator: false,
                 is_bot: true,
             },
+            None,
             state,
         )
         .err()
@@ -61,6 +63,12 @@ fn create_channel_impl(args: Args, is_proposals_channel: bool, state: &mut Runti
         return CommunityFrozen;
     }
 
+    if let Some(AccessGate::UserGroup(gate)) = &args.gate {
+        if state.data.members.get_user_group(gate.user_group_id).is_none() {
+            return UserGroupNotFound(gate.user_group_id);
+        }
+    }
+
     let caller = state.env.caller();
     if let Some(member) = state.data.members.get_mut(caller) {
         if member.suspended.value {
@@ -96,6 +104,8 @@ fn create_channel_impl(args: Args, is_proposals_channel: bool, state: &mut Runti
             }
         } else if let Err(error) = validate_avatar(args.avatar.as_ref()) {
             AvatarTooBig(error)
+        } else if let Some(error) = args.forum_tags.as_ref().and_then(|t| validate_forum_tags(t).err()) {
+            InvalidForumTags(error)
         } else if state.data.channels.is_name_taken(&args.name) {
             NameTaken
         } else {
@@ -125,6 +135,9 @@ fn create_channel_impl(args: Args, is_proposals_channel: bool, state: &mut Runti
                 id: channel_id,
                 chat,
                 date_imported: None,
+                forum: args.forum_tags.map(|tags| Forum::new(tags, now)),
+                user_group_permissions: Timestamped::default(),
+                audience_user_groups: Timestamped::default(),
             };
 
             if args.is_public && channel.chat.gate.is_none() {
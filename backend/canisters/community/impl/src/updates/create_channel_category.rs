@@ -0,0 +1,56 @@
+use crate::activity_notifications::handle_activity_notification;
+use crate::{mutate_state, run_regular_jobs, RuntimeState};
+use canister_tracing_macros::trace;
+use community_canister::create_channel_category::{Response::*, *};
+use ic_cdk_macros::update;
+use types::MAX_CHANNEL_CATEGORIES;
+use utils::text_validation::{validate_channel_category_name, StringLengthValidationError};
+
+#[update]
+#[trace]
+fn create_channel_category(args: Args) -> Response {
+    run_regular_jobs();
+
+    mutate_state(|state| create_channel_category_impl(args, state))
+}
+
+fn create_channel_category_impl(args: Args, state: &mut RuntimeState) -> Response {
+    if state.data.is_frozen() {
+        return CommunityFrozen;
+    }
+
+    let caller = state.env.caller();
+    if let Some(member) = state.data.members.get(caller) {
+        if member.suspended.value {
+            return UserSuspended;
+        }
+
+        if !member.role.can_update_details(&state.data.permissions) {
+            NotAuthorized
+        } else if let Err(error) = validate_channel_category_name(&args.name) {
+            match error {
+                StringLengthValidationError::TooShort(s) => NameTooShort(s),
+                StringLengthValidationError::TooLong(l) => NameTooLong(l),
+            }
+        } else if state.data.channel_categories.count() >= MAX_CHANNEL_CATEGORIES {
+            TooManyCategories(MAX_CHANNEL_CATEGORIES as u32)
+        } else {
+            let now = state.env.now();
+            let rng = state.env.rng();
+
+            if let Some(category_id) =
+                state
+                    .data
+                    .channel_categories
+                    .create(args.name.trim().to_string(), args.collapsed_by_default, rng, now)
+            {
+                handle_activity_notification(state);
+                Success(SuccessResult { category_id })
+            } else {
+                NameTaken
+            }
+        }
+    } else {
+        NotAuthorized
+    }
+}
@@ -33,6 +33,8 @@ fn delete_channel_impl(channel_id: ChannelId, state: &mut RuntimeState) -> Respo
                 if channel_member.role.can_delete_group() {
                     let now = state.env.now();
                     let channel = state.data.channels.delete(channel_id).expect("Channel should exist");
+                    state.data.calendar_events.remove_channel(channel_id);
+                    state.data.channel_categories.remove_channel(&channel_id, now);
 
                     state.data.events.push_event(
                         CommunityEventInternal::ChannelDeleted(Box::new(ChannelDeleted {
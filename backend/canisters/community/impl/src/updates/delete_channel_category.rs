@@ -0,0 +1,35 @@
+use crate::activity_notifications::handle_activity_notification;
+use crate::{mutate_state, run_regular_jobs, RuntimeState};
+use canister_tracing_macros::trace;
+use community_canister::delete_channel_category::{Response::*, *};
+use ic_cdk_macros::update;
+
+#[update]
+#[trace]
+fn delete_channel_category(args: Args) -> Response {
+    run_regular_jobs();
+
+    mutate_state(|state| delete_channel_category_impl(args, state))
+}
+
+fn delete_channel_category_impl(args: Args, state: &mut RuntimeState) -> Response {
+    if state.data.is_frozen() {
+        return CommunityFrozen;
+    }
+
+    let caller = state.env.caller();
+    match state.data.members.get(caller) {
+        Some(m) if m.suspended.value => UserSuspended,
+        Some(m) if m.role.can_update_details(&state.data.permissions) => {
+            let now = state.env.now();
+
+            if state.data.channel_categories.delete(args.category_id, now) {
+                handle_activity_notification(state);
+                Success
+            } else {
+                CategoryNotFound
+            }
+        }
+        _ => NotAuthorized,
+    }
+}
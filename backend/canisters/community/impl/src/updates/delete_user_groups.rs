@@ -23,13 +23,14 @@ fn delete_user_groups_impl(args: Args, state: &mut RuntimeState) -> Response {
         Some(m) if m.role.can_manage_user_groups(&state.data.permissions) => {
             let now = state.env.now();
 
-            let mut updated = false;
+            let mut deleted = Vec::new();
             for user_group_id in args.user_group_ids {
                 if state.data.members.delete_user_group(user_group_id, now) {
-                    updated = true;
+                    deleted.push(user_group_id);
                 }
             }
-            if updated {
+            if !deleted.is_empty() {
+                state.data.sync_channel_audiences(&deleted, now);
                 handle_activity_notification(state);
             }
             Success
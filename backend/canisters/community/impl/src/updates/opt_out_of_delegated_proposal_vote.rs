@@ -0,0 +1,62 @@
+use crate::{mutate_state, run_regular_jobs, RuntimeState};
+use canister_tracing_macros::trace;
+use chat_events::{MessageContentInternal, Reader};
+use community_canister::opt_out_of_delegated_proposal_vote::{Response::*, *};
+use ic_cdk_macros::update;
+
+#[update]
+#[trace]
+fn opt_out_of_delegated_proposal_vote(args: Args) -> Response {
+    run_regular_jobs();
+
+    mutate_state(|state| opt_out_of_delegated_proposal_vote_impl(args, state))
+}
+
+fn opt_out_of_delegated_proposal_vote_impl(args: Args, state: &mut RuntimeState) -> Response {
+    if state.data.is_frozen() {
+        return CommunityFrozen;
+    }
+
+    let caller = state.env.caller();
+
+    let member = match state.data.members.get(caller) {
+        Some(m) => m,
+        None => return UserNotInCommunity,
+    };
+
+    if member.suspended.value {
+        return UserSuspended;
+    }
+
+    let user_id = member.user_id;
+
+    let channel = match state.data.channels.get_mut(&args.channel_id) {
+        Some(c) => c,
+        None => return ChannelNotFound,
+    };
+
+    let min_visible_event_index = match channel.chat.members.get(&user_id) {
+        Some(m) => m.min_visible_event_index(),
+        None => return UserNotInChannel,
+    };
+
+    if !channel
+        .chat
+        .events
+        .visible_main_events_reader(min_visible_event_index)
+        .message_internal(args.message_index.into())
+        .map_or(false, |m| matches!(m.content, MessageContentInternal::GovernanceProposal(_)))
+    {
+        return ProposalMessageNotFound;
+    }
+
+    if channel
+        .chat
+        .proposal_vote_delegations
+        .set_opted_out(&user_id, args.message_index, args.opt_out)
+    {
+        Success
+    } else {
+        NoDelegation
+    }
+}
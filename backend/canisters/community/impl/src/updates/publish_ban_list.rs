@@ -0,0 +1,49 @@
+use crate::activity_notifications::handle_activity_notification;
+use crate::{mutate_state, run_regular_jobs, RuntimeState};
+use canister_tracing_macros::trace;
+use community_canister::publish_ban_list::{Response::*, *};
+use ic_cdk_macros::update;
+use msgpack::serialize_then_unwrap;
+
+#[update]
+#[trace]
+fn publish_ban_list(args: Args) -> Response {
+    run_regular_jobs();
+
+    mutate_state(|state| publish_ban_list_impl(args, state))
+}
+
+// Only public communities can block users, so only they have a ban list to publish
+fn publish_ban_list_impl(args: Args, state: &mut RuntimeState) -> Response {
+    if state.data.is_frozen() {
+        return CommunityFrozen;
+    }
+
+    let caller = state.env.caller();
+    match state.data.members.get(caller) {
+        Some(m) if m.suspended.value => UserSuspended,
+        Some(m) if m.role.can_update_details(&state.data.permissions) => {
+            if args.published && !state.data.is_public {
+                return CommunityNotPublic;
+            }
+
+            // Let any subscribers know that the list they are subscribed to no longer exists
+            let c2c_args = community_canister::c2c_ban_list_updated::Args {
+                blocked: Vec::new(),
+                unblocked: Vec::new(),
+                unpublished: true,
+            };
+            for community_id in state.data.ban_lists.set_published(args.published) {
+                state.data.fire_and_forget_handler.send(
+                    community_id.into(),
+                    "c2c_ban_list_updated_msgpack".to_string(),
+                    serialize_then_unwrap(&c2c_args),
+                );
+            }
+
+            handle_activity_notification(state);
+            Success
+        }
+        _ => NotAuthorized,
+    }
+}
@@ -1,19 +1,109 @@
 use crate::activity_notifications::handle_activity_notification;
-use crate::{mutate_state, run_regular_jobs, RuntimeState};
+use crate::timer_job_types::{MakeTransferJob, TimerJob};
+use crate::{mutate_state, read_state, run_regular_jobs, RuntimeState};
 use canister_tracing_macros::trace;
 use chat_events::{RegisterPollVoteArgs, RegisterPollVoteResult};
 use community_canister::register_poll_vote::{Response::*, *};
+use gated_groups::PollVoteWeightArgs;
 use ic_cdk_macros::update;
+use types::{PollVoteTokenLock, UserId, VoteOperation};
+use utils::consts::MEMO_POLL_VOTE_REFUND;
 
 #[update]
 #[trace]
 async fn register_poll_vote(args: Args) -> Response {
     run_regular_jobs();
 
-    mutate_state(|state| register_poll_vote_impl(args, state))
+    let (weight, token_lock) = match read_state(|state| prepare(&args, state)) {
+        Ok(Some(weight_args)) => {
+            let user_id = weight_args.user_id;
+            match gated_groups::get_poll_vote_weight(weight_args).await {
+                Ok((0, _)) => return NoVotingPower,
+                Ok((weight, token_lock)) => (Some(weight), token_lock.map(|l| (user_id, l))),
+                Err(error) => return InternalError(error),
+            }
+        }
+        Ok(None) => (None, None),
+        Err(response) => return response,
+    };
+
+    mutate_state(|state| register_poll_vote_impl(args, weight, token_lock, state))
 }
 
-fn register_poll_vote_impl(args: Args, state: &mut RuntimeState) -> Response {
+// If the poll is weighted and the user's weight has not yet been captured, returns what is needed
+// to look it up
+fn prepare(args: &Args, state: &RuntimeState) -> Result<Option<PollVoteWeightArgs>, Response> {
+    if state.data.is_frozen() {
+        return Err(CommunityFrozen);
+    }
+
+    let caller = state.env.caller();
+
+    let member = match state.data.members.get(caller) {
+        Some(m) => m,
+        None => return Err(UserNotInCommunity),
+    };
+
+    if member.suspended.value {
+        return Err(UserSuspended);
+    }
+
+    let channel = match state.data.channels.get(&args.channel_id) {
+        Some(c) => c,
+        None => return Err(ChannelNotFound),
+    };
+
+    let channel_member = match channel.chat.members.get(&member.user_id) {
+        Some(m) => m,
+        None => return Err(UserNotInChannel),
+    };
+
+    if !matches!(args.operation, VoteOperation::RegisterVote) {
+        return Ok(None);
+    }
+
+    Ok(channel
+        .chat
+        .events
+        .poll_weight_required(
+            member.user_id,
+            channel_member.min_visible_event_index(),
+            args.thread_root_message_index,
+            args.message_index,
+        )
+        .map(|(weighting, poll_created, poll_end)| PollVoteWeightArgs {
+            weighting,
+            user_id: member.user_id,
+            poll_created,
+            poll_end,
+            this_canister: state.env.canister_id(),
+            now_nanos: state.env.now_nanos(),
+        }))
+}
+
+fn register_poll_vote_impl(
+    args: Args,
+    weight: Option<u128>,
+    token_lock: Option<(UserId, PollVoteTokenLock)>,
+    state: &mut RuntimeState,
+) -> Response {
+    let response = register_poll_vote_inner(args, weight, token_lock.as_ref().map(|(_, l)| l.clone()), state);
+
+    // If the vote wasn't registered then any tokens locked for it must be returned to the user
+    if let Some((user_id, lock)) = token_lock {
+        if !matches!(response, Success(_)) {
+            refund_token_lock(lock, user_id, state);
+        }
+    }
+    response
+}
+
+fn register_poll_vote_inner(
+    args: Args,
+    weight: Option<u128>,
+    token_lock: Option<PollVoteTokenLock>,
+    state: &mut RuntimeState,
+) -> Response {
     if state.data.is_frozen() {
         return CommunityFrozen;
     }
@@ -49,6 +139,9 @@ fn register_poll_vote_impl(args: Args, state: &mut RuntimeState) -> Response {
         thread_root_message_index: args.thread_root_message_index,
         message_index: args.message_index,
         option_index: args.poll_option,
+        ranked_options: args.ranked_options,
+        weight,
+        token_lock,
         operation: args.operation,
         now,
         correlation_id: 0,
@@ -64,5 +157,16 @@ fn register_poll_vote_impl(args: Args, state: &mut RuntimeState) -> Response {
         RegisterPollVoteResult::PollNotFound => PollNotFound,
         RegisterPollVoteResult::OptionIndexOutOfRange => OptionIndexOutOfRange,
         RegisterPollVoteResult::UserCannotChangeVote => UserCannotChangeVote,
+        RegisterPollVoteResult::InvalidRanking => InvalidRanking,
+    }
+}
+
+fn refund_token_lock(lock: PollVoteTokenLock, user_id: UserId, state: &mut RuntimeState) {
+    let now = state.env.now();
+    if let Some(pending_transaction) = lock.refund(user_id, &MEMO_POLL_VOTE_REFUND, state.env.now_nanos()) {
+        state
+            .data
+            .timer_jobs
+            .enqueue_job(TimerJob::MakeTransfer(MakeTransferJob { pending_transaction }), now, now);
     }
 }
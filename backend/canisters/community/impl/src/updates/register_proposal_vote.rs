@@ -1,25 +1,57 @@
 use crate::activity_notifications::handle_activity_notification;
+use crate::timer_job_types::{CastDelegatedProposalVoteJob, TimerJob};
 use crate::{mutate_state, read_state, run_regular_jobs, RuntimeState};
 use canister_tracing_macros::trace;
 use chat_events::{MessageContentInternal, Reader, RecordProposalVoteResult};
 use community_canister::register_proposal_vote::{Response::*, *};
 use ic_cdk_macros::update;
-use types::{CanisterId, ChannelId, ProposalId, UserId};
+use tracing::info;
+use types::{CanisterId, ChannelId, MessageIndex, ProposalId, UserId};
 
 #[update]
 #[trace]
 async fn register_proposal_vote(args: Args) -> Response {
     run_regular_jobs();
 
+    let prepare_result = match read_state(|state| prepare(&args, state)) {
+        Ok(ok) => ok,
+        Err(response) => return response,
+    };
+
+    vote_on_proposal(prepare_result, args).await
+}
+
+// Casts the vote of a member who has delegated their votes to another member who has just voted
+pub(crate) async fn cast_delegated_vote(channel_id: ChannelId, user_id: UserId, message_index: MessageIndex, adopt: bool) {
+    let prepare_result = match read_state(|state| {
+        if state.data.is_frozen() {
+            Err(CommunityFrozen)
+        } else {
+            prepare_vote(channel_id, user_id, message_index, state)
+        }
+    }) {
+        Ok(ok) => ok,
+        Err(_) => return,
+    };
+
+    let args = Args {
+        channel_id,
+        message_index,
+        adopt,
+    };
+    let response = vote_on_proposal(prepare_result, args).await;
+    if !matches!(response, Success) {
+        info!(%user_id, ?channel_id, ?message_index, ?response, "Failed to cast delegated proposal vote");
+    }
+}
+
+async fn vote_on_proposal(prepare_result: PrepareResult, args: Args) -> Response {
     let PrepareResult {
         user_id,
         is_nns,
         governance_canister_id,
         proposal_id,
-    } = match read_state(|state| prepare(&args, state)) {
-        Ok(ok) => ok,
-        Err(response) => return response,
-    };
+    } = prepare_result;
 
     let c2c_args = user_canister::c2c_vote_on_proposal::Args {
         is_nns,
@@ -65,30 +97,43 @@ fn prepare(args: &Args, state: &RuntimeState) -> Result<PrepareResult, Response>
         return Err(UserSuspended);
     }
 
-    let channel = match state.data.channels.get(&args.channel_id) {
+    prepare_vote(args.channel_id, member.user_id, args.message_index, state)
+}
+
+fn prepare_vote(
+    channel_id: ChannelId,
+    user_id: UserId,
+    message_index: MessageIndex,
+    state: &RuntimeState,
+) -> Result<PrepareResult, Response> {
+    let channel = match state.data.channels.get(&channel_id) {
         Some(c) => c,
         None => return Err(ChannelNotFound),
     };
 
-    let channel_member = match channel.chat.members.get(&member.user_id) {
+    let channel_member = match channel.chat.members.get(&user_id) {
         Some(m) => m,
         None => return Err(UserNotInChannel),
     };
 
+    if channel_member.suspended.value {
+        return Err(UserSuspended);
+    }
+
     let min_visible_event_index = channel_member.min_visible_event_index();
 
     if let Some(proposal) = channel
         .chat
         .events
         .visible_main_events_reader(min_visible_event_index)
-        .message_internal(args.message_index.into())
+        .message_internal(message_index.into())
         .and_then(|m| if let MessageContentInternal::GovernanceProposal(p) = &m.content { Some(p) } else { None })
     {
-        if let Some(vote) = proposal.votes.get(&member.user_id) {
+        if let Some(vote) = proposal.votes.get(&user_id) {
             Err(AlreadyVoted(*vote))
         } else {
             Ok(PrepareResult {
-                user_id: member.user_id,
+                user_id,
                 is_nns: proposal.proposal.is_nns(),
                 governance_canister_id: proposal.governance_canister_id,
                 proposal_id: proposal.proposal.id(),
@@ -124,6 +169,7 @@ fn commit(channel_id: ChannelId, user_id: UserId, args: Args, state: &mut Runtim
             if !votes.contains(&args.message_index) {
                 votes.push(args.message_index);
             }
+            enqueue_delegated_votes(channel_id, user_id, args.message_index, args.adopt, state);
             handle_activity_notification(state);
             Success
         }
@@ -131,3 +177,52 @@ fn commit(channel_id: ChannelId, user_id: UserId, args: Args, state: &mut Runtim
         RecordProposalVoteResult::ProposalNotFound => ProposalNotFound,
     }
 }
+
+// Queues up votes on behalf of the members who follow `user_id`'s votes. Once those votes have been
+// cast, the votes of their own followers will be queued up in turn.
+pub(crate) fn enqueue_delegated_votes(
+    channel_id: ChannelId,
+    user_id: UserId,
+    message_index: MessageIndex,
+    adopt: bool,
+    state: &mut RuntimeState,
+) {
+    let Some(channel) = state.data.channels.get(&channel_id) else {
+        return;
+    };
+
+    let topic = match channel
+        .chat
+        .events
+        .main_events_reader()
+        .message_internal(message_index.into())
+        .and_then(|m| {
+            if let MessageContentInternal::GovernanceProposal(p) = &m.content {
+                Some(p.proposal.topic())
+            } else {
+                None
+            }
+        }) {
+        Some(t) => t,
+        None => return,
+    };
+
+    let followers = channel
+        .chat
+        .proposal_vote_delegations
+        .followers(user_id, message_index, topic);
+
+    let now = state.env.now();
+    for follower in followers {
+        state.data.timer_jobs.enqueue_job(
+            TimerJob::CastDelegatedProposalVote(CastDelegatedProposalVoteJob {
+                channel_id,
+                user_id: follower,
+                message_index,
+                adopt,
+            }),
+            now,
+            now,
+        );
+    }
+}
@@ -112,6 +112,7 @@ fn commit(user_id: UserId, block: bool, removed_by: UserId, state: &mut RuntimeS
     if block {
         // Also block the user
         state.data.members.block(user_id);
+        state.data.notify_ban_list_subscribers(vec![user_id], Vec::new());
     }
 
     // Push relevant event
@@ -143,7 +144,7 @@ fn commit(user_id: UserId, block: bool, removed_by: UserId, state: &mut RuntimeS
     );
 }
 
-fn remove_membership_from_user_canister(
+pub(crate) fn remove_membership_from_user_canister(
     user_id: UserId,
     removed_by: UserId,
     blocked: bool,
@@ -1,91 +1,122 @@
 use crate::activity_notifications::handle_activity_notification;
-use crate::{mutate_state, read_state, run_regular_jobs, RuntimeState};
+use crate::timer_job_types::{EscalateChatReportJob, TimerJob};
+use crate::{mutate_state, run_regular_jobs, RuntimeState};
 use canister_tracing_macros::trace;
 use chat_events::Reader;
 use community_canister::report_message::{Response::*, *};
+use group_chat_core::{AddChatReportArgs, AddChatReportResult, AUTO_ESCALATE_REPORTS_AFTER};
 use group_index_canister::c2c_report_message;
 use ic_cdk_macros::update;
-use types::{CanisterId, MultiUserChat, UserId};
+use types::{ChannelMessageReported, MultiUserChat, Notification, UserId};
 
+// Reports are added to the channel's reports inbox to be handled by the channel's moderators, who
+// can then choose to escalate them to the platform moderators. If the channel has no moderators, or
+// they don't handle the report in time, it is escalated automatically.
 #[update]
 #[trace]
-async fn report_message(args: Args) -> Response {
+fn report_message(args: Args) -> Response {
     run_regular_jobs();
 
-    let (c2c_args, group_index_canister) = match read_state(|state| build_c2c_args(&args, state)) {
-        Ok(ok) => ok,
-        Err(response) => return response,
-    };
-
-    match group_index_canister_c2c_client::c2c_report_message(group_index_canister, &c2c_args).await {
-        Ok(result) => {
-            if args.delete {
-                mutate_state(|state| delete_message(&args, c2c_args.reporter, state));
-            }
-
-            match result {
-                c2c_report_message::Response::Success => Success,
-                c2c_report_message::Response::AlreadyReported => AlreadyReported,
-                c2c_report_message::Response::InternalError(error) => InternalError(error),
-            }
-        }
-        Err(err) => InternalError(format!("{err:?}")),
-    }
+    mutate_state(|state| report_message_impl(args, state))
 }
 
-fn build_c2c_args(args: &Args, state: &RuntimeState) -> Result<(c2c_report_message::Args, CanisterId), Response> {
+fn report_message_impl(args: Args, state: &mut RuntimeState) -> Response {
     if state.data.is_frozen() {
-        return Err(CommunityFrozen);
+        return CommunityFrozen;
     }
 
     let caller = state.env.caller();
 
-    if let Some(member) = state.data.members.get(caller) {
-        if member.suspended.value {
-            return Err(UserSuspended);
-        }
+    let user_id = match state.data.members.get(caller) {
+        Some(m) if m.suspended.value => return UserSuspended,
+        Some(m) => m.user_id,
+        None => return UserNotInCommunity,
+    };
+
+    let channel = match state.data.channels.get_mut(&args.channel_id) {
+        Some(c) => c,
+        None => return ChannelNotFound,
+    };
+
+    let chat = &mut channel.chat;
 
-        let user_id = member.user_id;
-
-        if let Some(channel) = state.data.channels.get(&args.channel_id) {
-            let chat = &channel.chat;
-
-            if let Some(channel_member) = chat.members.get(&user_id) {
-                if args.delete && !channel_member.role.can_delete_messages(&chat.permissions) {
-                    return Err(NotAuthorized);
-                }
-
-                if let Some(events_reader) = channel
-                    .chat
-                    .events
-                    .events_reader(channel_member.min_visible_event_index(), args.thread_root_message_index)
-                {
-                    if let Some(message) = events_reader.message(args.message_id.into(), Some(user_id)) {
-                        Ok((
-                            c2c_report_message::Args {
-                                reporter: user_id,
-                                chat_id: MultiUserChat::Channel(state.env.canister_id().into(), args.channel_id),
-                                thread_root_message_index: args.thread_root_message_index,
-                                message,
-                                already_deleted: args.delete,
-                            },
-                            state.data.group_index_canister_id,
-                        ))
-                    } else {
-                        Err(MessageNotFound)
-                    }
-                } else {
-                    Err(MessageNotFound)
-                }
-            } else {
-                Err(UserNotInChannel)
+    let min_visible_event_index = match chat.members.get(&user_id) {
+        Some(m) => {
+            if args.delete && !m.role.can_delete_messages(&chat.permissions) {
+                return NotAuthorized;
             }
-        } else {
-            Err(ChannelNotFound)
+            m.min_visible_event_index()
         }
-    } else {
-        Err(UserNotInCommunity)
+        None => return UserNotInChannel,
+    };
+
+    let message = match chat
+        .events
+        .events_reader(min_visible_event_index, args.thread_root_message_index)
+        .and_then(|events_reader| events_reader.message(args.message_id.into(), Some(user_id)))
+    {
+        Some(m) => m,
+        None => return MessageNotFound,
+    };
+
+    let now = state.env.now();
+
+    match chat.reports.add(AddChatReportArgs {
+        reporter: user_id,
+        thread_root_message_index: args.thread_root_message_index,
+        message_index: message.message_index,
+        message_id: message.message_id,
+        sender: message.sender,
+        now,
+    }) {
+        AddChatReportResult::New(report_id) => {
+            let community_id = state.env.canister_id().into();
+            let report_handlers = chat.report_handlers();
+            let escalate_at = if report_handlers.is_empty() { now } else { now + AUTO_ESCALATE_REPORTS_AFTER };
+
+            // Notify the channel's moderators of the new report
+            let recipients: Vec<UserId> = report_handlers.into_iter().filter(|u| *u != user_id).collect();
+
+            let notification = Notification::ChannelMessageReported(ChannelMessageReported {
+                community_id,
+                channel_id: args.channel_id,
+                report_id,
+                thread_root_message_index: args.thread_root_message_index,
+                message_index: message.message_index,
+                community_name: state.data.name.clone(),
+                channel_name: chat.name.value.clone(),
+                community_avatar_id: state.data.avatar.as_ref().map(|d| d.id),
+                channel_avatar_id: chat.avatar.as_ref().map(|d| d.id),
+            });
+            state.push_notification(recipients, notification);
+
+            // The message is captured now so that the platform moderators see what was reported
+            // even if it is subsequently edited or deleted
+            state.data.timer_jobs.enqueue_job(
+                TimerJob::EscalateChatReport(EscalateChatReportJob {
+                    channel_id: args.channel_id,
+                    report_id,
+                    c2c_args: c2c_report_message::Args {
+                        reporter: user_id,
+                        chat_id: MultiUserChat::Channel(community_id, args.channel_id),
+                        thread_root_message_index: args.thread_root_message_index,
+                        message,
+                        already_deleted: args.delete,
+                    },
+                }),
+                escalate_at,
+                now,
+            );
+        }
+        AddChatReportResult::ExistingPending(_) | AddChatReportResult::ExistingResolved(_) => {}
+        AddChatReportResult::AlreadyReportedByUser => return AlreadyReported,
     }
+
+    if args.delete {
+        delete_message(&args, user_id, state);
+    }
+
+    Success
 }
 
 fn delete_message(args: &Args, reporter: UserId, state: &mut RuntimeState) {
@@ -0,0 +1,163 @@
+use crate::activity_notifications::handle_activity_notification;
+use crate::{mutate_state, read_state, run_regular_jobs, RuntimeState};
+use canister_tracing_macros::trace;
+use chat_events::{DeleteMessageResult, Reader};
+use community_canister::resolve_report::{Response::*, *};
+use group_chat_core::{DeleteMessagesResult, RemoveMemberResult, TimeoutMemberResult};
+use group_index_canister::c2c_report_message;
+use ic_cdk_macros::update;
+use types::{CanisterId, ChatReportAction, MultiUserChat, UserId};
+
+#[update]
+#[trace]
+async fn resolve_report(args: Args) -> Response {
+    run_regular_jobs();
+
+    let PrepareResult { resolved_by, escalation } = match read_state(|state| prepare(&args, state)) {
+        Ok(ok) => ok,
+        Err(response) => return response,
+    };
+
+    if let Some((c2c_args, group_index_canister_id)) = escalation {
+        if let Err(error) = group_index_canister_c2c_client::c2c_report_message(group_index_canister_id, &c2c_args).await {
+            return InternalError(format!("{error:?}"));
+        }
+    }
+
+    mutate_state(|state| commit(args, resolved_by, state))
+}
+
+struct PrepareResult {
+    resolved_by: UserId,
+    escalation: Option<(c2c_report_message::Args, CanisterId)>,
+}
+
+fn prepare(args: &Args, state: &RuntimeState) -> Result<PrepareResult, Response> {
+    if state.data.is_frozen() {
+        return Err(CommunityFrozen);
+    }
+
+    let caller = state.env.caller();
+
+    let user_id = match state.data.members.get(caller) {
+        Some(m) if m.suspended.value => return Err(UserSuspended),
+        Some(m) => m.user_id,
+        None => return Err(UserNotInCommunity),
+    };
+
+    let channel = match state.data.channels.get(&args.channel_id) {
+        Some(c) => c,
+        None => return Err(ChannelNotFound),
+    };
+
+    let chat = &channel.chat;
+
+    let member = match chat.members.get(&user_id) {
+        Some(m) => m,
+        None => return Err(UserNotInChannel),
+    };
+
+    if !member.role.can_handle_reports() {
+        return Err(NotAuthorized);
+    }
+
+    let report = match chat.reports.get(args.report_id) {
+        Some(r) if r.resolution.is_some() => return Err(AlreadyResolved),
+        Some(r) => r,
+        None => return Err(ReportNotFound),
+    };
+
+    // Escalating sends the message to the platform moderators, with this moderator as the reporter
+    let escalation = if matches!(args.action, ChatReportAction::EscalateToPlatform) {
+        let message = chat
+            .events
+            .events_reader(member.min_visible_event_index(), report.thread_root_message_index)
+            .and_then(|events_reader| events_reader.message(report.message_id.into(), Some(user_id)))
+            .ok_or(MessageNotFound)?;
+
+        Some((
+            c2c_report_message::Args {
+                reporter: user_id,
+                chat_id: MultiUserChat::Channel(state.env.canister_id().into(), args.channel_id),
+                thread_root_message_index: report.thread_root_message_index,
+                message,
+                already_deleted: false,
+            },
+            state.data.group_index_canister_id,
+        ))
+    } else {
+        None
+    };
+
+    Ok(PrepareResult {
+        resolved_by: user_id,
+        escalation,
+    })
+}
+
+fn commit(args: Args, resolved_by: UserId, state: &mut RuntimeState) -> Response {
+    let now = state.env.now();
+
+    let channel = match state.data.channels.get_mut(&args.channel_id) {
+        Some(c) => c,
+        None => return ChannelNotFound,
+    };
+
+    let chat = &mut channel.chat;
+
+    let report = match chat.reports.get(args.report_id) {
+        Some(r) if r.resolution.is_some() => return AlreadyResolved,
+        Some(r) => r.clone(),
+        None => return ReportNotFound,
+    };
+
+    match args.action {
+        ChatReportAction::DeleteMessage => {
+            match chat.delete_messages(
+                resolved_by,
+                report.thread_root_message_index,
+                vec![report.message_id],
+                false,
+                now,
+            ) {
+                DeleteMessagesResult::Success(results) => match results[0].1 {
+                    DeleteMessageResult::Success(_) | DeleteMessageResult::AlreadyDeleted => {}
+                    DeleteMessageResult::NotFound => return MessageNotFound,
+                    DeleteMessageResult::NotAuthorized => return NotAuthorized,
+                },
+                DeleteMessagesResult::MessageNotFound => return MessageNotFound,
+                DeleteMessagesResult::UserNotInGroup => return UserNotInChannel,
+                DeleteMessagesResult::UserSuspended => return UserSuspended,
+            }
+        }
+        ChatReportAction::TimeoutMember(duration) => match chat.timeout_member(resolved_by, report.sender, duration, now) {
+            TimeoutMemberResult::Success => {}
+            TimeoutMemberResult::InvalidDuration => return InvalidTimeout,
+            TimeoutMemberResult::TargetUserNotInGroup => return TargetUserNotInChannel,
+            TimeoutMemberResult::UserNotInGroup => return UserNotInChannel,
+            TimeoutMemberResult::UserSuspended => return UserSuspended,
+            TimeoutMemberResult::NotAuthorized | TimeoutMemberResult::CannotTimeoutSelf => return NotAuthorized,
+        },
+        // Removes the sender from the channel but not from the community
+        ChatReportAction::RemoveMember => match chat.remove_member(resolved_by, report.sender, false, now) {
+            RemoveMemberResult::Success => {
+                if let Some(member) = state.data.members.get_by_user_id_mut(&report.sender) {
+                    member.leave(args.channel_id, now);
+                }
+            }
+            RemoveMemberResult::TargetUserNotInGroup => return TargetUserNotInChannel,
+            RemoveMemberResult::UserNotInGroup => return UserNotInChannel,
+            RemoveMemberResult::UserSuspended => return UserSuspended,
+            RemoveMemberResult::NotAuthorized | RemoveMemberResult::CannotRemoveSelf => return NotAuthorized,
+        },
+        ChatReportAction::Dismiss | ChatReportAction::EscalateToPlatform => {}
+    }
+
+    chat.reports.resolve(args.report_id, args.action, resolved_by, now);
+
+    if matches!(args.action, ChatReportAction::DeleteMessage | ChatReportAction::RemoveMember) {
+        handle_activity_notification(state);
+    }
+
+    Success
+}
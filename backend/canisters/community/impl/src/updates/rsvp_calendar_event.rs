@@ -0,0 +1,68 @@
+use crate::activity_notifications::handle_activity_notification;
+use crate::{mutate_state, run_regular_jobs, RuntimeState};
+use canister_tracing_macros::trace;
+use chat_events::{RegisterCalendarEventRsvpArgs, RegisterCalendarEventRsvpResult};
+use community_canister::rsvp_calendar_event::{Response::*, *};
+use ic_cdk_macros::update;
+
+#[update]
+#[trace]
+fn rsvp_calendar_event(args: Args) -> Response {
+    run_regular_jobs();
+
+    mutate_state(|state| rsvp_calendar_event_impl(args, state))
+}
+
+fn rsvp_calendar_event_impl(args: Args, state: &mut RuntimeState) -> Response {
+    if state.data.is_frozen() {
+        return CommunityFrozen;
+    }
+
+    let caller = state.env.caller();
+
+    let member = match state.data.members.get(caller) {
+        Some(m) => m,
+        None => return UserNotInCommunity,
+    };
+
+    if member.suspended.value {
+        return UserSuspended;
+    }
+
+    let channel = match state.data.channels.get_mut(&args.channel_id) {
+        Some(c) => c,
+        None => return ChannelNotFound,
+    };
+
+    let channel_member = match channel.chat.members.get(&member.user_id) {
+        Some(m) => m,
+        None => return UserNotInChannel,
+    };
+
+    let now = state.env.now();
+    let user_id = member.user_id;
+    let min_visible_event_index = channel_member.min_visible_event_index();
+
+    let result = channel
+        .chat
+        .events
+        .register_calendar_event_rsvp(RegisterCalendarEventRsvpArgs {
+            user_id,
+            min_visible_event_index,
+            thread_root_message_index: args.thread_root_message_index,
+            message_index: args.message_index,
+            rsvp: args.rsvp,
+            now,
+        });
+
+    match result {
+        RegisterCalendarEventRsvpResult::Success(rsvps) => {
+            handle_activity_notification(state);
+            Success(rsvps)
+        }
+        RegisterCalendarEventRsvpResult::SuccessNoChange(rsvps) => Success(rsvps),
+        RegisterCalendarEventRsvpResult::EventNotFound => EventNotFound,
+        RegisterCalendarEventRsvpResult::EventStarted => EventStarted,
+        RegisterCalendarEventRsvpResult::EventFull => EventFull,
+    }
+}
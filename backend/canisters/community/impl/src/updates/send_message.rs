@@ -1,7 +1,10 @@
 use crate::activity_notifications::handle_activity_notification;
+use crate::model::calendar_events::CalendarEventEntry;
 use crate::model::members::CommunityMembers;
 use crate::model::user_groups::UserGroup;
-use crate::timer_job_types::{DeleteFileReferencesJob, EndPollJob, RefundPrizeJob, RemoveExpiredEventsJob, TimerJob};
+use crate::timer_job_types::{
+    DeleteFileReferencesJob, EndPollJob, RefundPrizeJob, RemoveExpiredEventsJob, SendCalendarEventReminderJob, TimerJob,
+};
 use crate::{mutate_state, run_regular_jobs, RuntimeState};
 use canister_api_macros::update_candid_and_msgpack;
 use canister_timer_jobs::TimerJobs;
@@ -54,6 +57,26 @@ fn send_message_impl(args: Args, state: &mut RuntimeState) -> Response {
     if let Some(channel) = state.data.channels.get_mut(&args.channel_id) {
         let user_id = member.user_id;
 
+        if !channel.is_permitted_by_user_groups(&channel.user_group_permissions.send_messages, user_id, &state.data.members) {
+            return NotAuthorized;
+        }
+
+        if let Some(forum) = &channel.forum {
+            if let Some(root_message_index) = args.thread_root_message_index {
+                if forum.is_locked(&root_message_index) {
+                    return ForumPostLocked;
+                }
+            } else if let Some(details) = &args.forum_post {
+                if let Err(error) = forum.validate_post(details) {
+                    return InvalidRequest(error);
+                }
+            } else {
+                return InvalidRequest("Messages in forum channels must be posts with a title".to_string());
+            }
+        } else if args.forum_post.is_some() {
+            return InvalidRequest("Channel is not a forum".to_string());
+        }
+
         let user_groups_mentioned = extract_user_groups_mentioned(&args.content, &state.data.members);
         let mentioned: Vec<_> = args
             .mentioned
@@ -80,6 +103,10 @@ fn send_message_impl(args: Args, state: &mut RuntimeState) -> Response {
                 let message_index = result.message_event.event.message_index;
                 let expires_at = result.message_event.expires_at;
 
+                if let Some((forum, details)) = channel.forum.as_mut().zip(args.forum_post) {
+                    forum.add_post(message_index, details);
+                }
+
                 let mut is_next_event_to_expire = false;
                 if let Some(expiry) = expires_at {
                     is_next_event_to_expire = state.data.next_event_expiry.map_or(true, |ex| expiry < ex);
@@ -97,6 +124,19 @@ fn send_message_impl(args: Args, state: &mut RuntimeState) -> Response {
                     &mut state.data.timer_jobs,
                 );
 
+                if let MessageContent::CalendarEvent(e) = &result.message_event.event.content {
+                    state.data.calendar_events.add(
+                        CalendarEventEntry {
+                            channel_id: args.channel_id,
+                            thread_root_message_index: args.thread_root_message_index,
+                            message_index,
+                            start: e.details.start,
+                            end: e.details.end,
+                        },
+                        now,
+                    );
+                }
+
                 // Exclude suspended members from notification
                 let users_to_notify: Vec<UserId> = result
                     .users_to_notify
@@ -177,6 +217,20 @@ fn register_timer_jobs(
         }
     }
 
+    if let MessageContent::CalendarEvent(e) = &message_event.event.content {
+        if let Some(reminder_time) = e.details.reminder_time(now) {
+            timer_jobs.enqueue_job(
+                TimerJob::SendCalendarEventReminder(SendCalendarEventReminderJob {
+                    channel_id,
+                    thread_root_message_index,
+                    message_index: message_event.event.message_index,
+                }),
+                reminder_time,
+                now,
+            );
+        }
+    }
+
     let files = message_event.event.content.blob_references();
     if !files.is_empty() {
         if let Some(expiry) = message_event.expires_at {
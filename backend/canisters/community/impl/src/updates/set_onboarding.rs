@@ -0,0 +1,54 @@
+use crate::activity_notifications::handle_activity_notification;
+use crate::{mutate_state, run_regular_jobs, RuntimeState};
+use canister_tracing_macros::trace;
+use community_canister::set_onboarding::{Response::*, *};
+use ic_cdk_macros::update;
+use types::Timestamped;
+
+#[update]
+#[trace]
+fn set_onboarding(args: Args) -> Response {
+    run_regular_jobs();
+
+    mutate_state(|state| set_onboarding_impl(args, state))
+}
+
+fn set_onboarding_impl(args: Args, state: &mut RuntimeState) -> Response {
+    if state.data.is_frozen() {
+        return CommunityFrozen;
+    }
+
+    let caller = state.env.caller();
+    if let Some(member) = state.data.members.get(caller) {
+        if member.suspended.value {
+            return UserSuspended;
+        }
+
+        if !member.role.can_update_details(&state.data.permissions) {
+            return NotAuthorized;
+        }
+
+        if let Some(onboarding) = &args.onboarding {
+            if let Err(reason) = onboarding.validate() {
+                return InvalidOnboarding(reason);
+            }
+            if let Some(user_group_id) = onboarding
+                .user_group_ids()
+                .find(|id| state.data.members.get_user_group(*id).is_none())
+            {
+                return UserGroupNotFound(user_group_id);
+            }
+            if let Some(channel_id) = onboarding.channel_ids().find(|id| state.data.channels.get(id).is_none()) {
+                return ChannelNotFound(channel_id);
+            }
+        }
+
+        let now = state.env.now();
+        state.data.onboarding = Timestamped::new(args.onboarding, now);
+
+        handle_activity_notification(state);
+        Success
+    } else {
+        NotAuthorized
+    }
+}
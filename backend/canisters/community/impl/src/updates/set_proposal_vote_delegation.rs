@@ -0,0 +1,64 @@
+use crate::{mutate_state, run_regular_jobs, RuntimeState};
+use canister_tracing_macros::trace;
+use community_canister::set_proposal_vote_delegation::{Response::*, *};
+use ic_cdk_macros::update;
+use types::GroupSubtype;
+
+#[update]
+#[trace]
+fn set_proposal_vote_delegation(args: Args) -> Response {
+    run_regular_jobs();
+
+    mutate_state(|state| set_proposal_vote_delegation_impl(args, state))
+}
+
+fn set_proposal_vote_delegation_impl(args: Args, state: &mut RuntimeState) -> Response {
+    if state.data.is_frozen() {
+        return CommunityFrozen;
+    }
+
+    let caller = state.env.caller();
+
+    let member = match state.data.members.get(caller) {
+        Some(m) => m,
+        None => return UserNotInCommunity,
+    };
+
+    if member.suspended.value {
+        return UserSuspended;
+    }
+
+    let user_id = member.user_id;
+
+    let channel = match state.data.channels.get_mut(&args.channel_id) {
+        Some(c) => c,
+        None => return ChannelNotFound,
+    };
+
+    if channel.chat.members.get(&user_id).is_none() {
+        return UserNotInChannel;
+    }
+
+    if !matches!(channel.chat.subtype.value, Some(GroupSubtype::GovernanceProposals(_))) {
+        return NotProposalsChannel;
+    }
+
+    match args.delegate {
+        Some(delegate) if delegate == user_id => CannotDelegateToSelf,
+        Some(delegate) => {
+            if channel.chat.members.get(&delegate).is_none() {
+                return DelegateNotInChannel;
+            }
+            let now = state.env.now();
+            channel
+                .chat
+                .proposal_vote_delegations
+                .set(user_id, delegate, args.topics, now);
+            Success
+        }
+        None => {
+            channel.chat.proposal_vote_delegations.remove(&user_id);
+            Success
+        }
+    }
+}
@@ -0,0 +1,41 @@
+use crate::{mutate_state, run_regular_jobs, RuntimeState};
+use canister_tracing_macros::trace;
+use community_canister::set_typing::{Response::*, *};
+use group_chat_core::SetTypingResult;
+use ic_cdk_macros::update;
+
+#[update]
+#[trace]
+fn set_typing(args: Args) -> Response {
+    run_regular_jobs();
+
+    mutate_state(|state| set_typing_impl(args, state))
+}
+
+fn set_typing_impl(args: Args, state: &mut RuntimeState) -> Response {
+    if state.data.is_frozen() {
+        return CommunityFrozen;
+    }
+
+    let caller = state.env.caller();
+    let now = state.env.now();
+
+    let user_id = match state.data.members.get(caller) {
+        Some(member) if member.suspended.value => return UserSuspended,
+        Some(member) => member.user_id,
+        None => return UserNotInCommunity,
+    };
+
+    if let Some(channel) = state.data.channels.get_mut(&args.channel_id) {
+        match channel
+            .chat
+            .set_typing(user_id, args.thread_root_message_index, args.typing, now)
+        {
+            SetTypingResult::Success => Success,
+            SetTypingResult::UserNotInGroup => UserNotInChannel,
+            SetTypingResult::UserSuspended => UserSuspended,
+        }
+    } else {
+        ChannelNotFound
+    }
+}
@@ -0,0 +1,77 @@
+use crate::activity_notifications::handle_activity_notification;
+use crate::updates::c2c_join_channel::join_channel_unchecked;
+use crate::{mutate_state, run_regular_jobs, RuntimeState};
+use canister_tracing_macros::trace;
+use community_canister::submit_onboarding_answers::{Response::*, *};
+use ic_cdk_macros::update;
+use std::collections::HashSet;
+
+#[update]
+#[trace]
+fn submit_onboarding_answers(args: Args) -> Response {
+    run_regular_jobs();
+
+    mutate_state(|state| submit_onboarding_answers_impl(args, state))
+}
+
+fn submit_onboarding_answers_impl(args: Args, state: &mut RuntimeState) -> Response {
+    if state.data.is_frozen() {
+        return CommunityFrozen;
+    }
+
+    let caller = state.env.caller();
+    let user_id = match state.data.members.get(caller) {
+        Some(m) if m.suspended.value => return UserSuspended,
+        Some(m) => m.user_id,
+        None => return UserNotInCommunity,
+    };
+
+    let onboarding = match &state.data.onboarding.value {
+        Some(o) => o,
+        None => return NoOnboarding,
+    };
+
+    let (user_group_ids, channel_ids) = match onboarding.chosen_options(&args.answers) {
+        Some(options) => {
+            let user_group_ids: HashSet<_> = options.iter().flat_map(|o| o.user_groups.iter().copied()).collect();
+            let channel_ids: HashSet<_> = options.iter().flat_map(|o| o.channels.iter().copied()).collect();
+            (user_group_ids, channel_ids)
+        }
+        None => return InvalidAnswers,
+    };
+
+    let now = state.env.now();
+    let onboarding_updated = state.data.onboarding.timestamp;
+
+    // Answers can only be submitted once, unless the onboarding questions are later updated
+    if let Some(member) = state.data.members.get_mut(caller) {
+        if !member.answer_onboarding(onboarding_updated, now) {
+            return AlreadyAnswered;
+        }
+    }
+
+    for user_group_id in user_group_ids.iter() {
+        state
+            .data
+            .members
+            .update_user_group(*user_group_id, None, vec![user_id], Vec::new(), now);
+    }
+
+    let user_group_ids: Vec<_> = user_group_ids.into_iter().collect();
+    state.data.sync_channel_audiences(&user_group_ids, now);
+
+    // Channels chosen by the admins are joined even if they are private, but channels with an
+    // access gate must still be joined manually so that the gate can be checked
+    if let Some(member) = state.data.members.get_mut(caller) {
+        for channel_id in channel_ids {
+            if let Some(channel) = state.data.channels.get_mut(&channel_id) {
+                if channel.chat.gate.is_none() {
+                    join_channel_unchecked(channel, member, state.data.is_public, now);
+                }
+            }
+        }
+    }
+
+    handle_activity_notification(state);
+    Success
+}
@@ -0,0 +1,69 @@
+use crate::activity_notifications::handle_activity_notification;
+use crate::{mutate_state, read_state, run_regular_jobs, RuntimeState};
+use canister_tracing_macros::trace;
+use community_canister::subscribe_to_ban_list::{Response::*, *};
+use ic_cdk_macros::update;
+use types::{Empty, UserId, MAX_BAN_LIST_SUBSCRIPTIONS};
+
+#[update]
+#[trace]
+async fn subscribe_to_ban_list(args: Args) -> Response {
+    run_regular_jobs();
+
+    if let Err(response) = read_state(|state| prepare(&args, state)) {
+        return response;
+    }
+
+    match community_canister_c2c_client::c2c_subscribe_to_ban_list(args.community_id.into(), &Empty {}).await {
+        Ok(community_canister::c2c_subscribe_to_ban_list::Response::Success(result)) => {
+            mutate_state(|state| commit(args, result.blocked_users, state));
+            Success
+        }
+        Ok(community_canister::c2c_subscribe_to_ban_list::Response::BanListNotPublished)
+        | Ok(community_canister::c2c_subscribe_to_ban_list::Response::CommunityFrozen) => BanListNotPublished,
+        Ok(community_canister::c2c_subscribe_to_ban_list::Response::TooManySubscribers(max)) => TooManySubscribers(max),
+        Ok(community_canister::c2c_subscribe_to_ban_list::Response::NotAuthorized) => NotAuthorized,
+        Ok(community_canister::c2c_subscribe_to_ban_list::Response::InternalError(error)) => InternalError(error),
+        Err(error) => InternalError(format!("{error:?}")),
+    }
+}
+
+fn prepare(args: &Args, state: &RuntimeState) -> Result<(), Response> {
+    if state.data.is_frozen() {
+        return Err(CommunityFrozen);
+    }
+
+    let caller = state.env.caller();
+    match state.data.members.get(caller) {
+        Some(m) if m.suspended.value => Err(UserSuspended),
+        Some(m) if m.role.can_update_details(&state.data.permissions) => {
+            if args.community_id == state.env.canister_id().into() {
+                Err(CannotSubscribeToSelf)
+            } else if state.data.ban_lists.is_subscribed(&args.community_id) {
+                Err(AlreadySubscribed)
+            } else if state.data.ban_lists.subscription_count() >= MAX_BAN_LIST_SUBSCRIPTIONS {
+                Err(TooManySubscriptions(MAX_BAN_LIST_SUBSCRIPTIONS as u32))
+            } else {
+                Ok(())
+            }
+        }
+        _ => Err(NotAuthorized),
+    }
+}
+
+fn commit(args: Args, blocked_users: Vec<UserId>, state: &mut RuntimeState) {
+    let now = state.env.now();
+
+    if args.remove_existing_members {
+        state
+            .data
+            .remove_members_banned_by_list(args.community_id, blocked_users.clone(), now);
+    }
+
+    state
+        .data
+        .ban_lists
+        .subscribe(args.community_id, args.remove_existing_members, blocked_users, now);
+
+    handle_activity_notification(state);
+}
@@ -36,6 +36,7 @@ fn unblock_user_impl(args: Args, state: &mut RuntimeState) -> Response {
             let now = state.env.now();
 
             state.data.members.unblock(&args.user_id);
+            state.data.notify_ban_list_subscribers(Vec::new(), vec![args.user_id]);
 
             let event = UsersUnblocked {
                 user_ids: vec![args.user_id],
@@ -0,0 +1,42 @@
+use crate::activity_notifications::handle_activity_notification;
+use crate::{mutate_state, run_regular_jobs, RuntimeState};
+use canister_tracing_macros::trace;
+use community_canister::unsubscribe_from_ban_list::{Response::*, *};
+use ic_cdk_macros::update;
+use msgpack::serialize_then_unwrap;
+use types::Empty;
+
+#[update]
+#[trace]
+fn unsubscribe_from_ban_list(args: Args) -> Response {
+    run_regular_jobs();
+
+    mutate_state(|state| unsubscribe_from_ban_list_impl(args, state))
+}
+
+// Users who were kept out by the list are able to join again, but members who were removed are not
+// re-added
+fn unsubscribe_from_ban_list_impl(args: Args, state: &mut RuntimeState) -> Response {
+    if state.data.is_frozen() {
+        return CommunityFrozen;
+    }
+
+    let caller = state.env.caller();
+    match state.data.members.get(caller) {
+        Some(m) if m.suspended.value => UserSuspended,
+        Some(m) if m.role.can_update_details(&state.data.permissions) => {
+            if state.data.ban_lists.unsubscribe(&args.community_id) {
+                state.data.fire_and_forget_handler.send(
+                    args.community_id.into(),
+                    "c2c_unsubscribe_from_ban_list_msgpack".to_string(),
+                    serialize_then_unwrap(Empty {}),
+                );
+                handle_activity_notification(state);
+                Success
+            } else {
+                NotSubscribed
+            }
+        }
+        _ => NotAuthorized,
+    }
+}
@@ -4,6 +4,7 @@ use canister_tracing_macros::trace;
 use community_canister::update_channel::{Response::*, *};
 use group_chat_core::UpdateResult;
 use ic_cdk_macros::update;
+use types::{validate_forum_tags, AccessGate, OptionUpdate, Timestamped};
 
 #[update]
 #[trace]
@@ -27,6 +28,42 @@ fn update_channel_impl(mut args: Args, state: &mut RuntimeState) -> Response {
     }
 
     if let Some(channel) = state.data.channels.get_mut(&args.channel_id) {
+        if let Some(tags) = &args.forum_tags {
+            if channel.forum.is_none() {
+                return ChannelNotForum;
+            } else if let Err(error) = validate_forum_tags(tags) {
+                return InvalidForumTags(error);
+            }
+        }
+
+        let mut user_group_ids: Vec<u32> = Vec::new();
+        if let Some(permissions) = &args.user_group_permissions {
+            user_group_ids.extend(permissions.user_group_ids());
+        }
+        if let Some(audience) = &args.audience_user_groups {
+            user_group_ids.extend(audience.iter().copied());
+        }
+        if let OptionUpdate::SetToSome(AccessGate::UserGroup(gate)) = &args.gate {
+            user_group_ids.push(gate.user_group_id);
+        }
+        if let Some(user_group_id) = user_group_ids
+            .into_iter()
+            .find(|id| state.data.members.get_user_group(*id).is_none())
+        {
+            return UserGroupNotFound(user_group_id);
+        }
+
+        // Channels with an audience have their membership managed by the audience user groups,
+        // which only makes sense for private channels
+        let will_be_public = args.public.unwrap_or(channel.chat.is_public.value);
+        let audience = args
+            .audience_user_groups
+            .as_ref()
+            .unwrap_or(&channel.audience_user_groups.value);
+        if will_be_public && !audience.is_empty() {
+            return AudienceRequiresPrivateChannel;
+        }
+
         let caller = state.env.caller();
 
         if let Some(member) = state.data.members.get(caller) {
@@ -44,12 +81,30 @@ fn update_channel_impl(mut args: Args, state: &mut RuntimeState) -> Response {
                 now,
             ) {
                 UpdateResult::Success(result) => {
+                    if let Some((forum, tags)) = channel.forum.as_mut().zip(args.forum_tags) {
+                        forum.set_tags(tags, now);
+                    }
+
+                    if let Some(user_group_permissions) = args.user_group_permissions {
+                        channel.user_group_permissions = Timestamped::new(user_group_permissions, now);
+                    }
+
+                    let mut audience_to_sync = Vec::new();
+                    if let Some(audience_user_groups) = args.audience_user_groups {
+                        audience_to_sync = audience_user_groups.clone();
+                        channel.audience_user_groups = Timestamped::new(audience_user_groups, now);
+                    }
+
                     if result.newly_public && channel.chat.gate.is_none() {
                         for m in state.data.members.iter_mut() {
                             join_channel_unchecked(channel, m, true, now);
                         }
                     }
 
+                    if !audience_to_sync.is_empty() {
+                        state.data.sync_channel_audiences(&audience_to_sync, now);
+                    }
+
                     handle_activity_notification(state);
                     SuccessV2(SuccessResult {
                         rules_version: result.rules_version,
@@ -0,0 +1,62 @@
+use crate::activity_notifications::handle_activity_notification;
+use crate::model::channel_categories::UpdateChannelCategoryResult;
+use crate::{mutate_state, run_regular_jobs, RuntimeState};
+use canister_tracing_macros::trace;
+use community_canister::update_channel_category::{Response::*, *};
+use ic_cdk_macros::update;
+use utils::text_validation::{validate_channel_category_name, StringLengthValidationError};
+
+#[update]
+#[trace]
+fn update_channel_category(args: Args) -> Response {
+    run_regular_jobs();
+
+    mutate_state(|state| update_channel_category_impl(args, state))
+}
+
+fn update_channel_category_impl(args: Args, state: &mut RuntimeState) -> Response {
+    if state.data.is_frozen() {
+        return CommunityFrozen;
+    }
+
+    let caller = state.env.caller();
+    if let Some(member) = state.data.members.get(caller) {
+        if member.suspended.value {
+            return UserSuspended;
+        }
+
+        if !member.role.can_update_details(&state.data.permissions) {
+            return NotAuthorized;
+        }
+
+        if let Some(Err(error)) = args.name.as_ref().map(|n| validate_channel_category_name(n)) {
+            return match error {
+                StringLengthValidationError::TooShort(s) => NameTooShort(s),
+                StringLengthValidationError::TooLong(l) => NameTooLong(l),
+            };
+        }
+
+        if let Some(channel_id) = args.channels.iter().flatten().find(|c| state.data.channels.get(c).is_none()) {
+            return ChannelNotFound(*channel_id);
+        }
+
+        let now = state.env.now();
+
+        match state.data.channel_categories.update(
+            args.category_id,
+            args.name.map(|n| n.trim().to_string()),
+            args.collapsed_by_default,
+            args.channels,
+            now,
+        ) {
+            UpdateChannelCategoryResult::Success => {
+                handle_activity_notification(state);
+                Success
+            }
+            UpdateChannelCategoryResult::NotFound => CategoryNotFound,
+            UpdateChannelCategoryResult::NameTaken => NameTaken,
+        }
+    } else {
+        NotAuthorized
+    }
+}
@@ -9,7 +9,7 @@ use tracing::error;
 use types::{
     AccessGate, AvatarChanged, BannerChanged, CanisterId, CommunityId, CommunityPermissions, CommunityPermissionsChanged,
     Document, GroupDescriptionChanged, GroupGateUpdated, GroupNameChanged, GroupRulesChanged, GroupVisibilityChanged,
-    OptionalCommunityPermissions, PrimaryLanguageChanged, Timestamped, UserId,
+    OptionUpdate, OptionalCommunityPermissions, PrimaryLanguageChanged, Timestamped, UserId,
 };
 use utils::document_validation::{validate_avatar, validate_banner};
 use utils::text_validation::{
@@ -154,6 +154,11 @@ fn prepare(args: &Args, state: &RuntimeState) -> Result<PrepareResult, Response>
         }
     }
 
+    // User group gates are only supported on channels
+    if matches!(args.gate, OptionUpdate::SetToSome(AccessGate::UserGroup(_))) {
+        return Err(InvalidGate);
+    }
+
     if let Some(member) = state.data.members.get(caller) {
         if member.suspended.value {
             return Err(UserSuspended);
@@ -0,0 +1,80 @@
+use crate::activity_notifications::handle_activity_notification;
+use crate::{mutate_state, run_regular_jobs, RuntimeState};
+use canister_tracing_macros::trace;
+use chat_events::Reader;
+use community_canister::update_forum_post::{Response::*, *};
+use ic_cdk_macros::update;
+
+#[update]
+#[trace]
+fn update_forum_post(args: Args) -> Response {
+    run_regular_jobs();
+
+    mutate_state(|state| update_forum_post_impl(args, state))
+}
+
+fn update_forum_post_impl(args: Args, state: &mut RuntimeState) -> Response {
+    if state.data.is_frozen() {
+        return CommunityFrozen;
+    }
+
+    let caller = state.env.caller();
+
+    let member = match state.data.members.get(caller) {
+        Some(m) => m,
+        None => return UserNotInCommunity,
+    };
+
+    if member.suspended.value {
+        return UserSuspended;
+    }
+
+    let channel = match state.data.channels.get_mut(&args.channel_id) {
+        Some(c) => c,
+        None => return ChannelNotFound,
+    };
+
+    let channel_member = match channel.chat.members.get(&member.user_id) {
+        Some(m) => m,
+        None => return UserNotInChannel,
+    };
+
+    let forum = match channel.forum.as_mut() {
+        Some(f) => f,
+        None => return ChannelNotForum,
+    };
+
+    let sender = match channel
+        .chat
+        .events
+        .visible_main_events_reader(channel_member.min_visible_event_index())
+        .message_internal(args.message_index.into())
+        .filter(|m| m.deleted_by.is_none())
+        .map(|m| m.sender)
+    {
+        Some(s) => s,
+        None => return PostNotFound,
+    };
+
+    let post = match forum.get_mut(&args.message_index) {
+        Some(p) => p,
+        None => return PostNotFound,
+    };
+
+    // Authors can mark their own posts as resolved, but only moderators can lock posts or
+    // change the resolved status of other users' posts
+    let can_moderate = channel_member.role.can_delete_messages(&channel.chat.permissions);
+    if (args.locked.is_some() || (args.resolved.is_some() && sender != member.user_id)) && !can_moderate {
+        return NotAuthorized;
+    }
+
+    if let Some(resolved) = args.resolved {
+        post.resolved = resolved;
+    }
+    if let Some(locked) = args.locked {
+        post.locked = locked;
+    }
+
+    handle_activity_notification(state);
+    Success
+}
@@ -40,6 +40,7 @@ fn update_user_group_impl(args: Args, state: &mut RuntimeState) -> Response {
                 .members
                 .update_user_group(args.user_group_id, args.name, args.users_to_add, args.users_to_remove, now)
             {
+                state.data.sync_channel_audiences(&[args.user_group_id], now);
                 handle_activity_notification(state);
                 Success
             } else {
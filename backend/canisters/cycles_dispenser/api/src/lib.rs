@@ -1,3 +1,7 @@
+use candid::CandidType;
+use serde::{Deserialize, Serialize};
+use types::{CanisterId, Cycles, Milliseconds, UserId};
+
 mod lifecycle;
 mod queries;
 mod updates;
@@ -5,3 +9,11 @@ mod updates;
 pub use lifecycle::*;
 pub use queries::*;
 pub use updates::*;
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct AlertConfig {
+    pub user_index_canister_id: CanisterId,
+    pub recipients: Vec<UserId>,
+    pub min_runway: Milliseconds,
+    pub max_canister_burn_rate_per_day: Option<Cycles>,
+}
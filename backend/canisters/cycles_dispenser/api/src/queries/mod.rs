@@ -1 +1 @@
-
+pub mod runway;
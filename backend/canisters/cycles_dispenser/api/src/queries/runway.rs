@@ -0,0 +1,30 @@
+use candid::CandidType;
+use ic_ledger_types::Tokens;
+use serde::{Deserialize, Serialize};
+use types::{CanisterId, Cycles, Milliseconds, TimestampMillis};
+
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub struct Args {}
+
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub enum Response {
+    Success(SuccessResult),
+}
+
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub struct SuccessResult {
+    pub cycles_balance: Cycles,
+    pub icp_balance: Option<Tokens>,
+    pub cycles_per_e8: Option<Cycles>,
+    pub burn_rate_per_day: Cycles,
+    pub cycles_runway: Option<Milliseconds>,
+    pub total_runway: Option<Milliseconds>,
+    pub canisters: Vec<CanisterForecast>,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub struct CanisterForecast {
+    pub canister_id: CanisterId,
+    pub burn_rate_per_day: Option<Cycles>,
+    pub next_top_up_expected: Option<TimestampMillis>,
+}
@@ -1,8 +1,9 @@
+use crate::AlertConfig;
 use candid::CandidType;
 use human_readable::HumanReadable;
 use ic_ledger_types::Tokens;
 use serde::{Deserialize, Serialize};
-use types::{Cycles, Milliseconds};
+use types::{Cycles, Milliseconds, OptionUpdate};
 
 #[derive(CandidType, Serialize, Deserialize, HumanReadable, Clone, Debug, Default)]
 pub struct Args {
@@ -10,6 +11,8 @@ pub struct Args {
     pub min_interval: Option<Milliseconds>,
     pub min_cycles_balance: Option<Cycles>,
     pub icp_burn_amount: Option<Tokens>,
+    pub alert_config: OptionUpdate<AlertConfig>,
+    pub auto_tune_icp_burn_amount: Option<bool>,
 }
 
 #[derive(CandidType, Serialize, Deserialize, Debug)]
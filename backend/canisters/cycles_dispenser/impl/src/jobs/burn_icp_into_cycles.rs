@@ -42,6 +42,7 @@ struct NotifyTopUpDetails {
     this_canister_id: CanisterId,
     cmc: CanisterId,
     block_index: BlockIndex,
+    amount: Option<Tokens>,
 }
 
 fn get_next_action(state: &mut State) -> Action {
@@ -50,6 +51,7 @@ fn get_next_action(state: &mut State) -> Action {
             this_canister_id: state.env.canister_id(),
             cmc: state.data.cycles_minting_canister,
             block_index,
+            amount: None,
         })
     } else {
         let cycles_balance = state.env.cycles_balance();
@@ -94,6 +96,7 @@ async fn burn_icp(burn_details: BurnIcpDetails) {
                 this_canister_id: burn_details.this_canister_id,
                 cmc: burn_details.cmc,
                 block_index,
+                amount: Some(burn_details.amount),
             })
             .await;
         }
@@ -122,6 +125,10 @@ async fn notify_cmc(notify_details: NotifyTopUpDetails) {
     match response {
         Ok(Ok(cycles)) => {
             info!(cycles, "Canister topped up with cycles");
+            // Track the conversion rate so that the ICP balance can be included in the forecast
+            if let Some(amount) = notify_details.amount.filter(|a| a.e8s() > 0) {
+                mutate_state(|state| state.data.cycles_per_e8 = Some(cycles / amount.e8s() as Cycles));
+            }
         }
         err => {
             error!(?err, "Failed to notify the CMC");
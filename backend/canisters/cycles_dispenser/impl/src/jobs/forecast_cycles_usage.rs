@@ -0,0 +1,84 @@
+use crate::{mutate_state, read_state, State};
+use ic_ledger_types::{AccountBalanceArgs, AccountIdentifier, Tokens, DEFAULT_SUBACCOUNT};
+use std::time::Duration;
+use tracing::{error, info};
+use types::{CanisterId, Cycles, MessageContent, TextContent, Timestamped};
+use user_index_canister::c2c_send_openchat_bot_messages;
+use utils::canister_timers::run_now_then_interval;
+
+const INTERVAL: Duration = Duration::from_secs(60 * 60); // 1 hour
+const MIN_ICP_BURN_AMOUNT_E8S: u64 = 100_000_000; // 1 ICP
+const MAX_ICP_BURN_AMOUNT_E8S: u64 = 100 * 100_000_000; // 100 ICP
+
+pub fn start_job() {
+    run_now_then_interval(INTERVAL, run);
+}
+
+fn run() {
+    ic_cdk::spawn(run_async());
+}
+
+async fn run_async() {
+    let (ledger, this_canister_id) = read_state(|state| (state.data.ledger_canister, state.env.canister_id()));
+
+    match ic_ledger_types::account_balance(
+        ledger,
+        AccountBalanceArgs {
+            account: AccountIdentifier::new(&this_canister_id, &DEFAULT_SUBACCOUNT),
+        },
+    )
+    .await
+    {
+        Ok(balance) => mutate_state(|state| state.data.icp_balance = Some(Timestamped::new(balance, state.env.now()))),
+        Err(error) => error!(?error, "Failed to get ICP balance"),
+    }
+
+    if let Some((user_index_canister_id, args)) = mutate_state(process_forecast) {
+        if let Err(error) = user_index_canister_c2c_client::c2c_send_openchat_bot_messages(user_index_canister_id, &args).await
+        {
+            error!(?error, "Failed to send cycles dispenser alerts");
+        }
+    }
+}
+
+fn process_forecast(state: &mut State) -> Option<(CanisterId, c2c_send_openchat_bot_messages::Args)> {
+    let forecast = state.forecast();
+    let now = state.env.now();
+
+    if state.data.auto_tune_icp_burn_amount {
+        if let Some(amount) = calculate_icp_burn_amount(forecast.burn_rate_per_day, state.data.cycles_per_e8) {
+            if amount != state.data.icp_burn_amount {
+                info!(%amount, "Updated ICP burn amount based on forecast");
+                state.data.icp_burn_amount = amount;
+            }
+        }
+    }
+
+    let alerts = state.data.alerts.check(&forecast, now);
+    let config = state.data.alerts.config()?;
+
+    if alerts.is_empty() || config.recipients.is_empty() {
+        None
+    } else {
+        Some((
+            config.user_index_canister_id,
+            c2c_send_openchat_bot_messages::Args {
+                user_ids: config.recipients.clone(),
+                message: MessageContent::Text(TextContent { text: alerts.join("\n") }),
+            },
+        ))
+    }
+}
+
+// Each burn should cover roughly one day of the forecast cycles usage, so that ICP is burned in
+// larger amounts as usage grows, rather than frequently burning small amounts
+fn calculate_icp_burn_amount(burn_rate_per_day: Cycles, cycles_per_e8: Option<Cycles>) -> Option<Tokens> {
+    let cycles_per_e8 = cycles_per_e8.filter(|c| *c > 0)?;
+    if burn_rate_per_day == 0 {
+        return None;
+    }
+
+    let e8s = (burn_rate_per_day / cycles_per_e8).min(u64::MAX as Cycles) as u64;
+
+    Some(Tokens::from_e8s(e8s.clamp(MIN_ICP_BURN_AMOUNT_E8S, MAX_ICP_BURN_AMOUNT_E8S)))
+}
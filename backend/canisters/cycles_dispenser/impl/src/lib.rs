@@ -1,6 +1,8 @@
+use crate::model::alerts::Alerts;
 use crate::model::canisters::{CanisterMetrics, Canisters};
 use candid::{CandidType, Principal};
 use canister_state_macros::canister_state;
+use cycles_dispenser_canister::runway;
 use ic_ledger_types::{BlockIndex, Tokens};
 use ledger_utils::default_ledger_account;
 use serde::{Deserialize, Serialize};
@@ -8,6 +10,7 @@ use std::cell::RefCell;
 use std::collections::HashSet;
 use types::{BuildVersion, CanisterId, Cycles, Milliseconds, TimestampMillis, Timestamped};
 use utils::env::Environment;
+use utils::time::DAY_IN_MS;
 
 mod guards;
 mod jobs;
@@ -37,6 +40,33 @@ impl State {
         self.data.governance_principals.contains(&self.env.caller())
     }
 
+    pub fn forecast(&self) -> runway::SuccessResult {
+        let now = self.env.now();
+        let cycles_balance = self.env.cycles_balance();
+        let canisters = self.data.canisters.forecasts(now);
+        let burn_rate_per_day: Cycles = canisters.iter().filter_map(|c| c.burn_rate_per_day).sum();
+        let icp_balance = self.data.icp_balance.as_ref().map(|b| b.value);
+
+        // Cycles below `min_cycles_balance` can't be used to top up canisters
+        let available_cycles = cycles_balance.saturating_sub(self.data.min_cycles_balance);
+        let icp_as_cycles = icp_balance
+            .zip(self.data.cycles_per_e8)
+            .map(|(icp, cycles_per_e8)| icp.e8s() as Cycles * cycles_per_e8);
+        let calculate_runway = |cycles: Cycles| {
+            (burn_rate_per_day > 0).then(|| (cycles * DAY_IN_MS as Cycles / burn_rate_per_day) as Milliseconds)
+        };
+
+        runway::SuccessResult {
+            cycles_balance,
+            icp_balance,
+            cycles_per_e8: self.data.cycles_per_e8,
+            burn_rate_per_day,
+            cycles_runway: calculate_runway(available_cycles),
+            total_runway: icp_as_cycles.and_then(|c| calculate_runway(available_cycles + c)),
+            canisters,
+        }
+    }
+
     pub fn metrics(&self) -> Metrics {
         Metrics {
             memory_used: utils::memory::used(),
@@ -54,6 +84,9 @@ impl State {
             icp_burn_amount: self.data.icp_burn_amount,
             ledger_canister: self.data.ledger_canister,
             cycles_minting_canister: self.data.cycles_minting_canister,
+            auto_tune_icp_burn_amount: self.data.auto_tune_icp_burn_amount,
+            cycles_per_e8: self.data.cycles_per_e8,
+            alert_recipients: self.data.alerts.config().map(|c| c.recipients.len()).unwrap_or_default(),
         }
     }
 }
@@ -73,6 +106,14 @@ struct Data {
     #[serde(default)]
     pub rng_seed: [u8; 32],
     pub test_mode: bool,
+    #[serde(default)]
+    pub icp_balance: Option<Timestamped<Tokens>>,
+    #[serde(default)]
+    pub cycles_per_e8: Option<Cycles>,
+    #[serde(default)]
+    pub auto_tune_icp_burn_amount: bool,
+    #[serde(default)]
+    pub alerts: Alerts,
 }
 
 impl Data {
@@ -102,6 +143,10 @@ impl Data {
             cycles_top_up_pending_notification: None,
             rng_seed: [0; 32],
             test_mode,
+            icp_balance: None,
+            cycles_per_e8: None,
+            auto_tune_icp_burn_amount: false,
+            alerts: Alerts::default(),
         }
     }
 }
@@ -123,4 +168,7 @@ pub struct Metrics {
     pub icp_burn_amount: Tokens,
     pub ledger_canister: CanisterId,
     pub cycles_minting_canister: CanisterId,
+    pub auto_tune_icp_burn_amount: bool,
+    pub cycles_per_e8: Option<Cycles>,
+    pub alert_recipients: usize,
 }
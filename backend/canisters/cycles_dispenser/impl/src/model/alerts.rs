@@ -0,0 +1,69 @@
+use cycles_dispenser_canister::runway::SuccessResult as Forecast;
+use cycles_dispenser_canister::AlertConfig;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use types::{CanisterId, Milliseconds, TimestampMillis};
+use utils::time::DAY_IN_MS;
+
+const MIN_INTERVAL_BETWEEN_ALERTS: Milliseconds = DAY_IN_MS;
+
+#[derive(Serialize, Deserialize, Default)]
+pub struct Alerts {
+    config: Option<AlertConfig>,
+    runway_alert_last_sent: Option<TimestampMillis>,
+    burn_rate_alerts_last_sent: HashMap<CanisterId, TimestampMillis>,
+}
+
+impl Alerts {
+    pub fn config(&self) -> Option<&AlertConfig> {
+        self.config.as_ref()
+    }
+
+    pub fn set_config(&mut self, config: Option<AlertConfig>) {
+        self.config = config;
+        self.runway_alert_last_sent = None;
+        self.burn_rate_alerts_last_sent.clear();
+    }
+
+    // Returns the text of each alert triggered by the forecast. Each alert is sent at most once per
+    // `MIN_INTERVAL_BETWEEN_ALERTS` so that recipients aren't spammed while an issue is outstanding.
+    pub fn check(&mut self, forecast: &Forecast, now: TimestampMillis) -> Vec<String> {
+        let Some(config) = &self.config else {
+            return Vec::new();
+        };
+
+        let mut alerts = Vec::new();
+
+        if let Some(runway) = forecast.total_runway.or(forecast.cycles_runway) {
+            if runway < config.min_runway && is_due(self.runway_alert_last_sent, now) {
+                alerts.push(format!(
+                    "The cycles dispenser is forecast to run out of cycles in {} days (burning {} cycles per day)",
+                    runway / DAY_IN_MS,
+                    forecast.burn_rate_per_day
+                ));
+                self.runway_alert_last_sent = Some(now);
+            }
+        }
+
+        if let Some(max_burn_rate) = config.max_canister_burn_rate_per_day {
+            for canister in forecast.canisters.iter() {
+                let Some(burn_rate) = canister.burn_rate_per_day.filter(|r| *r > max_burn_rate) else {
+                    continue;
+                };
+                if is_due(self.burn_rate_alerts_last_sent.get(&canister.canister_id).copied(), now) {
+                    alerts.push(format!(
+                        "Canister {} is burning {burn_rate} cycles per day which exceeds the threshold of {max_burn_rate}",
+                        canister.canister_id
+                    ));
+                    self.burn_rate_alerts_last_sent.insert(canister.canister_id, now);
+                }
+            }
+        }
+
+        alerts
+    }
+}
+
+fn is_due(last_sent: Option<TimestampMillis>, now: TimestampMillis) -> bool {
+    last_sent.map_or(true, |ts| now.saturating_sub(ts) >= MIN_INTERVAL_BETWEEN_ALERTS)
+}
@@ -1,8 +1,12 @@
 use candid::CandidType;
+use cycles_dispenser_canister::runway::CanisterForecast;
 use serde::{Deserialize, Serialize};
 use std::collections::hash_map::Entry::Vacant;
 use std::collections::{BinaryHeap, HashMap};
-use types::{CanisterId, Cycles, TimestampMillis};
+use types::{CanisterId, Cycles, Milliseconds, TimestampMillis};
+use utils::time::DAY_IN_MS;
+
+const BURN_RATE_WINDOW: Milliseconds = 30 * DAY_IN_MS;
 
 #[derive(Serialize, Deserialize)]
 pub struct Canisters {
@@ -56,6 +60,17 @@ impl Canisters {
             .collect()
     }
 
+    pub fn forecasts(&self, now: TimestampMillis) -> Vec<CanisterForecast> {
+        self.canisters
+            .iter()
+            .map(|(id, c)| CanisterForecast {
+                canister_id: *id,
+                burn_rate_per_day: c.burn_rate_per_day(now),
+                next_top_up_expected: c.next_top_up_expected(now),
+            })
+            .collect()
+    }
+
     pub fn latest_top_ups(&self, count: usize) -> Vec<CanisterTopUp> {
         let mut heap = BinaryHeap::with_capacity(count);
 
@@ -107,11 +122,57 @@ impl Canister {
         self.top_ups.last().map(|t| t.date)
     }
 
+    // Canisters request top ups when they are running low on cycles, so the cycles added by each top
+    // up within the window (other than the latest) are assumed to have been burned by the time of the
+    // next top up
+    pub fn burn_rate_per_day(&self, now: TimestampMillis) -> Option<Cycles> {
+        let summary = self.recent_top_ups_summary(now)?;
+
+        Some(summary.burned * DAY_IN_MS as Cycles / summary.duration() as Cycles)
+    }
+
+    pub fn next_top_up_expected(&self, now: TimestampMillis) -> Option<TimestampMillis> {
+        let summary = self.recent_top_ups_summary(now)?;
+
+        Some(summary.last + summary.duration() / (summary.count - 1))
+    }
+
+    fn recent_top_ups_summary(&self, now: TimestampMillis) -> Option<TopUpsSummary> {
+        let window_start = now.saturating_sub(BURN_RATE_WINDOW);
+        let top_ups: Vec<_> = self.top_ups.iter().filter(|t| t.date >= window_start).collect();
+
+        let first = top_ups.first()?.date;
+        let last = top_ups.last()?.date;
+        if last <= first {
+            return None;
+        }
+
+        Some(TopUpsSummary {
+            first,
+            last,
+            count: top_ups.len() as u64,
+            burned: top_ups.iter().rev().skip(1).map(|t| t.amount).sum(),
+        })
+    }
+
     pub fn record_top_up(&mut self, amount: Cycles, now: TimestampMillis) {
         self.top_ups.push(CyclesTopUp { date: now, amount });
     }
 }
 
+struct TopUpsSummary {
+    first: TimestampMillis,
+    last: TimestampMillis,
+    count: u64,
+    burned: Cycles,
+}
+
+impl TopUpsSummary {
+    fn duration(&self) -> Milliseconds {
+        self.last - self.first
+    }
+}
+
 #[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
 struct CyclesTopUp {
     pub date: TimestampMillis,
@@ -1 +1,2 @@
+pub mod alerts;
 pub mod canisters;
@@ -17,6 +17,7 @@ fn http_request(request: HttpRequest) -> HttpResponse {
         "metrics" => read_state(|state| to_json_response(&state.metrics())),
         "trace" => encode_logs(canister_logger::export_traces()),
         "latest_top_ups" => read_state(get_latest_top_ups_impl),
+        "runway" => read_state(|state| to_json_response(&state.forecast())),
         _ => HttpResponse::not_found(),
     }
 }
@@ -36,6 +37,7 @@ fn encode_logs(logs: Vec<LogEntry>) -> HttpResponse {
         ],
         body: ByteBuf::from(body),
         streaming_strategy: None,
+        upgrade: None,
     }
 }
 
@@ -50,6 +52,7 @@ fn to_json_response<T: Serialize>(data: &T) -> HttpResponse {
         ],
         body: ByteBuf::from(body),
         streaming_strategy: None,
+        upgrade: None,
     }
 }
 
@@ -66,6 +69,7 @@ fn get_ledger_account_impl(state: &State) -> HttpResponse {
         ],
         body: ByteBuf::from(body),
         streaming_strategy: None,
+        upgrade: None,
     }
 }
 
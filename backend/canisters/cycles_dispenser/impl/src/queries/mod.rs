@@ -1 +1,2 @@
 mod http_request;
+mod runway;
@@ -0,0 +1,12 @@
+use crate::{read_state, State};
+use cycles_dispenser_canister::runway::{Response::*, *};
+use ic_cdk_macros::query;
+
+#[query]
+fn runway(_args: Args) -> Response {
+    read_state(runway_impl)
+}
+
+fn runway_impl(state: &State) -> Response {
+    Success(state.forecast())
+}
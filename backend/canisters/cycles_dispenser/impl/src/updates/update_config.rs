@@ -3,6 +3,7 @@ use crate::{mutate_state, State};
 use canister_api_macros::proposal;
 use canister_tracing_macros::trace;
 use cycles_dispenser_canister::update_config::{Response::*, *};
+use types::OptionUpdate;
 
 #[proposal(guard = "caller_is_governance_principal")]
 #[trace]
@@ -23,5 +24,13 @@ fn update_config_impl(args: Args, state: &mut State) -> Response {
     if let Some(icp_burn_amount) = args.icp_burn_amount {
         state.data.icp_burn_amount = icp_burn_amount;
     }
+    if let Some(auto_tune_icp_burn_amount) = args.auto_tune_icp_burn_amount {
+        state.data.auto_tune_icp_burn_amount = auto_tune_icp_burn_amount;
+    }
+    match args.alert_config {
+        OptionUpdate::NoChange => {}
+        OptionUpdate::SetToNone => state.data.alerts.set_config(None),
+        OptionUpdate::SetToSome(config) => state.data.alerts.set_config(Some(config)),
+    }
     Success
 }
@@ -2,6 +2,7 @@ use candid_gen::generate_candid_method;
 
 #[allow(deprecated)]
 fn main() {
+    generate_candid_method!(group, chat_reports, query);
     generate_candid_method!(group, deleted_message, query);
     generate_candid_method!(group, events, query);
     generate_candid_method!(group, events_by_index, query);
@@ -9,7 +10,9 @@ fn main() {
     generate_candid_method!(group, invite_code, query);
     generate_candid_method!(group, local_user_index, query);
     generate_candid_method!(group, messages_by_message_index, query);
+    generate_candid_method!(group, proposal_vote_delegation, query);
     generate_candid_method!(group, thread_previews, query);
+    generate_candid_method!(group, typing, query);
     generate_candid_method!(group, public_summary, query);
     generate_candid_method!(group, rules, query);
     generate_candid_method!(group, search_messages, query);
@@ -23,12 +26,14 @@ fn main() {
     generate_candid_method!(group, change_role, update);
     generate_candid_method!(group, claim_prize, update);
     generate_candid_method!(group, convert_into_community, update);
+    generate_candid_method!(group, create_referral_campaign, update);
     generate_candid_method!(group, decline_invitation, update);
     generate_candid_method!(group, delete_messages, update);
     generate_candid_method!(group, disable_invite_code, update);
     generate_candid_method!(group, edit_message_v2, update);
     generate_candid_method!(group, enable_invite_code, update);
     generate_candid_method!(group, follow_thread, update);
+    generate_candid_method!(group, opt_out_of_delegated_proposal_vote, update);
     generate_candid_method!(group, pin_message_v2, update);
     generate_candid_method!(group, register_poll_vote, update);
     generate_candid_method!(group, register_proposal_vote, update);
@@ -37,7 +42,11 @@ fn main() {
     generate_candid_method!(group, remove_reaction, update);
     generate_candid_method!(group, report_message, update);
     generate_candid_method!(group, reset_invite_code, update);
+    generate_candid_method!(group, resolve_report, update);
+    generate_candid_method!(group, rsvp_calendar_event, update);
     generate_candid_method!(group, send_message_v2, update);
+    generate_candid_method!(group, set_proposal_vote_delegation, update);
+    generate_candid_method!(group, set_typing, update);
     generate_candid_method!(group, toggle_mute_notifications, update);
     generate_candid_method!(group, unblock_user, update);
     generate_candid_method!(group, undelete_messages, update);
@@ -2,6 +2,8 @@ pub mod c2c_events_internal;
 pub mod c2c_name_and_members;
 pub mod c2c_summary;
 pub mod c2c_summary_updates;
+pub mod c2c_unvoted_proposals;
+pub mod chat_reports;
 pub mod deleted_message;
 pub mod events;
 pub mod events_by_index;
@@ -9,6 +11,7 @@ pub mod events_window;
 pub mod invite_code;
 pub mod local_user_index;
 pub mod messages_by_message_index;
+pub mod proposal_vote_delegation;
 pub mod public_summary;
 pub mod rules;
 pub mod search_messages;
@@ -17,3 +20,4 @@ pub mod selected_updates_v2;
 pub mod summary;
 pub mod summary_updates;
 pub mod thread_previews;
+pub mod typing;
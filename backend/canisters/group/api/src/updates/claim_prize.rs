@@ -1,6 +1,6 @@
 use candid::CandidType;
 use serde::{Deserialize, Serialize};
-use types::{CompletedCryptoTransaction, FailedCryptoTransaction, MessageId};
+use types::{CompletedCryptoTransaction, FailedCryptoTransaction, MessageId, PrizeIneligibilityReason};
 
 #[derive(CandidType, Serialize, Deserialize, Debug)]
 pub struct Args {
@@ -11,6 +11,8 @@ pub struct Args {
 #[derive(CandidType, Serialize, Deserialize, Debug)]
 pub enum Response {
     Success,
+    // For prize draws, the user has been entered and the winners will be paid once the draw is made
+    EnteredDraw,
     CallerNotInGroup,
     UserSuspended,
     ChatFrozen,
@@ -18,6 +20,8 @@ pub enum Response {
     AlreadyClaimed,
     PrizeFullyClaimed,
     PrizeEnded,
+    NotEligible(PrizeIneligibilityReason),
     TransferFailed(String, FailedCryptoTransaction),
     FailedAfterTransfer(String, CompletedCryptoTransaction),
+    InternalError(String),
 }
@@ -0,0 +1,28 @@
+use candid::CandidType;
+use serde::{Deserialize, Serialize};
+use types::TimestampMillis;
+
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub struct Args {
+    pub code: String,
+    pub name: String,
+    pub expires: Option<TimestampMillis>,
+    pub max_uses: Option<u32>,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub enum Response {
+    Success,
+    CodeInvalid,
+    CodeTaken,
+    NameTooShort(u32),
+    NameTooLong(u32),
+    ExpiryInPast,
+    InvalidMaxUses,
+    NotAuthorized,
+    CallerNotInGroup,
+    UserSuspended,
+    ChatNotPublic,
+    ChatFrozen,
+    InternalError(String),
+}
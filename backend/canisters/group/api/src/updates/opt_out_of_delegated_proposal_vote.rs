@@ -0,0 +1,19 @@
+use candid::CandidType;
+use serde::{Deserialize, Serialize};
+use types::MessageIndex;
+
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub struct Args {
+    pub message_index: MessageIndex,
+    pub opt_out: bool,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub enum Response {
+    Success,
+    CallerNotInGroup,
+    NoDelegation,
+    ProposalMessageNotFound,
+    UserSuspended,
+    ChatFrozen,
+}
@@ -7,6 +7,9 @@ pub struct Args {
     pub thread_root_message_index: Option<MessageIndex>,
     pub message_index: MessageIndex,
     pub poll_option: u32,
+    // For ranked choice polls, the user's full ranking of the options, starting with `poll_option`
+    #[serde(default)]
+    pub ranked_options: Option<Vec<u32>>,
     pub operation: VoteOperation,
     pub correlation_id: u64,
 }
@@ -18,7 +21,10 @@ pub enum Response {
     PollEnded,
     OptionIndexOutOfRange,
     UserCannotChangeVote,
+    InvalidRanking,
+    NoVotingPower,
     CallerNotInGroup,
     UserSuspended,
     ChatFrozen,
+    InternalError(String),
 }
@@ -0,0 +1,24 @@
+use candid::CandidType;
+use serde::{Deserialize, Serialize};
+use types::ChatReportAction;
+
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub struct Args {
+    pub report_id: u32,
+    pub action: ChatReportAction,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub enum Response {
+    Success,
+    ReportNotFound,
+    AlreadyResolved,
+    MessageNotFound,
+    TargetUserNotInGroup,
+    InvalidTimeout,
+    CallerNotInGroup,
+    NotAuthorized,
+    UserSuspended,
+    ChatFrozen,
+    InternalError(String),
+}
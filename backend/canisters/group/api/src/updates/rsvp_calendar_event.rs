@@ -0,0 +1,21 @@
+use candid::CandidType;
+use serde::{Deserialize, Serialize};
+use types::{CalendarEventRsvps, MessageIndex, Rsvp};
+
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub struct Args {
+    pub thread_root_message_index: Option<MessageIndex>,
+    pub message_index: MessageIndex,
+    pub rsvp: Rsvp,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub enum Response {
+    Success(CalendarEventRsvps),
+    EventNotFound,
+    EventStarted,
+    EventFull,
+    CallerNotInGroup,
+    UserSuspended,
+    ChatFrozen,
+}
@@ -0,0 +1,22 @@
+use candid::CandidType;
+use serde::{Deserialize, Serialize};
+use types::UserId;
+
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub struct Args {
+    // Set to `None` to stop following the delegate's votes
+    pub delegate: Option<UserId>,
+    // If set, only votes on proposals with these topics are followed
+    pub topics: Option<Vec<u64>>,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub enum Response {
+    Success,
+    CallerNotInGroup,
+    DelegateNotInGroup,
+    CannotDelegateToSelf,
+    NotProposalsGroup,
+    UserSuspended,
+    ChatFrozen,
+}
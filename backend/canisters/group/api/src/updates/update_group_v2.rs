@@ -33,6 +33,7 @@ pub enum Response {
     NameTaken,
     UserSuspended,
     ChatFrozen,
+    InvalidGate,
     InternalError,
 }
 
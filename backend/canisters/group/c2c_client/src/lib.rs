@@ -6,6 +6,7 @@ generate_c2c_call!(c2c_events_internal);
 generate_c2c_call!(c2c_name_and_members);
 generate_c2c_call!(c2c_summary);
 generate_c2c_call!(c2c_summary_updates);
+generate_c2c_call!(c2c_unvoted_proposals);
 generate_c2c_call!(public_summary);
 
 generate_candid_c2c_call!(events);
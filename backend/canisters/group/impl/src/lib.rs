@@ -1,7 +1,7 @@
 use crate::memory::{get_instruction_counts_data_memory, get_instruction_counts_index_memory};
 use crate::model::new_joiner_rewards::{NewJoinerRewardMetrics, NewJoinerRewardStatus, NewJoinerRewards};
 use crate::new_joiner_rewards::process_new_joiner_reward;
-use crate::timer_job_types::{RemoveExpiredEventsJob, TimerJob};
+use crate::timer_job_types::{MakeTransferJob, RemoveExpiredEventsJob, TimerJob};
 use crate::updates::c2c_freeze_group::freeze_group_impl;
 use activity_notification_state::ActivityNotificationState;
 use candid::Principal;
@@ -26,7 +26,7 @@ use types::{
     FrozenGroupInfo, GroupCanisterGroupChatSummary, GroupMembership, GroupPermissions, GroupSubtype, MessageIndex,
     Milliseconds, Notification, PaymentGate, Rules, TimestampMillis, Timestamped, UserId, MAX_THREADS_IN_SUMMARY,
 };
-use utils::consts::OPENCHAT_BOT_USER_ID;
+use utils::consts::{MEMO_POLL_VOTE_REFUND, OPENCHAT_BOT_USER_ID};
 use utils::env::Environment;
 use utils::regular_jobs::RegularJobs;
 use utils::time::{DAY_IN_MS, HOUR_IN_MS};
@@ -104,6 +104,21 @@ impl RuntimeState {
         }
     }
 
+    // Records the joining fee in the user's wallet transaction history. The user canister looks up
+    // the transfer on the ledger, so only the block index is passed.
+    pub fn notify_joining_fee_paid(&mut self, user_id: UserId, gate: &PaymentGate, block_index: u64) {
+        let args = user_canister::c2c_notify_joining_fee_paid::Args {
+            ledger: gate.ledger_canister_id,
+            block_index,
+            fee: gate.fee,
+        };
+        self.data.fire_and_forget_handler.send(
+            user_id.into(),
+            "c2c_notify_joining_fee_paid_msgpack".to_string(),
+            serialize_then_unwrap(args),
+        );
+    }
+
     pub fn queue_access_gate_payments(&mut self, gate: PaymentGate) {
         // The amount available is the gate amount less the approval fee and the transfer_from fee
         let amount_available = gate.amount - 2 * gate.fee;
@@ -266,7 +281,14 @@ impl RuntimeState {
 
     pub fn run_event_expiry_job(&mut self) {
         let now = self.env.now();
-        self.data.chat.remove_expired_events(now);
+        let now_nanos = self.env.now_nanos();
+        for (user_id, lock) in self.data.chat.remove_expired_events(now) {
+            if let Some(pending_transaction) = lock.refund(user_id, &MEMO_POLL_VOTE_REFUND, now_nanos) {
+                self.data
+                    .timer_jobs
+                    .enqueue_job(TimerJob::MakeTransfer(MakeTransferJob { pending_transaction }), now, now);
+            }
+        }
 
         self.data.next_event_expiry = self.data.chat.events.next_event_expiry();
         if let Some(expiry) = self.data.next_event_expiry {
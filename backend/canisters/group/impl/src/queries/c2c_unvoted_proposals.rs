@@ -0,0 +1,32 @@
+use crate::read_state;
+use crate::RuntimeState;
+use canister_api_macros::query_msgpack;
+use group_canister::c2c_unvoted_proposals::{Response::*, *};
+use types::MultiUserChat;
+
+#[query_msgpack]
+fn c2c_unvoted_proposals(args: Args) -> Response {
+    read_state(|state| c2c_unvoted_proposals_impl(args, state))
+}
+
+fn c2c_unvoted_proposals_impl(args: Args, state: &RuntimeState) -> Response {
+    let caller = state.env.caller();
+    if let Some(member) = state.data.get_member(caller) {
+        if state.data.chat.subtype.value.is_none() {
+            return NoProposals;
+        }
+
+        let chat = MultiUserChat::Group(state.env.canister_id().into());
+        let now = state.env.now();
+
+        Success(state.data.chat.events.unvoted_proposals(
+            chat,
+            member.user_id,
+            member.min_visible_event_index(),
+            args.closing_before,
+            now,
+        ))
+    } else {
+        CallerNotInGroup
+    }
+}
@@ -0,0 +1,26 @@
+use crate::{read_state, RuntimeState};
+use group_canister::chat_reports::{Response::*, *};
+use ic_cdk_macros::query;
+
+#[query]
+fn chat_reports(args: Args) -> Response {
+    read_state(|state| chat_reports_impl(args, state))
+}
+
+fn chat_reports_impl(args: Args, state: &RuntimeState) -> Response {
+    let caller = state.env.caller();
+    if let Some(member) = state.data.get_member(caller) {
+        if !member.role.can_handle_reports() {
+            return NotAuthorized;
+        }
+
+        let reports = &state.data.chat.reports;
+
+        Success(SuccessResult {
+            reports: reports.list(args.include_resolved),
+            member_report_counts: reports.member_report_counts(),
+        })
+    } else {
+        CallerNotInGroup
+    }
+}
@@ -3,6 +3,8 @@ use types::TimestampMillis;
 
 mod c2c_events_internal;
 mod c2c_name_and_members;
+mod c2c_unvoted_proposals;
+mod chat_reports;
 mod deleted_message;
 mod events;
 mod events_by_index;
@@ -11,6 +13,7 @@ mod http_request;
 mod invite_code;
 mod local_user_index;
 mod messages_by_message_index;
+mod proposal_vote_delegation;
 mod public_summary;
 mod rules;
 mod search_messages;
@@ -19,6 +22,7 @@ mod selected_updates;
 mod summary;
 mod summary_updates;
 mod thread_previews;
+mod typing;
 
 fn check_replica_up_to_date(latest_known_update: Option<TimestampMillis>, state: &RuntimeState) -> Result<(), TimestampMillis> {
     if let Some(ts) = latest_known_update {
@@ -0,0 +1,22 @@
+use crate::{read_state, RuntimeState};
+use group_canister::proposal_vote_delegation::{Response::*, *};
+use ic_cdk_macros::query;
+
+#[query]
+fn proposal_vote_delegation(_args: Args) -> Response {
+    read_state(proposal_vote_delegation_impl)
+}
+
+fn proposal_vote_delegation_impl(state: &RuntimeState) -> Response {
+    let caller = state.env.caller();
+    if let Some(member) = state.data.get_member(caller) {
+        let delegations = &state.data.chat.proposal_vote_delegations;
+
+        Success(SuccessResult {
+            delegation: delegations.get(&member.user_id),
+            followers: delegations.follower_count(&member.user_id) as u32,
+        })
+    } else {
+        CallerNotInGroup
+    }
+}
@@ -0,0 +1,19 @@
+use crate::{read_state, RuntimeState};
+use group_canister::typing::{Response::*, *};
+use ic_cdk_macros::query;
+
+#[query]
+fn typing(args: Args) -> Response {
+    read_state(|state| typing_impl(args, state))
+}
+
+fn typing_impl(args: Args, state: &RuntimeState) -> Response {
+    let caller = state.env.caller();
+    let now = state.env.now();
+
+    state
+        .data
+        .lookup_user_id(caller)
+        .and_then(|user_id| state.data.chat.typing(user_id, args.thread_root_message_index, now))
+        .map_or(CallerNotInGroup, Success)
+}
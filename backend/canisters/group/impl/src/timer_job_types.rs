@@ -1,13 +1,20 @@
+use crate::updates::claim_prize::commit_prize_claim;
 use crate::{activity_notifications::handle_activity_notification, mutate_state, read_state};
 use canister_timer_jobs::Job;
 use chat_events::MessageContentInternal;
-use ledger_utils::process_transaction;
+use group_index_canister::c2c_report_message;
+use ledger_utils::{process_transaction, try_process_transaction};
 use serde::{Deserialize, Serialize};
 use tracing::error;
-use types::{BlobReference, CanisterId, MessageId, MessageIndex, PendingCryptoTransaction};
-use utils::consts::MEMO_PRIZE_REFUND;
+use types::{
+    BlobReference, CanisterId, ChatReportAction, EventIndex, GroupCalendarEventReminder, MessageId, MessageIndex, Notification,
+    PendingCryptoTransaction, UserId,
+};
+use utils::consts::{MEMO_POLL_VOTE_REFUND, MEMO_PRIZE_CLAIM, MEMO_PRIZE_REFUND};
 use utils::time::MINUTE_IN_MS;
 
+const MAX_PAY_PRIZE_WINNER_ATTEMPTS: u32 = 10;
+
 #[derive(Serialize, Deserialize, Clone)]
 pub enum TimerJob {
     HardDeleteMessageContent(HardDeleteMessageContentJob),
@@ -15,7 +22,11 @@ pub enum TimerJob {
     EndPoll(EndPollJob),
     RefundPrize(RefundPrizeJob),
     MakeTransfer(MakeTransferJob),
+    PayPrizeWinner(PayPrizeWinnerJob),
     RemoveExpiredEvents(RemoveExpiredEventsJob),
+    CastDelegatedProposalVote(CastDelegatedProposalVoteJob),
+    SendCalendarEventReminder(SendCalendarEventReminderJob),
+    EscalateChatReport(EscalateChatReportJob),
 }
 
 #[derive(Serialize, Deserialize, Clone)]
@@ -46,9 +57,37 @@ pub struct MakeTransferJob {
     pub pending_transaction: PendingCryptoTransaction,
 }
 
+#[derive(Serialize, Deserialize, Clone)]
+pub struct PayPrizeWinnerJob {
+    pub message_id: MessageId,
+    pub winner: UserId,
+    pub pending_transaction: PendingCryptoTransaction,
+    #[serde(default)]
+    pub attempt: u32,
+}
+
 #[derive(Serialize, Deserialize, Clone)]
 pub struct RemoveExpiredEventsJob;
 
+#[derive(Serialize, Deserialize, Clone)]
+pub struct CastDelegatedProposalVoteJob {
+    pub user_id: UserId,
+    pub message_index: MessageIndex,
+    pub adopt: bool,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct SendCalendarEventReminderJob {
+    pub thread_root_message_index: Option<MessageIndex>,
+    pub message_index: MessageIndex,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct EscalateChatReportJob {
+    pub report_id: u32,
+    pub c2c_args: c2c_report_message::Args,
+}
+
 impl Job for TimerJob {
     fn execute(self) {
         match self {
@@ -57,7 +96,11 @@ impl Job for TimerJob {
             TimerJob::EndPoll(job) => job.execute(),
             TimerJob::RefundPrize(job) => job.execute(),
             TimerJob::MakeTransfer(job) => job.execute(),
+            TimerJob::PayPrizeWinner(job) => job.execute(),
             TimerJob::RemoveExpiredEvents(job) => job.execute(),
+            TimerJob::CastDelegatedProposalVote(job) => job.execute(),
+            TimerJob::SendCalendarEventReminder(job) => job.execute(),
+            TimerJob::EscalateChatReport(job) => job.execute(),
         }
     }
 }
@@ -113,6 +156,13 @@ impl Job for HardDeleteMessageContentJob {
                             }
                         }
                     }
+                } else if let MessageContentInternal::Poll(poll) = content {
+                    let now_nanos = state.env.now_nanos();
+                    for (user_id, lock) in poll.token_locks {
+                        if let Some(pending_transaction) = lock.refund(user_id, &MEMO_POLL_VOTE_REFUND, now_nanos) {
+                            follow_on_jobs.push(TimerJob::MakeTransfer(MakeTransferJob { pending_transaction }));
+                        }
+                    }
                 }
             }
         });
@@ -131,8 +181,9 @@ impl Job for DeleteFileReferencesJob {
 
 impl Job for EndPollJob {
     fn execute(self) {
-        mutate_state(|state| {
+        let refunds: Vec<_> = mutate_state(|state| {
             let now = state.env.now();
+            let now_nanos = state.env.now_nanos();
             state
                 .data
                 .chat
@@ -140,12 +191,51 @@ impl Job for EndPollJob {
                 .end_poll(self.thread_root_message_index, self.message_index, now);
 
             handle_activity_notification(state);
+
+            // Return any tokens which were locked to weight votes in the poll
+            state
+                .data
+                .chat
+                .events
+                .take_poll_token_locks(self.thread_root_message_index, self.message_index)
+                .into_iter()
+                .filter_map(|(user_id, lock)| lock.refund(user_id, &MEMO_POLL_VOTE_REFUND, now_nanos))
+                .collect()
         });
+
+        for pending_transaction in refunds {
+            let make_transfer_job = MakeTransferJob { pending_transaction };
+            make_transfer_job.execute();
+        }
     }
 }
 
 impl Job for RefundPrizeJob {
     fn execute(self) {
+        // For prize draws, first pick the winners and pay them their prizes
+        if let Some((message_id, payouts)) = mutate_state(|state| {
+            let now = state.env.now();
+            let now_nanos = state.env.now_nanos();
+            state.data.chat.events.draw_prize(
+                self.thread_root_message_index,
+                self.message_index,
+                &MEMO_PRIZE_CLAIM,
+                state.env.rng(),
+                now_nanos,
+                now,
+            )
+        }) {
+            for (winner, pending_transaction) in payouts {
+                let pay_prize_winner_job = PayPrizeWinnerJob {
+                    message_id,
+                    winner,
+                    pending_transaction,
+                    attempt: 0,
+                };
+                pay_prize_winner_job.execute();
+            }
+        }
+
         if let Some(pending_transaction) = read_state(|state| {
             state.data.chat.events.prize_refund(
                 self.thread_root_message_index,
@@ -182,8 +272,152 @@ impl Job for MakeTransferJob {
     }
 }
 
+impl Job for PayPrizeWinnerJob {
+    fn execute(self) {
+        let sender = read_state(|state| state.env.canister_id());
+        ic_cdk::spawn(pay_prize_winner(self, sender));
+
+        async fn pay_prize_winner(job: PayPrizeWinnerJob, sender: CanisterId) {
+            match try_process_transaction(job.pending_transaction.clone(), sender).await {
+                Ok(transaction) => {
+                    if let Some(error) =
+                        mutate_state(|state| commit_prize_claim(job.message_id, job.winner, transaction, state))
+                    {
+                        error!(%error, "Failed to record prize draw winner");
+                    }
+                }
+                Err(failure) if failure.transient && job.attempt + 1 < MAX_PAY_PRIZE_WINNER_ATTEMPTS => {
+                    // The pending transaction is reused so that the ledger will deduplicate the
+                    // transfer if the previous attempt actually succeeded
+                    mutate_state(|state| {
+                        let now = state.env.now();
+                        state.data.timer_jobs.enqueue_job(
+                            TimerJob::PayPrizeWinner(PayPrizeWinnerJob {
+                                attempt: job.attempt + 1,
+                                ..job
+                            }),
+                            now + MINUTE_IN_MS,
+                            now,
+                        );
+                    });
+                }
+                Err(failure) => {
+                    error!(
+                        error = failure.transaction.error_message(),
+                        winner = %job.winner,
+                        attempts = job.attempt + 1,
+                        "Failed to pay prize draw winner"
+                    );
+                    // Release the winner's reservation and refund their prize to the prize's sender
+                    if let Some(pending_transaction) = mutate_state(|state| {
+                        let now = state.env.now();
+                        let now_nanos = state.env.now_nanos();
+                        state.data.chat.events.refund_prize_reservation(
+                            job.message_id,
+                            job.winner,
+                            job.pending_transaction.units(),
+                            &MEMO_PRIZE_REFUND,
+                            now_nanos,
+                            now,
+                        )
+                    }) {
+                        let make_transfer_job = MakeTransferJob { pending_transaction };
+                        make_transfer_job.execute();
+                    }
+                }
+            }
+        }
+    }
+}
+
 impl Job for RemoveExpiredEventsJob {
     fn execute(self) {
         mutate_state(|state| state.run_event_expiry_job());
     }
 }
+
+impl Job for CastDelegatedProposalVoteJob {
+    fn execute(self) {
+        ic_cdk::spawn(crate::updates::register_proposal_vote::cast_delegated_vote(
+            self.user_id,
+            self.message_index,
+            self.adopt,
+        ));
+    }
+}
+
+impl Job for SendCalendarEventReminderJob {
+    fn execute(self) {
+        mutate_state(|state| {
+            let chat = &state.data.chat;
+            if let Some((event, event_index)) =
+                chat.events
+                    .calendar_event(EventIndex::default(), self.thread_root_message_index, self.message_index)
+            {
+                // Only remind attendees who are still members of the group
+                let recipients: Vec<UserId> = event
+                    .attendees()
+                    .into_iter()
+                    .filter(|u| chat.members.get(u).map_or(false, |m| !m.suspended.value))
+                    .collect();
+
+                let notification = Notification::GroupCalendarEventReminder(GroupCalendarEventReminder {
+                    chat_id: state.env.canister_id().into(),
+                    thread_root_message_index: self.thread_root_message_index,
+                    message_index: self.message_index,
+                    message_event_index: event_index,
+                    group_name: chat.name.value.clone(),
+                    title: event.details.title.clone(),
+                    start: event.details.start,
+                    location: event.details.location.clone(),
+                    group_avatar_id: chat.avatar.as_ref().map(|d| d.id),
+                });
+                state.push_notification(recipients, notification);
+            }
+        });
+    }
+}
+
+impl Job for EscalateChatReportJob {
+    fn execute(self) {
+        // Skip reports which have already been handled by the group's moderators
+        let Some(group_index_canister_id) = read_state(|state| {
+            state
+                .data
+                .chat
+                .reports
+                .get(self.report_id)
+                .filter(|r| r.resolution.is_none())
+                .map(|_| state.data.group_index_canister_id)
+        }) else {
+            return;
+        };
+
+        ic_cdk::spawn(escalate_chat_report(self, group_index_canister_id));
+
+        async fn escalate_chat_report(job: EscalateChatReportJob, group_index_canister_id: CanisterId) {
+            match group_index_canister_c2c_client::c2c_report_message(group_index_canister_id, &job.c2c_args).await {
+                Ok(_) => mutate_state(|state| {
+                    let now = state.env.now();
+                    // Automatic escalations are attributed to the user who reported the message
+                    state.data.chat.reports.resolve(
+                        job.report_id,
+                        ChatReportAction::EscalateToPlatform,
+                        job.c2c_args.reporter,
+                        now,
+                    );
+                }),
+                Err(error) => {
+                    error!(?error, "Failed to escalate chat report");
+                    mutate_state(|state| {
+                        let now = state.env.now();
+                        state
+                            .data
+                            .timer_jobs
+                            .enqueue_job(TimerJob::EscalateChatReport(job), now + MINUTE_IN_MS, now);
+                    });
+                }
+            }
+        }
+    }
+}
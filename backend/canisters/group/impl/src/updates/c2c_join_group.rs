@@ -14,17 +14,18 @@ use types::{AccessGate, MemberJoined, UsersUnblocked};
 async fn c2c_join_group(args: Args) -> Response {
     run_regular_jobs();
 
-    match read_state(|state| is_permitted_to_join(&args, state)) {
+    let payment_block_index = match read_state(|state| is_permitted_to_join(&args, state)) {
         Ok(Some(check_gate_args)) => match check_if_passes_gate(check_gate_args).await {
-            CheckIfPassesGateResult::Success => {}
+            CheckIfPassesGateResult::Success => None,
+            CheckIfPassesGateResult::PaymentTaken(block_index) => Some(block_index),
             CheckIfPassesGateResult::Failed(reason) => return GateCheckFailed(reason),
             CheckIfPassesGateResult::InternalError(error) => return InternalError(error),
         },
-        Ok(None) => {}
+        Ok(None) => None,
         Err(response) => return response,
     };
 
-    mutate_state(|state| c2c_join_group_impl(args, state))
+    mutate_state(|state| c2c_join_group_impl(args, payment_block_index, state))
 }
 
 fn is_permitted_to_join(args: &Args, state: &RuntimeState) -> Result<Option<CheckGateArgs>, Response> {
@@ -53,7 +54,7 @@ fn is_permitted_to_join(args: &Args, state: &RuntimeState) -> Result<Option<Chec
     }
 }
 
-fn c2c_join_group_impl(args: Args, state: &mut RuntimeState) -> Response {
+fn c2c_join_group_impl(args: Args, payment_block_index: Option<u64>, state: &mut RuntimeState) -> Response {
     let now = state.env.now();
     let min_visible_event_index;
     let min_visible_message_index;
@@ -118,8 +119,11 @@ fn c2c_join_group_impl(args: Args, state: &mut RuntimeState) -> Response {
             let summary = state.summary(&participant);
 
             // If there is a payment gate on this group then queue payments to owner(s) and treasury
-            if let Some(AccessGate::Payment(gate)) = state.data.chat.gate.value.as_ref() {
-                state.queue_access_gate_payments(gate.clone());
+            if let Some(AccessGate::Payment(gate)) = state.data.chat.gate.value.as_ref().cloned() {
+                if let Some(block_index) = payment_block_index {
+                    state.notify_joining_fee_paid(args.user_id, &gate, block_index);
+                }
+                state.queue_access_gate_payments(gate);
             }
 
             Success(Box::new(summary))
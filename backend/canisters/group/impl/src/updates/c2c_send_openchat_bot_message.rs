@@ -0,0 +1,37 @@
+use crate::activity_notifications::handle_activity_notification;
+use crate::guards::caller_is_user_index;
+use crate::{mutate_state, run_regular_jobs, RuntimeState};
+use canister_api_macros::update_msgpack;
+use canister_tracing_macros::trace;
+use chat_events::{MessageContentInternal, PushMessageArgs, TextContentInternal};
+use group_canister::c2c_send_openchat_bot_message::{Response::*, *};
+use rand::Rng;
+use utils::consts::OPENCHAT_BOT_USER_ID;
+
+// Used by the user_index to post into the platform moderators group, eg. when a user files an appeal
+#[update_msgpack(guard = "caller_is_user_index")]
+#[trace]
+fn c2c_send_openchat_bot_message(args: Args) -> Response {
+    run_regular_jobs();
+
+    mutate_state(|state| c2c_send_openchat_bot_message_impl(args, state))
+}
+
+fn c2c_send_openchat_bot_message_impl(args: Args, state: &mut RuntimeState) -> Response {
+    let now = state.env.now();
+
+    state.data.chat.events.push_message(PushMessageArgs {
+        sender: OPENCHAT_BOT_USER_ID,
+        thread_root_message_index: None,
+        message_id: state.env.rng().gen(),
+        content: MessageContentInternal::Text(TextContentInternal { text: args.text }),
+        mentioned: Vec::new(),
+        replies_to: None,
+        forwarded: false,
+        correlation_id: 0,
+        now,
+    });
+
+    handle_activity_notification(state);
+    Success
+}
@@ -1,12 +1,18 @@
 use crate::activity_notifications::handle_activity_notification;
-use crate::{mutate_state, run_regular_jobs, RuntimeState};
+use crate::{mutate_state, read_state, run_regular_jobs, RuntimeState};
 use canister_tracing_macros::trace;
 use chat_events::ReservePrizeResult;
+use gated_groups::{check_if_passes_gate, CheckGateArgs, CheckIfPassesGateResult};
 use group_canister::claim_prize::{Response::*, *};
 use ic_cdk_macros::update;
 use ic_ledger_types::Tokens;
 use ledger_utils::{create_pending_transaction, process_transaction};
-use types::{CanisterId, CompletedCryptoTransaction, GroupMessageNotification, Notification, PendingCryptoTransaction, UserId};
+use msgpack::serialize_then_unwrap;
+use types::{
+    CanisterId, CompletedCryptoTransaction, GroupMessageNotification, MessageId, Notification, PendingCryptoTransaction,
+    PrizeEligibility, PrizeIneligibilityReason, UserId,
+};
+use user_canister::c2c_notify_prize_claimed;
 use utils::consts::{MEMO_PRIZE_CLAIM, OPENCHAT_BOT_USERNAME, OPENCHAT_BOT_USER_ID};
 
 #[update]
@@ -14,7 +20,22 @@ use utils::consts::{MEMO_PRIZE_CLAIM, OPENCHAT_BOT_USERNAME, OPENCHAT_BOT_USER_I
 async fn claim_prize(args: Args) -> Response {
     run_regular_jobs();
 
-    // Validate the request and reserve a prize
+    // Check the user meets the prize's eligibility rules, leaving any access gates until last since
+    // they require c2c calls
+    let gate_checks = match read_state(|state| check_eligibility(&args, state)) {
+        Ok(g) => g,
+        Err(response) => return *response,
+    };
+
+    for check_gate_args in gate_checks {
+        match check_if_passes_gate(check_gate_args).await {
+            CheckIfPassesGateResult::Success | CheckIfPassesGateResult::PaymentTaken(_) => {}
+            CheckIfPassesGateResult::Failed(reason) => return NotEligible(PrizeIneligibilityReason::GateCheckFailed(reason)),
+            CheckIfPassesGateResult::InternalError(error) => return InternalError(error),
+        }
+    }
+
+    // Validate the request and reserve a prize, or enter the user into the draw
     let prepare_result = match mutate_state(|state| prepare(&args, state)) {
         Ok(c) => c,
         Err(response) => return *response,
@@ -26,9 +47,9 @@ async fn claim_prize(args: Args) -> Response {
     match result {
         Ok(completed_transaction) => {
             // Claim the prize and send a message to the group
-            if let Some(error_message) =
-                mutate_state(|state| commit(args, prepare_result.user_id, completed_transaction.clone(), state))
-            {
+            if let Some(error_message) = mutate_state(|state| {
+                commit_prize_claim(args.message_id, prepare_result.user_id, completed_transaction.clone(), state)
+            }) {
                 FailedAfterTransfer(error_message, completed_transaction)
             } else {
                 Success
@@ -43,6 +64,65 @@ async fn claim_prize(args: Args) -> Response {
     }
 }
 
+fn check_eligibility(args: &Args, state: &RuntimeState) -> Result<Vec<CheckGateArgs>, Box<Response>> {
+    if state.data.is_frozen() {
+        return Err(Box::new(ChatFrozen));
+    }
+
+    let caller = state.env.caller();
+
+    let member = match state.data.get_member(caller) {
+        Some(m) => m,
+        None => return Err(Box::new(CallerNotInGroup)),
+    };
+
+    if member.suspended.value {
+        return Err(Box::new(UserSuspended));
+    }
+
+    let now = state.env.now();
+    let min_visible_event_index = member.min_visible_event_index();
+    let eligibility = match state
+        .data
+        .chat
+        .events
+        .prize_eligibility(args.message_id, min_visible_event_index)
+    {
+        Some(e) => e,
+        None => return Err(Box::new(MessageNotFound)),
+    };
+
+    let mut gate_checks = Vec::new();
+    for rule in eligibility {
+        match rule {
+            PrizeEligibility::MinMembershipDuration(duration) => {
+                if now.saturating_sub(member.date_added) < *duration {
+                    return Err(Box::new(NotEligible(PrizeIneligibilityReason::MembershipTooRecent)));
+                }
+            }
+            PrizeEligibility::EngagedWithMessage(message_index) => {
+                if !state
+                    .data
+                    .chat
+                    .events
+                    .has_engaged_with_message(member.user_id, min_visible_event_index, *message_index)
+                {
+                    return Err(Box::new(NotEligible(PrizeIneligibilityReason::NotEngagedWithMessage)));
+                }
+            }
+            PrizeEligibility::AccessGate(gate) => gate_checks.push(CheckGateArgs {
+                gate: gate.clone(),
+                user_index_canister: state.data.user_index_canister_id,
+                user_id: member.user_id,
+                this_canister: state.env.canister_id(),
+                now_nanos: state.env.now_nanos(),
+            }),
+        }
+    }
+
+    Ok(gate_checks)
+}
+
 struct PrepareResult {
     pub transaction: PendingCryptoTransaction,
     pub group: CanisterId,
@@ -75,6 +155,8 @@ fn prepare(args: &Args, state: &mut RuntimeState) -> Result<PrepareResult, Box<R
             {
                 ReservePrizeResult::AlreadyClaimed => return Err(Box::new(AlreadyClaimed)),
                 ReservePrizeResult::Success(t, l, a, f) => (t, l, a, f),
+                // There is nothing to transfer until the draw is made
+                ReservePrizeResult::EnteredDraw => return Err(Box::new(EnteredDraw)),
                 ReservePrizeResult::MessageNotFound => return Err(Box::new(MessageNotFound)),
                 ReservePrizeResult::PrizeFullyClaimed => return Err(Box::new(PrizeFullyClaimed)),
                 ReservePrizeResult::PrizeEnded => return Err(Box::new(PrizeEnded)),
@@ -92,13 +174,24 @@ fn prepare(args: &Args, state: &mut RuntimeState) -> Result<PrepareResult, Box<R
     }
 }
 
-fn commit(args: Args, winner: UserId, transaction: CompletedCryptoTransaction, state: &mut RuntimeState) -> Option<String> {
+// Also used to pay out the winners of prize draws
+pub(crate) fn commit_prize_claim(
+    message_id: MessageId,
+    winner: UserId,
+    transaction: CompletedCryptoTransaction,
+    state: &mut RuntimeState,
+) -> Option<String> {
     let now = state.env.now();
+    let prize_claimed_args = c2c_notify_prize_claimed::Args {
+        ledger: transaction.ledger_canister_id(),
+        amount: transaction.units(),
+        block_index: transaction.block_index(),
+    };
     match state
         .data
         .chat
         .events
-        .claim_prize(args.message_id, winner, transaction, state.env.rng(), now)
+        .claim_prize(message_id, winner, transaction, state.env.rng(), now)
     {
         chat_events::ClaimPrizeResult::Success(message_event) => {
             // Send a notification to group participants
@@ -129,6 +222,13 @@ fn commit(args: Args, winner: UserId, transaction: CompletedCryptoTransaction, s
             });
             state.push_notification(notification_recipients, notification);
 
+            // Fire-and-forget call to notify the winner's user canister
+            state.data.fire_and_forget_handler.send(
+                winner.into(),
+                "c2c_notify_prize_claimed_msgpack".to_string(),
+                serialize_then_unwrap(prize_claimed_args),
+            );
+
             handle_activity_notification(state);
             None
         }
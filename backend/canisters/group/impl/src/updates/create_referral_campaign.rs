@@ -0,0 +1,74 @@
+use crate::{read_state, run_regular_jobs, RuntimeState};
+use canister_tracing_macros::trace;
+use group_canister::create_referral_campaign::{Response::*, *};
+use ic_cdk_macros::update;
+use types::{CanisterId, ReferralCampaignTarget, UserId};
+
+#[update]
+#[trace]
+async fn create_referral_campaign(args: Args) -> Response {
+    run_regular_jobs();
+
+    let PrepareResult {
+        caller_id,
+        user_index_canister_id,
+        target,
+    } = match read_state(prepare) {
+        Ok(result) => result,
+        Err(response) => return response,
+    };
+
+    let c2c_args = user_index_canister::c2c_create_referral_campaign::Args {
+        code: args.code,
+        name: args.name,
+        target,
+        created_by: caller_id,
+        expires: args.expires,
+        max_uses: args.max_uses,
+    };
+    match user_index_canister_c2c_client::c2c_create_referral_campaign(user_index_canister_id, &c2c_args).await {
+        Ok(response) => match response {
+            user_index_canister::c2c_create_referral_campaign::Response::Success => Success,
+            user_index_canister::c2c_create_referral_campaign::Response::CodeInvalid => CodeInvalid,
+            user_index_canister::c2c_create_referral_campaign::Response::CodeTaken => CodeTaken,
+            user_index_canister::c2c_create_referral_campaign::Response::NameTooShort(min) => NameTooShort(min),
+            user_index_canister::c2c_create_referral_campaign::Response::NameTooLong(max) => NameTooLong(max),
+            user_index_canister::c2c_create_referral_campaign::Response::ExpiryInPast => ExpiryInPast,
+            user_index_canister::c2c_create_referral_campaign::Response::InvalidMaxUses => InvalidMaxUses,
+            user_index_canister::c2c_create_referral_campaign::Response::NotAuthorized => NotAuthorized,
+        },
+        Err(error) => InternalError(format!("{error:?}")),
+    }
+}
+
+struct PrepareResult {
+    caller_id: UserId,
+    user_index_canister_id: CanisterId,
+    target: ReferralCampaignTarget,
+}
+
+// Users who register via a campaign are joined to the group without an invite code, so only public
+// groups can run campaigns
+fn prepare(state: &RuntimeState) -> Result<PrepareResult, Response> {
+    if state.data.is_frozen() {
+        return Err(ChatFrozen);
+    }
+
+    let caller = state.env.caller();
+    let Some(member) = state.data.get_member(caller) else {
+        return Err(CallerNotInGroup);
+    };
+    if member.suspended.value {
+        Err(UserSuspended)
+    } else if !member.role.is_owner() {
+        Err(NotAuthorized)
+    } else if !state.data.chat.is_public.value {
+        Err(ChatNotPublic)
+    } else {
+        Ok(PrepareResult {
+            caller_id: member.user_id,
+            user_index_canister_id: state.data.user_index_canister_id,
+            target: ReferralCampaignTarget::Group(state.env.canister_id().into()),
+        })
+    }
+}
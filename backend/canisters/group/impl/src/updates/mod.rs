@@ -6,21 +6,25 @@ pub mod c2c_invite_users;
 pub mod c2c_join_group;
 pub mod c2c_leave_group;
 pub mod c2c_report_message_v2;
+pub mod c2c_send_openchat_bot_message;
 pub mod c2c_set_user_suspended;
 pub mod c2c_start_import_into_community;
 pub mod c2c_tip_message;
+pub mod c2c_undelete_reported_message;
 pub mod c2c_unfreeze_group;
 pub mod c2c_update_proposals;
 pub mod c2c_update_user_principal;
 pub mod change_role;
 pub mod claim_prize;
 pub mod convert_into_community;
+pub mod create_referral_campaign;
 pub mod decline_invitation;
 pub mod delete_messages;
 pub mod disable_invite_code;
 pub mod edit_message;
 pub mod enable_invite_code;
 pub mod follow_thread;
+pub mod opt_out_of_delegated_proposal_vote;
 pub mod pin_message;
 pub mod register_poll_vote;
 pub mod register_proposal_vote;
@@ -28,7 +32,11 @@ pub mod register_proposal_vote_v2;
 pub mod remove_participant;
 pub mod remove_reaction;
 pub mod report_message;
+pub mod resolve_report;
+pub mod rsvp_calendar_event;
 pub mod send_message;
+pub mod set_proposal_vote_delegation;
+pub mod set_typing;
 pub mod toggle_mute_notifications;
 pub mod unblock_user;
 pub mod undelete_messages;
@@ -0,0 +1,54 @@
+use crate::{mutate_state, run_regular_jobs, RuntimeState};
+use canister_tracing_macros::trace;
+use chat_events::{MessageContentInternal, Reader};
+use group_canister::opt_out_of_delegated_proposal_vote::{Response::*, *};
+use ic_cdk_macros::update;
+
+#[update]
+#[trace]
+fn opt_out_of_delegated_proposal_vote(args: Args) -> Response {
+    run_regular_jobs();
+
+    mutate_state(|state| opt_out_of_delegated_proposal_vote_impl(args, state))
+}
+
+fn opt_out_of_delegated_proposal_vote_impl(args: Args, state: &mut RuntimeState) -> Response {
+    if state.data.is_frozen() {
+        return ChatFrozen;
+    }
+
+    let caller = state.env.caller();
+
+    let member = match state.data.get_member(caller) {
+        Some(p) => p,
+        None => return CallerNotInGroup,
+    };
+
+    if member.suspended.value {
+        return UserSuspended;
+    }
+
+    let user_id = member.user_id;
+
+    if !state
+        .data
+        .chat
+        .events
+        .visible_main_events_reader(member.min_visible_event_index())
+        .message_internal(args.message_index.into())
+        .map_or(false, |m| matches!(m.content, MessageContentInternal::GovernanceProposal(_)))
+    {
+        return ProposalMessageNotFound;
+    }
+
+    if state
+        .data
+        .chat
+        .proposal_vote_delegations
+        .set_opted_out(&user_id, args.message_index, args.opt_out)
+    {
+        Success
+    } else {
+        NoDelegation
+    }
+}
@@ -1,25 +1,55 @@
 use crate::activity_notifications::handle_activity_notification;
+use crate::timer_job_types::{CastDelegatedProposalVoteJob, TimerJob};
 use crate::{mutate_state, read_state, run_regular_jobs, RuntimeState};
 use canister_tracing_macros::trace;
 use chat_events::{MessageContentInternal, Reader, RecordProposalVoteResult};
 use group_canister::register_proposal_vote::{Response::*, *};
 use ic_cdk_macros::update;
-use types::{CanisterId, ProposalId, UserId};
+use tracing::info;
+use types::{CanisterId, MessageIndex, ProposalId, UserId};
 
 #[update]
 #[trace]
 async fn register_proposal_vote(args: Args) -> Response {
     run_regular_jobs();
 
+    let prepare_result = match read_state(|state| prepare(&args, state)) {
+        Ok(ok) => ok,
+        Err(response) => return response,
+    };
+
+    vote_on_proposal(prepare_result, args).await
+}
+
+// Casts the vote of a member who has delegated their votes to another member who has just voted
+pub(crate) async fn cast_delegated_vote(user_id: UserId, message_index: MessageIndex, adopt: bool) {
+    let prepare_result =
+        match read_state(
+            |state| {
+                if state.data.is_frozen() {
+                    Err(ChatFrozen)
+                } else {
+                    prepare_vote(user_id, message_index, state)
+                }
+            },
+        ) {
+            Ok(ok) => ok,
+            Err(_) => return,
+        };
+
+    let response = vote_on_proposal(prepare_result, Args { message_index, adopt }).await;
+    if !matches!(response, Success) {
+        info!(%user_id, ?message_index, ?response, "Failed to cast delegated proposal vote");
+    }
+}
+
+async fn vote_on_proposal(prepare_result: PrepareResult, args: Args) -> Response {
     let PrepareResult {
         user_id,
         is_nns,
         governance_canister_id,
         proposal_id,
-    } = match read_state(|state| prepare(&args, state)) {
-        Ok(ok) => ok,
-        Err(response) => return response,
-    };
+    } = prepare_result;
 
     let c2c_args = user_canister::c2c_vote_on_proposal::Args {
         is_nns,
@@ -56,7 +86,14 @@ fn prepare(args: &Args, state: &RuntimeState) -> Result<PrepareResult, Response>
 
     let caller = state.env.caller();
 
-    let member = match state.data.get_member(caller) {
+    match state.data.get_member(caller) {
+        Some(p) => prepare_vote(p.user_id, args.message_index, state),
+        None => Err(CallerNotInGroup),
+    }
+}
+
+fn prepare_vote(user_id: UserId, message_index: MessageIndex, state: &RuntimeState) -> Result<PrepareResult, Response> {
+    let member = match state.data.chat.members.get(&user_id) {
         Some(p) => p,
         None => return Err(CallerNotInGroup),
     };
@@ -72,7 +109,7 @@ fn prepare(args: &Args, state: &RuntimeState) -> Result<PrepareResult, Response>
         .chat
         .events
         .visible_main_events_reader(min_visible_event_index)
-        .message_internal(args.message_index.into())
+        .message_internal(message_index.into())
         .and_then(|m| if let MessageContentInternal::GovernanceProposal(p) = &m.content { Some(p) } else { None })
     {
         if let Some(vote) = proposal.votes.get(&member.user_id) {
@@ -110,6 +147,7 @@ fn commit(user_id: UserId, args: Args, state: &mut RuntimeState) -> Response {
             if !votes.contains(&args.message_index) {
                 votes.push(args.message_index);
             }
+            enqueue_delegated_votes(user_id, args.message_index, args.adopt, state);
             handle_activity_notification(state);
             Success
         }
@@ -117,3 +155,42 @@ fn commit(user_id: UserId, args: Args, state: &mut RuntimeState) -> Response {
         RecordProposalVoteResult::ProposalNotFound => ProposalNotFound,
     }
 }
+
+// Queues up votes on behalf of the members who follow `user_id`'s votes. Once those votes have been
+// cast, the votes of their own followers will be queued up in turn.
+pub(crate) fn enqueue_delegated_votes(user_id: UserId, message_index: MessageIndex, adopt: bool, state: &mut RuntimeState) {
+    let topic = match state
+        .data
+        .chat
+        .events
+        .main_events_reader()
+        .message_internal(message_index.into())
+        .and_then(|m| {
+            if let MessageContentInternal::GovernanceProposal(p) = &m.content {
+                Some(p.proposal.topic())
+            } else {
+                None
+            }
+        }) {
+        Some(t) => t,
+        None => return,
+    };
+
+    let now = state.env.now();
+    for follower in state
+        .data
+        .chat
+        .proposal_vote_delegations
+        .followers(user_id, message_index, topic)
+    {
+        state.data.timer_jobs.enqueue_job(
+            TimerJob::CastDelegatedProposalVote(CastDelegatedProposalVoteJob {
+                user_id: follower,
+                message_index,
+                adopt,
+            }),
+            now,
+            now,
+        );
+    }
+}
@@ -1,4 +1,5 @@
 use crate::activity_notifications::handle_activity_notification;
+use crate::updates::register_proposal_vote::enqueue_delegated_votes;
 use crate::{mutate_state, run_regular_jobs, RuntimeState};
 use canister_tracing_macros::trace;
 use chat_events::RecordProposalVoteResult;
@@ -52,6 +53,7 @@ fn register_proposal_vote_impl(args: Args, state: &mut RuntimeState) -> Response
                 .or_default()
                 .push(args.message_index);
 
+            enqueue_delegated_votes(user_id, args.message_index, args.adopt, state);
             handle_activity_notification(state);
             Success
         }
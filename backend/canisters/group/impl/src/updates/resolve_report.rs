@@ -0,0 +1,155 @@
+use crate::activity_notifications::handle_activity_notification;
+use crate::updates::remove_participant::remove_participant_impl;
+use crate::{mutate_state, read_state, run_regular_jobs, RuntimeState};
+use canister_tracing_macros::trace;
+use chat_events::{DeleteMessageResult, Reader};
+use group_canister::resolve_report::{Response::*, *};
+use group_chat_core::{DeleteMessagesResult, TimeoutMemberResult};
+use group_index_canister::c2c_report_message;
+use ic_cdk_macros::update;
+use types::{CanisterId, ChatReportAction, MultiUserChat, UserId};
+
+#[update]
+#[trace]
+async fn resolve_report(args: Args) -> Response {
+    run_regular_jobs();
+
+    let PrepareResult {
+        resolved_by,
+        sender,
+        escalation,
+    } = match read_state(|state| prepare(&args, state)) {
+        Ok(ok) => ok,
+        Err(response) => return response,
+    };
+
+    match args.action {
+        ChatReportAction::RemoveMember => match remove_participant_impl(sender, false).await {
+            group_canister::remove_participant::Response::Success => {}
+            group_canister::remove_participant::Response::UserNotInGroup => return TargetUserNotInGroup,
+            group_canister::remove_participant::Response::InternalError(error) => return InternalError(error),
+            _ => return NotAuthorized,
+        },
+        ChatReportAction::EscalateToPlatform => {
+            let (c2c_args, group_index_canister_id) = escalation.unwrap();
+            if let Err(error) = group_index_canister_c2c_client::c2c_report_message(group_index_canister_id, &c2c_args).await {
+                return InternalError(format!("{error:?}"));
+            }
+        }
+        _ => {}
+    }
+
+    mutate_state(|state| commit(args, resolved_by, state))
+}
+
+struct PrepareResult {
+    resolved_by: UserId,
+    sender: UserId,
+    escalation: Option<(c2c_report_message::Args, CanisterId)>,
+}
+
+fn prepare(args: &Args, state: &RuntimeState) -> Result<PrepareResult, Response> {
+    if state.data.is_frozen() {
+        return Err(ChatFrozen);
+    }
+
+    let caller = state.env.caller();
+
+    let member = match state.data.get_member(caller) {
+        Some(m) => m,
+        None => return Err(CallerNotInGroup),
+    };
+
+    if member.suspended.value {
+        return Err(UserSuspended);
+    }
+
+    if !member.role.can_handle_reports() {
+        return Err(NotAuthorized);
+    }
+
+    let chat = &state.data.chat;
+
+    let report = match chat.reports.get(args.report_id) {
+        Some(r) if r.resolution.is_some() => return Err(AlreadyResolved),
+        Some(r) => r,
+        None => return Err(ReportNotFound),
+    };
+
+    // Escalating sends the message to the platform moderators, with this moderator as the reporter
+    let escalation = if matches!(args.action, ChatReportAction::EscalateToPlatform) {
+        let message = chat
+            .events
+            .events_reader(member.min_visible_event_index(), report.thread_root_message_index)
+            .and_then(|events_reader| events_reader.message(report.message_id.into(), Some(member.user_id)))
+            .ok_or(MessageNotFound)?;
+
+        Some((
+            c2c_report_message::Args {
+                reporter: member.user_id,
+                chat_id: MultiUserChat::Group(state.env.canister_id().into()),
+                thread_root_message_index: report.thread_root_message_index,
+                message,
+                already_deleted: false,
+            },
+            state.data.group_index_canister_id,
+        ))
+    } else {
+        None
+    };
+
+    Ok(PrepareResult {
+        resolved_by: member.user_id,
+        sender: report.sender,
+        escalation,
+    })
+}
+
+fn commit(args: Args, resolved_by: UserId, state: &mut RuntimeState) -> Response {
+    let now = state.env.now();
+    let chat = &mut state.data.chat;
+
+    let report = match chat.reports.get(args.report_id) {
+        Some(r) if r.resolution.is_some() => return AlreadyResolved,
+        Some(r) => r.clone(),
+        None => return ReportNotFound,
+    };
+
+    match args.action {
+        ChatReportAction::DeleteMessage => {
+            match chat.delete_messages(
+                resolved_by,
+                report.thread_root_message_index,
+                vec![report.message_id],
+                false,
+                now,
+            ) {
+                DeleteMessagesResult::Success(results) => match results[0].1 {
+                    DeleteMessageResult::Success(_) | DeleteMessageResult::AlreadyDeleted => {}
+                    DeleteMessageResult::NotFound => return MessageNotFound,
+                    DeleteMessageResult::NotAuthorized => return NotAuthorized,
+                },
+                DeleteMessagesResult::MessageNotFound => return MessageNotFound,
+                DeleteMessagesResult::UserNotInGroup => return CallerNotInGroup,
+                DeleteMessagesResult::UserSuspended => return UserSuspended,
+            }
+        }
+        ChatReportAction::TimeoutMember(duration) => match chat.timeout_member(resolved_by, report.sender, duration, now) {
+            TimeoutMemberResult::Success => {}
+            TimeoutMemberResult::InvalidDuration => return InvalidTimeout,
+            TimeoutMemberResult::TargetUserNotInGroup => return TargetUserNotInGroup,
+            TimeoutMemberResult::UserNotInGroup => return CallerNotInGroup,
+            TimeoutMemberResult::UserSuspended => return UserSuspended,
+            TimeoutMemberResult::NotAuthorized | TimeoutMemberResult::CannotTimeoutSelf => return NotAuthorized,
+        },
+        ChatReportAction::Dismiss | ChatReportAction::RemoveMember | ChatReportAction::EscalateToPlatform => {}
+    }
+
+    state.data.chat.reports.resolve(args.report_id, args.action, resolved_by, now);
+
+    if matches!(args.action, ChatReportAction::DeleteMessage) {
+        handle_activity_notification(state);
+    }
+
+    Success
+}
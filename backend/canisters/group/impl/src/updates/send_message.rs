@@ -1,5 +1,7 @@
 use crate::activity_notifications::handle_activity_notification;
-use crate::timer_job_types::{DeleteFileReferencesJob, EndPollJob, RefundPrizeJob, RemoveExpiredEventsJob};
+use crate::timer_job_types::{
+    DeleteFileReferencesJob, EndPollJob, RefundPrizeJob, RemoveExpiredEventsJob, SendCalendarEventReminderJob,
+};
 use crate::{mutate_state, run_regular_jobs, RuntimeState, TimerJob};
 use canister_api_macros::update_candid_and_msgpack;
 use canister_timer_jobs::TimerJobs;
@@ -120,6 +122,19 @@ fn register_timer_jobs(
         }
     }
 
+    if let MessageContent::CalendarEvent(e) = &message_event.event.content {
+        if let Some(reminder_time) = e.details.reminder_time(now) {
+            timer_jobs.enqueue_job(
+                TimerJob::SendCalendarEventReminder(SendCalendarEventReminderJob {
+                    thread_root_message_index,
+                    message_index: message_event.event.message_index,
+                }),
+                reminder_time,
+                now,
+            );
+        }
+    }
+
     let files = message_event.event.content.blob_references();
     if !files.is_empty() {
         if let Some(expiry) = message_event.expires_at {
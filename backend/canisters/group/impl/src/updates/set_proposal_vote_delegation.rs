@@ -0,0 +1,56 @@
+use crate::{mutate_state, run_regular_jobs, RuntimeState};
+use canister_tracing_macros::trace;
+use group_canister::set_proposal_vote_delegation::{Response::*, *};
+use ic_cdk_macros::update;
+use types::GroupSubtype;
+
+#[update]
+#[trace]
+fn set_proposal_vote_delegation(args: Args) -> Response {
+    run_regular_jobs();
+
+    mutate_state(|state| set_proposal_vote_delegation_impl(args, state))
+}
+
+fn set_proposal_vote_delegation_impl(args: Args, state: &mut RuntimeState) -> Response {
+    if state.data.is_frozen() {
+        return ChatFrozen;
+    }
+
+    let caller = state.env.caller();
+
+    let member = match state.data.get_member(caller) {
+        Some(p) => p,
+        None => return CallerNotInGroup,
+    };
+
+    if member.suspended.value {
+        return UserSuspended;
+    }
+
+    if !matches!(state.data.chat.subtype.value, Some(GroupSubtype::GovernanceProposals(_))) {
+        return NotProposalsGroup;
+    }
+
+    let user_id = member.user_id;
+
+    match args.delegate {
+        Some(delegate) if delegate == user_id => CannotDelegateToSelf,
+        Some(delegate) => {
+            if state.data.chat.members.get(&delegate).is_none() {
+                return DelegateNotInGroup;
+            }
+            let now = state.env.now();
+            state
+                .data
+                .chat
+                .proposal_vote_delegations
+                .set(user_id, delegate, args.topics, now);
+            Success
+        }
+        None => {
+            state.data.chat.proposal_vote_delegations.remove(&user_id);
+            Success
+        }
+    }
+}
@@ -0,0 +1,38 @@
+use crate::{mutate_state, run_regular_jobs, RuntimeState};
+use canister_tracing_macros::trace;
+use group_canister::set_typing::{Response::*, *};
+use group_chat_core::SetTypingResult;
+use ic_cdk_macros::update;
+
+#[update]
+#[trace]
+fn set_typing(args: Args) -> Response {
+    run_regular_jobs();
+
+    mutate_state(|state| set_typing_impl(args, state))
+}
+
+fn set_typing_impl(args: Args, state: &mut RuntimeState) -> Response {
+    if state.data.is_frozen() {
+        return GroupFrozen;
+    }
+
+    let caller = state.env.caller();
+
+    let user_id = match state.data.lookup_user_id(caller) {
+        Some(uid) => uid,
+        None => return UserNotInGroup,
+    };
+
+    let now = state.env.now();
+
+    match state
+        .data
+        .chat
+        .set_typing(user_id, args.thread_root_message_index, args.typing, now)
+    {
+        SetTypingResult::Success => Success,
+        SetTypingResult::UserNotInGroup => UserNotInGroup,
+        SetTypingResult::UserSuspended => UserSuspended,
+    }
+}
@@ -7,7 +7,7 @@ use group_chat_core::UpdateResult;
 use group_index_canister::{c2c_make_private, c2c_update_group};
 use ic_cdk_macros::update;
 use tracing::error;
-use types::{AccessGate, CanisterId, ChatId, Document, UserId};
+use types::{AccessGate, CanisterId, ChatId, Document, OptionUpdate, UserId};
 
 #[update]
 #[trace]
@@ -87,6 +87,11 @@ fn prepare(args: &Args, state: &RuntimeState) -> Result<PrepareResult, Response>
         return Err(ChatFrozen);
     }
 
+    // User group gates are only supported on community channels
+    if matches!(args.gate, OptionUpdate::SetToSome(AccessGate::UserGroup(_))) {
+        return Err(InvalidGate);
+    }
+
     let caller = state.env.caller();
     let gate = args.gate.as_ref().apply_to(state.data.chat.gate.value.as_ref());
 
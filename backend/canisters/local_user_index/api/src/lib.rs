@@ -30,6 +30,8 @@ pub enum Event {
     DiamondMembershipPaymentReceived(DiamondMembershipPaymentReceived),
     OpenChatBotMessage(Box<OpenChatBotMessage>),
     ReferralCodeAdded(ReferralCodeAdded),
+    ReferralCampaignAdded(ReferralCampaignAdded),
+    ReferralCampaignEnded(ReferralCampaignEnded),
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug)]
@@ -134,6 +136,17 @@ pub struct ReferralCodeAdded {
     pub expiry: Option<TimestampMillis>,
 }
 
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct ReferralCampaignAdded {
+    pub code: String,
+    pub expiry: Option<TimestampMillis>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct ReferralCampaignEnded {
+    pub code: String,
+}
+
 #[derive(CandidType, Serialize, Deserialize, Debug)]
 pub struct GlobalUser {
     pub user_id: UserId,
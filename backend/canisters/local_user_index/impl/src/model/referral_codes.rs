@@ -7,20 +7,30 @@ use types::{ReferralType, TimestampMillis, UserId};
 pub enum ReferralCode {
     BtcMiami(String),
     User(UserId),
+    Campaign(String),
 }
 
 impl ReferralCode {
     pub fn user(&self) -> Option<UserId> {
         match self {
-            ReferralCode::BtcMiami(_) => None,
+            ReferralCode::BtcMiami(_) | ReferralCode::Campaign(_) => None,
             ReferralCode::User(user_id) => Some(*user_id),
         }
     }
+
+    pub fn campaign(&self) -> Option<String> {
+        match self {
+            ReferralCode::Campaign(code) => Some(code.clone()),
+            _ => None,
+        }
+    }
 }
 
 #[derive(Serialize, Deserialize, Default)]
 pub struct ReferralCodes {
     codes: HashMap<String, ReferralCodeDetails>,
+    #[serde(default)]
+    campaigns: HashMap<String, ReferralCampaignDetails>,
 }
 
 #[derive(Serialize, Deserialize)]
@@ -31,6 +41,14 @@ pub struct ReferralCodeDetails {
     expiry: Option<TimestampMillis>,
 }
 
+// Campaign codes can be used many times, so usage is counted by the UserIndex, which ends the
+// campaign across all LocalUserIndexes once its usage cap is reached
+#[derive(Serialize, Deserialize)]
+pub struct ReferralCampaignDetails {
+    expiry: Option<TimestampMillis>,
+    ended: bool,
+}
+
 #[derive(Serialize, Deserialize)]
 pub struct ReferralCodeClaim {
     when: TimestampMillis,
@@ -87,6 +105,16 @@ impl ReferralCodes {
         }
     }
 
+    pub fn add_campaign(&mut self, code: String, expiry: Option<TimestampMillis>) {
+        self.campaigns.insert(code, ReferralCampaignDetails { expiry, ended: false });
+    }
+
+    pub fn end_campaign(&mut self, code: &str) {
+        if let Some(campaign) = self.campaigns.get_mut(code) {
+            campaign.ended = true;
+        }
+    }
+
     pub fn check(&self, code: &String, now: TimestampMillis) -> Result<ReferralCode, ReferralCodeError> {
         if code.len() > 100 {
             return Err(ReferralCodeError::NotFound);
@@ -100,6 +128,12 @@ impl ReferralCodes {
             } else {
                 Ok(ReferralCode::BtcMiami(code.clone()))
             }
+        } else if let Some(campaign) = self.campaigns.get(code) {
+            if campaign.ended || campaign.expiry.as_ref().map_or(false, |ts| *ts < now) {
+                Err(ReferralCodeError::Expired)
+            } else {
+                Ok(ReferralCode::Campaign(code.clone()))
+            }
         } else if let Ok(user_id) = Principal::from_text(code).map(|p| p.into()) {
             Ok(ReferralCode::User(user_id))
         } else {
@@ -146,5 +146,11 @@ fn handle_event(event: Event, state: &mut RuntimeState) {
                 .referral_codes
                 .add(ev.referral_type, ev.code, ev.expiry, state.env.now());
         }
+        Event::ReferralCampaignAdded(ev) => {
+            state.data.referral_codes.add_campaign(ev.code, ev.expiry);
+        }
+        Event::ReferralCampaignEnded(ev) => {
+            state.data.referral_codes.end_campaign(&ev.code);
+        }
     }
 }
@@ -207,6 +207,7 @@ fn commit(
         username: username.clone(),
         display_name: display_name.clone(),
         referred_by: referral_code.as_ref().and_then(|r| r.user()),
+        referral_campaign: referral_code.as_ref().and_then(|r| r.campaign()),
     })));
 
     match referral_code {
@@ -246,7 +247,8 @@ fn commit(
                 now,
             )
         }
-        None => {}
+        // Campaign codes are handled by the UserIndex, which joins the user to the campaign's group or community
+        Some(ReferralCode::Campaign(_)) | None => {}
     }
 }
 
@@ -1,6 +1,7 @@
 use candid::CandidType;
 use serde::{Deserialize, Serialize};
 use std::fmt::{Display, Formatter};
+use types::{Milliseconds, OrderType, TimestampMillis};
 
 mod lifecycle;
 mod queries;
@@ -11,6 +12,8 @@ pub use queries::*;
 pub use updates::*;
 
 pub const ICDEX_EXCHANGE_ID: ExchangeId = ExchangeId::new(1);
+pub const ICPSWAP_EXCHANGE_ID: ExchangeId = ExchangeId::new(2);
+pub const SONIC_EXCHANGE_ID: ExchangeId = ExchangeId::new(3);
 
 #[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
 pub struct ExchangeInfo {
@@ -38,3 +41,65 @@ impl Display for ExchangeId {
         write!(f, "{}", self.0)
     }
 }
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug, Default)]
+pub enum Strategy {
+    #[default]
+    FixedLadder,
+    InventorySkewed(InventorySkewedStrategy),
+    TwapAnchored(TwapAnchoredStrategy),
+}
+
+// Shifts both the bids and asks down when holding more than the target proportion of the base token
+// (by value) and up when holding less, by up to `max_skew` price increments
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct InventorySkewedStrategy {
+    pub target_base_ratio_bps: u32,
+    pub max_skew: u64,
+}
+
+// Keeps bids and asks within `max_deviation` price increments of the time weighted average price
+// over `window`, so that sudden price moves are not chased
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct TwapAnchoredStrategy {
+    pub window: Milliseconds,
+    pub max_deviation: u64,
+}
+
+// Once the base token balance reaches `max_base_balance` no more bids are made, and once the quote
+// token balance reaches `max_quote_balance` no more asks are made
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct InventoryLimits {
+    pub max_base_balance: u64,
+    pub max_quote_balance: u64,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct TradingHistoryEntry {
+    pub index: u64,
+    pub timestamp: TimestampMillis,
+    pub exchange_id: ExchangeId,
+    pub event: TradingEvent,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub enum TradingEvent {
+    Fill(Fill),
+    Snapshot(BalanceSnapshot),
+}
+
+// An order (or part of an order) which was detected as having been taken since the previous round
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct Fill {
+    pub order_type: OrderType,
+    pub price: u64,
+    pub amount: u64,
+}
+
+// Balances include any funds held by the exchange to cover our open orders
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct BalanceSnapshot {
+    pub base_balance: u64,
+    pub quote_balance: u64,
+    pub mid_price: u64,
+}
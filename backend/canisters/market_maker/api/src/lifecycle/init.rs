@@ -1,5 +1,7 @@
+use crate::ExchangeId;
 use candid::CandidType;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use types::{BuildVersion, CanisterId};
 
 #[derive(CandidType, Serialize, Deserialize, Debug)]
@@ -8,6 +10,7 @@ pub struct Args {
     pub cycles_dispenser_canister_id: CanisterId,
     pub icp_ledger_canister_id: CanisterId,
     pub chat_ledger_canister_id: CanisterId,
+    pub exchange_canister_ids: HashMap<ExchangeId, CanisterId>,
     pub wasm_version: BuildVersion,
     pub test_mode: bool,
 }
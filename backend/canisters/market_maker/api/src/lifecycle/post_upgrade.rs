@@ -1,8 +1,12 @@
+use crate::ExchangeId;
 use candid::CandidType;
 use serde::{Deserialize, Serialize};
-use types::BuildVersion;
+use std::collections::HashMap;
+use types::{BuildVersion, CanisterId};
 
 #[derive(CandidType, Serialize, Deserialize, Debug)]
 pub struct Args {
     pub wasm_version: BuildVersion,
+    #[serde(default)]
+    pub exchange_canister_ids: HashMap<ExchangeId, CanisterId>,
 }
@@ -2,6 +2,7 @@ use candid_gen::generate_candid_method;
 
 #[allow(deprecated)]
 fn main() {
+    generate_candid_method!(market_maker, trading_history, query);
     generate_candid_method!(market_maker, update_config, update);
 
     candid::export_service!();
@@ -1 +1,2 @@
 pub mod list_exchanges;
+pub mod trading_history;
@@ -0,0 +1,28 @@
+use crate::{ExchangeId, TradingHistoryEntry};
+use candid::CandidType;
+use serde::{Deserialize, Serialize};
+use types::TimestampMillis;
+
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub struct Args {
+    pub exchange_id: Option<ExchangeId>,
+    pub from: Option<TimestampMillis>,
+    pub to: Option<TimestampMillis>,
+    // Entries are returned oldest first, starting from `start` if set, otherwise from the first
+    // entry at or after `from`
+    pub start: Option<u64>,
+    pub max_results: u32,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub enum Response {
+    Success(SuccessResult),
+}
+
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub struct SuccessResult {
+    pub entries: Vec<TradingHistoryEntry>,
+    // If set, there may be more matching entries, which can be retrieved by passing this value as
+    // `start` in the next request
+    pub next: Option<u64>,
+}
@@ -1,6 +1,7 @@
-use crate::ExchangeId;
+use crate::{ExchangeId, InventoryLimits, Strategy};
 use candid::CandidType;
 use serde::{Deserialize, Serialize};
+use types::{CanisterId, OptionUpdate};
 
 #[derive(CandidType, Serialize, Deserialize, Debug)]
 pub struct Args {
@@ -16,12 +17,16 @@ pub struct Args {
     pub max_orders_per_direction: Option<u32>,
     pub max_orders_to_make_per_iteration: Option<u32>,
     pub max_orders_to_cancel_per_iteration: Option<u32>,
+    pub strategy: Option<Strategy>,
+    pub inventory_limits: OptionUpdate<InventoryLimits>,
+    pub exchange_canister_id: Option<CanisterId>,
 }
 
 #[derive(CandidType, Serialize, Deserialize, Debug)]
 pub enum Response {
     Success,
     ExchangeNotFound,
+    InvalidConfig(String),
     NotAuthorized,
     InternalError(String),
 }
@@ -0,0 +1,279 @@
+use crate::exchanges::Exchange;
+use crate::{mutate_state, read_state};
+use async_trait::async_trait;
+use ic_cdk::api::call::{CallResult, RejectionCode};
+use icrc_ledger_types::icrc1::transfer::TransferArg;
+use market_maker_canister::ExchangeId;
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use swap_client::SwapClient;
+use tracing::error;
+use types::{AggregatedOrders, CancelOrderRequest, MakeOrderRequest, MarketState, Order, OrderType, TokenInfo};
+
+// AMMs such as ICPSwap and Sonic have no orderbook, so orders are held by the market maker and are
+// filled by swapping against the pool once the pool's price crosses the order's price. The
+// orderbook returned to the market maker is made up of the pool's current bid and ask prices.
+pub struct AmmExchange {
+    exchange_id: ExchangeId,
+    quote_token: TokenInfo,
+    base_token: TokenInfo,
+    buy_client: Box<dyn SwapClient>,
+    sell_client: Box<dyn SwapClient>,
+}
+
+// Funds which were transferred into an exchange but which weren't swapped, either because
+// depositing them failed or because the swap failed and then withdrawing them failed
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct AmmDepositRecovery {
+    order_type: OrderType,
+    amount: u128,
+    deposited: bool,
+}
+
+impl AmmExchange {
+    pub fn new(
+        exchange_id: ExchangeId,
+        quote_token: TokenInfo,
+        base_token: TokenInfo,
+        buy_client: Box<dyn SwapClient>,
+        sell_client: Box<dyn SwapClient>,
+    ) -> AmmExchange {
+        AmmExchange {
+            exchange_id,
+            quote_token,
+            base_token,
+            buy_client,
+            sell_client,
+        }
+    }
+
+    // Returns the price (in quote token units per whole base token) which we would receive for
+    // selling one whole base token and the price we would pay to buy one whole base token
+    async fn pool_prices(&self) -> CallResult<(u64, u64)> {
+        let bid = self.sell_client.quote(self.base_token_units_per_whole() as u128).await?;
+        if bid == 0 {
+            return Err((RejectionCode::Unknown, "Pool has no liquidity".to_string()));
+        }
+
+        let base_out = self.buy_client.quote(bid).await?;
+        if base_out == 0 {
+            return Err((RejectionCode::Unknown, "Pool has no liquidity".to_string()));
+        }
+        let ask = bid * self.base_token_units_per_whole() as u128 / base_out;
+
+        Ok((bid as u64, ask as u64))
+    }
+
+    async fn fill_order(&self, order: &Order) -> CallResult<()> {
+        let quote_amount = order.amount as u128 * order.price as u128 / self.base_token_units_per_whole() as u128;
+
+        match order.order_type {
+            OrderType::Bid => self.swap(order.order_type, quote_amount, order.amount as u128).await,
+            OrderType::Ask => self.swap(order.order_type, order.amount as u128, quote_amount).await,
+        }
+    }
+
+    // Returns the client used to swap in the given direction, the client used for the opposite
+    // direction (which withdraws our input token, so is used to recover deposits), the input token
+    // and the output token
+    fn swap_direction(&self, order_type: OrderType) -> (&dyn SwapClient, &dyn SwapClient, &TokenInfo, &TokenInfo) {
+        match order_type {
+            OrderType::Bid => (&*self.buy_client, &*self.sell_client, &self.quote_token, &self.base_token),
+            OrderType::Ask => (&*self.sell_client, &*self.buy_client, &self.base_token, &self.quote_token),
+        }
+    }
+
+    // Transfers the input token into the exchange, swaps it, then withdraws the output token. If the
+    // swap fails (including when the amount out would be less than `min_amount_out`) the input is
+    // withdrawn again. If the deposit or that withdrawal fails, recovering the funds is queued to be
+    // retried on subsequent runs, so that they are not left sitting in the exchange.
+    async fn swap(&self, order_type: OrderType, amount: u128, min_amount_out: u128) -> CallResult<()> {
+        let (client, _, input_token, output_token) = self.swap_direction(order_type);
+        let account = client.deposit_account().await?;
+
+        icrc_ledger_canister_c2c_client::icrc1_transfer(
+            input_token.ledger,
+            &TransferArg {
+                from_subaccount: None,
+                to: account,
+                fee: None,
+                created_at_time: None,
+                memo: None,
+                amount: (amount + input_token.fee).into(),
+            },
+        )
+        .await?
+        .map_err(|t| (RejectionCode::Unknown, format!("{t:?}")))?;
+
+        if let Err(error) = client.deposit(amount).await {
+            self.queue_recovery(AmmDepositRecovery {
+                order_type,
+                amount,
+                deposited: false,
+            });
+            return Err(error);
+        }
+
+        let amount_out = match client.swap(amount, min_amount_out).await {
+            Ok(a) => a,
+            Err(error) => {
+                self.recover_deposit(AmmDepositRecovery {
+                    order_type,
+                    amount,
+                    deposited: true,
+                })
+                .await;
+                return Err(error);
+            }
+        };
+
+        client.withdraw(amount_out.saturating_sub(output_token.fee)).await?;
+        Ok(())
+    }
+
+    // Retries recovering any deposits which were left in the exchange by previous failed swaps
+    async fn recover_queued_deposits(&self) {
+        let recoveries = mutate_state(|state| {
+            state
+                .data
+                .amm_deposit_recoveries
+                .remove(&self.exchange_id)
+                .unwrap_or_default()
+        });
+
+        for recovery in recoveries {
+            self.recover_deposit(recovery).await;
+        }
+    }
+
+    // Deposits the funds into the exchange if that had previously failed, then withdraws them back
+    // out. If either step fails the recovery is queued to be retried.
+    async fn recover_deposit(&self, mut recovery: AmmDepositRecovery) {
+        let (client, refund_client, input_token, _) = self.swap_direction(recovery.order_type);
+
+        if !recovery.deposited {
+            if let Err(error) = client.deposit(recovery.amount).await {
+                error!(exchange_id = %self.exchange_id, ?error, "Failed to deposit funds into exchange, will retry");
+                self.queue_recovery(recovery);
+                return;
+            }
+            recovery.deposited = true;
+        }
+
+        if let Err(error) = refund_client.withdraw(recovery.amount.saturating_sub(input_token.fee)).await {
+            error!(exchange_id = %self.exchange_id, ?error, "Failed to withdraw deposit from exchange, will retry");
+            self.queue_recovery(recovery);
+        }
+    }
+
+    fn queue_recovery(&self, recovery: AmmDepositRecovery) {
+        mutate_state(|state| {
+            state
+                .data
+                .amm_deposit_recoveries
+                .entry(self.exchange_id)
+                .or_default()
+                .push(recovery)
+        });
+    }
+
+    fn base_token_units_per_whole(&self) -> u64 {
+        10u64.pow(self.base_token.decimals as u32)
+    }
+}
+
+#[async_trait]
+impl Exchange for AmmExchange {
+    fn exchange_id(&self) -> ExchangeId {
+        self.exchange_id
+    }
+
+    fn open_orders_hold_funds(&self) -> bool {
+        false
+    }
+
+    async fn market_state(&self) -> CallResult<MarketState> {
+        let (bid, ask) = self.pool_prices().await?;
+
+        let my_open_orders = read_state(|state| state.data.amm_orders.get(&self.exchange_id).cloned().unwrap_or_default());
+
+        let mut orderbook = AggregatedOrders::default();
+        orderbook.add(OrderType::Bid, bid, self.base_token_units_per_whole());
+        orderbook.add(OrderType::Ask, ask, self.base_token_units_per_whole());
+
+        Ok(MarketState {
+            latest_price: (bid + ask) / 2,
+            my_open_orders,
+            orderbook,
+        })
+    }
+
+    // Fills each of our open orders whose price has been crossed by the pool's price by swapping
+    // against the pool. Orders which fail to be filled are left open and retried next round.
+    async fn fill_crossed_orders(&self, market_state: &MarketState) -> Vec<String> {
+        self.recover_queued_deposits().await;
+
+        let (bid, ask) = match (
+            market_state.orderbook.bids.keys().max().copied(),
+            market_state.orderbook.asks.keys().min().copied(),
+        ) {
+            (Some(bid), Some(ask)) => (bid, ask),
+            _ => return Vec::new(),
+        };
+
+        let mut filled = Vec::new();
+        for order in market_state.my_open_orders.iter() {
+            let crossed = match order.order_type {
+                OrderType::Bid => order.price >= ask,
+                OrderType::Ask => order.price <= bid,
+            };
+            if !crossed {
+                continue;
+            }
+            match self.fill_order(order).await {
+                Ok(_) => {
+                    mutate_state(|state| {
+                        if let Some(orders) = state.data.amm_orders.get_mut(&self.exchange_id) {
+                            orders.retain(|o| o.id != order.id);
+                        }
+                    });
+                    filled.push(order.id.clone());
+                }
+                Err(error) => {
+                    error!(exchange_id = %self.exchange_id, order_id = order.id.as_str(), ?error, "Failed to fill order");
+                }
+            }
+        }
+        filled
+    }
+
+    async fn make_orders(&self, orders: Vec<MakeOrderRequest>) -> CallResult<()> {
+        mutate_state(|state| {
+            let now = state.env.now();
+            for order in orders {
+                let id = format!("{:x}", state.env.rng().next_u64());
+                state.data.amm_orders.entry(self.exchange_id).or_default().push(Order {
+                    order_type: order.order_type,
+                    id,
+                    price: order.price,
+                    amount: order.amount,
+                });
+                state.data.orders_log.log_order_made(self.exchange_id, order, now);
+            }
+        });
+        Ok(())
+    }
+
+    async fn cancel_orders(&self, orders: Vec<CancelOrderRequest>) -> CallResult<()> {
+        mutate_state(|state| {
+            let now = state.env.now();
+            for order in orders {
+                if let Some(open_orders) = state.data.amm_orders.get_mut(&self.exchange_id) {
+                    open_orders.retain(|o| o.id != order.id);
+                }
+                state.data.orders_log.log_order_cancelled(self.exchange_id, order, now);
+            }
+        });
+        Ok(())
+    }
+}
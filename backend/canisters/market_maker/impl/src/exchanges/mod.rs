@@ -3,12 +3,23 @@ use ic_cdk::api::call::CallResult;
 use market_maker_canister::ExchangeId;
 use types::{CancelOrderRequest, MakeOrderRequest, MarketState};
 
+pub mod amm;
 pub mod icdex;
 
 #[async_trait]
 pub trait Exchange {
     fn exchange_id(&self) -> ExchangeId;
+    // Whether the funds needed to cover our open orders are transferred to the exchange when the
+    // orders are made
+    fn open_orders_hold_funds(&self) -> bool {
+        true
+    }
     async fn market_state(&self) -> CallResult<MarketState>;
+    // Exchanges which don't match our orders themselves fill any orders which the market has
+    // crossed, returning the ids of the orders which were filled
+    async fn fill_crossed_orders(&self, _market_state: &MarketState) -> Vec<String> {
+        Vec::new()
+    }
     async fn make_orders(&self, orders: Vec<MakeOrderRequest>) -> CallResult<()>;
     async fn cancel_orders(&self, orders: Vec<CancelOrderRequest>) -> CallResult<()>;
 }
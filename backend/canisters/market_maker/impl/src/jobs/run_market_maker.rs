@@ -1,14 +1,16 @@
 use crate::exchanges::Exchange;
+use crate::strategies::{build_strategy, StrategyInputs};
 use crate::{mutate_state, read_state, Config, RuntimeState};
-use ic_cdk::api::call::CallResult;
+use ic_cdk::api::call::{CallResult, RejectionCode};
+use icrc_ledger_types::icrc1::account::Account;
 use itertools::Itertools;
-use market_maker_canister::ExchangeId;
+use market_maker_canister::{BalanceSnapshot, ExchangeId, Fill, InventoryLimits};
 use std::cmp::{max, min, Reverse};
 use std::collections::btree_map::Entry::Occupied;
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, HashSet};
 use std::time::Duration;
 use tracing::trace;
-use types::{AggregatedOrders, CancelOrderRequest, MakeOrderRequest, Milliseconds, Order, OrderType};
+use types::{AggregatedOrders, CancelOrderRequest, CanisterId, MakeOrderRequest, Milliseconds, Order, OrderType};
 use utils::time::MINUTE_IN_MS;
 
 const RUN_MARKET_MAKER_INTERVAL: Milliseconds = MINUTE_IN_MS;
@@ -58,12 +60,29 @@ async fn run_single(exchange_client: Box<dyn Exchange>, config: Config) -> CallR
     let exchange_id = exchange_client.exchange_id();
     trace!(%exchange_id, "Running market maker");
 
-    let my_previous_open_orders = mutate_state(|state| {
+    let (my_previous_open_orders, this_canister_id, quote_token, base_token) = mutate_state(|state| {
         state.data.market_makers_in_progress.insert(exchange_id, state.env.now());
-        state.data.my_open_orders.get(&exchange_id).cloned()
+        (
+            state.data.my_open_orders.get(&exchange_id).cloned(),
+            state.env.canister_id(),
+            state.quote_token(),
+            state.base_token(),
+        )
     });
 
-    let market_state = exchange_client.market_state().await?;
+    let mut market_state = exchange_client.market_state().await?;
+
+    let filled = exchange_client.fill_crossed_orders(&market_state).await;
+    if !filled.is_empty() {
+        market_state.my_open_orders.retain(|o| !filled.contains(&o.id));
+    }
+
+    // Balances are retrieved after filling crossed orders since filling orders changes our balances
+    let (quote_balance, base_balance) = futures::future::try_join(
+        get_balance(quote_token.ledger, this_canister_id),
+        get_balance(base_token.ledger, this_canister_id),
+    )
+    .await?;
 
     let (current_bid, current_ask) = match (
         market_state.orderbook.bids.keys().max().copied(),
@@ -75,22 +94,84 @@ async fn run_single(exchange_client: Box<dyn Exchange>, config: Config) -> CallR
 
     let my_open_orders_aggregated: AggregatedOrders = market_state.my_open_orders.as_slice().into();
 
-    let (latest_bid_taken, latest_ask_taken) =
-        calculate_orders_taken_since_previous_round(&my_open_orders_aggregated, my_previous_open_orders.as_ref());
+    let fills = calculate_orders_taken_since_previous_round(&my_open_orders_aggregated, my_previous_open_orders.as_ref());
+    let latest_bid_taken = fills
+        .iter()
+        .filter(|f| matches!(f.order_type, OrderType::Bid))
+        .map(|f| f.price)
+        .min();
+    let latest_ask_taken = fills
+        .iter()
+        .filter(|f| matches!(f.order_type, OrderType::Ask))
+        .map(|f| f.price)
+        .max();
+
+    let base_token_units_per_whole = 10u64.pow(base_token.decimals as u32);
+    let snapshot = if exchange_client.open_orders_hold_funds() {
+        let (base_in_orders, quote_in_orders) = funds_held_in_orders(&market_state.my_open_orders, base_token_units_per_whole);
+        BalanceSnapshot {
+            base_balance: base_balance + base_in_orders,
+            quote_balance: quote_balance + quote_in_orders,
+            mid_price: (current_bid + current_ask) / 2,
+        }
+    } else {
+        BalanceSnapshot {
+            base_balance,
+            quote_balance,
+            mid_price: (current_bid + current_ask) / 2,
+        }
+    };
+
+    let strategy = mutate_state(|state| {
+        let now = state.env.now();
+        for fill in fills {
+            state.data.trading_history.record_fill(exchange_id, fill, now);
+        }
+        state.data.trading_history.record_snapshot_if_due(exchange_id, snapshot, now);
+
+        let price_history = state.data.price_history.entry(exchange_id).or_default();
+        price_history.push(market_state.latest_price, now);
+        build_strategy(&config, Some(&*price_history), now)
+    });
 
     let (max_bid_price, min_ask_price) =
         calculate_price_limits(current_bid, current_ask, latest_bid_taken, latest_ask_taken, &config);
 
-    let orders_to_make = calculate_orders_to_make(max_bid_price, min_ask_price, my_open_orders_aggregated, &config);
+    let strategy_inputs = StrategyInputs {
+        current_bid,
+        current_ask,
+        latest_price: market_state.latest_price,
+        base_balance,
+        quote_balance,
+        base_token_units_per_whole,
+    };
+    let (max_bid_price, min_ask_price) = strategy.adjust_price_limits(max_bid_price, min_ask_price, &strategy_inputs, &config);
+    let max_bid_price = round_down_to_next_increment(max_bid_price, config.price_increment);
+    let min_ask_price = round_up_to_next_increment(min_ask_price, config.price_increment);
+
+    let mut orders_to_make = calculate_orders_to_make(max_bid_price, min_ask_price, my_open_orders_aggregated, &config);
 
-    let orders_to_cancel = calculate_orders_to_cancel(
+    let mut orders_to_cancel = calculate_orders_to_cancel(
         &market_state.my_open_orders,
         config.max_orders_per_direction as usize,
         config.max_orders_to_cancel_per_iteration as usize,
     );
 
+    if let Some(limits) = &config.inventory_limits {
+        apply_inventory_limits(
+            limits,
+            base_balance,
+            quote_balance,
+            &market_state.my_open_orders,
+            &mut orders_to_make,
+            &mut orders_to_cancel,
+            config.max_orders_to_cancel_per_iteration as usize,
+        );
+    }
+
     let orders_made = orders_to_make.len();
     let orders_cancelled = orders_to_cancel.len();
+    let cancelled_ids: HashSet<_> = orders_to_cancel.iter().map(|o| o.id.clone()).collect();
 
     futures::future::try_join(
         exchange_client.make_orders(orders_to_make.clone()),
@@ -98,7 +179,11 @@ async fn run_single(exchange_client: Box<dyn Exchange>, config: Config) -> CallR
     )
     .await?;
 
-    let mut my_open_orders: AggregatedOrders = market_state.my_open_orders.as_slice().into();
+    // Exclude the cancelled orders so that next round they aren't mistaken for orders which were taken
+    let mut my_open_orders = AggregatedOrders::default();
+    for order in market_state.my_open_orders.iter().filter(|o| !cancelled_ids.contains(&o.id)) {
+        my_open_orders.add(order.order_type, order.price, order.amount);
+    }
     for order in orders_to_make {
         my_open_orders.add(order.order_type, order.price, order.amount);
     }
@@ -115,33 +200,51 @@ fn mark_market_maker_complete(exchange_id: &ExchangeId) {
     mutate_state(|state| state.data.market_makers_in_progress.remove(exchange_id));
 }
 
+// Returns the orders which were open at the end of the previous round but have since been taken,
+// working inwards from the best bid and ask until reaching a price at which we still have an order
 fn calculate_orders_taken_since_previous_round(
     my_open_orders: &AggregatedOrders,
     my_previous_open_orders: Option<&AggregatedOrders>,
-) -> (Option<u64>, Option<u64>) {
+) -> Vec<Fill> {
     if let Some(previous_orders) = my_previous_open_orders {
-        let bids_taken: Vec<_> = previous_orders
+        let bids_taken = previous_orders
             .bids
-            .keys()
+            .iter()
             .rev()
-            .take_while(|p| !my_open_orders.bids.contains_key(p))
-            .copied()
-            .collect();
-
-        let asks_taken: Vec<_> = previous_orders
+            .take_while(|(p, _)| !my_open_orders.bids.contains_key(p))
+            .map(|(&price, &amount)| Fill {
+                order_type: OrderType::Bid,
+                price,
+                amount,
+            });
+
+        let asks_taken = previous_orders
             .asks
-            .keys()
-            .take_while(|p| !my_open_orders.asks.contains_key(p))
-            .copied()
-            .collect();
-
-        let latest_bid_taken = bids_taken.iter().min().copied();
-        let latest_ask_taken = asks_taken.iter().max().copied();
-
-        (latest_bid_taken, latest_ask_taken)
+            .iter()
+            .take_while(|(p, _)| !my_open_orders.asks.contains_key(p))
+            .map(|(&price, &amount)| Fill {
+                order_type: OrderType::Ask,
+                price,
+                amount,
+            });
+
+        bids_taken.chain(asks_taken).collect()
     } else {
-        (None, None)
+        Vec::new()
+    }
+}
+
+// Returns the amount of the base and quote tokens held by the exchange to cover our open orders
+fn funds_held_in_orders(my_open_orders: &[Order], base_token_units_per_whole: u64) -> (u64, u64) {
+    let mut base = 0;
+    let mut quote = 0;
+    for order in my_open_orders {
+        match order.order_type {
+            OrderType::Bid => quote += order.amount * order.price / base_token_units_per_whole,
+            OrderType::Ask => base += order.amount,
+        }
     }
+    (base, quote)
 }
 
 fn calculate_price_limits(
@@ -290,6 +393,41 @@ fn calculate_orders_to_cancel(
         .collect()
 }
 
+// Stops quoting whichever side would increase a balance which has reached its limit, including
+// cancelling any open orders on that side
+fn apply_inventory_limits(
+    limits: &InventoryLimits,
+    base_balance: u64,
+    quote_balance: u64,
+    my_open_orders: &[Order],
+    orders_to_make: &mut Vec<MakeOrderRequest>,
+    orders_to_cancel: &mut Vec<CancelOrderRequest>,
+    max_orders_to_cancel: usize,
+) {
+    let stop_bids = base_balance >= limits.max_base_balance;
+    let stop_asks = quote_balance >= limits.max_quote_balance;
+    let is_stopped = |order_type: OrderType| match order_type {
+        OrderType::Bid => stop_bids,
+        OrderType::Ask => stop_asks,
+    };
+
+    orders_to_make.retain(|o| !is_stopped(o.order_type));
+
+    for order in my_open_orders.iter().filter(|o| is_stopped(o.order_type)) {
+        if orders_to_cancel.len() >= max_orders_to_cancel {
+            break;
+        }
+        if !orders_to_cancel.iter().any(|o| o.id == order.id) {
+            orders_to_cancel.push(CancelOrderRequest { id: order.id.clone() });
+        }
+    }
+}
+
+async fn get_balance(ledger_canister_id: CanisterId, owner: CanisterId) -> CallResult<u64> {
+    let balance = icrc_ledger_canister_c2c_client::icrc1_balance_of(ledger_canister_id, &Account::from(owner)).await?;
+    u64::try_from(balance.0).map_err(|_| (RejectionCode::Unknown, "Balance exceeds u64::MAX".to_string()))
+}
+
 fn build_orders(max_bid_price: u64, min_ask_price: u64, config: &Config) -> (Vec<MakeOrderRequest>, Vec<MakeOrderRequest>) {
     let starting_bid = round_down_to_next_increment(max_bid_price, config.price_increment);
     let starting_ask = round_up_to_next_increment(min_ask_price, config.price_increment);
@@ -333,6 +471,7 @@ fn round_up_to_next_increment(price: u64, increment: u64) -> u64 {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use market_maker_canister::Strategy;
     use test_case::test_case;
 
     #[test_case(100, 10, 100)]
@@ -384,6 +523,8 @@ mod tests {
             max_orders_per_direction: 5,
             max_orders_to_make_per_iteration: 2,
             max_orders_to_cancel_per_iteration: 2,
+            strategy: Strategy::FixedLadder,
+            inventory_limits: None,
         };
 
         let (max_bid_price, min_ask_price) =
@@ -392,4 +533,60 @@ mod tests {
         assert_eq!(max_bid_price, expected_max_bid_price);
         assert_eq!(min_ask_price, expected_min_ask_price);
     }
+
+    #[test_case(100, 100, 2, 2)]
+    #[test_case(200, 100, 0, 2)]
+    #[test_case(100, 200, 2, 0)]
+    #[test_case(200, 200, 0, 0)]
+    fn apply_inventory_limits_tests(base_balance: u64, quote_balance: u64, expected_bids: usize, expected_asks: usize) {
+        let limits = InventoryLimits {
+            max_base_balance: 150,
+            max_quote_balance: 150,
+        };
+        let my_open_orders = vec![
+            Order {
+                order_type: OrderType::Bid,
+                id: "1".to_string(),
+                price: 40,
+                amount: 10,
+            },
+            Order {
+                order_type: OrderType::Ask,
+                id: "2".to_string(),
+                price: 60,
+                amount: 10,
+            },
+        ];
+        let mut orders_to_make = vec![
+            MakeOrderRequest {
+                order_type: OrderType::Bid,
+                price: 50,
+                amount: 10,
+            },
+            MakeOrderRequest {
+                order_type: OrderType::Ask,
+                price: 50,
+                amount: 10,
+            },
+        ];
+        let mut orders_to_cancel = Vec::new();
+
+        apply_inventory_limits(
+            &limits,
+            base_balance,
+            quote_balance,
+            &my_open_orders,
+            &mut orders_to_make,
+            &mut orders_to_cancel,
+            5,
+        );
+
+        let bids_made = orders_to_make.iter().filter(|o| o.order_type == OrderType::Bid).count();
+        let asks_made = orders_to_make.iter().filter(|o| o.order_type == OrderType::Ask).count();
+        let bids_cancelled = orders_to_cancel.iter().filter(|o| o.id == "1").count();
+        let asks_cancelled = orders_to_cancel.iter().filter(|o| o.id == "2").count();
+
+        assert_eq!(bids_made + (1 - bids_cancelled), expected_bids);
+        assert_eq!(asks_made + (1 - asks_cancelled), expected_asks);
+    }
 }
@@ -1,14 +1,19 @@
+use crate::exchanges::amm::{AmmDepositRecovery, AmmExchange};
 use crate::exchanges::Exchange;
 use crate::model::orders_log::OrdersLog;
+use crate::model::price_history::PriceHistory;
+use crate::model::trading_history::TradingHistory;
 use canister_state_macros::canister_state;
 use icdex_client::ICDexClient;
-use market_maker_canister::{ExchangeId, ICDEX_EXCHANGE_ID};
+use icpswap_client::ICPSwapClient;
+use market_maker_canister::{ExchangeId, InventoryLimits, Strategy, ICDEX_EXCHANGE_ID, ICPSWAP_EXCHANGE_ID, SONIC_EXCHANGE_ID};
 use serde::{Deserialize, Serialize};
+use sonic_client::SonicClient;
 use std::cell::RefCell;
 use std::collections::HashMap;
 use types::{
-    AggregatedOrders, BuildVersion, CancelOrderRequest, CanisterId, Cryptocurrency, Cycles, MakeOrderRequest, TimestampMillis,
-    Timestamped, TokenInfo,
+    AggregatedOrders, BuildVersion, CancelOrderRequest, CanisterId, Cryptocurrency, Cycles, MakeOrderRequest, Order,
+    TimestampMillis, Timestamped, TokenInfo,
 };
 use utils::env::Environment;
 
@@ -18,6 +23,7 @@ mod lifecycle;
 mod memory;
 mod model;
 mod queries;
+mod strategies;
 mod updates;
 
 thread_local! {
@@ -36,31 +42,88 @@ impl RuntimeState {
         RuntimeState { env, data }
     }
 
+    // Returns None if the exchange is not supported or if its canister id has not been configured
     pub fn get_exchange_client(&self, exchange_id: ExchangeId) -> Option<Box<dyn Exchange>> {
+        let this_canister_id = self.env.canister_id();
+        let exchange_canister_id = self.data.exchange_config.get(&exchange_id)?.exchange_canister_id?;
+
         match exchange_id {
             ICDEX_EXCHANGE_ID => Some(Box::new(ICDexClient::new(
-                self.env.canister_id(),
-                CanisterId::from_text("3we4s-lyaaa-aaaak-aegrq-cai").unwrap(),
-                TokenInfo {
-                    token: Cryptocurrency::InternetComputer,
-                    ledger: self.data.icp_ledger_canister_id,
-                    decimals: 8,
-                    fee: 10_000,
-                },
-                TokenInfo {
-                    token: Cryptocurrency::CHAT,
-                    ledger: self.data.chat_ledger_canister_id,
-                    decimals: 8,
-                    fee: 100_000,
-                },
+                this_canister_id,
+                exchange_canister_id,
+                self.quote_token(),
+                self.base_token(),
                 10_000_000,
                 |order| on_order_made(ICDEX_EXCHANGE_ID, order),
                 |order| on_order_cancelled(ICDEX_EXCHANGE_ID, order),
             ))),
+            ICPSWAP_EXCHANGE_ID => {
+                // CHAT is token0 in the ICPSwap CHAT/ICP pool
+                Some(Box::new(AmmExchange::new(
+                    ICPSWAP_EXCHANGE_ID,
+                    self.quote_token(),
+                    self.base_token(),
+                    Box::new(ICPSwapClient::new(
+                        this_canister_id,
+                        exchange_canister_id,
+                        self.base_token(),
+                        self.quote_token(),
+                        false,
+                    )),
+                    Box::new(ICPSwapClient::new(
+                        this_canister_id,
+                        exchange_canister_id,
+                        self.base_token(),
+                        self.quote_token(),
+                        true,
+                    )),
+                )))
+            }
+            SONIC_EXCHANGE_ID => Some(Box::new(AmmExchange::new(
+                SONIC_EXCHANGE_ID,
+                self.quote_token(),
+                self.base_token(),
+                Box::new(SonicClient::new(
+                    this_canister_id,
+                    exchange_canister_id,
+                    self.base_token(),
+                    self.quote_token(),
+                    false,
+                )),
+                Box::new(SonicClient::new(
+                    this_canister_id,
+                    exchange_canister_id,
+                    self.base_token(),
+                    self.quote_token(),
+                    true,
+                )),
+            ))),
             _ => None,
         }
     }
 
+    pub fn is_supported_exchange(exchange_id: ExchangeId) -> bool {
+        matches!(exchange_id, ICDEX_EXCHANGE_ID | ICPSWAP_EXCHANGE_ID | SONIC_EXCHANGE_ID)
+    }
+
+    pub fn quote_token(&self) -> TokenInfo {
+        TokenInfo {
+            token: Cryptocurrency::InternetComputer,
+            ledger: self.data.icp_ledger_canister_id,
+            decimals: 8,
+            fee: 10_000,
+        }
+    }
+
+    pub fn base_token(&self) -> TokenInfo {
+        TokenInfo {
+            token: Cryptocurrency::CHAT,
+            ledger: self.data.chat_ledger_canister_id,
+            decimals: 8,
+            fee: 100_000,
+        }
+    }
+
     pub fn metrics(&self) -> Metrics {
         Metrics {
             memory_used: utils::memory::used(),
@@ -92,6 +155,14 @@ struct Data {
     pub my_open_orders: HashMap<ExchangeId, AggregatedOrders>,
     pub market_makers_in_progress: HashMap<ExchangeId, TimestampMillis>,
     #[serde(default)]
+    pub amm_orders: HashMap<ExchangeId, Vec<Order>>,
+    #[serde(default)]
+    pub amm_deposit_recoveries: HashMap<ExchangeId, Vec<AmmDepositRecovery>>,
+    #[serde(default)]
+    pub price_history: HashMap<ExchangeId, PriceHistory>,
+    #[serde(default)]
+    pub trading_history: TradingHistory,
+    #[serde(default)]
     pub rng_seed: [u8; 32],
     pub test_mode: bool,
 }
@@ -113,10 +184,23 @@ impl Data {
             orders_log: OrdersLog::default(),
             my_open_orders: HashMap::new(),
             market_makers_in_progress: HashMap::new(),
+            amm_orders: HashMap::new(),
+            amm_deposit_recoveries: HashMap::new(),
+            price_history: HashMap::new(),
+            trading_history: TradingHistory::default(),
             rng_seed: [0; 32],
             test_mode,
         }
     }
+
+    // Exchanges which are supported but not yet configured start off with a disabled config
+    pub fn set_exchange_canister_ids(&mut self, exchange_canister_ids: HashMap<ExchangeId, CanisterId>) {
+        for (exchange_id, canister_id) in exchange_canister_ids {
+            if RuntimeState::is_supported_exchange(exchange_id) {
+                self.exchange_config.entry(exchange_id).or_default().exchange_canister_id = Some(canister_id);
+            }
+        }
+    }
 }
 
 #[derive(Serialize, Debug)]
@@ -140,7 +224,7 @@ pub struct CanisterIds {
     pub chat_ledger: CanisterId,
 }
 
-#[derive(Serialize, Deserialize, Clone, Debug)]
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
 pub struct Config {
     enabled: bool,
     price_increment: u64,
@@ -153,6 +237,12 @@ pub struct Config {
     max_orders_per_direction: u32,
     max_orders_to_make_per_iteration: u32,
     max_orders_to_cancel_per_iteration: u32,
+    #[serde(default)]
+    strategy: Strategy,
+    #[serde(default)]
+    inventory_limits: Option<InventoryLimits>,
+    #[serde(default)]
+    exchange_canister_id: Option<CanisterId>,
 }
 
 fn on_order_made(exchange_id: ExchangeId, order: MakeOrderRequest) {
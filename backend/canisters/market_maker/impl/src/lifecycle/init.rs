@@ -13,13 +13,14 @@ fn init(args: Args) {
     init_cycles_dispenser_client(args.cycles_dispenser_canister_id);
 
     let env = init_env([0; 32]);
-    let data = Data::new(
+    let mut data = Data::new(
         args.user_index_canister_id,
         args.cycles_dispenser_canister_id,
         args.icp_ledger_canister_id,
         args.chat_ledger_canister_id,
         args.test_mode,
     );
+    data.set_exchange_canister_ids(args.exchange_canister_ids);
 
     init_state(env, data, args.wasm_version);
 
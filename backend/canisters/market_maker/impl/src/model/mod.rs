@@ -1 +1,3 @@
 pub mod orders_log;
+pub mod price_history;
+pub mod trading_history;
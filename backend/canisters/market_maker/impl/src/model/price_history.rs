@@ -0,0 +1,75 @@
+use serde::{Deserialize, Serialize};
+use std::cmp::max;
+use std::collections::VecDeque;
+use types::{Milliseconds, TimestampMillis};
+use utils::time::DAY_IN_MS;
+
+const MAX_HISTORY: Milliseconds = 7 * DAY_IN_MS;
+
+#[derive(Serialize, Deserialize, Default)]
+pub struct PriceHistory {
+    samples: VecDeque<(TimestampMillis, u64)>,
+}
+
+impl PriceHistory {
+    pub fn push(&mut self, price: u64, now: TimestampMillis) {
+        self.samples.push_back((now, price));
+
+        while self
+            .samples
+            .front()
+            .map_or(false, |(ts, _)| now.saturating_sub(*ts) > MAX_HISTORY)
+        {
+            self.samples.pop_front();
+        }
+    }
+
+    // Each price is weighted by the length of time until the next sample (or until `now` for the
+    // latest sample). Samples from before the start of the window are weighted from the start of
+    // the window.
+    pub fn twap(&self, window: Milliseconds, now: TimestampMillis) -> Option<u64> {
+        let window_start = now.saturating_sub(window);
+
+        let mut weighted_sum = 0u128;
+        let mut total_duration = 0u128;
+        for (i, (ts, price)) in self.samples.iter().enumerate() {
+            let end = self.samples.get(i + 1).map_or(now, |(next, _)| *next);
+            if end <= window_start {
+                continue;
+            }
+            let duration = end.saturating_sub(max(*ts, window_start)) as u128;
+            weighted_sum += duration * *price as u128;
+            total_duration += duration;
+        }
+
+        if total_duration > 0 {
+            Some((weighted_sum / total_duration) as u64)
+        } else {
+            self.samples.back().map(|(_, price)| *price)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn twap_weights_prices_by_duration() {
+        let mut history = PriceHistory::default();
+        history.push(100, 0);
+        history.push(200, 3000);
+
+        assert_eq!(history.twap(4000, 4000), Some(125));
+    }
+
+    #[test]
+    fn twap_ignores_samples_before_window() {
+        let mut history = PriceHistory::default();
+        history.push(1000, 0);
+        history.push(100, 5000);
+        history.push(200, 8000);
+
+        assert_eq!(history.twap(4000, 10000), Some(150));
+    }
+}
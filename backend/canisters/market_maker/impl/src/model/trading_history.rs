@@ -0,0 +1,257 @@
+use crate::memory::{get_trading_history_data_memory, get_trading_history_index_memory, Memory};
+use ic_stable_structures::storable::Bound;
+use ic_stable_structures::{StableLog, Storable};
+use market_maker_canister::{BalanceSnapshot, ExchangeId, Fill, TradingEvent};
+use msgpack::{deserialize_then_unwrap, serialize_then_unwrap};
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+use std::collections::HashMap;
+use types::{Milliseconds, OrderType, TimestampMillis};
+use utils::time::MINUTE_IN_MS;
+
+const SNAPSHOT_INTERVAL: Milliseconds = 10 * MINUTE_IN_MS;
+
+#[derive(Serialize, Deserialize)]
+pub struct TradingHistory {
+    #[serde(skip, default = "init_log")]
+    log: StableLog<TradingHistoryEntry, Memory, Memory>,
+    last_snapshot: HashMap<ExchangeId, TimestampMillis>,
+}
+
+impl TradingHistory {
+    pub fn record_fill(&mut self, exchange_id: ExchangeId, fill: Fill, now: TimestampMillis) {
+        self.append(exchange_id, TradingEvent::Fill(fill), now);
+    }
+
+    // Snapshots are only recorded once per `SNAPSHOT_INTERVAL` for each exchange
+    pub fn record_snapshot_if_due(&mut self, exchange_id: ExchangeId, snapshot: BalanceSnapshot, now: TimestampMillis) {
+        if self
+            .last_snapshot
+            .get(&exchange_id)
+            .map_or(true, |ts| now.saturating_sub(*ts) >= SNAPSHOT_INTERVAL)
+        {
+            self.append(exchange_id, TradingEvent::Snapshot(snapshot), now);
+            self.last_snapshot.insert(exchange_id, now);
+        }
+    }
+
+    pub fn get(&self, index: u64) -> Option<market_maker_canister::TradingHistoryEntry> {
+        self.log.get(index).map(|e| e.hydrate(index))
+    }
+
+    pub fn len(&self) -> u64 {
+        self.log.len()
+    }
+
+    // Entries are appended in timestamp order so this can binary search for the index of the first
+    // entry at or after `timestamp`
+    pub fn first_index_at_or_after(&self, timestamp: TimestampMillis) -> u64 {
+        let mut low = 0;
+        let mut high = self.log.len();
+        while low < high {
+            let mid = (low + high) / 2;
+            if self.log.get(mid).map_or(false, |e| e.timestamp < timestamp) {
+                low = mid + 1;
+            } else {
+                high = mid;
+            }
+        }
+        low
+    }
+
+    // Iterates over the entries for the exchange (or all exchanges) within the time range
+    pub fn range(
+        &self,
+        exchange_id: Option<ExchangeId>,
+        from: TimestampMillis,
+        to: TimestampMillis,
+    ) -> impl Iterator<Item = market_maker_canister::TradingHistoryEntry> + '_ {
+        let start = self.first_index_at_or_after(from);
+
+        (start..self.log.len())
+            .filter_map(|i| self.get(i))
+            .take_while(move |e| e.timestamp <= to)
+            .filter(move |e| exchange_id.map_or(true, |id| e.exchange_id == id))
+    }
+
+    fn append(&mut self, exchange_id: ExchangeId, event: TradingEvent, now: TimestampMillis) {
+        self.log
+            .append(&TradingHistoryEntry {
+                timestamp: now,
+                exchange_id,
+                event,
+            })
+            .unwrap();
+    }
+}
+
+fn init_log() -> StableLog<TradingHistoryEntry, Memory, Memory> {
+    let index_memory = get_trading_history_index_memory();
+    let data_memory = get_trading_history_data_memory();
+
+    StableLog::init(index_memory, data_memory).unwrap()
+}
+
+impl Default for TradingHistory {
+    fn default() -> Self {
+        TradingHistory {
+            log: init_log(),
+            last_snapshot: HashMap::new(),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct TradingHistoryEntry {
+    timestamp: TimestampMillis,
+    exchange_id: ExchangeId,
+    event: TradingEvent,
+}
+
+impl TradingHistoryEntry {
+    fn hydrate(self, index: u64) -> market_maker_canister::TradingHistoryEntry {
+        market_maker_canister::TradingHistoryEntry {
+            index,
+            timestamp: self.timestamp,
+            exchange_id: self.exchange_id,
+            event: self.event,
+        }
+    }
+}
+
+impl Storable for TradingHistoryEntry {
+    fn to_bytes(&self) -> Cow<[u8]> {
+        Cow::Owned(serialize_then_unwrap(self))
+    }
+
+    fn from_bytes(bytes: Cow<[u8]>) -> Self {
+        deserialize_then_unwrap(bytes.as_ref())
+    }
+
+    const BOUND: Bound = Bound::Unbounded;
+}
+
+// All values are in units of the quote token. Realised P&L is calculated from the volume which was
+// both bought and sold within the range, the remaining net position is valued at the latest mid
+// price to give the unrealised P&L.
+#[derive(Serialize, Default, Debug, Eq, PartialEq)]
+pub struct TradingSummary {
+    pub fills: u32,
+    pub base_bought: u64,
+    pub quote_spent: u64,
+    pub base_sold: u64,
+    pub quote_received: u64,
+    pub realised_pnl: i64,
+    pub unrealised_pnl: i64,
+    pub start_value: Option<u64>,
+    pub end_value: Option<u64>,
+}
+
+impl TradingSummary {
+    pub fn build<'a>(
+        entries: impl Iterator<Item = &'a market_maker_canister::TradingHistoryEntry>,
+        base_token_units_per_whole: u64,
+    ) -> TradingSummary {
+        let units = base_token_units_per_whole as u128;
+        let mut summary = TradingSummary::default();
+        let mut latest_mid_price = None;
+
+        for entry in entries {
+            match &entry.event {
+                TradingEvent::Fill(fill) => {
+                    let quote_amount = (fill.amount as u128 * fill.price as u128 / units) as u64;
+                    summary.fills += 1;
+                    match fill.order_type {
+                        OrderType::Bid => {
+                            summary.base_bought += fill.amount;
+                            summary.quote_spent += quote_amount;
+                        }
+                        OrderType::Ask => {
+                            summary.base_sold += fill.amount;
+                            summary.quote_received += quote_amount;
+                        }
+                    }
+                }
+                TradingEvent::Snapshot(snapshot) => {
+                    let value =
+                        snapshot.quote_balance + (snapshot.base_balance as u128 * snapshot.mid_price as u128 / units) as u64;
+                    if summary.start_value.is_none() {
+                        summary.start_value = Some(value);
+                    }
+                    summary.end_value = Some(value);
+                    latest_mid_price = Some(snapshot.mid_price);
+                }
+            }
+        }
+
+        let bought = summary.base_bought as i128;
+        let sold = summary.base_sold as i128;
+        let spent = summary.quote_spent as i128;
+        let received = summary.quote_received as i128;
+
+        let matched = bought.min(sold);
+        if matched > 0 {
+            summary.realised_pnl = (matched * received / sold - matched * spent / bought) as i64;
+        }
+
+        if let Some(mid_price) = latest_mid_price {
+            let net = bought - sold;
+            let net_value = net.abs() * mid_price as i128 / units as i128;
+            summary.unrealised_pnl = if net > 0 {
+                (net_value - net * spent / bought) as i64
+            } else if net < 0 {
+                (-net * received / sold - net_value) as i64
+            } else {
+                0
+            };
+        }
+
+        summary
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(event: TradingEvent) -> market_maker_canister::TradingHistoryEntry {
+        market_maker_canister::TradingHistoryEntry {
+            index: 0,
+            timestamp: 0,
+            exchange_id: ExchangeId::new(1),
+            event,
+        }
+    }
+
+    fn fill(order_type: OrderType, price: u64, amount: u64) -> TradingEvent {
+        TradingEvent::Fill(Fill {
+            order_type,
+            price,
+            amount,
+        })
+    }
+
+    #[test]
+    fn summary_calculates_realised_and_unrealised_pnl() {
+        let entries = vec![
+            entry(fill(OrderType::Bid, 90, 300)),
+            entry(fill(OrderType::Ask, 110, 100)),
+            entry(TradingEvent::Snapshot(BalanceSnapshot {
+                base_balance: 1000,
+                quote_balance: 1000,
+                mid_price: 100,
+            })),
+        ];
+
+        let summary = TradingSummary::build(entries.iter(), 100);
+
+        assert_eq!(summary.fills, 2);
+        assert_eq!(summary.quote_spent, 270);
+        assert_eq!(summary.quote_received, 110);
+        // 100 bought at 90 and sold at 110
+        assert_eq!(summary.realised_pnl, 20);
+        // 200 bought at 90 now valued at 100
+        assert_eq!(summary.unrealised_pnl, 20);
+        assert_eq!(summary.end_value, Some(2000));
+    }
+}
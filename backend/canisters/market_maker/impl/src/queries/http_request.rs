@@ -1,9 +1,14 @@
+use crate::model::trading_history::TradingSummary;
 use crate::{read_state, RuntimeState};
 use http_request::{build_json_response, build_response, encode_logs, extract_route, Route};
 use ic_cdk_macros::query;
+use market_maker_canister::{ExchangeId, TradingEvent, TradingHistoryEntry};
+use serde::Serialize;
 use std::io::Write;
 use types::{HttpRequest, HttpResponse, TimestampMillis};
 
+const MAX_REPORT_ENTRIES: usize = 10_000;
+
 #[query]
 fn http_request(request: HttpRequest) -> HttpResponse {
     fn get_logs_impl(since: Option<TimestampMillis>) -> HttpResponse {
@@ -30,11 +35,79 @@ fn http_request(request: HttpRequest) -> HttpResponse {
         build_response(body, "text/plain")
     }
 
+    // Supports `exchange`, `from`, `to` and `format` (either `json` or `csv`) query string params
+    fn get_report(qs: String, state: &RuntimeState) -> HttpResponse {
+        let mut exchange_id = None;
+        let mut from = 0;
+        let mut to = TimestampMillis::MAX;
+        let mut csv = false;
+        for (key, value) in qs.split('&').filter_map(|p| p.split_once('=')) {
+            match key {
+                "exchange" => exchange_id = value.parse::<u32>().ok().map(ExchangeId::from),
+                "from" => from = value.parse().unwrap_or(from),
+                "to" => to = value.parse().unwrap_or(to),
+                "format" => csv = value == "csv",
+                _ => {}
+            }
+        }
+
+        let entries: Vec<_> = state
+            .data
+            .trading_history
+            .range(exchange_id, from, to)
+            .take(MAX_REPORT_ENTRIES)
+            .collect();
+
+        if csv {
+            let mut body = Vec::new();
+            writeln!(
+                &mut body,
+                "index,timestamp,exchange_id,event,order_type,price,amount,base_balance,quote_balance,mid_price"
+            )
+            .unwrap();
+            for entry in entries {
+                write_csv_row(&mut body, &entry);
+            }
+            build_response(body, "text/csv")
+        } else {
+            let base_token_units_per_whole = 10u64.pow(state.base_token().decimals as u32);
+            build_json_response(&Report {
+                summary: TradingSummary::build(entries.iter(), base_token_units_per_whole),
+                entries,
+            })
+        }
+    }
+
     match extract_route(&request.url) {
         Route::Logs(since) => get_logs_impl(since),
         Route::Traces(since) => get_traces_impl(since),
         Route::Metrics => read_state(get_metrics_impl),
         Route::Other(p, _) if p == "orders" => read_state(get_order_logs),
+        Route::Other(p, qs) if p == "report" => read_state(|state| get_report(qs, state)),
         _ => HttpResponse::not_found(),
     }
 }
+
+#[derive(Serialize)]
+struct Report {
+    summary: TradingSummary,
+    entries: Vec<TradingHistoryEntry>,
+}
+
+fn write_csv_row(body: &mut Vec<u8>, entry: &TradingHistoryEntry) {
+    let (index, timestamp, exchange_id) = (entry.index, entry.timestamp, entry.exchange_id);
+
+    match &entry.event {
+        TradingEvent::Fill(f) => writeln!(
+            body,
+            "{index},{timestamp},{exchange_id},Fill,{},{},{},,,",
+            f.order_type, f.price, f.amount
+        ),
+        TradingEvent::Snapshot(s) => writeln!(
+            body,
+            "{index},{timestamp},{exchange_id},Snapshot,,,,{},{},{}",
+            s.base_balance, s.quote_balance, s.mid_price
+        ),
+    }
+    .unwrap();
+}
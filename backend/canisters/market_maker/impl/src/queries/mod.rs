@@ -1 +1,2 @@
 mod http_request;
+mod trading_history;
@@ -0,0 +1,43 @@
+use crate::{read_state, RuntimeState};
+use ic_cdk_macros::query;
+use market_maker_canister::trading_history::{Response::*, *};
+
+const MAX_RESULTS: u32 = 500;
+// Limits the number of instructions used when filtering by exchange matches few entries
+const MAX_ENTRIES_SCANNED: u64 = 10_000;
+
+#[query]
+fn trading_history(args: Args) -> Response {
+    read_state(|state| trading_history_impl(args, state))
+}
+
+fn trading_history_impl(args: Args, state: &RuntimeState) -> Response {
+    let history = &state.data.trading_history;
+    let total = history.len();
+    let start = args
+        .start
+        .unwrap_or_else(|| args.from.map_or(0, |from| history.first_index_at_or_after(from)));
+    let to = args.to.unwrap_or(u64::MAX);
+    let max_results = args.max_results.min(MAX_RESULTS) as usize;
+
+    let mut entries = Vec::new();
+    let mut next = start;
+    while next < total && entries.len() < max_results && next - start < MAX_ENTRIES_SCANNED {
+        let Some(entry) = history.get(next) else {
+            break;
+        };
+        if entry.timestamp > to {
+            next = total;
+            break;
+        }
+        next += 1;
+        if args.exchange_id.map_or(true, |id| entry.exchange_id == id) {
+            entries.push(entry);
+        }
+    }
+
+    Success(SuccessResult {
+        entries,
+        next: Some(next).filter(|n| *n < total),
+    })
+}
@@ -0,0 +1,11 @@
+use crate::strategies::{PricingStrategy, StrategyInputs};
+use crate::Config;
+
+// The original strategy, a fixed ladder of orders either side of the price limits
+pub struct FixedLadder;
+
+impl PricingStrategy for FixedLadder {
+    fn adjust_price_limits(&self, max_bid_price: u64, min_ask_price: u64, _: &StrategyInputs, _: &Config) -> (u64, u64) {
+        (max_bid_price, min_ask_price)
+    }
+}
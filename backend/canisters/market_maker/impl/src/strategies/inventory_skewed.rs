@@ -0,0 +1,90 @@
+use crate::strategies::{PricingStrategy, StrategyInputs};
+use crate::Config;
+use std::cmp::{max, min};
+
+pub struct InventorySkewed {
+    target_base_ratio_bps: u32,
+    max_skew: u64,
+}
+
+impl InventorySkewed {
+    pub fn new(target_base_ratio_bps: u32, max_skew: u64) -> InventorySkewed {
+        InventorySkewed {
+            target_base_ratio_bps,
+            max_skew,
+        }
+    }
+
+    // Returns the number of price increments to shift the prices down by (or up by if negative).
+    // The skew grows linearly with the deviation from the target ratio, reaching `max_skew` once
+    // the deviation is 50%.
+    fn skew(&self, inputs: &StrategyInputs) -> i64 {
+        let base_value = inputs.base_balance as u128 * inputs.latest_price as u128 / inputs.base_token_units_per_whole as u128;
+        let total_value = base_value + inputs.quote_balance as u128;
+        if total_value == 0 {
+            return 0;
+        }
+
+        let base_ratio_bps = (base_value * 10000 / total_value) as i64;
+        let deviation_bps = base_ratio_bps - self.target_base_ratio_bps as i64;
+        let max_skew = self.max_skew as i64;
+
+        (deviation_bps * max_skew / 5000).clamp(-max_skew, max_skew)
+    }
+}
+
+impl PricingStrategy for InventorySkewed {
+    fn adjust_price_limits(
+        &self,
+        max_bid_price: u64,
+        min_ask_price: u64,
+        inputs: &StrategyInputs,
+        config: &Config,
+    ) -> (u64, u64) {
+        let skew = self.skew(inputs);
+        let shift = skew.unsigned_abs() * config.price_increment;
+
+        // Never cross the orderbook, otherwise our orders would immediately be taken
+        if skew > 0 {
+            (
+                max_bid_price.saturating_sub(shift),
+                max(
+                    min_ask_price.saturating_sub(shift),
+                    inputs.current_bid.saturating_add(config.price_increment),
+                ),
+            )
+        } else {
+            (
+                min(
+                    max_bid_price.saturating_add(shift),
+                    inputs.current_ask.saturating_sub(config.price_increment),
+                ),
+                min_ask_price.saturating_add(shift),
+            )
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    #[test_case(0, 1000, -10)]
+    #[test_case(500, 500, 0)]
+    #[test_case(1000, 0, 10)]
+    #[test_case(750, 250, 5)]
+    fn skew_tests(base_balance: u64, quote_balance: u64, expected: i64) {
+        let strategy = InventorySkewed::new(5000, 10);
+        let inputs = StrategyInputs {
+            current_bid: 90,
+            current_ask: 110,
+            latest_price: 100,
+            base_balance,
+            quote_balance,
+            base_token_units_per_whole: 100,
+        };
+
+        assert_eq!(strategy.skew(&inputs), expected);
+    }
+}
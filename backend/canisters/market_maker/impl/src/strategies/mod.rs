@@ -0,0 +1,43 @@
+use crate::model::price_history::PriceHistory;
+use crate::strategies::fixed_ladder::FixedLadder;
+use crate::strategies::inventory_skewed::InventorySkewed;
+use crate::strategies::twap_anchored::TwapAnchored;
+use crate::Config;
+use market_maker_canister::Strategy;
+use types::TimestampMillis;
+
+pub mod fixed_ladder;
+pub mod inventory_skewed;
+pub mod twap_anchored;
+
+pub struct StrategyInputs {
+    pub current_bid: u64,
+    pub current_ask: u64,
+    pub latest_price: u64,
+    pub base_balance: u64,
+    pub quote_balance: u64,
+    pub base_token_units_per_whole: u64,
+}
+
+// Each strategy takes the bid and ask price limits calculated from the orderbook and the orders
+// taken since the previous round, and adjusts them before the ladder of orders is built
+pub trait PricingStrategy {
+    fn adjust_price_limits(
+        &self,
+        max_bid_price: u64,
+        min_ask_price: u64,
+        inputs: &StrategyInputs,
+        config: &Config,
+    ) -> (u64, u64);
+}
+
+pub fn build_strategy(config: &Config, price_history: Option<&PriceHistory>, now: TimestampMillis) -> Box<dyn PricingStrategy> {
+    match &config.strategy {
+        Strategy::FixedLadder => Box::new(FixedLadder),
+        Strategy::InventorySkewed(s) => Box::new(InventorySkewed::new(s.target_base_ratio_bps, s.max_skew)),
+        Strategy::TwapAnchored(s) => Box::new(TwapAnchored::new(
+            price_history.and_then(|h| h.twap(s.window, now)),
+            s.max_deviation,
+        )),
+    }
+}
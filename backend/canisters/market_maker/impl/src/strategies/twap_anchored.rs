@@ -0,0 +1,29 @@
+use crate::strategies::{PricingStrategy, StrategyInputs};
+use crate::Config;
+use std::cmp::{max, min};
+
+pub struct TwapAnchored {
+    twap: Option<u64>,
+    max_deviation: u64,
+}
+
+impl TwapAnchored {
+    pub fn new(twap: Option<u64>, max_deviation: u64) -> TwapAnchored {
+        TwapAnchored { twap, max_deviation }
+    }
+}
+
+impl PricingStrategy for TwapAnchored {
+    fn adjust_price_limits(&self, max_bid_price: u64, min_ask_price: u64, _: &StrategyInputs, config: &Config) -> (u64, u64) {
+        if let Some(twap) = self.twap {
+            let deviation = self.max_deviation * config.price_increment;
+
+            (
+                min(max_bid_price, twap.saturating_add(deviation)),
+                max(min_ask_price, twap.saturating_sub(deviation)),
+            )
+        } else {
+            (max_bid_price, min_ask_price)
+        }
+    }
+}
@@ -1,7 +1,8 @@
-use crate::{mutate_state, read_state, RuntimeState};
+use crate::{mutate_state, read_state, Config, RuntimeState};
 use canister_tracing_macros::trace;
 use ic_cdk_macros::update;
 use market_maker_canister::update_config::{Response::*, *};
+use market_maker_canister::Strategy;
 use user_index_canister_c2c_client::{lookup_user, LookupUserError};
 
 #[update]
@@ -19,28 +20,50 @@ async fn update_config(args: Args) -> Response {
 }
 
 fn update_config_impl(args: Args, state: &mut RuntimeState) -> Response {
-    if let Some(config) = state.data.exchange_config.get_mut(&args.exchange_id) {
-        update_if_some(args.enabled, &mut config.enabled);
-        update_if_some(args.price_increment, &mut config.price_increment);
-        update_if_some(args.order_size, &mut config.order_size);
-        update_if_some(args.min_order_size, &mut config.min_order_size);
-        update_if_some(args.max_buy_price, &mut config.max_buy_price);
-        update_if_some(args.min_sell_price, &mut config.min_sell_price);
-        update_if_some(args.spread, &mut config.spread);
-        update_if_some(args.min_orders_per_direction, &mut config.min_orders_per_direction);
-        update_if_some(args.max_orders_per_direction, &mut config.max_orders_per_direction);
-        update_if_some(
-            args.max_orders_to_make_per_iteration,
-            &mut config.max_orders_to_make_per_iteration,
-        );
-        update_if_some(
-            args.max_orders_to_cancel_per_iteration,
-            &mut config.max_orders_to_cancel_per_iteration,
-        );
-        Success
-    } else {
-        ExchangeNotFound
+    // Exchanges which are supported but not yet configured start off with a disabled config
+    let mut updated = match state.data.exchange_config.get(&args.exchange_id) {
+        Some(config) => config.clone(),
+        None if RuntimeState::is_supported_exchange(args.exchange_id) => Config::default(),
+        None => return ExchangeNotFound,
+    };
+
+    update_if_some(args.enabled, &mut updated.enabled);
+    update_if_some(args.price_increment, &mut updated.price_increment);
+    update_if_some(args.order_size, &mut updated.order_size);
+    update_if_some(args.min_order_size, &mut updated.min_order_size);
+    update_if_some(args.max_buy_price, &mut updated.max_buy_price);
+    update_if_some(args.min_sell_price, &mut updated.min_sell_price);
+    update_if_some(args.spread, &mut updated.spread);
+    update_if_some(args.min_orders_per_direction, &mut updated.min_orders_per_direction);
+    update_if_some(args.max_orders_per_direction, &mut updated.max_orders_per_direction);
+    update_if_some(
+        args.max_orders_to_make_per_iteration,
+        &mut updated.max_orders_to_make_per_iteration,
+    );
+    update_if_some(
+        args.max_orders_to_cancel_per_iteration,
+        &mut updated.max_orders_to_cancel_per_iteration,
+    );
+    update_if_some(args.strategy, &mut updated.strategy);
+    updated.inventory_limits = args.inventory_limits.apply_to(updated.inventory_limits);
+    if let Some(canister_id) = args.exchange_canister_id {
+        updated.exchange_canister_id = Some(canister_id);
+    }
+
+    if updated.enabled && (updated.price_increment == 0 || updated.order_size == 0) {
+        return InvalidConfig("Price increment and order size must be greater than 0".to_string());
     }
+    if updated.enabled && updated.exchange_canister_id.is_none() {
+        return InvalidConfig("Exchange canister id must be set".to_string());
+    }
+    if let Strategy::InventorySkewed(s) = &updated.strategy {
+        if s.target_base_ratio_bps > 10000 {
+            return InvalidConfig("Target base ratio must not exceed 10000 bps".to_string());
+        }
+    }
+
+    state.data.exchange_config.insert(args.exchange_id, updated);
+    Success
 }
 
 fn update_if_some<T>(input: Option<T>, target: &mut T) {
@@ -1,3 +1,7 @@
+use candid::CandidType;
+use serde::{Deserialize, Serialize};
+use types::{TimestampMillis, UserId};
+
 mod lifecycle;
 mod queries;
 mod updates;
@@ -5,3 +9,26 @@ mod updates;
 pub use lifecycle::*;
 pub use queries::*;
 pub use updates::*;
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum Availability {
+    #[default]
+    Available,
+    Away,
+    DoNotDisturb,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct PresenceStatus {
+    pub availability: Availability,
+    pub text: Option<String>,
+    pub expires: Option<TimestampMillis>,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug, Default)]
+pub enum PresenceVisibility {
+    #[default]
+    Everyone,
+    SelectedUsers(Vec<UserId>),
+    Nobody,
+}
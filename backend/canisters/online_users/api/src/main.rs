@@ -3,7 +3,11 @@ use candid_gen::generate_candid_method;
 #[allow(deprecated)]
 fn main() {
     generate_candid_method!(online_users, last_online, query);
+    generate_candid_method!(online_users, presence, query);
     generate_candid_method!(online_users, mark_as_online, update);
+    generate_candid_method!(online_users, set_presence_privacy, update);
+    generate_candid_method!(online_users, set_status, update);
+    generate_candid_method!(online_users, set_typing, update);
 
     candid::export_service!();
     std::print!("{}", __export_service());
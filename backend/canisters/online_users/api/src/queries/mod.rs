@@ -1 +1,2 @@
 pub mod last_online;
+pub mod presence;
@@ -0,0 +1,35 @@
+use crate::PresenceStatus;
+use candid::CandidType;
+use serde::{Deserialize, Serialize};
+use types::{MessageIndex, Milliseconds, UserId};
+
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub struct Args {
+    pub user_ids: Vec<UserId>,
+    // If set, returns whether the other user in this direct chat is currently typing
+    pub typing_in: Option<TypingIn>,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct TypingIn {
+    pub user_id: UserId,
+    pub thread_root_message_index: Option<MessageIndex>,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub enum Response {
+    Success(SuccessResult),
+}
+
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub struct SuccessResult {
+    pub users: Vec<UserPresence>,
+    pub typing: Vec<UserId>,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub struct UserPresence {
+    pub user_id: UserId,
+    pub duration_since_last_online: Option<Milliseconds>,
+    pub status: Option<PresenceStatus>,
+}
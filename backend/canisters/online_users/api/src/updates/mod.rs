@@ -1 +1,4 @@
 pub mod mark_as_online;
+pub mod set_presence_privacy;
+pub mod set_status;
+pub mod set_typing;
@@ -0,0 +1,17 @@
+use crate::PresenceVisibility;
+use candid::CandidType;
+use serde::{Deserialize, Serialize};
+
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub struct Args {
+    pub last_online: PresenceVisibility,
+    pub status: PresenceVisibility,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub enum Response {
+    Success,
+    TooManyUsers(u32),
+    UserNotFound,
+    InternalError(String),
+}
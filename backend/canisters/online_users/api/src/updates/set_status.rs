@@ -0,0 +1,19 @@
+use crate::Availability;
+use candid::CandidType;
+use serde::{Deserialize, Serialize};
+use types::Milliseconds;
+
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub struct Args {
+    pub availability: Availability,
+    pub text: Option<String>,
+    pub expires_in: Option<Milliseconds>,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub enum Response {
+    Success,
+    TextTooLong(u32),
+    UserNotFound,
+    InternalError(String),
+}
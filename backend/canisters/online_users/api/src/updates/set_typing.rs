@@ -0,0 +1,19 @@
+use candid::CandidType;
+use serde::{Deserialize, Serialize};
+use types::{MessageIndex, UserId};
+
+// Typing in group chats and channels is set via the group and community canisters, so that it is
+// only visible to members
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub struct Args {
+    pub recipient: UserId,
+    pub thread_root_message_index: Option<MessageIndex>,
+    pub typing: bool,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub enum Response {
+    Success,
+    UserNotFound,
+    InternalError(String),
+}
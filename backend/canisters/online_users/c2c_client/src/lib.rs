@@ -0,0 +1,7 @@
+use canister_client::generate_candid_c2c_call;
+use online_users_canister::*;
+
+// Queries
+generate_candid_c2c_call!(last_online);
+
+// Updates
@@ -1,5 +1,11 @@
-use canister_client::generate_update_call;
+use canister_client::{generate_query_call, generate_update_call};
 use online_users_canister::*;
 
+// Queries
+generate_query_call!(presence);
+
 // Updates
 generate_update_call!(mark_as_online);
+generate_update_call!(set_presence_privacy);
+generate_update_call!(set_status);
+generate_update_call!(set_typing);
@@ -1,7 +1,9 @@
 use crate::RuntimeState;
 
 pub mod calculate_active_users;
+pub mod prune_presence;
 
 pub(crate) fn start(_state: &RuntimeState) {
     calculate_active_users::start_job();
+    prune_presence::start_job();
 }
@@ -0,0 +1,18 @@
+use crate::mutate_state;
+use std::time::Duration;
+use types::Milliseconds;
+use utils::canister_timers::run_now_then_interval;
+use utils::time::MINUTE_IN_MS;
+
+const PRUNE_PRESENCE_INTERVAL: Milliseconds = MINUTE_IN_MS;
+
+pub fn start_job() {
+    run_now_then_interval(Duration::from_millis(PRUNE_PRESENCE_INTERVAL), run);
+}
+
+fn run() {
+    mutate_state(|state| {
+        let now = state.env.now();
+        state.data.presence.prune_expired(now);
+    });
+}
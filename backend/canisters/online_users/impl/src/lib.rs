@@ -1,4 +1,5 @@
 use crate::model::last_online_dates::LastOnlineDates;
+use crate::model::presence::Presence;
 use crate::model::principal_to_user_id_map::PrincipalToUserIdMap;
 use canister_state_macros::canister_state;
 use serde::{Deserialize, Serialize};
@@ -38,6 +39,7 @@ impl RuntimeState {
             git_commit_id: utils::git::git_commit_id().to_string(),
             mark_as_online_count: self.data.mark_as_online_count,
             active_users: self.data.cached_active_users.clone(),
+            statuses: self.data.presence.statuses_count() as u32,
             canister_ids: CanisterIds {
                 user_index: self.data.user_index_canister_id,
                 cycles_dispenser: self.data.cycles_dispenser_canister_id,
@@ -57,6 +59,8 @@ struct Data {
     #[serde(default)]
     pub rng_seed: [u8; 32],
     pub test_mode: bool,
+    #[serde(default)]
+    pub presence: Presence,
 }
 
 impl Data {
@@ -70,6 +74,7 @@ impl Data {
             cached_active_users: ActiveUsers::default(),
             rng_seed: [0; 32],
             test_mode,
+            presence: Presence::default(),
         }
     }
 }
@@ -83,6 +88,7 @@ pub struct Metrics {
     pub git_commit_id: String,
     pub mark_as_online_count: u64,
     pub active_users: ActiveUsers,
+    pub statuses: u32,
     pub canister_ids: CanisterIds,
 }
 
@@ -1,2 +1,3 @@
 pub mod last_online_dates;
+pub mod presence;
 pub mod principal_to_user_id_map;
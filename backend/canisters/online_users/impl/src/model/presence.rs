@@ -0,0 +1,188 @@
+use online_users_canister::{Availability, PresenceStatus, PresenceVisibility};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use types::{MessageIndex, Milliseconds, TimestampMillis, UserId};
+use utils::time::SECOND_IN_MS;
+
+// Clients should call `set_typing` every few seconds while the user is typing, so typing
+// notifications expire shortly after the user stops typing, even if `typing: false` is never sent
+const TYPING_TTL: Milliseconds = 5 * SECOND_IN_MS;
+
+// Keyed by the recipient, so that users can only see who is typing to them
+type TypingKey = (UserId, Option<MessageIndex>);
+
+#[derive(Serialize, Deserialize, Default)]
+pub struct Presence {
+    statuses: HashMap<UserId, PresenceStatus>,
+    privacy: HashMap<UserId, PrivacySettings>,
+    // Typing notifications are ephemeral so are not persisted across upgrades
+    #[serde(skip)]
+    typing: HashMap<TypingKey, HashMap<UserId, TimestampMillis>>,
+}
+
+#[derive(Serialize, Deserialize, Default)]
+pub struct PrivacySettings {
+    pub last_online: PresenceVisibility,
+    pub status: PresenceVisibility,
+}
+
+impl Presence {
+    pub fn set_status(&mut self, user_id: UserId, status: PresenceStatus) {
+        if status.availability == Availability::Available && status.text.is_none() {
+            self.statuses.remove(&user_id);
+        } else {
+            self.statuses.insert(user_id, status);
+        }
+    }
+
+    pub fn status(&self, user_id: UserId, viewer: Option<UserId>, now: TimestampMillis) -> Option<PresenceStatus> {
+        let status = self.statuses.get(&user_id)?;
+        if status.expires.map_or(false, |ts| ts <= now) {
+            return None;
+        }
+
+        let visible = self
+            .privacy
+            .get(&user_id)
+            .map_or(true, |p| is_visible(&p.status, user_id, viewer));
+
+        visible.then(|| status.clone())
+    }
+
+    pub fn can_see_last_online(&self, user_id: UserId, viewer: Option<UserId>) -> bool {
+        self.privacy
+            .get(&user_id)
+            .map_or(true, |p| is_visible(&p.last_online, user_id, viewer))
+    }
+
+    pub fn set_privacy(&mut self, user_id: UserId, settings: PrivacySettings) {
+        if matches!(settings.last_online, PresenceVisibility::Everyone)
+            && matches!(settings.status, PresenceVisibility::Everyone)
+        {
+            self.privacy.remove(&user_id);
+        } else {
+            self.privacy.insert(user_id, settings);
+        }
+    }
+
+    pub fn set_typing(
+        &mut self,
+        user_id: UserId,
+        recipient: UserId,
+        thread_root_message_index: Option<MessageIndex>,
+        typing: bool,
+        now: TimestampMillis,
+    ) {
+        let key = (recipient, thread_root_message_index);
+        if typing {
+            self.typing.entry(key).or_default().insert(user_id, now + TYPING_TTL);
+        } else if let Some(users) = self.typing.get_mut(&key) {
+            users.remove(&user_id);
+            if users.is_empty() {
+                self.typing.remove(&key);
+            }
+        }
+    }
+
+    // Returns `them` if they are currently typing in their direct chat with the viewer
+    pub fn typing(
+        &self,
+        them: UserId,
+        thread_root_message_index: Option<MessageIndex>,
+        viewer: Option<UserId>,
+        now: TimestampMillis,
+    ) -> Vec<UserId> {
+        let Some(me) = viewer else {
+            return Vec::new();
+        };
+
+        self.typing
+            .get(&(me, thread_root_message_index))
+            .and_then(|users| users.get(&them))
+            .filter(|expires| **expires > now)
+            .map(|_| vec![them])
+            .unwrap_or_default()
+    }
+
+    pub fn prune_expired(&mut self, now: TimestampMillis) {
+        self.typing.retain(|_, users| {
+            users.retain(|_, expires| *expires > now);
+            !users.is_empty()
+        });
+        self.statuses.retain(|_, s| s.expires.map_or(true, |ts| ts > now));
+    }
+
+    pub fn statuses_count(&self) -> usize {
+        self.statuses.len()
+    }
+}
+
+fn is_visible(visibility: &PresenceVisibility, user_id: UserId, viewer: Option<UserId>) -> bool {
+    if viewer == Some(user_id) {
+        return true;
+    }
+
+    match visibility {
+        PresenceVisibility::Everyone => true,
+        PresenceVisibility::SelectedUsers(users) => viewer.map_or(false, |v| users.contains(&v)),
+        PresenceVisibility::Nobody => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use candid::Principal;
+
+    fn user(index: u8) -> UserId {
+        Principal::from_slice(&[index]).into()
+    }
+
+    #[test]
+    fn direct_chat_typing_only_visible_to_recipient() {
+        let mut presence = Presence::default();
+        presence.set_typing(user(1), user(2), None, true, 1000);
+
+        assert_eq!(presence.typing(user(1), None, Some(user(2)), 1001), vec![user(1)]);
+        assert!(presence.typing(user(1), None, Some(user(3)), 1001).is_empty());
+        assert!(presence.typing(user(1), None, None, 1001).is_empty());
+    }
+
+    #[test]
+    fn typing_expires() {
+        let mut presence = Presence::default();
+        presence.set_typing(user(1), user(2), None, true, 1000);
+
+        assert!(presence.typing(user(1), None, Some(user(2)), 1000 + TYPING_TTL).is_empty());
+
+        presence.set_typing(user(1), user(2), None, true, 2000);
+        presence.set_typing(user(1), user(2), None, false, 2001);
+
+        assert!(presence.typing(user(1), None, Some(user(2)), 2002).is_empty());
+    }
+
+    #[test]
+    fn status_respects_privacy() {
+        let mut presence = Presence::default();
+        presence.set_status(
+            user(1),
+            PresenceStatus {
+                availability: Availability::Away,
+                text: None,
+                expires: None,
+            },
+        );
+        presence.set_privacy(
+            user(1),
+            PrivacySettings {
+                last_online: PresenceVisibility::Nobody,
+                status: PresenceVisibility::SelectedUsers(vec![user(2)]),
+            },
+        );
+
+        assert!(presence.status(user(1), Some(user(2)), 0).is_some());
+        assert!(presence.status(user(1), Some(user(3)), 0).is_none());
+        assert!(presence.status(user(1), Some(user(1)), 0).is_some());
+        assert!(!presence.can_see_last_online(user(1), Some(user(2))));
+    }
+}
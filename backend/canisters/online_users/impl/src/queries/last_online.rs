@@ -11,10 +11,15 @@ fn last_online(args: Args) -> Response {
 
 fn last_online_impl(args: Args, state: &RuntimeState) -> Response {
     let now = state.env.now();
+    let caller = state.env.caller();
+    // The UserIndex uses this to measure referral campaign retention, so privacy settings don't apply to it
+    let is_user_index = caller == state.data.user_index_canister_id;
+    let viewer = state.data.principal_to_user_id_map.get(&caller);
 
     let result = args
         .user_ids
         .into_iter()
+        .filter(|u| is_user_index || state.data.presence.can_see_last_online(*u, viewer))
         .filter_map(|u| {
             state.data.last_online_dates.get(u).map(|ts| UserLastOnline {
                 user_id: u,
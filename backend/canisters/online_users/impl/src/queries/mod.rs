@@ -1,2 +1,3 @@
 mod http_request;
 mod last_online;
+mod presence;
@@ -0,0 +1,37 @@
+use crate::{read_state, RuntimeState};
+use canister_tracing_macros::trace;
+use ic_cdk_macros::query;
+use online_users_canister::presence::{Response::*, *};
+
+#[query]
+#[trace]
+fn presence(args: Args) -> Response {
+    read_state(|state| presence_impl(args, state))
+}
+
+fn presence_impl(args: Args, state: &RuntimeState) -> Response {
+    let now = state.env.now();
+    let viewer = state.data.principal_to_user_id_map.get(&state.env.caller());
+    let presence = &state.data.presence;
+
+    let users = args
+        .user_ids
+        .into_iter()
+        .map(|u| UserPresence {
+            user_id: u,
+            duration_since_last_online: presence
+                .can_see_last_online(u, viewer)
+                .then(|| state.data.last_online_dates.get(u))
+                .flatten()
+                .map(|ts| now.saturating_sub(ts)),
+            status: presence.status(u, viewer, now),
+        })
+        .collect();
+
+    let typing = args
+        .typing_in
+        .map(|t| presence.typing(t.user_id, t.thread_root_message_index, viewer, now))
+        .unwrap_or_default();
+
+    Success(SuccessResult { users, typing })
+}
@@ -1,2 +1,41 @@
+use crate::{mutate_state, read_state, RuntimeState};
+use candid::Principal;
+use types::{CanisterId, UserId};
+
 mod mark_as_online;
+mod set_presence_privacy;
+mod set_status;
+mod set_typing;
 mod wallet_receive;
+
+pub(crate) enum LookupUserError {
+    UserNotFound,
+    InternalError(String),
+}
+
+// Returns the caller's userId, calling into the user_index if the caller's principal is not yet known
+pub(crate) async fn lookup_caller_user_id() -> Result<UserId, LookupUserError> {
+    match read_state(try_get_user_id_locally) {
+        Ok(u) => Ok(u),
+        Err((p, user_index_canister_id)) => {
+            let c2c_args = user_index_canister::c2c_lookup_user::Args { user_id_or_principal: p };
+            match user_index_canister_c2c_client::c2c_lookup_user(user_index_canister_id, &c2c_args).await {
+                Ok(user_index_canister::c2c_lookup_user::Response::Success(res)) => {
+                    mutate_state(|state| state.data.principal_to_user_id_map.add(p, res.user_id));
+                    Ok(res.user_id)
+                }
+                Ok(_) => Err(LookupUserError::UserNotFound),
+                Err(error) => Err(LookupUserError::InternalError(format!("{error:?}"))),
+            }
+        }
+    }
+}
+
+fn try_get_user_id_locally(state: &RuntimeState) -> Result<UserId, (Principal, CanisterId)> {
+    let caller = state.env.caller();
+    state
+        .data
+        .principal_to_user_id_map
+        .get(&caller)
+        .ok_or((caller, state.data.user_index_canister_id))
+}
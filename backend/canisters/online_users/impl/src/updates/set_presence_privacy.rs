@@ -0,0 +1,40 @@
+use crate::model::presence::PrivacySettings;
+use crate::updates::{lookup_caller_user_id, LookupUserError};
+use crate::{mutate_state, RuntimeState};
+use canister_tracing_macros::trace;
+use ic_cdk_macros::update;
+use online_users_canister::set_presence_privacy::{Response::*, *};
+use online_users_canister::PresenceVisibility;
+use types::UserId;
+
+const MAX_SELECTED_USERS: u32 = 1000;
+
+#[update]
+#[trace]
+async fn set_presence_privacy(args: Args) -> Response {
+    if [&args.last_online, &args.status]
+        .into_iter()
+        .any(|v| matches!(v, PresenceVisibility::SelectedUsers(users) if users.len() > MAX_SELECTED_USERS as usize))
+    {
+        return TooManyUsers(MAX_SELECTED_USERS);
+    }
+
+    let user_id = match lookup_caller_user_id().await {
+        Ok(u) => u,
+        Err(LookupUserError::UserNotFound) => return UserNotFound,
+        Err(LookupUserError::InternalError(error)) => return InternalError(error),
+    };
+
+    mutate_state(|state| set_presence_privacy_impl(user_id, args, state))
+}
+
+fn set_presence_privacy_impl(user_id: UserId, args: Args, state: &mut RuntimeState) -> Response {
+    state.data.presence.set_privacy(
+        user_id,
+        PrivacySettings {
+            last_online: args.last_online,
+            status: args.status,
+        },
+    );
+    Success
+}
@@ -0,0 +1,40 @@
+use crate::updates::{lookup_caller_user_id, LookupUserError};
+use crate::{mutate_state, RuntimeState};
+use canister_tracing_macros::trace;
+use ic_cdk_macros::update;
+use online_users_canister::set_status::{Response::*, *};
+use online_users_canister::PresenceStatus;
+use types::UserId;
+
+const MAX_STATUS_TEXT_LENGTH: u32 = 100;
+
+#[update]
+#[trace]
+async fn set_status(args: Args) -> Response {
+    if args
+        .text
+        .as_ref()
+        .map_or(false, |t| t.chars().count() > MAX_STATUS_TEXT_LENGTH as usize)
+    {
+        return TextTooLong(MAX_STATUS_TEXT_LENGTH);
+    }
+
+    let user_id = match lookup_caller_user_id().await {
+        Ok(u) => u,
+        Err(LookupUserError::UserNotFound) => return UserNotFound,
+        Err(LookupUserError::InternalError(error)) => return InternalError(error),
+    };
+
+    mutate_state(|state| set_status_impl(user_id, args, state))
+}
+
+fn set_status_impl(user_id: UserId, args: Args, state: &mut RuntimeState) -> Response {
+    let now = state.env.now();
+    let status = PresenceStatus {
+        availability: args.availability,
+        text: args.text.filter(|t| !t.trim().is_empty()),
+        expires: args.expires_in.map(|e| now + e),
+    };
+    state.data.presence.set_status(user_id, status);
+    Success
+}
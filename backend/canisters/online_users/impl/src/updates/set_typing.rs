@@ -0,0 +1,27 @@
+use crate::updates::{lookup_caller_user_id, LookupUserError};
+use crate::{mutate_state, RuntimeState};
+use canister_tracing_macros::trace;
+use ic_cdk_macros::update;
+use online_users_canister::set_typing::{Response::*, *};
+use types::UserId;
+
+#[update]
+#[trace]
+async fn set_typing(args: Args) -> Response {
+    let user_id = match lookup_caller_user_id().await {
+        Ok(u) => u,
+        Err(LookupUserError::UserNotFound) => return UserNotFound,
+        Err(LookupUserError::InternalError(error)) => return InternalError(error),
+    };
+
+    mutate_state(|state| set_typing_impl(user_id, args, state))
+}
+
+fn set_typing_impl(user_id: UserId, args: Args, state: &mut RuntimeState) -> Response {
+    let now = state.env.now();
+    state
+        .data
+        .presence
+        .set_typing(user_id, args.recipient, args.thread_root_message_index, args.typing, now);
+    Success
+}
@@ -70,3 +70,9 @@ mod updates;
 
 pub use lifecycle::*;
 pub use updates::*;
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Copy, Debug, Eq, PartialEq)]
+pub enum DigestFrequency {
+    Daily,
+    Weekly,
+}
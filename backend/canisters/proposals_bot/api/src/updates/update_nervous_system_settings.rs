@@ -0,0 +1,39 @@
+use crate::DigestFrequency;
+use candid::CandidType;
+use human_readable::{HumanReadablePrincipal, ToHumanReadable};
+use serde::{Deserialize, Serialize};
+use types::{CanisterId, OptionUpdate};
+
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub struct Args {
+    pub governance_canister_id: CanisterId,
+    pub digest_frequency: OptionUpdate<DigestFrequency>,
+    // Proposals with these topics (NNS) or actions (SNS) are only included in the digest rather than
+    // being pushed individually
+    pub excluded_topics: Option<Vec<u64>>,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub enum Response {
+    Success,
+    NotFound,
+}
+
+#[derive(Serialize)]
+pub struct HumanReadableArgs {
+    pub governance_canister_id: HumanReadablePrincipal,
+    pub digest_frequency: OptionUpdate<DigestFrequency>,
+    pub excluded_topics: Option<Vec<u64>>,
+}
+
+impl ToHumanReadable for Args {
+    type Target = HumanReadableArgs;
+
+    fn to_human_readable(&self) -> Self::Target {
+        HumanReadableArgs {
+            governance_canister_id: self.governance_canister_id.into(),
+            digest_frequency: self.digest_frequency.clone(),
+            excluded_topics: self.excluded_topics.clone(),
+        }
+    }
+}
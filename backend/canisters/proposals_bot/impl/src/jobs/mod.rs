@@ -4,6 +4,7 @@ mod check_for_nervous_system_updates;
 pub(crate) mod increase_dissolve_delay;
 mod push_proposals;
 mod retrieve_proposals;
+mod send_proposal_digests;
 mod update_finished_proposals;
 mod update_proposals;
 
@@ -12,6 +13,7 @@ pub(crate) fn start(state: &RuntimeState) {
     increase_dissolve_delay::start_job_if_required(state);
     push_proposals::start_job_if_required(state);
     retrieve_proposals::start_job();
+    send_proposal_digests::start_job();
     update_finished_proposals::start_job_if_required(state);
     update_proposals::start_job_if_required(state);
 }
@@ -52,22 +52,14 @@ async fn push_proposal(
 
 async fn push_group_proposal(governance_canister_id: CanisterId, group_id: ChatId, proposal: Proposal) {
     let message_id = generate_message_id(governance_canister_id, proposal.id());
-    let send_message_args = group_canister::send_message_v2::Args {
+    let send_message_args = group_send_message_args(
         message_id,
-        thread_root_message_index: None,
-        content: MessageContentInitial::GovernanceProposal(ProposalContent {
+        MessageContentInitial::GovernanceProposal(ProposalContent {
             governance_canister_id,
             proposal: proposal.clone(),
             my_vote: None,
         }),
-        sender_name: "ProposalsBot".to_string(),
-        sender_display_name: None,
-        replies_to: None,
-        mentioned: Vec::new(),
-        forwarding: false,
-        rules_accepted: None,
-        correlation_id: 0,
-    };
+    );
 
     let response = group_canister_c2c_client::send_message_v2(group_id.into(), &send_message_args).await;
 
@@ -81,14 +73,48 @@ async fn push_channel_proposal(
     proposal: Proposal,
 ) {
     let message_id = generate_message_id(governance_canister_id, proposal.id());
-    let send_message_args = community_canister::send_message::Args {
+    let send_message_args = channel_send_message_args(
+        channel_id,
         message_id,
-        thread_root_message_index: None,
-        content: MessageContentInitial::GovernanceProposal(ProposalContent {
+        MessageContentInitial::GovernanceProposal(ProposalContent {
             governance_canister_id,
             proposal: proposal.clone(),
             my_vote: None,
         }),
+    );
+
+    let response = community_canister_c2c_client::send_message(community_id.into(), &send_message_args).await;
+
+    mark_proposal_pushed(governance_canister_id, proposal, message_id, is_failure(response));
+}
+
+pub(crate) fn group_send_message_args(
+    message_id: MessageId,
+    content: MessageContentInitial,
+) -> group_canister::send_message_v2::Args {
+    group_canister::send_message_v2::Args {
+        message_id,
+        thread_root_message_index: None,
+        content,
+        sender_name: "ProposalsBot".to_string(),
+        sender_display_name: None,
+        replies_to: None,
+        mentioned: Vec::new(),
+        forwarding: false,
+        rules_accepted: None,
+        correlation_id: 0,
+    }
+}
+
+pub(crate) fn channel_send_message_args(
+    channel_id: ChannelId,
+    message_id: MessageId,
+    content: MessageContentInitial,
+) -> community_canister::send_message::Args {
+    community_canister::send_message::Args {
+        message_id,
+        thread_root_message_index: None,
+        content,
         sender_name: "ProposalsBot".to_string(),
         sender_display_name: None,
         replies_to: None,
@@ -97,11 +123,8 @@ async fn push_channel_proposal(
         channel_id,
         community_rules_accepted: None,
         channel_rules_accepted: None,
-    };
-
-    let response = community_canister_c2c_client::send_message(community_id.into(), &send_message_args).await;
-
-    mark_proposal_pushed(governance_canister_id, proposal, message_id, is_failure(response));
+        forum_post: None,
+    }
 }
 
 fn mark_proposal_pushed(governance_canister_id: CanisterId, proposal: Proposal, message_id: MessageId, failed: bool) {
@@ -0,0 +1,50 @@
+use crate::jobs::push_proposals::{channel_send_message_args, group_send_message_args};
+use crate::{mutate_state, RuntimeState};
+use rand::Rng;
+use std::time::Duration;
+use tracing::error;
+use types::{CanisterId, MessageContentInitial, MessageId, MultiUserChat, TextContent};
+use utils::time::HOUR_IN_MS;
+
+pub fn start_job() {
+    ic_cdk_timers::set_timer_interval(Duration::from_millis(HOUR_IN_MS), run);
+}
+
+fn run() {
+    for (governance_canister_id, chat_id, message_id, text) in mutate_state(take_due_digests) {
+        ic_cdk::spawn(send_digest(governance_canister_id, chat_id, message_id, text));
+    }
+}
+
+fn take_due_digests(state: &mut RuntimeState) -> Vec<(CanisterId, MultiUserChat, MessageId, String)> {
+    let now = state.env.now();
+    let digests = state.data.nervous_systems.take_due_digests(now);
+
+    digests
+        .into_iter()
+        .map(|(governance_canister_id, chat_id, text)| (governance_canister_id, chat_id, state.env.rng().gen(), text))
+        .collect()
+}
+
+async fn send_digest(governance_canister_id: CanisterId, chat_id: MultiUserChat, message_id: MessageId, text: String) {
+    let content = MessageContentInitial::Text(TextContent { text });
+
+    let failed = match chat_id {
+        MultiUserChat::Group(group_id) => {
+            let args = group_send_message_args(message_id, content);
+            group_canister_c2c_client::send_message_v2(group_id.into(), &args)
+                .await
+                .is_err()
+        }
+        MultiUserChat::Channel(community_id, channel_id) => {
+            let args = channel_send_message_args(channel_id, message_id, content);
+            community_canister_c2c_client::send_message(community_id.into(), &args)
+                .await
+                .is_err()
+        }
+    };
+
+    if failed {
+        error!(%governance_canister_id, "Failed to send proposals digest");
+    }
+}
@@ -4,6 +4,7 @@ use candid::{CandidType, Principal};
 use canister_state_macros::canister_state;
 use canister_timer_jobs::TimerJobs;
 use fire_and_forget_handler::FireAndForgetHandler;
+use proposals_bot_canister::DigestFrequency;
 use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
 use std::cell::RefCell;
@@ -143,6 +144,8 @@ pub struct NervousSystemMetrics {
     pub min_neuron_stake: u64,
     pub min_dissolve_delay_to_vote: Milliseconds,
     pub proposal_rejection_fee: u64,
+    pub digest_frequency: Option<DigestFrequency>,
+    pub excluded_topics: Vec<u64>,
 }
 
 #[derive(Serialize, Debug)]
@@ -1 +1,2 @@
 pub mod nervous_systems;
+pub mod proposals_digest;
@@ -1,12 +1,14 @@
+use crate::model::proposals_digest::ProposalsDigest;
 use crate::NervousSystemMetrics;
 use itertools::Itertools;
+use proposals_bot_canister::DigestFrequency;
 use serde::{Deserialize, Serialize};
 use std::cmp::max;
 use std::collections::hash_map::Entry::{Occupied, Vacant};
-use std::collections::{BTreeMap, HashMap};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 use std::mem;
 use types::{
-    icrc1, CanisterId, MessageId, Milliseconds, MultiUserChat, Proposal, ProposalDecisionStatus, ProposalId,
+    icrc1, CanisterId, MessageId, Milliseconds, MultiUserChat, OptionUpdate, Proposal, ProposalDecisionStatus, ProposalId,
     ProposalRewardStatus, ProposalUpdate, SnsNeuronId, TimestampMillis, UserId,
 };
 
@@ -192,12 +194,57 @@ impl NervousSystems {
                 ns.mark_proposal_inactive(proposal);
             }
 
+            if let Some(digest) = ns.digest.as_mut() {
+                for proposal in active_proposals.iter() {
+                    digest.record(proposal);
+                }
+                digest.retain(&active_proposals.iter().map(|p| p.id()).collect::<HashSet<_>>());
+            }
+
             for proposal in active_proposals {
                 ns.process_proposal(proposal);
             }
         }
     }
 
+    pub fn update_settings(
+        &mut self,
+        governance_canister_id: &CanisterId,
+        digest_frequency: OptionUpdate<DigestFrequency>,
+        excluded_topics: Option<Vec<u64>>,
+        now: TimestampMillis,
+    ) -> bool {
+        if let Some(ns) = self.nervous_systems.get_mut(governance_canister_id) {
+            match digest_frequency {
+                OptionUpdate::NoChange => {}
+                OptionUpdate::SetToNone => ns.digest = None,
+                OptionUpdate::SetToSome(frequency) => match ns.digest.as_mut() {
+                    Some(digest) => digest.set_frequency(frequency),
+                    None => ns.digest = Some(ProposalsDigest::new(frequency, now)),
+                },
+            }
+            if let Some(topics) = excluded_topics {
+                ns.excluded_topics = topics.into_iter().collect();
+            }
+            true
+        } else {
+            false
+        }
+    }
+
+    // Returns the text of each digest which is due along with the chat it should be sent to
+    pub fn take_due_digests(&mut self, now: TimestampMillis) -> Vec<(CanisterId, MultiUserChat, String)> {
+        self.nervous_systems
+            .values_mut()
+            .filter_map(|ns| {
+                let digest = ns.digest.as_mut().filter(|d| d.is_due(now))?;
+                digest
+                    .take_digest(now)
+                    .map(|text| (ns.governance_canister_id, ns.chat_id, text))
+            })
+            .collect()
+    }
+
     pub fn take_newly_decided_user_submitted_proposals(
         &mut self,
         governance_canister_id: CanisterId,
@@ -319,6 +366,10 @@ pub struct NervousSystem {
     min_neuron_stake: u64,
     min_dissolve_delay_to_vote: Milliseconds,
     proposal_rejection_fee: u64,
+    #[serde(default)]
+    digest: Option<ProposalsDigest>,
+    #[serde(default)]
+    excluded_topics: BTreeSet<u64>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Default)]
@@ -355,6 +406,8 @@ impl NervousSystem {
             min_neuron_stake: nervous_system.min_neuron_stake,
             min_dissolve_delay_to_vote: nervous_system.min_dissolve_delay_to_vote,
             proposal_rejection_fee: nervous_system.proposal_rejection_fee,
+            digest: None,
+            excluded_topics: BTreeSet::new(),
         }
     }
 
@@ -393,7 +446,7 @@ impl NervousSystem {
             };
 
             self.upsert_proposal_update(update);
-        } else {
+        } else if !self.excluded_topics.contains(&proposal.topic()) {
             self.proposals_to_be_pushed.queue.insert(proposal_id, proposal);
         }
     }
@@ -469,6 +522,8 @@ impl From<&NervousSystem> for NervousSystemMetrics {
             min_neuron_stake: ns.min_neuron_stake,
             min_dissolve_delay_to_vote: ns.min_dissolve_delay_to_vote,
             proposal_rejection_fee: ns.proposal_rejection_fee,
+            digest_frequency: ns.digest.as_ref().map(|d| d.frequency()),
+            excluded_topics: ns.excluded_topics.iter().copied().collect(),
         }
     }
 }
@@ -0,0 +1,160 @@
+use proposals_bot_canister::DigestFrequency;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+use std::fmt::Write;
+use types::{Milliseconds, Proposal, ProposalDecisionStatus, ProposalId, Tally, TimestampMillis};
+use utils::time::{DAY_IN_MS, HOUR_IN_MS, WEEK_IN_MS};
+
+// Tracks the active proposals of a nervous system so that a digest can periodically be posted
+// summarising the proposals which were opened or decided since the previous digest, along with
+// those which are about to expire.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct ProposalsDigest {
+    frequency: DigestFrequency,
+    last_sent: TimestampMillis,
+    proposals: BTreeMap<ProposalId, TrackedProposal>,
+    opened: BTreeSet<ProposalId>,
+    decided: BTreeMap<ProposalId, TrackedProposal>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+struct TrackedProposal {
+    title: String,
+    status: ProposalDecisionStatus,
+    tally: Tally,
+    deadline: TimestampMillis,
+}
+
+impl ProposalsDigest {
+    pub fn new(frequency: DigestFrequency, now: TimestampMillis) -> ProposalsDigest {
+        ProposalsDigest {
+            frequency,
+            last_sent: now,
+            proposals: BTreeMap::new(),
+            opened: BTreeSet::new(),
+            decided: BTreeMap::new(),
+        }
+    }
+
+    pub fn frequency(&self) -> DigestFrequency {
+        self.frequency
+    }
+
+    pub fn set_frequency(&mut self, frequency: DigestFrequency) {
+        self.frequency = frequency;
+    }
+
+    pub fn record(&mut self, proposal: &Proposal) {
+        let proposal_id = proposal.id();
+        let tracked = TrackedProposal {
+            title: proposal.title().to_string(),
+            status: proposal.status(),
+            tally: proposal.tally(),
+            deadline: proposal.deadline(),
+        };
+
+        let was_open = match self.proposals.get(&proposal_id) {
+            Some(previous) => is_open(previous.status),
+            None => {
+                if proposal.created() >= self.last_sent {
+                    self.opened.insert(proposal_id);
+                }
+                true
+            }
+        };
+
+        if was_open && !is_open(tracked.status) {
+            self.decided.insert(proposal_id, tracked.clone());
+        }
+        self.proposals.insert(proposal_id, tracked);
+    }
+
+    // Stops tracking any proposals which are no longer active
+    pub fn retain(&mut self, active: &HashSet<ProposalId>) {
+        self.proposals.retain(|id, _| active.contains(id));
+    }
+
+    pub fn is_due(&self, now: TimestampMillis) -> bool {
+        now.saturating_sub(self.last_sent) >= self.interval()
+    }
+
+    // Builds the text of the digest then resets the proposals opened and decided. Returns `None`
+    // if there is nothing to report.
+    pub fn take_digest(&mut self, now: TimestampMillis) -> Option<String> {
+        let closing_soon: Vec<_> = self
+            .proposals
+            .iter()
+            .filter(|(_, p)| is_open(p.status) && p.deadline > now && p.deadline <= now + self.interval())
+            .collect();
+
+        let mut text = String::new();
+
+        if !self.opened.is_empty() {
+            writeln!(text, "**Opened ({})**", self.opened.len()).unwrap();
+            for (id, proposal) in self.opened.iter().filter_map(|id| self.proposals.get(id).map(|p| (id, p))) {
+                writeln!(text, "- {}", format_proposal(*id, proposal, now)).unwrap();
+            }
+            writeln!(text).unwrap();
+        }
+        if !self.decided.is_empty() {
+            writeln!(text, "**Decided ({})**", self.decided.len()).unwrap();
+            for (id, proposal) in self.decided.iter() {
+                writeln!(text, "- {}", format_proposal(*id, proposal, now)).unwrap();
+            }
+            writeln!(text).unwrap();
+        }
+        if !closing_soon.is_empty() {
+            writeln!(text, "**Closing soon ({})**", closing_soon.len()).unwrap();
+            for (id, proposal) in closing_soon {
+                writeln!(text, "- {}", format_proposal(*id, proposal, now)).unwrap();
+            }
+        }
+
+        self.last_sent = now;
+        self.opened.clear();
+        self.decided.clear();
+
+        if text.is_empty() {
+            None
+        } else {
+            let heading = match self.frequency {
+                DigestFrequency::Daily => "Daily proposals digest",
+                DigestFrequency::Weekly => "Weekly proposals digest",
+            };
+            Some(format!("## {heading}\n\n{}", text.trim_end()))
+        }
+    }
+
+    fn interval(&self) -> Milliseconds {
+        match self.frequency {
+            DigestFrequency::Daily => DAY_IN_MS,
+            DigestFrequency::Weekly => WEEK_IN_MS,
+        }
+    }
+}
+
+fn is_open(status: ProposalDecisionStatus) -> bool {
+    matches!(status, ProposalDecisionStatus::Open | ProposalDecisionStatus::Unspecified)
+}
+
+fn format_proposal(proposal_id: ProposalId, proposal: &TrackedProposal, now: TimestampMillis) -> String {
+    let tally = &proposal.tally;
+    let (yes, no) = if tally.total > 0 {
+        (
+            tally.yes as u128 * 100 / tally.total as u128,
+            tally.no as u128 * 100 / tally.total as u128,
+        )
+    } else {
+        (0, 0)
+    };
+
+    let mut text = format!("{proposal_id}: {} (yes {yes}%, no {no}%", proposal.title);
+    if is_open(proposal.status) {
+        let hours_remaining = proposal.deadline.saturating_sub(now) / HOUR_IN_MS;
+        write!(text, ", closes in {hours_remaining}h").unwrap();
+    } else {
+        write!(text, ", {:?}", proposal.status).unwrap();
+    }
+    text.push(')');
+    text
+}
@@ -2,4 +2,5 @@ pub mod appoint_admins;
 pub mod c2c_submit_proposal;
 pub mod import_proposals_group_into_community;
 pub mod stake_neuron_for_submitting_proposals;
+pub mod update_nervous_system_settings;
 pub mod wallet_receive;
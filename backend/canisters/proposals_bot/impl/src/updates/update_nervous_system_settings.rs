@@ -0,0 +1,25 @@
+use crate::guards::caller_is_governance_principal;
+use crate::{mutate_state, RuntimeState};
+use canister_api_macros::proposal;
+use canister_tracing_macros::trace;
+use proposals_bot_canister::update_nervous_system_settings::{Response::*, *};
+
+#[proposal(guard = "caller_is_governance_principal")]
+#[trace]
+fn update_nervous_system_settings(args: Args) -> Response {
+    mutate_state(|state| update_nervous_system_settings_impl(args, state))
+}
+
+fn update_nervous_system_settings_impl(args: Args, state: &mut RuntimeState) -> Response {
+    let now = state.env.now();
+    if state.data.nervous_systems.update_settings(
+        &args.governance_canister_id,
+        args.digest_frequency,
+        args.excluded_topics,
+        now,
+    ) {
+        Success
+    } else {
+        NotFound
+    }
+}
@@ -0,0 +1,97 @@
+use crate::model::image_resizer::{ImageResizer, StepResult};
+use crate::model::index_sync_state::EventToSync;
+use crate::model::users::{FileStatusInternal, IndexSyncComplete};
+use crate::{mutate_state, RuntimeState};
+use ic_cdk_timers::TimerId;
+use rand::Rng;
+use std::cell::Cell;
+use std::time::Duration;
+use tracing::{error, trace};
+
+// Each step leaves plenty of headroom below the instruction limit of a single message
+const MAX_INSTRUCTIONS_PER_STEP: u64 = 2_000_000_000;
+
+thread_local! {
+    static TIMER_ID: Cell<Option<TimerId>> = Cell::default();
+}
+
+pub(crate) fn start_job_if_required(state: &RuntimeState) -> bool {
+    if TIMER_ID.get().is_none() && !state.data.image_variants.is_empty() {
+        let timer_id = ic_cdk_timers::set_timer_interval(Duration::ZERO, run);
+        TIMER_ID.set(Some(timer_id));
+        trace!("'generate_image_variants' job started");
+        true
+    } else {
+        false
+    }
+}
+
+fn run() {
+    if !mutate_state(run_step) {
+        if let Some(timer_id) = TIMER_ID.take() {
+            ic_cdk_timers::clear_timer(timer_id);
+            trace!("'generate_image_variants' job stopped");
+        }
+    }
+}
+
+// Returns false once there are no more variants to generate
+fn run_step(state: &mut RuntimeState) -> bool {
+    let Some((file_id, spec)) = state.data.image_variants.peek() else {
+        return false;
+    };
+
+    if state.data.image_variants.in_progress_mut().is_none() {
+        let bytes = state
+            .data
+            .files
+            .get(&file_id)
+            .filter(|_| state.data.files.image_variant(&file_id, &spec).is_none())
+            .filter(|_| state.data.files.can_generate_image_variants(&file_id))
+            .and_then(|f| state.data.files.blob_bytes(&f.hash));
+
+        if let Some(bytes) = bytes {
+            state.data.image_variants.set_in_progress(ImageResizer::new(bytes, spec));
+        } else {
+            // Either the file has been removed, the variant has already been generated, or the
+            // file can't be resized
+            state.data.image_variants.mark_completed();
+            return true;
+        }
+    }
+
+    let start = ic_cdk::api::instruction_counter();
+    let resizer = state.data.image_variants.in_progress_mut().unwrap();
+
+    match resizer.step(|| ic_cdk::api::instruction_counter() - start > MAX_INSTRUCTIONS_PER_STEP) {
+        StepResult::InProgress => return true,
+        StepResult::Completed(bytes) => {
+            let now = state.env.now();
+            let canister_id = state.env.canister_id();
+            let file_id_seed: u128 = state.env.rng().gen();
+
+            if let Some(file_added) = state
+                .data
+                .files
+                .add_image_variant(file_id, spec, bytes, canister_id, file_id_seed, now)
+            {
+                // Variants are owned by the owner of the original file so that they count
+                // towards that user's storage allowance
+                if let Some(user) = state.data.users.get_mut(&file_added.meta_data.owner) {
+                    user.set_file_status(file_added.file_id, FileStatusInternal::Complete(IndexSyncComplete::No));
+                }
+                state.data.index_sync_state.enqueue(EventToSync::FileAdded(file_added));
+            }
+        }
+        StepResult::NotRequired => {
+            state.data.files.link_image_variant_to_original(file_id, spec);
+        }
+        StepResult::Failed(error) => {
+            error!(file_id, ?spec, error, "Failed to generate image variant");
+            state.data.files.link_image_variant_to_original(file_id, spec);
+        }
+    }
+
+    state.data.image_variants.mark_completed();
+    true
+}
@@ -1,5 +1,6 @@
 mod calculate_freezing_limit;
 mod check_cycles_balance;
+pub mod generate_image_variants;
 
 pub(crate) fn start() {
     calculate_freezing_limit::start_job();
@@ -1,4 +1,5 @@
 use crate::model::files::{Files, RemoveFileResult};
+use crate::model::image_variants::PendingImageVariants;
 use crate::model::index_sync_state::{EventToSync, IndexSyncState};
 use crate::model::users::Users;
 use candid::{CandidType, Principal};
@@ -64,6 +65,7 @@ impl RuntimeState {
             file_count: file_metrics.file_count,
             blob_count: file_metrics.blob_count,
             index_sync_queue_length: self.data.index_sync_state.queue_len(),
+            image_variants_queue_length: self.data.image_variants.len(),
             freezing_limit: self.data.freezing_limit.value.unwrap_or_default(),
         }
     }
@@ -75,6 +77,8 @@ struct Data {
     users: Users,
     files: Files,
     index_sync_state: IndexSyncState,
+    #[serde(default)]
+    image_variants: PendingImageVariants,
     created: TimestampMillis,
     freezing_limit: Timestamped<Option<Cycles>>,
     #[serde(default)]
@@ -89,6 +93,7 @@ impl Data {
             users: Users::default(),
             files: Files::default(),
             index_sync_state: IndexSyncState::default(),
+            image_variants: PendingImageVariants::default(),
             created: now,
             freezing_limit: Timestamped::default(),
             rng_seed: [0; 32],
@@ -99,8 +104,10 @@ impl Data {
     pub fn remove_file(&mut self, caller: Principal, file_id: FileId) -> RemoveFileResult {
         let result = self.files.remove(caller, file_id);
 
-        if let RemoveFileResult::Success(f) = &result {
-            self.index_sync_state.enqueue(EventToSync::FileRemoved(f.clone()));
+        if let RemoveFileResult::Success(files_removed) = &result {
+            for file_removed in files_removed {
+                self.index_sync_state.enqueue(EventToSync::FileRemoved(file_removed.clone()));
+            }
         }
 
         result
@@ -118,6 +125,7 @@ pub struct Metrics {
     pub file_count: u64,
     pub blob_count: u64,
     pub index_sync_queue_length: u32,
+    pub image_variants_queue_length: u32,
     pub freezing_limit: Cycles,
 }
 
@@ -1,3 +1,5 @@
+use crate::model::files::RemoveFileResult;
+use crate::model::index_sync_state::EventToSync;
 use crate::model::users::FileStatusInternal;
 use crate::{mutate_state, RuntimeState, DATA_LIMIT_BYTES};
 use ic_cdk_macros::heartbeat;
@@ -55,8 +57,12 @@ mod sync_index {
 
                     if let Some(FileStatusInternal::Uploading(_)) = old_status {
                         state.data.files.remove_pending_file(&file_id);
-                    } else {
-                        state.data.files.remove(user_id, file_id);
+                    } else if let RemoveFileResult::Success(files_removed) = state.data.files.remove(user_id, file_id) {
+                        // The index doesn't hold the rejected file, but it does hold any image
+                        // variants which were generated from it, so those need to be removed
+                        for variant_removed in files_removed.into_iter().skip(1) {
+                            state.data.index_sync_state.enqueue(EventToSync::FileRemoved(variant_removed));
+                        }
                     }
                 }
             }
@@ -1,4 +1,4 @@
-use crate::{mutate_state, Data, RuntimeState, WASM_VERSION};
+use crate::{mutate_state, read_state, Data, RuntimeState, WASM_VERSION};
 use std::time::Duration;
 use tracing::trace;
 use types::{BuildVersion, Timestamped};
@@ -24,6 +24,7 @@ fn init_state(env: Box<dyn Environment>, data: Data, wasm_version: BuildVersion)
 
     crate::jobs::start();
     crate::init_state(state);
+    read_state(crate::jobs::generate_image_variants::start_job_if_required);
     WASM_VERSION.set(Timestamped::new(wasm_version, now));
 }
 
@@ -1,3 +1,5 @@
+use crate::model::image_resizer::MAX_SOURCE_BYTES;
+use crate::model::image_variants::{is_resizable_mime_type, ImageVariant, ImageVariantSpec};
 use crate::model::stable_blob_storage::StableBlobStorage;
 use crate::{calc_chunk_count, MAX_BLOB_SIZE_BYTES};
 use candid::Principal;
@@ -29,6 +31,10 @@ pub struct File {
     pub accessors: HashSet<AccessorId>,
     pub hash: Hash,
     pub mime_type: String,
+    #[serde(default)]
+    pub variants: Vec<ImageVariant>,
+    #[serde(default)]
+    pub variant_of: Option<FileId>,
 }
 
 impl File {
@@ -145,9 +151,9 @@ impl Files {
         if let Occupied(e) = self.files.entry(file_id) {
             if e.get().can_be_removed_by(caller) {
                 let file = e.remove();
-                let file_removed = self.process_removed_file(file_id, file);
+                let files_removed = self.process_removed_file(file_id, file);
 
-                RemoveFileResult::Success(file_removed)
+                RemoveFileResult::Success(files_removed)
             } else {
                 RemoveFileResult::NotAuthorized
             }
@@ -159,9 +165,9 @@ impl Files {
     pub fn remove_unchecked(&mut self, file_id: FileId) -> RemoveFileResult {
         if let Occupied(e) = self.files.entry(file_id) {
             let file = e.remove();
-            let file_removed = self.process_removed_file(file_id, file);
+            let files_removed = self.process_removed_file(file_id, file);
 
-            RemoveFileResult::Success(file_removed)
+            RemoveFileResult::Success(files_removed)
         } else {
             RemoveFileResult::NotFound
         }
@@ -194,6 +200,8 @@ impl Files {
             accessors,
             hash,
             mime_type: file.mime_type,
+            variants: Vec::new(),
+            variant_of: None,
         };
 
         if self.files.insert(new_file_id, new_file).is_none() {
@@ -289,12 +297,83 @@ impl Files {
         let mut files_removed = Vec::with_capacity(files_to_remove.len());
         for file_id in files_to_remove {
             if let Some(file) = self.files.remove(&file_id) {
-                files_removed.push(self.process_removed_file(file_id, file));
+                files_removed.extend(self.process_removed_file(file_id, file));
             }
         }
         files_removed
     }
 
+    // Variants are only generated from original images which are small enough to be resized
+    pub fn can_generate_image_variants(&self, file_id: &FileId) -> bool {
+        self.files.get(file_id).map_or(false, |f| {
+            f.variant_of.is_none()
+                && is_resizable_mime_type(&f.mime_type)
+                && self.blobs.data_size(&f.hash).map_or(false, |s| s <= MAX_SOURCE_BYTES)
+        })
+    }
+
+    pub fn image_variant(&self, file_id: &FileId, spec: &ImageVariantSpec) -> Option<FileId> {
+        self.files
+            .get(file_id)?
+            .variants
+            .iter()
+            .find(|v| v.spec == *spec)
+            .map(|v| v.file_id)
+    }
+
+    pub fn add_image_variant(
+        &mut self,
+        file_id: FileId,
+        spec: ImageVariantSpec,
+        bytes: Vec<u8>,
+        canister_id: CanisterId,
+        file_id_seed: u128,
+        now: TimestampMillis,
+    ) -> Option<FileAdded> {
+        let original = self.files.get(&file_id)?;
+        let owner = original.owner;
+        let accessors = original.accessors.clone();
+
+        let hash = hash_bytes(&bytes);
+        let size = bytes.len() as u64;
+        let variant_file_id = generate_file_id(canister_id, owner, hash, file_id_seed, now);
+
+        self.accessors_map
+            .link_many(owner, accessors.iter().copied(), variant_file_id);
+        self.reference_counts.incr(hash);
+        self.add_blob_if_not_exists(hash, bytes);
+
+        let variant = File {
+            owner,
+            created: now,
+            accessors,
+            hash,
+            mime_type: spec.format.mime_type().to_string(),
+            variants: Vec::new(),
+            variant_of: Some(file_id),
+        };
+        let meta_data = variant.meta_data();
+
+        if self.files.insert(variant_file_id, variant).is_some() {
+            // There should never be a file_id clash
+            unreachable!();
+        }
+        self.set_image_variant(file_id, spec, variant_file_id);
+
+        Some(FileAdded {
+            file_id: variant_file_id,
+            hash,
+            size,
+            meta_data,
+        })
+    }
+
+    // Points the variant back at the original file, used when the original doesn't need resizing
+    // or resizing it failed
+    pub fn link_image_variant_to_original(&mut self, file_id: FileId, spec: ImageVariantSpec) {
+        self.set_image_variant(file_id, spec, file_id);
+    }
+
     pub fn data_size(&self, hash: &Hash) -> Option<u64> {
         self.blobs.data_size(hash)
     }
@@ -329,11 +408,14 @@ impl Files {
                 accessors: completed_file.accessors,
                 hash: completed_file.hash,
                 mime_type: completed_file.mime_type,
+                variants: Vec::new(),
+                variant_of: None,
             },
         );
     }
 
-    fn process_removed_file(&mut self, file_id: FileId, file: File) -> FileRemoved {
+    // Returns the removed file followed by any image variants which were generated from it
+    fn process_removed_file(&mut self, file_id: FileId, file: File) -> Vec<FileRemoved> {
         if self.reference_counts.decr(file.hash) == 0 {
             self.remove_blob(&file.hash);
         }
@@ -342,9 +424,38 @@ impl Files {
             self.accessors_map.unlink(*accessor_id, &file_id);
         }
 
-        FileRemoved {
+        // If a variant is removed (eg. because the index rejected it) we fall back to serving the
+        // original file rather than generating the variant again
+        if let Some(original) = file.variant_of.and_then(|id| self.files.get_mut(&id)) {
+            for variant in original.variants.iter_mut().filter(|v| v.file_id == file_id) {
+                variant.file_id = file.variant_of.unwrap();
+            }
+        }
+
+        let mut files_removed = vec![FileRemoved {
             file_id,
             meta_data: file.meta_data(),
+        }];
+
+        for variant_file_id in file.variants.iter().map(|v| v.file_id).filter(|id| *id != file_id) {
+            if let Some(variant) = self.files.remove(&variant_file_id) {
+                files_removed.extend(self.process_removed_file(variant_file_id, variant));
+            }
+        }
+
+        files_removed
+    }
+
+    fn set_image_variant(&mut self, file_id: FileId, spec: ImageVariantSpec, variant_file_id: FileId) {
+        if let Some(file) = self.files.get_mut(&file_id) {
+            if let Some(variant) = file.variants.iter_mut().find(|v| v.spec == spec) {
+                variant.file_id = variant_file_id;
+            } else {
+                file.variants.push(ImageVariant {
+                    spec,
+                    file_id: variant_file_id,
+                });
+            }
         }
     }
 
@@ -571,7 +682,7 @@ pub struct PutChunkResultSuccess {
 }
 
 pub enum RemoveFileResult {
-    Success(FileRemoved),
+    Success(Vec<FileRemoved>),
     NotAuthorized,
     NotFound,
 }
@@ -0,0 +1,273 @@
+use crate::model::image_variants::{ImageVariantFormat, ImageVariantSpec};
+use image::codecs::jpeg::JpegEncoder;
+use image::codecs::webp::WebPEncoder;
+use image::io::Reader;
+use image::ColorType;
+use std::io::Cursor;
+
+// Decoding and encoding can't be split across messages, so the source image is capped such that each
+// of these can comfortably be completed within a single step
+pub const MAX_SOURCE_BYTES: u64 = 5 * 1024 * 1024; // 5MB
+const MAX_SOURCE_PIXELS: u64 = 4_000_000;
+const JPEG_QUALITY: u8 = 80;
+
+// Resizing a large image can take more instructions than are allowed in a single message, so the
+// work is split into stages, with the resizing itself being performed a batch of rows at a time.
+// Decoding and encoding are each given a step of their own.
+pub struct ImageResizer {
+    spec: ImageVariantSpec,
+    stage: Stage,
+}
+
+enum Stage {
+    Decode(Vec<u8>),
+    Resize(ResizeState),
+    Encode(ResizedImage),
+}
+
+struct ResizeState {
+    source: Vec<u8>,
+    source_width: u32,
+    source_height: u32,
+    output: ResizedImage,
+    next_row: u32,
+}
+
+struct ResizedImage {
+    rgba: Vec<u8>,
+    width: u32,
+    height: u32,
+}
+
+pub enum StepResult {
+    InProgress,
+    // The resized image, encoded in the requested format
+    Completed(Vec<u8>),
+    // The original image is already no larger than the requested size
+    NotRequired,
+    Failed(String),
+}
+
+impl ImageResizer {
+    pub fn new(bytes: Vec<u8>, spec: ImageVariantSpec) -> ImageResizer {
+        ImageResizer {
+            spec,
+            stage: Stage::Decode(bytes),
+        }
+    }
+
+    // Performs a single stage of work, or in the case of resizing, as many rows as possible until
+    // either the image is resized or `limit_reached` returns true.
+    pub fn step<F: Fn() -> bool>(&mut self, limit_reached: F) -> StepResult {
+        match &mut self.stage {
+            Stage::Decode(bytes) => match decode(bytes, self.spec.size) {
+                Ok(Some(state)) => {
+                    self.stage = Stage::Resize(state);
+                    StepResult::InProgress
+                }
+                Ok(None) => StepResult::NotRequired,
+                Err(error) => StepResult::Failed(error),
+            },
+            Stage::Resize(state) => {
+                while state.next_row < state.output.height {
+                    state.resize_row(state.next_row);
+                    state.next_row += 1;
+
+                    if limit_reached() {
+                        break;
+                    }
+                }
+                if state.next_row == state.output.height {
+                    let output = std::mem::replace(&mut state.output, ResizedImage::empty());
+                    self.stage = Stage::Encode(output);
+                }
+                StepResult::InProgress
+            }
+            Stage::Encode(image) => match encode(image, self.spec.format) {
+                Ok(bytes) => StepResult::Completed(bytes),
+                Err(error) => StepResult::Failed(error),
+            },
+        }
+    }
+}
+
+impl ResizeState {
+    // Each output pixel is the average of the block of source pixels which it covers
+    fn resize_row(&mut self, y: u32) {
+        let (sy_start, sy_end) = source_range(y, self.output.height, self.source_height);
+
+        for x in 0..self.output.width {
+            let (sx_start, sx_end) = source_range(x, self.output.width, self.source_width);
+
+            let mut totals = [0u64; 4];
+            for sy in sy_start..sy_end {
+                let row_offset = (sy as usize) * (self.source_width as usize) * 4;
+                for sx in sx_start..sx_end {
+                    let offset = row_offset + (sx as usize) * 4;
+                    for (channel, total) in totals.iter_mut().enumerate() {
+                        *total += self.source[offset + channel] as u64;
+                    }
+                }
+            }
+
+            let pixel_count = ((sy_end - sy_start) as u64) * ((sx_end - sx_start) as u64);
+            let output_offset = ((y as usize) * (self.output.width as usize) + (x as usize)) * 4;
+            for (channel, total) in totals.into_iter().enumerate() {
+                self.output.rgba[output_offset + channel] = (total / pixel_count) as u8;
+            }
+        }
+    }
+}
+
+impl ResizedImage {
+    fn empty() -> ResizedImage {
+        ResizedImage {
+            rgba: Vec::new(),
+            width: 0,
+            height: 0,
+        }
+    }
+}
+
+fn decode(bytes: &[u8], max_dimension: u32) -> Result<Option<ResizeState>, String> {
+    if bytes.len() as u64 > MAX_SOURCE_BYTES {
+        return Err(format!("Image too large: {} bytes", bytes.len()));
+    }
+
+    let reader = Reader::new(Cursor::new(bytes))
+        .with_guessed_format()
+        .map_err(|e| format!("{e:?}"))?;
+
+    let (width, height) = reader.into_dimensions().map_err(|e| format!("{e:?}"))?;
+    if (width as u64) * (height as u64) > MAX_SOURCE_PIXELS {
+        return Err(format!("Image too large: {width}x{height}"));
+    }
+    if width.max(height) <= max_dimension {
+        return Ok(None);
+    }
+
+    let image = image::load_from_memory(bytes).map_err(|e| format!("{e:?}"))?;
+    let source = image.into_rgba8();
+
+    let scale = max_dimension as f64 / width.max(height) as f64;
+    let output_width = ((width as f64 * scale).round() as u32).max(1);
+    let output_height = ((height as f64 * scale).round() as u32).max(1);
+
+    Ok(Some(ResizeState {
+        source: source.into_raw(),
+        source_width: width,
+        source_height: height,
+        output: ResizedImage {
+            rgba: vec![0; (output_width as usize) * (output_height as usize) * 4],
+            width: output_width,
+            height: output_height,
+        },
+        next_row: 0,
+    }))
+}
+
+fn encode(image: &ResizedImage, format: ImageVariantFormat) -> Result<Vec<u8>, String> {
+    let mut bytes = Vec::new();
+
+    match format {
+        ImageVariantFormat::Jpeg => {
+            // JPEG doesn't support transparency so we drop the alpha channel
+            let rgb: Vec<u8> = image.rgba.chunks_exact(4).flat_map(|p| [p[0], p[1], p[2]]).collect();
+
+            JpegEncoder::new_with_quality(&mut bytes, JPEG_QUALITY)
+                .encode(&rgb, image.width, image.height, ColorType::Rgb8)
+                .map_err(|e| format!("{e:?}"))?;
+        }
+        ImageVariantFormat::WebP => {
+            WebPEncoder::new_lossless(&mut bytes)
+                .encode(&image.rgba, image.width, image.height, ColorType::Rgba8)
+                .map_err(|e| format!("{e:?}"))?;
+        }
+    }
+
+    Ok(bytes)
+}
+
+// Returns the range of source pixels (along one axis) covered by the output pixel at `index`
+fn source_range(index: u32, output_len: u32, source_len: u32) -> (u32, u32) {
+    let start = ((index as u64) * (source_len as u64) / (output_len as u64)) as u32;
+    let end = (((index as u64) + 1) * (source_len as u64) / (output_len as u64)) as u32;
+
+    (start, end.max(start + 1).min(source_len))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::{DynamicImage, ImageOutputFormat, RgbaImage};
+
+    #[test]
+    fn resize_image() {
+        let mut resizer = ImageResizer::new(png(600, 300), spec(128));
+
+        let (bytes, _) = run_to_completion(&mut resizer, || false);
+        let output = image::load_from_memory(&bytes).unwrap();
+
+        assert_eq!(output.width(), 128);
+        assert_eq!(output.height(), 64);
+    }
+
+    #[test]
+    fn resize_is_split_across_steps() {
+        let mut resizer = ImageResizer::new(png(600, 300), spec(128));
+
+        let (_, steps) = run_to_completion(&mut resizer, || true);
+
+        // 1 step to decode, 1 per output row, then 1 to encode
+        assert_eq!(steps, 66);
+    }
+
+    #[test]
+    fn small_image_not_resized() {
+        let mut resizer = ImageResizer::new(png(100, 50), spec(128));
+
+        assert!(matches!(resizer.step(|| false), StepResult::NotRequired));
+    }
+
+    #[test]
+    fn image_with_too_many_pixels_fails() {
+        let mut resizer = ImageResizer::new(png(2001, 2000), spec(128));
+
+        assert!(matches!(resizer.step(|| false), StepResult::Failed(_)));
+    }
+
+    #[test]
+    fn image_with_too_many_bytes_fails() {
+        let mut resizer = ImageResizer::new(vec![0; MAX_SOURCE_BYTES as usize + 1], spec(128));
+
+        assert!(matches!(resizer.step(|| false), StepResult::Failed(_)));
+    }
+
+    fn run_to_completion<F: Fn() -> bool>(resizer: &mut ImageResizer, limit_reached: F) -> (Vec<u8>, u32) {
+        let mut steps = 0;
+        loop {
+            steps += 1;
+            match resizer.step(&limit_reached) {
+                StepResult::InProgress => continue,
+                StepResult::Completed(bytes) => return (bytes, steps),
+                StepResult::NotRequired => panic!("Resize not required"),
+                StepResult::Failed(error) => panic!("Resize failed: {error}"),
+            }
+        }
+    }
+
+    fn png(width: u32, height: u32) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        DynamicImage::ImageRgba8(RgbaImage::new(width, height))
+            .write_to(&mut Cursor::new(&mut bytes), ImageOutputFormat::Png)
+            .unwrap();
+        bytes
+    }
+
+    fn spec(size: u32) -> ImageVariantSpec {
+        ImageVariantSpec {
+            size,
+            format: ImageVariantFormat::Jpeg,
+        }
+    }
+}
@@ -0,0 +1,214 @@
+use crate::model::image_resizer::ImageResizer;
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::str::FromStr;
+use types::{FileId, TimestampMillis};
+use utils::time::MINUTE_IN_MS;
+
+const SUPPORTED_SIZES: [u32; 3] = [128, 512, 1024];
+const SUPPORTED_MIME_TYPES: [&str; 4] = ["image/gif", "image/jpeg", "image/png", "image/webp"];
+const MAX_QUEUE_LENGTH: usize = 1000;
+// Variants are requested via anonymous http requests, so this limits how much work can be queued up
+const MAX_ENQUEUED_PER_MINUTE: u32 = 100;
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct ImageVariantSpec {
+    pub size: u32,
+    pub format: ImageVariantFormat,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum ImageVariantFormat {
+    Jpeg,
+    WebP,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug)]
+pub struct ImageVariant {
+    pub spec: ImageVariantSpec,
+    // If the original image is already small enough, or it could not be resized, this will point
+    // back to the original file
+    pub file_id: FileId,
+}
+
+impl ImageVariantSpec {
+    // Extracts the requested variant from a url of the form `/files/{file_id}?size=512&format=webp`.
+    // Returns `None` if no size is specified or the size is not one of the supported sizes.
+    pub fn from_url(url: &str) -> Option<ImageVariantSpec> {
+        let (_, qs) = url.split_once('?')?;
+
+        let mut size = None;
+        let mut format = ImageVariantFormat::Jpeg;
+        for (key, value) in qs.split('&').filter_map(|p| p.split_once('=')) {
+            match key.to_lowercase().as_str() {
+                "size" => size = u32::from_str(value).ok(),
+                "format" => format = ImageVariantFormat::from_str(value).ok()?,
+                _ => {}
+            }
+        }
+
+        size.filter(|s| SUPPORTED_SIZES.contains(s))
+            .map(|size| ImageVariantSpec { size, format })
+    }
+}
+
+impl ImageVariantFormat {
+    pub fn mime_type(&self) -> &'static str {
+        match self {
+            ImageVariantFormat::Jpeg => "image/jpeg",
+            ImageVariantFormat::WebP => "image/webp",
+        }
+    }
+}
+
+impl FromStr for ImageVariantFormat {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "jpg" | "jpeg" => Ok(ImageVariantFormat::Jpeg),
+            "webp" => Ok(ImageVariantFormat::WebP),
+            _ => Err(()),
+        }
+    }
+}
+
+pub fn is_resizable_mime_type(mime_type: &str) -> bool {
+    SUPPORTED_MIME_TYPES.contains(&mime_type.to_lowercase().as_str())
+}
+
+// Variants are generated one at a time. The variant at the front of the queue is only removed once
+// it has been completed, so if the canister is upgraded while a variant is being generated, the
+// work will simply be restarted after the upgrade.
+#[derive(Serialize, Deserialize, Default)]
+pub struct PendingImageVariants {
+    queue: VecDeque<(FileId, ImageVariantSpec)>,
+    #[serde(skip)]
+    in_progress: Option<ImageResizer>,
+    #[serde(default)]
+    window_start: TimestampMillis,
+    #[serde(default)]
+    enqueued_in_window: u32,
+}
+
+impl PendingImageVariants {
+    pub fn enqueue(&mut self, file_id: FileId, spec: ImageVariantSpec, now: TimestampMillis) -> bool {
+        if now.saturating_sub(self.window_start) >= MINUTE_IN_MS {
+            self.window_start = now;
+            self.enqueued_in_window = 0;
+        }
+
+        if self.queue.len() >= MAX_QUEUE_LENGTH
+            || self.enqueued_in_window >= MAX_ENQUEUED_PER_MINUTE
+            || self.queue.contains(&(file_id, spec))
+        {
+            false
+        } else {
+            self.queue.push_back((file_id, spec));
+            self.enqueued_in_window += 1;
+            true
+        }
+    }
+
+    pub fn peek(&self) -> Option<(FileId, ImageVariantSpec)> {
+        self.queue.front().copied()
+    }
+
+    pub fn in_progress_mut(&mut self) -> Option<&mut ImageResizer> {
+        self.in_progress.as_mut()
+    }
+
+    pub fn set_in_progress(&mut self, resizer: ImageResizer) {
+        self.in_progress = Some(resizer);
+    }
+
+    pub fn mark_completed(&mut self) {
+        self.queue.pop_front();
+        self.in_progress = None;
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.queue.is_empty()
+    }
+
+    pub fn len(&self) -> u32 {
+        self.queue.len() as u32
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_url_with_size_and_format() {
+        let spec = ImageVariantSpec::from_url("/files/12345?size=512&format=webp").unwrap();
+
+        assert_eq!(spec.size, 512);
+        assert_eq!(spec.format, ImageVariantFormat::WebP);
+    }
+
+    #[test]
+    fn from_url_defaults_to_jpeg() {
+        let spec = ImageVariantSpec::from_url("/files/12345?size=128").unwrap();
+
+        assert_eq!(spec.size, 128);
+        assert_eq!(spec.format, ImageVariantFormat::Jpeg);
+    }
+
+    #[test]
+    fn from_url_with_unsupported_size() {
+        assert!(ImageVariantSpec::from_url("/files/12345?size=100").is_none());
+    }
+
+    #[test]
+    fn from_url_without_size() {
+        assert!(ImageVariantSpec::from_url("/files/12345").is_none());
+        assert!(ImageVariantSpec::from_url("/files/12345?format=jpeg").is_none());
+    }
+
+    #[test]
+    fn enqueue_ignores_duplicates() {
+        let mut pending = PendingImageVariants::default();
+
+        assert!(pending.enqueue(1, spec(128), 0));
+        assert!(!pending.enqueue(1, spec(128), 0));
+        assert!(pending.enqueue(1, spec(512), 0));
+        assert_eq!(pending.len(), 2);
+    }
+
+    #[test]
+    fn enqueue_is_rate_limited() {
+        let mut pending = PendingImageVariants::default();
+
+        for file_id in 0..MAX_ENQUEUED_PER_MINUTE {
+            assert!(pending.enqueue(file_id as FileId, spec(128), 1000));
+        }
+        assert!(!pending.enqueue(1000, spec(128), 1000 + MINUTE_IN_MS - 1));
+        assert!(pending.enqueue(1000, spec(128), 1000 + MINUTE_IN_MS));
+    }
+
+    #[test]
+    fn enqueue_respects_max_queue_length() {
+        let mut pending = PendingImageVariants::default();
+
+        let mut now = 0;
+        for file_id in 0..MAX_QUEUE_LENGTH {
+            if file_id as u32 % MAX_ENQUEUED_PER_MINUTE == 0 {
+                now += MINUTE_IN_MS;
+            }
+            assert!(pending.enqueue(file_id as FileId, spec(128), now));
+        }
+        assert!(!pending.enqueue(MAX_QUEUE_LENGTH as FileId, spec(128), now + MINUTE_IN_MS));
+
+        pending.mark_completed();
+        assert!(pending.enqueue(MAX_QUEUE_LENGTH as FileId, spec(128), now + MINUTE_IN_MS));
+    }
+
+    fn spec(size: u32) -> ImageVariantSpec {
+        ImageVariantSpec {
+            size,
+            format: ImageVariantFormat::Jpeg,
+        }
+    }
+}
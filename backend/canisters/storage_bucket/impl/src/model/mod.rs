@@ -1,4 +1,6 @@
 pub mod files;
+pub mod image_resizer;
+pub mod image_variants;
 pub mod index_sync_state;
 pub mod stable_blob_storage;
 pub mod users;
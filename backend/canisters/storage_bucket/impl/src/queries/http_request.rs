@@ -1,3 +1,4 @@
+use crate::model::image_variants::ImageVariantSpec;
 use crate::{calc_chunk_count, read_state, RuntimeState};
 use http_request::{build_json_response, encode_logs, extract_route, Route};
 use ic_cdk_macros::query;
@@ -10,7 +11,9 @@ use types::{
 };
 
 const BLOB_RESPONSE_CHUNK_SIZE_BYTES: u32 = 1 << 19; // 1/2 MB
-const CACHE_HEADER_VALUE: &str = "public, max-age=100000000, immutable";
+pub(crate) const CACHE_HEADER_VALUE: &str = "public, max-age=100000000, immutable";
+// Used when serving the original image while the requested variant is being generated
+pub(crate) const VARIANT_PENDING_CACHE_HEADER_VALUE: &str = "public, max-age=60";
 
 #[query]
 fn http_request(request: HttpRequest) -> HttpResponse {
@@ -27,7 +30,10 @@ fn http_request(request: HttpRequest) -> HttpResponse {
     }
 
     match extract_route(&request.url) {
-        Route::File(file_id) => read_state(|state| start_streaming_file(file_id, state)),
+        Route::File(file_id) => read_state(|state| match ImageVariantSpec::from_url(&request.url) {
+            Some(spec) => start_streaming_image_variant(file_id, spec, state),
+            None => start_streaming_file(file_id, CACHE_HEADER_VALUE, state),
+        }),
         Route::Logs(since) => get_logs_impl(since),
         Route::Traces(since) => get_traces_impl(since),
         Route::Metrics => read_state(get_metrics_impl),
@@ -40,7 +46,19 @@ fn http_request_streaming_callback(token: Token) -> StreamingCallbackHttpRespons
     read_state(|state| continue_streaming_file(token, state))
 }
 
-fn start_streaming_file(file_id: FileId, state: &RuntimeState) -> HttpResponse {
+// If the requested variant doesn't exist yet, the request is upgraded to an update call so that the
+// variant can be queued for generation (see `http_request_update`)
+fn start_streaming_image_variant(file_id: FileId, spec: ImageVariantSpec, state: &RuntimeState) -> HttpResponse {
+    if let Some(variant_file_id) = state.data.files.image_variant(&file_id, &spec) {
+        start_streaming_file(variant_file_id, CACHE_HEADER_VALUE, state)
+    } else if state.data.files.can_generate_image_variants(&file_id) {
+        HttpResponse::upgrade()
+    } else {
+        start_streaming_file(file_id, CACHE_HEADER_VALUE, state)
+    }
+}
+
+pub(crate) fn start_streaming_file(file_id: FileId, cache_header_value: &str, state: &RuntimeState) -> HttpResponse {
     if let Some(file) = state.data.files.get(&file_id) {
         if let Some(bytes) = state.data.files.blob_bytes(&file.hash) {
             let canister_id = state.env.canister_id();
@@ -60,7 +78,7 @@ fn start_streaming_file(file_id: FileId, state: &RuntimeState) -> HttpResponse {
                 status_code: 200,
                 headers: vec![
                     HeaderField("Content-Type".to_string(), file.mime_type.clone()),
-                    HeaderField("Cache-Control".to_string(), CACHE_HEADER_VALUE.to_string()),
+                    HeaderField("Cache-Control".to_string(), cache_header_value.to_string()),
                     HeaderField("Access-Control-Allow-Origin".to_string(), "*".to_string()),
                     HeaderField(
                         "Content-Security-Policy".to_string(),
@@ -69,6 +87,7 @@ fn start_streaming_file(file_id: FileId, state: &RuntimeState) -> HttpResponse {
                 ],
                 body: chunk_bytes,
                 streaming_strategy,
+                upgrade: None,
             };
         }
     }
@@ -1,3 +1,3 @@
 mod file_info;
 mod file_status;
-mod http_request;
+pub mod http_request;
@@ -24,7 +24,7 @@ fn c2c_sync_index_impl(args: Args, state: &mut RuntimeState) -> Response {
         if let Some(user) = state.data.users.remove(&user_id) {
             for file_id in user.files_owned() {
                 if let RemoveFileResult::Success(b) = state.data.files.remove(user_id, file_id) {
-                    files_removed.push(b)
+                    files_removed.extend(b)
                 }
             }
         }
@@ -35,8 +35,8 @@ fn c2c_sync_index_impl(args: Args, state: &mut RuntimeState) -> Response {
     }
 
     for file_id in args.files_to_remove {
-        if let RemoveFileResult::Success(file_removed) = state.data.files.remove_unchecked(file_id) {
-            files_removed.push(file_removed);
+        if let RemoveFileResult::Success(b) = state.data.files.remove_unchecked(file_id) {
+            files_removed.extend(b);
         }
     }
 
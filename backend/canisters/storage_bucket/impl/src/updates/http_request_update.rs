@@ -0,0 +1,41 @@
+use crate::model::image_variants::ImageVariantSpec;
+use crate::queries::http_request::{start_streaming_file, CACHE_HEADER_VALUE, VARIANT_PENDING_CACHE_HEADER_VALUE};
+use crate::{jobs, mutate_state, RuntimeState};
+use http_request::{extract_route, Route};
+use ic_cdk_macros::update;
+use types::{HttpRequest, HttpResponse};
+
+// Requests for image variants which don't exist yet are upgraded to update calls so that the
+// variant can be queued for generation. The original image is served in the meantime with a short
+// cache expiry so that the variant is picked up once it is ready. If too many variants have been
+// requested recently the request is not queued, and the variant will be requested again once the
+// cached response expires.
+#[update]
+fn http_request_update(request: HttpRequest) -> HttpResponse {
+    mutate_state(|state| http_request_update_impl(request, state))
+}
+
+fn http_request_update_impl(request: HttpRequest, state: &mut RuntimeState) -> HttpResponse {
+    let Route::File(file_id) = extract_route(&request.url) else {
+        return HttpResponse::not_found();
+    };
+
+    let Some(spec) = ImageVariantSpec::from_url(&request.url) else {
+        return HttpResponse::not_found();
+    };
+
+    if let Some(variant_file_id) = state.data.files.image_variant(&file_id, &spec) {
+        return start_streaming_file(variant_file_id, CACHE_HEADER_VALUE, state);
+    }
+
+    if !state.data.files.can_generate_image_variants(&file_id) {
+        return start_streaming_file(file_id, CACHE_HEADER_VALUE, state);
+    }
+
+    let now = state.env.now();
+    if state.data.image_variants.enqueue(file_id, spec, now) {
+        jobs::generate_image_variants::start_job_if_required(state);
+    }
+
+    start_streaming_file(file_id, VARIANT_PENDING_CACHE_HEADER_VALUE, state)
+}
@@ -2,5 +2,6 @@ mod c2c_sync_index;
 mod delete_file;
 mod delete_files;
 mod forward_file;
+mod http_request_update;
 mod upload_chunk;
 mod wallet_receive;
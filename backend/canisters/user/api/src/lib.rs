@@ -1,9 +1,11 @@
 use candid::CandidType;
+use ic_ledger_types::AccountIdentifier;
+use icrc_ledger_types::icrc1::account::Account;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use types::{
-    ChannelId, ChannelLatestMessageIndex, Chat, ChatId, CommunityId, Cryptocurrency, DiamondMembershipPlanDuration,
-    MessageContent, MessageIndex, PhoneNumber, SuspensionDuration, TimestampMillis, UserId,
+    CanisterId, ChannelId, ChannelLatestMessageIndex, Chat, ChatId, CommunityId, Cryptocurrency, DiamondMembershipPlanDuration,
+    MessageContent, MessageIndex, Milliseconds, PhoneNumber, SuspensionDuration, TimestampMillis, TokenInfo, UserId,
 };
 
 mod lifecycle;
@@ -178,3 +180,76 @@ pub struct NamedAccount {
     pub name: String,
     pub account: String,
 }
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub enum StandingOrderRecipient {
+    User(UserId),
+    Account(Account),
+}
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct StandingOrder {
+    pub standing_order_id: u64,
+    pub recipient: StandingOrderRecipient,
+    pub token: TokenInfo,
+    pub amount: u128,
+    pub interval: Milliseconds,
+    pub created: TimestampMillis,
+    pub end_date: Option<TimestampMillis>,
+    pub next_payment_due: Option<TimestampMillis>,
+    pub cancelled: Option<TimestampMillis>,
+    pub executions: Vec<StandingOrderExecution>,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct StandingOrderExecution {
+    pub due: TimestampMillis,
+    pub timestamp: TimestampMillis,
+    pub attempts: u32,
+    pub result: Result<u64, String>, // Block Index
+}
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct WalletTransaction {
+    pub id: u64,
+    pub timestamp: TimestampMillis,
+    pub kind: WalletTransactionKind,
+    pub direction: WalletTransactionDirection,
+    pub ledger: CanisterId,
+    pub amount: u128,
+    // The fee paid by this user, if known
+    pub fee: Option<u128>,
+    pub counterparty: Option<WalletCounterparty>,
+    pub block_index: Option<u64>,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Copy, Debug, Eq, PartialEq)]
+pub enum WalletTransactionKind {
+    Tip,
+    Message,
+    Prize,
+    PrizeClaim,
+    Withdrawal,
+    Swap,
+    Approval,
+    DiamondMembership,
+    StandingOrder,
+    P2PSwap,
+    JoiningFee,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Copy, Debug, Eq, PartialEq)]
+pub enum WalletTransactionDirection {
+    Incoming,
+    Outgoing,
+    // Approvals allow another account to spend on the user's behalf but don't move any funds
+    Approval,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub enum WalletCounterparty {
+    User(UserId),
+    Account(Account),
+    AccountIdentifier(AccountIdentifier),
+    Canister(CanisterId),
+}
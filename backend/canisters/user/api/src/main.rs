@@ -14,17 +14,24 @@ fn main() {
     generate_candid_method!(user, public_profile, query);
     generate_candid_method!(user, search_messages, query);
     generate_candid_method!(user, saved_crypto_accounts, query);
+    generate_candid_method!(user, standing_orders, query);
     generate_candid_method!(user, token_swap_status, query);
+    generate_candid_method!(user, unvoted_proposals, query);
     generate_candid_method!(user, updates, query);
+    generate_candid_method!(user, wallet_transactions, query);
 
+    generate_candid_method!(user, accept_p2p_swap, update);
     generate_candid_method!(user, add_hot_group_exclusions, update);
     generate_candid_method!(user, add_reaction, update);
     generate_candid_method!(user, approve_transfer, update);
     generate_candid_method!(user, archive_unarchive_chats, update);
     generate_candid_method!(user, block_user, update);
     generate_candid_method!(user, cancel_message_reminder, update);
+    generate_candid_method!(user, cancel_p2p_swap, update);
+    generate_candid_method!(user, cancel_standing_order, update);
     generate_candid_method!(user, create_community, update);
     generate_candid_method!(user, create_group, update);
+    generate_candid_method!(user, create_standing_order, update);
     generate_candid_method!(user, delete_community, update);
     generate_candid_method!(user, delete_direct_chat, update);
     generate_candid_method!(user, delete_group, update);
@@ -38,8 +45,10 @@ fn main() {
     generate_candid_method!(user, migrate_user_principal, update);
     generate_candid_method!(user, mute_notifications, update);
     generate_candid_method!(user, pin_chat_v2, update);
+    generate_candid_method!(user, quote_token_swap, update);
     generate_candid_method!(user, remove_reaction, update);
     generate_candid_method!(user, report_message, update);
+    generate_candid_method!(user, retry_failed_p2p_swap_transfers, update);
     generate_candid_method!(user, save_crypto_account, update);
     generate_candid_method!(user, send_message_with_transfer_to_channel, update);
     generate_candid_method!(user, send_message_with_transfer_to_group, update);
@@ -49,6 +58,7 @@ fn main() {
     generate_candid_method!(user, set_community_indexes, update);
     generate_candid_method!(user, set_contact, update);
     generate_candid_method!(user, set_message_reminder_v2, update);
+    generate_candid_method!(user, set_proposal_reminders, update);
     generate_candid_method!(user, submit_proposal, update);
     generate_candid_method!(user, swap_tokens, update);
     generate_candid_method!(user, tip_message, update);
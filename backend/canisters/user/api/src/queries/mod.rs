@@ -10,5 +10,8 @@ pub mod messages_by_message_index;
 pub mod public_profile;
 pub mod saved_crypto_accounts;
 pub mod search_messages;
+pub mod standing_orders;
 pub mod token_swap_status;
+pub mod unvoted_proposals;
 pub mod updates;
+pub mod wallet_transactions;
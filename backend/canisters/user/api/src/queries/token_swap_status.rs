@@ -1,7 +1,7 @@
 pub use candid::CandidType;
 use icrc_ledger_types::icrc1::account::Account;
 use serde::{Deserialize, Serialize};
-use types::TimestampMillis;
+use types::{ExchangeId, TimestampMillis};
 
 #[derive(CandidType, Serialize, Deserialize, Debug)]
 pub struct Args {
@@ -22,6 +22,7 @@ pub struct SuccessResult {
 #[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
 pub struct TokenSwapStatus {
     pub started: TimestampMillis,
+    pub exchange_id: ExchangeId,
     pub deposit_account: SwapSubtask<Account>,
     pub transfer: SwapSubtask<u64>, // Block Index
     pub notified_dex: SwapSubtask<()>,
@@ -0,0 +1,20 @@
+use candid::CandidType;
+use serde::{Deserialize, Serialize};
+use types::{Milliseconds, TimestampMillis, UnvotedProposal};
+
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub struct Args {
+    pub closing_within: Milliseconds,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub enum Response {
+    Success(SuccessResult),
+}
+
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub struct SuccessResult {
+    pub proposals: Vec<UnvotedProposal>,
+    pub reminders_enabled: bool,
+    pub last_refreshed: TimestampMillis,
+}
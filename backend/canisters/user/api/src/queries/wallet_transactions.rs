@@ -0,0 +1,29 @@
+use crate::{WalletTransaction, WalletTransactionDirection, WalletTransactionKind};
+use candid::CandidType;
+use serde::{Deserialize, Serialize};
+use types::CanisterId;
+
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub struct Args {
+    // Transactions are returned newest first, starting from the transaction before `start`, or from
+    // the latest transaction if `start` is not set
+    pub start: Option<u64>,
+    pub max_results: u32,
+    pub ledger: Option<CanisterId>,
+    pub kinds: Option<Vec<WalletTransactionKind>>,
+    pub direction: Option<WalletTransactionDirection>,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub enum Response {
+    Success(SuccessResult),
+}
+
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub struct SuccessResult {
+    pub transactions: Vec<WalletTransaction>,
+    // If set, there may be more matching transactions, which can be retrieved by passing this value
+    // as `start` in the next request
+    pub next: Option<u64>,
+    pub total: u64,
+}
@@ -0,0 +1,19 @@
+use candid::CandidType;
+use serde::{Deserialize, Serialize};
+use types::{MessageId, P2PSwapStatus};
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct Args {
+    pub message_id: MessageId,
+    pub token1_txn_in: u64,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub enum Response {
+    Success,
+    StatusError(P2PSwapStatus),
+    ChatNotFound,
+    SwapNotFound,
+    TransferInvalid(String),
+    InternalError(String),
+}
@@ -35,6 +35,7 @@ pub enum Response {
     UserSuspended,
     Unauthorized,
     DefaultChannelsInvalid,
+    InvalidGate,
     InternalError(String),
 }
 
@@ -30,6 +30,7 @@ pub enum Response {
     Throttled,
     UserSuspended,
     UnauthorizedToCreatePublicGroup,
+    InvalidGate,
     InternalError,
 }
 
@@ -0,0 +1,32 @@
+use crate::StandingOrderRecipient;
+use candid::CandidType;
+use serde::{Deserialize, Serialize};
+use types::{Milliseconds, TimestampMillis, TokenInfo};
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct Args {
+    pub recipient: StandingOrderRecipient,
+    pub token: TokenInfo,
+    pub amount: u128,
+    pub interval: Milliseconds,
+    // If not set, the first payment is made immediately
+    pub first_payment: Option<TimestampMillis>,
+    pub end_date: Option<TimestampMillis>,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub enum Response {
+    Success(SuccessResult),
+    AmountTooSmall,
+    IntervalTooShort(Milliseconds),
+    FirstPaymentInThePast,
+    EndDateBeforeFirstPayment,
+    TooManyStandingOrders(u32),
+    UserSuspended,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub struct SuccessResult {
+    pub standing_order_id: u64,
+    pub next_payment_due: TimestampMillis,
+}
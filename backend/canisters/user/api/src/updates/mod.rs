@@ -1,8 +1,10 @@
+pub mod accept_p2p_swap;
 pub mod add_hot_group_exclusions;
 pub mod add_reaction;
 pub mod approve_transfer;
 pub mod archive_unarchive_chats;
 pub mod block_user;
+pub mod c2c_accept_p2p_swap;
 pub mod c2c_charge_user_account;
 pub mod c2c_delete_messages;
 pub mod c2c_edit_message;
@@ -14,6 +16,9 @@ pub mod c2c_mark_read_v2;
 pub mod c2c_notify_community_deleted;
 pub mod c2c_notify_events;
 pub mod c2c_notify_group_deleted;
+pub mod c2c_notify_joining_fee_paid;
+pub mod c2c_notify_p2p_swap_status_change;
+pub mod c2c_notify_prize_claimed;
 pub mod c2c_notify_user_events;
 pub mod c2c_remove_from_community;
 pub mod c2c_remove_from_group;
@@ -25,8 +30,11 @@ pub mod c2c_toggle_reaction;
 pub mod c2c_undelete_messages;
 pub mod c2c_vote_on_proposal;
 pub mod cancel_message_reminder;
+pub mod cancel_p2p_swap;
+pub mod cancel_standing_order;
 pub mod create_community;
 pub mod create_group;
+pub mod create_standing_order;
 pub mod delete_community;
 pub mod delete_direct_chat;
 pub mod delete_group;
@@ -40,8 +48,10 @@ pub mod mark_read;
 pub mod migrate_user_principal;
 pub mod mute_notifications;
 pub mod pin_chat_v2;
+pub mod quote_token_swap;
 pub mod remove_reaction;
 pub mod report_message;
+pub mod retry_failed_p2p_swap_transfers;
 pub mod save_crypto_account;
 pub mod send_message_v2;
 pub mod send_message_with_transfer_to_channel;
@@ -51,6 +61,7 @@ pub mod set_bio;
 pub mod set_community_indexes;
 pub mod set_contact;
 pub mod set_message_reminder_v2;
+pub mod set_proposal_reminders;
 pub mod submit_proposal;
 pub mod swap_tokens;
 pub mod tip_message;
@@ -0,0 +1,28 @@
+use crate::swap_tokens::ExchangeArgs;
+use candid::CandidType;
+use serde::{Deserialize, Serialize};
+use types::{ExchangeId, TokenInfo};
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct Args {
+    pub input_token: TokenInfo,
+    pub output_token: TokenInfo,
+    pub input_amount: u128,
+    pub exchanges: Vec<ExchangeArgs>,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub enum Response {
+    Success(SuccessResult),
+}
+
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub struct SuccessResult {
+    pub quotes: Vec<Quote>,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct Quote {
+    pub exchange_id: ExchangeId,
+    pub amount_out: Result<u128, String>,
+}
@@ -1,8 +1,8 @@
 use candid::CandidType;
 use serde::{Deserialize, Serialize};
 use types::{
-    ChannelId, CommunityId, CompletedCryptoTransaction, Cryptocurrency, EventIndex, GroupReplyContext, MessageContentInitial,
-    MessageId, MessageIndex, TimestampMillis, User, Version,
+    ChannelId, CommunityId, CompletedCryptoTransaction, Cryptocurrency, EventIndex, ForumPostDetails, GroupReplyContext,
+    MessageContentInitial, MessageId, MessageIndex, TimestampMillis, User, Version,
 };
 
 #[derive(CandidType, Serialize, Deserialize, Debug)]
@@ -18,6 +18,8 @@ pub struct Args {
     pub mentioned: Vec<User>,
     pub community_rules_accepted: Option<Version>,
     pub channel_rules_accepted: Option<Version>,
+    #[serde(default)]
+    pub forum_post: Option<ForumPostDetails>,
 }
 
 #[derive(CandidType, Serialize, Deserialize, Debug)]
@@ -0,0 +1,21 @@
+use candid::CandidType;
+use serde::{Deserialize, Serialize};
+use types::Milliseconds;
+
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub struct Args {
+    // How long before each proposal's deadline to send the reminder, or `None` to disable reminders
+    pub notify_before: Option<Milliseconds>,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub enum Response {
+    Success,
+    NotifyBeforeOutOfRange(NotifyBeforeRange),
+}
+
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub struct NotifyBeforeRange {
+    pub min: Milliseconds,
+    pub max: Milliseconds,
+}
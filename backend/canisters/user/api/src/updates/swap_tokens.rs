@@ -1,6 +1,6 @@
 use candid::CandidType;
 use serde::{Deserialize, Serialize};
-use types::{CanisterId, TokenInfo};
+use types::{CanisterId, ExchangeId, TokenInfo};
 
 #[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
 pub struct Args {
@@ -10,11 +10,17 @@ pub struct Args {
     pub input_amount: u128,
     pub exchange_args: ExchangeArgs,
     pub min_output_amount: u128,
+    // If set, quotes will be requested from `exchange_args` plus each of these exchanges, and the
+    // swap will be routed to whichever exchange returns the highest output amount
+    #[serde(default)]
+    pub alternative_exchanges: Option<Vec<ExchangeArgs>>,
 }
 
 #[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
 pub enum ExchangeArgs {
     ICPSwap(ICPSwapArgs),
+    Sonic(SonicArgs),
+    ICDex(ICDexArgs),
 }
 
 #[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
@@ -23,6 +29,45 @@ pub struct ICPSwapArgs {
     pub zero_for_one: bool,
 }
 
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct SonicArgs {
+    pub swap_canister_id: CanisterId,
+    pub zero_for_one: bool,
+}
+
+// For ICDex, token0 is the base token and token1 is the quote token
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct ICDexArgs {
+    pub swap_canister_id: CanisterId,
+    pub zero_for_one: bool,
+}
+
+impl ExchangeArgs {
+    pub fn exchange_id(&self) -> ExchangeId {
+        match self {
+            ExchangeArgs::ICPSwap(_) => ExchangeId::ICPSwap,
+            ExchangeArgs::Sonic(_) => ExchangeId::Sonic,
+            ExchangeArgs::ICDex(_) => ExchangeId::ICDex,
+        }
+    }
+
+    pub fn swap_canister_id(&self) -> CanisterId {
+        match self {
+            ExchangeArgs::ICPSwap(a) => a.swap_canister_id,
+            ExchangeArgs::Sonic(a) => a.swap_canister_id,
+            ExchangeArgs::ICDex(a) => a.swap_canister_id,
+        }
+    }
+
+    pub fn zero_for_one(&self) -> bool {
+        match self {
+            ExchangeArgs::ICPSwap(a) => a.zero_for_one,
+            ExchangeArgs::Sonic(a) => a.zero_for_one,
+            ExchangeArgs::ICDex(a) => a.zero_for_one,
+        }
+    }
+}
+
 #[derive(CandidType, Serialize, Deserialize, Debug)]
 pub enum Response {
     Success(SuccessResult),
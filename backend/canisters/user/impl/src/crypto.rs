@@ -1,10 +1,18 @@
-use crate::read_state;
-use types::{CompletedCryptoTransaction, FailedCryptoTransaction, PendingCryptoTransaction, UserId};
+use crate::{mutate_state, read_state};
+use candid::Nat;
+use ic_ledger_types::{AccountIdentifier, GetBlocksArgs, Operation};
+use icrc_ledger_types::icrc1::account::Account;
+use ledger_utils::TransferFailure;
+use types::{CanisterId, CompletedCryptoTransaction, FailedCryptoTransaction, PendingCryptoTransaction, UserId};
+use user_canister::WalletTransactionKind;
 
 pub async fn process_transaction(
     transaction: PendingCryptoTransaction,
+    kind: WalletTransactionKind,
 ) -> Result<CompletedCryptoTransaction, FailedCryptoTransaction> {
-    process_transaction_internal(transaction, true).await
+    process_transaction_internal(transaction, kind, true)
+        .await
+        .map_err(|f| f.transaction)
 }
 
 // `process_transaction` should be used whenever possible.
@@ -14,14 +22,27 @@ pub async fn process_transaction(
 // If calling this method, ensure that the caller has been validated earlier on.
 pub async fn process_transaction_without_caller_check(
     transaction: PendingCryptoTransaction,
+    kind: WalletTransactionKind,
 ) -> Result<CompletedCryptoTransaction, FailedCryptoTransaction> {
-    process_transaction_internal(transaction, false).await
+    process_transaction_internal(transaction, kind, false)
+        .await
+        .map_err(|f| f.transaction)
+}
+
+// Same as `process_transaction_without_caller_check` but also reports whether a failed transfer is
+// worth retrying
+pub async fn try_process_transaction_without_caller_check(
+    transaction: PendingCryptoTransaction,
+    kind: WalletTransactionKind,
+) -> Result<CompletedCryptoTransaction, TransferFailure<FailedCryptoTransaction>> {
+    process_transaction_internal(transaction, kind, false).await
 }
 
 async fn process_transaction_internal(
     transaction: PendingCryptoTransaction,
+    kind: WalletTransactionKind,
     check_caller: bool,
-) -> Result<CompletedCryptoTransaction, FailedCryptoTransaction> {
+) -> Result<CompletedCryptoTransaction, TransferFailure<FailedCryptoTransaction>> {
     let my_user_id = read_state(|state| {
         if check_caller && state.env.caller() != state.data.owner {
             panic!("Only the owner can transfer cryptocurrency");
@@ -30,5 +51,115 @@ async fn process_transaction_internal(
         UserId::from(state.env.canister_id())
     });
 
-    ledger_utils::process_transaction(transaction, my_user_id.into()).await
+    // Tips and messages are sent to users, whereas other transfers are sent to accounts
+    let recipient = match kind {
+        WalletTransactionKind::Tip | WalletTransactionKind::Message => transaction.user_id(),
+        _ => None,
+    };
+
+    let result = ledger_utils::try_process_transaction(transaction, my_user_id.into()).await;
+
+    if let Ok(completed) = &result {
+        mutate_state(|state| {
+            let now = state.env.now();
+            state
+                .data
+                .wallet_transactions
+                .record_outgoing(kind, completed, recipient, now)
+        });
+    }
+
+    result
+}
+
+// The ICP ledger identifies accounts by their `AccountIdentifier` whereas ICRC ledgers use `Account`
+#[derive(PartialEq, Eq)]
+pub enum AccountOrIdentifier {
+    Account(Account),
+    Identifier(AccountIdentifier),
+}
+
+impl AccountOrIdentifier {
+    pub fn from(account: Account, is_icp: bool) -> AccountOrIdentifier {
+        if is_icp {
+            AccountOrIdentifier::Identifier(AccountIdentifier::new(
+                &account.owner,
+                &ic_ledger_types::Subaccount(account.subaccount.unwrap_or_default()),
+            ))
+        } else {
+            AccountOrIdentifier::Account(account)
+        }
+    }
+}
+
+pub struct LedgerTransfer {
+    pub from: AccountOrIdentifier,
+    pub to: AccountOrIdentifier,
+    pub amount: u128,
+}
+
+// Looks up the transfer at `block_index` on the ledger, returning None if the block doesn't exist
+// or isn't a transfer. The ICP ledger doesn't implement `get_transactions` so its blocks are
+// retrieved using `query_blocks`.
+pub async fn get_transfer(ledger: CanisterId, is_icp: bool, block_index: u64) -> Result<Option<LedgerTransfer>, String> {
+    if is_icp {
+        get_icp_transfer(ledger, block_index).await
+    } else {
+        get_icrc1_transfer(ledger, block_index).await
+    }
+}
+
+async fn get_icp_transfer(ledger: CanisterId, block_index: u64) -> Result<Option<LedgerTransfer>, String> {
+    let response = ic_ledger_types::query_blocks(
+        ledger,
+        GetBlocksArgs {
+            start: block_index,
+            length: 1,
+        },
+    )
+    .await
+    .map_err(|e| format!("{e:?}"))?;
+
+    if block_index >= response.chain_length {
+        return Ok(None);
+    }
+
+    match response.blocks.into_iter().next() {
+        Some(block) if response.first_block_index == block_index => match block.transaction.operation {
+            Some(Operation::Transfer { from, to, amount, .. }) | Some(Operation::TransferFrom { from, to, amount, .. }) => {
+                Ok(Some(LedgerTransfer {
+                    from: AccountOrIdentifier::Identifier(from),
+                    to: AccountOrIdentifier::Identifier(to),
+                    amount: amount.e8s() as u128,
+                }))
+            }
+            _ => Ok(None),
+        },
+        _ => Err("Block has been archived".to_string()),
+    }
+}
+
+async fn get_icrc1_transfer(ledger: CanisterId, block_index: u64) -> Result<Option<LedgerTransfer>, String> {
+    let args = icrc_ledger_canister::get_transactions::Args {
+        start: block_index.into(),
+        length: 1u64.into(),
+    };
+    let response = icrc_ledger_canister_c2c_client::get_transactions(ledger, &args)
+        .await
+        .map_err(|e| format!("{e:?}"))?;
+
+    if Nat::from(block_index) >= response.log_length {
+        return Ok(None);
+    }
+
+    match response.transactions.into_iter().next() {
+        Some(transaction) if response.first_index == Nat::from(block_index) => {
+            Ok(transaction.transfer.map(|t| LedgerTransfer {
+                from: AccountOrIdentifier::Account(t.from),
+                to: AccountOrIdentifier::Account(t.to),
+                amount: u128::try_from(t.amount.0).unwrap_or_default(),
+            }))
+        }
+        _ => Err("Transaction has been archived".to_string()),
+    }
 }
@@ -5,8 +5,11 @@ use crate::model::direct_chats::DirectChats;
 use crate::model::group_chat::GroupChat;
 use crate::model::group_chats::GroupChats;
 use crate::model::hot_group_exclusions::HotGroupExclusions;
+use crate::model::standing_orders::StandingOrders;
 use crate::model::token_swaps::TokenSwaps;
-use crate::timer_job_types::{RemoveExpiredEventsJob, TimerJob};
+use crate::model::unvoted_proposals::UnvotedProposals;
+use crate::model::wallet_transactions::WalletTransactions;
+use crate::timer_job_types::{ProcessP2PSwapTransferJob, RemoveExpiredEventsJob, TimerJob};
 use candid::Principal;
 use canister_state_macros::canister_state;
 use canister_timer_jobs::TimerJobs;
@@ -35,6 +38,8 @@ mod lifecycle;
 mod memory;
 mod model;
 mod openchat_bot;
+mod p2p_swaps;
+mod proposal_reminders;
 mod queries;
 mod regular_jobs;
 mod timer_job_types;
@@ -181,7 +186,17 @@ struct Data {
     #[serde(default)]
     pub token_swaps: TokenSwaps,
     #[serde(default)]
+    pub standing_orders: StandingOrders,
+    #[serde(default)]
+    pub wallet_transactions: WalletTransactions,
+    #[serde(default)]
+    pub next_p2p_swap_id: u32,
+    #[serde(default)]
     pub rng_seed: [u8; 32],
+    #[serde(default)]
+    pub unvoted_proposals: UnvotedProposals,
+    #[serde(default)]
+    pub failed_p2p_swap_transfers: Vec<ProcessP2PSwapTransferJob>,
 }
 
 impl Data {
@@ -230,7 +245,12 @@ impl Data {
             saved_crypto_accounts: Vec::new(),
             next_event_expiry: None,
             token_swaps: TokenSwaps::default(),
+            standing_orders: StandingOrders::default(),
+            wallet_transactions: WalletTransactions::default(),
+            next_p2p_swap_id: 0,
             rng_seed: [0; 32],
+            unvoted_proposals: UnvotedProposals::default(),
+            failed_p2p_swap_transfers: Vec::new(),
         }
     }
 
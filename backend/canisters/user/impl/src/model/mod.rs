@@ -8,5 +8,8 @@ pub mod favourite_chats;
 pub mod group_chat;
 pub mod group_chats;
 pub mod hot_group_exclusions;
+pub mod standing_orders;
 pub mod token_swaps;
 pub mod unread_message_index_map;
+pub mod unvoted_proposals;
+pub mod wallet_transactions;
@@ -0,0 +1,180 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use types::{Milliseconds, TimestampMillis, TokenInfo};
+use user_canister::{StandingOrderExecution, StandingOrderRecipient};
+
+const MAX_EXECUTIONS_PER_ORDER: usize = 100;
+
+#[derive(Serialize, Deserialize, Default)]
+pub struct StandingOrders {
+    orders: BTreeMap<u64, StandingOrder>,
+}
+
+impl StandingOrders {
+    pub fn add(&mut self, order: StandingOrder) {
+        self.orders.insert(order.id, order);
+    }
+
+    pub fn get(&self, id: u64) -> Option<&StandingOrder> {
+        self.orders.get(&id)
+    }
+
+    pub fn get_mut(&mut self, id: u64) -> Option<&mut StandingOrder> {
+        self.orders.get_mut(&id)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &StandingOrder> {
+        self.orders.values()
+    }
+
+    pub fn active_count(&self) -> usize {
+        self.orders.values().filter(|o| o.is_active()).count()
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct StandingOrder {
+    pub id: u64,
+    pub recipient: StandingOrderRecipient,
+    pub token: TokenInfo,
+    pub amount: u128,
+    pub interval: Milliseconds,
+    pub created: TimestampMillis,
+    pub end_date: Option<TimestampMillis>,
+    pub next_payment_due: Option<TimestampMillis>,
+    pub cancelled: Option<TimestampMillis>,
+    pub executions: Vec<StandingOrderExecution>,
+}
+
+impl StandingOrder {
+    pub fn is_active(&self) -> bool {
+        self.next_payment_due.is_some()
+    }
+
+    pub fn cancel(&mut self, now: TimestampMillis) -> bool {
+        if self.is_active() {
+            self.next_payment_due = None;
+            self.cancelled = Some(now);
+            true
+        } else {
+            false
+        }
+    }
+
+    // Records the outcome of the payment which was due at `due` and returns when the next payment
+    // is due, if there is one. If any payments were missed (eg. because the canister was stopped),
+    // they are skipped rather than all being made at once.
+    pub fn record_execution(&mut self, execution: StandingOrderExecution, now: TimestampMillis) -> Option<TimestampMillis> {
+        let mut next_due = execution.due + self.interval;
+        while next_due <= now {
+            next_due += self.interval;
+        }
+
+        self.executions.push(execution);
+        if self.executions.len() > MAX_EXECUTIONS_PER_ORDER {
+            self.executions.remove(0);
+        }
+
+        self.next_payment_due = Some(next_due).filter(|d| self.end_date.map_or(true, |e| *d <= e));
+        self.next_payment_due
+    }
+}
+
+impl From<&StandingOrder> for user_canister::StandingOrder {
+    fn from(value: &StandingOrder) -> Self {
+        user_canister::StandingOrder {
+            standing_order_id: value.id,
+            recipient: value.recipient.clone(),
+            token: value.token.clone(),
+            amount: value.amount,
+            interval: value.interval,
+            created: value.created,
+            end_date: value.end_date,
+            next_payment_due: value.next_payment_due,
+            cancelled: value.cancelled,
+            executions: value.executions.clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use candid::Principal;
+    use types::Cryptocurrency;
+
+    #[test]
+    fn next_payment_due_after_interval() {
+        let mut order = order(None);
+
+        assert_eq!(order.record_execution(execution(100), 105), Some(1100));
+        assert_eq!(order.executions.len(), 1);
+    }
+
+    #[test]
+    fn missed_payments_are_skipped() {
+        let mut order = order(None);
+
+        assert_eq!(order.record_execution(execution(100), 3500), Some(4100));
+    }
+
+    #[test]
+    fn order_ends_after_end_date() {
+        let mut order = order(Some(1500));
+
+        assert_eq!(order.record_execution(execution(100), 105), Some(1100));
+        assert_eq!(order.record_execution(execution(1100), 1105), None);
+        assert!(!order.is_active());
+    }
+
+    #[test]
+    fn executions_are_capped() {
+        let mut order = order(None);
+
+        for i in 0..=MAX_EXECUTIONS_PER_ORDER as u64 {
+            order.record_execution(execution(100 + i * 1000), 100 + i * 1000);
+        }
+
+        assert_eq!(order.executions.len(), MAX_EXECUTIONS_PER_ORDER);
+        assert_eq!(order.executions[0].due, 1100);
+    }
+
+    #[test]
+    fn cancel_only_once() {
+        let mut order = order(None);
+
+        assert!(order.cancel(10));
+        assert!(!order.cancel(20));
+        assert_eq!(order.cancelled, Some(10));
+        assert!(!order.is_active());
+    }
+
+    fn order(end_date: Option<TimestampMillis>) -> StandingOrder {
+        StandingOrder {
+            id: 1,
+            recipient: StandingOrderRecipient::User(Principal::from_slice(&[1]).into()),
+            token: TokenInfo {
+                token: Cryptocurrency::CHAT,
+                ledger: Principal::from_slice(&[2]),
+                decimals: 8,
+                fee: 100_000,
+            },
+            amount: 1_000_000,
+            interval: 1000,
+            created: 0,
+            end_date,
+            next_payment_due: Some(100),
+            cancelled: None,
+            executions: Vec::new(),
+        }
+    }
+
+    fn execution(due: TimestampMillis) -> StandingOrderExecution {
+        StandingOrderExecution {
+            due,
+            timestamp: due,
+            attempts: 1,
+            result: Ok(1),
+        }
+    }
+}
@@ -58,6 +58,7 @@ impl From<TokenSwap> for TokenSwapStatus {
     fn from(value: TokenSwap) -> Self {
         TokenSwapStatus {
             started: value.started,
+            exchange_id: value.args.exchange_args.exchange_id(),
             deposit_account: value.deposit_account.map(|a| a.value),
             transfer: value.transfer.map(|t| t.value),
             notified_dex: value.notified_dex_at.map(|t| t.value.map(|_| ())),
@@ -0,0 +1,228 @@
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use types::{CanisterId, MessageIndex, Milliseconds, MultiUserChat, ProposalId, TimestampMillis, UnvotedProposal};
+use utils::time::WEEK_IN_MS;
+
+// Chats without proposals are skipped when refreshing, but are occasionally checked again in case
+// proposals have since been added to them
+const RECHECK_CHATS_WITHOUT_PROPOSALS_INTERVAL: Milliseconds = WEEK_IN_MS;
+
+#[derive(Serialize, Deserialize, Default)]
+pub struct UnvotedProposals {
+    proposals: Vec<UnvotedProposal>,
+    last_refreshed: TimestampMillis,
+    notify_before: Option<Milliseconds>,
+    reminded: HashSet<(MultiUserChat, MessageIndex)>,
+    // The chats which had no proposals when last checked, along with when they were checked
+    #[serde(default)]
+    chats_without_proposals: HashMap<CanisterId, TimestampMillis>,
+}
+
+impl UnvotedProposals {
+    pub fn notify_before(&self) -> Option<Milliseconds> {
+        self.notify_before
+    }
+
+    pub fn set_notify_before(&mut self, notify_before: Option<Milliseconds>) {
+        self.notify_before = notify_before;
+    }
+
+    pub fn last_refreshed(&self) -> TimestampMillis {
+        self.last_refreshed
+    }
+
+    pub fn closing_before(&self, closing_before: TimestampMillis, now: TimestampMillis) -> Vec<UnvotedProposal> {
+        self.proposals
+            .iter()
+            .filter(|p| p.deadline > now && p.deadline <= closing_before)
+            .cloned()
+            .collect()
+    }
+
+    pub fn should_query(&self, canister_id: CanisterId, now: TimestampMillis) -> bool {
+        self.chats_without_proposals.get(&canister_id).map_or(true, |checked| {
+            now.saturating_sub(*checked) >= RECHECK_CHATS_WITHOUT_PROPOSALS_INTERVAL
+        })
+    }
+
+    // Joining a community may give the user access to its proposals channels, so it is checked
+    // again at the next refresh
+    pub fn chat_joined(&mut self, canister_id: CanisterId) {
+        self.chats_without_proposals.remove(&canister_id);
+    }
+
+    // Replaces the cached proposals with the latest set, except for those belonging to canisters
+    // which couldn't be reached, whose previously cached proposals are kept until the next refresh
+    pub fn set(
+        &mut self,
+        mut proposals: Vec<UnvotedProposal>,
+        failed_canisters: &HashSet<CanisterId>,
+        chats_without_proposals: Vec<CanisterId>,
+        now: TimestampMillis,
+    ) {
+        self.chats_without_proposals
+            .retain(|_, checked| now.saturating_sub(*checked) < RECHECK_CHATS_WITHOUT_PROPOSALS_INTERVAL);
+        self.chats_without_proposals
+            .extend(chats_without_proposals.into_iter().map(|c| (c, now)));
+
+        proposals.extend(
+            self.proposals
+                .drain(..)
+                .filter(|p| failed_canisters.contains(&canister_id(&p.chat)) && p.deadline > now),
+        );
+        proposals.sort_by_key(|p| p.deadline);
+
+        self.reminded
+            .retain(|(chat, message_index)| proposals.iter().any(|p| p.chat == *chat && p.message_index == *message_index));
+        self.proposals = proposals;
+        self.last_refreshed = now;
+    }
+
+    pub fn mark_voted(&mut self, governance_canister_id: CanisterId, proposal_id: ProposalId) {
+        self.proposals
+            .retain(|p| p.governance_canister_id != governance_canister_id || p.proposal_id != proposal_id);
+    }
+
+    // Returns the proposals whose reminders are now due, marking them as reminded so that each
+    // reminder is only sent once
+    pub fn take_due_reminders(&mut self, now: TimestampMillis) -> Vec<UnvotedProposal> {
+        let Some(notify_before) = self.notify_before else {
+            return Vec::new();
+        };
+
+        let mut due = Vec::new();
+        for proposal in self.proposals.iter() {
+            if proposal.deadline > now
+                && proposal.deadline.saturating_sub(notify_before) <= now
+                && self.reminded.insert((proposal.chat, proposal.message_index))
+            {
+                due.push(proposal.clone());
+            }
+        }
+        due
+    }
+
+    pub fn next_reminder_due(&self, now: TimestampMillis) -> Option<TimestampMillis> {
+        let notify_before = self.notify_before?;
+
+        self.proposals
+            .iter()
+            .filter(|p| p.deadline > now && !self.reminded.contains(&(p.chat, p.message_index)))
+            .map(|p| p.deadline.saturating_sub(notify_before))
+            .min()
+    }
+}
+
+fn canister_id(chat: &MultiUserChat) -> CanisterId {
+    match chat {
+        MultiUserChat::Group(chat_id) => (*chat_id).into(),
+        MultiUserChat::Channel(community_id, _) => (*community_id).into(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use candid::Principal;
+    use types::ChatId;
+    use utils::time::HOUR_IN_MS;
+
+    #[test]
+    fn chats_without_proposals_are_skipped_until_rechecked() {
+        let mut unvoted_proposals = UnvotedProposals::default();
+        let canister_id = canister_id_from(1);
+
+        unvoted_proposals.set(Vec::new(), &HashSet::new(), vec![canister_id], 0);
+
+        assert!(!unvoted_proposals.should_query(canister_id, HOUR_IN_MS));
+        assert!(unvoted_proposals.should_query(canister_id_from(2), HOUR_IN_MS));
+        assert!(unvoted_proposals.should_query(canister_id, RECHECK_CHATS_WITHOUT_PROPOSALS_INTERVAL));
+    }
+
+    #[test]
+    fn joining_chat_causes_it_to_be_rechecked() {
+        let mut unvoted_proposals = UnvotedProposals::default();
+        let canister_id = canister_id_from(1);
+
+        unvoted_proposals.set(Vec::new(), &HashSet::new(), vec![canister_id], 0);
+        unvoted_proposals.chat_joined(canister_id);
+
+        assert!(unvoted_proposals.should_query(canister_id, HOUR_IN_MS));
+    }
+
+    #[test]
+    fn proposals_from_failed_canisters_are_retained() {
+        let mut unvoted_proposals = UnvotedProposals::default();
+        unvoted_proposals.set(
+            vec![proposal(1, 1, 10 * HOUR_IN_MS), proposal(2, 2, 10 * HOUR_IN_MS)],
+            &HashSet::new(),
+            Vec::new(),
+            0,
+        );
+
+        let failed = HashSet::from([canister_id_from(1)]);
+        unvoted_proposals.set(Vec::new(), &failed, Vec::new(), HOUR_IN_MS);
+
+        let proposals = unvoted_proposals.closing_before(20 * HOUR_IN_MS, HOUR_IN_MS);
+        assert_eq!(proposals.len(), 1);
+        assert_eq!(proposals[0].chat, chat(1));
+    }
+
+    #[test]
+    fn each_reminder_is_only_sent_once() {
+        let mut unvoted_proposals = UnvotedProposals::default();
+        unvoted_proposals.set_notify_before(Some(2 * HOUR_IN_MS));
+        unvoted_proposals.set(
+            vec![proposal(1, 1, 10 * HOUR_IN_MS), proposal(1, 2, 20 * HOUR_IN_MS)],
+            &HashSet::new(),
+            Vec::new(),
+            0,
+        );
+
+        assert_eq!(unvoted_proposals.next_reminder_due(0), Some(8 * HOUR_IN_MS));
+        assert!(unvoted_proposals.take_due_reminders(7 * HOUR_IN_MS).is_empty());
+        assert_eq!(unvoted_proposals.take_due_reminders(8 * HOUR_IN_MS).len(), 1);
+        assert!(unvoted_proposals.take_due_reminders(9 * HOUR_IN_MS).is_empty());
+        assert_eq!(unvoted_proposals.next_reminder_due(9 * HOUR_IN_MS), Some(18 * HOUR_IN_MS));
+    }
+
+    #[test]
+    fn voted_proposals_are_removed() {
+        let mut unvoted_proposals = UnvotedProposals::default();
+        unvoted_proposals.set(
+            vec![proposal(1, 1, 10 * HOUR_IN_MS), proposal(1, 2, 10 * HOUR_IN_MS)],
+            &HashSet::new(),
+            Vec::new(),
+            0,
+        );
+
+        unvoted_proposals.mark_voted(governance_canister_id(), 1);
+
+        let proposals = unvoted_proposals.closing_before(20 * HOUR_IN_MS, 0);
+        assert_eq!(proposals.len(), 1);
+        assert_eq!(proposals[0].proposal_id, 2);
+    }
+
+    fn proposal(chat_index: u8, proposal_id: ProposalId, deadline: TimestampMillis) -> UnvotedProposal {
+        UnvotedProposal {
+            chat: chat(chat_index),
+            message_index: (proposal_id as u32).into(),
+            governance_canister_id: governance_canister_id(),
+            proposal_id,
+            title: format!("Proposal {proposal_id}"),
+            deadline,
+        }
+    }
+
+    fn chat(index: u8) -> MultiUserChat {
+        MultiUserChat::Group(ChatId::from(canister_id_from(index)))
+    }
+
+    fn canister_id_from(index: u8) -> CanisterId {
+        Principal::from_slice(&[1, index])
+    }
+
+    fn governance_canister_id() -> CanisterId {
+        Principal::from_slice(&[2])
+    }
+}
@@ -0,0 +1,177 @@
+use crate::memory::{get_wallet_transactions_data_memory, get_wallet_transactions_index_memory, Memory};
+use ic_stable_structures::storable::Bound;
+use ic_stable_structures::{StableLog, Storable};
+use msgpack::{deserialize_then_unwrap, serialize_then_unwrap};
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+use types::{CanisterId, CompletedCryptoTransaction, TimestampMillis, UserId};
+use user_canister::{WalletCounterparty, WalletTransaction, WalletTransactionDirection, WalletTransactionKind};
+
+// Transactions reported by other canisters are reported soon after they happen, so only the most
+// recent transactions need to be checked for duplicates
+const MAX_RECENT_SCANNED: u64 = 100;
+
+#[derive(Serialize, Deserialize)]
+pub struct WalletTransactions {
+    #[serde(skip, default = "init_log")]
+    log: StableLog<WalletTransactionEntry, Memory, Memory>,
+}
+
+impl WalletTransactions {
+    pub fn record(&mut self, entry: WalletTransactionEntry) {
+        self.log.append(&entry).unwrap();
+    }
+
+    pub fn record_outgoing(
+        &mut self,
+        kind: WalletTransactionKind,
+        transaction: &CompletedCryptoTransaction,
+        recipient: Option<UserId>,
+        now: TimestampMillis,
+    ) {
+        self.record(WalletTransactionEntry {
+            timestamp: now,
+            kind,
+            direction: WalletTransactionDirection::Outgoing,
+            ledger: transaction.ledger_canister_id(),
+            amount: transaction.units(),
+            fee: Some(transaction.fee()),
+            counterparty: recipient
+                .map(WalletCounterparty::User)
+                .or_else(|| recipient_account(transaction)),
+            block_index: Some(transaction.block_index()),
+        });
+    }
+
+    // Returns true if the transaction at `block_index` has recently been recorded
+    pub fn has_recorded(&self, ledger: CanisterId, block_index: u64) -> bool {
+        self.recent()
+            .any(|e| e.ledger == ledger && e.block_index == Some(block_index))
+    }
+
+    pub fn get(&self, id: u64) -> Option<WalletTransaction> {
+        self.log.get(id).map(|e| e.into_wallet_transaction(id))
+    }
+
+    pub fn len(&self) -> u64 {
+        self.log.len()
+    }
+
+    fn recent(&self) -> impl Iterator<Item = WalletTransactionEntry> + '_ {
+        let len = self.log.len();
+        (len.saturating_sub(MAX_RECENT_SCANNED)..len)
+            .rev()
+            .filter_map(|id| self.log.get(id))
+    }
+}
+
+fn recipient_account(transaction: &CompletedCryptoTransaction) -> Option<WalletCounterparty> {
+    match transaction {
+        CompletedCryptoTransaction::NNS(t) => match t.to {
+            types::nns::CryptoAccount::Account(a) => Some(WalletCounterparty::AccountIdentifier(a)),
+            types::nns::CryptoAccount::Mint => None,
+        },
+        CompletedCryptoTransaction::ICRC1(t) => match t.to {
+            types::icrc1::CryptoAccount::Account(a) => Some(WalletCounterparty::Account(a)),
+            types::icrc1::CryptoAccount::Mint => None,
+        },
+    }
+}
+
+fn init_log() -> StableLog<WalletTransactionEntry, Memory, Memory> {
+    let index_memory = get_wallet_transactions_index_memory();
+    let data_memory = get_wallet_transactions_data_memory();
+
+    StableLog::init(index_memory, data_memory).unwrap()
+}
+
+impl Default for WalletTransactions {
+    fn default() -> Self {
+        WalletTransactions { log: init_log() }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct WalletTransactionEntry {
+    pub timestamp: TimestampMillis,
+    pub kind: WalletTransactionKind,
+    pub direction: WalletTransactionDirection,
+    pub ledger: CanisterId,
+    pub amount: u128,
+    pub fee: Option<u128>,
+    pub counterparty: Option<WalletCounterparty>,
+    pub block_index: Option<u64>,
+}
+
+impl WalletTransactionEntry {
+    fn into_wallet_transaction(self, id: u64) -> WalletTransaction {
+        WalletTransaction {
+            id,
+            timestamp: self.timestamp,
+            kind: self.kind,
+            direction: self.direction,
+            ledger: self.ledger,
+            amount: self.amount,
+            fee: self.fee,
+            counterparty: self.counterparty,
+            block_index: self.block_index,
+        }
+    }
+}
+
+impl Storable for WalletTransactionEntry {
+    fn to_bytes(&self) -> Cow<[u8]> {
+        Cow::Owned(serialize_then_unwrap(self))
+    }
+
+    fn from_bytes(bytes: Cow<[u8]>) -> Self {
+        deserialize_then_unwrap(bytes.as_ref())
+    }
+
+    const BOUND: Bound = Bound::Unbounded;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use candid::Principal;
+
+    #[test]
+    fn has_recorded_matches_ledger_and_block_index() {
+        let mut transactions = WalletTransactions::default();
+        transactions.record(joining_fee(ledger(1), 5));
+
+        assert!(transactions.has_recorded(ledger(1), 5));
+        assert!(!transactions.has_recorded(ledger(1), 6));
+        assert!(!transactions.has_recorded(ledger(2), 5));
+    }
+
+    #[test]
+    fn old_transactions_are_ignored() {
+        let mut transactions = WalletTransactions::default();
+        transactions.record(joining_fee(ledger(1), 1));
+        for i in 0..MAX_RECENT_SCANNED {
+            transactions.record(joining_fee(ledger(2), i));
+        }
+
+        assert!(!transactions.has_recorded(ledger(1), 1));
+        assert!(transactions.has_recorded(ledger(2), 0));
+    }
+
+    fn joining_fee(ledger: CanisterId, block_index: u64) -> WalletTransactionEntry {
+        WalletTransactionEntry {
+            timestamp: 0,
+            kind: WalletTransactionKind::JoiningFee,
+            direction: WalletTransactionDirection::Outgoing,
+            ledger,
+            amount: 1_000_000,
+            fee: Some(10_000),
+            counterparty: Some(WalletCounterparty::Canister(Principal::from_slice(&[2]))),
+            block_index: Some(block_index),
+        }
+    }
+
+    fn ledger(id: u8) -> CanisterId {
+        Principal::from_slice(&[1, id])
+    }
+}
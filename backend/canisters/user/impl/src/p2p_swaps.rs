@@ -0,0 +1,238 @@
+use crate::crypto::{get_transfer, AccountOrIdentifier};
+use crate::model::wallet_transactions::WalletTransactionEntry;
+use crate::timer_job_types::{P2PSwapTransfer, ProcessP2PSwapTransferJob, TimerJob};
+use crate::{mutate_state, read_state, RuntimeState};
+use candid::Principal;
+use chat_events::{MessageContentInternal, Reader};
+use icrc_ledger_types::icrc1::account::{Account, Subaccount};
+use ledger_utils::compute_p2p_swap_escrow_subaccount_bytes;
+use msgpack::serialize_then_unwrap;
+use tracing::error;
+use types::{
+    icrc1, Cryptocurrency, MessageId, Milliseconds, P2PSwapContentInitial, P2PSwapStatus, PendingCryptoTransaction,
+    TimestampNanos, TokenInfo, UserId,
+};
+use user_canister::{c2c_notify_p2p_swap_status_change, WalletCounterparty, WalletTransactionDirection, WalletTransactionKind};
+use utils::consts::MEMO_P2P_SWAP;
+use utils::time::{DAY_IN_MS, MINUTE_IN_MS, SECOND_IN_MS};
+
+const MIN_EXPIRY: Milliseconds = 5 * MINUTE_IN_MS;
+const MAX_EXPIRY: Milliseconds = 7 * DAY_IN_MS;
+const MAX_TRANSFER_ATTEMPTS: u32 = 10;
+
+pub fn validate_p2p_swap(content: &P2PSwapContentInitial) -> Result<(), String> {
+    if content.token0.ledger == content.token1.ledger {
+        Err("Cannot swap a token for itself".to_string())
+    } else if content.token0_amount <= content.token0.fee || content.token1_amount <= content.token1.fee {
+        Err("Swap amounts must be greater than the transfer fees".to_string())
+    } else if content.expires_in < MIN_EXPIRY || content.expires_in > MAX_EXPIRY {
+        Err(format!("Expiry must be between {MIN_EXPIRY}ms and {MAX_EXPIRY}ms"))
+    } else {
+        Ok(())
+    }
+}
+
+// The escrowed funds for each swap are held in a subaccount of the canister of the user who offered
+// the swap, each swap using a different subaccount
+pub fn escrow_account(owner: UserId, swap_id: u32) -> Account {
+    Account {
+        owner: owner.into(),
+        subaccount: Some(escrow_subaccount(swap_id)),
+    }
+}
+
+// The amount deposited into escrow includes the fee for the transfer back out of escrow
+pub fn escrow_deposit(
+    token: &TokenInfo,
+    amount: u128,
+    escrow_account: Account,
+    now_nanos: TimestampNanos,
+) -> PendingCryptoTransaction {
+    PendingCryptoTransaction::ICRC1(icrc1::PendingCryptoTransaction {
+        ledger: token.ledger,
+        token: token.token.clone(),
+        amount: amount + token.fee,
+        to: escrow_account,
+        fee: token.fee,
+        memo: None,
+        created: now_nanos,
+    })
+    .set_memo(&MEMO_P2P_SWAP)
+}
+
+pub fn enqueue_escrow_transfer(them: UserId, message_id: MessageId, transfer: P2PSwapTransfer, state: &mut RuntimeState) {
+    let now = state.env.now();
+    state.data.timer_jobs.enqueue_job(
+        TimerJob::ProcessP2PSwapTransfer(Box::new(ProcessP2PSwapTransferJob {
+            them,
+            message_id,
+            transfer,
+            created: None,
+            attempt: 0,
+        })),
+        now,
+        now,
+    );
+}
+
+pub fn notify_status_change(them: UserId, message_id: MessageId, status: P2PSwapStatus, state: &mut RuntimeState) {
+    let args = c2c_notify_p2p_swap_status_change::Args { message_id, status };
+    state.data.fire_and_forget_handler.send(
+        them.into(),
+        "c2c_notify_p2p_swap_status_change_msgpack".to_string(),
+        serialize_then_unwrap(args),
+    );
+}
+
+pub(crate) async fn process_escrow_transfer(job: ProcessP2PSwapTransferJob) {
+    let Some((transaction, subaccount, my_user_id)) = read_state(|state| prepare_escrow_transfer(&job, state)) else {
+        return;
+    };
+    let created = transaction.created;
+
+    match ledger_utils::icrc1::try_process_transaction_from_subaccount(transaction, my_user_id.into(), Some(subaccount)).await {
+        Ok(completed) => mutate_state(|state| on_escrow_transfer_completed(&job, completed, state)),
+        Err(failure) => mutate_state(|state| {
+            if failure.transient && job.attempt + 1 < MAX_TRANSFER_ATTEMPTS {
+                // Retry with the same `created` timestamp so that the ledger will deduplicate the
+                // transfer if the previous attempt actually succeeded
+                let now = state.env.now();
+                state.data.timer_jobs.enqueue_job(
+                    TimerJob::ProcessP2PSwapTransfer(Box::new(ProcessP2PSwapTransferJob {
+                        created: Some(created),
+                        attempt: job.attempt + 1,
+                        ..job
+                    })),
+                    now + ((5 * SECOND_IN_MS) << job.attempt),
+                    now,
+                );
+            } else {
+                error!(
+                    ?job.transfer,
+                    message_id = ?job.message_id,
+                    error = failure.transaction.error_message.as_str(),
+                    "Failed to transfer funds out of P2P swap escrow"
+                );
+                // Keep hold of the transfer so that it can be retried via `retry_failed_p2p_swap_transfers`
+                state.data.failed_p2p_swap_transfers.push(job);
+            }
+        }),
+    }
+}
+
+fn prepare_escrow_transfer(
+    job: &ProcessP2PSwapTransferJob,
+    state: &RuntimeState,
+) -> Option<(icrc1::PendingCryptoTransaction, Subaccount, UserId)> {
+    let my_user_id: UserId = state.env.canister_id().into();
+    let chat = state.data.direct_chats.get(&job.them.into())?;
+    let message = chat.events.main_events_reader().message_internal(job.message_id.into())?;
+    let MessageContentInternal::P2PSwap(content) = &message.content else {
+        return None;
+    };
+
+    let (token, amount, recipient) = match job.transfer {
+        P2PSwapTransfer::RefundToken0 => (&content.token0, content.token0_amount, my_user_id),
+        P2PSwapTransfer::RefundToken1 => (&content.token1, content.token1_amount, job.them),
+        P2PSwapTransfer::SettleToken0 => (&content.token0, content.token0_amount, job.them),
+        P2PSwapTransfer::SettleToken1 => (&content.token1, content.token1_amount, my_user_id),
+    };
+
+    let transaction = icrc1::PendingCryptoTransaction {
+        ledger: token.ledger,
+        token: token.token.clone(),
+        amount,
+        to: Account::from(Principal::from(recipient)),
+        fee: token.fee,
+        memo: Some(MEMO_P2P_SWAP.to_vec().into()),
+        created: job.created.unwrap_or_else(|| state.env.now_nanos()),
+    };
+
+    Some((transaction, escrow_subaccount(content.swap_id), my_user_id))
+}
+
+fn on_escrow_transfer_completed(
+    job: &ProcessP2PSwapTransferJob,
+    transaction: icrc1::CompletedCryptoTransaction,
+    state: &mut RuntimeState,
+) {
+    let now = state.env.now();
+    let Some(chat) = state.data.direct_chats.get_mut(&job.them.into()) else {
+        return;
+    };
+
+    let result = match job.transfer {
+        P2PSwapTransfer::RefundToken0 => {
+            chat.events
+                .mark_p2p_swap_token0_refunded(job.message_id, transaction.block_index, now)
+        }
+        P2PSwapTransfer::SettleToken0 => {
+            chat.events
+                .mark_p2p_swap_transfer_out_completed(job.message_id, true, transaction.block_index, now)
+        }
+        P2PSwapTransfer::SettleToken1 => {
+            chat.events
+                .mark_p2p_swap_transfer_out_completed(job.message_id, false, transaction.block_index, now)
+        }
+        // Refunds of token1 aren't reflected in the swap's status since the swap was never accepted
+        P2PSwapTransfer::RefundToken1 => return,
+    };
+
+    if matches!(job.transfer, P2PSwapTransfer::RefundToken0 | P2PSwapTransfer::SettleToken1) {
+        state.data.wallet_transactions.record(WalletTransactionEntry {
+            timestamp: now,
+            kind: WalletTransactionKind::P2PSwap,
+            direction: WalletTransactionDirection::Incoming,
+            ledger: transaction.ledger,
+            amount: transaction.amount,
+            fee: None,
+            counterparty: matches!(job.transfer, P2PSwapTransfer::SettleToken1).then_some(WalletCounterparty::User(job.them)),
+            block_index: Some(transaction.block_index),
+        });
+    }
+
+    if let chat_events::UpdateP2PSwapResult::Success(content) = result {
+        notify_status_change(job.them, job.message_id, content.status, state);
+    }
+}
+
+pub enum VerifyEscrowDepositError {
+    Invalid(String),
+    InternalError(String),
+}
+
+// Looks up the block on the ledger and checks that it is a transfer of `amount` from `from` to the
+// escrow account
+pub async fn verify_escrow_deposit(
+    token: &TokenInfo,
+    amount: u128,
+    from: Account,
+    to: Account,
+    block_index: u64,
+) -> Result<(), VerifyEscrowDepositError> {
+    let is_icp = matches!(token.token, Cryptocurrency::InternetComputer);
+    let transfer = get_transfer(token.ledger, is_icp, block_index)
+        .await
+        .map_err(VerifyEscrowDepositError::InternalError)?;
+
+    match transfer {
+        Some(t) if t.from == AccountOrIdentifier::from(from, is_icp) && t.to == AccountOrIdentifier::from(to, is_icp) => {
+            if t.amount == amount {
+                Ok(())
+            } else {
+                Err(VerifyEscrowDepositError::Invalid(format!(
+                    "Incorrect amount. Expected: {amount}. Actual: {}",
+                    t.amount
+                )))
+            }
+        }
+        Some(_) => Err(VerifyEscrowDepositError::Invalid(
+            "Transfer was not into the escrow account".to_string(),
+        )),
+        None => Err(VerifyEscrowDepositError::Invalid("Transfer not found".to_string())),
+    }
+}
+
+fn escrow_subaccount(swap_id: u32) -> Subaccount {
+    compute_p2p_swap_escrow_subaccount_bytes(swap_id)
+}
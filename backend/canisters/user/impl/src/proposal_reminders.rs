@@ -0,0 +1,183 @@
+use crate::timer_job_types::{RefreshUnvotedProposalsJob, TimerJob};
+use crate::{mutate_state, openchat_bot, Data, RuntimeState};
+use std::collections::HashSet;
+use tracing::error;
+use types::{CanisterId, ChatId, CommunityId, Milliseconds, MultiUserChat, TimestampMillis, UnvotedProposal};
+use utils::time::{DAY_IN_MS, HOUR_IN_MS};
+
+// Only proposals closing within this window are cached, so this is also the maximum window which
+// can be queried
+pub const MAX_CLOSING_WITHIN: Milliseconds = 7 * DAY_IN_MS;
+const REFRESH_INTERVAL: Milliseconds = HOUR_IN_MS;
+
+pub(crate) struct RefreshArgs {
+    groups: Vec<ChatId>,
+    communities: Vec<CommunityId>,
+    now: TimestampMillis,
+}
+
+impl RefreshArgs {
+    pub fn has_chats_to_query(&self) -> bool {
+        !self.groups.is_empty() || !self.communities.is_empty()
+    }
+}
+
+enum ChatProposalsResult {
+    Success(Vec<UnvotedProposal>),
+    NoProposals,
+    Failed,
+}
+
+// Only chats which may contain proposals are queried
+pub(crate) fn build_refresh_args(now: TimestampMillis, data: &Data) -> RefreshArgs {
+    let should_query = |canister_id: CanisterId| data.unvoted_proposals.should_query(canister_id, now);
+
+    RefreshArgs {
+        groups: data
+            .group_chats
+            .iter()
+            .map(|g| g.chat_id)
+            .filter(|chat_id| should_query((*chat_id).into()))
+            .collect(),
+        communities: data
+            .communities
+            .iter()
+            .map(|c| c.community_id)
+            .filter(|community_id| should_query((*community_id).into()))
+            .collect(),
+        now,
+    }
+}
+
+pub(crate) async fn refresh_unvoted_proposals(args: RefreshArgs) {
+    let closing_before = args.now + MAX_CLOSING_WITHIN;
+
+    let group_futures: Vec<_> = args
+        .groups
+        .into_iter()
+        .map(|chat_id| unvoted_proposals_in_group(chat_id, closing_before))
+        .collect();
+    let community_futures: Vec<_> = args
+        .communities
+        .into_iter()
+        .map(|community_id| unvoted_proposals_in_community(community_id, closing_before))
+        .collect();
+
+    let (group_results, community_results) = futures::future::join(
+        futures::future::join_all(group_futures),
+        futures::future::join_all(community_futures),
+    )
+    .await;
+
+    let mut proposals = Vec::new();
+    let mut failed_canisters = HashSet::new();
+    let mut chats_without_proposals = Vec::new();
+    for (canister_id, result) in group_results.into_iter().chain(community_results) {
+        match result {
+            ChatProposalsResult::Success(p) => proposals.extend(p),
+            ChatProposalsResult::NoProposals => chats_without_proposals.push(canister_id),
+            ChatProposalsResult::Failed => {
+                failed_canisters.insert(canister_id);
+            }
+        }
+    }
+
+    mutate_state(|state| {
+        state
+            .data
+            .unvoted_proposals
+            .set(proposals, &failed_canisters, chats_without_proposals, args.now);
+        send_due_reminders(state);
+    });
+}
+
+pub(crate) fn send_due_reminders(state: &mut RuntimeState) {
+    let now = state.env.now();
+    let due = state.data.unvoted_proposals.take_due_reminders(now);
+
+    if !due.is_empty() {
+        openchat_bot::send_text_message(build_reminder_text(&due, now), false, state);
+    }
+
+    schedule_next_refresh(state);
+}
+
+// There is only ever a single refresh job queued, which runs either at the next refresh interval or
+// when the next reminder is due, whichever is sooner
+pub(crate) fn schedule_next_refresh(state: &mut RuntimeState) {
+    state
+        .data
+        .timer_jobs
+        .cancel_jobs(|job| matches!(job, TimerJob::RefreshUnvotedProposals(_)));
+
+    if state.data.unvoted_proposals.notify_before().is_some() {
+        let now = state.env.now();
+        let next_refresh = now + REFRESH_INTERVAL;
+        let run_at = state
+            .data
+            .unvoted_proposals
+            .next_reminder_due(now)
+            .map_or(next_refresh, |ts| ts.clamp(now, next_refresh));
+
+        state
+            .data
+            .timer_jobs
+            .enqueue_job(TimerJob::RefreshUnvotedProposals(RefreshUnvotedProposalsJob), run_at, now);
+    }
+}
+
+async fn unvoted_proposals_in_group(chat_id: ChatId, closing_before: TimestampMillis) -> (CanisterId, ChatProposalsResult) {
+    let canister_id = chat_id.into();
+    let args = group_canister::c2c_unvoted_proposals::Args { closing_before };
+
+    let result = match group_canister_c2c_client::c2c_unvoted_proposals(canister_id, &args).await {
+        Ok(group_canister::c2c_unvoted_proposals::Response::Success(proposals)) => ChatProposalsResult::Success(proposals),
+        Ok(group_canister::c2c_unvoted_proposals::Response::NoProposals) => ChatProposalsResult::NoProposals,
+        Ok(group_canister::c2c_unvoted_proposals::Response::CallerNotInGroup) => ChatProposalsResult::Success(Vec::new()),
+        Err(error) => {
+            error!(?chat_id, ?error, "Failed to get unvoted proposals from group");
+            ChatProposalsResult::Failed
+        }
+    };
+    (canister_id, result)
+}
+
+async fn unvoted_proposals_in_community(
+    community_id: CommunityId,
+    closing_before: TimestampMillis,
+) -> (CanisterId, ChatProposalsResult) {
+    let canister_id = community_id.into();
+    let args = community_canister::c2c_unvoted_proposals::Args { closing_before };
+
+    let result = match community_canister_c2c_client::c2c_unvoted_proposals(canister_id, &args).await {
+        Ok(community_canister::c2c_unvoted_proposals::Response::Success(proposals)) => ChatProposalsResult::Success(proposals),
+        Ok(community_canister::c2c_unvoted_proposals::Response::NoProposals) => ChatProposalsResult::NoProposals,
+        Ok(community_canister::c2c_unvoted_proposals::Response::UserNotInCommunity) => ChatProposalsResult::Success(Vec::new()),
+        Err(error) => {
+            error!(?community_id, ?error, "Failed to get unvoted proposals from community");
+            ChatProposalsResult::Failed
+        }
+    };
+    (canister_id, result)
+}
+
+fn build_reminder_text(proposals: &[UnvotedProposal], now: TimestampMillis) -> String {
+    let mut text = "You haven't voted on the following proposals which are closing soon:\n".to_string();
+
+    for proposal in proposals {
+        let hours_remaining = proposal.deadline.saturating_sub(now).div_ceil(HOUR_IN_MS);
+        let url = match proposal.chat {
+            MultiUserChat::Group(chat_id) => format!("/group/{chat_id}/{}", proposal.message_index),
+            MultiUserChat::Channel(community_id, channel_id) => {
+                format!("/community/{community_id}/channel/{channel_id}/{}", proposal.message_index)
+            }
+        };
+        text.push_str(&format!(
+            "\n- [{}]({url}) (closes in {hours_remaining} {})",
+            proposal.title,
+            if hours_remaining == 1 { "hour" } else { "hours" }
+        ));
+    }
+
+    text
+}
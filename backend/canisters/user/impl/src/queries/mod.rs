@@ -14,8 +14,11 @@ pub mod messages_by_message_index;
 pub mod public_profile;
 pub mod saved_crypto_accounts;
 pub mod search_messages;
+pub mod standing_orders;
 pub mod token_swap_status;
+pub mod unvoted_proposals;
 pub mod updates;
+pub mod wallet_transactions;
 
 fn check_replica_up_to_date(latest_known_update: Option<TimestampMillis>, state: &RuntimeState) -> Result<(), TimestampMillis> {
     if let Some(ts) = latest_known_update {
@@ -0,0 +1,21 @@
+use crate::guards::caller_is_owner;
+use crate::{read_state, RuntimeState};
+use ic_cdk_macros::query;
+use user_canister::standing_orders::{Response::*, *};
+
+#[query(guard = "caller_is_owner")]
+fn standing_orders(args: Args) -> Response {
+    read_state(|state| standing_orders_impl(args, state))
+}
+
+fn standing_orders_impl(args: Args, state: &RuntimeState) -> Response {
+    let standing_orders = state
+        .data
+        .standing_orders
+        .iter()
+        .filter(|o| args.include_inactive || o.is_active())
+        .map(|o| o.into())
+        .collect();
+
+    Success(SuccessResult { standing_orders })
+}
@@ -0,0 +1,22 @@
+use crate::guards::caller_is_owner;
+use crate::proposal_reminders::MAX_CLOSING_WITHIN;
+use crate::{read_state, RuntimeState};
+use ic_cdk_macros::query;
+use user_canister::unvoted_proposals::{Response::*, *};
+
+#[query(guard = "caller_is_owner")]
+fn unvoted_proposals(args: Args) -> Response {
+    read_state(|state| unvoted_proposals_impl(args, state))
+}
+
+fn unvoted_proposals_impl(args: Args, state: &RuntimeState) -> Response {
+    let now = state.env.now();
+    let closing_before = now + args.closing_within.min(MAX_CLOSING_WITHIN);
+    let unvoted_proposals = &state.data.unvoted_proposals;
+
+    Success(SuccessResult {
+        proposals: unvoted_proposals.closing_before(closing_before, now),
+        reminders_enabled: unvoted_proposals.notify_before().is_some(),
+        last_refreshed: unvoted_proposals.last_refreshed(),
+    })
+}
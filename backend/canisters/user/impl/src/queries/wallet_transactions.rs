@@ -0,0 +1,40 @@
+use crate::guards::caller_is_owner;
+use crate::{read_state, RuntimeState};
+use ic_cdk_macros::query;
+use user_canister::wallet_transactions::{Response::*, *};
+
+const MAX_RESULTS: u32 = 100;
+// Limits the number of instructions used when the filters match few transactions
+const MAX_TRANSACTIONS_SCANNED: u64 = 5_000;
+
+#[query(guard = "caller_is_owner")]
+fn wallet_transactions(args: Args) -> Response {
+    read_state(|state| wallet_transactions_impl(args, state))
+}
+
+fn wallet_transactions_impl(args: Args, state: &RuntimeState) -> Response {
+    let log = &state.data.wallet_transactions;
+    let total = log.len();
+    let end = args.start.map_or(total, |s| s.min(total));
+    let max_results = args.max_results.min(MAX_RESULTS) as usize;
+
+    let mut transactions = Vec::new();
+    let mut next = end;
+    while next > 0 && transactions.len() < max_results && end - next < MAX_TRANSACTIONS_SCANNED {
+        next -= 1;
+        if let Some(transaction) = log.get(next) {
+            if args.ledger.map_or(true, |l| transaction.ledger == l)
+                && args.kinds.as_ref().map_or(true, |k| k.contains(&transaction.kind))
+                && args.direction.map_or(true, |d| transaction.direction == d)
+            {
+                transactions.push(transaction);
+            }
+        }
+    }
+
+    Success(SuccessResult {
+        transactions,
+        next: Some(next).filter(|n| *n > 0),
+        total,
+    })
+}
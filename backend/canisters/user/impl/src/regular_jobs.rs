@@ -1,9 +1,11 @@
 use crate::group_summaries::{build_summaries_args, SummariesArgs, SummariesResult};
+use crate::proposal_reminders::build_refresh_args;
 use crate::{can_borrow_state, mutate_state, CachedGroupSummaries, Data};
+use std::collections::HashSet;
 use tracing::{error, info};
 use utils::env::Environment;
 use utils::regular_jobs::{RegularJob, RegularJobs};
-use utils::time::{MINUTE_IN_MS, WEEK_IN_MS};
+use utils::time::{HOUR_IN_MS, MINUTE_IN_MS, WEEK_IN_MS};
 
 pub(crate) fn build() -> RegularJobs<Data> {
     let check_cycles_balance = RegularJob::new("Check cycles balance", check_cycles_balance, 5 * MINUTE_IN_MS);
@@ -15,12 +17,14 @@ pub(crate) fn build() -> RegularJobs<Data> {
     let retry_deleting_files = RegularJob::new("Retry deleting files", retry_deleting_files, MINUTE_IN_MS);
     let update_cached_group_summaries =
         RegularJob::new("Update cached group summaries", update_cached_group_summaries, WEEK_IN_MS);
+    let refresh_unvoted_proposals = RegularJob::new("Refresh unvoted proposals", refresh_unvoted_proposals, 6 * HOUR_IN_MS);
 
     RegularJobs::new(vec![
         check_cycles_balance,
         aggregate_direct_chat_metrics,
         retry_deleting_files,
         update_cached_group_summaries,
+        refresh_unvoted_proposals,
     ])
 }
 
@@ -71,3 +75,14 @@ async fn update_cached_group_summaries_impl(args: SummariesArgs) {
         error!("Failed to update group summaries cache");
     }
 }
+
+fn refresh_unvoted_proposals(env: &dyn Environment, data: &mut Data) {
+    let args = build_refresh_args(env.now(), data);
+
+    if args.has_chats_to_query() {
+        ic_cdk::spawn(crate::proposal_reminders::refresh_unvoted_proposals(args));
+    } else {
+        // With no chats to query there are no proposals, so the cache can be cleared directly
+        data.unvoted_proposals.set(Vec::new(), &HashSet::new(), Vec::new(), env.now());
+    }
+}
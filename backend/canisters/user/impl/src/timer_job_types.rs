@@ -1,10 +1,17 @@
 use crate::model::token_swaps::TokenSwap;
+use crate::p2p_swaps::{enqueue_escrow_transfer, notify_status_change, process_escrow_transfer};
+use crate::proposal_reminders::{build_refresh_args, refresh_unvoted_proposals};
+use crate::updates::create_standing_order::execute_standing_order;
 use crate::updates::send_message::send_to_recipients_canister;
 use crate::updates::swap_tokens::process_token_swap;
 use crate::{mutate_state, openchat_bot, read_state};
 use canister_timer_jobs::Job;
+use chat_events::UpdateP2PSwapResult;
 use serde::{Deserialize, Serialize};
-use types::{BlobReference, Chat, ChatId, EventIndex, MessageContent, MessageId, MessageIndex, MessageReminderContent, UserId};
+use types::{
+    BlobReference, Chat, ChatId, EventIndex, MessageContent, MessageId, MessageIndex, MessageReminderContent, TimestampMillis,
+    TimestampNanos, UserId,
+};
 use user_canister::c2c_send_messages;
 use user_canister::c2c_send_messages::C2CReplyContext;
 use utils::consts::OPENCHAT_BOT_USER_ID;
@@ -17,6 +24,10 @@ pub enum TimerJob {
     MessageReminder(Box<MessageReminderJob>),
     RemoveExpiredEvents(RemoveExpiredEventsJob),
     ProcessTokenSwap(Box<ProcessTokenSwapJob>),
+    ExecuteStandingOrder(Box<ExecuteStandingOrderJob>),
+    P2PSwapExpired(Box<P2PSwapExpiredJob>),
+    ProcessP2PSwapTransfer(Box<ProcessP2PSwapTransferJob>),
+    RefreshUnvotedProposals(RefreshUnvotedProposalsJob),
 }
 
 #[derive(Serialize, Deserialize, Clone)]
@@ -57,6 +68,42 @@ pub struct ProcessTokenSwapJob {
     pub attempt: u32,
 }
 
+#[derive(Serialize, Deserialize, Clone)]
+pub struct ExecuteStandingOrderJob {
+    pub standing_order_id: u64,
+    pub due: TimestampMillis,
+    // Set when retrying so that the ledger can deduplicate the transfer
+    pub created: Option<TimestampNanos>,
+    pub attempt: u32,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct P2PSwapExpiredJob {
+    pub them: UserId,
+    pub message_id: MessageId,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct ProcessP2PSwapTransferJob {
+    pub them: UserId,
+    pub message_id: MessageId,
+    pub transfer: P2PSwapTransfer,
+    // Set when retrying so that the ledger can deduplicate the transfer
+    pub created: Option<TimestampNanos>,
+    pub attempt: u32,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct RefreshUnvotedProposalsJob;
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug)]
+pub enum P2PSwapTransfer {
+    RefundToken0,
+    RefundToken1,
+    SettleToken0,
+    SettleToken1,
+}
+
 impl Job for TimerJob {
     fn execute(self) {
         match self {
@@ -66,6 +113,10 @@ impl Job for TimerJob {
             TimerJob::MessageReminder(job) => job.execute(),
             TimerJob::RemoveExpiredEvents(job) => job.execute(),
             TimerJob::ProcessTokenSwap(job) => job.execute(),
+            TimerJob::ExecuteStandingOrder(job) => job.execute(),
+            TimerJob::P2PSwapExpired(job) => job.execute(),
+            TimerJob::ProcessP2PSwapTransfer(job) => job.execute(),
+            TimerJob::RefreshUnvotedProposals(job) => job.execute(),
         }
     }
 }
@@ -157,3 +208,36 @@ impl Job for ProcessTokenSwapJob {
         });
     }
 }
+
+impl Job for ExecuteStandingOrderJob {
+    fn execute(self) {
+        ic_cdk::spawn(execute_standing_order(self));
+    }
+}
+
+impl Job for P2PSwapExpiredJob {
+    fn execute(self) {
+        mutate_state(|state| {
+            let now = state.env.now();
+            if let Some(chat) = state.data.direct_chats.get_mut(&self.them.into()) {
+                if let UpdateP2PSwapResult::Success(content) = chat.events.cancel_p2p_swap(self.message_id, true, now) {
+                    enqueue_escrow_transfer(self.them, self.message_id, P2PSwapTransfer::RefundToken0, state);
+                    notify_status_change(self.them, self.message_id, content.status, state);
+                }
+            }
+        });
+    }
+}
+
+impl Job for ProcessP2PSwapTransferJob {
+    fn execute(self) {
+        ic_cdk::spawn(process_escrow_transfer(self));
+    }
+}
+
+impl Job for RefreshUnvotedProposalsJob {
+    fn execute(self) {
+        let args = read_state(|state| build_refresh_args(state.env.now(), &state.data));
+        ic_cdk::spawn(refresh_unvoted_proposals(args));
+    }
+}
@@ -0,0 +1,60 @@
+use async_trait::async_trait;
+use ic_cdk::api::call::CallResult;
+use icdex_client::ICDexClient;
+use icrc_ledger_types::icrc1::account::Account;
+use swap_client::SwapClient;
+use types::{CancelOrderRequest, CanisterId, MakeOrderRequest, OrderType, TokenInfo};
+
+pub struct ICDexSwapClient {
+    client: ICDexClient<fn(MakeOrderRequest), fn(CancelOrderRequest)>,
+    order_type: OrderType,
+}
+
+impl ICDexSwapClient {
+    pub fn new(
+        this_canister_id: CanisterId,
+        dex_canister_id: CanisterId,
+        base_token: TokenInfo,
+        quote_token: TokenInfo,
+        sell_base_token: bool,
+    ) -> Self {
+        ICDexSwapClient {
+            // `smallest_order_size` is only used when making limit orders, so we can set it to 1
+            client: ICDexClient::<fn(MakeOrderRequest), fn(CancelOrderRequest)>::new(
+                this_canister_id,
+                dex_canister_id,
+                quote_token,
+                base_token,
+                1,
+                |_| {},
+                |_| {},
+            ),
+            order_type: if sell_base_token { OrderType::Ask } else { OrderType::Bid },
+        }
+    }
+}
+
+// ICDex trades directly from the deposit account and transfers the output back to this canister
+// once the order is filled, so there is nothing to do when depositing or withdrawing
+#[async_trait]
+impl SwapClient for ICDexSwapClient {
+    async fn quote(&self, amount: u128) -> CallResult<u128> {
+        self.client.quote(self.order_type, amount).await
+    }
+
+    async fn deposit_account(&self) -> CallResult<Account> {
+        self.client.deposit_account().await
+    }
+
+    async fn deposit(&self, _amount: u128) -> CallResult<()> {
+        Ok(())
+    }
+
+    async fn swap(&self, amount: u128, min_amount_out: u128) -> CallResult<u128> {
+        self.client.swap(self.order_type, amount, min_amount_out).await
+    }
+
+    async fn withdraw(&self, amount: u128) -> CallResult<u128> {
+        Ok(amount)
+    }
+}
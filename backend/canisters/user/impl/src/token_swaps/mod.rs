@@ -1,2 +1,68 @@
-pub mod icpswap;
-pub mod swap_client;
+use crate::token_swaps::icdex::ICDexSwapClient;
+use icpswap_client::ICPSwapClient;
+use sonic_client::SonicClient;
+use swap_client::SwapClient;
+use types::{CanisterId, TokenInfo};
+use user_canister::swap_tokens::ExchangeArgs;
+
+pub mod icdex;
+
+pub fn build_swap_client(
+    exchange_args: &ExchangeArgs,
+    input_token: TokenInfo,
+    output_token: TokenInfo,
+    this_canister_id: CanisterId,
+) -> Box<dyn SwapClient> {
+    let swap_canister_id = exchange_args.swap_canister_id();
+    let zero_for_one = exchange_args.zero_for_one();
+    let (token0, token1) = if zero_for_one { (input_token, output_token) } else { (output_token, input_token) };
+
+    match exchange_args {
+        ExchangeArgs::ICPSwap(_) => Box::new(ICPSwapClient::new(
+            this_canister_id,
+            swap_canister_id,
+            token0,
+            token1,
+            zero_for_one,
+        )),
+        ExchangeArgs::Sonic(_) => Box::new(SonicClient::new(
+            this_canister_id,
+            swap_canister_id,
+            token0,
+            token1,
+            zero_for_one,
+        )),
+        ExchangeArgs::ICDex(_) => Box::new(ICDexSwapClient::new(
+            this_canister_id,
+            swap_canister_id,
+            token0,
+            token1,
+            zero_for_one,
+        )),
+    }
+}
+
+// Requests a quote from each exchange in parallel. The input token's fee is deducted from the amount
+// since that is charged when depositing into the exchange.
+pub async fn get_quotes(
+    exchanges: Vec<ExchangeArgs>,
+    input_token: TokenInfo,
+    output_token: TokenInfo,
+    input_amount: u128,
+    this_canister_id: CanisterId,
+) -> Vec<(ExchangeArgs, Result<u128, String>)> {
+    let amount = input_amount.saturating_sub(input_token.fee);
+
+    let futures: Vec<_> = exchanges
+        .into_iter()
+        .map(|exchange_args| {
+            let swap_client = build_swap_client(&exchange_args, input_token.clone(), output_token.clone(), this_canister_id);
+            async move {
+                let result = swap_client.quote(amount).await.map_err(|e| format!("{e:?}"));
+                (exchange_args, result)
+            }
+        })
+        .collect();
+
+    futures::future::join_all(futures).await
+}
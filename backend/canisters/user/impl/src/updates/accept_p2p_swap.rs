@@ -0,0 +1,136 @@
+use crate::crypto::process_transaction;
+use crate::guards::caller_is_owner;
+use crate::p2p_swaps::{escrow_account, escrow_deposit};
+use crate::{mutate_state, run_regular_jobs, RuntimeState};
+use canister_tracing_macros::trace;
+use chat_events::{Reader, UpdateP2PSwapResult};
+use ic_cdk_macros::update;
+use msgpack::serialize_then_unwrap;
+use tracing::error;
+use types::{MessageId, PendingCryptoTransaction, UserId};
+use user_canister::accept_p2p_swap::{Response::*, *};
+use user_canister::{c2c_accept_p2p_swap, WalletTransactionKind};
+
+#[update(guard = "caller_is_owner")]
+#[trace]
+async fn accept_p2p_swap(args: Args) -> Response {
+    run_regular_jobs();
+
+    let pending_transaction = match mutate_state(|state| reserve_p2p_swap(&args, state)) {
+        Ok(t) => t,
+        Err(response) => return response,
+    };
+
+    // Transfer token1 into the escrow account held by the offerer's canister
+    let token1_txn_in = match process_transaction(pending_transaction, WalletTransactionKind::P2PSwap).await {
+        Ok(completed) => completed.block_index(),
+        Err(failed) => {
+            mutate_state(|state| unreserve_p2p_swap(args.user_id, args.message_id, state));
+            return TransferFailed(failed.error_message().to_string());
+        }
+    };
+
+    let c2c_args = c2c_accept_p2p_swap::Args {
+        message_id: args.message_id,
+        token1_txn_in,
+    };
+
+    match user_canister_c2c_client::c2c_accept_p2p_swap(args.user_id.into(), &c2c_args).await {
+        Ok(c2c_accept_p2p_swap::Response::Success) => {
+            mutate_state(|state| mark_p2p_swap_accepted(args.user_id, args.message_id, token1_txn_in, state));
+            Success(SuccessResult { token1_txn_in })
+        }
+        Ok(c2c_accept_p2p_swap::Response::StatusError(status)) => {
+            // The offerer's canister will refund token1 since the swap can no longer be accepted
+            mutate_state(|state| {
+                let now = state.env.now();
+                if let Some(chat) = state.data.direct_chats.get_mut(&args.user_id.into()) {
+                    chat.events.set_p2p_swap_status(args.message_id, status.clone(), now);
+                }
+            });
+            StatusError(status)
+        }
+        Ok(c2c_accept_p2p_swap::Response::InternalError(error)) => {
+            // The offerer's canister was unable to check the ledger for our transfer into escrow, so
+            // we retry in the same way as if the call itself had failed
+            error!(error = error.as_str(), ?args.message_id, "Offerer's canister failed to verify P2P swap acceptance");
+            retry_c2c_accept_p2p_swap(args.user_id, args.message_id, c2c_args);
+            Success(SuccessResult { token1_txn_in })
+        }
+        Ok(response) => {
+            error!(?response, ?args.message_id, "Offerer's canister failed to find the P2P swap");
+            mutate_state(|state| unreserve_p2p_swap(args.user_id, args.message_id, state));
+            SwapNotFound
+        }
+        Err(error) => {
+            // The funds are already in escrow so we keep retrying until the offerer's canister
+            // processes the acceptance, at which point it will either settle or refund the swap
+            error!(?error, ?args.message_id, "Failed to notify offerer's canister of P2P swap acceptance");
+            retry_c2c_accept_p2p_swap(args.user_id, args.message_id, c2c_args);
+            Success(SuccessResult { token1_txn_in })
+        }
+    }
+}
+
+fn retry_c2c_accept_p2p_swap(them: UserId, message_id: MessageId, c2c_args: c2c_accept_p2p_swap::Args) {
+    mutate_state(|state| {
+        let token1_txn_in = c2c_args.token1_txn_in;
+        state.data.fire_and_forget_handler.send(
+            them.into(),
+            "c2c_accept_p2p_swap_msgpack".to_string(),
+            serialize_then_unwrap(c2c_args),
+        );
+        mark_p2p_swap_accepted(them, message_id, token1_txn_in, state);
+    });
+}
+
+fn reserve_p2p_swap(args: &Args, state: &mut RuntimeState) -> Result<PendingCryptoTransaction, Response> {
+    if state.data.suspended.value {
+        return Err(UserSuspended);
+    }
+
+    let my_user_id: UserId = state.env.canister_id().into();
+    let now = state.env.now();
+    let now_nanos = state.env.now_nanos();
+
+    let Some(chat) = state.data.direct_chats.get_mut(&args.user_id.into()) else {
+        return Err(ChatNotFound);
+    };
+
+    // Only the recipient of the offer can accept it
+    if chat
+        .events
+        .main_events_reader()
+        .message_internal(args.message_id.into())
+        .map_or(true, |m| m.sender != args.user_id)
+    {
+        return Err(SwapNotFound);
+    }
+
+    match chat.events.reserve_p2p_swap(my_user_id, args.message_id, now) {
+        UpdateP2PSwapResult::Success(content) => Ok(escrow_deposit(
+            &content.token1,
+            content.token1_amount,
+            escrow_account(args.user_id, content.swap_id),
+            now_nanos,
+        )),
+        UpdateP2PSwapResult::StatusError(status) => Err(StatusError(status)),
+        UpdateP2PSwapResult::SwapNotFound => Err(SwapNotFound),
+    }
+}
+
+fn unreserve_p2p_swap(them: UserId, message_id: MessageId, state: &mut RuntimeState) {
+    let my_user_id = state.env.canister_id().into();
+    let now = state.env.now();
+    if let Some(chat) = state.data.direct_chats.get_mut(&them.into()) {
+        chat.events.unreserve_p2p_swap(my_user_id, message_id, now);
+    }
+}
+
+fn mark_p2p_swap_accepted(them: UserId, message_id: MessageId, token1_txn_in: u64, state: &mut RuntimeState) {
+    let my_user_id = state.env.canister_id().into();
+    let now = state.env.now();
+    if let Some(chat) = state.data.direct_chats.get_mut(&them.into()) {
+        chat.events.accept_p2p_swap(my_user_id, message_id, token1_txn_in, now);
+    }
+}
@@ -1,9 +1,11 @@
 use crate::guards::caller_is_owner;
-use crate::{read_state, run_regular_jobs};
+use crate::model::wallet_transactions::WalletTransactionEntry;
+use crate::{mutate_state, read_state, run_regular_jobs};
 use canister_tracing_macros::trace;
 use ic_cdk_macros::update;
 use icrc_ledger_types::icrc2::approve::ApproveArgs;
 use user_canister::approve_transfer::{Response::*, *};
+use user_canister::{WalletCounterparty, WalletTransactionDirection, WalletTransactionKind};
 use utils::time::NANOS_PER_MILLISECOND;
 
 #[update(guard = "caller_is_owner")]
@@ -30,7 +32,22 @@ async fn approve_transfer(args: Args) -> Response {
     )
     .await
     {
-        Ok(icrc_ledger_canister::icrc2_approve::Response::Ok(_)) => Success,
+        Ok(icrc_ledger_canister::icrc2_approve::Response::Ok(block_index)) => {
+            mutate_state(|state| {
+                let now = state.env.now();
+                state.data.wallet_transactions.record(WalletTransactionEntry {
+                    timestamp: now,
+                    kind: WalletTransactionKind::Approval,
+                    direction: WalletTransactionDirection::Approval,
+                    ledger: args.ledger_canister_id,
+                    amount: args.amount,
+                    fee: None,
+                    counterparty: Some(WalletCounterparty::Account(args.spender)),
+                    block_index: block_index.0.try_into().ok(),
+                })
+            });
+            Success
+        }
         Ok(icrc_ledger_canister::icrc2_approve::Response::Err(err)) => ApproveError(err),
         Err(error) => InternalError(format!("{error:?}")),
     }
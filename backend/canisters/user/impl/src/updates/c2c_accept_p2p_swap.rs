@@ -0,0 +1,114 @@
+use crate::p2p_swaps::{
+    enqueue_escrow_transfer, escrow_account, notify_status_change, verify_escrow_deposit, VerifyEscrowDepositError,
+};
+use crate::timer_job_types::{P2PSwapTransfer, TimerJob};
+use crate::{mutate_state, read_state, run_regular_jobs, RuntimeState};
+use candid::Principal;
+use canister_api_macros::update_msgpack;
+use canister_tracing_macros::trace;
+use chat_events::{MessageContentInternal, Reader, UpdateP2PSwapResult};
+use icrc_ledger_types::icrc1::account::Account;
+use types::{P2PSwapStatus, TokenInfo, UserId};
+use user_canister::c2c_accept_p2p_swap::{Response::*, *};
+
+#[update_msgpack]
+#[trace]
+async fn c2c_accept_p2p_swap(args: Args) -> Response {
+    run_regular_jobs();
+
+    let PrepareResult {
+        token1,
+        amount,
+        from,
+        to,
+    } = match read_state(|state| prepare(&args, state)) {
+        Ok(ok) => ok,
+        Err(response) => return response,
+    };
+
+    // Check the ledger to make sure token1 really has been transferred into escrow before the swap
+    // is accepted, otherwise token0 could be released without anything having been paid for it
+    match verify_escrow_deposit(&token1, amount, from, to, args.token1_txn_in).await {
+        Ok(()) => mutate_state(|state| c2c_accept_p2p_swap_impl(args, state)),
+        Err(VerifyEscrowDepositError::Invalid(error)) => TransferInvalid(error),
+        Err(VerifyEscrowDepositError::InternalError(error)) => InternalError(error),
+    }
+}
+
+struct PrepareResult {
+    token1: TokenInfo,
+    amount: u128,
+    from: Account,
+    to: Account,
+}
+
+fn prepare(args: &Args, state: &RuntimeState) -> Result<PrepareResult, Response> {
+    let caller: UserId = state.env.caller().into();
+    let my_user_id: UserId = state.env.canister_id().into();
+
+    let Some(chat) = state.data.direct_chats.get(&caller.into()) else {
+        return Err(ChatNotFound);
+    };
+
+    // The escrow is held by the canister of the user who offered the swap
+    match chat.events.main_events_reader().message_internal(args.message_id.into()) {
+        Some(message) if message.sender == my_user_id => match &message.content {
+            MessageContentInternal::P2PSwap(content) => Ok(PrepareResult {
+                token1: content.token1.clone(),
+                amount: content.token1_amount + content.token1.fee,
+                from: Account::from(Principal::from(caller)),
+                to: escrow_account(my_user_id, content.swap_id),
+            }),
+            _ => Err(SwapNotFound),
+        },
+        _ => Err(SwapNotFound),
+    }
+}
+
+fn c2c_accept_p2p_swap_impl(args: Args, state: &mut RuntimeState) -> Response {
+    let caller: UserId = state.env.caller().into();
+    let my_user_id: UserId = state.env.canister_id().into();
+    let now = state.env.now();
+
+    let Some(chat) = state.data.direct_chats.get_mut(&caller.into()) else {
+        return ChatNotFound;
+    };
+
+    // The escrow is held by the canister of the user who offered the swap
+    if chat
+        .events
+        .main_events_reader()
+        .message_internal(args.message_id.into())
+        .map_or(true, |m| m.sender != my_user_id)
+    {
+        return SwapNotFound;
+    }
+
+    match chat.events.accept_p2p_swap(caller, args.message_id, args.token1_txn_in, now) {
+        UpdateP2PSwapResult::Success(content) => {
+            state.data.timer_jobs.cancel_jobs(
+                |job| matches!(job, TimerJob::P2PSwapExpired(j) if j.them == caller && j.message_id == args.message_id),
+            );
+            enqueue_escrow_transfer(caller, args.message_id, P2PSwapTransfer::SettleToken0, state);
+            enqueue_escrow_transfer(caller, args.message_id, P2PSwapTransfer::SettleToken1, state);
+            notify_status_change(caller, args.message_id, content.status, state);
+            Success
+        }
+        UpdateP2PSwapResult::StatusError(status) => {
+            let already_accepted = match &status {
+                P2PSwapStatus::Accepted(a) => a.token1_txn_in == args.token1_txn_in,
+                P2PSwapStatus::Completed(c) => c.token1_txn_in == args.token1_txn_in,
+                _ => false,
+            };
+            if already_accepted {
+                // This acceptance has been retried after already having been processed
+                Success
+            } else {
+                enqueue_escrow_transfer(caller, args.message_id, P2PSwapTransfer::RefundToken1, state);
+                notify_status_change(caller, args.message_id, status.clone(), state);
+                StatusError(status)
+            }
+        }
+        UpdateP2PSwapResult::SwapNotFound => SwapNotFound,
+    }
+}
@@ -1,10 +1,12 @@
 use crate::guards::caller_is_user_index;
-use crate::{read_state, run_regular_jobs};
+use crate::model::wallet_transactions::WalletTransactionEntry;
+use crate::{mutate_state, read_state, run_regular_jobs};
 use canister_api_macros::update_msgpack;
 use canister_tracing_macros::trace;
 use icrc_ledger_types::icrc1::account::Account;
 use icrc_ledger_types::icrc1::transfer::TransferArg;
 use user_canister::c2c_charge_user_account::{Response::*, *};
+use user_canister::{WalletCounterparty, WalletTransactionDirection, WalletTransactionKind};
 
 #[update_msgpack(guard = "caller_is_user_index")]
 #[trace]
@@ -26,7 +28,23 @@ async fn c2c_charge_user_account(args: Args) -> Response {
     )
     .await
     {
-        Ok(Ok(block_index)) => Success(block_index.0.try_into().unwrap()),
+        Ok(Ok(block_index)) => {
+            let block_index = block_index.0.try_into().unwrap();
+            mutate_state(|state| {
+                let now = state.env.now();
+                state.data.wallet_transactions.record(WalletTransactionEntry {
+                    timestamp: now,
+                    kind: WalletTransactionKind::DiamondMembership,
+                    direction: WalletTransactionDirection::Outgoing,
+                    ledger: args.ledger_canister_id,
+                    amount: args.amount.e8s().into(),
+                    fee: None,
+                    counterparty: Some(WalletCounterparty::Canister(user_index_canister_id)),
+                    block_index: Some(block_index),
+                })
+            });
+            Success(block_index)
+        }
         Ok(Err(transfer_error)) => TransferErrorV2(transfer_error),
         Err(error) => InternalError(format!("{error:?}")),
     }
@@ -52,9 +52,11 @@ fn process_event(event: Event, state: &mut RuntimeState) {
             let now = state.env.now();
             state.data.group_chats.join(ev.chat_id, ev.latest_message_index, now);
             state.data.hot_group_exclusions.remove(&ev.chat_id, now);
+            state.data.unvoted_proposals.chat_joined(ev.chat_id.into());
         }
         Event::UserJoinedCommunityOrChannel(ev) => {
             let now = state.env.now();
+            state.data.unvoted_proposals.chat_joined(ev.community_id.into());
             let (community, _) = state.data.communities.join(ev.community_id, now);
             community.mark_read(
                 ev.channels
@@ -0,0 +1,61 @@
+use crate::crypto::{get_transfer, AccountOrIdentifier};
+use crate::model::wallet_transactions::WalletTransactionEntry;
+use crate::{mutate_state, read_state, run_regular_jobs};
+use canister_api_macros::update_msgpack;
+use canister_tracing_macros::trace;
+use icrc_ledger_types::icrc1::account::Account;
+use tracing::error;
+use types::Cryptocurrency;
+use user_canister::c2c_notify_joining_fee_paid::{Response::*, *};
+use user_canister::{WalletCounterparty, WalletTransactionDirection, WalletTransactionKind};
+
+#[update_msgpack]
+#[trace]
+async fn c2c_notify_joining_fee_paid(args: Args) -> Response {
+    run_regular_jobs();
+
+    let (caller, my_account) = read_state(|state| (state.env.caller(), Account::from(state.env.canister_id())));
+
+    if read_state(|state| state.data.wallet_transactions.has_recorded(args.ledger, args.block_index)) {
+        return Success;
+    }
+
+    // Look up the transfer on the ledger so that only fees which were actually taken from this user
+    // by the calling canister are recorded
+    let is_icp = Cryptocurrency::InternetComputer.ledger_canister_id() == Some(args.ledger);
+    let transfer = match get_transfer(args.ledger, is_icp, args.block_index).await {
+        Ok(Some(t))
+            if t.from == AccountOrIdentifier::from(my_account, is_icp)
+                && t.to == AccountOrIdentifier::from(Account::from(caller), is_icp) =>
+        {
+            t
+        }
+        Ok(_) => return Success,
+        Err(error) => {
+            error!(
+                error = error.as_str(),
+                ledger = %args.ledger,
+                block_index = args.block_index,
+                "Failed to look up joining fee"
+            );
+            return Success;
+        }
+    };
+
+    mutate_state(|state| {
+        if !state.data.wallet_transactions.has_recorded(args.ledger, args.block_index) {
+            let now = state.env.now();
+            state.data.wallet_transactions.record(WalletTransactionEntry {
+                timestamp: now,
+                kind: WalletTransactionKind::JoiningFee,
+                direction: WalletTransactionDirection::Outgoing,
+                ledger: args.ledger,
+                amount: transfer.amount,
+                fee: Some(args.fee),
+                counterparty: Some(WalletCounterparty::Canister(caller)),
+                block_index: Some(args.block_index),
+            });
+        }
+    });
+    Success
+}
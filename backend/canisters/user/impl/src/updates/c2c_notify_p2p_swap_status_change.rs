@@ -0,0 +1,52 @@
+use crate::model::wallet_transactions::WalletTransactionEntry;
+use crate::{mutate_state, run_regular_jobs, RuntimeState};
+use canister_api_macros::update_msgpack;
+use canister_tracing_macros::trace;
+use chat_events::{MessageContentInternal, Reader, UpdateP2PSwapResult};
+use types::{P2PSwapStatus, UserId};
+use user_canister::c2c_notify_p2p_swap_status_change::{Response::*, *};
+use user_canister::{WalletCounterparty, WalletTransactionDirection, WalletTransactionKind};
+
+#[update_msgpack]
+#[trace]
+fn c2c_notify_p2p_swap_status_change(args: Args) -> Response {
+    run_regular_jobs();
+
+    mutate_state(|state| c2c_notify_p2p_swap_status_change_impl(args, state))
+}
+
+fn c2c_notify_p2p_swap_status_change_impl(args: Args, state: &mut RuntimeState) -> Response {
+    let caller: UserId = state.env.caller().into();
+    let now = state.env.now();
+
+    let Some(chat) = state.data.direct_chats.get_mut(&caller.into()) else {
+        return Success;
+    };
+
+    // Only the canister holding the escrow (ie. the offerer's canister) can update the status
+    let already_completed = match chat.events.main_events_reader().message_internal(args.message_id.into()) {
+        Some(m) if m.sender == caller => {
+            matches!(&m.content, MessageContentInternal::P2PSwap(p) if matches!(p.status, P2PSwapStatus::Completed(_)))
+        }
+        _ => return Success,
+    };
+
+    if let UpdateP2PSwapResult::Success(content) = chat.events.set_p2p_swap_status(args.message_id, args.status, now) {
+        if let P2PSwapStatus::Completed(completed) = &content.status {
+            if !already_completed {
+                state.data.wallet_transactions.record(WalletTransactionEntry {
+                    timestamp: now,
+                    kind: WalletTransactionKind::P2PSwap,
+                    direction: WalletTransactionDirection::Incoming,
+                    ledger: content.token0.ledger,
+                    amount: content.token0_amount,
+                    fee: None,
+                    counterparty: Some(WalletCounterparty::User(caller)),
+                    block_index: Some(completed.token0_txn_out),
+                });
+            }
+        }
+    }
+
+    Success
+}
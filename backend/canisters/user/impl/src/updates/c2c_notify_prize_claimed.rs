@@ -0,0 +1,34 @@
+use crate::model::wallet_transactions::WalletTransactionEntry;
+use crate::{mutate_state, run_regular_jobs, RuntimeState};
+use canister_api_macros::update_msgpack;
+use canister_tracing_macros::trace;
+use user_canister::c2c_notify_prize_claimed::{Response::*, *};
+use user_canister::{WalletCounterparty, WalletTransactionDirection, WalletTransactionKind};
+
+#[update_msgpack]
+#[trace]
+fn c2c_notify_prize_claimed(args: Args) -> Response {
+    run_regular_jobs();
+
+    mutate_state(|state| c2c_notify_prize_claimed_impl(args, state))
+}
+
+fn c2c_notify_prize_claimed_impl(args: Args, state: &mut RuntimeState) -> Response {
+    let caller = state.env.caller();
+
+    // Only record prizes claimed from groups or communities which the user is a member of
+    if state.data.group_chats.has(&caller.into()) || state.data.communities.has(&caller.into()) {
+        let now = state.env.now();
+        state.data.wallet_transactions.record(WalletTransactionEntry {
+            timestamp: now,
+            kind: WalletTransactionKind::PrizeClaim,
+            direction: WalletTransactionDirection::Incoming,
+            ledger: args.ledger,
+            amount: args.amount,
+            fee: None,
+            counterparty: Some(WalletCounterparty::Canister(caller)),
+            block_index: Some(args.block_index),
+        });
+    }
+    Success
+}
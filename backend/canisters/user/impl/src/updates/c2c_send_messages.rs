@@ -1,3 +1,4 @@
+use crate::model::wallet_transactions::WalletTransactionEntry;
 use crate::updates::send_message::register_timer_jobs;
 use crate::{mutate_state, read_state, run_regular_jobs, RuntimeState};
 use canister_api_macros::update_msgpack;
@@ -6,10 +7,11 @@ use chat_events::{MessageContentInternal, PushMessageArgs, Reader, ReplyContextI
 use ic_cdk_macros::update;
 use rand::Rng;
 use types::{
-    CanisterId, DirectMessageNotification, EventWrapper, Message, MessageContent, MessageContentInitial, MessageId,
-    MessageIndex, Notification, TimestampMillis, UserId,
+    CanisterId, ContentValidationError, CryptoTransaction, DirectMessageNotification, EventWrapper, Message, MessageContent,
+    MessageContentInitial, MessageId, MessageIndex, Notification, TimestampMillis, UserId,
 };
 use user_canister::c2c_send_messages::{Response::*, *};
+use user_canister::{WalletCounterparty, WalletTransactionDirection, WalletTransactionKind};
 
 #[update_msgpack]
 #[trace]
@@ -90,6 +92,11 @@ async fn c2c_handle_bot_messages(
     };
 
     for message in args.messages.iter() {
+        if matches!(message.content, MessageContentInitial::P2PSwap(_)) {
+            return user_canister::c2c_handle_bot_messages::Response::ContentValidationError(
+                ContentValidationError::Unauthorized,
+            );
+        }
         if let Err(error) = message.content.validate_for_new_direct_message(sender_user_id, false, now) {
             return user_canister::c2c_handle_bot_messages::Response::ContentValidationError(error);
         }
@@ -175,8 +182,12 @@ pub(crate) fn handle_message_impl(
     state: &mut RuntimeState,
 ) -> EventWrapper<Message> {
     let replies_to = convert_reply_context(args.replies_to, sender, state);
-    let initial_content: MessageContentInitial = args.content.into();
-    let content = MessageContentInternal::from(initial_content);
+    let content = match args.content {
+        // The initial form of a P2P swap doesn't include the swap id or the escrow transfer, so
+        // these are converted directly from the hydrated form
+        MessageContent::P2PSwap(p) => MessageContentInternal::P2PSwap(p.into()),
+        c => MessageContentInternal::from(MessageContentInitial::from(c)),
+    };
     let files = content.blob_references();
 
     let push_message_args = PushMessageArgs {
@@ -205,6 +216,21 @@ pub(crate) fn handle_message_impl(
         }
     }
 
+    if let MessageContent::Crypto(c) = &message_event.event.content {
+        if let CryptoTransaction::Completed(t) = &c.transfer {
+            state.data.wallet_transactions.record(WalletTransactionEntry {
+                timestamp: args.now,
+                kind: WalletTransactionKind::Message,
+                direction: WalletTransactionDirection::Incoming,
+                ledger: t.ledger_canister_id(),
+                amount: t.units(),
+                fee: None,
+                counterparty: Some(WalletCounterparty::User(sender)),
+                block_index: Some(t.block_index()),
+            });
+        }
+    }
+
     register_timer_jobs(
         &message_event,
         files,
@@ -1,3 +1,4 @@
+use crate::model::wallet_transactions::WalletTransactionEntry;
 use crate::{mutate_state, run_regular_jobs, RuntimeState};
 use canister_api_macros::update_msgpack;
 use canister_tracing_macros::trace;
@@ -5,6 +6,7 @@ use chat_events::{Reader, TipMessageArgs, TipMessageResult};
 use ledger_utils::format_crypto_amount_with_symbol;
 use types::{DirectMessageTipped, EventIndex, Notification, UserId};
 use user_canister::c2c_tip_message::{Response::*, *};
+use user_canister::{WalletCounterparty, WalletTransactionDirection, WalletTransactionKind};
 
 #[update_msgpack]
 #[trace]
@@ -35,6 +37,17 @@ fn c2c_tip_message_impl(args: Args, state: &mut RuntimeState) -> Response {
             chat.events.tip_message(tip_message_args, EventIndex::default(),),
             TipMessageResult::Success
         ) {
+            state.data.wallet_transactions.record(WalletTransactionEntry {
+                timestamp: now,
+                kind: WalletTransactionKind::Tip,
+                direction: WalletTransactionDirection::Incoming,
+                ledger: args.ledger,
+                amount: args.amount,
+                fee: None,
+                counterparty: Some(WalletCounterparty::User(user_id)),
+                block_index: None,
+            });
+
             if let Some(event) = chat
                 .events
                 .main_events_reader()
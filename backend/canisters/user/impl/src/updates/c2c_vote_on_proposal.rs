@@ -1,6 +1,6 @@
 use crate::governance_clients::nns::GetBallotsResult;
 use crate::guards::caller_is_known_group_or_community_canister;
-use crate::{read_state, run_regular_jobs};
+use crate::{mutate_state, read_state, run_regular_jobs};
 use canister_api_macros::update_msgpack;
 use canister_tracing_macros::trace;
 use ic_cdk::api::call::CallResult;
@@ -12,11 +12,22 @@ use user_canister::c2c_vote_on_proposal::{Response::*, *};
 async fn c2c_vote_on_proposal(args: Args) -> Response {
     run_regular_jobs();
 
-    if args.is_nns {
+    let response = if args.is_nns {
         nns::vote_on_proposal(args.governance_canister_id, args.proposal_id, args.adopt).await
     } else {
         sns::vote_on_proposal(args.governance_canister_id, args.proposal_id, args.adopt).await
+    };
+
+    if matches!(response, Success) {
+        mutate_state(|state| {
+            state
+                .data
+                .unvoted_proposals
+                .mark_voted(args.governance_canister_id, args.proposal_id)
+        });
     }
+
+    response
 }
 
 mod nns {
@@ -0,0 +1,49 @@
+use crate::guards::caller_is_owner;
+use crate::p2p_swaps::{enqueue_escrow_transfer, notify_status_change};
+use crate::timer_job_types::{P2PSwapTransfer, TimerJob};
+use crate::{mutate_state, run_regular_jobs, RuntimeState};
+use canister_tracing_macros::trace;
+use chat_events::{Reader, UpdateP2PSwapResult};
+use ic_cdk_macros::update;
+use types::UserId;
+use user_canister::cancel_p2p_swap::{Response::*, *};
+
+#[update(guard = "caller_is_owner")]
+#[trace]
+fn cancel_p2p_swap(args: Args) -> Response {
+    run_regular_jobs();
+
+    mutate_state(|state| cancel_p2p_swap_impl(args, state))
+}
+
+fn cancel_p2p_swap_impl(args: Args, state: &mut RuntimeState) -> Response {
+    let my_user_id: UserId = state.env.canister_id().into();
+    let now = state.env.now();
+
+    let Some(chat) = state.data.direct_chats.get_mut(&args.user_id.into()) else {
+        return ChatNotFound;
+    };
+
+    // Only the user who offered the swap can cancel it
+    if chat
+        .events
+        .main_events_reader()
+        .message_internal(args.message_id.into())
+        .map_or(true, |m| m.sender != my_user_id)
+    {
+        return SwapNotFound;
+    }
+
+    match chat.events.cancel_p2p_swap(args.message_id, false, now) {
+        UpdateP2PSwapResult::Success(content) => {
+            state.data.timer_jobs.cancel_jobs(
+                |job| matches!(job, TimerJob::P2PSwapExpired(j) if j.them == args.user_id && j.message_id == args.message_id),
+            );
+            enqueue_escrow_transfer(args.user_id, args.message_id, P2PSwapTransfer::RefundToken0, state);
+            notify_status_change(args.user_id, args.message_id, content.status, state);
+            Success
+        }
+        UpdateP2PSwapResult::StatusError(status) => StatusError(status),
+        UpdateP2PSwapResult::SwapNotFound => SwapNotFound,
+    }
+}
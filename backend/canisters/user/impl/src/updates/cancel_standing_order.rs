@@ -0,0 +1,33 @@
+use crate::guards::caller_is_owner;
+use crate::timer_job_types::TimerJob;
+use crate::{mutate_state, run_regular_jobs, RuntimeState};
+use canister_tracing_macros::trace;
+use ic_cdk_macros::update;
+use user_canister::cancel_standing_order::{Response::*, *};
+
+#[update(guard = "caller_is_owner")]
+#[trace]
+fn cancel_standing_order(args: Args) -> Response {
+    run_regular_jobs();
+
+    mutate_state(|state| cancel_standing_order_impl(args.standing_order_id, state))
+}
+
+fn cancel_standing_order_impl(standing_order_id: u64, state: &mut RuntimeState) -> Response {
+    let now = state.env.now();
+    let Some(order) = state.data.standing_orders.get_mut(standing_order_id) else {
+        return NotFound;
+    };
+
+    if order.cancel(now) {
+        state.data.timer_jobs.cancel_jobs(|j| {
+            if let TimerJob::ExecuteStandingOrder(job) = j {
+                job.standing_order_id == standing_order_id
+            } else {
+                false
+            }
+        });
+    }
+
+    Success
+}
@@ -5,7 +5,7 @@ use group_index_canister::c2c_create_community;
 use ic_cdk_macros::update;
 use std::collections::HashSet;
 use tracing::error;
-use types::{CanisterId, CommunityId};
+use types::{AccessGate, CanisterId, CommunityId};
 use user_canister::create_community::{Response::*, *};
 use utils::document_validation::{validate_avatar, validate_banner};
 use utils::text_validation::{
@@ -93,6 +93,9 @@ fn prepare(args: Args, state: &RuntimeState) -> Result<PrepareResult, Response>
         Err(BannerTooBig(error))
     } else if !default_channels_valid(&args.default_channels) {
         Err(DefaultChannelsInvalid)
+    } else if matches!(args.gate, Some(AccessGate::UserGroup(_))) {
+        // User group gates are only supported on community channels
+        Err(InvalidGate)
     } else {
         let create_community_args = c2c_create_community::Args {
             is_public: args.is_public,
@@ -6,7 +6,7 @@ use canister_tracing_macros::trace;
 use group_index_canister::c2c_create_group;
 use ic_cdk_macros::update;
 use tracing::error;
-use types::{CanisterId, ChatId};
+use types::{AccessGate, CanisterId, ChatId};
 use user_canister::create_group::{Response::*, *};
 use utils::document_validation::validate_avatar;
 use utils::text_validation::{
@@ -88,6 +88,9 @@ fn prepare(args: Args, state: &RuntimeState) -> Result<PrepareResult, Response>
         });
     } else if let Err(error) = validate_avatar(args.avatar.as_ref()) {
         Err(AvatarTooBig(error))
+    } else if matches!(args.gate, Some(AccessGate::UserGroup(_))) {
+        // User group gates are only supported on community channels
+        Err(InvalidGate)
     } else {
         let create_group_args = c2c_create_group::Args {
             is_public: args.is_public,
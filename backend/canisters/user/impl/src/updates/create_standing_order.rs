@@ -0,0 +1,199 @@
+use crate::crypto::try_process_transaction_without_caller_check;
+use crate::guards::caller_is_owner;
+use crate::model::standing_orders::StandingOrder;
+use crate::timer_job_types::{ExecuteStandingOrderJob, TimerJob};
+use crate::{mutate_state, run_regular_jobs, RuntimeState};
+use candid::Principal;
+use canister_tracing_macros::trace;
+use ic_cdk_macros::update;
+use icrc_ledger_types::icrc1::account::Account;
+use rand::RngCore;
+use tracing::error;
+use types::{icrc1, PendingCryptoTransaction, TimestampMillis, TimestampNanos};
+use user_canister::create_standing_order::{Response::*, *};
+use user_canister::{StandingOrderExecution, StandingOrderRecipient, WalletTransactionKind};
+use utils::consts::MEMO_STANDING_ORDER;
+use utils::time::{HOUR_IN_MS, SECOND_IN_MS};
+
+const MAX_ACTIVE_STANDING_ORDERS: u32 = 50;
+const MIN_INTERVAL: u64 = HOUR_IN_MS;
+const MAX_ATTEMPTS: u32 = 10;
+
+#[update(guard = "caller_is_owner")]
+#[trace]
+fn create_standing_order(args: Args) -> Response {
+    run_regular_jobs();
+
+    mutate_state(|state| create_standing_order_impl(args, state))
+}
+
+fn create_standing_order_impl(args: Args, state: &mut RuntimeState) -> Response {
+    if state.data.suspended.value {
+        return UserSuspended;
+    }
+
+    if args.amount <= args.token.fee {
+        return AmountTooSmall;
+    }
+
+    if args.interval < MIN_INTERVAL {
+        return IntervalTooShort(MIN_INTERVAL);
+    }
+
+    let now = state.env.now();
+    let first_payment = args.first_payment.unwrap_or(now);
+    if first_payment < now {
+        return FirstPaymentInThePast;
+    }
+
+    if args.end_date.map_or(false, |e| e < first_payment) {
+        return EndDateBeforeFirstPayment;
+    }
+
+    if state.data.standing_orders.active_count() >= MAX_ACTIVE_STANDING_ORDERS as usize {
+        return TooManyStandingOrders(MAX_ACTIVE_STANDING_ORDERS);
+    }
+
+    let standing_order_id = state.env.rng().next_u64();
+
+    state.data.standing_orders.add(StandingOrder {
+        id: standing_order_id,
+        recipient: args.recipient,
+        token: args.token,
+        amount: args.amount,
+        interval: args.interval,
+        created: now,
+        end_date: args.end_date,
+        next_payment_due: Some(first_payment),
+        cancelled: None,
+        executions: Vec::new(),
+    });
+
+    enqueue_job(standing_order_id, first_payment, None, 0, first_payment, state);
+
+    Success(SuccessResult {
+        standing_order_id,
+        next_payment_due: first_payment,
+    })
+}
+
+pub(crate) async fn execute_standing_order(job: ExecuteStandingOrderJob) {
+    let Some((transaction, created)) = mutate_state(|state| prepare(&job, state)) else {
+        return;
+    };
+
+    // Failures caused by the ledger being temporarily unreachable are retried, whereas failures
+    // returned by the ledger itself (eg. insufficient funds) are not
+    let (result, transient) =
+        match try_process_transaction_without_caller_check(transaction, WalletTransactionKind::StandingOrder).await {
+            Ok(completed) => (Ok(completed.block_index()), false),
+            Err(failure) => (Err(failure.transaction.error_message().to_string()), failure.transient),
+        };
+
+    mutate_state(|state| {
+        let now = state.env.now();
+
+        if let Err(error) = &result {
+            if transient && job.attempt + 1 < MAX_ATTEMPTS {
+                // Retry with the same `created` timestamp so that the ledger will deduplicate the
+                // transfer if the previous attempt actually succeeded
+                let retry_at = now + ((5 * SECOND_IN_MS) << job.attempt);
+                enqueue_job(
+                    job.standing_order_id,
+                    job.due,
+                    Some(created),
+                    job.attempt + 1,
+                    retry_at,
+                    state,
+                );
+                return;
+            }
+            error!(
+                standing_order_id = job.standing_order_id,
+                error, "Standing order payment failed"
+            );
+        }
+
+        record_execution(job.standing_order_id, job.due, job.attempt + 1, result, state);
+    });
+}
+
+fn prepare(job: &ExecuteStandingOrderJob, state: &mut RuntimeState) -> Option<(PendingCryptoTransaction, TimestampNanos)> {
+    let order = state.data.standing_orders.get(job.standing_order_id)?;
+    if order.next_payment_due != Some(job.due) {
+        // The standing order has been cancelled
+        return None;
+    }
+
+    let to = match &order.recipient {
+        StandingOrderRecipient::User(user_id) => Account::from(Principal::from(*user_id)),
+        StandingOrderRecipient::Account(account) => *account,
+    };
+    let created = job.created.unwrap_or_else(|| state.env.now_nanos());
+
+    let transaction = PendingCryptoTransaction::ICRC1(icrc1::PendingCryptoTransaction {
+        ledger: order.token.ledger,
+        token: order.token.token.clone(),
+        amount: order.amount,
+        to,
+        fee: order.token.fee,
+        memo: None,
+        created,
+    })
+    .set_memo(&MEMO_STANDING_ORDER);
+
+    if state.data.suspended.value {
+        record_execution(
+            job.standing_order_id,
+            job.due,
+            job.attempt,
+            Err("User suspended".to_string()),
+            state,
+        );
+        return None;
+    }
+
+    Some((transaction, created))
+}
+
+fn record_execution(
+    standing_order_id: u64,
+    due: TimestampMillis,
+    attempts: u32,
+    result: Result<u64, String>,
+    state: &mut RuntimeState,
+) {
+    let now = state.env.now();
+    if let Some(order) = state.data.standing_orders.get_mut(standing_order_id) {
+        let execution = StandingOrderExecution {
+            due,
+            timestamp: now,
+            attempts,
+            result,
+        };
+        if let Some(next_due) = order.record_execution(execution, now) {
+            enqueue_job(standing_order_id, next_due, None, 0, next_due, state);
+        }
+    }
+}
+
+fn enqueue_job(
+    standing_order_id: u64,
+    due: TimestampMillis,
+    created: Option<TimestampNanos>,
+    attempt: u32,
+    run_at: TimestampMillis,
+    state: &mut RuntimeState,
+) {
+    let now = state.env.now();
+    state.data.timer_jobs.enqueue_job(
+        TimerJob::ExecuteStandingOrder(Box::new(ExecuteStandingOrderJob {
+            standing_order_id,
+            due,
+            created,
+            attempt,
+        })),
+        run_at,
+        now,
+    );
+}
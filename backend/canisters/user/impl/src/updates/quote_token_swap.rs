@@ -0,0 +1,34 @@
+use crate::guards::caller_is_owner;
+use crate::token_swaps::get_quotes;
+use crate::{read_state, run_regular_jobs};
+use canister_tracing_macros::trace;
+use ic_cdk_macros::update;
+use user_canister::quote_token_swap::{Response::*, *};
+
+// This is an update call because the exchanges are hosted on other subnets
+#[update(guard = "caller_is_owner")]
+#[trace]
+async fn quote_token_swap(args: Args) -> Response {
+    run_regular_jobs();
+
+    let this_canister_id = read_state(|state| state.env.canister_id());
+
+    let quotes = get_quotes(
+        args.exchanges,
+        args.input_token,
+        args.output_token,
+        args.input_amount,
+        this_canister_id,
+    )
+    .await;
+
+    Success(SuccessResult {
+        quotes: quotes
+            .into_iter()
+            .map(|(exchange_args, amount_out)| Quote {
+                exchange_id: exchange_args.exchange_id(),
+                amount_out,
+            })
+            .collect(),
+    })
+}
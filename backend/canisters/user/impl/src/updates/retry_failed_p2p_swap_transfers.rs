@@ -0,0 +1,36 @@
+use crate::guards::caller_is_owner;
+use crate::timer_job_types::{ProcessP2PSwapTransferJob, TimerJob};
+use crate::{mutate_state, run_regular_jobs, RuntimeState};
+use canister_tracing_macros::trace;
+use ic_cdk_macros::update;
+use user_canister::retry_failed_p2p_swap_transfers::{Response::*, *};
+
+#[update(guard = "caller_is_owner")]
+#[trace]
+fn retry_failed_p2p_swap_transfers(_args: Args) -> Response {
+    run_regular_jobs();
+
+    mutate_state(retry_failed_p2p_swap_transfers_impl)
+}
+
+fn retry_failed_p2p_swap_transfers_impl(state: &mut RuntimeState) -> Response {
+    let now = state.env.now();
+    let failed = std::mem::take(&mut state.data.failed_p2p_swap_transfers);
+    let count = failed.len() as u32;
+
+    for job in failed {
+        // Each swap has its own escrow subaccount, so if a previous attempt actually succeeded then
+        // the retried transfer will fail due to insufficient funds rather than paying out twice
+        state.data.timer_jobs.enqueue_job(
+            TimerJob::ProcessP2PSwapTransfer(Box::new(ProcessP2PSwapTransferJob {
+                created: None,
+                attempt: 0,
+                ..job
+            })),
+            now,
+            now,
+        );
+    }
+
+    Success(count)
+}
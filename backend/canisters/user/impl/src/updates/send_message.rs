@@ -1,21 +1,25 @@
 use crate::crypto::process_transaction_without_caller_check;
 use crate::guards::caller_is_owner;
-use crate::timer_job_types::{DeleteFileReferencesJob, RemoveExpiredEventsJob, RetrySendingFailedMessagesJob};
+use crate::p2p_swaps::{escrow_account, escrow_deposit, validate_p2p_swap};
+use crate::timer_job_types::{
+    DeleteFileReferencesJob, P2PSwapExpiredJob, RemoveExpiredEventsJob, RetrySendingFailedMessagesJob,
+};
 use crate::{mutate_state, read_state, run_regular_jobs, RuntimeState, TimerJob};
 use candid::Principal;
 use canister_timer_jobs::TimerJobs;
 use canister_tracing_macros::trace;
-use chat_events::{PushMessageArgs, Reader};
+use chat_events::{MessageContentInternal, P2PSwapContentInternal, PushMessageArgs, Reader};
 use ic_cdk_macros::update;
 use rand::Rng;
 use tracing::error;
 use types::{
     BlobReference, CanisterId, CompletedCryptoTransaction, ContentValidationError, CryptoTransaction, EventWrapper, Message,
-    MessageContentInitial, MessageIndex, TimestampMillis, UserId,
+    MessageContent, MessageContentInitial, MessageIndex, TimestampMillis, UserId,
 };
 use user_canister::c2c_send_messages;
 use user_canister::c2c_send_messages::{C2CReplyContext, SendMessageArgs};
 use user_canister::send_message_v2::{Response::*, *};
+use user_canister::WalletTransactionKind;
 use utils::consts::{MEMO_MESSAGE, OPENCHAT_BOT_USER_ID};
 use utils::time::{MINUTE_IN_MS, SECOND_IN_MS};
 
@@ -66,16 +70,44 @@ async fn send_message_v2(mut args: Args) -> Response {
 
         // We have to use `process_transaction_without_caller_check` because we may be within a
         // reply callback due to calling `c2c_lookup_user` earlier.
-        completed_transfer = match process_transaction_without_caller_check(pending_transaction).await {
-            Ok(completed) => {
-                c.transfer = CryptoTransaction::Completed(completed.clone());
-                Some(completed)
-            }
-            Err(failed) => return TransferFailed(failed.error_message().to_string()),
-        };
+        completed_transfer =
+            match process_transaction_without_caller_check(pending_transaction, WalletTransactionKind::Message).await {
+                Ok(completed) => {
+                    c.transfer = CryptoTransaction::Completed(completed.clone());
+                    Some(completed)
+                }
+                Err(failed) => return TransferFailed(failed.error_message().to_string()),
+            };
+    }
+
+    let mut p2p_swap_id = None;
+    // If the message is a P2P swap offer, we transfer the offered funds into escrow before sending
+    // the message
+    if let MessageContentInitial::P2PSwap(p) = &args.content {
+        if !matches!(user_type, UserType::User) {
+            return InvalidRequest("P2P swaps can only be offered to other users".to_string());
+        }
+        let (swap_id, pending_transaction) = mutate_state(|state| {
+            let swap_id = state.data.next_p2p_swap_id;
+            state.data.next_p2p_swap_id += 1;
+            let escrow_account = escrow_account(my_user_id, swap_id);
+            (
+                swap_id,
+                escrow_deposit(&p.token0, p.token0_amount, escrow_account, state.env.now_nanos()),
+            )
+        });
+
+        completed_transfer =
+            match process_transaction_without_caller_check(pending_transaction, WalletTransactionKind::P2PSwap).await {
+                Ok(completed) => {
+                    p2p_swap_id = Some(swap_id);
+                    Some(completed)
+                }
+                Err(failed) => return TransferFailed(failed.error_message().to_string()),
+            };
     }
 
-    mutate_state(|state| send_message_impl(args, completed_transfer, user_type, state))
+    mutate_state(|state| send_message_impl(args, completed_transfer, p2p_swap_id, user_type, state))
 }
 
 enum UserType {
@@ -120,17 +152,29 @@ fn validate_request(args: &Args, state: &RuntimeState) -> ValidateRequestResult
         return ValidateRequestResult::Invalid(InvalidRequest("Cannot send a prize message in a direct chat".to_string()));
     }
 
+    if let MessageContentInitial::P2PSwap(p) = &args.content {
+        if let Err(error) = validate_p2p_swap(p) {
+            return ValidateRequestResult::Invalid(InvalidRequest(error));
+        }
+    }
+
     let my_user_id: UserId = state.env.canister_id().into();
     if let Err(error) = args.content.validate_for_new_direct_message(my_user_id, args.forwarding, now) {
         ValidateRequestResult::Invalid(match error {
             ContentValidationError::Empty => MessageEmpty,
             ContentValidationError::TextTooLong(max_length) => TextTooLong(max_length),
             ContentValidationError::InvalidPoll(reason) => InvalidPoll(reason),
+            ContentValidationError::InvalidCalendarEvent(reason) => {
+                InvalidRequest(format!("Invalid calendar event: {reason:?}"))
+            }
             ContentValidationError::TransferCannotBeZero => TransferCannotBeZero,
             ContentValidationError::InvalidTypeForForwarding => {
                 InvalidRequest("Cannot forward this type of message".to_string())
             }
             ContentValidationError::PrizeEndDateInThePast => unreachable!(),
+            ContentValidationError::InvalidPrizeEligibility => {
+                InvalidRequest("Payment gates cannot be used as prize eligibility rules".to_string())
+            }
             ContentValidationError::UnauthorizedToSendProposalMessages => {
                 InvalidRequest("User unauthorized to send proposal messages".to_string())
             }
@@ -139,7 +183,10 @@ fn validate_request(args: &Args, state: &RuntimeState) -> ValidateRequestResult
             }
         })
     } else if args.recipient == my_user_id {
-        if matches!(args.content, MessageContentInitial::Crypto(_)) {
+        if matches!(
+            args.content,
+            MessageContentInitial::Crypto(_) | MessageContentInitial::P2PSwap(_)
+        ) {
             ValidateRequestResult::Invalid(TransferCannotBeToSelf)
         } else {
             ValidateRequestResult::Valid(my_user_id, UserType::_Self)
@@ -155,6 +202,7 @@ fn validate_request(args: &Args, state: &RuntimeState) -> ValidateRequestResult
 fn send_message_impl(
     args: Args,
     completed_transfer: Option<CompletedCryptoTransaction>,
+    p2p_swap_id: Option<u32>,
     user_type: UserType,
     state: &mut RuntimeState,
 ) -> Response {
@@ -162,11 +210,18 @@ fn send_message_impl(
     let my_user_id = state.env.canister_id().into();
     let recipient = args.recipient;
 
+    let content = match (&args.content, p2p_swap_id, &completed_transfer) {
+        (MessageContentInitial::P2PSwap(p), Some(swap_id), Some(transfer)) => {
+            MessageContentInternal::P2PSwap(P2PSwapContentInternal::new(swap_id, p.clone(), transfer.block_index(), now))
+        }
+        _ => args.content.clone().into(),
+    };
+
     let push_message_args = PushMessageArgs {
         thread_root_message_index: None,
         message_id: args.message_id,
         sender: my_user_id,
-        content: args.content.clone().into(),
+        content,
         mentioned: Vec::new(),
         replies_to: args.replies_to.as_ref().map(|r| r.into()),
         forwarded: args.forwarding,
@@ -195,11 +250,24 @@ fn send_message_impl(
         &mut state.data.timer_jobs,
     );
 
+    if let MessageContent::P2PSwap(p) = &message_event.event.content {
+        state.data.timer_jobs.enqueue_job(
+            TimerJob::P2PSwapExpired(Box::new(P2PSwapExpiredJob {
+                them: recipient,
+                message_id: args.message_id,
+            })),
+            p.expires_at,
+            now,
+        );
+    }
+
     if !user_type.is_self() {
         let send_message_args = SendMessageArgs {
             message_id: args.message_id,
             sender_message_index: message_event.event.message_index,
-            content: args.content.into(),
+            // P2P swaps are sent in their hydrated form since the initial form doesn't include the
+            // swap id or the escrow transfer
+            content: if p2p_swap_id.is_some() { message_event.event.content.clone() } else { args.content.into() },
             replies_to: args.replies_to.and_then(|r| {
                 if let Some((chat, thread_root_message_index)) = r.chat_if_other {
                     Some(C2CReplyContext::OtherChat(chat, thread_root_message_index, r.event_index))
@@ -314,7 +382,12 @@ async fn send_to_bot_canister(recipient: UserId, message_index: MessageIndex, ar
         Ok(bot_api::handle_direct_message::Response::Success(result)) => {
             mutate_state(|state| {
                 let now = state.env.now();
-                for message in result.messages {
+                // Bots can't offer P2P swaps since they have no escrow
+                for message in result
+                    .messages
+                    .into_iter()
+                    .filter(|m| !matches!(m.content, MessageContentInitial::P2PSwap(_)))
+                {
                     let push_message_args = PushMessageArgs {
                         sender: recipient,
                         thread_root_message_index: None,
@@ -11,6 +11,7 @@ use types::{
 };
 use user_canister::send_message_with_transfer_to_channel;
 use user_canister::send_message_with_transfer_to_group;
+use user_canister::WalletTransactionKind;
 use utils::consts::{MEMO_MESSAGE, MEMO_PRIZE};
 
 #[update(guard = "caller_is_owner")]
@@ -39,7 +40,7 @@ async fn send_message_with_transfer_to_channel(
     };
 
     // Make the crypto transfer
-    let completed_transaction = match process_transaction(pending_transaction).await {
+    let completed_transaction = match process_transaction(pending_transaction, wallet_transaction_kind(&args.content)).await {
         Ok(completed) => completed,
         Err(failed) => return TransferFailed(failed.error_message().to_string()),
     };
@@ -60,6 +61,7 @@ async fn send_message_with_transfer_to_channel(
         forwarding: false,
         community_rules_accepted: args.community_rules_accepted,
         channel_rules_accepted: args.channel_rules_accepted,
+        forum_post: args.forum_post,
     };
 
     // Send the message to the community
@@ -79,6 +81,7 @@ async fn send_message_with_transfer_to_channel(
             send_message::Response::CommunityFrozen => CommunityFrozen,
             send_message::Response::RulesNotAccepted => RulesNotAccepted,
             send_message::Response::CommunityRulesNotAccepted => CommunityRulesNotAccepted,
+            send_message::Response::ForumPostLocked => InternalError("Forum post is locked".to_string(), completed_transaction),
             send_message::Response::MessageEmpty
             | send_message::Response::InvalidPoll(_)
             | send_message::Response::NotAuthorized
@@ -117,7 +120,7 @@ async fn send_message_with_transfer_to_group(
     };
 
     // Make the crypto transfer
-    let completed_transaction = match process_transaction(pending_transaction).await {
+    let completed_transaction = match process_transaction(pending_transaction, wallet_transaction_kind(&args.content)).await {
         Ok(completed) => completed,
         Err(failed) => return TransferFailed(failed.error_message().to_string()),
     };
@@ -204,6 +207,9 @@ fn prepare(content: &MessageContentInitial, state: &RuntimeState) -> PrepareResu
             if c.end_date <= now {
                 return InvalidRequest("Prize end date must be in the future".to_string());
             }
+            if !c.eligibility.iter().all(|e| e.is_valid()) {
+                return InvalidRequest("Payment gates cannot be used as prize eligibility rules".to_string());
+            }
             match &c.transfer {
                 CryptoTransaction::Pending(t) => {
                     let total_prize = c.prizes.iter().map(|t| t.e8s()).sum::<u64>() as u128;
@@ -229,6 +235,14 @@ fn prepare(content: &MessageContentInitial, state: &RuntimeState) -> PrepareResu
     }
 }
 
+fn wallet_transaction_kind(content: &MessageContentInitial) -> WalletTransactionKind {
+    if matches!(content, MessageContentInitial::Prize(_)) {
+        WalletTransactionKind::Prize
+    } else {
+        WalletTransactionKind::Message
+    }
+}
+
 fn transform_content_with_completed_transaction(
     content: MessageContentInitial,
     completed_transaction: CompletedCryptoTransaction,
@@ -246,6 +260,8 @@ fn transform_content_with_completed_transaction(
             end_date: c.end_date,
             caption: c.caption,
             diamond_only: c.diamond_only,
+            distribution: c.distribution,
+            eligibility: c.eligibility,
         }),
         _ => unreachable!("Message must include a crypto transfer"),
     }
@@ -0,0 +1,47 @@
+use crate::guards::caller_is_owner;
+use crate::proposal_reminders::{build_refresh_args, refresh_unvoted_proposals, schedule_next_refresh, RefreshArgs};
+use crate::{mutate_state, run_regular_jobs, RuntimeState};
+use canister_tracing_macros::trace;
+use ic_cdk_macros::update;
+use types::Milliseconds;
+use user_canister::set_proposal_reminders::{Response::*, *};
+use utils::time::{DAY_IN_MS, MINUTE_IN_MS};
+
+const MIN_NOTIFY_BEFORE: Milliseconds = 15 * MINUTE_IN_MS;
+const MAX_NOTIFY_BEFORE: Milliseconds = 3 * DAY_IN_MS;
+
+#[update(guard = "caller_is_owner")]
+#[trace]
+async fn set_proposal_reminders(args: Args) -> Response {
+    run_regular_jobs();
+
+    let refresh_args = match mutate_state(|state| set_proposal_reminders_impl(args, state)) {
+        Ok(Some(refresh_args)) => refresh_args,
+        Ok(None) => return Success,
+        Err(response) => return response,
+    };
+
+    // Refresh straight away so that reminders for proposals which are already closing soon are
+    // scheduled without waiting for the next refresh
+    refresh_unvoted_proposals(refresh_args).await;
+    Success
+}
+
+fn set_proposal_reminders_impl(args: Args, state: &mut RuntimeState) -> Result<Option<RefreshArgs>, Response> {
+    if let Some(notify_before) = args.notify_before {
+        if !(MIN_NOTIFY_BEFORE..=MAX_NOTIFY_BEFORE).contains(&notify_before) {
+            return Err(NotifyBeforeOutOfRange(NotifyBeforeRange {
+                min: MIN_NOTIFY_BEFORE,
+                max: MAX_NOTIFY_BEFORE,
+            }));
+        }
+    }
+
+    state.data.unvoted_proposals.set_notify_before(args.notify_before);
+    schedule_next_refresh(state);
+
+    Ok(args
+        .notify_before
+        .is_some()
+        .then(|| build_refresh_args(state.env.now(), &state.data)))
+}
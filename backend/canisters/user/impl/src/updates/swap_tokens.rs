@@ -1,22 +1,28 @@
 use crate::guards::caller_is_owner;
 use crate::model::token_swaps::TokenSwap;
+use crate::model::wallet_transactions::WalletTransactionEntry;
 use crate::timer_job_types::{ProcessTokenSwapJob, TimerJob};
-use crate::token_swaps::swap_client::SwapClient;
-use crate::{mutate_state, read_state, run_regular_jobs, Data, RuntimeState};
+use crate::token_swaps::{build_swap_client, get_quotes};
+use crate::{mutate_state, read_state, run_regular_jobs, Data};
 use canister_tracing_macros::trace;
 use ic_cdk_macros::update;
-use icpswap_client::ICPSwapClient;
 use icrc_ledger_types::icrc1::transfer::TransferArg;
+use tracing::info;
 use types::{TimestampMillis, Timestamped};
 use user_canister::swap_tokens::{Response::*, *};
+use user_canister::{WalletCounterparty, WalletTransactionDirection, WalletTransactionKind};
 use utils::consts::MEMO_SWAP;
 use utils::time::{NANOS_PER_MILLISECOND, SECOND_IN_MS};
 
 #[update(guard = "caller_is_owner")]
 #[trace]
-async fn swap_tokens(args: Args) -> Response {
+async fn swap_tokens(mut args: Args) -> Response {
     run_regular_jobs();
 
+    if let Some(alternative_exchanges) = args.alternative_exchanges.clone() {
+        args.exchange_args = select_exchange_with_best_quote(&args, alternative_exchanges).await;
+    }
+
     let token_swap = mutate_state(|state| {
         let now = state.env.now();
         state.data.token_swaps.push_new(args, now)
@@ -27,7 +33,13 @@ async fn swap_tokens(args: Args) -> Response {
 
 pub(crate) async fn process_token_swap(mut token_swap: TokenSwap, attempt: u32) -> Response {
     let args = token_swap.args.clone();
-    let swap_client = read_state(|state| build_swap_client(&args, state));
+    let this_canister_id = read_state(|state| state.env.canister_id());
+    let swap_client = build_swap_client(
+        &args.exchange_args,
+        args.input_token.clone(),
+        args.output_token.clone(),
+        this_canister_id,
+    );
 
     let account = if let Some(a) = extract_result(&token_swap.deposit_account) {
         *a
@@ -78,8 +90,19 @@ pub(crate) async fn process_token_swap(mut token_swap: TokenSwap, attempt: u32)
             Ok(index) => {
                 mutate_state(|state| {
                     let now = state.env.now();
-                    token_swap.transfer = Some(Timestamped::new(Ok(index.0.try_into().unwrap()), now));
+                    let block_index = index.0.try_into().unwrap();
+                    token_swap.transfer = Some(Timestamped::new(Ok(block_index), now));
                     state.data.token_swaps.upsert(token_swap.clone());
+                    state.data.wallet_transactions.record(WalletTransactionEntry {
+                        timestamp: now,
+                        kind: WalletTransactionKind::Swap,
+                        direction: WalletTransactionDirection::Outgoing,
+                        ledger: args.input_token.ledger,
+                        amount: args.input_amount,
+                        fee: Some(args.input_token.fee),
+                        counterparty: Some(WalletCounterparty::Account(account)),
+                        block_index: Some(block_index),
+                    });
                 });
             }
             Err(msg) => {
@@ -159,6 +182,16 @@ pub(crate) async fn process_token_swap(mut token_swap: TokenSwap, attempt: u32)
                 token_swap.withdrawn_from_dex_at = Some(Timestamped::new(Ok(()), now));
                 token_swap.success = Some(Timestamped::new(true, now));
                 state.data.token_swaps.upsert(token_swap);
+                state.data.wallet_transactions.record(WalletTransactionEntry {
+                    timestamp: now,
+                    kind: WalletTransactionKind::Swap,
+                    direction: WalletTransactionDirection::Incoming,
+                    ledger: args.output_token.ledger,
+                    amount: amount_out,
+                    fee: None,
+                    counterparty: Some(WalletCounterparty::Canister(args.exchange_args.swap_canister_id())),
+                    block_index: None,
+                });
             });
         }
     }
@@ -166,22 +199,31 @@ pub(crate) async fn process_token_swap(mut token_swap: TokenSwap, attempt: u32)
     Success(SuccessResult { amount_out })
 }
 
-fn build_swap_client(args: &Args, state: &RuntimeState) -> Box<dyn SwapClient> {
-    let this_canister_id = state.env.canister_id();
-    let input_token = args.input_token.clone();
-    let output_token = args.output_token.clone();
-
-    match &args.exchange_args {
-        ExchangeArgs::ICPSwap(icpswap) => {
-            let (token0, token1) = if icpswap.zero_for_one { (input_token, output_token) } else { (output_token, input_token) };
-            Box::new(ICPSwapClient::new(
-                this_canister_id,
-                icpswap.swap_canister_id,
-                token0,
-                token1,
-                icpswap.zero_for_one,
-            ))
-        }
+// If none of the exchanges return a quote, we fall back to the exchange specified in `exchange_args`
+async fn select_exchange_with_best_quote(args: &Args, alternative_exchanges: Vec<ExchangeArgs>) -> ExchangeArgs {
+    let this_canister_id = read_state(|state| state.env.canister_id());
+    let exchanges = std::iter::once(args.exchange_args.clone())
+        .chain(alternative_exchanges)
+        .collect();
+
+    let quotes = get_quotes(
+        exchanges,
+        args.input_token.clone(),
+        args.output_token.clone(),
+        args.input_amount,
+        this_canister_id,
+    )
+    .await;
+
+    if let Some((exchange_args, amount_out)) = quotes
+        .into_iter()
+        .filter_map(|(e, r)| r.ok().map(|a| (e, a)))
+        .max_by_key(|(_, a)| *a)
+    {
+        info!(swap_id = args.swap_id, exchange_id = %exchange_args.exchange_id(), amount_out, "Selected exchange with best quote");
+        exchange_args
+    } else {
+        args.exchange_args.clone()
     }
 }
 
@@ -8,6 +8,7 @@ use ic_cdk_macros::update;
 use serde::Serialize;
 use types::{icrc1, CanisterId, Chat, ChatId, CommunityId, EventIndex, PendingCryptoTransaction, TimestampNanos, UserId};
 use user_canister::tip_message::{Response::*, *};
+use user_canister::WalletTransactionKind;
 use utils::consts::MEMO_TIP;
 
 #[update(guard = "caller_is_owner")]
@@ -30,7 +31,7 @@ async fn tip_message(args: Args) -> Response {
         created: now_nanos,
     });
     // Make the crypto transfer
-    if let Err(failed) = process_transaction(pending_transfer).await {
+    if let Err(failed) = process_transaction(pending_transfer, WalletTransactionKind::Tip).await {
         return TransferFailed(failed.error_message().to_string());
     }
 
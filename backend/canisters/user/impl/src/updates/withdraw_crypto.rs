@@ -4,6 +4,7 @@ use crate::run_regular_jobs;
 use canister_tracing_macros::trace;
 use ic_cdk_macros::update;
 use user_canister::withdraw_crypto_v2::{Response::*, *};
+use user_canister::WalletTransactionKind;
 use utils::consts::MEMO_SEND;
 
 #[update(guard = "caller_is_owner")]
@@ -11,7 +12,7 @@ use utils::consts::MEMO_SEND;
 async fn withdraw_crypto_v2(args: Args) -> Response {
     run_regular_jobs();
 
-    match process_transaction(args.withdrawal.set_memo(&MEMO_SEND)).await {
+    match process_transaction(args.withdrawal.set_memo(&MEMO_SEND), WalletTransactionKind::Withdrawal).await {
         Ok(completed_withdrawal) => Success(completed_withdrawal),
         Err(failed_withdrawal) => TransactionFailed(failed_withdrawal),
     }
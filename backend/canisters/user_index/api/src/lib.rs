@@ -1,6 +1,9 @@
-use candid::Principal;
+use candid::{CandidType, Principal};
 use serde::{Deserialize, Serialize};
-use types::{ChannelLatestMessageIndex, ChatId, CommunityId, MessageContent, MessageIndex, UserId};
+use types::{
+    ChannelLatestMessageIndex, ChatId, CommunityId, Cryptocurrency, DiamondMembershipPlanDuration, MessageContent,
+    MessageIndex, TimestampMillis, UserId,
+};
 
 mod lifecycle;
 mod queries;
@@ -26,6 +29,8 @@ pub struct UserRegistered {
     pub username: String,
     pub display_name: Option<String>,
     pub referred_by: Option<UserId>,
+    #[serde(default)]
+    pub referral_campaign: Option<String>,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug)]
@@ -53,3 +58,37 @@ pub struct OpenChatBotMessage {
     pub user_id: UserId,
     pub message: MessageContent,
 }
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct DiamondMembershipSeats {
+    pub purchased_by: UserId,
+    pub timestamp: TimestampMillis,
+    pub duration: DiamondMembershipPlanDuration,
+    pub token: Cryptocurrency,
+    pub seats: u32,
+    pub remaining: u32,
+}
+
+// Users can appeal against their current suspension or against a report of one of their messages
+// being upheld, where the report is identified by its index
+#[derive(CandidType, Serialize, Deserialize, Clone, Copy, Debug, Eq, PartialEq)]
+pub enum AppealSubject {
+    Suspension,
+    ReportedMessage(u64),
+}
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Copy, Debug, Eq, PartialEq)]
+pub enum AppealOutcome {
+    Upheld,
+    Overturned,
+}
+
+// Determines where reported messages are sent to be moderated. When both are used, whichever
+// reaches a decision first determines the outcome.
+#[derive(CandidType, Serialize, Deserialize, Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum ModerationMode {
+    #[default]
+    Modclub,
+    Local,
+    Both,
+}
@@ -15,6 +15,7 @@ pub struct Args {
     pub cycles_dispenser_canister_id: CanisterId,
     pub storage_index_canister_id: CanisterId,
     pub internet_identity_canister_id: CanisterId,
+    pub online_users_canister_id: CanisterId,
     pub wasm_version: BuildVersion,
     pub test_mode: bool,
 }
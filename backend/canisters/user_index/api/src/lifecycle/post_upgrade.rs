@@ -1,8 +1,10 @@
 use candid::CandidType;
 use serde::{Deserialize, Serialize};
-use types::BuildVersion;
+use types::{BuildVersion, CanisterId};
 
 #[derive(CandidType, Serialize, Deserialize, Debug)]
 pub struct Args {
     pub wasm_version: BuildVersion,
+    #[serde(default)]
+    pub online_users_canister_id: Option<CanisterId>,
 }
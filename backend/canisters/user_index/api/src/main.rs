@@ -2,12 +2,16 @@ use candid_gen::generate_candid_method;
 
 #[allow(deprecated)]
 fn main() {
+    generate_candid_method!(user_index, appeals, query);
     generate_candid_method!(user_index, check_username, query);
     generate_candid_method!(user_index, current_user, query);
     generate_candid_method!(user_index, diamond_membership_fees, query);
+    generate_candid_method!(user_index, diamond_membership_seats, query);
+    generate_candid_method!(user_index, moderation_queue, query);
     generate_candid_method!(user_index, platform_moderators, query);
     generate_candid_method!(user_index, platform_moderators_group, query);
     generate_candid_method!(user_index, platform_operators, query);
+    generate_candid_method!(user_index, referral_campaigns, query);
     generate_candid_method!(user_index, referral_leaderboard, query);
     generate_candid_method!(user_index, referral_metrics, query);
     generate_candid_method!(user_index, search, query);
@@ -21,14 +25,20 @@ fn main() {
     generate_candid_method!(user_index, add_platform_operator, update);
     generate_candid_method!(user_index, add_referral_codes, update);
     generate_candid_method!(user_index, assign_platform_moderators_group, update);
+    generate_candid_method!(user_index, decide_appeal, update);
+    generate_candid_method!(user_index, gift_diamond_membership, update);
     generate_candid_method!(user_index, mark_suspected_bot, update);
     generate_candid_method!(user_index, pay_for_diamond_membership, update);
+    generate_candid_method!(user_index, pay_for_diamond_membership_seats, update);
     generate_candid_method!(user_index, remove_platform_moderator, update);
     generate_candid_method!(user_index, remove_platform_operator, update);
     generate_candid_method!(user_index, set_display_name, update);
     generate_candid_method!(user_index, set_user_upgrade_concurrency, update);
     generate_candid_method!(user_index, set_moderation_flags, update);
+    generate_candid_method!(user_index, set_moderation_mode, update);
     generate_candid_method!(user_index, set_username, update);
+    generate_candid_method!(user_index, submit_appeal, update);
+    generate_candid_method!(user_index, submit_moderation_decision, update);
     generate_candid_method!(user_index, suspend_user, update);
     generate_candid_method!(user_index, unsuspend_user, update);
 
@@ -0,0 +1,32 @@
+use crate::{AppealOutcome, AppealSubject};
+use candid::CandidType;
+use serde::{Deserialize, Serialize};
+use types::{TimestampMillis, UserId};
+
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub struct Args {
+    pub pending_only: bool,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub enum Response {
+    Success(Vec<AppealSummary>),
+}
+
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub struct AppealSummary {
+    pub appeal_id: u32,
+    pub user_id: UserId,
+    pub subject: AppealSubject,
+    pub text: String,
+    pub submitted: TimestampMillis,
+    pub decision: Option<AppealDecision>,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct AppealDecision {
+    pub outcome: AppealOutcome,
+    pub decided_by: UserId,
+    pub notes: Option<String>,
+    pub timestamp: TimestampMillis,
+}
@@ -0,0 +1,20 @@
+use crate::DiamondMembershipSeats;
+use candid::CandidType;
+use serde::{Deserialize, Serialize};
+use types::CommunityId;
+
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub struct Args {
+    pub community_id: CommunityId,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub enum Response {
+    Success(SuccessResult),
+}
+
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub struct SuccessResult {
+    pub seats_available: u32,
+    pub purchases: Vec<DiamondMembershipSeats>,
+}
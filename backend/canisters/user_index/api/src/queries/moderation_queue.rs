@@ -0,0 +1,33 @@
+use crate::ModerationMode;
+use candid::CandidType;
+use serde::{Deserialize, Serialize};
+use types::{Chat, Empty, MessageIndex, TimestampMillis, UserId};
+
+pub type Args = Empty;
+
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub enum Response {
+    Success(SuccessResult),
+}
+
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub struct SuccessResult {
+    pub mode: ModerationMode,
+    pub local_quorum: u32,
+    pub rules: Vec<String>,
+    pub reports: Vec<PendingReport>,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub struct PendingReport {
+    pub report_index: u64,
+    pub chat_id: Chat,
+    pub thread_root_message_index: Option<MessageIndex>,
+    pub message_index: MessageIndex,
+    pub message_link: String,
+    pub sender: UserId,
+    pub reported_by: u32,
+    pub first_reported: TimestampMillis,
+    pub votes: u32,
+    pub voted: bool,
+}
@@ -0,0 +1,32 @@
+use candid::CandidType;
+use serde::{Deserialize, Serialize};
+use types::{Empty, ReferralCampaignTarget, TimestampMillis};
+
+pub type Args = Empty;
+
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub enum Response {
+    Success(Vec<ReferralCampaignSummary>),
+}
+
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub struct ReferralCampaignSummary {
+    pub code: String,
+    pub name: String,
+    pub target: ReferralCampaignTarget,
+    pub created: TimestampMillis,
+    pub expires: Option<TimestampMillis>,
+    pub max_uses: Option<u32>,
+    pub ended: bool,
+    pub registrations: u32,
+    pub diamond_upgrades: u32,
+    pub retention_7_days: RetentionStats,
+    pub retention_30_days: RetentionStats,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Debug, Default)]
+pub struct RetentionStats {
+    // The number of users who registered long enough ago for their retention to have been measured
+    pub measured: u32,
+    pub retained: u32,
+}
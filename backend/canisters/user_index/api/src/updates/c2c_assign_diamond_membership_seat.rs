@@ -0,0 +1,25 @@
+use crate::pay_for_diamond_membership::CannotExtendResult;
+use candid::CandidType;
+use serde::{Deserialize, Serialize};
+use types::{TimestampMillis, UserId};
+
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub struct Args {
+    pub user_id: UserId,
+    pub assigned_by: UserId,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub enum Response {
+    Success(SuccessResult),
+    NoSeatsAvailable,
+    UserNotFound,
+    CannotExtend(CannotExtendResult),
+    PaymentAlreadyInProgress,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub struct SuccessResult {
+    pub expires_at: TimestampMillis,
+    pub seats_remaining: u32,
+}
@@ -0,0 +1,24 @@
+use serde::{Deserialize, Serialize};
+use types::{ReferralCampaignTarget, TimestampMillis, UserId};
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Args {
+    pub code: String,
+    pub name: String,
+    pub target: ReferralCampaignTarget,
+    pub created_by: UserId,
+    pub expires: Option<TimestampMillis>,
+    pub max_uses: Option<u32>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub enum Response {
+    Success,
+    CodeInvalid,
+    CodeTaken,
+    NameTooShort(u32),
+    NameTooLong(u32),
+    ExpiryInPast,
+    InvalidMaxUses,
+    NotAuthorized,
+}
@@ -0,0 +1,17 @@
+use crate::AppealOutcome;
+use candid::CandidType;
+use serde::{Deserialize, Serialize};
+
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub struct Args {
+    pub appeal_id: u32,
+    pub outcome: AppealOutcome,
+    pub notes: Option<String>,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub enum Response {
+    Success,
+    AppealNotFound,
+    AlreadyDecided,
+}
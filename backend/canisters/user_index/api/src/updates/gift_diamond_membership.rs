@@ -0,0 +1,35 @@
+use crate::pay_for_diamond_membership::CannotExtendResult;
+use candid::CandidType;
+use serde::{Deserialize, Serialize};
+use types::{Cryptocurrency, DiamondMembershipPlanDuration, TimestampMillis, UserId};
+
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub struct Args {
+    pub recipient: UserId,
+    pub duration: DiamondMembershipPlanDuration,
+    pub token: Cryptocurrency,
+    pub expected_price_e8s: u64,
+    pub message: Option<String>,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub enum Response {
+    Success(SuccessResult),
+    CannotGiftToSelf,
+    RecipientNotFound,
+    RecipientIsBot,
+    CannotExtend(CannotExtendResult),
+    CurrencyNotSupported,
+    PriceMismatch,
+    PaymentAlreadyInProgress,
+    MessageTooLong(u32),
+    UserNotFound,
+    InsufficientFunds(u64), // Returns the account balance in e8s
+    TransferFailed(String),
+    InternalError(String),
+}
+
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub struct SuccessResult {
+    pub recipient_expires_at: TimestampMillis,
+}
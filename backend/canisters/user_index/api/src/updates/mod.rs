@@ -3,26 +3,35 @@ pub mod add_platform_moderator;
 pub mod add_platform_operator;
 pub mod add_referral_codes;
 pub mod assign_platform_moderators_group;
+pub mod c2c_assign_diamond_membership_seat;
+pub mod c2c_create_referral_campaign;
 pub mod c2c_migrate_user_principal;
 pub mod c2c_notify_events;
 pub mod c2c_notify_low_balance;
 pub mod c2c_register_bot;
 pub mod c2c_report_message;
+pub mod c2c_send_openchat_bot_messages;
 pub mod c2c_set_avatar;
 pub mod c2c_suspend_users;
 pub mod create_challenge;
+pub mod decide_appeal;
+pub mod gift_diamond_membership;
 pub mod mark_local_user_index_full;
 pub mod mark_suspected_bot;
 pub mod modclub_callback;
 pub mod pay_for_diamond_membership;
+pub mod pay_for_diamond_membership_seats;
 pub mod remove_platform_moderator;
 pub mod remove_platform_operator;
 pub mod remove_sms_messages;
 pub mod set_display_name;
 pub mod set_max_concurrent_user_canister_upgrades;
 pub mod set_moderation_flags;
+pub mod set_moderation_mode;
 pub mod set_user_upgrade_concurrency;
 pub mod set_username;
+pub mod submit_appeal;
+pub mod submit_moderation_decision;
 pub mod suspend_user;
 pub mod unsuspend_user;
 pub mod upgrade_local_user_index_canister_wasm;
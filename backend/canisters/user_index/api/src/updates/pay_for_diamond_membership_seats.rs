@@ -0,0 +1,32 @@
+use candid::CandidType;
+use serde::{Deserialize, Serialize};
+use types::{CommunityId, Cryptocurrency, DiamondMembershipPlanDuration};
+
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub struct Args {
+    pub community_id: CommunityId,
+    pub seats: u32,
+    pub duration: DiamondMembershipPlanDuration,
+    pub token: Cryptocurrency,
+    // The total price for all of the seats
+    pub expected_price_e8s: u64,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub enum Response {
+    Success(SuccessResult),
+    InvalidSeatCount(u32), // Returns the max number of seats which can be purchased at once
+    CurrencyNotSupported,
+    PriceMismatch,
+    UserNotFound,
+    CommunityNotFound,
+    NotCommunityMember,
+    InsufficientFunds(u64), // Returns the account balance in e8s
+    TransferFailed(String),
+    InternalError(String),
+}
+
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub struct SuccessResult {
+    pub seats_available: u32,
+}
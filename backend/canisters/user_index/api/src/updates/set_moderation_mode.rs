@@ -0,0 +1,16 @@
+use crate::ModerationMode;
+use candid::CandidType;
+use serde::{Deserialize, Serialize};
+
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub struct Args {
+    pub mode: ModerationMode,
+    // The number of platform moderators who must vote on a report before it is decided locally
+    pub local_quorum: Option<u32>,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub enum Response {
+    Success,
+    InvalidQuorum,
+}
@@ -0,0 +1,27 @@
+use crate::AppealSubject;
+use candid::CandidType;
+use serde::{Deserialize, Serialize};
+
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub struct Args {
+    pub subject: AppealSubject,
+    pub text: String,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub enum Response {
+    Success(SuccessResult),
+    TextEmpty,
+    TextTooLong(u32),
+    NotSuspended,
+    ReportNotFound,
+    NotMessageSender,
+    MessageNotJudgedAsViolating,
+    AlreadyAppealed,
+    UserNotFound,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub struct SuccessResult {
+    pub appeal_id: u32,
+}
@@ -0,0 +1,26 @@
+use candid::CandidType;
+use serde::{Deserialize, Serialize};
+
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub struct Args {
+    pub report_index: u64,
+    pub breaks_rules: bool,
+    // Indexes into the list of rules returned by `moderation_queue`
+    pub violated_rules: Vec<u32>,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub enum Response {
+    Success(SuccessResult),
+    ReportNotFound,
+    OutcomeAlreadyDecided,
+    AlreadyVoted,
+    InvalidRule(u32),
+    LocalModerationDisabled,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub struct SuccessResult {
+    // True if this vote completed the quorum and so the outcome has been decided
+    pub decided: bool,
+}
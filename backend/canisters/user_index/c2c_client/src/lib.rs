@@ -9,9 +9,12 @@ generate_candid_c2c_call!(platform_moderators_group);
 generate_c2c_call!(user);
 
 // Updates
+generate_c2c_call!(c2c_assign_diamond_membership_seat);
+generate_c2c_call!(c2c_create_referral_campaign);
 generate_c2c_call!(c2c_report_message);
 generate_c2c_call!(c2c_migrate_user_principal);
 generate_c2c_call!(c2c_notify_events);
+generate_c2c_call!(c2c_send_openchat_bot_messages);
 generate_candid_c2c_call_with_payment!(c2c_register_bot);
 generate_c2c_call!(c2c_set_avatar);
 generate_c2c_call!(c2c_suspend_users);
@@ -40,6 +40,14 @@ pub fn caller_is_group_index() -> Result<(), String> {
     }
 }
 
+pub fn caller_is_cycles_dispenser() -> Result<(), String> {
+    if read_state(|state| state.is_caller_cycles_dispenser()) {
+        Ok(())
+    } else {
+        Err("Caller is not the cycles dispenser".to_string())
+    }
+}
+
 pub fn caller_is_platform_moderator() -> Result<(), String> {
     if read_state(|state| state.is_caller_platform_moderator()) {
         Ok(())
@@ -1,4 +1,7 @@
+use crate::model::appeals::Appeals;
+use crate::model::diamond_membership_seats::DiamondMembershipSeatsMap;
 use crate::model::local_user_index_map::LocalUserIndex;
+use crate::model::referral_campaigns::ReferralCampaigns;
 use crate::model::storage_index_user_sync_queue::OpenStorageUserSyncQueue;
 use crate::model::user_map::UserMap;
 use crate::model::user_principal_migration_queue::UserPrincipalMigrationQueue;
@@ -19,6 +22,7 @@ use std::collections::{HashMap, HashSet};
 use types::{
     BuildVersion, CanisterId, CanisterWasm, ChatId, Cryptocurrency, Cycles, Milliseconds, TimestampMillis, Timestamped, UserId,
 };
+use user_index_canister::ModerationMode;
 use utils::canister::{CanistersRequiringUpgrade, FailedUpgradeCount};
 use utils::canister_event_sync_queue::CanisterEventSyncQueue;
 use utils::consts::DEV_TEAM_DFX_PRINCIPAL;
@@ -40,6 +44,7 @@ const USER_CANISTER_TOP_UP_AMOUNT: Cycles = 100_000_000_000; // 0.1T cycles
 const TIME_UNTIL_SUSPENDED_ACCOUNT_IS_DELETED_MILLIS: Milliseconds = DAY_IN_MS * 90; // 90 days
 const ONE_MB: u64 = 1024 * 1024;
 const ONE_GB: u64 = 1024 * ONE_MB;
+const DEFAULT_LOCAL_MODERATION_QUORUM: u32 = 3;
 
 thread_local! {
     static WASM_VERSION: RefCell<Timestamped<BuildVersion>> = RefCell::default();
@@ -82,6 +87,11 @@ impl RuntimeState {
         caller == self.data.group_index_canister_id
     }
 
+    pub fn is_caller_cycles_dispenser(&self) -> bool {
+        let caller = self.env.caller();
+        caller == self.data.cycles_dispenser_canister_id
+    }
+
     pub fn is_caller_platform_moderator(&self) -> bool {
         let caller = self.env.caller();
         if let Some(user) = self.data.users.get_by_principal(&caller) {
@@ -177,6 +187,7 @@ impl RuntimeState {
                 proposals_bot: self.data.proposals_bot_canister_id,
                 cycles_dispenser: self.data.cycles_dispenser_canister_id,
                 internet_identity: self.data.internet_identity_canister_id,
+                online_users: self.data.online_users_canister_id,
             },
             pending_modclub_submissions: self.data.pending_modclub_submissions_queue.len(),
             reporting_metrics: self.data.reported_messages.metrics(),
@@ -218,6 +229,22 @@ struct Data {
     pub fire_and_forget_handler: FireAndForgetHandler,
     #[serde(default)]
     pub rng_seed: [u8; 32],
+    #[serde(default)]
+    pub diamond_membership_seats: DiamondMembershipSeatsMap,
+    #[serde(default = "Principal::anonymous")]
+    pub online_users_canister_id: CanisterId,
+    #[serde(default)]
+    pub referral_campaigns: ReferralCampaigns,
+    #[serde(default)]
+    pub appeals: Appeals,
+    #[serde(default)]
+    pub moderation_mode: ModerationMode,
+    #[serde(default = "local_moderation_quorum")]
+    pub local_moderation_quorum: u32,
+}
+
+fn local_moderation_quorum() -> u32 {
+    DEFAULT_LOCAL_MODERATION_QUORUM
 }
 
 impl Data {
@@ -232,6 +259,7 @@ impl Data {
         cycles_dispenser_canister_id: CanisterId,
         storage_index_canister_id: CanisterId,
         internet_identity_canister_id: CanisterId,
+        online_users_canister_id: CanisterId,
         test_mode: bool,
     ) -> Self {
         let mut data = Data {
@@ -266,6 +294,12 @@ impl Data {
             reported_messages: ReportedMessages::default(),
             fire_and_forget_handler: FireAndForgetHandler::default(),
             rng_seed: [0; 32],
+            diamond_membership_seats: DiamondMembershipSeatsMap::default(),
+            online_users_canister_id,
+            referral_campaigns: ReferralCampaigns::default(),
+            appeals: Appeals::default(),
+            moderation_mode: ModerationMode::default(),
+            local_moderation_quorum: DEFAULT_LOCAL_MODERATION_QUORUM,
         };
 
         // Register the ProposalsBot
@@ -318,6 +352,12 @@ impl Default for Data {
             reported_messages: ReportedMessages::default(),
             fire_and_forget_handler: FireAndForgetHandler::default(),
             rng_seed: [0; 32],
+            diamond_membership_seats: DiamondMembershipSeatsMap::default(),
+            online_users_canister_id: Principal::anonymous(),
+            referral_campaigns: ReferralCampaigns::default(),
+            appeals: Appeals::default(),
+            moderation_mode: ModerationMode::default(),
+            local_moderation_quorum: DEFAULT_LOCAL_MODERATION_QUORUM,
         }
     }
 }
@@ -368,6 +408,12 @@ pub struct DiamondMembershipPaymentMetrics {
     pub manual_payments_taken: u64,
     pub recurring_payments_taken: u64,
     pub recurring_payments_failed_due_to_insufficient_funds: u64,
+    #[serde(default)]
+    pub gifted_payments_taken: u64,
+    #[serde(default)]
+    pub seats_purchased: u64,
+    #[serde(default)]
+    pub seats_assigned: u64,
 }
 
 #[derive(Serialize, Debug)]
@@ -377,4 +423,5 @@ pub struct CanisterIds {
     pub proposals_bot: CanisterId,
     pub cycles_dispenser: CanisterId,
     pub internet_identity: CanisterId,
+    pub online_users: CanisterId,
 }
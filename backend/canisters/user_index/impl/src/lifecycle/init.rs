@@ -24,6 +24,7 @@ fn init(args: Args) {
         args.cycles_dispenser_canister_id,
         args.storage_index_canister_id,
         args.internet_identity_canister_id,
+        args.online_users_canister_id,
         args.test_mode,
     );
 
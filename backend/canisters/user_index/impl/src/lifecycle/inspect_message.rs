@@ -17,8 +17,10 @@ fn accept_if_valid(state: &RuntimeState) {
 
     let is_valid = match method_name.as_str() {
         "create_canister"
+        | "gift_diamond_membership"
         | "mark_as_online"
         | "pay_for_diamond_membership"
+        | "pay_for_diamond_membership_seats"
         | "set_display_name"
         | "set_moderation_flags"
         | "set_username"
@@ -0,0 +1,160 @@
+use crate::model::user::SuspensionDetails;
+use serde::{Deserialize, Serialize};
+use types::{TimestampMillis, UserId};
+use user_index_canister::appeals::{AppealDecision, AppealSummary};
+use user_index_canister::{AppealOutcome, AppealSubject};
+
+// Each appeal's id is its index within the list
+#[derive(Serialize, Deserialize, Default)]
+pub struct Appeals {
+    appeals: Vec<Appeal>,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct Appeal {
+    pub user_id: UserId,
+    pub subject: AppealSubject,
+    pub text: String,
+    pub submitted: TimestampMillis,
+    // For suspension appeals, this identifies which suspension is being appealed
+    pub suspended_at: Option<TimestampMillis>,
+    pub decision: Option<AppealDecision>,
+}
+
+impl Appeals {
+    pub fn add(&mut self, appeal: Appeal) -> u32 {
+        self.appeals.push(appeal);
+        (self.appeals.len() - 1) as u32
+    }
+
+    pub fn exists(&self, user_id: UserId, subject: AppealSubject, suspended_at: Option<TimestampMillis>) -> bool {
+        self.appeals
+            .iter()
+            .any(|a| a.user_id == user_id && a.subject == subject && a.suspended_at == suspended_at)
+    }
+
+    pub fn decide(
+        &mut self,
+        appeal_id: u32,
+        outcome: AppealOutcome,
+        decided_by: UserId,
+        notes: Option<String>,
+        now: TimestampMillis,
+    ) -> DecideAppealResult {
+        match self.appeals.get_mut(appeal_id as usize) {
+            Some(appeal) if appeal.decision.is_some() => DecideAppealResult::AlreadyDecided,
+            Some(appeal) => {
+                appeal.decision = Some(AppealDecision {
+                    outcome,
+                    decided_by,
+                    notes,
+                    timestamp: now,
+                });
+                DecideAppealResult::Success(appeal.clone())
+            }
+            None => DecideAppealResult::NotFound,
+        }
+    }
+
+    pub fn summaries(&self, pending_only: bool) -> Vec<AppealSummary> {
+        self.appeals
+            .iter()
+            .enumerate()
+            .filter(|(_, a)| !pending_only || a.decision.is_none())
+            .map(|(i, a)| AppealSummary {
+                appeal_id: i as u32,
+                user_id: a.user_id,
+                subject: a.subject,
+                text: a.text.clone(),
+                submitted: a.submitted,
+                decision: a.decision.clone(),
+            })
+            .collect()
+    }
+}
+
+impl Appeal {
+    // Returns true if the appeal is against the given suspension, or against the report which caused it
+    pub fn caused_suspension(&self, suspension: &SuspensionDetails) -> bool {
+        match self.subject {
+            AppealSubject::Suspension => self.suspended_at == Some(suspension.timestamp),
+            AppealSubject::ReportedMessage(index) => suspension.report_index == Some(index),
+        }
+    }
+}
+
+pub enum DecideAppealResult {
+    Success(Appeal),
+    NotFound,
+    AlreadyDecided,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::user::SuspensionDuration;
+    use candid::Principal;
+    use utils::consts::OPENCHAT_BOT_USER_ID;
+
+    #[test]
+    fn appeal_can_only_be_decided_once() {
+        let mut appeals = Appeals::default();
+        let appeal_id = appeals.add(appeal(AppealSubject::Suspension, Some(1)));
+
+        assert!(matches!(
+            appeals.decide(appeal_id, AppealOutcome::Upheld, user_id(2), None, 10),
+            DecideAppealResult::Success(_)
+        ));
+        assert!(matches!(
+            appeals.decide(appeal_id, AppealOutcome::Overturned, user_id(2), None, 20),
+            DecideAppealResult::AlreadyDecided
+        ));
+        assert!(matches!(
+            appeals.decide(appeal_id + 1, AppealOutcome::Overturned, user_id(2), None, 20),
+            DecideAppealResult::NotFound
+        ));
+        assert!(appeals.summaries(true).is_empty());
+    }
+
+    #[test]
+    fn suspension_appeal_only_applies_to_suspension_appealed_against() {
+        let appeal = appeal(AppealSubject::Suspension, Some(1));
+
+        assert!(appeal.caused_suspension(&suspension(1, None)));
+        assert!(!appeal.caused_suspension(&suspension(2, None)));
+    }
+
+    #[test]
+    fn reported_message_appeal_only_applies_to_suspension_caused_by_report() {
+        let appeal = appeal(AppealSubject::ReportedMessage(5), None);
+
+        assert!(appeal.caused_suspension(&suspension(1, Some(5))));
+        assert!(!appeal.caused_suspension(&suspension(1, Some(6))));
+        assert!(!appeal.caused_suspension(&suspension(1, None)));
+    }
+
+    fn appeal(subject: AppealSubject, suspended_at: Option<TimestampMillis>) -> Appeal {
+        Appeal {
+            user_id: user_id(1),
+            subject,
+            text: "Please reconsider".to_string(),
+            submitted: 0,
+            suspended_at,
+            decision: None,
+        }
+    }
+
+    fn suspension(timestamp: TimestampMillis, report_index: Option<u64>) -> SuspensionDetails {
+        SuspensionDetails {
+            timestamp,
+            duration: SuspensionDuration::Indefinitely,
+            reason: "Violation of platform rules".to_string(),
+            suspended_by: OPENCHAT_BOT_USER_ID,
+            report_index,
+        }
+    }
+
+    fn user_id(index: u8) -> UserId {
+        Principal::from_slice(&[index]).into()
+    }
+}
@@ -1,6 +1,6 @@
 use serde::{Deserialize, Serialize};
 use std::cmp::max;
-use types::{Cryptocurrency, DiamondMembershipDetails, DiamondMembershipPlanDuration, Milliseconds, TimestampMillis};
+use types::{Cryptocurrency, DiamondMembershipDetails, DiamondMembershipPlanDuration, Milliseconds, TimestampMillis, UserId};
 use user_index_canister::pay_for_diamond_membership::CannotExtendResult;
 use utils::time::DAY_IN_MS;
 
@@ -20,6 +20,9 @@ pub struct DiamondMembershipPayment {
     pub block_index: u64,
     pub duration: DiamondMembershipPlanDuration,
     pub manual_payment: bool,
+    // Set if the membership was gifted, either directly or via a community's seats
+    #[serde(default)]
+    pub gifted_by: Option<UserId>,
 }
 
 const THREE_MONTHS: Milliseconds = DiamondMembershipPlanDuration::ThreeMonths.as_millis();
@@ -79,6 +82,7 @@ impl DiamondMembershipDetailsInternal {
         duration: DiamondMembershipPlanDuration,
         recurring: bool,
         manual_payment: bool,
+        gifted_by: Option<UserId>,
         now: TimestampMillis,
     ) {
         let payment = DiamondMembershipPayment {
@@ -88,6 +92,7 @@ impl DiamondMembershipDetailsInternal {
             block_index,
             duration,
             manual_payment,
+            gifted_by,
         };
 
         let duration_millis = duration.as_millis();
@@ -0,0 +1,155 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use types::{CommunityId, Cryptocurrency, DiamondMembershipPlanDuration, Milliseconds, TimestampMillis, UserId};
+use user_index_canister::DiamondMembershipSeats;
+use utils::time::DAY_IN_MS;
+
+// Seats which haven't been assigned within a year of being purchased are refunded to the purchaser
+pub const SEATS_EXPIRY: Milliseconds = 365 * DAY_IN_MS;
+
+// Seats are purchased for a community and are then assigned to members by the community's admins.
+// The funds for each seat are held until the seat is assigned, at which point they are distributed
+// in the same way as if the member had paid for the membership themselves.
+#[derive(Serialize, Deserialize, Default)]
+pub struct DiamondMembershipSeatsMap {
+    communities: HashMap<CommunityId, Vec<SeatsPurchase>>,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct SeatsPurchase {
+    pub purchased_by: UserId,
+    pub timestamp: TimestampMillis,
+    pub duration: DiamondMembershipPlanDuration,
+    pub token: Cryptocurrency,
+    pub price_per_seat_e8s: u64,
+    pub block_index: u64,
+    pub seats: u32,
+    pub remaining: u32,
+}
+
+impl DiamondMembershipSeatsMap {
+    pub fn add(&mut self, community_id: CommunityId, purchase: SeatsPurchase) {
+        self.communities.entry(community_id).or_default().push(purchase);
+    }
+
+    // Seats are taken from the oldest purchase which still has seats remaining
+    pub fn take_seat(&mut self, community_id: &CommunityId) -> Option<SeatsPurchase> {
+        let purchases = self.communities.get_mut(community_id)?;
+        let purchase = purchases.iter_mut().find(|p| p.remaining > 0)?;
+        purchase.remaining -= 1;
+        Some(purchase.clone())
+    }
+
+    // Returns a seat which was taken but could not be assigned
+    pub fn return_seat(&mut self, community_id: &CommunityId, token: &Cryptocurrency, block_index: u64) {
+        if let Some(purchase) = self.communities.get_mut(community_id).and_then(|purchases| {
+            purchases
+                .iter_mut()
+                .find(|p| p.token == *token && p.block_index == block_index)
+        }) {
+            purchase.remaining += 1;
+        }
+    }
+
+    // Removes the seats remaining from the given purchase, returning the purchase as it was beforehand
+    // so that its remaining seats can be refunded
+    pub fn expire(&mut self, community_id: &CommunityId, token: &Cryptocurrency, block_index: u64) -> Option<SeatsPurchase> {
+        let purchase = self
+            .communities
+            .get_mut(community_id)?
+            .iter_mut()
+            .find(|p| p.token == *token && p.block_index == block_index && p.remaining > 0)?;
+
+        let expired = purchase.clone();
+        purchase.remaining = 0;
+        Some(expired)
+    }
+
+    pub fn available(&self, community_id: &CommunityId) -> u32 {
+        self.communities
+            .get(community_id)
+            .map_or(0, |purchases| purchases.iter().map(|p| p.remaining).sum())
+    }
+
+    pub fn purchases(&self, community_id: &CommunityId) -> Vec<DiamondMembershipSeats> {
+        self.communities
+            .get(community_id)
+            .map(|purchases| {
+                purchases
+                    .iter()
+                    .map(|p| DiamondMembershipSeats {
+                        purchased_by: p.purchased_by,
+                        timestamp: p.timestamp,
+                        duration: p.duration,
+                        token: p.token.clone(),
+                        seats: p.seats,
+                        remaining: p.remaining,
+                    })
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use candid::Principal;
+
+    #[test]
+    fn seats_taken_from_oldest_purchase_first() {
+        let mut seats = DiamondMembershipSeatsMap::default();
+        let community_id = community_id();
+        seats.add(community_id, purchase(1, 1));
+        seats.add(community_id, purchase(2, 2));
+
+        assert_eq!(seats.take_seat(&community_id).unwrap().block_index, 1);
+        assert_eq!(seats.take_seat(&community_id).unwrap().block_index, 2);
+        assert_eq!(seats.available(&community_id), 1);
+    }
+
+    #[test]
+    fn returned_seat_can_be_taken_again() {
+        let mut seats = DiamondMembershipSeatsMap::default();
+        let community_id = community_id();
+        seats.add(community_id, purchase(1, 1));
+
+        let seat = seats.take_seat(&community_id).unwrap();
+        assert!(seats.take_seat(&community_id).is_none());
+
+        seats.return_seat(&community_id, &seat.token, seat.block_index);
+        assert_eq!(seats.available(&community_id), 1);
+    }
+
+    #[test]
+    fn expiring_purchase_returns_remaining_seats_once() {
+        let mut seats = DiamondMembershipSeatsMap::default();
+        let community_id = community_id();
+        seats.add(community_id, purchase(1, 3));
+        seats.add(community_id, purchase(2, 1));
+        seats.take_seat(&community_id);
+
+        let expired = seats.expire(&community_id, &Cryptocurrency::InternetComputer, 1).unwrap();
+        assert_eq!(expired.remaining, 2);
+        assert_eq!(seats.available(&community_id), 1);
+
+        assert!(seats.expire(&community_id, &Cryptocurrency::InternetComputer, 1).is_none());
+    }
+
+    fn purchase(block_index: u64, seats: u32) -> SeatsPurchase {
+        SeatsPurchase {
+            purchased_by: Principal::from_slice(&[1]).into(),
+            timestamp: 0,
+            duration: DiamondMembershipPlanDuration::OneMonth,
+            token: Cryptocurrency::InternetComputer,
+            price_per_seat_e8s: 1_000_000,
+            block_index,
+            seats,
+            remaining: seats,
+        }
+    }
+
+    fn community_id() -> CommunityId {
+        Principal::from_slice(&[2]).into()
+    }
+}
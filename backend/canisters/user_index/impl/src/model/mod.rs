@@ -1,8 +1,11 @@
 pub mod account_billing;
+pub mod appeals;
 pub mod diamond_membership_details;
+pub mod diamond_membership_seats;
 pub mod local_user_index_map;
 pub mod pending_modclub_submissions_queue;
 pub mod pending_payments_queue;
+pub mod referral_campaigns;
 pub mod reported_messages;
 pub mod storage_index_user_sync_queue;
 pub mod user;
@@ -36,4 +36,5 @@ pub struct PendingPayment {
 pub enum PendingPaymentReason {
     Treasury,
     ReferralReward,
+    DiamondMembershipSeatsRefund,
 }
@@ -0,0 +1,205 @@
+use serde::{Deserialize, Serialize};
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
+use types::{Milliseconds, ReferralCampaignTarget, TimestampMillis, UserId};
+use utils::time::DAY_IN_MS;
+
+// Referral campaigns are created by the owners of a group or community. Users who register using a
+// campaign's code are joined to the campaign's target, and their progress is tracked so that the
+// campaign's conversion stats can be reported back to its creator.
+#[derive(Serialize, Deserialize, Default)]
+pub struct ReferralCampaigns {
+    campaigns: HashMap<String, ReferralCampaign>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct ReferralCampaign {
+    pub name: String,
+    pub target: ReferralCampaignTarget,
+    pub created_by: UserId,
+    pub created: TimestampMillis,
+    pub expires: Option<TimestampMillis>,
+    pub max_uses: Option<u32>,
+    pub ended: bool,
+    pub registrations: Vec<CampaignRegistration>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct CampaignRegistration {
+    pub user_id: UserId,
+    pub timestamp: TimestampMillis,
+    pub retained_7_days: Option<bool>,
+    pub retained_30_days: Option<bool>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug)]
+pub enum RetentionCheckpoint {
+    SevenDays,
+    ThirtyDays,
+}
+
+impl RetentionCheckpoint {
+    pub fn after_registration(&self) -> Milliseconds {
+        match self {
+            RetentionCheckpoint::SevenDays => 7 * DAY_IN_MS,
+            RetentionCheckpoint::ThirtyDays => 30 * DAY_IN_MS,
+        }
+    }
+}
+
+pub struct RecordRegistrationResult {
+    pub target: ReferralCampaignTarget,
+    pub usage_cap_reached: bool,
+}
+
+impl ReferralCampaigns {
+    pub fn add(&mut self, code: String, campaign: ReferralCampaign) -> bool {
+        match self.campaigns.entry(code) {
+            Entry::Occupied(_) => false,
+            Entry::Vacant(e) => {
+                e.insert(campaign);
+                true
+            }
+        }
+    }
+
+    pub fn get(&self, code: &str) -> Option<&ReferralCampaign> {
+        self.campaigns.get(code)
+    }
+
+    // Registrations may still arrive after a campaign has ended, since each LocalUserIndex only hears
+    // about the campaign ending once the event has been synced to it, so these are recorded regardless
+    pub fn record_registration(
+        &mut self,
+        code: &str,
+        user_id: UserId,
+        now: TimestampMillis,
+    ) -> Option<RecordRegistrationResult> {
+        let campaign = self.campaigns.get_mut(code)?;
+
+        campaign.registrations.push(CampaignRegistration {
+            user_id,
+            timestamp: now,
+            retained_7_days: None,
+            retained_30_days: None,
+        });
+
+        let usage_cap_reached = !campaign.ended
+            && campaign
+                .max_uses
+                .map_or(false, |max| campaign.registrations.len() >= max as usize);
+        if usage_cap_reached {
+            campaign.ended = true;
+        }
+
+        Some(RecordRegistrationResult {
+            target: campaign.target,
+            usage_cap_reached,
+        })
+    }
+
+    pub fn set_retained(&mut self, code: &str, user_id: UserId, checkpoint: RetentionCheckpoint, retained: bool) {
+        if let Some(registration) = self
+            .campaigns
+            .get_mut(code)
+            .and_then(|c| c.registrations.iter_mut().find(|r| r.user_id == user_id))
+        {
+            match checkpoint {
+                RetentionCheckpoint::SevenDays => registration.retained_7_days = Some(retained),
+                RetentionCheckpoint::ThirtyDays => registration.retained_30_days = Some(retained),
+            }
+        }
+    }
+
+    pub fn created_by(&self, user_id: UserId) -> impl Iterator<Item = (&String, &ReferralCampaign)> {
+        self.campaigns.iter().filter(move |(_, c)| c.created_by == user_id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use candid::Principal;
+
+    #[test]
+    fn duplicate_code_rejected() {
+        let mut campaigns = ReferralCampaigns::default();
+
+        assert!(campaigns.add("code".to_string(), campaign(None)));
+        assert!(!campaigns.add("code".to_string(), campaign(None)));
+    }
+
+    #[test]
+    fn campaign_ends_when_usage_cap_reached() {
+        let mut campaigns = ReferralCampaigns::default();
+        campaigns.add("code".to_string(), campaign(Some(2)));
+
+        let first = campaigns.record_registration("code", user_id(1), 1).unwrap();
+        assert!(!first.usage_cap_reached);
+        assert!(!campaigns.get("code").unwrap().ended);
+
+        let second = campaigns.record_registration("code", user_id(2), 2).unwrap();
+        assert!(second.usage_cap_reached);
+        assert!(campaigns.get("code").unwrap().ended);
+
+        // Late registrations are still recorded but the cap is only reported as reached once
+        let third = campaigns.record_registration("code", user_id(3), 3).unwrap();
+        assert!(!third.usage_cap_reached);
+        assert_eq!(campaigns.get("code").unwrap().registrations.len(), 3);
+    }
+
+    #[test]
+    fn unknown_code_not_recorded() {
+        let mut campaigns = ReferralCampaigns::default();
+
+        assert!(campaigns.record_registration("code", user_id(1), 1).is_none());
+    }
+
+    #[test]
+    fn retention_recorded_per_checkpoint() {
+        let mut campaigns = ReferralCampaigns::default();
+        campaigns.add("code".to_string(), campaign(None));
+        campaigns.record_registration("code", user_id(1), 1);
+
+        campaigns.set_retained("code", user_id(1), RetentionCheckpoint::SevenDays, true);
+        campaigns.set_retained("code", user_id(1), RetentionCheckpoint::ThirtyDays, false);
+
+        let registration = &campaigns.get("code").unwrap().registrations[0];
+        assert_eq!(registration.retained_7_days, Some(true));
+        assert_eq!(registration.retained_30_days, Some(false));
+    }
+
+    #[test]
+    fn created_by_filters_by_creator() {
+        let mut campaigns = ReferralCampaigns::default();
+        campaigns.add("a".to_string(), campaign(None));
+        campaigns.add(
+            "b".to_string(),
+            ReferralCampaign {
+                created_by: user_id(2),
+                ..campaign(None)
+            },
+        );
+
+        let codes: Vec<_> = campaigns.created_by(user_id(1)).map(|(code, _)| code.as_str()).collect();
+
+        assert_eq!(codes, vec!["a"]);
+    }
+
+    fn campaign(max_uses: Option<u32>) -> ReferralCampaign {
+        ReferralCampaign {
+            name: "Campaign".to_string(),
+            target: ReferralCampaignTarget::Community(Principal::from_slice(&[10]).into()),
+            created_by: user_id(1),
+            created: 0,
+            expires: None,
+            max_uses,
+            ended: false,
+            registrations: Vec::new(),
+        }
+    }
+
+    fn user_id(index: u8) -> UserId {
+        Principal::from_slice(&[index]).into()
+    }
+}
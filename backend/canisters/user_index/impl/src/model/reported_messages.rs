@@ -2,14 +2,18 @@ use chat_events::deep_message_links;
 use local_user_index_canister::{Event as LocalUserIndexEvent, OpenChatBotMessage};
 use modclub_canister::{getProviderRules::Rule, subscribe::ContentResult};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
 use types::{Chat, MessageContent, MessageId, MessageIndex, TextContent, TimestampMillis, UserId};
 
 #[derive(Serialize, Deserialize, Default)]
+#[serde(from = "ReportedMessagesTrimmed")]
 pub struct ReportedMessages {
     messages: Vec<ReportedMessage>,
     lookup: HashMap<(Chat, Option<MessageIndex>, MessageIndex), usize>,
     rules: Vec<Rule>,
+    // The indexes of the reports which are still awaiting an outcome
+    #[serde(skip)]
+    pending: BTreeSet<u64>,
 }
 
 impl ReportedMessages {
@@ -42,8 +46,14 @@ impl ReportedMessages {
                 already_deleted: args.already_deleted,
                 reports: HashMap::from([(args.reporter, args.timestamp)]),
                 outcome: None,
+                overturned: false,
+                local_votes: HashMap::new(),
             });
-            AddReportResult::New((self.messages.len() - 1) as u64)
+            let index = self.messages.len() - 1;
+            self.lookup
+                .insert((args.chat_id, args.thread_root_message_index, args.message_index), index);
+            self.pending.insert(index as u64);
+            AddReportResult::New(index as u64)
         }
     }
 
@@ -64,13 +74,74 @@ impl ReportedMessages {
                     rejected,
                 })
                 .collect(),
+            local: false,
         };
 
+        self.set_outcome(report_index, outcome)
+    }
+
+    // Once the quorum of platform moderators have voted, the outcome is decided by majority, with
+    // each rule's count being the number of moderators who judged the message to have violated it
+    pub fn add_local_vote(
+        &mut self,
+        report_index: u64,
+        moderator: UserId,
+        vote: LocalModerationVote,
+        quorum: u32,
+        now: TimestampMillis,
+    ) -> AddLocalVoteResult {
+        let message = match self.messages.get_mut(report_index as usize) {
+            Some(m) => m,
+            None => return AddLocalVoteResult::ReportNotFound,
+        };
+        if message.outcome.is_some() {
+            return AddLocalVoteResult::OutcomeExists;
+        }
+        if message.local_votes.contains_key(&moderator) {
+            return AddLocalVoteResult::AlreadyVoted;
+        }
+        message.local_votes.insert(moderator, vote);
+        if (message.local_votes.len() as u32) < quorum {
+            return AddLocalVoteResult::Success;
+        }
+
+        let rejected = message.local_votes.values().filter(|v| v.breaks_rules).count() as u32;
+        let approved = message.local_votes.len() as u32 - rejected;
+
+        let mut rule_counts: BTreeMap<usize, u32> = BTreeMap::new();
+        for rule_index in message
+            .local_votes
+            .values()
+            .filter(|v| v.breaks_rules)
+            .flat_map(|v| v.violated_rules.iter())
+        {
+            *rule_counts.entry(*rule_index).or_default() += 1;
+        }
+
+        let outcome = ReportOutcome {
+            timestamp: now,
+            approved,
+            rejected,
+            violated_rules: rule_counts
+                .into_iter()
+                .map(|(rule_index, rejected)| ViolatedRules { rule_index, rejected })
+                .collect(),
+            local: true,
+        };
+
+        match self.set_outcome(report_index, outcome) {
+            RecordOutcomeResult::Success(m) => AddLocalVoteResult::Decided(m),
+            _ => AddLocalVoteResult::OutcomeExists,
+        }
+    }
+
+    fn set_outcome(&mut self, report_index: u64, outcome: ReportOutcome) -> RecordOutcomeResult {
         if let Some(message) = self.messages.get_mut(report_index as usize) {
             if message.outcome.is_some() {
                 RecordOutcomeResult::OutcomeExists(report_index)
             } else {
                 message.outcome = Some(outcome);
+                self.pending.remove(&report_index);
                 RecordOutcomeResult::Success(message.clone())
             }
         } else {
@@ -78,14 +149,34 @@ impl ReportedMessages {
         }
     }
 
+    pub fn pending(&self) -> impl Iterator<Item = (u64, &ReportedMessage)> {
+        self.pending
+            .iter()
+            .filter_map(|i| self.messages.get(*i as usize).map(|m| (*i, m)))
+    }
+
+    pub fn rule_descriptions(&self) -> Vec<String> {
+        self.rules.iter().map(|r| r.description.clone()).collect()
+    }
+
     pub fn get(&self, index: u64) -> Option<&ReportedMessage> {
         self.messages.get(index as usize)
     }
 
+    // Called when the sender's appeal against the outcome succeeds
+    pub fn overturn(&mut self, index: u64) -> bool {
+        if let Some(message) = self.messages.get_mut(index as usize) {
+            message.overturned = true;
+            true
+        } else {
+            false
+        }
+    }
+
     pub fn metrics(&self) -> ReportingMetrics {
         ReportingMetrics {
             messages_reported: self.messages.len(),
-            messages_pending_outcome: self.messages.iter().filter(|m| m.outcome.is_none()).count(),
+            messages_pending_outcome: self.pending.len(),
             rules: self.rules.clone(),
         }
     }
@@ -95,6 +186,32 @@ impl ReportedMessages {
     }
 }
 
+#[derive(Deserialize)]
+struct ReportedMessagesTrimmed {
+    messages: Vec<ReportedMessage>,
+    lookup: HashMap<(Chat, Option<MessageIndex>, MessageIndex), usize>,
+    rules: Vec<Rule>,
+}
+
+impl From<ReportedMessagesTrimmed> for ReportedMessages {
+    fn from(value: ReportedMessagesTrimmed) -> Self {
+        let pending = value
+            .messages
+            .iter()
+            .enumerate()
+            .filter(|(_, m)| m.outcome.is_none())
+            .map(|(i, _)| i as u64)
+            .collect();
+
+        ReportedMessages {
+            messages: value.messages,
+            lookup: value.lookup,
+            rules: value.rules,
+            pending,
+        }
+    }
+}
+
 #[derive(Serialize, Debug)]
 pub struct ReportingMetrics {
     pub messages_reported: usize,
@@ -120,6 +237,14 @@ pub enum AddReportResult {
     AlreadyReportedByUser,
 }
 
+pub enum AddLocalVoteResult {
+    Success,
+    Decided(ReportedMessage),
+    ReportNotFound,
+    OutcomeExists,
+    AlreadyVoted,
+}
+
 pub enum RecordOutcomeResult {
     Success(ReportedMessage),
     OutcomeExists(u64),
@@ -136,11 +261,22 @@ pub struct ReportedMessage {
     pub already_deleted: bool,
     pub reports: HashMap<UserId, TimestampMillis>,
     pub outcome: Option<ReportOutcome>,
+    #[serde(default)]
+    pub overturned: bool,
+    #[serde(default)]
+    pub local_votes: HashMap<UserId, LocalModerationVote>,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct LocalModerationVote {
+    pub breaks_rules: bool,
+    pub violated_rules: Vec<usize>,
+    pub timestamp: TimestampMillis,
 }
 
 impl ReportedMessage {
     pub fn rejected(&self) -> bool {
-        self.outcome.as_ref().map(|o| o.approved < o.rejected).unwrap_or_default()
+        !self.overturned && self.outcome.as_ref().map(|o| o.approved < o.rejected).unwrap_or_default()
     }
 }
 
@@ -150,6 +286,9 @@ pub struct ReportOutcome {
     pub approved: u32,
     pub rejected: u32,
     pub violated_rules: Vec<ViolatedRules>,
+    // True if decided by the platform moderators rather than by Modclub
+    #[serde(default)]
+    pub local: bool,
 }
 
 impl ReportOutcome {
@@ -182,8 +321,9 @@ pub fn build_message_to_reporter(reported_message: &ReportedMessage, reporter: U
     let outcome = reported_message.outcome.as_ref().unwrap();
     let rejected = reported_message.rejected();
 
-    let text = format!("You reported [this message]({}) for breaking [the platform rules](https://oc.app/guidelines?section=3) and it was referred to [Modclub](https://modclub.ai/) for external moderation. A group of {} moderators decided the message {} the platform rules {} - {}.",
+    let text = format!("You reported [this message]({}) for breaking [the platform rules](https://oc.app/guidelines?section=3) and it was {}. A group of {} moderators decided the message {} the platform rules {} - {}.",
         build_message_link(reported_message),
+        moderated_by(outcome),
         outcome.rejected + outcome.approved,
         if rejected {"broke"} else {"didn't break"},
         if rejected {outcome.rejected} else {outcome.approved},
@@ -197,8 +337,9 @@ pub fn build_message_to_sender(reported_message: &ReportedMessage) -> LocalUserI
     let outcome = reported_message.outcome.as_ref().unwrap();
 
     let text = format!(
-        "Your [message]({}) was reported by another user for breaking [the platform rules](https://oc.app/guidelines?section=3) and it was referred to [Modclub](https://modclub.ai/) for external moderation. A group of {} moderators decided your message broke the platform rules {} - {}.", 
+        "Your [message]({}) was reported by another user for breaking [the platform rules](https://oc.app/guidelines?section=3) and it was {}. A group of {} moderators decided your message broke the platform rules {} - {}.", 
         build_message_link(reported_message),
+        moderated_by(outcome),
         outcome.rejected + outcome.approved,
         outcome.rejected,
         outcome.approved);
@@ -206,6 +347,14 @@ pub fn build_message_to_sender(reported_message: &ReportedMessage) -> LocalUserI
     build_oc_bot_message(text, reported_message.sender)
 }
 
+fn moderated_by(outcome: &ReportOutcome) -> &'static str {
+    if outcome.local {
+        "reviewed by the OpenChat platform moderators"
+    } else {
+        "referred to [Modclub](https://modclub.ai/) for external moderation"
+    }
+}
+
 fn build_oc_bot_message(text: String, user_id: UserId) -> LocalUserIndexEvent {
     LocalUserIndexEvent::OpenChatBotMessage(Box::new(OpenChatBotMessage {
         user_id,
@@ -220,3 +369,94 @@ fn build_message_link(reported_message: &ReportedMessage) -> String {
         reported_message.message_index,
     )
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use candid::Principal;
+    use types::ChatId;
+
+    #[test]
+    fn repeat_reports_of_same_message_are_merged() {
+        let mut reported_messages = ReportedMessages::default();
+
+        assert!(matches!(
+            reported_messages.add_report(report_args(1, 1)),
+            AddReportResult::New(0)
+        ));
+        assert!(matches!(
+            reported_messages.add_report(report_args(1, 2)),
+            AddReportResult::ExistingPending
+        ));
+        assert!(matches!(
+            reported_messages.add_report(report_args(1, 2)),
+            AddReportResult::AlreadyReportedByUser
+        ));
+        assert!(matches!(
+            reported_messages.add_report(report_args(2, 1)),
+            AddReportResult::New(1)
+        ));
+
+        assert_eq!(reported_messages.pending().map(|(i, _)| i).collect::<Vec<_>>(), vec![0, 1]);
+        assert_eq!(reported_messages.get(0).unwrap().reports.len(), 2);
+    }
+
+    #[test]
+    fn second_vote_by_same_moderator_is_rejected_and_does_not_replace_first() {
+        let mut reported_messages = ReportedMessages::default();
+        reported_messages.add_report(report_args(1, 1));
+
+        let moderator = user_id(10);
+        assert!(matches!(
+            reported_messages.add_local_vote(0, moderator, vote(true), 2, 1),
+            AddLocalVoteResult::Success
+        ));
+        assert!(matches!(
+            reported_messages.add_local_vote(0, moderator, vote(false), 2, 2),
+            AddLocalVoteResult::AlreadyVoted
+        ));
+
+        assert!(reported_messages.get(0).unwrap().local_votes[&moderator].breaks_rules);
+        assert_eq!(reported_messages.pending().count(), 1);
+    }
+
+    #[test]
+    fn decided_reports_are_no_longer_pending() {
+        let mut reported_messages = ReportedMessages::default();
+        reported_messages.add_report(report_args(1, 1));
+        reported_messages.add_report(report_args(2, 1));
+
+        assert!(matches!(
+            reported_messages.add_local_vote(0, user_id(10), vote(true), 1, 1),
+            AddLocalVoteResult::Decided(_)
+        ));
+
+        assert_eq!(reported_messages.pending().map(|(i, _)| i).collect::<Vec<_>>(), vec![1]);
+        assert_eq!(reported_messages.metrics().messages_pending_outcome, 1);
+    }
+
+    fn report_args(message_index: u32, reporter: u8) -> AddReportArgs {
+        AddReportArgs {
+            chat_id: Chat::Group(ChatId::from(Principal::from_slice(&[1]))),
+            thread_root_message_index: None,
+            message_index: message_index.into(),
+            message_id: (message_index as u128).into(),
+            sender: user_id(100),
+            reporter: user_id(reporter),
+            already_deleted: false,
+            timestamp: 0,
+        }
+    }
+
+    fn vote(breaks_rules: bool) -> LocalModerationVote {
+        LocalModerationVote {
+            breaks_rules,
+            violated_rules: Vec::new(),
+            timestamp: 0,
+        }
+    }
+
+    fn user_id(index: u8) -> UserId {
+        Principal::from_slice(&[index]).into()
+    }
+}
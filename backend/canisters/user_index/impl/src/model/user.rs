@@ -107,6 +107,9 @@ pub struct SuspensionDetails {
     pub duration: SuspensionDuration,
     pub reason: String,
     pub suspended_by: UserId,
+    // Set if the user was automatically suspended as a result of this report
+    #[serde(default)]
+    pub report_index: Option<u64>,
 }
 
 #[derive(CandidType, Serialize, Deserialize, Clone, Debug, Eq, PartialEq)]
@@ -164,6 +164,7 @@ impl UserMap {
         duration: Option<Milliseconds>,
         reason: String,
         suspended_by: UserId,
+        report_index: Option<u64>,
         now: TimestampMillis,
     ) -> bool {
         if let Some(user) = self.users.get_mut(user_id) {
@@ -172,6 +173,7 @@ impl UserMap {
                 duration: duration.map_or(SuspensionDuration::Indefinitely, SuspensionDuration::Duration),
                 reason,
                 suspended_by,
+                report_index,
             });
             true
         } else {
@@ -0,0 +1,13 @@
+use crate::guards::caller_is_platform_moderator;
+use crate::{read_state, RuntimeState};
+use ic_cdk_macros::query;
+use user_index_canister::appeals::{Response::*, *};
+
+#[query(guard = "caller_is_platform_moderator")]
+fn appeals(args: Args) -> Response {
+    read_state(|state| appeals_impl(args, state))
+}
+
+fn appeals_impl(args: Args, state: &RuntimeState) -> Response {
+    Success(state.data.appeals.summaries(args.pending_only))
+}
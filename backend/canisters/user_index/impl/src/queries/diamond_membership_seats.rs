@@ -0,0 +1,15 @@
+use crate::{read_state, RuntimeState};
+use ic_cdk_macros::query;
+use user_index_canister::diamond_membership_seats::{Response::*, *};
+
+#[query]
+fn diamond_membership_seats(args: Args) -> Response {
+    read_state(|state| diamond_membership_seats_impl(args, state))
+}
+
+fn diamond_membership_seats_impl(args: Args, state: &RuntimeState) -> Response {
+    Success(SuccessResult {
+        seats_available: state.data.diamond_membership_seats.available(&args.community_id),
+        purchases: state.data.diamond_membership_seats.purchases(&args.community_id),
+    })
+}
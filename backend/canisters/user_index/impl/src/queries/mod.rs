@@ -1,11 +1,15 @@
+pub mod appeals;
 pub mod c2c_lookup_user;
 pub mod check_username;
 pub mod current_user;
 pub mod diamond_membership_fees;
+pub mod diamond_membership_seats;
 pub mod http_request;
+pub mod moderation_queue;
 pub mod platform_moderators;
 pub mod platform_moderators_group;
 pub mod platform_operators;
+pub mod referral_campaigns;
 pub mod referral_leaderboard;
 pub mod referral_metrics;
 pub mod search;
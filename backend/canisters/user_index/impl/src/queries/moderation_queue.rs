@@ -0,0 +1,40 @@
+use crate::guards::caller_is_platform_moderator;
+use crate::{read_state, RuntimeState};
+use chat_events::deep_message_links;
+use ic_cdk_macros::query;
+use user_index_canister::moderation_queue::{Response::*, *};
+
+#[query(guard = "caller_is_platform_moderator")]
+fn moderation_queue(_args: Args) -> Response {
+    read_state(moderation_queue_impl)
+}
+
+fn moderation_queue_impl(state: &RuntimeState) -> Response {
+    let caller = state.env.caller();
+    let moderator = state.data.users.get_by_principal(&caller).unwrap().user_id;
+
+    let reports = state
+        .data
+        .reported_messages
+        .pending()
+        .map(|(report_index, m)| PendingReport {
+            report_index,
+            chat_id: m.chat_id,
+            thread_root_message_index: m.thread_root_message_index,
+            message_index: m.message_index,
+            message_link: deep_message_links::build_message_link(m.chat_id, m.thread_root_message_index, m.message_index),
+            sender: m.sender,
+            reported_by: m.reports.len() as u32,
+            first_reported: m.reports.values().min().copied().unwrap_or_default(),
+            votes: m.local_votes.len() as u32,
+            voted: m.local_votes.contains_key(&moderator),
+        })
+        .collect();
+
+    Success(SuccessResult {
+        mode: state.data.moderation_mode,
+        local_quorum: state.data.local_moderation_quorum,
+        rules: state.data.reported_messages.rule_descriptions(),
+        reports,
+    })
+}
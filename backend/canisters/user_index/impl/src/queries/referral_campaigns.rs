@@ -0,0 +1,67 @@
+use crate::guards::caller_is_openchat_user;
+use crate::model::referral_campaigns::ReferralCampaign;
+use crate::{read_state, RuntimeState};
+use ic_cdk_macros::query;
+use user_index_canister::referral_campaigns::{Response::*, *};
+
+#[query(guard = "caller_is_openchat_user")]
+fn referral_campaigns(_args: Args) -> Response {
+    read_state(referral_campaigns_impl)
+}
+
+fn referral_campaigns_impl(state: &RuntimeState) -> Response {
+    let caller = state.env.caller();
+    let Some(user_id) = state.data.users.get_by_principal(&caller).map(|u| u.user_id) else {
+        return Success(Vec::new());
+    };
+
+    let mut campaigns: Vec<_> = state
+        .data
+        .referral_campaigns
+        .created_by(user_id)
+        .map(|(code, campaign)| summarize(code, campaign, state))
+        .collect();
+
+    campaigns.sort_by_key(|c| c.created);
+
+    Success(campaigns)
+}
+
+fn summarize(code: &str, campaign: &ReferralCampaign, state: &RuntimeState) -> ReferralCampaignSummary {
+    let mut retention_7_days = RetentionStats::default();
+    let mut retention_30_days = RetentionStats::default();
+    let mut diamond_upgrades = 0;
+
+    for registration in campaign.registrations.iter() {
+        if state
+            .data
+            .users
+            .get_by_user_id(&registration.user_id)
+            .map_or(false, |u| u.diamond_membership_details.has_ever_been_diamond_member())
+        {
+            diamond_upgrades += 1;
+        }
+        if let Some(retained) = registration.retained_7_days {
+            retention_7_days.measured += 1;
+            retention_7_days.retained += retained as u32;
+        }
+        if let Some(retained) = registration.retained_30_days {
+            retention_30_days.measured += 1;
+            retention_30_days.retained += retained as u32;
+        }
+    }
+
+    ReferralCampaignSummary {
+        code: code.to_string(),
+        name: campaign.name.clone(),
+        target: campaign.target,
+        created: campaign.created,
+        expires: campaign.expires,
+        max_uses: campaign.max_uses,
+        ended: campaign.ended || campaign.expires.map_or(false, |ts| ts <= state.env.now()),
+        registrations: campaign.registrations.len() as u32,
+        diamond_upgrades,
+        retention_7_days,
+        retention_30_days,
+    }
+}
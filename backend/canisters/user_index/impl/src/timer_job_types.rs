@@ -1,15 +1,21 @@
+use crate::model::pending_payments_queue::{PendingPayment, PendingPaymentReason};
+use crate::model::referral_campaigns::RetentionCheckpoint;
 use crate::updates::pay_for_diamond_membership::pay_for_diamond_membership_impl;
 use crate::updates::suspend_user::suspend_user_impl;
 use crate::updates::unsuspend_user::unsuspend_user_impl;
 use crate::{mutate_state, read_state};
 use canister_timer_jobs::Job;
 use ic_ledger_types::Tokens;
-use local_user_index_canister::{Event as LocalUserIndexEvent, OpenChatBotMessage, UserJoinedGroup};
+use local_user_index_canister::{
+    Event as LocalUserIndexEvent, OpenChatBotMessage, UserJoinedCommunityOrChannel, UserJoinedGroup,
+};
+use rand::Rng;
 use serde::{Deserialize, Serialize};
 use types::{
-    ChatId, CommunityId, Cryptocurrency, DiamondMembershipPlanDuration, MessageContent, Milliseconds, TextContent, UserId,
+    CanisterId, ChannelLatestMessageIndex, ChatId, CommunityId, Cryptocurrency, DiamondMembershipPlanDuration, MessageContent,
+    Milliseconds, TextContent, TimestampMillis, UserId,
 };
-use utils::time::{MINUTE_IN_MS, SECOND_IN_MS};
+use utils::time::{DAY_IN_MS, MINUTE_IN_MS, SECOND_IN_MS};
 
 #[derive(Serialize, Deserialize, Clone)]
 pub enum TimerJob {
@@ -19,6 +25,9 @@ pub enum TimerJob {
     SetUserSuspendedInCommunity(SetUserSuspendedInCommunity),
     UnsuspendUser(UnsuspendUser),
     JoinUserToGroup(JoinUserToGroup),
+    JoinUserToCommunity(JoinUserToCommunity),
+    CheckReferralCampaignRetention(CheckReferralCampaignRetention),
+    RefundExpiredDiamondMembershipSeats(RefundExpiredDiamondMembershipSeats),
 }
 
 #[derive(Serialize, Deserialize, Clone)]
@@ -48,6 +57,8 @@ pub struct SetUserSuspended {
     pub duration: Option<Milliseconds>,
     pub reason: String,
     pub suspended_by: UserId,
+    #[serde(default)]
+    pub report_index: Option<u64>,
 }
 
 #[derive(Serialize, Deserialize, Clone)]
@@ -62,6 +73,29 @@ pub struct JoinUserToGroup {
     pub attempt: usize,
 }
 
+#[derive(Serialize, Deserialize, Clone)]
+pub struct JoinUserToCommunity {
+    pub user_id: UserId,
+    pub community_id: CommunityId,
+    pub attempt: usize,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct CheckReferralCampaignRetention {
+    pub code: String,
+    pub user_id: UserId,
+    pub registered: TimestampMillis,
+    pub checkpoint: RetentionCheckpoint,
+    pub attempt: usize,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct RefundExpiredDiamondMembershipSeats {
+    pub community_id: CommunityId,
+    pub token: Cryptocurrency,
+    pub block_index: u64,
+}
+
 impl Job for TimerJob {
     fn execute(self) {
         match self {
@@ -71,6 +105,9 @@ impl Job for TimerJob {
             TimerJob::SetUserSuspendedInCommunity(job) => job.execute(),
             TimerJob::UnsuspendUser(job) => job.execute(),
             TimerJob::JoinUserToGroup(job) => job.execute(),
+            TimerJob::JoinUserToCommunity(job) => job.execute(),
+            TimerJob::CheckReferralCampaignRetention(job) => job.execute(),
+            TimerJob::RefundExpiredDiamondMembershipSeats(job) => job.execute(),
         }
     }
 }
@@ -151,10 +188,17 @@ impl Job for SetUserSuspended {
             self.duration,
             self.reason.clone(),
             self.suspended_by,
+            self.report_index,
         ));
 
-        async fn suspend_user(user_id: UserId, duration: Option<Milliseconds>, reason: String, suspended_by: UserId) {
-            suspend_user_impl(user_id, duration, reason, suspended_by).await;
+        async fn suspend_user(
+            user_id: UserId,
+            duration: Option<Milliseconds>,
+            reason: String,
+            suspended_by: UserId,
+            report_index: Option<u64>,
+        ) {
+            suspend_user_impl(user_id, duration, reason, suspended_by, report_index).await;
         }
     }
 }
@@ -291,3 +335,134 @@ impl Job for JoinUserToGroup {
         }
     }
 }
+
+impl Job for JoinUserToCommunity {
+    fn execute(self) {
+        if let Some(args) = read_state(|state| {
+            state
+                .data
+                .users
+                .get_by_user_id(&self.user_id)
+                .map(|u| community_canister::c2c_join_community::Args {
+                    user_id: self.user_id,
+                    principal: u.principal,
+                    invite_code: None,
+                    is_platform_moderator: state.data.platform_moderators.contains(&self.user_id),
+                    is_bot: u.is_bot,
+                })
+        }) {
+            ic_cdk::spawn(join_community(self.community_id, args, self.attempt));
+        }
+
+        async fn join_community(community_id: CommunityId, args: community_canister::c2c_join_community::Args, attempt: usize) {
+            use community_canister::c2c_join_community::*;
+
+            match community_canister_c2c_client::c2c_join_community(community_id.into(), &args).await {
+                Ok(Response::Success(s) | Response::AlreadyInCommunity(s)) => mutate_state(|state| {
+                    state.push_event_to_local_user_index(
+                        args.user_id,
+                        LocalUserIndexEvent::UserJoinedCommunityOrChannel(UserJoinedCommunityOrChannel {
+                            user_id: args.user_id,
+                            community_id,
+                            channels: s
+                                .channels
+                                .iter()
+                                .map(|c| ChannelLatestMessageIndex {
+                                    channel_id: c.channel_id,
+                                    latest_message_index: c.latest_message.as_ref().map(|m| m.event.message_index),
+                                })
+                                .collect(),
+                        }),
+                    )
+                }),
+                Ok(Response::InternalError(_)) | Err(_) => {
+                    if attempt < 50 {
+                        mutate_state(|state| {
+                            let now = state.env.now();
+                            state.data.timer_jobs.enqueue_job(
+                                TimerJob::JoinUserToCommunity(JoinUserToCommunity {
+                                    user_id: args.user_id,
+                                    community_id,
+                                    attempt: attempt + 1,
+                                }),
+                                now + 10 * SECOND_IN_MS,
+                                now,
+                            );
+                        })
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+impl Job for CheckReferralCampaignRetention {
+    fn execute(self) {
+        let online_users_canister_id = read_state(|state| state.data.online_users_canister_id);
+
+        ic_cdk::spawn(check_retention(self, online_users_canister_id));
+
+        async fn check_retention(job: CheckReferralCampaignRetention, online_users_canister_id: CanisterId) {
+            let args = online_users_canister::last_online::Args {
+                user_ids: vec![job.user_id],
+            };
+
+            match online_users_canister_c2c_client::last_online(online_users_canister_id, &args).await {
+                Ok(online_users_canister::last_online::Response::Success(users)) => mutate_state(|state| {
+                    // The user is retained if they have been online at some point since the start of the
+                    // final day before the checkpoint
+                    let threshold = job.registered + job.checkpoint.after_registration() - DAY_IN_MS;
+                    let now = state.env.now();
+                    let retained = users
+                        .first()
+                        .map_or(false, |u| now.saturating_sub(u.duration_since_last_online) >= threshold);
+
+                    state
+                        .data
+                        .referral_campaigns
+                        .set_retained(&job.code, job.user_id, job.checkpoint, retained);
+                }),
+                Err(_) if job.attempt < 10 => mutate_state(|state| {
+                    let now = state.env.now();
+                    state.data.timer_jobs.enqueue_job(
+                        TimerJob::CheckReferralCampaignRetention(CheckReferralCampaignRetention {
+                            attempt: job.attempt + 1,
+                            ..job
+                        }),
+                        now + MINUTE_IN_MS,
+                        now,
+                    );
+                }),
+                Err(_) => {}
+            }
+        }
+    }
+}
+
+impl Job for RefundExpiredDiamondMembershipSeats {
+    fn execute(self) {
+        mutate_state(|state| {
+            if let Some(purchase) =
+                state
+                    .data
+                    .diamond_membership_seats
+                    .expire(&self.community_id, &self.token, self.block_index)
+            {
+                let fee = self.token.fee().unwrap_or_default() as u64;
+                let amount = (purchase.remaining as u64 * purchase.price_per_seat_e8s).saturating_sub(fee);
+                if amount > 0 {
+                    let refund = PendingPayment {
+                        amount,
+                        currency: self.token,
+                        timestamp: state.env.now_nanos(),
+                        recipient: purchase.purchased_by.into(),
+                        memo: state.env.rng().gen(),
+                        reason: PendingPaymentReason::DiamondMembershipSeatsRefund,
+                    };
+                    state.queue_payment(refund);
+                }
+            }
+        });
+    }
+}
@@ -0,0 +1,58 @@
+use crate::updates::pay_for_diamond_membership::{apply_payment, MembershipPayment};
+use crate::{mutate_state, RuntimeState};
+use canister_api_macros::update_msgpack;
+use canister_tracing_macros::trace;
+use types::CommunityId;
+use user_index_canister::c2c_assign_diamond_membership_seat::{Response::*, *};
+
+// Seats are keyed by community, and the caller must be the community itself, so a canister can only
+// ever assign seats which were purchased for it
+#[update_msgpack]
+#[trace]
+fn c2c_assign_diamond_membership_seat(args: Args) -> Response {
+    mutate_state(|state| c2c_assign_diamond_membership_seat_impl(args, state))
+}
+
+fn c2c_assign_diamond_membership_seat_impl(args: Args, state: &mut RuntimeState) -> Response {
+    let community_id: CommunityId = state.env.caller().into();
+    let now = state.env.now();
+
+    let Some(diamond_membership) = state.data.users.diamond_membership_details_mut(&args.user_id) else {
+        return UserNotFound;
+    };
+    if diamond_membership.payment_in_progress() {
+        return PaymentAlreadyInProgress;
+    }
+    if let Err(result) = diamond_membership.can_extend(now) {
+        return CannotExtend(result);
+    }
+
+    let Some(seat) = state.data.diamond_membership_seats.take_seat(&community_id) else {
+        return NoSeatsAvailable;
+    };
+
+    let payment = MembershipPayment {
+        token: seat.token.clone(),
+        amount_e8s: seat.price_per_seat_e8s,
+        block_index: seat.block_index,
+        duration: seat.duration,
+        recurring: false,
+        manual_payment: true,
+        gifted_by: Some(seat.purchased_by),
+    };
+
+    if let Some(result) = apply_payment(args.user_id, payment, true, state) {
+        state.data.diamond_membership_payment_metrics.seats_assigned += 1;
+
+        Success(SuccessResult {
+            expires_at: result.expires_at,
+            seats_remaining: state.data.diamond_membership_seats.available(&community_id),
+        })
+    } else {
+        state
+            .data
+            .diamond_membership_seats
+            .return_seat(&community_id, &seat.token, seat.block_index);
+        UserNotFound
+    }
+}
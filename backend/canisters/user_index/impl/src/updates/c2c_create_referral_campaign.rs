@@ -0,0 +1,85 @@
+use crate::model::referral_campaigns::ReferralCampaign;
+use crate::{mutate_state, RuntimeState};
+use candid::Principal;
+use canister_api_macros::update_msgpack;
+use canister_tracing_macros::trace;
+use local_user_index_canister::{Event as LocalUserIndexEvent, ReferralCampaignAdded};
+use types::{CanisterId, ReferralCampaignTarget};
+use user_index_canister::c2c_create_referral_campaign::{Response::*, *};
+
+const MIN_CODE_LENGTH: usize = 3;
+const MAX_CODE_LENGTH: usize = 30;
+const MIN_NAME_LENGTH: u32 = 3;
+const MAX_NAME_LENGTH: u32 = 50;
+
+// Campaigns can only be created by the group or community they target, which is responsible for
+// checking that the user creating the campaign is one of its owners
+#[update_msgpack]
+#[trace]
+fn c2c_create_referral_campaign(args: Args) -> Response {
+    mutate_state(|state| c2c_create_referral_campaign_impl(args, state))
+}
+
+fn c2c_create_referral_campaign_impl(args: Args, state: &mut RuntimeState) -> Response {
+    let target_canister_id: CanisterId = match args.target {
+        ReferralCampaignTarget::Group(chat_id) => chat_id.into(),
+        ReferralCampaignTarget::Community(community_id) => community_id.into(),
+    };
+    if target_canister_id != state.env.caller() || state.data.users.get_by_user_id(&args.created_by).is_none() {
+        return NotAuthorized;
+    }
+
+    if !is_valid_code(&args.code) {
+        return CodeInvalid;
+    }
+
+    let name_length = args.name.chars().count() as u32;
+    if name_length < MIN_NAME_LENGTH {
+        return NameTooShort(MIN_NAME_LENGTH);
+    }
+    if name_length > MAX_NAME_LENGTH {
+        return NameTooLong(MAX_NAME_LENGTH);
+    }
+
+    let now = state.env.now();
+    if args.expires.map_or(false, |ts| ts <= now) {
+        return ExpiryInPast;
+    }
+    if args.max_uses == Some(0) {
+        return InvalidMaxUses;
+    }
+
+    let added = state.data.referral_campaigns.add(
+        args.code.clone(),
+        ReferralCampaign {
+            name: args.name,
+            target: args.target,
+            created_by: args.created_by,
+            created: now,
+            expires: args.expires,
+            max_uses: args.max_uses,
+            ended: false,
+            registrations: Vec::new(),
+        },
+    );
+
+    if added {
+        state.push_event_to_all_local_user_indexes(
+            LocalUserIndexEvent::ReferralCampaignAdded(ReferralCampaignAdded {
+                code: args.code,
+                expiry: args.expires,
+            }),
+            None,
+        );
+        Success
+    } else {
+        CodeTaken
+    }
+}
+
+// Codes which could be parsed as a principal are rejected since those are used as user referral codes
+fn is_valid_code(code: &str) -> bool {
+    (MIN_CODE_LENGTH..=MAX_CODE_LENGTH).contains(&code.len())
+        && code.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+        && Principal::from_text(code).is_err()
+}
@@ -1,15 +1,16 @@
 use crate::guards::caller_is_local_user_index_canister;
-use crate::timer_job_types::{JoinUserToGroup, TimerJob};
+use crate::model::referral_campaigns::RetentionCheckpoint;
+use crate::timer_job_types::{CheckReferralCampaignRetention, JoinUserToCommunity, JoinUserToGroup, TimerJob};
 use crate::{mutate_state, RuntimeState, ONE_MB};
 use candid::Principal;
 use canister_api_macros::update_msgpack;
 use canister_tracing_macros::trace;
 use local_user_index_canister::{
-    Event as LocalUserIndexEvent, OpenChatBotMessage, UserJoinedCommunityOrChannel, UserJoinedGroup, UserRegistered,
-    UsernameChanged,
+    Event as LocalUserIndexEvent, OpenChatBotMessage, ReferralCampaignEnded, UserJoinedCommunityOrChannel, UserJoinedGroup,
+    UserRegistered, UsernameChanged,
 };
 use storage_index_canister::add_or_update_users::UserConfig;
-use types::{CanisterId, MessageContent, TextContent, UserId};
+use types::{CanisterId, MessageContent, ReferralCampaignTarget, TextContent, UserId};
 use user_index_canister::c2c_notify_events::{Response::*, *};
 use user_index_canister::Event;
 
@@ -31,15 +32,20 @@ fn handle_event(event: Event, state: &mut RuntimeState) {
     let caller: CanisterId = state.env.caller();
 
     match event {
-        Event::UserRegistered(ev) => process_new_user(
-            ev.principal,
-            ev.username,
-            ev.display_name,
-            ev.user_id,
-            ev.referred_by,
-            caller,
-            state,
-        ),
+        Event::UserRegistered(ev) => {
+            process_new_user(
+                ev.principal,
+                ev.username,
+                ev.display_name,
+                ev.user_id,
+                ev.referred_by,
+                caller,
+                state,
+            );
+            if let Some(code) = ev.referral_campaign {
+                process_campaign_registration(code, ev.user_id, state);
+            }
+        }
         Event::UserJoinedGroup(ev) => {
             state.push_event_to_local_user_index(
                 ev.user_id,
@@ -158,3 +164,45 @@ You can change your username at any time by clicking \"Profile settings\" from t
         state.data.user_referral_leaderboards.add_referral(referrer, now);
     }
 }
+
+fn process_campaign_registration(code: String, user_id: UserId, state: &mut RuntimeState) {
+    let now = state.env.now();
+    let Some(result) = state.data.referral_campaigns.record_registration(&code, user_id, now) else {
+        return;
+    };
+
+    let join_job = match result.target {
+        ReferralCampaignTarget::Group(group_id) => TimerJob::JoinUserToGroup(JoinUserToGroup {
+            user_id,
+            group_id,
+            attempt: 0,
+        }),
+        ReferralCampaignTarget::Community(community_id) => TimerJob::JoinUserToCommunity(JoinUserToCommunity {
+            user_id,
+            community_id,
+            attempt: 0,
+        }),
+    };
+    state.data.timer_jobs.enqueue_job(join_job, now, now);
+
+    for checkpoint in [RetentionCheckpoint::SevenDays, RetentionCheckpoint::ThirtyDays] {
+        state.data.timer_jobs.enqueue_job(
+            TimerJob::CheckReferralCampaignRetention(CheckReferralCampaignRetention {
+                code: code.clone(),
+                user_id,
+                registered: now,
+                checkpoint,
+                attempt: 0,
+            }),
+            now + checkpoint.after_registration(),
+            now,
+        );
+    }
+
+    if result.usage_cap_reached {
+        state.push_event_to_all_local_user_indexes(
+            LocalUserIndexEvent::ReferralCampaignEnded(ReferralCampaignEnded { code }),
+            None,
+        );
+    }
+}
@@ -12,6 +12,7 @@ use chat_events::deep_message_links;
 use modclub_canister::submitHtmlContent::Level;
 use types::{Chat, Message, MessageContent, MessageIndex};
 use user_index_canister::c2c_report_message::{Response::*, *};
+use user_index_canister::ModerationMode;
 
 #[update_msgpack(guard = "caller_is_user_canister_or_group_index")]
 #[trace]
@@ -45,13 +46,16 @@ fn c2c_report_message_impl(args: Args, state: &mut RuntimeState) -> Response {
     // Record the reported message against the sender's user record
     state.data.users.push_reported_message(args.message.sender, report_index);
 
-    // Queue submission of the report to Modclub
-    state.queue_modclub_submission(PendingModclubSubmission {
-        report_index,
-        title: construct_report_title(&args.message),
-        html_report: construct_html_report(args.chat_id, args.thread_root_message_index, args.message),
-        level: if state.data.test_mode { Level::simple } else { Level::normal },
-    });
+    // Queue submission of the report to Modclub, unless reports are only being moderated locally, in
+    // which case the report will appear in the platform moderators' queue
+    if state.data.moderation_mode != ModerationMode::Local {
+        state.queue_modclub_submission(PendingModclubSubmission {
+            report_index,
+            title: construct_report_title(&args.message),
+            html_report: construct_html_report(args.chat_id, args.thread_root_message_index, args.message),
+            level: if state.data.test_mode { Level::simple } else { Level::normal },
+        });
+    }
 
     Success
 }
@@ -0,0 +1,25 @@
+use crate::guards::caller_is_cycles_dispenser;
+use crate::{mutate_state, RuntimeState};
+use canister_api_macros::update_msgpack;
+use canister_tracing_macros::trace;
+use local_user_index_canister::{Event as LocalUserIndexEvent, OpenChatBotMessage};
+use user_index_canister::c2c_send_openchat_bot_messages::{Response::*, *};
+
+#[update_msgpack(guard = "caller_is_cycles_dispenser")]
+#[trace]
+fn c2c_send_openchat_bot_messages(args: Args) -> Response {
+    mutate_state(|state| c2c_send_openchat_bot_messages_impl(args, state))
+}
+
+fn c2c_send_openchat_bot_messages_impl(args: Args, state: &mut RuntimeState) -> Response {
+    for user_id in args.user_ids {
+        state.push_event_to_local_user_index(
+            user_id,
+            LocalUserIndexEvent::OpenChatBotMessage(Box::new(OpenChatBotMessage {
+                user_id,
+                message: args.message.clone(),
+            })),
+        );
+    }
+    Success
+}
@@ -0,0 +1,120 @@
+use crate::guards::caller_is_platform_moderator;
+use crate::model::appeals::DecideAppealResult;
+use crate::model::reported_messages::ReportedMessage;
+use crate::timer_job_types::{TimerJob, UnsuspendUser};
+use crate::{mutate_state, RuntimeState};
+use canister_tracing_macros::trace;
+use fire_and_forget_handler::FireAndForgetHandler;
+use ic_cdk_macros::update;
+use local_user_index_canister::{Event as LocalUserIndexEvent, OpenChatBotMessage};
+use msgpack::serialize_then_unwrap;
+use types::{Chat, MessageContent, TextContent};
+use user_index_canister::decide_appeal::{Response::*, *};
+use user_index_canister::{AppealOutcome, AppealSubject};
+
+#[update(guard = "caller_is_platform_moderator")]
+#[trace]
+fn decide_appeal(args: Args) -> Response {
+    mutate_state(|state| decide_appeal_impl(args, state))
+}
+
+fn decide_appeal_impl(args: Args, state: &mut RuntimeState) -> Response {
+    let caller = state.env.caller();
+    let decided_by = state.data.users.get_by_principal(&caller).unwrap().user_id;
+    let now = state.env.now();
+
+    let appeal = match state
+        .data
+        .appeals
+        .decide(args.appeal_id, args.outcome, decided_by, args.notes.clone(), now)
+    {
+        DecideAppealResult::Success(appeal) => appeal,
+        DecideAppealResult::NotFound => return AppealNotFound,
+        DecideAppealResult::AlreadyDecided => return AlreadyDecided,
+    };
+
+    let user_id = appeal.user_id;
+
+    let text = if args.outcome == AppealOutcome::Overturned {
+        // The report no longer counts against the user and the message is restored
+        if let AppealSubject::ReportedMessage(index) = appeal.subject {
+            state.data.reported_messages.overturn(index);
+            if let Some(report) = state.data.reported_messages.get(index) {
+                if !report.already_deleted {
+                    undelete_message(report, &mut state.data.fire_and_forget_handler);
+                }
+            }
+        }
+
+        // Only lift the user's current suspension if it is the one being appealed against, or if it
+        // was caused by the report being appealed against
+        let should_unsuspend = state
+            .data
+            .users
+            .get_by_user_id(&user_id)
+            .and_then(|u| u.suspension_details.as_ref())
+            .map_or(false, |s| appeal.caused_suspension(s));
+
+        if should_unsuspend {
+            state
+                .data
+                .timer_jobs
+                .enqueue_job(TimerJob::UnsuspendUser(UnsuspendUser { user_id }), now, now);
+        }
+
+        match appeal.subject {
+            AppealSubject::Suspension => "Your appeal against your suspension has been accepted and your suspension has been lifted.".to_string(),
+            AppealSubject::ReportedMessage(_) => "Your appeal against your message being judged as breaking the platform rules has been accepted and the violation has been removed from your record.".to_string(),
+        }
+    } else {
+        match appeal.subject {
+            AppealSubject::Suspension => "Your appeal against your suspension has been reviewed by the platform moderators and your suspension has been upheld.".to_string(),
+            AppealSubject::ReportedMessage(_) => "Your appeal against your message being judged as breaking the platform rules has been reviewed by the platform moderators and the decision has been upheld.".to_string(),
+        }
+    };
+
+    let text = match args.notes {
+        Some(notes) => format!("{text}\n\nNotes from the moderators: {notes}"),
+        None => text,
+    };
+
+    state.push_event_to_local_user_index(
+        user_id,
+        LocalUserIndexEvent::OpenChatBotMessage(Box::new(OpenChatBotMessage {
+            user_id,
+            message: MessageContent::Text(TextContent { text }),
+        })),
+    );
+
+    Success
+}
+
+fn undelete_message(report: &ReportedMessage, fire_and_forget_handler: &mut FireAndForgetHandler) {
+    match report.chat_id {
+        Chat::Group(group_id) => {
+            let args = group_canister::c2c_undelete_reported_message::Args {
+                thread_root_message_index: report.thread_root_message_index,
+                message_id: report.message_id,
+            };
+            fire_and_forget_handler.send(
+                group_id.into(),
+                "c2c_undelete_reported_message_msgpack".to_string(),
+                serialize_then_unwrap(args),
+            );
+        }
+        Chat::Channel(community_id, channel_id) => {
+            let args = community_canister::c2c_undelete_reported_message::Args {
+                channel_id,
+                thread_root_message_index: report.thread_root_message_index,
+                message_id: report.message_id,
+            };
+            fire_and_forget_handler.send(
+                community_id.into(),
+                "c2c_undelete_reported_message_msgpack".to_string(),
+                serialize_then_unwrap(args),
+            );
+        }
+        // Messages in direct chats are never deleted as a result of being reported
+        Chat::Direct(_) => {}
+    }
+}
@@ -0,0 +1,124 @@
+use crate::guards::caller_is_openchat_user;
+use crate::updates::pay_for_diamond_membership::{
+    apply_payment, charge_user, validate_price, ChargeUserError, MembershipPayment, PriceError,
+};
+use crate::{mutate_state, RuntimeState};
+use canister_tracing_macros::trace;
+use ic_cdk_macros::update;
+use ic_ledger_types::BlockIndex;
+use local_user_index_canister::{Event as LocalUserIndexEvent, OpenChatBotMessage};
+use tracing::error;
+use types::{DiamondMembershipPlanDuration, MessageContent, TextContent, UserId};
+use user_index_canister::gift_diamond_membership::{Response::*, *};
+
+const MAX_MESSAGE_LENGTH: u32 = 1000;
+
+#[update(guard = "caller_is_openchat_user")]
+#[trace]
+async fn gift_diamond_membership(args: Args) -> Response {
+    let gifted_by = match mutate_state(|state| prepare(&args, state)) {
+        Ok(user_id) => user_id,
+        Err(response) => return response,
+    };
+
+    let recipient = args.recipient;
+    let response = match charge_user(gifted_by, &args.token, args.expected_price_e8s).await {
+        Ok(block_index) => mutate_state(|state| process_charge(args, gifted_by, block_index, state)),
+        Err(ChargeUserError::InsufficientFunds(balance)) => InsufficientFunds(balance),
+        Err(ChargeUserError::TransferFailed(error)) => TransferFailed(error),
+        Err(ChargeUserError::InternalError(error)) => InternalError(error),
+    };
+    if !matches!(response, Success(_)) {
+        mutate_state(|state| {
+            if let Some(diamond_membership) = state.data.users.diamond_membership_details_mut(&recipient) {
+                diamond_membership.set_payment_in_progress(false);
+            }
+        });
+    }
+    response
+}
+
+// The recipient's membership is locked while the payment is in progress, so that it can't be extended
+// concurrently beyond the maximum allowed by `can_extend`
+fn prepare(args: &Args, state: &mut RuntimeState) -> Result<UserId, Response> {
+    let caller = state.env.caller();
+    let Some(gifted_by) = state.data.users.get_by_principal(&caller).map(|u| u.user_id) else {
+        return Err(UserNotFound);
+    };
+    if args.recipient == gifted_by {
+        return Err(CannotGiftToSelf);
+    }
+    if let Some(message) = &args.message {
+        if message.len() > MAX_MESSAGE_LENGTH as usize {
+            return Err(MessageTooLong(MAX_MESSAGE_LENGTH));
+        }
+    }
+    match state.data.users.get_by_user_id(&args.recipient) {
+        Some(recipient) if recipient.is_bot => return Err(RecipientIsBot),
+        Some(_) => {}
+        None => return Err(RecipientNotFound),
+    }
+
+    let now = state.env.now();
+    let diamond_membership = state.data.users.diamond_membership_details_mut(&args.recipient).unwrap();
+    if diamond_membership.payment_in_progress() {
+        Err(PaymentAlreadyInProgress)
+    } else if let Err(result) = diamond_membership.can_extend(now) {
+        Err(CannotExtend(result))
+    } else {
+        match validate_price(&args.token, args.duration, 1, args.expected_price_e8s) {
+            Ok(_) => {
+                diamond_membership.set_payment_in_progress(true);
+                Ok(gifted_by)
+            }
+            Err(PriceError::CurrencyNotSupported) => Err(CurrencyNotSupported),
+            Err(PriceError::PriceMismatch) => Err(PriceMismatch),
+        }
+    }
+}
+
+fn process_charge(args: Args, gifted_by: UserId, block_index: BlockIndex, state: &mut RuntimeState) -> Response {
+    let payment = MembershipPayment {
+        token: args.token,
+        amount_e8s: args.expected_price_e8s,
+        block_index,
+        duration: args.duration,
+        recurring: false,
+        manual_payment: true,
+        gifted_by: Some(gifted_by),
+    };
+
+    if let Some(result) = apply_payment(args.recipient, payment, false, state) {
+        state.data.diamond_membership_payment_metrics.gifted_payments_taken += 1;
+
+        let text = build_gift_message_text(gifted_by, args.duration, args.message);
+        state.push_event_to_local_user_index(
+            args.recipient,
+            LocalUserIndexEvent::OpenChatBotMessage(Box::new(OpenChatBotMessage {
+                user_id: args.recipient,
+                message: MessageContent::Text(TextContent { text }),
+            })),
+        );
+
+        Success(SuccessResult {
+            recipient_expires_at: result.expires_at,
+        })
+    } else {
+        error!(recipient = %args.recipient, %gifted_by, "Diamond membership gift payment taken, but recipient no longer exists");
+        RecipientNotFound
+    }
+}
+
+fn build_gift_message_text(gifted_by: UserId, duration: DiamondMembershipPlanDuration, message: Option<String>) -> String {
+    let duration_text = match duration {
+        DiamondMembershipPlanDuration::OneMonth => "1 month",
+        DiamondMembershipPlanDuration::ThreeMonths => "3 months",
+        DiamondMembershipPlanDuration::OneYear => "1 year",
+    };
+
+    let mut text = format!("@UserId({gifted_by}) has gifted you {duration_text} of Diamond membership!");
+    if let Some(message) = message.filter(|m| !m.trim().is_empty()) {
+        text.push_str(&format!("\n\n{message}"));
+    }
+    text
+}
@@ -1,7 +1,9 @@
 use crate::{
     guards::caller_is_modclub,
     model::{
-        reported_messages::{build_message_to_reporter, build_message_to_sender, RecordOutcomeResult, ReportOutcome},
+        reported_messages::{
+            build_message_to_reporter, build_message_to_sender, RecordOutcomeResult, ReportOutcome, ReportedMessage,
+        },
         user::{SuspensionDetails, SuspensionDuration},
     },
     mutate_state,
@@ -26,6 +28,7 @@ fn modclub_callback(args: Args) {
 async fn handle_modclub_callback(args: Args) {
     mutate_state(|state| {
         let now = state.env.now();
+        let report_index: u64 = args.sourceId.parse().unwrap();
         let reported_message = match state.data.reported_messages.record_outcome(args, now) {
             RecordOutcomeResult::Success(m) => m,
             RecordOutcomeResult::OutcomeExists(index) => {
@@ -38,53 +41,64 @@ async fn handle_modclub_callback(args: Args) {
             }
         };
 
-        if reported_message.rejected() {
-            // If the message has been judged to break the platform rules then delete it
-            if !reported_message.already_deleted {
-                match reported_message.chat_id {
-                    types::Chat::Group(group_id) => delete_group_message(
-                        group_id.into(),
-                        reported_message.thread_root_message_index,
-                        reported_message.message_id,
-                        &mut state.data.fire_and_forget_handler,
-                    ),
-                    types::Chat::Channel(community_id, channel_id) => delete_channel_message(
-                        community_id.into(),
-                        channel_id,
-                        reported_message.thread_root_message_index,
-                        reported_message.message_id,
-                        &mut state.data.fire_and_forget_handler,
-                    ),
-                    // But don't delete messages from direct chats - the reporter can delete it themselves
-                    types::Chat::Direct(_) => (),
-                }
-            }
+        handle_report_outcome(report_index, reported_message, state);
+    });
+}
 
-            // Suspend the sender for a day or permanently for repeat or egregious violations
-            if let Some(details) =
-                should_suspend_sender(reported_message.sender, reported_message.outcome.as_ref().unwrap(), state)
-            {
-                state.data.timer_jobs.enqueue_job(
-                    TimerJob::SetUserSuspended(SetUserSuspended {
-                        user_id: reported_message.sender,
-                        duration: details.duration.into(),
-                        reason: details.reason,
-                        suspended_by: details.suspended_by,
-                    }),
-                    now,
-                    now,
-                );
-            }
+// Shared by Modclub and local moderation once a report's outcome has been decided
+pub(crate) fn handle_report_outcome(report_index: u64, reported_message: ReportedMessage, state: &mut RuntimeState) {
+    let now = state.env.now();
 
-            // Inform the sender that their message has violated the platform rules
-            state.push_event_to_local_user_index(reported_message.sender, build_message_to_sender(&reported_message));
+    if reported_message.rejected() {
+        // If the message has been judged to break the platform rules then delete it
+        if !reported_message.already_deleted {
+            match reported_message.chat_id {
+                types::Chat::Group(group_id) => delete_group_message(
+                    group_id.into(),
+                    reported_message.thread_root_message_index,
+                    reported_message.message_id,
+                    &mut state.data.fire_and_forget_handler,
+                ),
+                types::Chat::Channel(community_id, channel_id) => delete_channel_message(
+                    community_id.into(),
+                    channel_id,
+                    reported_message.thread_root_message_index,
+                    reported_message.message_id,
+                    &mut state.data.fire_and_forget_handler,
+                ),
+                // But don't delete messages from direct chats - the reporter can delete it themselves
+                types::Chat::Direct(_) => (),
+            }
         }
 
-        // Inform each reporter of the outcome of their report
-        for reporter in reported_message.reports.keys() {
-            state.push_event_to_local_user_index(*reporter, build_message_to_reporter(&reported_message, *reporter));
+        // Suspend the sender for a day or permanently for repeat or egregious violations
+        if let Some(details) = should_suspend_sender(
+            reported_message.sender,
+            report_index,
+            reported_message.outcome.as_ref().unwrap(),
+            state,
+        ) {
+            state.data.timer_jobs.enqueue_job(
+                TimerJob::SetUserSuspended(SetUserSuspended {
+                    user_id: reported_message.sender,
+                    duration: details.duration.into(),
+                    reason: details.reason,
+                    suspended_by: details.suspended_by,
+                    report_index: details.report_index,
+                }),
+                now,
+                now,
+            );
         }
-    });
+
+        // Inform the sender that their message has violated the platform rules
+        state.push_event_to_local_user_index(reported_message.sender, build_message_to_sender(&reported_message));
+    }
+
+    // Inform each reporter of the outcome of their report
+    for reporter in reported_message.reports.keys() {
+        state.push_event_to_local_user_index(*reporter, build_message_to_reporter(&reported_message, *reporter));
+    }
 }
 
 fn delete_channel_message(
@@ -120,7 +134,12 @@ fn delete_group_message(
     fire_and_forget_handler.send(canister_id, "delete_messages".to_string(), Encode!(&args).unwrap());
 }
 
-fn should_suspend_sender(sender: UserId, outcome: &ReportOutcome, state: &RuntimeState) -> Option<SuspensionDetails> {
+fn should_suspend_sender(
+    sender: UserId,
+    report_index: u64,
+    outcome: &ReportOutcome,
+    state: &RuntimeState,
+) -> Option<SuspensionDetails> {
     if true {
         // TODO: Disable auto suspensions until the user canisters are released otherwise when a user is suspended
         // they won't be suspended from the communities they are in
@@ -160,6 +179,7 @@ fn should_suspend_sender(sender: UserId, outcome: &ReportOutcome, state: &Runtim
             duration,
             reason,
             suspended_by: OPENCHAT_BOT_USER_ID,
+            report_index: Some(report_index),
         })
     } else {
         None
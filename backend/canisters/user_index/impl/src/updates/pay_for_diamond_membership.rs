@@ -10,7 +10,7 @@ use local_user_index_canister::{DiamondMembershipPaymentReceived, Event};
 use rand::Rng;
 use storage_index_canister::add_or_update_users::UserConfig;
 use tracing::error;
-use types::{Cryptocurrency, DiamondMembershipPlanDuration, UserId, ICP};
+use types::{Cryptocurrency, DiamondMembershipDetails, DiamondMembershipPlanDuration, UserId, ICP};
 use user_index_canister::pay_for_diamond_membership::{Response::*, *};
 use utils::consts::SNS_GOVERNANCE_CANISTER_ID;
 use utils::time::DAY_IN_MS;
@@ -34,21 +34,11 @@ pub(crate) async fn pay_for_diamond_membership_impl(args: Args, user_id: UserId,
         return response;
     };
 
-    let c2c_args = user_canister::c2c_charge_user_account::Args {
-        ledger_canister_id: args.token.ledger_canister_id().unwrap(),
-        amount: ICP::from_e8s(args.expected_price_e8s - args.token.fee().unwrap() as u64),
-    };
-
-    let response = match user_canister_c2c_client::c2c_charge_user_account(user_id.into(), &c2c_args).await {
-        Ok(result) => match result {
-            user_canister::c2c_charge_user_account::Response::Success(block_index) => {
-                mutate_state(|state| process_charge(args, user_id, block_index, manual_payment, state))
-            }
-            user_canister::c2c_charge_user_account::Response::TransferError(error) => process_error(error),
-            user_canister::c2c_charge_user_account::Response::TransferErrorV2(error) => process_error_v2(error),
-            user_canister::c2c_charge_user_account::Response::InternalError(error) => InternalError(error),
-        },
-        Err(error) => InternalError(format!("{error:?}")),
+    let response = match charge_user(user_id, &args.token, args.expected_price_e8s).await {
+        Ok(block_index) => mutate_state(|state| process_charge(args, user_id, block_index, manual_payment, state)),
+        Err(ChargeUserError::InsufficientFunds(balance)) => InsufficientFunds(balance),
+        Err(ChargeUserError::TransferFailed(error)) => TransferFailed(error),
+        Err(ChargeUserError::InternalError(error)) => InternalError(error),
     };
     if !matches!(response, Success(_)) {
         mutate_state(|state| {
@@ -67,22 +57,14 @@ fn prepare(args: &Args, user_id: UserId, state: &mut RuntimeState) -> Result<(),
     } else if let Err(result) = diamond_membership.can_extend(state.env.now()) {
         Err(CannotExtend(result))
     } else {
-        match args.token {
-            Cryptocurrency::CHAT => {
-                if args.expected_price_e8s != args.duration.chat_price_e8s() {
-                    return Err(PriceMismatch);
-                }
+        match validate_price(&args.token, args.duration, 1, args.expected_price_e8s) {
+            Ok(_) => {
+                diamond_membership.set_payment_in_progress(true);
+                Ok(())
             }
-            Cryptocurrency::InternetComputer => {
-                if args.expected_price_e8s != args.duration.icp_price_e8s() {
-                    return Err(PriceMismatch);
-                }
-            }
-            _ => return Err(CurrencyNotSupported),
+            Err(PriceError::CurrencyNotSupported) => Err(CurrencyNotSupported),
+            Err(PriceError::PriceMismatch) => Err(PriceMismatch),
         }
-
-        diamond_membership.set_payment_in_progress(true);
-        Ok(())
     }
 }
 
@@ -93,125 +75,210 @@ fn process_charge(
     manual_payment: bool,
     state: &mut RuntimeState,
 ) -> Response {
+    let payment = MembershipPayment {
+        token: args.token,
+        amount_e8s: args.expected_price_e8s,
+        block_index,
+        duration: args.duration,
+        recurring: args.recurring,
+        manual_payment,
+        gifted_by: None,
+    };
+
+    if let Some(result) = apply_payment(user_id, payment, true, state) {
+        if manual_payment {
+            state.data.diamond_membership_payment_metrics.manual_payments_taken += 1;
+        } else {
+            state.data.diamond_membership_payment_metrics.recurring_payments_taken += 1;
+        }
+        Success(result)
+    } else {
+        error!(%user_id, "Diamond membership payment taken, but user no longer exists");
+        UserNotFound
+    }
+}
+
+pub(crate) struct MembershipPayment {
+    pub token: Cryptocurrency,
+    pub amount_e8s: u64,
+    pub block_index: BlockIndex,
+    pub duration: DiamondMembershipPlanDuration,
+    pub recurring: bool,
+    pub manual_payment: bool,
+    pub gifted_by: Option<UserId>,
+}
+
+// Extends the user's membership, then splits the payment between the user's referrer (if they are
+// eligible for a referral reward) and the treasury
+pub(crate) fn apply_payment(
+    user_id: UserId,
+    payment: MembershipPayment,
+    send_bot_message: bool,
+    state: &mut RuntimeState,
+) -> Option<DiamondMembershipDetails> {
     let share_with = referrer_to_share_payment(user_id, state);
 
-    if let Some(diamond_membership) = state.data.users.diamond_membership_details_mut(&user_id) {
-        let now = state.env.now();
-        let has_ever_been_diamond_member = diamond_membership.has_ever_been_diamond_member();
-
-        diamond_membership.add_payment(
-            args.token.clone(),
-            args.expected_price_e8s,
-            block_index,
-            args.duration,
-            args.recurring,
-            manual_payment,
-            now,
-        );
+    let diamond_membership = state.data.users.diamond_membership_details_mut(&user_id)?;
+    let now = state.env.now();
+    let has_ever_been_diamond_member = diamond_membership.has_ever_been_diamond_member();
 
-        let expires_at = diamond_membership.expires_at().unwrap();
-        let result = diamond_membership.hydrate(now).unwrap();
+    diamond_membership.add_payment(
+        payment.token.clone(),
+        payment.amount_e8s,
+        payment.block_index,
+        payment.duration,
+        payment.recurring,
+        payment.manual_payment,
+        payment.gifted_by,
+        now,
+    );
 
-        state.data.users.mark_updated(&user_id, now);
-        state.push_event_to_local_user_index(
-            user_id,
-            Event::DiamondMembershipPaymentReceived(DiamondMembershipPaymentReceived {
-                user_id,
-                timestamp: now,
-                expires_at,
-                token: args.token.clone(),
-                amount_e8s: args.expected_price_e8s,
-                block_index,
-                duration: args.duration,
-                recurring: args.recurring,
-                send_bot_message: true,
-            }),
-        );
-        crate::jobs::sync_events_to_local_user_index_canisters::start_job_if_required(state);
-
-        if let Some(user) = state.data.users.get_by_user_id(&user_id) {
-            state.data.storage_index_user_sync_queue.push(UserConfig {
-                user_id: user.principal,
-                byte_limit: ONE_GB,
-            });
-            crate::jobs::sync_users_to_storage_index::start_job_if_required(state);
-        }
+    let expires_at = diamond_membership.expires_at().unwrap();
+    let result = diamond_membership.hydrate(now).unwrap();
 
-        if args.recurring {
-            state.data.timer_jobs.enqueue_job(
-                TimerJob::RecurringDiamondMembershipPayment(RecurringDiamondMembershipPayment { user_id }),
-                expires_at.saturating_sub(DAY_IN_MS),
-                now,
-            );
-        }
+    state.data.users.mark_updated(&user_id, now);
+    state.push_event_to_local_user_index(
+        user_id,
+        Event::DiamondMembershipPaymentReceived(DiamondMembershipPaymentReceived {
+            user_id,
+            timestamp: now,
+            expires_at,
+            token: payment.token.clone(),
+            amount_e8s: payment.amount_e8s,
+            block_index: payment.block_index,
+            duration: payment.duration,
+            recurring: payment.recurring,
+            send_bot_message,
+        }),
+    );
+    crate::jobs::sync_events_to_local_user_index_canisters::start_job_if_required(state);
 
-        let transaction_fee = args.token.fee().unwrap() as u64;
+    if let Some(user) = state.data.users.get_by_user_id(&user_id) {
+        state.data.storage_index_user_sync_queue.push(UserConfig {
+            user_id: user.principal,
+            byte_limit: ONE_GB,
+        });
+        crate::jobs::sync_users_to_storage_index::start_job_if_required(state);
+    }
 
-        let mut amount_to_treasury = args.expected_price_e8s - (2 * transaction_fee);
+    if payment.recurring {
+        state.data.timer_jobs.enqueue_job(
+            TimerJob::RecurringDiamondMembershipPayment(RecurringDiamondMembershipPayment { user_id }),
+            expires_at.saturating_sub(DAY_IN_MS),
+            now,
+        );
+    }
 
-        let now_nanos = state.env.now_nanos();
+    let transaction_fee = payment.token.fee().unwrap() as u64;
 
-        if let Some(share_with) = share_with {
-            let amount_to_referrer = args.expected_price_e8s / 2;
-            amount_to_treasury = amount_to_treasury.saturating_sub(amount_to_referrer + transaction_fee);
+    let mut amount_to_treasury = payment.amount_e8s - (2 * transaction_fee);
 
-            let referral_payment = PendingPayment {
-                amount: amount_to_referrer,
-                currency: args.token.clone(),
-                timestamp: now_nanos,
-                recipient: share_with.into(),
-                memo: state.env.rng().gen(),
-                reason: PendingPaymentReason::ReferralReward,
-            };
-            state.queue_payment(referral_payment);
+    let now_nanos = state.env.now_nanos();
 
-            state.data.user_referral_leaderboards.add_reward(
-                share_with,
-                !has_ever_been_diamond_member,
-                amount_to_referrer,
-                now,
-            );
-        }
+    if let Some(share_with) = share_with {
+        let amount_to_referrer = payment.amount_e8s / 2;
+        amount_to_treasury = amount_to_treasury.saturating_sub(amount_to_referrer + transaction_fee);
 
-        let treasury_payment = PendingPayment {
-            amount: amount_to_treasury,
-            currency: args.token.clone(),
+        let referral_payment = PendingPayment {
+            amount: amount_to_referrer,
+            currency: payment.token.clone(),
             timestamp: now_nanos,
-            recipient: SNS_GOVERNANCE_CANISTER_ID,
+            recipient: share_with.into(),
             memo: state.env.rng().gen(),
-            reason: PendingPaymentReason::Treasury,
+            reason: PendingPaymentReason::ReferralReward,
         };
-        state.queue_payment(treasury_payment);
+        state.queue_payment(referral_payment);
 
-        if manual_payment {
-            state.data.diamond_membership_payment_metrics.manual_payments_taken += 1;
-        } else {
-            state.data.diamond_membership_payment_metrics.recurring_payments_taken += 1;
-        }
-        if let Some(amount) = state
+        state
+            .data
+            .user_referral_leaderboards
+            .add_reward(share_with, !has_ever_been_diamond_member, amount_to_referrer, now);
+    }
+
+    let treasury_payment = PendingPayment {
+        amount: amount_to_treasury,
+        currency: payment.token.clone(),
+        timestamp: now_nanos,
+        recipient: SNS_GOVERNANCE_CANISTER_ID,
+        memo: state.env.rng().gen(),
+        reason: PendingPaymentReason::Treasury,
+    };
+    state.queue_payment(treasury_payment);
+
+    if let Some(amount) = state
+        .data
+        .diamond_membership_payment_metrics
+        .amount_raised
+        .iter_mut()
+        .find(|(t, _)| *t == payment.token)
+        .map(|(_, amount)| amount)
+    {
+        *amount += payment.amount_e8s as u128;
+    } else {
+        state
             .data
             .diamond_membership_payment_metrics
             .amount_raised
-            .iter_mut()
-            .find(|(t, _)| *t == args.token)
-            .map(|(_, amount)| amount)
-        {
-            *amount += args.expected_price_e8s as u128;
-        } else {
-            state
-                .data
-                .diamond_membership_payment_metrics
-                .amount_raised
-                .push((args.token, args.expected_price_e8s as u128));
-        }
+            .push((payment.token, payment.amount_e8s as u128));
+    }
 
-        Success(result)
+    Some(result)
+}
+
+pub(crate) enum ChargeUserError {
+    InsufficientFunds(u64),
+    TransferFailed(String),
+    InternalError(String),
+}
+
+// Transfers `amount_e8s` (which includes the transfer fee) from the user's account into this canister
+pub(crate) async fn charge_user(
+    user_id: UserId,
+    token: &Cryptocurrency,
+    amount_e8s: u64,
+) -> Result<BlockIndex, ChargeUserError> {
+    let c2c_args = user_canister::c2c_charge_user_account::Args {
+        ledger_canister_id: token.ledger_canister_id().unwrap(),
+        amount: ICP::from_e8s(amount_e8s - token.fee().unwrap() as u64),
+    };
+
+    match user_canister_c2c_client::c2c_charge_user_account(user_id.into(), &c2c_args).await {
+        Ok(result) => match result {
+            user_canister::c2c_charge_user_account::Response::Success(block_index) => Ok(block_index),
+            user_canister::c2c_charge_user_account::Response::TransferError(error) => Err(process_error(error)),
+            user_canister::c2c_charge_user_account::Response::TransferErrorV2(error) => Err(process_error_v2(error)),
+            user_canister::c2c_charge_user_account::Response::InternalError(error) => {
+                Err(ChargeUserError::InternalError(error))
+            }
+        },
+        Err(error) => Err(ChargeUserError::InternalError(format!("{error:?}"))),
+    }
+}
+
+// Checks that the expected price matches the price of the plan in the given token
+pub(crate) fn validate_price(
+    token: &Cryptocurrency,
+    duration: DiamondMembershipPlanDuration,
+    quantity: u64,
+    expected_price_e8s: u64,
+) -> Result<(), PriceError> {
+    let price_e8s = match token {
+        Cryptocurrency::CHAT => duration.chat_price_e8s(),
+        Cryptocurrency::InternetComputer => duration.icp_price_e8s(),
+        _ => return Err(PriceError::CurrencyNotSupported),
+    };
+    if expected_price_e8s != price_e8s * quantity {
+        Err(PriceError::PriceMismatch)
     } else {
-        error!(%user_id, "Diamond membership payment taken, but user no longer exists");
-        UserNotFound
+        Ok(())
     }
 }
 
+pub(crate) enum PriceError {
+    CurrencyNotSupported,
+    PriceMismatch,
+}
+
 fn referrer_to_share_payment(user_id: UserId, state: &RuntimeState) -> Option<UserId> {
     if let Some(user) = state.data.users.get_by_user_id(&user_id) {
         let now = state.env.now();
@@ -234,16 +301,18 @@ fn referrer_to_share_payment(user_id: UserId, state: &RuntimeState) -> Option<Us
     None
 }
 
-fn process_error(transfer_error: TransferError) -> Response {
+fn process_error(transfer_error: TransferError) -> ChargeUserError {
     match transfer_error {
-        TransferError::InsufficientFunds { balance } => InsufficientFunds(balance.e8s()),
-        error => TransferFailed(format!("{error:?}")),
+        TransferError::InsufficientFunds { balance } => ChargeUserError::InsufficientFunds(balance.e8s()),
+        error => ChargeUserError::TransferFailed(format!("{error:?}")),
     }
 }
 
-fn process_error_v2(transfer_error: icrc1::transfer::TransferError) -> Response {
+fn process_error_v2(transfer_error: icrc1::transfer::TransferError) -> ChargeUserError {
     match transfer_error {
-        icrc1::transfer::TransferError::InsufficientFunds { balance } => InsufficientFunds(balance.0.try_into().unwrap()),
-        error => TransferFailed(format!("{error:?}")),
+        icrc1::transfer::TransferError::InsufficientFunds { balance } => {
+            ChargeUserError::InsufficientFunds(balance.0.try_into().unwrap())
+        }
+        error => ChargeUserError::TransferFailed(format!("{error:?}")),
     }
 }
@@ -0,0 +1,91 @@
+use crate::guards::caller_is_openchat_user;
+use crate::model::diamond_membership_seats::{SeatsPurchase, SEATS_EXPIRY};
+use crate::timer_job_types::{RefundExpiredDiamondMembershipSeats, TimerJob};
+use crate::updates::pay_for_diamond_membership::{charge_user, validate_price, ChargeUserError, PriceError};
+use crate::{mutate_state, read_state, RuntimeState};
+use canister_tracing_macros::trace;
+use ic_cdk::api::call::RejectionCode;
+use ic_cdk_macros::update;
+use ic_ledger_types::BlockIndex;
+use types::UserId;
+use user_index_canister::pay_for_diamond_membership_seats::{Response::*, *};
+
+const MAX_SEATS_PER_PURCHASE: u32 = 1000;
+
+#[update(guard = "caller_is_openchat_user")]
+#[trace]
+async fn pay_for_diamond_membership_seats(args: Args) -> Response {
+    let purchased_by = match read_state(|state| prepare(&args, state)) {
+        Ok(user_id) => user_id,
+        Err(response) => return response,
+    };
+
+    // Seats can only be bought for an existing community by one of its members
+    match community_canister_c2c_client::c2c_is_member(
+        args.community_id.into(),
+        &community_canister::c2c_is_member::Args { user_id: purchased_by },
+    )
+    .await
+    {
+        Ok(community_canister::c2c_is_member::Response::Yes) => {}
+        Ok(community_canister::c2c_is_member::Response::No) => return NotCommunityMember,
+        Err((RejectionCode::DestinationInvalid | RejectionCode::CanisterError, _)) => return CommunityNotFound,
+        Err(error) => return InternalError(format!("{error:?}")),
+    }
+
+    match charge_user(purchased_by, &args.token, args.expected_price_e8s).await {
+        Ok(block_index) => mutate_state(|state| process_charge(args, purchased_by, block_index, state)),
+        Err(ChargeUserError::InsufficientFunds(balance)) => InsufficientFunds(balance),
+        Err(ChargeUserError::TransferFailed(error)) => TransferFailed(error),
+        Err(ChargeUserError::InternalError(error)) => InternalError(error),
+    }
+}
+
+fn prepare(args: &Args, state: &RuntimeState) -> Result<UserId, Response> {
+    let caller = state.env.caller();
+    let Some(purchased_by) = state.data.users.get_by_principal(&caller).map(|u| u.user_id) else {
+        return Err(UserNotFound);
+    };
+    if args.seats == 0 || args.seats > MAX_SEATS_PER_PURCHASE {
+        return Err(InvalidSeatCount(MAX_SEATS_PER_PURCHASE));
+    }
+
+    match validate_price(&args.token, args.duration, args.seats as u64, args.expected_price_e8s) {
+        Ok(_) => Ok(purchased_by),
+        Err(PriceError::CurrencyNotSupported) => Err(CurrencyNotSupported),
+        Err(PriceError::PriceMismatch) => Err(PriceMismatch),
+    }
+}
+
+// The funds are held by this canister until each seat is assigned, at which point that seat's share
+// is distributed as if the member had paid for the membership themselves
+fn process_charge(args: Args, purchased_by: UserId, block_index: BlockIndex, state: &mut RuntimeState) -> Response {
+    let now = state.env.now();
+    state.data.timer_jobs.enqueue_job(
+        TimerJob::RefundExpiredDiamondMembershipSeats(RefundExpiredDiamondMembershipSeats {
+            community_id: args.community_id,
+            token: args.token.clone(),
+            block_index,
+        }),
+        now + SEATS_EXPIRY,
+        now,
+    );
+    state.data.diamond_membership_seats.add(
+        args.community_id,
+        SeatsPurchase {
+            purchased_by,
+            timestamp: now,
+            duration: args.duration,
+            token: args.token,
+            price_per_seat_e8s: args.expected_price_e8s / args.seats as u64,
+            block_index,
+            seats: args.seats,
+            remaining: args.seats,
+        },
+    );
+    state.data.diamond_membership_payment_metrics.seats_purchased += args.seats as u64;
+
+    Success(SuccessResult {
+        seats_available: state.data.diamond_membership_seats.available(&args.community_id),
+    })
+}
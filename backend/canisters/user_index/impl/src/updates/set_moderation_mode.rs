@@ -0,0 +1,23 @@
+use crate::guards::caller_is_platform_operator;
+use crate::{mutate_state, RuntimeState};
+use canister_tracing_macros::trace;
+use ic_cdk_macros::update;
+use user_index_canister::set_moderation_mode::{Response::*, *};
+
+#[update(guard = "caller_is_platform_operator")]
+#[trace]
+fn set_moderation_mode(args: Args) -> Response {
+    mutate_state(|state| set_moderation_mode_impl(args, state))
+}
+
+fn set_moderation_mode_impl(args: Args, state: &mut RuntimeState) -> Response {
+    if let Some(quorum) = args.local_quorum {
+        if quorum == 0 {
+            return InvalidQuorum;
+        }
+        state.data.local_moderation_quorum = quorum;
+    }
+
+    state.data.moderation_mode = args.mode;
+    Success
+}
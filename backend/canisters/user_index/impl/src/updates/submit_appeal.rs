@@ -0,0 +1,77 @@
+use crate::guards::caller_is_openchat_user;
+use crate::model::appeals::Appeal;
+use crate::{mutate_state, RuntimeState};
+use canister_tracing_macros::trace;
+use ic_cdk_macros::update;
+use msgpack::serialize_then_unwrap;
+use user_index_canister::submit_appeal::{Response::*, *};
+use user_index_canister::AppealSubject;
+
+const MAX_APPEAL_LENGTH: usize = 2000;
+
+#[update(guard = "caller_is_openchat_user")]
+#[trace]
+fn submit_appeal(args: Args) -> Response {
+    mutate_state(|state| submit_appeal_impl(args, state))
+}
+
+fn submit_appeal_impl(args: Args, state: &mut RuntimeState) -> Response {
+    let text = args.text.trim().to_string();
+    if text.is_empty() {
+        return TextEmpty;
+    } else if text.len() > MAX_APPEAL_LENGTH {
+        return TextTooLong(MAX_APPEAL_LENGTH as u32);
+    }
+
+    let caller = state.env.caller();
+    let user = match state.data.users.get_by_principal(&caller) {
+        Some(u) => u,
+        None => return UserNotFound,
+    };
+    let user_id = user.user_id;
+
+    let suspended_at = match args.subject {
+        AppealSubject::Suspension => match user.suspension_details.as_ref() {
+            Some(details) => Some(details.timestamp),
+            None => return NotSuspended,
+        },
+        AppealSubject::ReportedMessage(index) => match state.data.reported_messages.get(index) {
+            Some(report) if report.sender != user_id => return NotMessageSender,
+            Some(report) if !report.rejected() => return MessageNotJudgedAsViolating,
+            Some(_) => None,
+            None => return ReportNotFound,
+        },
+    };
+
+    if state.data.appeals.exists(user_id, args.subject, suspended_at) {
+        return AlreadyAppealed;
+    }
+
+    let now = state.env.now();
+    let appeal_id = state.data.appeals.add(Appeal {
+        user_id,
+        subject: args.subject,
+        text: text.clone(),
+        submitted: now,
+        suspended_at,
+        decision: None,
+    });
+
+    // Post the appeal into the platform moderators group so that it gets picked up
+    if let Some(group_id) = state.data.platform_moderators_group {
+        let subject = match args.subject {
+            AppealSubject::Suspension => "their suspension".to_string(),
+            AppealSubject::ReportedMessage(index) => format!("the outcome of report {index}"),
+        };
+        let c2c_args = group_canister::c2c_send_openchat_bot_message::Args {
+            text: format!("@UserId({user_id}) has appealed against {subject} (appeal {appeal_id}):\n\n{text}"),
+        };
+        state.data.fire_and_forget_handler.send(
+            group_id.into(),
+            "c2c_send_openchat_bot_message_msgpack".to_string(),
+            serialize_then_unwrap(c2c_args),
+        );
+    }
+
+    Success(SuccessResult { appeal_id })
+}
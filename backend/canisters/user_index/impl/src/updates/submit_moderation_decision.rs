@@ -0,0 +1,56 @@
+use crate::guards::caller_is_platform_moderator;
+use crate::model::reported_messages::{AddLocalVoteResult, LocalModerationVote};
+use crate::updates::modclub_callback::handle_report_outcome;
+use crate::{mutate_state, RuntimeState};
+use canister_tracing_macros::trace;
+use ic_cdk_macros::update;
+use user_index_canister::submit_moderation_decision::{Response::*, *};
+use user_index_canister::ModerationMode;
+
+#[update(guard = "caller_is_platform_moderator")]
+#[trace]
+fn submit_moderation_decision(args: Args) -> Response {
+    mutate_state(|state| submit_moderation_decision_impl(args, state))
+}
+
+fn submit_moderation_decision_impl(args: Args, state: &mut RuntimeState) -> Response {
+    if state.data.moderation_mode == ModerationMode::Modclub {
+        return LocalModerationDisabled;
+    }
+
+    let rule_count = state.data.reported_messages.rule_descriptions().len();
+    if let Some(rule_index) = args.violated_rules.iter().find(|r| **r as usize >= rule_count) {
+        return InvalidRule(*rule_index);
+    }
+
+    let caller = state.env.caller();
+    let moderator = state.data.users.get_by_principal(&caller).unwrap().user_id;
+    let now = state.env.now();
+
+    let vote = LocalModerationVote {
+        breaks_rules: args.breaks_rules,
+        violated_rules: if args.breaks_rules {
+            args.violated_rules.into_iter().map(|r| r as usize).collect()
+        } else {
+            Vec::new()
+        },
+        timestamp: now,
+    };
+
+    match state.data.reported_messages.add_local_vote(
+        args.report_index,
+        moderator,
+        vote,
+        state.data.local_moderation_quorum,
+        now,
+    ) {
+        AddLocalVoteResult::Success => Success(SuccessResult { decided: false }),
+        AddLocalVoteResult::Decided(reported_message) => {
+            handle_report_outcome(args.report_index, reported_message, state);
+            Success(SuccessResult { decided: true })
+        }
+        AddLocalVoteResult::ReportNotFound => ReportNotFound,
+        AddLocalVoteResult::OutcomeExists => OutcomeAlreadyDecided,
+        AddLocalVoteResult::AlreadyVoted => AlreadyVoted,
+    }
+}
@@ -15,7 +15,7 @@ async fn suspend_user(args: Args) -> Response {
         Ok(ok) => ok,
     };
 
-    suspend_user_impl(args.user_id, args.duration, args.reason, suspended_by).await
+    suspend_user_impl(args.user_id, args.duration, args.reason, suspended_by, None).await
 }
 
 pub(crate) async fn suspend_user_impl(
@@ -23,6 +23,7 @@ pub(crate) async fn suspend_user_impl(
     duration: Option<Milliseconds>,
     reason: String,
     suspended_by: UserId,
+    report_index: Option<u64>,
 ) -> Response {
     let c2c_args = user_canister::c2c_set_user_suspended::Args { suspended: true };
     match user_canister_c2c_client::c2c_set_user_suspended(user_id.into(), &c2c_args).await {
@@ -35,6 +36,7 @@ pub(crate) async fn suspend_user_impl(
                     result.groups,
                     result.communities,
                     suspended_by,
+                    report_index,
                     state,
                 )
             });
@@ -62,6 +64,7 @@ fn commit(
     groups: Vec<ChatId>,
     communities: Vec<CommunityId>,
     suspended_by: UserId,
+    report_index: Option<u64>,
     state: &mut RuntimeState,
 ) {
     let now = state.env.now();
@@ -94,7 +97,7 @@ fn commit(
     state
         .data
         .users
-        .suspend_user(&user_id, duration, reason.clone(), suspended_by, now);
+        .suspend_user(&user_id, duration, reason.clone(), suspended_by, report_index, now);
 
     // If the user is only suspended for a specified duration, schedule them to be unsuspended
     if let Some(ms) = duration {
@@ -32,6 +32,23 @@ pub enum MakeOrderResponse {
 pub struct MakeOrderSuccess {
     pub status: OrderStatus,
     pub txid: Vec<u8>,
+    pub filled: Vec<OrderFilled>,
+}
+
+// token0 is the base token and token1 is the quote token
+#[derive(CandidType, Deserialize)]
+pub struct OrderFilled {
+    #[serde(rename = "token0Value")]
+    pub token0_value: BalanceChange,
+    #[serde(rename = "token1Value")]
+    pub token1_value: BalanceChange,
+}
+
+#[derive(CandidType, Deserialize)]
+pub enum BalanceChange {
+    DebitRecord(Nat),
+    CreditRecord(Nat),
+    NoChange,
 }
 
 #[derive(CandidType, Deserialize, Debug)]
@@ -55,6 +72,8 @@ pub enum MakeOrderErrorCode {
 pub enum ICDexOrderType {
     #[serde(rename = "LMT")]
     Limit,
+    #[serde(rename = "FOK")]
+    FillOrKill,
 }
 
 #[derive(CandidType, Deserialize, Debug)]
@@ -82,6 +101,14 @@ pub struct StatsResponse {
     pub price: f64,
 }
 
+#[derive(CandidType, Deserialize)]
+pub struct DexSetting {
+    #[serde(rename = "UNIT_SIZE")]
+    pub unit_size: Nat,
+    #[serde(rename = "TRADING_FEE")]
+    pub trading_fee: Nat, // In parts per million
+}
+
 #[derive(CandidType, Debug)]
 pub enum Side {
     Buy,
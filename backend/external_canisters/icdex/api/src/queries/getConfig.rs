@@ -0,0 +1,4 @@
+use crate::DexSetting;
+
+pub type Args = ();
+pub type Response = (DexSetting,);
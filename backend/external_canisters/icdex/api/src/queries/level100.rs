@@ -0,0 +1,5 @@
+use crate::Orderbook;
+use candid::Nat;
+
+pub type Args = ();
+pub type Response = (Nat, Orderbook);
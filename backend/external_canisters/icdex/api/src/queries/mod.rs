@@ -1,5 +1,8 @@
 #[allow(non_snake_case)]
+pub mod getConfig;
+#[allow(non_snake_case)]
 pub mod getTxAccount;
 pub mod level10;
+pub mod level100;
 pub mod pending;
 pub mod stats;
@@ -3,8 +3,10 @@ use canister_client::generate_candid_c2c_call_tuple_args;
 use icdex_canister::*;
 
 // Queries
+generate_candid_c2c_call_tuple_args!(getConfig);
 generate_candid_c2c_call_tuple_args!(getTxAccount);
 generate_candid_c2c_call_tuple_args!(level10);
+generate_candid_c2c_call_tuple_args!(level100);
 generate_candid_c2c_call_tuple_args!(pending);
 generate_candid_c2c_call_tuple_args!(stats);
 
@@ -0,0 +1,33 @@
+use candid::{CandidType, Nat};
+use icrc_ledger_types::icrc1::account::Account;
+use serde::Deserialize;
+
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct Args {
+    pub start: Nat,
+    pub length: Nat,
+}
+
+// Only the fields which we use are included, any others returned by the ledger are ignored.
+// Transactions which have been moved to the ledger's archive canisters are not returned.
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct Response {
+    pub log_length: Nat,
+    pub first_index: Nat,
+    pub transactions: Vec<Transaction>,
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct Transaction {
+    pub kind: String,
+    pub transfer: Option<Transfer>,
+    pub timestamp: u64,
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct Transfer {
+    pub amount: Nat,
+    pub from: Account,
+    pub to: Account,
+    pub fee: Option<Nat>,
+}
@@ -1,3 +1,4 @@
+pub mod get_transactions;
 pub mod icrc1_balance_of;
 pub mod icrc1_decimals;
 pub mod icrc1_fee;
@@ -5,3 +6,4 @@ pub mod icrc1_metadata;
 pub mod icrc1_name;
 pub mod icrc1_supported_standards;
 pub mod icrc1_symbol;
+pub mod icrc2_allowance;
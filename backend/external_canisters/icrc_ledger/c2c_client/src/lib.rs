@@ -2,6 +2,7 @@ use canister_client::{generate_candid_c2c_call, generate_candid_c2c_call_no_args
 use icrc_ledger_canister::*;
 
 // Queries
+generate_candid_c2c_call!(get_transactions);
 generate_candid_c2c_call!(icrc1_balance_of);
 generate_candid_c2c_call_no_args!(icrc1_decimals);
 generate_candid_c2c_call_no_args!(icrc1_fee);
@@ -9,6 +10,7 @@ generate_candid_c2c_call_no_args!(icrc1_metadata);
 generate_candid_c2c_call_no_args!(icrc1_name);
 generate_candid_c2c_call_no_args!(icrc1_supported_standards);
 generate_candid_c2c_call_no_args!(icrc1_symbol);
+generate_candid_c2c_call!(icrc2_allowance);
 
 // Updates
 generate_candid_c2c_call!(icrc1_transfer);
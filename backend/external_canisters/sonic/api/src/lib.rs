@@ -30,3 +30,11 @@ pub struct PairInfoExt {
     pub total_supply: Nat,
     pub lptoken: String,
 }
+
+// Only the fields needed are included, for swaps `amount0` is the amount in and `amount1` the amount out
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct TxRecord {
+    pub index: Nat,
+    pub amount0: Nat,
+    pub amount1: Nat,
+}
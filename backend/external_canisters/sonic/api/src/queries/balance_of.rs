@@ -0,0 +1,4 @@
+use candid::{Nat, Principal};
+
+pub type Args = (String, Principal);
+pub type Response = (Nat,);
@@ -0,0 +1,6 @@
+use crate::TxRecord;
+use candid::Nat;
+
+// https://docs.sonic.ooo/dev/swaps-api/query-calls#get-transaction-gettransaction
+pub type Args = (Nat,);
+pub type Response = (TxRecord,);
@@ -1 +1,3 @@
+pub mod balance_of;
 pub mod get_pair;
+pub mod get_transaction;
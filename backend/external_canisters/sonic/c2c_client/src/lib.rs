@@ -2,7 +2,9 @@ use canister_client::{generate_candid_c2c_call_no_args, generate_candid_c2c_call
 use sonic_canister::*;
 
 // Queries
+generate_candid_c2c_call_tuple_args!(balance_of, balanceOf);
 generate_candid_c2c_call_tuple_args!(get_pair, getPair);
+generate_candid_c2c_call_tuple_args!(get_transaction, getTransaction);
 
 // Updates
 generate_candid_c2c_call_tuple_args!(deposit);
@@ -0,0 +1,176 @@
+use crate::env::ENV;
+use crate::rng::{random_message_id, random_string};
+use crate::utils::tick_many;
+use crate::{client, TestEnv};
+use std::ops::Deref;
+use std::time::Duration;
+use types::{ChatReportAction, MessageContentInitial, TextContent};
+
+#[test]
+fn reported_message_handled_by_group_moderators() {
+    let mut wrapper = ENV.deref().get();
+    let TestEnv {
+        env,
+        canister_ids,
+        controller,
+        ..
+    } = wrapper.env();
+
+    let owner = client::register_diamond_user(env, canister_ids, *controller);
+    let reporter = client::register_diamond_user(env, canister_ids, *controller);
+    let sender = client::register_diamond_user(env, canister_ids, *controller);
+
+    let group_id = client::user::happy_path::create_group(env, &owner, &random_string(), true, true);
+    client::local_user_index::happy_path::join_group(env, reporter.principal, canister_ids.local_user_index, group_id);
+    client::local_user_index::happy_path::join_group(env, sender.principal, canister_ids.local_user_index, group_id);
+
+    let message_id = random_message_id();
+    client::group::happy_path::send_text_message(env, &sender, group_id, None, "Spam", Some(message_id));
+
+    let report_response = client::group::report_message(
+        env,
+        reporter.principal,
+        group_id.into(),
+        &group_canister::report_message::Args {
+            thread_root_message_index: None,
+            message_id,
+            delete: false,
+        },
+    );
+    assert!(matches!(report_response, group_canister::report_message::Response::Success));
+
+    // Regular members can't see the reports inbox
+    let reports_response = client::group::chat_reports(
+        env,
+        reporter.principal,
+        group_id.into(),
+        &group_canister::chat_reports::Args { include_resolved: true },
+    );
+    assert!(matches!(
+        reports_response,
+        group_canister::chat_reports::Response::NotAuthorized
+    ));
+
+    let result = match client::group::chat_reports(
+        env,
+        owner.principal,
+        group_id.into(),
+        &group_canister::chat_reports::Args { include_resolved: false },
+    ) {
+        group_canister::chat_reports::Response::Success(r) => r,
+        response => panic!("'chat_reports' error: {response:?}"),
+    };
+    assert_eq!(result.reports.len(), 1);
+    let report = &result.reports[0];
+    assert_eq!(report.message_id, message_id);
+    assert_eq!(report.sender, sender.user_id);
+    assert_eq!(report.reported_by, vec![reporter.user_id]);
+    assert_eq!(result.member_report_counts.len(), 1);
+    assert_eq!(result.member_report_counts[0].user_id, sender.user_id);
+    assert_eq!(result.member_report_counts[0].reports, 1);
+
+    let resolve_response = client::group::resolve_report(
+        env,
+        owner.principal,
+        group_id.into(),
+        &group_canister::resolve_report::Args {
+            report_id: report.report_id,
+            action: ChatReportAction::TimeoutMember(60 * 60 * 1000),
+        },
+    );
+    assert!(
+        matches!(resolve_response, group_canister::resolve_report::Response::Success),
+        "{resolve_response:?}"
+    );
+
+    // The sender has been timed out so can't send messages
+    let send_message_response = client::group::send_message_v2(
+        env,
+        sender.principal,
+        group_id.into(),
+        &group_canister::send_message_v2::Args {
+            thread_root_message_index: None,
+            message_id: random_message_id(),
+            content: MessageContentInitial::Text(TextContent {
+                text: "More spam".to_string(),
+            }),
+            sender_name: sender.username(),
+            sender_display_name: None,
+            replies_to: None,
+            mentioned: Vec::new(),
+            forwarding: false,
+            rules_accepted: None,
+            correlation_id: 0,
+        },
+    );
+    assert!(matches!(
+        send_message_response,
+        group_canister::send_message_v2::Response::UserSuspended
+    ));
+
+    let result = match client::group::chat_reports(
+        env,
+        owner.principal,
+        group_id.into(),
+        &group_canister::chat_reports::Args { include_resolved: false },
+    ) {
+        group_canister::chat_reports::Response::Success(r) => r,
+        response => panic!("'chat_reports' error: {response:?}"),
+    };
+    assert!(result.reports.is_empty());
+}
+
+#[test]
+fn unhandled_report_escalated_to_platform_automatically() {
+    let mut wrapper = ENV.deref().get();
+    let TestEnv {
+        env,
+        canister_ids,
+        controller,
+        ..
+    } = wrapper.env();
+
+    let owner = client::register_diamond_user(env, canister_ids, *controller);
+    let reporter = client::register_diamond_user(env, canister_ids, *controller);
+    let sender = client::register_diamond_user(env, canister_ids, *controller);
+
+    let group_id = client::user::happy_path::create_group(env, &owner, &random_string(), true, true);
+    client::local_user_index::happy_path::join_group(env, reporter.principal, canister_ids.local_user_index, group_id);
+    client::local_user_index::happy_path::join_group(env, sender.principal, canister_ids.local_user_index, group_id);
+
+    let message_id = random_message_id();
+    client::group::happy_path::send_text_message(env, &sender, group_id, None, "Spam", Some(message_id));
+
+    let report_response = client::group::report_message(
+        env,
+        reporter.principal,
+        group_id.into(),
+        &group_canister::report_message::Args {
+            thread_root_message_index: None,
+            message_id,
+            delete: false,
+        },
+    );
+    assert!(matches!(report_response, group_canister::report_message::Response::Success));
+
+    // The owner doesn't handle the report, so once the timeout has passed it is escalated
+    env.advance_time(Duration::from_secs(3 * 24 * 60 * 60 + 1));
+    tick_many(env, 5);
+
+    let result = match client::group::chat_reports(
+        env,
+        owner.principal,
+        group_id.into(),
+        &group_canister::chat_reports::Args { include_resolved: true },
+    ) {
+        group_canister::chat_reports::Response::Success(r) => r,
+        response => panic!("'chat_reports' error: {response:?}"),
+    };
+    assert_eq!(result.reports.len(), 1);
+    let resolution = result.reports[0]
+        .resolution
+        .as_ref()
+        .expect("Report should have been escalated");
+    assert!(matches!(resolution.action, ChatReportAction::EscalateToPlatform));
+    assert_eq!(resolution.resolved_by, reporter.user_id);
+}
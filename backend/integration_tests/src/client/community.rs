@@ -67,6 +67,7 @@ pub mod happy_path {
                 permissions_v2: None,
                 events_ttl: None,
                 gate: None,
+                forum_tags: None,
             },
         );
 
@@ -114,6 +115,7 @@ pub mod happy_path {
                 forwarding: false,
                 community_rules_accepted: None,
                 channel_rules_accepted: None,
+                forum_post: None,
             },
         );
 
@@ -2,6 +2,7 @@ use crate::{generate_query_call, generate_update_call};
 use group_canister::*;
 
 // Queries
+generate_query_call!(chat_reports);
 generate_query_call!(events);
 generate_query_call!(events_by_index);
 generate_query_call!(events_window);
@@ -10,6 +11,7 @@ generate_query_call!(selected_initial);
 generate_query_call!(selected_updates_v2);
 generate_query_call!(summary);
 generate_query_call!(summary_updates);
+generate_query_call!(typing);
 
 // Updates
 generate_update_call!(add_reaction);
@@ -24,7 +26,10 @@ generate_update_call!(pin_message_v2);
 generate_update_call!(register_poll_vote);
 generate_update_call!(remove_participant);
 generate_update_call!(remove_reaction);
+generate_update_call!(report_message);
+generate_update_call!(resolve_report);
 generate_update_call!(send_message_v2);
+generate_update_call!(set_typing);
 generate_update_call!(toggle_mute_notifications);
 generate_update_call!(unblock_user);
 generate_update_call!(undelete_messages);
@@ -120,6 +125,7 @@ pub mod happy_path {
                 thread_root_message_index: None,
                 message_index,
                 poll_option,
+                ranked_options: None,
                 operation: VoteOperation::RegisterVote,
                 correlation_id: 0,
             },
@@ -2,12 +2,14 @@ use crate::{generate_query_call, generate_update_call};
 use candid::Nat;
 use icrc_ledger_types::icrc1::account::Account;
 use icrc_ledger_types::icrc1::transfer::{NumTokens, TransferArg, TransferError};
+use icrc_ledger_types::icrc2::approve::{ApproveArgs, ApproveError};
 
 // Queries
 generate_query_call!(icrc1_balance_of);
 
 // Updates
 generate_update_call!(icrc1_transfer);
+generate_update_call!(icrc2_approve);
 
 pub mod icrc1_balance_of {
     use super::*;
@@ -26,6 +28,13 @@ pub mod icrc1_transfer {
     pub type Response = Result<Nat, TransferError>;
 }
 
+pub mod icrc2_approve {
+    use super::*;
+
+    pub type Args = ApproveArgs;
+    pub type Response = Result<Nat, ApproveError>;
+}
+
 pub mod happy_path {
     use super::*;
     use candid::Principal;
@@ -59,6 +68,34 @@ pub mod happy_path {
         .unwrap()
     }
 
+    pub fn approve(
+        env: &mut PocketIc,
+        sender: Principal,
+        ledger_canister_id: CanisterId,
+        spender: Principal,
+        amount: u128,
+    ) -> BlockIndex {
+        icrc2_approve(
+            env,
+            sender,
+            ledger_canister_id,
+            &icrc2_approve::Args {
+                from_subaccount: None,
+                spender: Account::from(spender),
+                amount: amount.into(),
+                expected_allowance: None,
+                expires_at: None,
+                fee: None,
+                memo: None,
+                created_at_time: None,
+            },
+        )
+        .unwrap()
+        .0
+        .try_into()
+        .unwrap()
+    }
+
     pub fn balance_of(env: &PocketIc, icp_ledger_canister_id: CanisterId, principal: Principal) -> u64 {
         icrc1_balance_of(
             env,
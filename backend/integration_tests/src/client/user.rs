@@ -7,11 +7,15 @@ generate_query_call!(events_by_index);
 generate_query_call!(initial_state);
 generate_query_call!(saved_crypto_accounts);
 generate_query_call!(updates);
+generate_query_call!(wallet_transactions);
 
 // Updates
+generate_update_call!(accept_p2p_swap);
 generate_update_call!(add_reaction);
+generate_update_call!(approve_transfer);
 generate_update_call!(block_user);
 generate_update_call!(cancel_message_reminder);
+generate_update_call!(cancel_p2p_swap);
 generate_update_call!(create_community);
 generate_update_call!(create_group);
 generate_update_call!(delete_community);
@@ -24,6 +28,7 @@ generate_update_call!(leave_group);
 generate_update_call!(mark_read);
 generate_update_call!(mute_notifications);
 generate_update_call!(remove_reaction);
+generate_update_call!(retry_failed_p2p_swap_transfers);
 generate_update_call!(save_crypto_account);
 generate_update_call!(send_message_v2);
 generate_update_call!(send_message_with_transfer_to_channel);
@@ -4,6 +4,7 @@ use user_index_canister::*;
 // Queries
 generate_query_call!(check_username);
 generate_query_call!(current_user);
+generate_query_call!(moderation_queue);
 generate_query_call!(search);
 generate_query_call!(platform_moderators);
 generate_query_call!(platform_moderators_group);
@@ -13,12 +14,16 @@ generate_query_call!(users_v2);
 // Updates
 generate_update_call!(add_local_user_index_canister);
 generate_update_call!(add_platform_moderator);
+generate_update_call!(add_platform_operator);
 generate_update_call!(assign_platform_moderators_group);
 generate_update_call!(c2c_register_bot);
 generate_update_call!(pay_for_diamond_membership);
+generate_update_call!(pay_for_diamond_membership_seats);
 generate_update_call!(remove_platform_moderator);
 generate_update_call!(set_display_name);
+generate_update_call!(set_moderation_mode);
 generate_update_call!(set_username);
+generate_update_call!(submit_moderation_decision);
 generate_update_call!(suspend_user);
 generate_update_call!(unsuspend_user);
 generate_update_call!(upgrade_local_user_index_canister_wasm);
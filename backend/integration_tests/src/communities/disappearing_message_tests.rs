@@ -33,6 +33,9 @@ fn disappearing_messages_in_channel() {
             events_ttl: OptionUpdate::SetToSome(1000),
             gate: OptionUpdate::NoChange,
             public: None,
+            forum_tags: None,
+            user_group_permissions: None,
+            audience_user_groups: None,
         },
     );
 
@@ -78,6 +81,9 @@ fn disappearing_messages_in_channel() {
             events_ttl: OptionUpdate::SetToNone,
             gate: OptionUpdate::NoChange,
             public: None,
+            forum_tags: None,
+            user_group_permissions: None,
+            audience_user_groups: None,
         },
     );
 
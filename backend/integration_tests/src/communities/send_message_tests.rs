@@ -11,7 +11,7 @@ use std::ops::Deref;
 use std::time::Duration;
 use types::{
     CanisterId, ChannelId, ChatEvent, CommunityId, CryptoContent, CryptoTransaction, Cryptocurrency, MessageContent,
-    MessageContentInitial, OptionUpdate, PrizeContentInitial, TextContent, UpdatedRules, Version,
+    MessageContentInitial, OptionUpdate, PrizeContentInitial, PrizeDistribution, TextContent, UpdatedRules, Version,
 };
 
 #[test]
@@ -93,6 +93,7 @@ fn send_crypto_in_channel() {
             mentioned: Vec::new(),
             community_rules_accepted: None,
             channel_rules_accepted: None,
+            forum_post: None,
         },
     );
 
@@ -153,6 +154,8 @@ fn send_prize_in_channel() {
                 prizes,
                 end_date: now_millis(env) + 1000,
                 diamond_only: false,
+                distribution: PrizeDistribution::FirstComeFirstServed,
+                eligibility: Vec::new(),
             }),
             sender_name: user1.username(),
             sender_display_name: None,
@@ -160,6 +163,7 @@ fn send_prize_in_channel() {
             mentioned: Vec::new(),
             community_rules_accepted: None,
             channel_rules_accepted: None,
+            forum_post: None,
         },
     );
 
@@ -656,6 +660,7 @@ fn send_dummy_message_with_rules(
             forwarding: false,
             community_rules_accepted,
             channel_rules_accepted,
+            forum_post: None,
         },
     )
 }
@@ -714,6 +719,9 @@ fn set_channel_rules(env: &mut PocketIc, sender: Principal, community_id: Commun
         events_ttl: OptionUpdate::NoChange,
         gate: OptionUpdate::NoChange,
         public: None,
+        forum_tags: None,
+        user_group_permissions: None,
+        audience_user_groups: None,
         channel_id,
     };
 
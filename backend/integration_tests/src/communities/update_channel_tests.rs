@@ -40,6 +40,9 @@ fn make_private_channel_public_succeeds() {
             events_ttl: OptionUpdate::NoChange,
             gate: OptionUpdate::NoChange,
             public: Some(true),
+            forum_tags: None,
+            user_group_permissions: None,
+            audience_user_groups: None,
         },
     );
 
@@ -6,7 +6,7 @@ use candid::Principal;
 use pocket_ic::PocketIc;
 use std::ops::Deref;
 use std::time::Duration;
-use types::{ChannelId, CommunityId};
+use types::{ChannelId, CommunityId, OptionUpdate};
 
 #[test]
 fn create_user_group_succeeds() {
@@ -188,6 +188,74 @@ fn delete_user_group_succeeds() {
     assert_eq!(summary_updates.unwrap().user_groups_deleted, vec![user_group_id]);
 }
 
+#[test]
+fn deleting_audience_user_group_keeps_channel_members() {
+    let mut wrapper = ENV.deref().get();
+    let TestEnv {
+        env,
+        canister_ids,
+        controller,
+        ..
+    } = wrapper.env();
+
+    let TestData {
+        user1,
+        user2,
+        community_id,
+        ..
+    } = init_test_data(env, canister_ids, *controller);
+
+    let channel_id = client::community::happy_path::create_channel(env, user1.principal, community_id, false, random_string());
+    let user_group_id = client::community::happy_path::create_user_group(
+        env,
+        user1.principal,
+        community_id,
+        random_string(),
+        vec![user2.user_id],
+    );
+
+    client::community::happy_path::update_channel(
+        env,
+        user1.principal,
+        community_id,
+        &community_canister::update_channel::Args {
+            channel_id,
+            name: None,
+            description: None,
+            rules: None,
+            avatar: OptionUpdate::NoChange,
+            permissions_v2: None,
+            events_ttl: OptionUpdate::NoChange,
+            gate: OptionUpdate::NoChange,
+            public: None,
+            forum_tags: None,
+            user_group_permissions: None,
+            audience_user_groups: Some(vec![user_group_id]),
+        },
+    );
+
+    let channel_summary = client::community::happy_path::channel_summary(env, &user2, community_id, channel_id);
+    assert!(channel_summary.membership.is_some());
+
+    let delete_response = client::community::delete_user_groups(
+        env,
+        user1.principal,
+        community_id.into(),
+        &community_canister::delete_user_groups::Args {
+            user_group_ids: vec![user_group_id],
+        },
+    );
+
+    assert!(matches!(
+        delete_response,
+        community_canister::delete_user_groups::Response::Success
+    ));
+
+    // The channel no longer has an audience, so its existing members are not removed
+    let channel_summary = client::community::happy_path::channel_summary(env, &user2, community_id, channel_id);
+    assert!(channel_summary.membership.is_some());
+}
+
 #[test]
 fn send_message_mentioning_user_group() {
     let mut wrapper = ENV.deref().get();
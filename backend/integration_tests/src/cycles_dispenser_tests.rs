@@ -4,6 +4,7 @@ use crate::{client, TestEnv, T};
 use ic_ledger_types::Tokens;
 use std::ops::Deref;
 use std::time::Duration;
+use types::OptionUpdate;
 
 #[test]
 fn icp_is_burned_into_cycles() {
@@ -35,6 +36,8 @@ fn icp_is_burned_into_cycles() {
             min_interval: None,
             max_top_up_amount: None,
             icp_burn_amount: Some(Tokens::from_e8s(10_000_000_000)),
+            alert_config: OptionUpdate::NoChange,
+            auto_tune_icp_burn_amount: None,
         },
     );
 
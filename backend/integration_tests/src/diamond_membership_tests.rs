@@ -8,7 +8,7 @@ use std::time::Duration;
 use test_case::test_case;
 use types::{Cryptocurrency, DiamondMembershipPlanDuration};
 use utils::consts::SNS_GOVERNANCE_CANISTER_ID;
-use utils::time::MINUTE_IN_MS;
+use utils::time::{DAY_IN_MS, MINUTE_IN_MS};
 
 #[test]
 #[serial]
@@ -150,3 +150,65 @@ fn membership_payment_shared_with_referrer() {
         10_000_000 - (3 * Cryptocurrency::InternetComputer.fee().unwrap()) as u64
     );
 }
+
+#[test]
+#[serial]
+fn diamond_membership_seats_require_membership_and_are_refunded_once_expired() {
+    let mut wrapper = ENV.deref().get();
+    let TestEnv {
+        env,
+        canister_ids,
+        controller,
+        ..
+    } = wrapper.env();
+
+    let owner = client::register_diamond_user(env, canister_ids, *controller);
+    let buyer = client::local_user_index::happy_path::register_user(env, canister_ids.local_user_index);
+    client::icrc1::happy_path::transfer(
+        env,
+        *controller,
+        canister_ids.icp_ledger,
+        buyer.user_id.into(),
+        10_000_000_000u64,
+    );
+
+    let community_id = client::user::happy_path::create_community(env, &owner, "TEST_NAME", true, vec!["general".to_string()]);
+
+    let seats = 2;
+    let args = user_index_canister::pay_for_diamond_membership_seats::Args {
+        community_id,
+        seats,
+        duration: DiamondMembershipPlanDuration::OneMonth,
+        token: Cryptocurrency::InternetComputer,
+        expected_price_e8s: DiamondMembershipPlanDuration::OneMonth.icp_price_e8s() * seats as u64,
+    };
+
+    let response = client::user_index::pay_for_diamond_membership_seats(env, buyer.principal, canister_ids.user_index, &args);
+    assert!(
+        matches!(
+            response,
+            user_index_canister::pay_for_diamond_membership_seats::Response::NotCommunityMember
+        ),
+        "{response:?}"
+    );
+
+    client::local_user_index::happy_path::join_community(env, buyer.principal, canister_ids.local_user_index, community_id);
+
+    let balance_before = client::icrc1::happy_path::balance_of(env, canister_ids.icp_ledger, buyer.user_id.into());
+
+    let response = client::user_index::pay_for_diamond_membership_seats(env, buyer.principal, canister_ids.user_index, &args);
+    match response {
+        user_index_canister::pay_for_diamond_membership_seats::Response::Success(result) => {
+            assert_eq!(result.seats_available, seats)
+        }
+        response => panic!("'pay_for_diamond_membership_seats' error: {response:?}"),
+    }
+
+    env.advance_time(Duration::from_millis(366 * DAY_IN_MS));
+    tick_many(env, 10);
+
+    // The unassigned seats are refunded, less the fee for the refund transfer
+    let fee = Cryptocurrency::InternetComputer.fee().unwrap() as u64;
+    let balance_after = client::icrc1::happy_path::balance_of(env, canister_ids.icp_ledger, buyer.user_id.into());
+    assert_eq!(balance_after, balance_before - fee);
+}
@@ -6,6 +6,7 @@ use pocket_ic::PocketIc;
 use types::{CanisterId, Cycles, UserId};
 
 mod change_group_role_tests;
+mod chat_reports_tests;
 mod client;
 mod communities;
 mod cycles_dispenser_tests;
@@ -20,7 +21,9 @@ mod freeze_group_tests;
 mod gated_group_tests;
 mod join_group_tests;
 mod last_online_date_tests;
+mod local_moderation_tests;
 mod notification_tests;
+mod p2p_swap_tests;
 mod platform_moderator_tests;
 mod poll_tests;
 mod prize_message_tests;
@@ -36,9 +39,11 @@ mod setup;
 mod storage;
 mod suspend_user_tests;
 mod tip_message_tests;
+mod typing_tests;
 mod update_group_tests;
 mod update_profile_tests;
 mod utils;
+mod wallet_transaction_tests;
 mod wasms;
 
 pub struct TestEnv {
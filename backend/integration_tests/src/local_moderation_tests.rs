@@ -0,0 +1,127 @@
+use crate::env::ENV;
+use crate::rng::{random_message_id, random_string};
+use crate::utils::tick_many;
+use crate::{client, TestEnv};
+use std::ops::Deref;
+use types::ChatReportAction;
+use user_index_canister::ModerationMode;
+
+#[test]
+fn reported_message_decided_by_local_moderators() {
+    let mut wrapper = ENV.deref().get();
+    let TestEnv {
+        env,
+        canister_ids,
+        controller,
+        ..
+    } = wrapper.env();
+
+    let moderator = client::register_diamond_user(env, canister_ids, *controller);
+    let reporter = client::register_diamond_user(env, canister_ids, *controller);
+    let sender = client::register_diamond_user(env, canister_ids, *controller);
+
+    client::user_index::add_platform_moderator(
+        env,
+        *controller,
+        canister_ids.user_index,
+        &user_index_canister::add_platform_moderator::Args {
+            user_id: moderator.user_id,
+        },
+    );
+    client::user_index::add_platform_operator(
+        env,
+        *controller,
+        canister_ids.user_index,
+        &user_index_canister::add_platform_operator::Args {
+            user_id: moderator.user_id,
+        },
+    );
+
+    let set_mode_response = client::user_index::set_moderation_mode(
+        env,
+        moderator.principal,
+        canister_ids.user_index,
+        &user_index_canister::set_moderation_mode::Args {
+            mode: ModerationMode::Local,
+            local_quorum: Some(1),
+        },
+    );
+    assert!(matches!(
+        set_mode_response,
+        user_index_canister::set_moderation_mode::Response::Success
+    ));
+
+    let group_id = client::user::happy_path::create_group(env, &reporter, &random_string(), true, true);
+    client::local_user_index::happy_path::join_group(env, sender.principal, canister_ids.local_user_index, group_id);
+
+    let message_id = random_message_id();
+    client::group::happy_path::send_text_message(env, &sender, group_id, None, "Hello", Some(message_id));
+
+    let report_response = client::group::report_message(
+        env,
+        reporter.principal,
+        group_id.into(),
+        &group_canister::report_message::Args {
+            thread_root_message_index: None,
+            message_id,
+            delete: false,
+        },
+    );
+    assert!(
+        matches!(report_response, group_canister::report_message::Response::Success),
+        "{report_response:?}"
+    );
+
+    // Reports go to the group's moderators first, so the owner escalates it to the platform
+    let escalate_response = client::group::resolve_report(
+        env,
+        reporter.principal,
+        group_id.into(),
+        &group_canister::resolve_report::Args {
+            report_id: 0,
+            action: ChatReportAction::EscalateToPlatform,
+        },
+    );
+    assert!(
+        matches!(escalate_response, group_canister::resolve_report::Response::Success),
+        "{escalate_response:?}"
+    );
+
+    tick_many(env, 3);
+
+    let user_index_canister::moderation_queue::Response::Success(queue) = client::user_index::moderation_queue(
+        env,
+        moderator.principal,
+        canister_ids.user_index,
+        &user_index_canister::moderation_queue::Args {},
+    );
+    assert_eq!(queue.mode, ModerationMode::Local);
+    let report = queue.reports.iter().find(|r| r.sender == sender.user_id).unwrap();
+    assert_eq!(report.reported_by, 1);
+    assert!(!report.voted);
+
+    let decision_response = client::user_index::submit_moderation_decision(
+        env,
+        moderator.principal,
+        canister_ids.user_index,
+        &user_index_canister::submit_moderation_decision::Args {
+            report_index: report.report_index,
+            breaks_rules: false,
+            violated_rules: Vec::new(),
+        },
+    );
+    assert!(matches!(
+        decision_response,
+        user_index_canister::submit_moderation_decision::Response::Success(
+            user_index_canister::submit_moderation_decision::SuccessResult { decided: true }
+        )
+    ));
+
+    let user_index_canister::moderation_queue::Response::Success(queue) = client::user_index::moderation_queue(
+        env,
+        moderator.principal,
+        canister_ids.user_index,
+        &user_index_canister::moderation_queue::Args {},
+    );
+    assert!(!queue.reports.iter().any(|r| r.report_index == report.report_index));
+}
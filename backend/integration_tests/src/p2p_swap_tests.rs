@@ -0,0 +1,262 @@
+use crate::env::ENV;
+use crate::rng::random_message_id;
+use crate::setup::install_icrc1_ledger;
+use crate::utils::tick_many;
+use crate::{client, CanisterIds, TestEnv, User};
+use candid::Principal;
+use icrc_ledger_types::icrc1::account::Account;
+use pocket_ic::PocketIc;
+use std::ops::Deref;
+use std::time::Duration;
+use types::{CanisterId, Cryptocurrency, MessageContentInitial, MessageId, P2PSwapContentInitial, TokenInfo};
+
+const ICP_FEE: u128 = 10_000;
+const ABC_FEE: u128 = 100_000;
+
+#[test]
+fn p2p_swap_succeeds() {
+    let mut wrapper = ENV.deref().get();
+    let TestEnv {
+        env,
+        canister_ids,
+        controller,
+        ..
+    } = wrapper.env();
+
+    let TestData {
+        user1,
+        user2,
+        abc_ledger,
+    } = init_test_data(env, canister_ids, *controller);
+
+    let message_id = random_message_id();
+    let token0_amount = 1_0000_0000;
+    let token1_amount = 5_0000_0000;
+
+    send_p2p_swap_offer(
+        env,
+        &user1,
+        &user2,
+        message_id,
+        canister_ids.icp_ledger,
+        abc_ledger,
+        token0_amount,
+        token1_amount,
+    );
+
+    let accept_response = client::user::accept_p2p_swap(
+        env,
+        user2.principal,
+        user2.canister(),
+        &user_canister::accept_p2p_swap::Args {
+            user_id: user1.user_id,
+            message_id,
+        },
+    );
+    assert!(
+        matches!(accept_response, user_canister::accept_p2p_swap::Response::Success(_)),
+        "{accept_response:?}"
+    );
+
+    tick_many(env, 5);
+
+    let user1_icp_balance = client::icrc1::happy_path::balance_of(env, canister_ids.icp_ledger, user1.user_id.into());
+    let user1_abc_balance = client::icrc1::happy_path::balance_of(env, abc_ledger, user1.user_id.into());
+    let user2_icp_balance = client::icrc1::happy_path::balance_of(env, canister_ids.icp_ledger, user2.user_id.into());
+    let user2_abc_balance = client::icrc1::happy_path::balance_of(env, abc_ledger, user2.user_id.into());
+
+    assert_eq!(user1_icp_balance as u128, 100_0000_0000 - token0_amount - 2 * ICP_FEE);
+    assert_eq!(user1_abc_balance as u128, token1_amount);
+    assert_eq!(user2_icp_balance as u128, token0_amount);
+    assert_eq!(user2_abc_balance as u128, 100_0000_0000 - token1_amount - 2 * ABC_FEE);
+}
+
+#[test]
+fn expired_p2p_swap_is_refunded() {
+    let mut wrapper = ENV.deref().get();
+    let TestEnv {
+        env,
+        canister_ids,
+        controller,
+        ..
+    } = wrapper.env();
+
+    let TestData {
+        user1,
+        user2,
+        abc_ledger,
+    } = init_test_data(env, canister_ids, *controller);
+
+    let message_id = random_message_id();
+
+    send_p2p_swap_offer(
+        env,
+        &user1,
+        &user2,
+        message_id,
+        canister_ids.icp_ledger,
+        abc_ledger,
+        1_0000_0000,
+        5_0000_0000,
+    );
+
+    env.advance_time(Duration::from_secs(6 * 60));
+    tick_many(env, 5);
+
+    let accept_response = client::user::accept_p2p_swap(
+        env,
+        user2.principal,
+        user2.canister(),
+        &user_canister::accept_p2p_swap::Args {
+            user_id: user1.user_id,
+            message_id,
+        },
+    );
+    assert!(
+        matches!(accept_response, user_canister::accept_p2p_swap::Response::StatusError(_)),
+        "{accept_response:?}"
+    );
+
+    let user1_icp_balance = client::icrc1::happy_path::balance_of(env, canister_ids.icp_ledger, user1.user_id.into());
+    let user2_abc_balance = client::icrc1::happy_path::balance_of(env, abc_ledger, user2.user_id.into());
+
+    assert_eq!(user1_icp_balance as u128, 100_0000_0000 - 2 * ICP_FEE);
+    assert_eq!(user2_abc_balance as u128, 100_0000_0000);
+}
+
+#[test]
+fn cancelled_p2p_swap_is_refunded() {
+    let mut wrapper = ENV.deref().get();
+    let TestEnv {
+        env,
+        canister_ids,
+        controller,
+        ..
+    } = wrapper.env();
+
+    let TestData {
+        user1,
+        user2,
+        abc_ledger,
+    } = init_test_data(env, canister_ids, *controller);
+
+    let message_id = random_message_id();
+
+    send_p2p_swap_offer(
+        env,
+        &user1,
+        &user2,
+        message_id,
+        canister_ids.icp_ledger,
+        abc_ledger,
+        1_0000_0000,
+        5_0000_0000,
+    );
+
+    let cancel_response = client::user::cancel_p2p_swap(
+        env,
+        user1.principal,
+        user1.canister(),
+        &user_canister::cancel_p2p_swap::Args {
+            user_id: user2.user_id,
+            message_id,
+        },
+    );
+    assert!(
+        matches!(cancel_response, user_canister::cancel_p2p_swap::Response::Success),
+        "{cancel_response:?}"
+    );
+
+    tick_many(env, 5);
+
+    let user1_icp_balance = client::icrc1::happy_path::balance_of(env, canister_ids.icp_ledger, user1.user_id.into());
+    assert_eq!(user1_icp_balance as u128, 100_0000_0000 - 2 * ICP_FEE);
+
+    let retry_response =
+        client::user::retry_failed_p2p_swap_transfers(env, user1.principal, user1.canister(), &types::Empty {});
+    assert!(matches!(
+        retry_response,
+        user_canister::retry_failed_p2p_swap_transfers::Response::Success(0)
+    ));
+}
+
+#[allow(clippy::too_many_arguments)]
+fn send_p2p_swap_offer(
+    env: &mut PocketIc,
+    sender: &User,
+    recipient: &User,
+    message_id: MessageId,
+    token0_ledger: CanisterId,
+    token1_ledger: CanisterId,
+    token0_amount: u128,
+    token1_amount: u128,
+) {
+    let send_message_response = client::user::send_message_v2(
+        env,
+        sender.principal,
+        sender.canister(),
+        &user_canister::send_message_v2::Args {
+            recipient: recipient.user_id,
+            thread_root_message_index: None,
+            message_id,
+            content: MessageContentInitial::P2PSwap(P2PSwapContentInitial {
+                token0: TokenInfo {
+                    token: Cryptocurrency::InternetComputer,
+                    ledger: token0_ledger,
+                    decimals: 8,
+                    fee: ICP_FEE,
+                },
+                token0_amount,
+                token1: TokenInfo {
+                    token: Cryptocurrency::Other("ABC".to_string()),
+                    ledger: token1_ledger,
+                    decimals: 8,
+                    fee: ABC_FEE,
+                },
+                token1_amount,
+                expires_in: 5 * 60 * 1000,
+                caption: None,
+            }),
+            replies_to: None,
+            forwarding: false,
+            correlation_id: 0,
+        },
+    );
+    assert!(
+        matches!(
+            send_message_response,
+            user_canister::send_message_v2::Response::TransferSuccessV2(_)
+        ),
+        "{send_message_response:?}"
+    );
+
+    env.tick();
+}
+
+fn init_test_data(env: &mut PocketIc, canister_ids: &CanisterIds, controller: Principal) -> TestData {
+    let user1 = client::register_diamond_user(env, canister_ids, controller);
+    let user2 = client::local_user_index::happy_path::register_user(env, canister_ids.local_user_index);
+
+    client::icrc1::happy_path::transfer(env, controller, canister_ids.icp_ledger, user1.user_id.into(), 10_000_000_000);
+
+    let abc_ledger = install_icrc1_ledger(
+        env,
+        controller,
+        "ABC Token".to_string(),
+        "ABC".to_string(),
+        ABC_FEE as u64,
+        vec![(Account::from(Principal::from(user2.user_id)), 10_000_000_000)],
+    );
+
+    TestData {
+        user1,
+        user2,
+        abc_ledger,
+    }
+}
+
+struct TestData {
+    user1: User,
+    user2: User,
+    abc_ledger: CanisterId,
+}
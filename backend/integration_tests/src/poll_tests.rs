@@ -1,13 +1,18 @@
 use crate::env::ENV;
 use crate::rng::random_message_id;
+use crate::setup::install_icrc1_ledger;
+use crate::utils::tick_many;
 use crate::{client, TestEnv, User};
+use candid::Principal;
+use icrc_ledger_types::icrc1::account::Account;
 use itertools::Itertools;
 use pocket_ic::PocketIc;
 use std::collections::HashMap;
 use std::ops::Deref;
 use std::time::{Duration, SystemTime};
 use types::{
-    CanisterId, ChatEvent, ChatId, MessageContent, MessageContentInitial, PollConfig, PollContent, PollVotes, TotalVotes,
+    CanisterId, ChatEvent, ChatId, EventIndex, MessageContent, MessageContentInitial, MessageIndex, PollConfig, PollContent,
+    PollKind, PollVoteWeighting, PollVotes, TotalVotes, VoteOperation,
 };
 
 #[test]
@@ -23,6 +28,8 @@ fn allow_multiple_votes_per_user() {
         show_votes_before_end_date: false,
         allow_multiple_votes_per_user: true,
         allow_user_to_change_vote: true,
+        kind: PollKind::Standard,
+        vote_weighting: None,
     };
 
     let TestData {
@@ -54,6 +61,8 @@ fn single_vote_per_user() {
         show_votes_before_end_date: false,
         allow_multiple_votes_per_user: false,
         allow_user_to_change_vote: true,
+        kind: PollKind::Standard,
+        vote_weighting: None,
     };
 
     let TestData {
@@ -87,6 +96,8 @@ fn polls_ended_correctly() {
         show_votes_before_end_date: false,
         allow_multiple_votes_per_user: false,
         allow_user_to_change_vote: true,
+        kind: PollKind::Standard,
+        vote_weighting: None,
     };
 
     let TestData {
@@ -104,6 +115,8 @@ fn polls_ended_correctly() {
         show_votes_before_end_date: false,
         allow_multiple_votes_per_user: false,
         allow_user_to_change_vote: true,
+        kind: PollKind::Standard,
+        vote_weighting: None,
     };
 
     let create_poll_result2 = client::group::send_message_v2(
@@ -120,6 +133,7 @@ fn polls_ended_correctly() {
                     user: Vec::new(),
                 },
                 ended: false,
+                results: None,
             }),
             sender_name: user1.username(),
             sender_display_name: None,
@@ -218,6 +232,232 @@ fn polls_ended_correctly() {
     }
 }
 
+#[test]
+fn ranked_choice_winner_decided_by_instant_runoff() {
+    let mut wrapper = ENV.deref().get();
+    let TestEnv { env, canister_ids, .. } = wrapper.env();
+
+    let poll_config = PollConfig {
+        text: None,
+        options: vec!["1".to_string(), "2".to_string(), "3".to_string()],
+        end_date: None,
+        anonymous: false,
+        show_votes_before_end_date: false,
+        allow_multiple_votes_per_user: false,
+        allow_user_to_change_vote: true,
+        kind: PollKind::RankedChoice,
+        vote_weighting: None,
+    };
+
+    let TestData {
+        user1,
+        user2,
+        group,
+        create_poll_result,
+    } = init_test_data(env, canister_ids.local_user_index, poll_config);
+
+    let (message_index, event_index) = match create_poll_result {
+        group_canister::send_message_v2::Response::Success(r) => (r.message_index, r.event_index),
+        response => panic!("'send_message_v2' error: {response:?}"),
+    };
+
+    let invalid_ranking_response = try_register_vote(env, &user1, group, message_index, vec![2, 2]);
+    assert!(matches!(
+        invalid_ranking_response,
+        group_canister::register_poll_vote::Response::InvalidRanking
+    ));
+
+    let votes = match try_register_vote(env, &user1, group, message_index, vec![2, 1]) {
+        group_canister::register_poll_vote::Response::Success(votes) => votes,
+        response => panic!("'register_poll_vote' error: {response:?}"),
+    };
+    assert_eq!(votes.user, vec![2, 1]);
+
+    try_register_vote(env, &user2, group, message_index, vec![1, 0]);
+
+    let event = client::group::happy_path::events_by_index(env, &user1, group, vec![event_index])
+        .events
+        .pop()
+        .unwrap();
+
+    let results = match event.event {
+        ChatEvent::Message(m) => match m.content {
+            MessageContent::Poll(p) => p.results.unwrap(),
+            _ => unreachable!(),
+        },
+        _ => unreachable!(),
+    };
+
+    // Option 0 is eliminated, then option 2 loses the tie break, leaving option 1 with both votes
+    assert_eq!(results.rounds.len(), 3);
+    assert_eq!(results.winner, Some(1));
+}
+
+#[test]
+fn quiz_answer_revealed_after_voting() {
+    let mut wrapper = ENV.deref().get();
+    let TestEnv { env, canister_ids, .. } = wrapper.env();
+
+    let poll_config = PollConfig {
+        text: None,
+        options: vec!["1".to_string(), "2".to_string()],
+        end_date: None,
+        anonymous: false,
+        show_votes_before_end_date: false,
+        allow_multiple_votes_per_user: false,
+        allow_user_to_change_vote: false,
+        kind: PollKind::Quiz(Some(1)),
+        vote_weighting: None,
+    };
+
+    let TestData {
+        user1: _,
+        user2,
+        group,
+        create_poll_result,
+    } = init_test_data(env, canister_ids.local_user_index, poll_config);
+
+    let (message_index, event_index) = match create_poll_result {
+        group_canister::send_message_v2::Response::Success(r) => (r.message_index, r.event_index),
+        response => panic!("'send_message_v2' error: {response:?}"),
+    };
+
+    assert_eq!(quiz_kind(env, &user2, group, event_index), PollKind::Quiz(None));
+
+    client::group::happy_path::register_poll_vote(env, &user2, group, message_index, 0);
+
+    assert_eq!(quiz_kind(env, &user2, group, event_index), PollKind::Quiz(Some(1)));
+
+    let change_vote_response = try_register_vote(env, &user2, group, message_index, vec![1]);
+    assert!(matches!(
+        change_vote_response,
+        group_canister::register_poll_vote::Response::UserCannotChangeVote
+    ));
+}
+
+#[test]
+fn token_weighted_poll_locks_tokens_until_poll_ends() {
+    let mut wrapper = ENV.deref().get();
+    let TestEnv {
+        env,
+        canister_ids,
+        controller,
+        ..
+    } = wrapper.env();
+
+    let current_time = env.get_time().duration_since(SystemTime::UNIX_EPOCH).unwrap().as_millis() as u64;
+
+    let user1 = client::local_user_index::happy_path::register_user(env, canister_ids.local_user_index);
+    let user2 = client::local_user_index::happy_path::register_user(env, canister_ids.local_user_index);
+
+    let fee = 10_000;
+    let initial_balance = 1_000_000_000;
+    let ledger = install_icrc1_ledger(
+        env,
+        *controller,
+        "ABC Token".to_string(),
+        "ABC".to_string(),
+        fee,
+        vec![(Account::from(Principal::from(user1.user_id)), initial_balance)],
+    );
+
+    let poll_config = PollConfig {
+        text: None,
+        options: vec!["1".to_string(), "2".to_string()],
+        end_date: Some(current_time + 1000),
+        anonymous: false,
+        show_votes_before_end_date: true,
+        allow_multiple_votes_per_user: false,
+        allow_user_to_change_vote: true,
+        kind: PollKind::Standard,
+        vote_weighting: Some(PollVoteWeighting::TokenBalance(ledger)),
+    };
+
+    let group = client::user::happy_path::create_group(env, &user1, "TEST_NAME", false, false);
+    client::local_user_index::happy_path::add_users_to_group(
+        env,
+        user1.principal,
+        canister_ids.local_user_index,
+        group,
+        vec![(user2.user_id, user2.principal)],
+    );
+
+    let message_index = match send_poll(env, &user1, group, poll_config) {
+        group_canister::send_message_v2::Response::Success(r) => r.message_index,
+        response => panic!("'send_message_v2' error: {response:?}"),
+    };
+
+    let allowance = 100_000_000;
+    client::icrc1::happy_path::approve(env, user1.user_id.into(), ledger, Principal::from(group), allowance);
+
+    let vote_response = try_register_vote(env, &user1, group, message_index, vec![0]);
+    assert!(
+        matches!(vote_response, group_canister::register_poll_vote::Response::Success(_)),
+        "{vote_response:?}"
+    );
+
+    // The approved tokens, less the transfer fee, are locked in the group
+    let locked = allowance - fee as u128;
+    assert_eq!(
+        client::icrc1::happy_path::balance_of(env, ledger, Principal::from(group)) as u128,
+        locked
+    );
+
+    // Users who haven't locked any tokens have no voting power
+    let vote_response = try_register_vote(env, &user2, group, message_index, vec![1]);
+    assert!(
+        matches!(vote_response, group_canister::register_poll_vote::Response::NoVotingPower),
+        "{vote_response:?}"
+    );
+
+    env.advance_time(Duration::from_millis(1000));
+    tick_many(env, 5);
+
+    // Once the poll has ended the locked tokens are refunded
+    assert_eq!(client::icrc1::happy_path::balance_of(env, ledger, Principal::from(group)), 0);
+    assert_eq!(
+        client::icrc1::happy_path::balance_of(env, ledger, user1.user_id.into()),
+        initial_balance - 3 * fee
+    );
+}
+
+fn try_register_vote(
+    env: &mut PocketIc,
+    user: &User,
+    group: ChatId,
+    message_index: MessageIndex,
+    ranked_options: Vec<u32>,
+) -> group_canister::register_poll_vote::Response {
+    client::group::register_poll_vote(
+        env,
+        user.principal,
+        group.into(),
+        &group_canister::register_poll_vote::Args {
+            thread_root_message_index: None,
+            message_index,
+            poll_option: ranked_options[0],
+            ranked_options: Some(ranked_options),
+            operation: VoteOperation::RegisterVote,
+            correlation_id: 0,
+        },
+    )
+}
+
+fn quiz_kind(env: &mut PocketIc, user: &User, group: ChatId, event_index: EventIndex) -> PollKind {
+    let event = client::group::happy_path::events_by_index(env, user, group, vec![event_index])
+        .events
+        .pop()
+        .unwrap();
+
+    match event.event {
+        ChatEvent::Message(m) => match m.content {
+            MessageContent::Poll(p) => p.config.kind,
+            _ => unreachable!(),
+        },
+        _ => unreachable!(),
+    }
+}
+
 fn init_test_data(env: &mut PocketIc, local_user_index: CanisterId, poll_config: PollConfig) -> TestData {
     let user1 = client::local_user_index::happy_path::register_user(env, local_user_index);
     let user2 = client::local_user_index::happy_path::register_user(env, local_user_index);
@@ -231,9 +471,25 @@ fn init_test_data(env: &mut PocketIc, local_user_index: CanisterId, poll_config:
         vec![(user2.user_id, user2.principal)],
     );
 
-    let create_poll_result = client::group::send_message_v2(
+    let create_poll_result = send_poll(env, &user1, group, poll_config);
+
+    TestData {
+        user1,
+        user2,
+        group,
+        create_poll_result,
+    }
+}
+
+fn send_poll(
+    env: &mut PocketIc,
+    user: &User,
+    group: ChatId,
+    poll_config: PollConfig,
+) -> group_canister::send_message_v2::Response {
+    client::group::send_message_v2(
         env,
-        user1.principal,
+        user.principal,
         group.into(),
         &group_canister::send_message_v2::Args {
             thread_root_message_index: None,
@@ -245,8 +501,9 @@ fn init_test_data(env: &mut PocketIc, local_user_index: CanisterId, poll_config:
                     user: Vec::new(),
                 },
                 ended: false,
+                results: None,
             }),
-            sender_name: user1.username(),
+            sender_name: user.username(),
             sender_display_name: None,
             replies_to: None,
             mentioned: Vec::new(),
@@ -254,14 +511,7 @@ fn init_test_data(env: &mut PocketIc, local_user_index: CanisterId, poll_config:
             rules_accepted: None,
             correlation_id: 0,
         },
-    );
-
-    TestData {
-        user1,
-        user2,
-        group,
-        create_poll_result,
-    }
+    )
 }
 
 struct TestData {
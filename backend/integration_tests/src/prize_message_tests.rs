@@ -1,14 +1,18 @@
 use crate::env::ENV;
 use crate::rng::{random_message_id, random_string};
 use crate::utils::{now_millis, now_nanos};
-use crate::{client, TestEnv};
+use crate::{client, CanisterIds, TestEnv, User};
 use candid::Principal;
 use ic_ledger_types::Tokens;
 use icrc_ledger_types::icrc1::account::Account;
+use pocket_ic::PocketIc;
 use std::ops::Deref;
 use std::time::Duration;
 use test_case::test_case;
-use types::{icrc1, CryptoTransaction, Cryptocurrency, MessageContentInitial, PendingCryptoTransaction, PrizeContentInitial};
+use types::{
+    icrc1, ChatId, CryptoTransaction, Cryptocurrency, MessageContentInitial, MessageId, PendingCryptoTransaction,
+    PrizeContentInitial, PrizeDistribution, PrizeEligibility, PrizeIneligibilityReason,
+};
 use utils::time::{HOUR_IN_MS, MINUTE_IN_MS};
 
 #[test]
@@ -64,6 +68,8 @@ fn prize_messages_can_be_claimed_successfully() {
                 end_date: now_millis(env) + HOUR_IN_MS,
                 caption: None,
                 diamond_only: false,
+                distribution: PrizeDistribution::FirstComeFirstServed,
+                eligibility: Vec::new(),
             }),
             sender_name: user1.username(),
             sender_display_name: None,
@@ -135,6 +141,8 @@ fn unclaimed_prizes_get_refunded(delete_message: bool) {
                 end_date: now_millis(env) + HOUR_IN_MS,
                 caption: None,
                 diamond_only: false,
+                distribution: PrizeDistribution::FirstComeFirstServed,
+                eligibility: Vec::new(),
             }),
             sender_name: user1.username(),
             sender_display_name: None,
@@ -166,3 +174,170 @@ fn unclaimed_prizes_get_refunded(delete_message: bool) {
 
     assert_eq!(user1_balance_after_refund, user1_balance_before_refund + 100000);
 }
+
+#[test]
+fn prize_split_between_entrants_at_end_date() {
+    let mut wrapper = ENV.deref().get();
+    let TestEnv {
+        env,
+        canister_ids,
+        controller,
+        ..
+    } = wrapper.env();
+
+    let user1 = client::register_diamond_user(env, canister_ids, *controller);
+    let user2 = client::local_user_index::happy_path::register_user(env, canister_ids.local_user_index);
+    let user3 = client::local_user_index::happy_path::register_user(env, canister_ids.local_user_index);
+    let group_id = client::user::happy_path::create_group(env, &user1, random_string().as_str(), true, true);
+    client::local_user_index::happy_path::join_group(env, user2.principal, canister_ids.local_user_index, group_id);
+    client::local_user_index::happy_path::join_group(env, user3.principal, canister_ids.local_user_index, group_id);
+
+    let fee = Cryptocurrency::InternetComputer.fee().unwrap();
+    let message_id = send_prize_message(
+        env,
+        canister_ids,
+        *controller,
+        &user1,
+        group_id,
+        PrizeDistribution::ProportionalSplit,
+        Vec::new(),
+    );
+
+    for user in [&user2, &user3] {
+        let response = claim_prize(env, user, group_id, message_id);
+        assert!(
+            matches!(response, group_canister::claim_prize::Response::EnteredDraw),
+            "{response:?}"
+        );
+    }
+
+    // Nothing is paid out until the end date
+    let user2_balance = client::icrc1::happy_path::balance_of(env, canister_ids.icp_ledger, user2.user_id.into());
+    assert_eq!(user2_balance, 0);
+
+    env.advance_time(Duration::from_millis(HOUR_IN_MS));
+    env.tick();
+    env.tick();
+
+    // The prize fund of 300000 plus 2 fees is split equally, with each entrant paying their own fee
+    let expected = (300000 + 2 * fee) / 2 - fee;
+    for user in [&user2, &user3] {
+        let balance = client::icrc1::happy_path::balance_of(env, canister_ids.icp_ledger, user.user_id.into());
+        assert_eq!(balance as u128, expected);
+    }
+}
+
+#[test]
+fn users_not_meeting_eligibility_rules_cannot_claim_prize() {
+    let mut wrapper = ENV.deref().get();
+    let TestEnv {
+        env,
+        canister_ids,
+        controller,
+        ..
+    } = wrapper.env();
+
+    let user1 = client::register_diamond_user(env, canister_ids, *controller);
+    let user2 = client::local_user_index::happy_path::register_user(env, canister_ids.local_user_index);
+    let group_id = client::user::happy_path::create_group(env, &user1, random_string().as_str(), true, true);
+    client::local_user_index::happy_path::join_group(env, user2.principal, canister_ids.local_user_index, group_id);
+
+    let message_id = send_prize_message(
+        env,
+        canister_ids,
+        *controller,
+        &user1,
+        group_id,
+        PrizeDistribution::FirstComeFirstServed,
+        vec![PrizeEligibility::MinMembershipDuration(HOUR_IN_MS)],
+    );
+
+    let response = claim_prize(env, &user2, group_id, message_id);
+    assert!(
+        matches!(
+            response,
+            group_canister::claim_prize::Response::NotEligible(PrizeIneligibilityReason::MembershipTooRecent)
+        ),
+        "{response:?}"
+    );
+}
+
+fn send_prize_message(
+    env: &mut PocketIc,
+    canister_ids: &CanisterIds,
+    controller: Principal,
+    sender: &User,
+    group_id: ChatId,
+    distribution: PrizeDistribution,
+    eligibility: Vec<PrizeEligibility>,
+) -> MessageId {
+    // Send the sender some ICP
+    client::icrc1::happy_path::transfer(
+        env,
+        controller,
+        canister_ids.icp_ledger,
+        sender.user_id.into(),
+        1_000_000_000u64,
+    );
+
+    let prizes = [100000, 200000];
+    let token = Cryptocurrency::InternetComputer;
+    let fee = token.fee().unwrap();
+    let message_id = random_message_id();
+
+    let response = client::user::send_message_with_transfer_to_group(
+        env,
+        sender.principal,
+        sender.user_id.into(),
+        &user_canister::send_message_with_transfer_to_group::Args {
+            group_id,
+            thread_root_message_index: None,
+            message_id,
+            content: MessageContentInitial::Prize(PrizeContentInitial {
+                prizes: prizes.iter().copied().map(Tokens::from_e8s).collect(),
+                transfer: CryptoTransaction::Pending(PendingCryptoTransaction::ICRC1(icrc1::PendingCryptoTransaction {
+                    ledger: canister_ids.icp_ledger,
+                    token,
+                    amount: prizes.iter().sum::<u64>() as u128 + fee * prizes.len() as u128,
+                    to: Account::from(Principal::from(group_id)),
+                    fee,
+                    memo: None,
+                    created: now_nanos(env),
+                })),
+                end_date: now_millis(env) + HOUR_IN_MS,
+                caption: None,
+                diamond_only: false,
+                distribution,
+                eligibility,
+            }),
+            sender_name: sender.username(),
+            sender_display_name: None,
+            replies_to: None,
+            mentioned: Vec::new(),
+            correlation_id: 0,
+            rules_accepted: None,
+        },
+    );
+
+    match response {
+        user_canister::send_message_with_transfer_to_group::Response::Success(_) => message_id,
+        response => panic!("'send_message_with_transfer_to_group' error: {response:?}"),
+    }
+}
+
+fn claim_prize(
+    env: &mut PocketIc,
+    user: &User,
+    group_id: ChatId,
+    message_id: MessageId,
+) -> group_canister::claim_prize::Response {
+    client::group::claim_prize(
+        env,
+        user.principal,
+        group_id.into(),
+        &group_canister::claim_prize::Args {
+            message_id,
+            correlation_id: 0,
+        },
+    )
+}
@@ -100,6 +100,7 @@ fn install_canisters(env: &mut PocketIc, controller: Principal) -> CanisterIds {
         cycles_dispenser_canister_id,
         storage_index_canister_id,
         internet_identity_canister_id: NNS_INTERNET_IDENTITY_CANISTER_ID,
+        online_users_canister_id,
         wasm_version: BuildVersion::min(),
         test_mode: true,
     };
@@ -385,6 +386,12 @@ pub fn install_icrc1_ledger(
         pub token_symbol: String,
         pub metadata: Vec<(String, MetadataValue)>,
         pub archive_options: ArchiveOptions,
+        pub feature_flags: Option<FeatureFlags>,
+    }
+
+    #[derive(CandidType)]
+    pub struct FeatureFlags {
+        pub icrc2: bool,
     }
 
     #[derive(CandidType)]
@@ -411,6 +418,7 @@ pub fn install_icrc1_ledger(
             num_blocks_to_archive: 1000,
             controller_id: controller,
         },
+        feature_flags: Some(FeatureFlags { icrc2: true }),
     });
 
     let canister_id = create_canister(env, controller);
@@ -0,0 +1,72 @@
+use crate::env::ENV;
+use crate::rng::random_string;
+use crate::{client, TestEnv};
+use std::ops::Deref;
+use std::time::Duration;
+
+#[test]
+fn group_typing_only_visible_to_members() {
+    let mut wrapper = ENV.deref().get();
+    let TestEnv {
+        env,
+        canister_ids,
+        controller,
+        ..
+    } = wrapper.env();
+
+    let user1 = client::register_diamond_user(env, canister_ids, *controller);
+    let user2 = client::register_diamond_user(env, canister_ids, *controller);
+    let non_member = client::register_diamond_user(env, canister_ids, *controller);
+
+    let group_id = client::user::happy_path::create_group(env, &user1, &random_string(), true, true);
+    client::local_user_index::happy_path::join_group(env, user2.principal, canister_ids.local_user_index, group_id);
+
+    let set_typing_response = client::group::set_typing(
+        env,
+        user1.principal,
+        group_id.into(),
+        &group_canister::set_typing::Args {
+            thread_root_message_index: None,
+            typing: true,
+        },
+    );
+    assert!(matches!(set_typing_response, group_canister::set_typing::Response::Success));
+
+    // Non-members can't set typing in the group
+    let non_member_set_typing_response = client::group::set_typing(
+        env,
+        non_member.principal,
+        group_id.into(),
+        &group_canister::set_typing::Args {
+            thread_root_message_index: None,
+            typing: true,
+        },
+    );
+    assert!(matches!(
+        non_member_set_typing_response,
+        group_canister::set_typing::Response::UserNotInGroup
+    ));
+
+    let typing_args = group_canister::typing::Args {
+        thread_root_message_index: None,
+    };
+
+    match client::group::typing(env, user2.principal, group_id.into(), &typing_args) {
+        group_canister::typing::Response::Success(users) => assert_eq!(users, vec![user1.user_id]),
+        response => panic!("'typing' error: {response:?}"),
+    }
+
+    // Non-members can't see who is typing in the group
+    assert!(matches!(
+        client::group::typing(env, non_member.principal, group_id.into(), &typing_args),
+        group_canister::typing::Response::CallerNotInGroup
+    ));
+
+    // Typing notifications expire shortly after the last call to `set_typing`
+    env.advance_time(Duration::from_secs(10));
+
+    match client::group::typing(env, user2.principal, group_id.into(), &typing_args) {
+        group_canister::typing::Response::Success(users) => assert!(users.is_empty()),
+        response => panic!("'typing' error: {response:?}"),
+    }
+}
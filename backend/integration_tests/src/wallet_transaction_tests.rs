@@ -0,0 +1,184 @@
+use crate::env::ENV;
+use crate::rng::random_string;
+use crate::setup::install_icrc1_ledger;
+use crate::utils::tick_many;
+use crate::{client, TestEnv};
+use candid::Principal;
+use icrc_ledger_types::icrc1::account::Account;
+use std::ops::Deref;
+use types::{AccessGate, PaymentGate, Rules};
+use user_canister::{WalletCounterparty, WalletTransactionDirection, WalletTransactionKind};
+
+#[test]
+fn approval_and_joining_fee_recorded() {
+    let mut wrapper = ENV.deref().get();
+    let TestEnv {
+        env,
+        canister_ids,
+        controller,
+        ..
+    } = wrapper.env();
+
+    let user1 = client::register_diamond_user(env, canister_ids, *controller);
+    let user2 = client::local_user_index::happy_path::register_user(env, canister_ids.local_user_index);
+
+    let fee = 10_000;
+    let amount = 1_000_000;
+    let ledger = install_icrc1_ledger(
+        env,
+        *controller,
+        "ABC Token".to_string(),
+        "ABC".to_string(),
+        fee as u64,
+        vec![(Account::from(Principal::from(user2.user_id)), 10_000_000)],
+    );
+
+    let group_name = random_string();
+    let group_id = match client::user::create_group(
+        env,
+        user1.principal,
+        user1.canister(),
+        &user_canister::create_group::Args {
+            is_public: true,
+            name: group_name.clone(),
+            description: format!("{group_name}_description"),
+            avatar: None,
+            history_visible_to_new_joiners: true,
+            permissions_v2: None,
+            rules: Rules::default(),
+            events_ttl: None,
+            gate: Some(AccessGate::Payment(PaymentGate {
+                ledger_canister_id: ledger,
+                amount,
+                fee,
+            })),
+        },
+    ) {
+        user_canister::create_group::Response::Success(result) => result.chat_id,
+        response => panic!("'create_group' error: {response:?}"),
+    };
+
+    // The allowance must cover the amount transferred plus the transfer_from fee
+    let approve_transfer_response = client::user::approve_transfer(
+        env,
+        user2.principal,
+        user2.canister(),
+        &user_canister::approve_transfer::Args {
+            spender: Account::from(Principal::from(group_id)),
+            ledger_canister_id: ledger,
+            amount: amount - fee,
+            expires_in: None,
+        },
+    );
+    assert!(matches!(
+        approve_transfer_response,
+        user_canister::approve_transfer::Response::Success
+    ));
+
+    client::local_user_index::happy_path::join_group(env, user2.principal, canister_ids.local_user_index, group_id);
+
+    tick_many(env, 5);
+
+    let transactions = match client::user::wallet_transactions(
+        env,
+        user2.principal,
+        user2.canister(),
+        &user_canister::wallet_transactions::Args {
+            start: None,
+            max_results: 10,
+            ledger: Some(ledger),
+            kinds: None,
+            direction: None,
+        },
+    ) {
+        user_canister::wallet_transactions::Response::Success(result) => result.transactions,
+    };
+
+    assert_eq!(transactions.len(), 2);
+
+    let joining_fee = &transactions[0];
+    assert_eq!(joining_fee.kind, WalletTransactionKind::JoiningFee);
+    assert_eq!(joining_fee.direction, WalletTransactionDirection::Outgoing);
+    assert_eq!(joining_fee.amount, amount - 2 * fee);
+    assert_eq!(joining_fee.fee, Some(fee));
+    assert!(matches!(joining_fee.counterparty, Some(WalletCounterparty::Canister(c)) if c == Principal::from(group_id)));
+
+    let approval = &transactions[1];
+    assert_eq!(approval.kind, WalletTransactionKind::Approval);
+    assert_eq!(approval.direction, WalletTransactionDirection::Approval);
+    assert_eq!(approval.amount, amount - fee);
+}
+
+#[test]
+fn joining_fee_not_recorded_without_approval() {
+    let mut wrapper = ENV.deref().get();
+    let TestEnv {
+        env,
+        canister_ids,
+        controller,
+        ..
+    } = wrapper.env();
+
+    let user1 = client::register_diamond_user(env, canister_ids, *controller);
+    let user2 = client::local_user_index::happy_path::register_user(env, canister_ids.local_user_index);
+
+    let fee = 10_000;
+    let amount = 1_000_000;
+    let ledger = install_icrc1_ledger(
+        env,
+        *controller,
+        "ABC Token".to_string(),
+        "ABC".to_string(),
+        fee as u64,
+        vec![(Account::from(Principal::from(user2.user_id)), 10_000_000)],
+    );
+
+    let group_name = random_string();
+    let group_id = match client::user::create_group(
+        env,
+        user1.principal,
+        user1.canister(),
+        &user_canister::create_group::Args {
+            is_public: true,
+            name: group_name.clone(),
+            description: format!("{group_name}_description"),
+            avatar: None,
+            history_visible_to_new_joiners: true,
+            permissions_v2: None,
+            rules: Rules::default(),
+            events_ttl: None,
+            gate: Some(AccessGate::Payment(PaymentGate {
+                ledger_canister_id: ledger,
+                amount,
+                fee,
+            })),
+        },
+    ) {
+        user_canister::create_group::Response::Success(result) => result.chat_id,
+        response => panic!("'create_group' error: {response:?}"),
+    };
+
+    // Approve the group directly via the ledger, bypassing the user canister
+    client::icrc1::happy_path::approve(env, user2.user_id.into(), ledger, Principal::from(group_id), amount - fee);
+
+    client::local_user_index::happy_path::join_group(env, user2.principal, canister_ids.local_user_index, group_id);
+
+    tick_many(env, 5);
+
+    let total = match client::user::wallet_transactions(
+        env,
+        user2.principal,
+        user2.canister(),
+        &user_canister::wallet_transactions::Args {
+            start: None,
+            max_results: 10,
+            ledger: Some(ledger),
+            kinds: None,
+            direction: None,
+        },
+    ) {
+        user_canister::wallet_transactions::Response::Success(result) => result.total,
+    };
+
+    assert_eq!(total, 0);
+}
@@ -273,6 +273,12 @@ impl MessageInternal {
                 incr(&mut metrics.message_reminders);
             }
             MessageContentInternal::ReportedMessage(_) => {}
+            MessageContentInternal::P2PSwap(_) => {
+                incr(&mut metrics.p2p_swaps);
+            }
+            MessageContentInternal::CalendarEvent(_) => {
+                incr(&mut metrics.calendar_events);
+            }
             MessageContentInternal::Custom(_) => {
                 incr(&mut metrics.custom_type_messages);
             }
@@ -532,6 +538,10 @@ pub struct ChatMetricsInternal {
     pub message_reminders: u64,
     #[serde(rename = "cu", default, skip_serializing_if = "is_default")]
     pub custom_type_messages: u64,
+    #[serde(rename = "p2p", default, skip_serializing_if = "is_default")]
+    pub p2p_swaps: u64,
+    #[serde(rename = "ce", default, skip_serializing_if = "is_default")]
+    pub calendar_events: u64,
     #[serde(rename = "la")]
     pub last_active: TimestampMillis,
 }
@@ -558,6 +568,8 @@ impl ChatMetricsInternal {
         self.edits += other.edits;
         self.reactions += other.reactions;
         self.proposals += other.proposals;
+        self.p2p_swaps += other.p2p_swaps;
+        self.calendar_events += other.calendar_events;
         self.last_active = max(self.last_active, other.last_active);
     }
 
@@ -586,6 +598,8 @@ impl ChatMetricsInternal {
             reported_messages: self.reported_messages,
             message_reminders: self.message_reminders,
             custom_type_messages: self.custom_type_messages,
+            p2p_swaps: self.p2p_swaps,
+            calendar_events: self.calendar_events,
             last_active: self.last_active,
         }
     }
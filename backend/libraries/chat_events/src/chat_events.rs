@@ -4,6 +4,7 @@ use crate::*;
 use candid::Principal;
 use ic_ledger_types::Tokens;
 use itertools::Itertools;
+use ledger_utils::create_pending_transaction;
 use rand::rngs::StdRng;
 use rand::Rng;
 use search::{Document, Query};
@@ -11,15 +12,18 @@ use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
 use std::cmp::{max, Reverse};
 use std::collections::hash_map::Entry::{Occupied, Vacant};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use types::{
-    CanisterId, Chat, CompletedCryptoTransaction, Cryptocurrency, DirectChatCreated, EventIndex, EventWrapper,
-    EventsTimeToLiveUpdated, GroupCanisterThreadDetails, GroupCreated, GroupFrozen, GroupUnfrozen, Hash, HydratedMention,
-    Mention, Message, MessageContentInitial, MessageId, MessageIndex, MessageMatch, MessageReport, Milliseconds, MultiUserChat,
-    PendingCryptoTransaction, PollVotes, ProposalUpdate, PushEventResult, Reaction, RegisterVoteResult, TimestampMillis,
-    TimestampNanos, Timestamped, Tips, UserId, VoteOperation,
+    CalendarEventRsvps, CanisterId, Chat, CompletedCryptoTransaction, Cryptocurrency, DirectChatCreated, EventIndex,
+    EventWrapper, EventsTimeToLiveUpdated, GroupCanisterThreadDetails, GroupCreated, GroupFrozen, GroupUnfrozen, Hash,
+    HydratedMention, Mention, Message, MessageContentInitial, MessageId, MessageIndex, MessageMatch, MessageReport,
+    Milliseconds, MultiUserChat, P2PSwapStatus, PendingCryptoTransaction, PollVoteTokenLock, PollVoteWeighting, PollVotes,
+    PrizeDistribution, PrizeEligibility, ProposalDecisionStatus, ProposalUpdate, PushEventResult, Reaction, RegisterVoteResult,
+    Rsvp, TimestampMillis, TimestampNanos, Timestamped, Tips, UnvotedProposal, UserId, VoteOperation,
 };
 
+const MAX_UNVOTED_PROPOSAL_AGE: Milliseconds = 30 * 24 * 60 * 60 * 1000; // 30 days
+
 pub const OPENCHAT_BOT_USER_ID: UserId = UserId::new(Principal::from_slice(&[228, 104, 142, 9, 133, 211, 135, 217, 129, 1]));
 
 #[derive(Serialize, Deserialize)]
@@ -33,6 +37,10 @@ pub struct ChatEvents {
     events_ttl: Timestamped<Option<Milliseconds>>,
     expiring_events: ExpiringEvents,
     last_updated_timestamps: LastUpdatedTimestamps,
+    // The users who have replied to each message which a prize requires users to engage with, keyed
+    // by the event index of that message
+    #[serde(default)]
+    message_repliers: HashMap<EventIndex, HashSet<UserId>>,
 }
 
 impl ChatEvents {
@@ -47,6 +55,7 @@ impl ChatEvents {
             events_ttl: Timestamped::new(events_ttl, now),
             expiring_events: ExpiringEvents::default(),
             last_updated_timestamps: LastUpdatedTimestamps::default(),
+            message_repliers: HashMap::new(),
         };
 
         events.push_event(None, ChatEventInternal::DirectChatCreated(DirectChatCreated {}), 0, now);
@@ -71,6 +80,7 @@ impl ChatEvents {
             events_ttl: Timestamped::new(events_ttl, now),
             expiring_events: ExpiringEvents::default(),
             last_updated_timestamps: LastUpdatedTimestamps::default(),
+            message_repliers: HashMap::new(),
         };
 
         events.push_event(
@@ -94,6 +104,21 @@ impl ChatEvents {
     }
 
     pub fn push_message(&mut self, args: PushMessageArgs) -> EventWrapper<Message> {
+        let replied_to = args
+            .replies_to
+            .as_ref()
+            .filter(|r| args.thread_root_message_index.is_none() && r.chat_if_other.is_none())
+            .map(|r| r.event_index);
+
+        let messages_to_engage_with: Vec<_> = if let MessageContentInternal::Prize(p) = &args.content {
+            p.eligibility
+                .iter()
+                .filter_map(|e| if let PrizeEligibility::EngagedWithMessage(m) = e { Some(*m) } else { None })
+                .collect()
+        } else {
+            Vec::new()
+        };
+
         let events_list = if let Some(root_message_index) = args.thread_root_message_index {
             self.threads.entry(root_message_index).or_default()
         } else {
@@ -146,6 +171,14 @@ impl ChatEvents {
             );
         }
 
+        if let Some(repliers) = replied_to.and_then(|e| self.message_repliers.get_mut(&e)) {
+            repliers.insert(args.sender);
+        }
+
+        for message_index in messages_to_engage_with {
+            self.track_message_repliers(message_index);
+        }
+
         EventWrapper {
             index: push_event_result.index,
             timestamp: args.now,
@@ -161,6 +194,13 @@ impl ChatEvents {
             args.thread_root_message_index,
             args.message_id.into(),
         ) {
+            // P2P swaps hold state about the escrowed funds, so they can't be edited, nor can other
+            // messages be edited into P2P swaps
+            if matches!(message.content, MessageContentInternal::P2PSwap(_))
+                || matches!(args.content, MessageContentInitial::P2PSwap(_))
+            {
+                return EditMessageResult::NotAuthorized;
+            }
             if message.sender == args.sender {
                 if !matches!(message.content, MessageContentInternal::Deleted(_)) {
                     message.content = args.content.into();
@@ -215,6 +255,8 @@ impl ChatEvents {
             if message.sender == args.caller || args.is_admin {
                 if message.deleted_by.is_some() || matches!(message.content, MessageContentInternal::Deleted(_)) {
                     DeleteMessageResult::AlreadyDeleted
+                } else if matches!(&message.content, MessageContentInternal::P2PSwap(s) if !s.escrow_released()) {
+                    DeleteMessageResult::NotAuthorized
                 } else {
                     let sender = message.sender;
                     message.last_updated = Some(args.now);
@@ -323,7 +365,14 @@ impl ChatEvents {
             args.message_index.into(),
         ) {
             if let MessageContentInternal::Poll(p) = &mut message.content {
-                return match p.register_vote(args.user_id, args.option_index, args.operation) {
+                return match p.register_vote(
+                    args.user_id,
+                    args.option_index,
+                    args.ranked_options,
+                    args.weight,
+                    args.token_lock,
+                    args.operation,
+                ) {
                     RegisterVoteResult::Success(existing_vote_removed) => {
                         message.last_updated = Some(args.now);
                         let votes = p.hydrate(Some(args.user_id)).votes;
@@ -362,6 +411,7 @@ impl ChatEvents {
                     RegisterVoteResult::PollEnded => RegisterPollVoteResult::PollEnded,
                     RegisterVoteResult::OptionIndexOutOfRange => RegisterPollVoteResult::OptionIndexOutOfRange,
                     RegisterVoteResult::UserCannotChangeVote => RegisterPollVoteResult::UserCannotChangeVote,
+                    RegisterVoteResult::InvalidRanking => RegisterPollVoteResult::InvalidRanking,
                 };
             }
         }
@@ -369,6 +419,44 @@ impl ChatEvents {
         RegisterPollVoteResult::PollNotFound
     }
 
+    // Returns the poll's vote weighting, the time the poll was created and the time it ends, if the
+    // user's vote needs to be weighted and their weight has not yet been captured
+    pub fn poll_weight_required(
+        &self,
+        user_id: UserId,
+        min_visible_event_index: EventIndex,
+        thread_root_message_index: Option<MessageIndex>,
+        message_index: MessageIndex,
+    ) -> Option<(PollVoteWeighting, TimestampMillis, TimestampMillis)> {
+        let event = self
+            .events_list(min_visible_event_index, thread_root_message_index)?
+            .get_event(message_index.into(), min_visible_event_index)?;
+
+        if let MessageContentInternal::Poll(p) = &event.event.as_message()?.content {
+            if !p.ended && !p.has_weight(&user_id) {
+                let end_date = p.config.end_date?;
+                return p.config.vote_weighting.map(|w| (w, event.timestamp, end_date));
+            }
+        }
+        None
+    }
+
+    // Removes and returns the tokens locked by voters in a token weighted poll so that they can be refunded
+    pub fn take_poll_token_locks(
+        &mut self,
+        thread_root_message_index: Option<MessageIndex>,
+        message_index: MessageIndex,
+    ) -> Vec<(UserId, PollVoteTokenLock)> {
+        if let Some((message, _)) =
+            self.message_internal_mut(EventIndex::default(), thread_root_message_index, message_index.into())
+        {
+            if let MessageContentInternal::Poll(p) = &mut message.content {
+                return p.token_locks.drain().collect();
+            }
+        }
+        Vec::new()
+    }
+
     pub fn end_poll(
         &mut self,
         thread_root_message_index: Option<MessageIndex>,
@@ -395,6 +483,51 @@ impl ChatEvents {
         EndPollResult::PollNotFound
     }
 
+    pub fn register_calendar_event_rsvp(&mut self, args: RegisterCalendarEventRsvpArgs) -> RegisterCalendarEventRsvpResult {
+        if let Some((message, event_index)) = self.message_internal_mut(
+            args.min_visible_event_index,
+            args.thread_root_message_index,
+            args.message_index.into(),
+        ) {
+            if let MessageContentInternal::CalendarEvent(e) = &mut message.content {
+                return match e.register_rsvp(args.user_id, args.rsvp, args.now) {
+                    RegisterRsvpResult::Success => {
+                        message.last_updated = Some(args.now);
+                        let rsvps = e.rsvp_counts(Some(args.user_id));
+
+                        self.last_updated_timestamps
+                            .mark_updated(args.thread_root_message_index, event_index, args.now);
+
+                        RegisterCalendarEventRsvpResult::Success(rsvps)
+                    }
+                    RegisterRsvpResult::SuccessNoChange => {
+                        RegisterCalendarEventRsvpResult::SuccessNoChange(e.rsvp_counts(Some(args.user_id)))
+                    }
+                    RegisterRsvpResult::EventStarted => RegisterCalendarEventRsvpResult::EventStarted,
+                    RegisterRsvpResult::EventFull => RegisterCalendarEventRsvpResult::EventFull,
+                };
+            }
+        }
+
+        RegisterCalendarEventRsvpResult::EventNotFound
+    }
+
+    pub fn calendar_event(
+        &self,
+        min_visible_event_index: EventIndex,
+        thread_root_message_index: Option<MessageIndex>,
+        message_index: MessageIndex,
+    ) -> Option<(&CalendarEventContentInternal, EventIndex)> {
+        self.message_internal(min_visible_event_index, thread_root_message_index, message_index.into())
+            .and_then(|(m, event_index)| {
+                if let MessageContentInternal::CalendarEvent(e) = &m.content {
+                    Some((e, event_index))
+                } else {
+                    None
+                }
+            })
+    }
+
     pub fn prize_refund(
         &self,
         thread_root_message_index: Option<MessageIndex>,
@@ -447,6 +580,46 @@ impl ChatEvents {
         }
     }
 
+    // Returns the open proposals which the user hasn't voted on and which close before
+    // `closing_before`. Proposals are only open for a matter of days, so the scan stops once it
+    // reaches messages older than `MAX_UNVOTED_PROPOSAL_AGE`.
+    pub fn unvoted_proposals(
+        &self,
+        chat: MultiUserChat,
+        user_id: UserId,
+        min_visible_event_index: EventIndex,
+        closing_before: TimestampMillis,
+        now: TimestampMillis,
+    ) -> Vec<UnvotedProposal> {
+        let oldest = now.saturating_sub(MAX_UNVOTED_PROPOSAL_AGE);
+
+        self.visible_main_events_reader(min_visible_event_index)
+            .iter_events(None, false)
+            .take_while(|e| e.timestamp >= oldest)
+            .filter_map(|e| e.event.as_message())
+            .filter_map(|m| {
+                if let MessageContentInternal::GovernanceProposal(p) = &m.content {
+                    let deadline = p.proposal.deadline();
+                    if p.proposal.status() == ProposalDecisionStatus::Open
+                        && deadline > now
+                        && deadline <= closing_before
+                        && !p.votes.contains_key(&user_id)
+                    {
+                        return Some(UnvotedProposal {
+                            chat,
+                            message_index: m.message_index,
+                            governance_canister_id: p.governance_canister_id,
+                            proposal_id: p.proposal.id(),
+                            title: p.proposal.title().to_string(),
+                            deadline,
+                        });
+                    }
+                }
+                None
+            })
+            .collect()
+    }
+
     pub fn update_proposals(&mut self, user_id: UserId, updates: Vec<ProposalUpdate>, now: TimestampMillis) {
         for update in updates {
             if let Some((message, event_index)) =
@@ -595,10 +768,28 @@ impl ChatEvents {
                     return ReservePrizeResult::PrizeFullyClaimed;
                 }
 
-                if content.winners.contains(&user_id) || content.reservations.contains(&user_id) {
+                if content.winners.contains(&user_id)
+                    || content.reservations.contains(&user_id)
+                    || content.entrants.contains(&user_id)
+                {
                     return ReservePrizeResult::AlreadyClaimed;
                 }
 
+                if content.distribution != PrizeDistribution::FirstComeFirstServed {
+                    // A proportional split has at most one entrant per prize
+                    if content.distribution == PrizeDistribution::ProportionalSplit
+                        && content.entrants.len() >= content.prizes_remaining.len()
+                    {
+                        return ReservePrizeResult::PrizeFullyClaimed;
+                    }
+
+                    content.entrants.push(user_id);
+                    message.last_updated = Some(now);
+                    self.last_updated_timestamps.mark_updated(None, event_index, now);
+
+                    return ReservePrizeResult::EnteredDraw;
+                }
+
                 // Pop the last prize and reserve it
                 let amount = content.prizes_remaining.pop().expect("some prizes_remaining");
                 let token = content.transaction.token();
@@ -616,6 +807,127 @@ impl ChatEvents {
         ReservePrizeResult::MessageNotFound
     }
 
+    // Picks the winners of a prize draw and reserves their prizes, returning the message id of the
+    // prize message along with the transfers to be made to each winner
+    pub fn draw_prize(
+        &mut self,
+        thread_root_message_index: Option<MessageIndex>,
+        message_index: MessageIndex,
+        memo: &[u8],
+        rng: &mut StdRng,
+        now_nanos: TimestampNanos,
+        now: TimestampMillis,
+    ) -> Option<(MessageId, Vec<(UserId, PendingCryptoTransaction)>)> {
+        let (message, event_index) =
+            self.message_internal_mut(EventIndex::default(), thread_root_message_index, message_index.into())?;
+
+        if let MessageContentInternal::Prize(content) = &mut message.content {
+            let winners = content.draw_winners(rng);
+            if winners.is_empty() {
+                return None;
+            }
+
+            let token = content.transaction.token();
+            let ledger_canister_id = content.transaction.ledger_canister_id();
+            let fee = content.transaction.fee();
+            let payouts = winners
+                .into_iter()
+                .map(|(user_id, amount)| {
+                    let transaction = create_pending_transaction(
+                        token.clone(),
+                        ledger_canister_id,
+                        amount,
+                        fee,
+                        user_id,
+                        Some(memo),
+                        now_nanos,
+                    );
+                    (user_id, transaction)
+                })
+                .collect();
+
+            message.last_updated = Some(now);
+            let message_id = message.message_id;
+            self.last_updated_timestamps
+                .mark_updated(thread_root_message_index, event_index, now);
+
+            Some((message_id, payouts))
+        } else {
+            None
+        }
+    }
+
+    pub fn prize_eligibility(&self, message_id: MessageId, min_visible_event_index: EventIndex) -> Option<&[PrizeEligibility]> {
+        if let MessageContentInternal::Prize(content) = &self
+            .message_internal(min_visible_event_index, None, message_id.into())?
+            .0
+            .content
+        {
+            Some(&content.eligibility)
+        } else {
+            None
+        }
+    }
+
+    // Returns true if the user has reacted to the message, replied in its thread or quoted it in a reply
+    pub fn has_engaged_with_message(
+        &self,
+        user_id: UserId,
+        min_visible_event_index: EventIndex,
+        message_index: MessageIndex,
+    ) -> bool {
+        let (message, event_index) = match self.message_internal(min_visible_event_index, None, message_index.into()) {
+            Some(m) => m,
+            None => return false,
+        };
+
+        if message.reactions.iter().any(|(_, users)| users.contains(&user_id)) {
+            return true;
+        }
+
+        if message
+            .thread_summary
+            .as_ref()
+            .map_or(false, |t| t.participant_ids.contains(&user_id))
+        {
+            return true;
+        }
+
+        self.message_repliers
+            .get(&event_index)
+            .map_or(false, |repliers| repliers.contains(&user_id))
+    }
+
+    // Starts keeping track of the users who reply to the given message. Replies sent before this
+    // point are found by scanning the events once, after which replies are recorded as they are
+    // pushed.
+    fn track_message_repliers(&mut self, message_index: MessageIndex) {
+        let Some(event_index) = self
+            .main
+            .get_event(message_index.into(), EventIndex::default())
+            .map(|e| e.index)
+        else {
+            return;
+        };
+
+        if let Vacant(e) = self.message_repliers.entry(event_index) {
+            let repliers = self
+                .main
+                .iter(Some(event_index.into()), true, EventIndex::default())
+                .filter_map(|e| e.as_event())
+                .filter_map(|e| e.event.as_message())
+                .filter(|m| {
+                    m.replies_to
+                        .as_ref()
+                        .map_or(false, |r| r.chat_if_other.is_none() && r.event_index == event_index)
+                })
+                .map(|m| m.sender)
+                .collect();
+
+            e.insert(repliers);
+        }
+    }
+
     pub fn claim_prize(
         &mut self,
         message_id: MessageId,
@@ -687,6 +999,121 @@ impl ChatEvents {
         UnreservePrizeResult::MessageNotFound
     }
 
+    // Used when a prize draw winner can't be paid. The unclaimed prizes will already have been
+    // refunded when the draw was made, so rather than returning the winner's prize to the prize
+    // fund, it is refunded directly to the prize's sender.
+    pub fn refund_prize_reservation(
+        &mut self,
+        message_id: MessageId,
+        user_id: UserId,
+        amount: u128,
+        memo: &[u8],
+        now_nanos: TimestampNanos,
+        now: TimestampMillis,
+    ) -> Option<PendingCryptoTransaction> {
+        let (message, event_index) = self.message_internal_mut(EventIndex::default(), None, message_id.into())?;
+
+        if let MessageContentInternal::Prize(content) = &mut message.content {
+            if content.reservations.remove(&user_id) {
+                let refund = create_pending_transaction(
+                    content.transaction.token(),
+                    content.transaction.ledger_canister_id(),
+                    amount,
+                    content.transaction.fee(),
+                    message.sender,
+                    Some(memo),
+                    now_nanos,
+                );
+                message.last_updated = Some(now);
+                self.last_updated_timestamps.mark_updated(None, event_index, now);
+
+                return Some(refund);
+            }
+        }
+
+        None
+    }
+
+    pub fn reserve_p2p_swap(&mut self, user_id: UserId, message_id: MessageId, now: TimestampMillis) -> UpdateP2PSwapResult {
+        self.update_p2p_swap(message_id, now, |content| content.reserve(user_id, now))
+    }
+
+    pub fn unreserve_p2p_swap(&mut self, user_id: UserId, message_id: MessageId, now: TimestampMillis) -> UpdateP2PSwapResult {
+        self.update_p2p_swap(message_id, now, |content| content.unreserve(user_id))
+    }
+
+    pub fn accept_p2p_swap(
+        &mut self,
+        user_id: UserId,
+        message_id: MessageId,
+        token1_txn_in: u64,
+        now: TimestampMillis,
+    ) -> UpdateP2PSwapResult {
+        self.update_p2p_swap(message_id, now, |content| content.accept(user_id, token1_txn_in, now))
+    }
+
+    pub fn cancel_p2p_swap(&mut self, message_id: MessageId, expired: bool, now: TimestampMillis) -> UpdateP2PSwapResult {
+        self.update_p2p_swap(message_id, now, |content| content.cancel(expired))
+    }
+
+    pub fn mark_p2p_swap_token0_refunded(
+        &mut self,
+        message_id: MessageId,
+        token0_txn_out: u64,
+        now: TimestampMillis,
+    ) -> UpdateP2PSwapResult {
+        self.update_p2p_swap(message_id, now, |content| content.mark_token0_refunded(token0_txn_out))
+    }
+
+    pub fn mark_p2p_swap_transfer_out_completed(
+        &mut self,
+        message_id: MessageId,
+        token0: bool,
+        block_index: u64,
+        now: TimestampMillis,
+    ) -> UpdateP2PSwapResult {
+        self.update_p2p_swap(message_id, now, |content| {
+            content.mark_transfer_out_completed(token0, block_index)
+        })
+    }
+
+    // Used by the counterparty's canister to keep its copy of the swap in sync with the copy held
+    // by the canister which holds the escrowed funds
+    pub fn set_p2p_swap_status(
+        &mut self,
+        message_id: MessageId,
+        status: P2PSwapStatus,
+        now: TimestampMillis,
+    ) -> UpdateP2PSwapResult {
+        self.update_p2p_swap(message_id, now, |content| {
+            content.status = status;
+            true
+        })
+    }
+
+    fn update_p2p_swap<F: FnOnce(&mut P2PSwapContentInternal) -> bool>(
+        &mut self,
+        message_id: MessageId,
+        now: TimestampMillis,
+        f: F,
+    ) -> UpdateP2PSwapResult {
+        if let Some((message, event_index)) = self.message_internal_mut(EventIndex::default(), None, message_id.into()) {
+            if let MessageContentInternal::P2PSwap(content) = &mut message.content {
+                return if f(content) {
+                    let content = content.clone();
+                    message.last_updated = Some(now);
+                    self.last_updated_timestamps.mark_updated(None, event_index, now);
+
+                    UpdateP2PSwapResult::Success(content)
+                } else {
+                    UpdateP2PSwapResult::StatusError(content.status.clone())
+                };
+            }
+        }
+
+        UpdateP2PSwapResult::SwapNotFound
+    }
+
     #[allow(clippy::too_many_arguments)]
     pub fn report_message(
         &mut self,
@@ -1156,8 +1583,16 @@ impl ChatEvents {
             if let Some(event) = self.main.remove(event_index) {
                 result.events.push(event_index);
                 if let ChatEventInternal::Message(m) = event.event {
+                    result.poll_token_locks.extend(poll_token_locks(&m));
                     if let Some(thread) = m.thread_summary {
-                        self.threads.remove(&m.message_index);
+                        if let Some(thread_events) = self.threads.remove(&m.message_index) {
+                            for message in ChatEventsListReader::new(&thread_events)
+                                .iter_events(None, true)
+                                .filter_map(|e| e.event.as_message())
+                            {
+                                result.poll_token_locks.extend(poll_token_locks(message));
+                            }
+                        }
                         result
                             .threads
                             .push((m.message_index, thread.participants_and_followers(true)));
@@ -1281,6 +1716,8 @@ impl ChatEvents {
                         | ChatEventInternal::ChatUnfrozen(_)
                         | ChatEventInternal::EventsTimeToLiveUpdated(_)
                 )
+                // P2P swaps must outlive their escrowed funds, so they are never set to expire
+                || matches!(event, ChatEventInternal::Message(m) if matches!(m.content, MessageContentInternal::P2PSwap(_)))
             {
                 None
             } else {
@@ -1292,6 +1729,14 @@ impl ChatEvents {
     }
 }
 
+fn poll_token_locks(message: &MessageInternal) -> Vec<(UserId, PollVoteTokenLock)> {
+    if let MessageContentInternal::Poll(p) = &message.content {
+        p.token_locks.iter().map(|(user_id, lock)| (*user_id, lock.clone())).collect()
+    } else {
+        Vec::new()
+    }
+}
+
 fn add_to_metrics<F: FnMut(&mut ChatMetricsInternal)>(
     metrics: &mut ChatMetricsInternal,
     per_user_metrics: &mut HashMap<UserId, ChatMetricsInternal>,
@@ -1393,6 +1838,9 @@ pub struct RegisterPollVoteArgs {
     pub thread_root_message_index: Option<MessageIndex>,
     pub message_index: MessageIndex,
     pub option_index: u32,
+    pub ranked_options: Option<Vec<u32>>,
+    pub weight: Option<u128>,
+    pub token_lock: Option<PollVoteTokenLock>,
     pub operation: VoteOperation,
     pub correlation_id: u64,
     pub now: TimestampMillis,
@@ -1405,6 +1853,24 @@ pub enum RegisterPollVoteResult {
     PollNotFound,
     OptionIndexOutOfRange,
     UserCannotChangeVote,
+    InvalidRanking,
+}
+
+pub struct RegisterCalendarEventRsvpArgs {
+    pub user_id: UserId,
+    pub min_visible_event_index: EventIndex,
+    pub thread_root_message_index: Option<MessageIndex>,
+    pub message_index: MessageIndex,
+    pub rsvp: Rsvp,
+    pub now: TimestampMillis,
+}
+
+pub enum RegisterCalendarEventRsvpResult {
+    Success(CalendarEventRsvps),
+    SuccessNoChange(CalendarEventRsvps),
+    EventNotFound,
+    EventStarted,
+    EventFull,
 }
 
 pub enum EndPollResult {
@@ -1455,6 +1921,7 @@ pub enum TipMessageResult {
 
 pub enum ReservePrizeResult {
     Success(Cryptocurrency, CanisterId, u128, u128),
+    EnteredDraw,
     MessageNotFound,
     AlreadyClaimed,
     PrizeFullyClaimed,
@@ -1474,6 +1941,12 @@ pub enum UnreservePrizeResult {
     ReservationNotFound,
 }
 
+pub enum UpdateP2PSwapResult {
+    Success(P2PSwapContentInternal),
+    StatusError(P2PSwapStatus),
+    SwapNotFound,
+}
+
 pub enum FollowThreadResult {
     Success,
     AlreadyFollowing,
@@ -1490,6 +1963,8 @@ pub enum UnfollowThreadResult {
 pub struct RemoveExpiredEventsResult {
     pub events: Vec<EventIndex>,
     pub threads: Vec<(MessageIndex, Vec<UserId>)>,
+    // Tokens locked in expired polls which must be refunded since the polls will no longer be ended
+    pub poll_token_locks: Vec<(UserId, PollVoteTokenLock)>,
 }
 
 #[derive(Copy, Clone)]
@@ -1516,3 +1991,191 @@ impl From<MessageId> for EventKey {
         EventKey::MessageId(value)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use types::{P2PSwapContentInitial, PollConfig, TokenInfo};
+
+    #[test]
+    fn open_p2p_swap_cannot_be_deleted_until_refunded() {
+        let mut events = ChatEvents::new_direct_chat(None, 0);
+        let message_id = push_p2p_swap(&mut events, 0);
+
+        assert!(matches!(delete(&mut events, message_id), DeleteMessageResult::NotAuthorized));
+
+        // The swap can still be cancelled and refunded since its content is retained
+        assert!(matches!(
+            events.cancel_p2p_swap(message_id, false, 1),
+            UpdateP2PSwapResult::Success(_)
+        ));
+        assert!(matches!(delete(&mut events, message_id), DeleteMessageResult::NotAuthorized));
+        assert!(matches!(
+            events.mark_p2p_swap_token0_refunded(message_id, 10, 2),
+            UpdateP2PSwapResult::Success(_)
+        ));
+
+        assert!(matches!(delete(&mut events, message_id), DeleteMessageResult::Success(_)));
+    }
+
+    #[test]
+    fn p2p_swaps_never_expire() {
+        let mut events = ChatEvents::new_direct_chat(Some(1000), 0);
+        let swap_message_id = push_p2p_swap(&mut events, 0);
+        let text_message_id = 2u128.into();
+        events.push_message(PushMessageArgs {
+            sender: user_id(1),
+            thread_root_message_index: None,
+            message_id: text_message_id,
+            content: MessageContentInternal::Text(TextContentInternal {
+                text: "hello".to_string(),
+            }),
+            mentioned: Vec::new(),
+            replies_to: None,
+            forwarded: false,
+            correlation_id: 0,
+            now: 0,
+        });
+
+        let result = events.remove_expired_events(2000);
+
+        assert_eq!(result.events.len(), 1);
+        let events_reader = events.main_events_reader();
+        assert!(events_reader.message_internal(swap_message_id.into()).is_some());
+        assert!(events_reader.message_internal(text_message_id.into()).is_none());
+    }
+
+    #[test]
+    fn expired_poll_token_locks_returned() {
+        let mut events = ChatEvents::new_group_chat("Group".to_string(), String::new(), user_id(1), Some(1000), 0);
+        push_message(&mut events, None, 1, token_weighted_poll(user_id(2)));
+
+        let result = events.remove_expired_events(2000);
+
+        assert_eq!(result.events.len(), 1);
+        assert_eq!(result.poll_token_locks.len(), 1);
+        assert_eq!(result.poll_token_locks[0].0, user_id(2));
+    }
+
+    #[test]
+    fn token_locks_of_polls_in_expired_threads_returned() {
+        let mut events = ChatEvents::new_group_chat("Group".to_string(), String::new(), user_id(1), Some(1000), 0);
+        push_message(&mut events, None, 1, text("root"));
+        push_message(&mut events, Some(0.into()), 2, token_weighted_poll(user_id(2)));
+        push_message(&mut events, Some(0.into()), 3, token_weighted_poll(user_id(3)));
+
+        let result = events.remove_expired_events(2000);
+
+        assert_eq!(result.threads.len(), 1);
+        let mut users: Vec<_> = result.poll_token_locks.iter().map(|(u, _)| *u).collect();
+        users.sort();
+        assert_eq!(users, vec![user_id(2), user_id(3)]);
+    }
+
+    fn push_message(
+        events: &mut ChatEvents,
+        thread_root_message_index: Option<MessageIndex>,
+        message_id: u128,
+        content: MessageContentInternal,
+    ) {
+        events.push_message(PushMessageArgs {
+            sender: user_id(1),
+            thread_root_message_index,
+            message_id: message_id.into(),
+            content,
+            mentioned: Vec::new(),
+            replies_to: None,
+            forwarded: false,
+            correlation_id: 0,
+            now: 0,
+        });
+    }
+
+    fn text(text: &str) -> MessageContentInternal {
+        MessageContentInternal::Text(TextContentInternal { text: text.to_string() })
+    }
+
+    fn token_weighted_poll(voter: UserId) -> MessageContentInternal {
+        let token = token(1);
+        MessageContentInternal::Poll(PollContentInternal {
+            config: PollConfig {
+                text: None,
+                options: vec!["yes".to_string(), "no".to_string()],
+                end_date: Some(10_000),
+                anonymous: false,
+                show_votes_before_end_date: true,
+                allow_multiple_votes_per_user: false,
+                allow_user_to_change_vote: true,
+                kind: Default::default(),
+                vote_weighting: Some(PollVoteWeighting::TokenBalance(token.ledger)),
+            },
+            votes: HashMap::new(),
+            ended: false,
+            ballots: HashMap::new(),
+            weights: HashMap::new(),
+            token_locks: [(
+                voter,
+                PollVoteTokenLock {
+                    ledger: token.ledger,
+                    token: token.token,
+                    amount: 1_000_000,
+                    fee: token.fee,
+                },
+            )]
+            .into_iter()
+            .collect(),
+        })
+    }
+
+    fn push_p2p_swap(events: &mut ChatEvents, now: TimestampMillis) -> MessageId {
+        let message_id = 1u128.into();
+        let content = P2PSwapContentInitial {
+            token0: token(1),
+            token0_amount: 1_000_000,
+            token1: token(2),
+            token1_amount: 2_000_000,
+            expires_in: 60_000,
+            caption: None,
+        };
+        events.push_message(PushMessageArgs {
+            sender: user_id(1),
+            thread_root_message_index: None,
+            message_id,
+            content: MessageContentInternal::P2PSwap(P2PSwapContentInternal::new(1, content, 5, now)),
+            mentioned: Vec::new(),
+            replies_to: None,
+            forwarded: false,
+            correlation_id: 0,
+            now,
+        });
+        message_id
+    }
+
+    fn delete(events: &mut ChatEvents, message_id: MessageId) -> DeleteMessageResult {
+        events
+            .delete_messages(DeleteUndeleteMessagesArgs {
+                caller: user_id(1),
+                is_admin: false,
+                min_visible_event_index: EventIndex::default(),
+                thread_root_message_index: None,
+                message_ids: vec![message_id],
+                now: 3,
+            })
+            .pop()
+            .unwrap()
+            .1
+    }
+
+    fn token(index: u8) -> TokenInfo {
+        TokenInfo {
+            token: Cryptocurrency::Other(format!("TOKEN{index}")),
+            ledger: Principal::from_slice(&[index]),
+            decimals: 8,
+            fee: 10_000,
+        }
+    }
+
+    fn user_id(index: u8) -> UserId {
+        Principal::from_slice(&[index]).into()
+    }
+}
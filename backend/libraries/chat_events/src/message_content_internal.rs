@@ -1,16 +1,21 @@
 use crate::DeletedByInternal;
 use ic_ledger_types::Tokens;
 use ledger_utils::{create_pending_transaction, format_crypto_amount};
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
 use search::Document;
 use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, HashSet};
 use types::{
-    is_default, is_empty_hashmap, is_empty_hashset, is_empty_slice, AudioContent, BlobReference, CanisterId,
-    CompletedCryptoTransaction, CryptoContent, CryptoTransaction, CustomContent, FileContent, GiphyContent, GiphyImageVariant,
-    ImageContent, MessageContent, MessageContentInitial, MessageIndex, MessageReminderContent, MessageReminderCreatedContent,
-    MessageReport, PendingCryptoTransaction, PollConfig, PollContent, PollVotes, PrizeContent, PrizeContentInitial,
-    PrizeWinnerContent, Proposal, ProposalContent, RegisterVoteResult, ReportedMessage, TextContent, ThumbnailData,
-    TimestampMillis, TimestampNanos, TotalVotes, UserId, VideoContent, VoteOperation,
+    is_default, is_empty_hashmap, is_empty_hashset, is_empty_slice, AudioContent, BlobReference, CalendarEventContent,
+    CalendarEventDetails, CalendarEventRsvps, CanisterId, CompletedCryptoTransaction, CryptoContent, CryptoTransaction,
+    CustomContent, FileContent, GiphyContent, GiphyImageVariant, ImageContent, MessageContent, MessageContentInitial,
+    MessageIndex, MessageReminderContent, MessageReminderCreatedContent, MessageReport, P2PSwapAccepted, P2PSwapCancelled,
+    P2PSwapCompleted, P2PSwapContent, P2PSwapContentInitial, P2PSwapReserved, P2PSwapStatus, PendingCryptoTransaction,
+    PollConfig, PollContent, PollKind, PollResults, PollVoteTokenLock, PollVotes, PrizeContent, PrizeContentInitial,
+    PrizeDistribution, PrizeEligibility, PrizeWinnerContent, Proposal, ProposalContent, RegisterVoteResult, ReportedMessage,
+    Rsvp, TextContent, ThumbnailData, TimestampMillis, TimestampNanos, TokenInfo, TotalVotes, UserId, VideoContent,
+    VoteOperation,
 };
 
 #[derive(Serialize, Deserialize, Clone, Debug)]
@@ -45,6 +50,10 @@ pub enum MessageContentInternal {
     MessageReminder(MessageReminderContentInternal),
     #[serde(rename = "rm")]
     ReportedMessage(ReportedMessageInternal),
+    #[serde(rename = "p2p")]
+    P2PSwap(P2PSwapContentInternal),
+    #[serde(rename = "ce")]
+    CalendarEvent(CalendarEventContentInternal),
     #[serde(rename = "cu")]
     Custom(CustomContentInternal),
 }
@@ -67,6 +76,8 @@ impl MessageContentInternal {
             MessageContentInternal::MessageReminderCreated(r) => MessageContent::MessageReminderCreated(r.hydrate(my_user_id)),
             MessageContentInternal::MessageReminder(r) => MessageContent::MessageReminder(r.hydrate(my_user_id)),
             MessageContentInternal::ReportedMessage(r) => MessageContent::ReportedMessage(r.hydrate(my_user_id)),
+            MessageContentInternal::P2PSwap(p) => MessageContent::P2PSwap(p.hydrate(my_user_id)),
+            MessageContentInternal::CalendarEvent(e) => MessageContent::CalendarEvent(e.hydrate(my_user_id)),
             MessageContentInternal::Custom(c) => MessageContent::Custom(c.hydrate(my_user_id)),
         }
     }
@@ -85,6 +96,8 @@ impl MessageContentInternal {
             MessageContentInternal::Prize(c) => c.caption.as_deref(),
             MessageContentInternal::MessageReminderCreated(r) => r.notes.as_deref(),
             MessageContentInternal::MessageReminder(r) => r.notes.as_deref(),
+            MessageContentInternal::P2PSwap(p) => p.caption.as_deref(),
+            MessageContentInternal::CalendarEvent(e) => Some(&e.details.title),
             MessageContentInternal::PrizeWinner(_)
             | MessageContentInternal::Deleted(_)
             | MessageContentInternal::ReportedMessage(_)
@@ -130,6 +143,8 @@ impl MessageContentInternal {
             | MessageContentInternal::MessageReminderCreated(_)
             | MessageContentInternal::MessageReminder(_)
             | MessageContentInternal::ReportedMessage(_)
+            | MessageContentInternal::P2PSwap(_)
+            | MessageContentInternal::CalendarEvent(_)
             | MessageContentInternal::Custom(_) => {}
         }
 
@@ -153,6 +168,10 @@ impl From<MessageContentInitial> for MessageContentInternal {
             MessageContentInitial::Prize(p) => MessageContentInternal::Prize(p.into()),
             MessageContentInitial::MessageReminderCreated(r) => MessageContentInternal::MessageReminderCreated(r.into()),
             MessageContentInitial::MessageReminder(r) => MessageContentInternal::MessageReminder(r.into()),
+            // P2P swaps are rejected by `validate_for_new_message` outside of direct chats and the user
+            // canister builds the internal content itself, so this conversion is never reached
+            MessageContentInitial::P2PSwap(_) => panic!("Unable to convert P2PSwapContentInitial to P2PSwapContentInternal"),
+            MessageContentInitial::CalendarEvent(e) => MessageContentInternal::CalendarEvent(e.into()),
             MessageContentInitial::Custom(c) => MessageContentInternal::Custom(c.into()),
         }
     }
@@ -213,6 +232,17 @@ impl From<&MessageContentInternal> for Document {
             }
             MessageContentInternal::MessageReminderCreated(r) => try_add_caption(&mut document, r.notes.as_ref()),
             MessageContentInternal::MessageReminder(r) => try_add_caption(&mut document, r.notes.as_ref()),
+            MessageContentInternal::P2PSwap(p) => {
+                document.add_field(p.token0.token.token_symbol().to_string(), 1.0, false);
+                document.add_field(p.token1.token.token_symbol().to_string(), 1.0, false);
+                try_add_caption(&mut document, p.caption.as_ref())
+            }
+            MessageContentInternal::CalendarEvent(e) => {
+                document.add_field("event".to_string(), 1.0, false);
+                document.add_field(e.details.title.clone(), 1.0, false);
+                try_add_caption(&mut document, e.details.description.as_ref());
+                try_add_caption(&mut document, e.details.location.as_ref());
+            }
             MessageContentInternal::Custom(c) => {
                 document.add_field(c.kind.clone(), 1.0, false);
             }
@@ -437,6 +467,15 @@ pub struct PollContentInternal {
     pub votes: HashMap<u32, Vec<UserId>>,
     #[serde(rename = "e", alias = "ended")]
     pub ended: bool,
+    // For ranked choice polls, each user's ranking of the options. `votes` holds their first preference.
+    #[serde(rename = "b", default, skip_serializing_if = "is_empty_hashmap")]
+    pub ballots: HashMap<UserId, Vec<u32>>,
+    // For weighted polls, the weight captured for each user when they first voted
+    #[serde(rename = "w", default, skip_serializing_if = "is_empty_hashmap")]
+    pub weights: HashMap<UserId, u128>,
+    // For token weighted polls, the tokens each user has locked in the chat, refunded when the poll ends
+    #[serde(rename = "l", default, skip_serializing_if = "is_empty_hashmap")]
+    pub token_locks: HashMap<UserId, PollVoteTokenLock>,
 }
 
 impl From<PollContent> for PollContentInternal {
@@ -445,6 +484,9 @@ impl From<PollContent> for PollContentInternal {
             config: value.config,
             votes: HashMap::new(),
             ended: false,
+            ballots: HashMap::new(),
+            weights: HashMap::new(),
+            token_locks: HashMap::new(),
         }
     }
 }
@@ -455,15 +497,25 @@ impl MessageContentInternalSubtype for PollContentInternal {
 
     fn hydrate(&self, my_user_id: Option<UserId>) -> Self::ContentType {
         let user_votes = if let Some(user_id) = my_user_id {
-            self.votes
-                .iter()
-                .filter(|(_, v)| v.contains(&user_id))
-                .map(|(k, _)| *k)
-                .collect()
+            if self.config.kind == PollKind::RankedChoice {
+                self.ballots.get(&user_id).cloned().unwrap_or_default()
+            } else {
+                self.votes
+                    .iter()
+                    .filter(|(_, v)| v.contains(&user_id))
+                    .map(|(k, _)| *k)
+                    .collect()
+            }
         } else {
             Vec::new()
         };
 
+        let mut config = self.config.clone();
+        if matches!(config.kind, PollKind::Quiz(_)) && !self.ended && user_votes.is_empty() {
+            // Only reveal the correct answer once the user has voted or the poll has ended
+            config.kind = PollKind::Quiz(None);
+        }
+
         let total_votes: TotalVotes;
         let hide_votes = self.config.end_date.is_some() && !self.ended && !self.config.show_votes_before_end_date;
         if hide_votes {
@@ -474,31 +526,48 @@ impl MessageContentInternalSubtype for PollContentInternal {
             total_votes = TotalVotes::Visible(self.votes.clone());
         }
 
+        let results = if !hide_votes && (self.config.kind == PollKind::RankedChoice || self.config.vote_weighting.is_some()) {
+            Some(self.results())
+        } else {
+            None
+        };
+
         PollContent {
-            config: self.config.clone(),
+            config,
             votes: PollVotes {
                 total: total_votes,
                 user: user_votes,
             },
             ended: self.ended,
+            results,
         }
     }
 }
 
 impl PollContentInternal {
-    pub fn register_vote(&mut self, user_id: UserId, option_index: u32, operation: VoteOperation) -> RegisterVoteResult {
-        if self.ended {
+    pub fn register_vote(
+        &mut self,
+        user_id: UserId,
+        option_index: u32,
+        ranked_options: Option<Vec<u32>>,
+        weight: Option<u128>,
+        token_lock: Option<PollVoteTokenLock>,
+        operation: VoteOperation,
+    ) -> RegisterVoteResult {
+        let result = if self.ended {
             RegisterVoteResult::PollEnded
         } else if option_index > (self.config.options.len() as u32) + 1 {
             RegisterVoteResult::OptionIndexOutOfRange
+        } else if matches!(self.config.kind, PollKind::Quiz(_)) && matches!(operation, VoteOperation::DeleteVote) {
+            RegisterVoteResult::UserCannotChangeVote
+        } else if self.config.kind == PollKind::RankedChoice {
+            self.register_ranked_vote(user_id, option_index, ranked_options, operation)
         } else {
             match operation {
                 VoteOperation::RegisterVote => {
-                    let votes = self.votes.entry(option_index).or_default();
-                    if votes.contains(&user_id) {
+                    if self.votes.get(&option_index).map_or(false, |v| v.contains(&user_id)) {
                         return RegisterVoteResult::SuccessNoChange;
                     }
-                    votes.push(user_id);
                     let mut existing_vote_removed = false;
                     if !self.config.allow_multiple_votes_per_user {
                         // If the user has already left a vote, remove it
@@ -514,6 +583,7 @@ impl PollContentInternal {
                             }
                         }
                     }
+                    self.votes.entry(option_index).or_default().push(user_id);
 
                     RegisterVoteResult::Success(existing_vote_removed)
                 }
@@ -521,14 +591,261 @@ impl PollContentInternal {
                     if let Some(votes) = self.votes.get_mut(&option_index) {
                         if let Some((index, _)) = votes.iter().enumerate().find(|(_, &u)| u == user_id) {
                             votes.remove(index);
-                            return RegisterVoteResult::Success(true);
+                            RegisterVoteResult::Success(true)
+                        } else {
+                            RegisterVoteResult::SuccessNoChange
                         }
+                    } else {
+                        RegisterVoteResult::SuccessNoChange
                     }
+                }
+            }
+        };
+
+        if matches!(result, RegisterVoteResult::Success(_) | RegisterVoteResult::SuccessNoChange)
+            && self.config.vote_weighting.is_some()
+        {
+            if let Some(lock) = token_lock {
+                // Locked tokens stay with the user's weight until the poll ends, even if they remove their vote
+                *self.weights.entry(user_id).or_default() += lock.amount;
+                self.token_locks
+                    .entry(user_id)
+                    .and_modify(|l| l.amount += lock.amount)
+                    .or_insert(lock);
+            } else if self.votes.values().any(|v| v.contains(&user_id)) {
+                if let Some(weight) = weight {
+                    self.weights.entry(user_id).or_insert(weight);
+                }
+            } else if !self.token_locks.contains_key(&user_id) {
+                self.weights.remove(&user_id);
+            }
+        }
+
+        result
+    }
+
+    pub fn has_weight(&self, user_id: &UserId) -> bool {
+        self.weights.contains_key(user_id)
+    }
+
+    fn register_ranked_vote(
+        &mut self,
+        user_id: UserId,
+        option_index: u32,
+        ranked_options: Option<Vec<u32>>,
+        operation: VoteOperation,
+    ) -> RegisterVoteResult {
+        match operation {
+            VoteOperation::RegisterVote => {
+                let ranking = ranked_options.unwrap_or_else(|| vec![option_index]);
+                let options_count = self.config.options.len() as u32;
+                let mut seen = HashSet::new();
+                if ranking.first() != Some(&option_index) || ranking.iter().any(|o| *o >= options_count || !seen.insert(*o)) {
+                    return RegisterVoteResult::InvalidRanking;
+                }
+
+                let existing_vote_removed = match self.ballots.get(&user_id) {
+                    Some(existing) if *existing == ranking => return RegisterVoteResult::SuccessNoChange,
+                    Some(_) if !self.config.allow_user_to_change_vote => return RegisterVoteResult::UserCannotChangeVote,
+                    Some(existing) => {
+                        let previous_first = existing[0];
+                        if let Some(votes) = self.votes.get_mut(&previous_first) {
+                            votes.retain(|u| *u != user_id);
+                        }
+                        true
+                    }
+                    None => false,
+                };
+
+                self.votes.entry(option_index).or_default().push(user_id);
+                self.ballots.insert(user_id, ranking);
+                RegisterVoteResult::Success(existing_vote_removed)
+            }
+            VoteOperation::DeleteVote => {
+                if self.ballots.get(&user_id).map_or(false, |b| b[0] == option_index) {
+                    self.ballots.remove(&user_id);
+                    if let Some(votes) = self.votes.get_mut(&option_index) {
+                        votes.retain(|u| *u != user_id);
+                    }
+                    RegisterVoteResult::Success(true)
+                } else {
                     RegisterVoteResult::SuccessNoChange
                 }
             }
         }
     }
+
+    fn results(&self) -> PollResults {
+        let option_weights = self
+            .votes
+            .iter()
+            .map(|(o, users)| (*o, users.iter().map(|u| self.vote_weight(u)).sum()))
+            .collect();
+
+        if self.config.kind == PollKind::RankedChoice {
+            let (rounds, winner) = self.instant_runoff();
+            PollResults {
+                option_weights,
+                rounds,
+                winner,
+            }
+        } else {
+            let winner = single_leader(&option_weights);
+            PollResults {
+                option_weights,
+                rounds: Vec::new(),
+                winner,
+            }
+        }
+    }
+
+    // Each round, every ballot counts towards its most preferred option which has not yet been
+    // eliminated. If no option has a majority, the option with the lowest tally is eliminated (ties
+    // are broken by eliminating the option with the higher index) and the next round begins.
+    fn instant_runoff(&self) -> (Vec<HashMap<u32, u128>>, Option<u32>) {
+        let mut remaining: HashSet<u32> = (0..self.config.options.len() as u32).collect();
+        let mut rounds = Vec::new();
+
+        if self.ballots.is_empty() {
+            return (rounds, None);
+        }
+
+        while !remaining.is_empty() {
+            let mut tallies: HashMap<u32, u128> = remaining.iter().map(|o| (*o, 0)).collect();
+            for (user_id, ballot) in self.ballots.iter() {
+                if let Some(option) = ballot.iter().find(|o| remaining.contains(o)) {
+                    *tallies.entry(*option).or_default() += self.vote_weight(user_id);
+                }
+            }
+
+            let total: u128 = tallies.values().sum();
+            let leader = tallies
+                .iter()
+                .max_by_key(|(o, v)| (**v, std::cmp::Reverse(**o)))
+                .map(|(o, v)| (*o, *v));
+            let loser = tallies
+                .iter()
+                .min_by_key(|(o, v)| (**v, std::cmp::Reverse(**o)))
+                .map(|(o, _)| *o);
+            rounds.push(tallies);
+
+            if total == 0 {
+                return (rounds, None);
+            }
+            if let Some((option, votes)) = leader {
+                if votes.saturating_mul(2) > total || remaining.len() == 1 {
+                    return (rounds, Some(option));
+                }
+            }
+            if let Some(option) = loser {
+                remaining.remove(&option);
+            }
+        }
+
+        (rounds, None)
+    }
+
+    fn vote_weight(&self, user_id: &UserId) -> u128 {
+        if self.config.vote_weighting.is_some() {
+            self.weights.get(user_id).copied().unwrap_or_default()
+        } else {
+            1
+        }
+    }
+}
+
+fn single_leader(option_weights: &HashMap<u32, u128>) -> Option<u32> {
+    let max = option_weights.values().copied().max().filter(|m| *m > 0)?;
+    let mut leaders = option_weights.iter().filter(|(_, w)| **w == max);
+    let (option, _) = leaders.next()?;
+    if leaders.next().is_none() {
+        Some(*option)
+    } else {
+        None
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct CalendarEventContentInternal {
+    #[serde(rename = "d")]
+    pub details: CalendarEventDetails,
+    #[serde(rename = "r", default, skip_serializing_if = "is_empty_hashmap")]
+    pub rsvps: HashMap<UserId, Rsvp>,
+}
+
+impl From<CalendarEventContent> for CalendarEventContentInternal {
+    fn from(value: CalendarEventContent) -> Self {
+        CalendarEventContentInternal {
+            details: value.details,
+            rsvps: HashMap::new(),
+        }
+    }
+}
+
+impl MessageContentInternalSubtype for CalendarEventContentInternal {
+    type ContentType = CalendarEventContent;
+    type ContentTypeInitial = CalendarEventContent;
+
+    fn hydrate(&self, my_user_id: Option<UserId>) -> Self::ContentType {
+        CalendarEventContent {
+            details: self.details.clone(),
+            rsvps: self.rsvp_counts(my_user_id),
+        }
+    }
+}
+
+impl CalendarEventContentInternal {
+    pub fn rsvp_counts(&self, my_user_id: Option<UserId>) -> CalendarEventRsvps {
+        let mut counts = CalendarEventRsvps {
+            user: my_user_id.and_then(|u| self.rsvps.get(&u).copied()),
+            ..Default::default()
+        };
+        for rsvp in self.rsvps.values() {
+            match rsvp {
+                Rsvp::Yes => counts.yes += 1,
+                Rsvp::No => counts.no += 1,
+                Rsvp::Maybe => counts.maybe += 1,
+            }
+        }
+        counts
+    }
+
+    pub fn register_rsvp(&mut self, user_id: UserId, rsvp: Rsvp, now: TimestampMillis) -> RegisterRsvpResult {
+        if now >= self.details.start {
+            return RegisterRsvpResult::EventStarted;
+        }
+
+        if self.rsvps.get(&user_id) == Some(&rsvp) {
+            return RegisterRsvpResult::SuccessNoChange;
+        }
+
+        if rsvp == Rsvp::Yes {
+            if let Some(capacity) = self.details.capacity {
+                let attending = self.rsvps.values().filter(|r| matches!(r, Rsvp::Yes)).count() as u32;
+                if attending >= capacity {
+                    return RegisterRsvpResult::EventFull;
+                }
+            }
+        }
+
+        self.rsvps.insert(user_id, rsvp);
+        RegisterRsvpResult::Success
+    }
+
+    pub fn attendees(&self) -> Vec<UserId> {
+        self.rsvps
+            .iter()
+            .filter(|(_, r)| matches!(r, Rsvp::Yes))
+            .map(|(u, _)| *u)
+            .collect()
+    }
+}
+
+pub enum RegisterRsvpResult {
+    Success,
+    SuccessNoChange,
+    EventStarted,
+    EventFull,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug)]
@@ -688,6 +1005,13 @@ pub struct PrizeContentInternal {
     pub caption: Option<String>,
     #[serde(rename = "d", default, skip_serializing_if = "is_default")]
     pub diamond_only: bool,
+    #[serde(rename = "m", default, skip_serializing_if = "is_default")]
+    pub distribution: PrizeDistribution,
+    #[serde(rename = "g", default, skip_serializing_if = "is_empty_slice")]
+    pub eligibility: Vec<PrizeEligibility>,
+    // Users who have entered a prize draw, in the order they entered. Cleared once the draw is made.
+    #[serde(rename = "n", default, skip_serializing_if = "is_empty_slice")]
+    pub entrants: Vec<UserId>,
 }
 
 impl PrizeContentInternal {
@@ -708,6 +1032,47 @@ impl PrizeContentInternal {
             None
         }
     }
+
+    // Picks the winners of a prize draw, reserving each of their prizes so that they can then be
+    // paid out. Any prizes left over are returned by `prize_refund`.
+    pub fn draw_winners(&mut self, rng: &mut StdRng) -> Vec<(UserId, u128)> {
+        let mut entrants = std::mem::take(&mut self.entrants);
+        let mut winners = Vec::new();
+
+        match self.distribution {
+            PrizeDistribution::FirstComeFirstServed => {}
+            PrizeDistribution::RandomDraw => {
+                entrants.shuffle(rng);
+                for user_id in entrants {
+                    if let Some(amount) = self.prizes_remaining.pop() {
+                        winners.push((user_id, amount.e8s() as u128));
+                    } else {
+                        break;
+                    }
+                }
+            }
+            PrizeDistribution::ProportionalSplit => {
+                if !entrants.is_empty() {
+                    // The prize fund includes a fee for each prize, so paying each entrant their
+                    // share plus a fee uses up the whole fund, bar any rounding
+                    let fee = self.transaction.fee();
+                    let fund = self.prizes_remaining.iter().map(|t| (t.e8s() as u128) + fee).sum::<u128>();
+                    let share = fund / entrants.len() as u128;
+                    if share > fee {
+                        let leftover = fund - share * entrants.len() as u128;
+                        self.prizes_remaining =
+                            if leftover > fee { vec![Tokens::from_e8s((leftover - fee) as u64)] } else { Vec::new() };
+                        winners = entrants.into_iter().map(|u| (u, share - fee)).collect();
+                    }
+                }
+            }
+        }
+
+        for (user_id, _) in winners.iter() {
+            self.reservations.insert(*user_id);
+        }
+        winners
+    }
 }
 
 impl From<PrizeContentInitial> for PrizeContentInternal {
@@ -721,6 +1086,9 @@ impl From<PrizeContentInitial> for PrizeContentInternal {
                 end_date: value.end_date,
                 caption: value.caption,
                 diamond_only: value.diamond_only,
+                distribution: value.distribution,
+                eligibility: value.eligibility,
+                entrants: Vec::new(),
             }
         } else {
             panic!("Unable to convert PrizeContentInitial to PrizeContentInternal");
@@ -741,6 +1109,9 @@ impl MessageContentInternalSubtype for PrizeContentInternal {
             end_date: self.end_date,
             caption: self.caption.clone(),
             diamond_only: self.diamond_only,
+            distribution: self.distribution,
+            eligibility: self.eligibility.clone(),
+            entrants: self.entrants.len() as u32,
         }
     }
 }
@@ -868,6 +1239,183 @@ impl MessageContentInternalSubtype for ReportedMessageInternal {
     }
 }
 
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct P2PSwapContentInternal {
+    #[serde(rename = "i")]
+    pub swap_id: u32,
+    #[serde(rename = "t0")]
+    pub token0: TokenInfo,
+    #[serde(rename = "a0")]
+    pub token0_amount: u128,
+    #[serde(rename = "t1")]
+    pub token1: TokenInfo,
+    #[serde(rename = "a1")]
+    pub token1_amount: u128,
+    #[serde(rename = "e")]
+    pub expires_at: TimestampMillis,
+    #[serde(rename = "c", default, skip_serializing_if = "Option::is_none")]
+    pub caption: Option<String>,
+    #[serde(rename = "ti")]
+    pub token0_txn_in: u64,
+    #[serde(rename = "s")]
+    pub status: P2PSwapStatus,
+    // Settlement consists of 2 transfers out of the escrow account, these are populated as each
+    // one completes, once both have completed the status moves from `Accepted` to `Completed`
+    #[serde(rename = "o0", default, skip_serializing_if = "Option::is_none")]
+    pub token0_txn_out: Option<u64>,
+    #[serde(rename = "o1", default, skip_serializing_if = "Option::is_none")]
+    pub token1_txn_out: Option<u64>,
+}
+
+impl P2PSwapContentInternal {
+    pub fn new(
+        swap_id: u32,
+        content: P2PSwapContentInitial,
+        token0_txn_in: u64,
+        now: TimestampMillis,
+    ) -> P2PSwapContentInternal {
+        P2PSwapContentInternal {
+            swap_id,
+            token0: content.token0,
+            token0_amount: content.token0_amount,
+            token1: content.token1,
+            token1_amount: content.token1_amount,
+            expires_at: now + content.expires_in,
+            caption: content.caption,
+            token0_txn_in,
+            status: P2PSwapStatus::Open,
+            token0_txn_out: None,
+            token1_txn_out: None,
+        }
+    }
+
+    pub fn is_open(&self, now: TimestampMillis) -> bool {
+        matches!(self.status, P2PSwapStatus::Open) && self.expires_at > now
+    }
+
+    // Funds remain in escrow until the swap has either been completed or refunded, and the transfers
+    // out of escrow are built from this content, so the message must be retained until then
+    pub fn escrow_released(&self) -> bool {
+        match &self.status {
+            P2PSwapStatus::Completed(_) => true,
+            P2PSwapStatus::Cancelled(c) | P2PSwapStatus::Expired(c) => c.token0_txn_out.is_some(),
+            _ => false,
+        }
+    }
+
+    pub fn reserve(&mut self, user_id: UserId, now: TimestampMillis) -> bool {
+        if self.is_open(now) {
+            self.status = P2PSwapStatus::Reserved(P2PSwapReserved { reserved_by: user_id });
+            true
+        } else {
+            false
+        }
+    }
+
+    pub fn unreserve(&mut self, user_id: UserId) -> bool {
+        if matches!(&self.status, P2PSwapStatus::Reserved(r) if r.reserved_by == user_id) {
+            self.status = P2PSwapStatus::Open;
+            true
+        } else {
+            false
+        }
+    }
+
+    pub fn accept(&mut self, user_id: UserId, token1_txn_in: u64, now: TimestampMillis) -> bool {
+        let can_accept = match &self.status {
+            P2PSwapStatus::Open => self.expires_at > now,
+            P2PSwapStatus::Reserved(r) => r.reserved_by == user_id,
+            _ => false,
+        };
+        if can_accept {
+            self.status = P2PSwapStatus::Accepted(P2PSwapAccepted {
+                accepted_by: user_id,
+                token1_txn_in,
+            });
+        }
+        can_accept
+    }
+
+    pub fn cancel(&mut self, expired: bool) -> bool {
+        if matches!(self.status, P2PSwapStatus::Open) {
+            let cancelled = P2PSwapCancelled { token0_txn_out: None };
+            self.status = if expired { P2PSwapStatus::Expired(cancelled) } else { P2PSwapStatus::Cancelled(cancelled) };
+            true
+        } else {
+            false
+        }
+    }
+
+    pub fn mark_token0_refunded(&mut self, token0_txn_out: u64) -> bool {
+        match &mut self.status {
+            P2PSwapStatus::Cancelled(c) | P2PSwapStatus::Expired(c) if c.token0_txn_out.is_none() => {
+                c.token0_txn_out = Some(token0_txn_out);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    pub fn mark_transfer_out_completed(&mut self, token0: bool, block_index: u64) -> bool {
+        let P2PSwapStatus::Accepted(accepted) = &self.status else {
+            return false;
+        };
+
+        if token0 {
+            self.token0_txn_out = Some(block_index);
+        } else {
+            self.token1_txn_out = Some(block_index);
+        }
+
+        if let (Some(token0_txn_out), Some(token1_txn_out)) = (self.token0_txn_out, self.token1_txn_out) {
+            self.status = P2PSwapStatus::Completed(P2PSwapCompleted {
+                accepted_by: accepted.accepted_by,
+                token1_txn_in: accepted.token1_txn_in,
+                token0_txn_out,
+                token1_txn_out,
+            });
+        }
+        true
+    }
+}
+
+impl From<P2PSwapContent> for P2PSwapContentInternal {
+    fn from(value: P2PSwapContent) -> Self {
+        P2PSwapContentInternal {
+            swap_id: value.swap_id,
+            token0: value.token0,
+            token0_amount: value.token0_amount,
+            token1: value.token1,
+            token1_amount: value.token1_amount,
+            expires_at: value.expires_at,
+            caption: value.caption,
+            token0_txn_in: value.token0_txn_in,
+            status: value.status,
+            token0_txn_out: None,
+            token1_txn_out: None,
+        }
+    }
+}
+
+impl MessageContentInternalSubtype for P2PSwapContentInternal {
+    type ContentType = P2PSwapContent;
+    type ContentTypeInitial = P2PSwapContent;
+
+    fn hydrate(&self, _my_user_id: Option<UserId>) -> Self::ContentType {
+        P2PSwapContent {
+            swap_id: self.swap_id,
+            token0: self.token0.clone(),
+            token0_amount: self.token0_amount,
+            token1: self.token1.clone(),
+            token1_amount: self.token1_amount,
+            expires_at: self.expires_at,
+            caption: self.caption.clone(),
+            token0_txn_in: self.token0_txn_in,
+            status: self.status.clone(),
+        }
+    }
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug)]
 pub struct CustomContentInternal {
     #[serde(rename = "k", alias = "kind")]
@@ -896,3 +1444,87 @@ impl MessageContentInternalSubtype for CustomContentInternal {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use candid::Principal;
+
+    #[test]
+    fn rsvps_counted_by_response() {
+        let mut event = calendar_event(None);
+        event.register_rsvp(user_id(1), Rsvp::Yes, 0);
+        event.register_rsvp(user_id(2), Rsvp::Maybe, 0);
+        event.register_rsvp(user_id(3), Rsvp::Yes, 0);
+
+        let counts = event.rsvp_counts(Some(user_id(2)));
+
+        assert_eq!(counts.yes, 2);
+        assert_eq!(counts.no, 0);
+        assert_eq!(counts.maybe, 1);
+        assert_eq!(counts.user, Some(Rsvp::Maybe));
+        assert_eq!(event.attendees().len(), 2);
+    }
+
+    #[test]
+    fn rsvp_can_be_changed() {
+        let mut event = calendar_event(None);
+
+        assert!(matches!(
+            event.register_rsvp(user_id(1), Rsvp::Yes, 0),
+            RegisterRsvpResult::Success
+        ));
+        assert!(matches!(
+            event.register_rsvp(user_id(1), Rsvp::Yes, 0),
+            RegisterRsvpResult::SuccessNoChange
+        ));
+        assert!(matches!(
+            event.register_rsvp(user_id(1), Rsvp::No, 0),
+            RegisterRsvpResult::Success
+        ));
+        assert!(event.attendees().is_empty());
+    }
+
+    #[test]
+    fn rsvp_rejected_once_full() {
+        let mut event = calendar_event(Some(1));
+        event.register_rsvp(user_id(1), Rsvp::Yes, 0);
+
+        assert!(matches!(
+            event.register_rsvp(user_id(2), Rsvp::Yes, 0),
+            RegisterRsvpResult::EventFull
+        ));
+        assert!(matches!(
+            event.register_rsvp(user_id(2), Rsvp::Maybe, 0),
+            RegisterRsvpResult::Success
+        ));
+    }
+
+    #[test]
+    fn rsvp_rejected_once_started() {
+        let mut event = calendar_event(None);
+
+        assert!(matches!(
+            event.register_rsvp(user_id(1), Rsvp::Yes, 100),
+            RegisterRsvpResult::EventStarted
+        ));
+    }
+
+    fn calendar_event(capacity: Option<u32>) -> CalendarEventContentInternal {
+        CalendarEventContentInternal {
+            details: CalendarEventDetails {
+                title: "Event".to_string(),
+                description: None,
+                start: 100,
+                end: 200,
+                location: None,
+                capacity,
+            },
+            rsvps: HashMap::new(),
+        }
+    }
+
+    fn user_id(index: u8) -> UserId {
+        Principal::from_slice(&[index]).into()
+    }
+}
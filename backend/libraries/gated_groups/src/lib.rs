@@ -1,15 +1,19 @@
 use candid::Principal;
+use icrc_ledger_types::icrc2::allowance::AllowanceArgs;
 use icrc_ledger_types::icrc2::transfer_from::TransferFromArgs;
 use sns_governance_canister::types::neuron::DissolveState;
 use sns_governance_canister::types::Neuron;
 use types::{
-    AccessGate, CanisterId, GateCheckFailedReason, PaymentGate, SnsNeuronGate, TimestampNanos, UserId, VerifiedCredentialGate,
+    AccessGate, CanisterId, Cryptocurrency, GateCheckFailedReason, PaymentGate, PollVoteTokenLock, PollVoteWeighting,
+    SnsNeuronGate, TimestampMillis, TimestampNanos, UserId, VerifiedCredentialGate,
 };
 use user_index_canister_c2c_client::LookupUserError;
-use utils::consts::MEMO_JOINING_FEE;
+use utils::consts::{MEMO_JOINING_FEE, MEMO_POLL_VOTE_LOCK};
 
 pub enum CheckIfPassesGateResult {
     Success,
+    // The gate is a payment gate and the joining fee was taken in the transfer at this block index
+    PaymentTaken(u64),
     Failed(GateCheckFailedReason),
     InternalError(String),
 }
@@ -28,6 +32,8 @@ pub async fn check_if_passes_gate(args: CheckGateArgs) -> CheckIfPassesGateResul
         AccessGate::DiamondMember => check_diamond_member_gate(args.user_id, args.user_index_canister).await,
         AccessGate::SnsNeuron(g) => check_sns_neuron_gate(&g, args.user_id).await,
         AccessGate::Payment(g) => try_transfer_from(&g, args.user_id, args.this_canister, args.now_nanos).await,
+        // User group gates are checked by the community itself, so should never reach here
+        AccessGate::UserGroup(_) => CheckIfPassesGateResult::Failed(GateCheckFailedReason::NotInUserGroup),
     }
 }
 
@@ -110,7 +116,9 @@ async fn try_transfer_from(
     )
     .await
     {
-        Ok(icrc_ledger_canister::icrc2_transfer_from::Response::Ok(_)) => CheckIfPassesGateResult::Success,
+        Ok(icrc_ledger_canister::icrc2_transfer_from::Response::Ok(block_index)) => {
+            CheckIfPassesGateResult::PaymentTaken(block_index.0.try_into().unwrap())
+        }
         Ok(icrc_ledger_canister::icrc2_transfer_from::Response::Err(err)) => {
             CheckIfPassesGateResult::Failed(GateCheckFailedReason::PaymentFailed(err))
         }
@@ -118,6 +126,131 @@ async fn try_transfer_from(
     }
 }
 
+pub struct PollVoteWeightArgs {
+    pub weighting: PollVoteWeighting,
+    pub user_id: UserId,
+    pub poll_created: TimestampMillis,
+    pub poll_end: TimestampMillis,
+    pub this_canister: CanisterId,
+    pub now_nanos: TimestampNanos,
+}
+
+// Gets the weight of a user's vote in a weighted poll.
+// ICRC-1 ledgers don't expose historical balances, so for token weighted polls the tokens the user
+// has approved for this canister are transferred in and locked, and the returned lock must be
+// refunded once the poll ends. For neuron weighted polls, only neurons which existed
+// when the poll was created and which can't be dissolved before the poll ends are counted.
+pub async fn get_poll_vote_weight(args: PollVoteWeightArgs) -> Result<(u128, Option<PollVoteTokenLock>), String> {
+    match args.weighting {
+        PollVoteWeighting::TokenBalance(ledger_canister_id) => lock_poll_vote_tokens(ledger_canister_id, &args).await,
+        PollVoteWeighting::NeuronStake(governance_canister_id) => {
+            let now_seconds = args.now_nanos / 1_000_000_000;
+            let mut total: u128 = 0;
+            let mut start_page_at = None;
+
+            loop {
+                let list_neurons_args = sns_governance_canister::list_neurons::Args {
+                    limit: NEURONS_PAGE_SIZE,
+                    start_page_at: start_page_at.take(),
+                    of_principal: Some(Principal::from(args.user_id)),
+                };
+
+                let neurons =
+                    match sns_governance_canister_c2c_client::list_neurons(governance_canister_id, &list_neurons_args).await {
+                        Ok(response) => response.neurons,
+                        Err(error) => return Err(format!("Error calling 'list_neurons': {error:?}")),
+                    };
+
+                total += neurons
+                    .iter()
+                    .filter(|n| n.created_timestamp_seconds <= args.poll_created / 1000)
+                    .filter(|n| now_seconds + dissolve_delay_seconds(n, now_seconds) >= args.poll_end / 1000)
+                    .map(|n| (n.cached_neuron_stake_e8s + n.staked_maturity_e8s_equivalent.unwrap_or_default()) as u128)
+                    .sum::<u128>();
+
+                if neurons.len() < NEURONS_PAGE_SIZE as usize {
+                    return Ok((total, None));
+                }
+
+                start_page_at = neurons.last().and_then(|n| n.id.clone());
+                if start_page_at.is_none() {
+                    return Ok((total, None));
+                }
+            }
+        }
+    }
+}
+
+const NEURONS_PAGE_SIZE: u32 = 100;
+
+async fn lock_poll_vote_tokens(
+    ledger_canister_id: CanisterId,
+    args: &PollVoteWeightArgs,
+) -> Result<(u128, Option<PollVoteTokenLock>), String> {
+    let owner: Principal = args.user_id.into();
+
+    let (fee_response, symbol_response, allowance_response) = futures::future::join3(
+        icrc_ledger_canister_c2c_client::icrc1_fee(ledger_canister_id),
+        icrc_ledger_canister_c2c_client::icrc1_symbol(ledger_canister_id),
+        icrc_ledger_canister_c2c_client::icrc2_allowance(
+            ledger_canister_id,
+            &AllowanceArgs {
+                account: owner.into(),
+                spender: args.this_canister.into(),
+            },
+        ),
+    )
+    .await;
+
+    let fee = match fee_response {
+        Ok(fee) => u128::try_from(fee.0).map_err(|_| "Fee out of range".to_string())?,
+        Err(error) => return Err(format!("Error calling 'icrc1_fee': {error:?}")),
+    };
+    let symbol = symbol_response.map_err(|error| format!("Error calling 'icrc1_symbol': {error:?}"))?;
+    let allowance = match allowance_response {
+        Ok(response) if response.expires_at.map_or(true, |ts| ts > args.now_nanos) => {
+            u128::try_from(response.allowance.0).unwrap_or(u128::MAX)
+        }
+        Ok(_) => 0,
+        Err(error) => return Err(format!("Error calling 'icrc2_allowance': {error:?}")),
+    };
+
+    // The transfer_from fee is taken from the allowance and the refund fee from the locked amount
+    if allowance <= 2 * fee {
+        return Ok((0, None));
+    }
+    let amount = allowance - fee;
+
+    match icrc_ledger_canister_c2c_client::icrc2_transfer_from(
+        ledger_canister_id,
+        &TransferFromArgs {
+            spender_subaccount: None,
+            from: owner.into(),
+            to: args.this_canister.into(),
+            amount: amount.into(),
+            fee: Some(fee.into()),
+            memo: Some(MEMO_POLL_VOTE_LOCK.to_vec().into()),
+            created_at_time: Some(args.now_nanos),
+        },
+    )
+    .await
+    {
+        Ok(icrc_ledger_canister::icrc2_transfer_from::Response::Ok(_)) => Ok((
+            amount,
+            Some(PollVoteTokenLock {
+                ledger: ledger_canister_id,
+                token: Cryptocurrency::Other(symbol),
+                amount,
+                fee,
+            }),
+        )),
+        Ok(icrc_ledger_canister::icrc2_transfer_from::Response::Err(error)) => {
+            Err(format!("Failed to lock tokens for poll vote: {error:?}"))
+        }
+        Err(error) => Err(format!("Error calling 'icrc2_transfer_from': {error:?}")),
+    }
+}
+
 fn dissolve_delay_seconds(neuron: &Neuron, now_seconds: u64) -> u64 {
     match neuron.dissolve_state {
         Some(DissolveState::DissolveDelaySeconds(d)) => d,
@@ -15,8 +15,9 @@ use types::{
     GroupSubtype, GroupVisibilityChanged, HydratedMention, InvalidPollReason, MemberLeft, MembersRemoved, Message,
     MessageContent, MessageContentInitial, MessageId, MessageIndex, MessageMatch, MessagePermissions, MessagePinned,
     MessageUnpinned, MessagesResponse, Milliseconds, OptionUpdate, OptionalGroupPermissions, OptionalMessagePermissions,
-    PermissionsChanged, PushEventResult, PushIfNotContains, Reaction, RoleChanged, Rules, SelectedGroupUpdates, ThreadPreview,
-    TimestampMillis, Timestamped, UpdatedRules, UserId, UsersBlocked, UsersInvited, Version, Versioned, VersionedRules,
+    PermissionsChanged, PollVoteTokenLock, PushEventResult, PushIfNotContains, Reaction, RoleChanged, Rules,
+    SelectedGroupUpdates, ThreadPreview, TimestampMillis, Timestamped, UpdatedRules, UserId, UsersBlocked, UsersInvited,
+    Version, Versioned, VersionedRules, MAX_CHAT_REPORT_TIMEOUT,
 };
 use utils::document_validation::validate_avatar;
 use utils::text_validation::{
@@ -26,12 +27,18 @@ use utils::text_validation::{
 mod invited_users;
 mod members;
 mod mentions;
+mod proposal_vote_delegations;
+mod reports;
 mod roles;
+mod typing;
 
 pub use invited_users::*;
 pub use members::*;
 pub use mentions::*;
+pub use proposal_vote_delegations::*;
+pub use reports::*;
 pub use roles::*;
+pub use typing::*;
 
 #[derive(Serialize, Deserialize)]
 pub struct GroupChatCore {
@@ -60,6 +67,12 @@ pub struct GroupChatCore {
     pub gate: Timestamped<Option<AccessGate>>,
     pub invited_users: InvitedUsers,
     pub min_visible_indexes_for_new_members: Option<(EventIndex, MessageIndex)>,
+    #[serde(default)]
+    pub reports: ChatReports,
+    #[serde(default)]
+    pub proposal_vote_delegations: ProposalVoteDelegations,
+    #[serde(skip)]
+    pub typing: TypingUsers,
 }
 
 #[allow(clippy::too_many_arguments)]
@@ -101,6 +114,9 @@ impl GroupChatCore {
             gate: Timestamped::new(gate, now),
             invited_users: InvitedUsers::default(),
             min_visible_indexes_for_new_members: None,
+            reports: ChatReports::default(),
+            proposal_vote_delegations: ProposalVoteDelegations::default(),
+            typing: TypingUsers::default(),
         }
     }
 
@@ -534,7 +550,7 @@ impl GroupChatCore {
 
         match self.members.get_mut(&sender) {
             Some(m) => {
-                if m.suspended.value {
+                if m.suspended.value || m.is_timed_out(now) {
                     return UserSuspended;
                 }
                 if let Some(version) = rules_accepted {
@@ -555,6 +571,9 @@ impl GroupChatCore {
                 ContentValidationError::Empty => MessageEmpty,
                 ContentValidationError::TextTooLong(max_length) => TextTooLong(max_length),
                 ContentValidationError::InvalidPoll(reason) => InvalidPoll(reason),
+                ContentValidationError::InvalidCalendarEvent(reason) => {
+                    InvalidRequest(format!("Invalid calendar event: {reason:?}"))
+                }
                 ContentValidationError::TransferCannotBeZero => {
                     unreachable!()
                 }
@@ -562,6 +581,9 @@ impl GroupChatCore {
                     InvalidRequest("Cannot forward this type of message".to_string())
                 }
                 ContentValidationError::PrizeEndDateInThePast => InvalidRequest("Prize ended in the past".to_string()),
+                ContentValidationError::InvalidPrizeEligibility => {
+                    InvalidRequest("Payment gates cannot be used as prize eligibility rules".to_string())
+                }
                 ContentValidationError::UnauthorizedToSendProposalMessages => {
                     InvalidRequest("User unauthorized to send proposal messages".to_string())
                 }
@@ -1125,6 +1147,7 @@ impl GroupChatCore {
         match self.can_leave(user_id) {
             CanLeaveResult::Yes => {
                 let removed = self.members.remove(user_id, now).unwrap();
+                self.proposal_vote_delegations.remove_member(&user_id);
 
                 self.events
                     .push_main_event(ChatEventInternal::ParticipantLeft(Box::new(MemberLeft { user_id })), 0, now);
@@ -1167,6 +1190,7 @@ impl GroupChatCore {
             {
                 // Remove the user from the group
                 self.members.remove(target_user_id, now);
+                self.proposal_vote_delegations.remove_member(&target_user_id);
 
                 if block && !self.members.block(target_user_id, now) {
                     // Return Success if the user was already blocked
@@ -1199,6 +1223,58 @@ impl GroupChatCore {
         }
     }
 
+    // Prevents the member from sending messages until the timeout ends
+    pub fn timeout_member(
+        &mut self,
+        user_id: UserId,
+        target_user_id: UserId,
+        duration: Milliseconds,
+        now: TimestampMillis,
+    ) -> TimeoutMemberResult {
+        use TimeoutMemberResult::*;
+
+        if user_id == target_user_id {
+            return CannotTimeoutSelf;
+        }
+
+        if duration == 0 || duration > MAX_CHAT_REPORT_TIMEOUT {
+            return InvalidDuration;
+        }
+
+        if let Some(member) = self.members.get(&user_id) {
+            if member.suspended.value {
+                return UserSuspended;
+            }
+
+            let target_member_role = match self.members.get(&target_user_id) {
+                Some(m) => m.role.value,
+                None => return TargetUserNotInGroup,
+            };
+
+            if member
+                .role
+                .can_remove_members_with_role(target_member_role, &self.permissions)
+            {
+                let target_member = self.members.get_mut(&target_user_id).unwrap();
+                target_member.timed_out_until = Some(now + duration);
+                Success
+            } else {
+                NotAuthorized
+            }
+        } else {
+            UserNotInGroup
+        }
+    }
+
+    // The members who are notified of, and can act on, reported messages
+    pub fn report_handlers(&self) -> Vec<UserId> {
+        self.members
+            .iter()
+            .filter(|m| m.role.can_handle_reports() && !m.suspended.value)
+            .map(|m| m.user_id)
+            .collect()
+    }
+
     pub fn update(
         &mut self,
         user_id: UserId,
@@ -1480,6 +1556,37 @@ impl GroupChatCore {
         }
     }
 
+    pub fn set_typing(
+        &mut self,
+        user_id: UserId,
+        thread_root_message_index: Option<MessageIndex>,
+        typing: bool,
+        now: TimestampMillis,
+    ) -> SetTypingResult {
+        use SetTypingResult::*;
+
+        match self.members.get(&user_id) {
+            Some(member) if member.suspended.value => UserSuspended,
+            Some(_) => {
+                self.typing.set(user_id, thread_root_message_index, typing, now);
+                Success
+            }
+            None => UserNotInGroup,
+        }
+    }
+
+    // Only members of the group can see who is typing
+    pub fn typing(
+        &self,
+        user_id: UserId,
+        thread_root_message_index: Option<MessageIndex>,
+        now: TimestampMillis,
+    ) -> Option<Vec<UserId>> {
+        self.members
+            .get(&user_id)
+            .map(|_| self.typing.get(thread_root_message_index, user_id, now))
+    }
+
     pub fn unfollow_thread(
         &mut self,
         user_id: UserId,
@@ -1506,7 +1613,8 @@ impl GroupChatCore {
         }
     }
 
-    pub fn remove_expired_events(&mut self, now: TimestampMillis) {
+    // Returns the tokens locked in any expired polls, which must be refunded
+    pub fn remove_expired_events(&mut self, now: TimestampMillis) -> Vec<(UserId, PollVoteTokenLock)> {
         let result = self.events.remove_expired_events(now);
 
         for (thread_root_message_index, users) in result.threads {
@@ -1517,6 +1625,8 @@ impl GroupChatCore {
                 }
             }
         }
+
+        result.poll_token_locks
     }
 
     fn events_reader(&self, user_id: Option<UserId>, thread_root_message_index: Option<MessageIndex>) -> EventsReaderResult {
@@ -1754,6 +1864,16 @@ pub enum RemoveMemberResult {
     CannotRemoveSelf,
 }
 
+pub enum TimeoutMemberResult {
+    Success,
+    UserSuspended,
+    UserNotInGroup,
+    TargetUserNotInGroup,
+    NotAuthorized,
+    CannotTimeoutSelf,
+    InvalidDuration,
+}
+
 pub enum UpdateResult {
     Success(UpdateSuccessResult),
     UserSuspended,
@@ -1831,6 +1951,12 @@ pub enum FollowThreadResult {
     UserSuspended,
 }
 
+pub enum SetTypingResult {
+    Success,
+    UserNotInGroup,
+    UserSuspended,
+}
+
 pub enum UnfollowThreadResult {
     Success,
     NotFollowing,
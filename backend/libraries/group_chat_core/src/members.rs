@@ -55,6 +55,7 @@ impl GroupMembers {
             suspended: Timestamped::default(),
             rules_accepted: Some(Timestamped::new(Version::zero(), now)),
             is_bot,
+            timed_out_until: None,
         };
 
         GroupMembers {
@@ -97,6 +98,7 @@ impl GroupMembers {
                         suspended: Timestamped::default(),
                         rules_accepted: None,
                         is_bot,
+                        timed_out_until: None,
                     };
                     e.insert(member.clone());
                     self.updates.insert((now, user_id, MemberUpdate::Added));
@@ -336,6 +338,8 @@ pub struct GroupMemberInternal {
     pub rules_accepted: Option<Timestamped<Version>>,
     #[serde(rename = "b", default, skip_serializing_if = "is_default")]
     pub is_bot: bool,
+    #[serde(rename = "to", default, skip_serializing_if = "is_default")]
+    pub timed_out_until: Option<TimestampMillis>,
 
     #[serde(rename = "me", default, skip_serializing_if = "is_default")]
     min_visible_event_index: EventIndex,
@@ -357,6 +361,11 @@ impl GroupMemberInternal {
         .unwrap()
     }
 
+    // Members who have been timed out by the chat's moderators can't send messages until the timeout ends
+    pub fn is_timed_out(&self, now: TimestampMillis) -> bool {
+        self.timed_out_until.map_or(false, |until| until > now)
+    }
+
     pub fn min_visible_event_index(&self) -> EventIndex {
         if self.role.can_view_full_message_history() {
             EventIndex::default()
@@ -465,6 +474,7 @@ mod tests {
             min_visible_message_index: 0.into(),
             rules_accepted: Some(Timestamped::new(Version::zero(), 1)),
             is_bot: false,
+            timed_out_until: None,
         };
 
         let member_bytes = msgpack::serialize_then_unwrap(&member);
@@ -494,12 +504,13 @@ mod tests {
             min_visible_message_index: 1.into(),
             rules_accepted: Some(Timestamped::new(Version::zero(), 1)),
             is_bot: true,
+            timed_out_until: Some(1),
         };
 
         let member_bytes = msgpack::serialize_then_unwrap(&member);
         let member_bytes_len = member_bytes.len();
 
-        assert_eq!(member_bytes_len, 120);
+        assert_eq!(member_bytes_len, 124);
 
         let _deserialized: GroupMemberInternal = msgpack::deserialize_then_unwrap(&member_bytes);
     }
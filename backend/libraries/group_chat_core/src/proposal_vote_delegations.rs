@@ -0,0 +1,160 @@
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashMap, HashSet};
+use types::{MessageIndex, ProposalVoteDelegation, TimestampMillis, UserId};
+
+// Each member can follow the proposal votes of one other member, optionally restricted to a set
+// of proposal topics. Followers can opt out of having their vote cast on individual proposals.
+#[derive(Serialize, Deserialize, Default)]
+pub struct ProposalVoteDelegations {
+    delegations: HashMap<UserId, Delegation>,
+    // Maps each delegate to the members who follow their votes
+    followers: HashMap<UserId, HashSet<UserId>>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct Delegation {
+    delegate: UserId,
+    topics: Option<BTreeSet<u64>>,
+    opted_out: BTreeSet<MessageIndex>,
+    updated: TimestampMillis,
+}
+
+impl ProposalVoteDelegations {
+    pub fn get(&self, follower: &UserId) -> Option<ProposalVoteDelegation> {
+        self.delegations.get(follower).map(|d| ProposalVoteDelegation {
+            delegate: d.delegate,
+            topics: d.topics.as_ref().map(|t| t.iter().copied().collect()),
+            opted_out: d.opted_out.iter().copied().collect(),
+        })
+    }
+
+    pub fn set(&mut self, follower: UserId, delegate: UserId, topics: Option<Vec<u64>>, now: TimestampMillis) {
+        let opted_out = self.remove_delegation(&follower).map(|d| d.opted_out).unwrap_or_default();
+
+        self.delegations.insert(
+            follower,
+            Delegation {
+                delegate,
+                topics: topics.map(|t| t.into_iter().collect()),
+                opted_out,
+                updated: now,
+            },
+        );
+        self.followers.entry(delegate).or_default().insert(follower);
+    }
+
+    pub fn remove(&mut self, follower: &UserId) -> bool {
+        self.remove_delegation(follower).is_some()
+    }
+
+    // Called when a user leaves or is removed from the chat. Their own delegation is removed, as are
+    // the delegations of any members who were following them.
+    pub fn remove_member(&mut self, user_id: &UserId) {
+        self.remove_delegation(user_id);
+
+        if let Some(followers) = self.followers.remove(user_id) {
+            for follower in followers {
+                self.delegations.remove(&follower);
+            }
+        }
+    }
+
+    pub fn set_opted_out(&mut self, follower: &UserId, message_index: MessageIndex, opted_out: bool) -> bool {
+        if let Some(delegation) = self.delegations.get_mut(follower) {
+            if opted_out {
+                delegation.opted_out.insert(message_index);
+            } else {
+                delegation.opted_out.remove(&message_index);
+            }
+            true
+        } else {
+            false
+        }
+    }
+
+    pub fn follower_count(&self, delegate: &UserId) -> usize {
+        self.followers.get(delegate).map_or(0, |f| f.len())
+    }
+
+    // Returns the users whose votes should be cast when `delegate` votes on the proposal
+    pub fn followers(&self, delegate: UserId, message_index: MessageIndex, topic: u64) -> Vec<UserId> {
+        self.followers
+            .get(&delegate)
+            .into_iter()
+            .flatten()
+            .filter(|follower| {
+                self.delegations.get(follower).map_or(false, |d| {
+                    d.topics.as_ref().map_or(true, |t| t.contains(&topic)) && !d.opted_out.contains(&message_index)
+                })
+            })
+            .copied()
+            .collect()
+    }
+
+    fn remove_delegation(&mut self, follower: &UserId) -> Option<Delegation> {
+        let delegation = self.delegations.remove(follower)?;
+
+        if let Some(followers) = self.followers.get_mut(&delegation.delegate) {
+            followers.remove(follower);
+            if followers.is_empty() {
+                self.followers.remove(&delegation.delegate);
+            }
+        }
+        Some(delegation)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use candid::Principal;
+
+    #[test]
+    fn followers_filtered_by_topic_and_opt_out() {
+        let mut delegations = ProposalVoteDelegations::default();
+        delegations.set(user_id(1), user_id(10), None, 0);
+        delegations.set(user_id(2), user_id(10), Some(vec![5]), 0);
+        delegations.set(user_id(3), user_id(10), None, 0);
+        delegations.set(user_id(4), user_id(11), None, 0);
+        delegations.set_opted_out(&user_id(3), 7.into(), true);
+
+        let mut followers = delegations.followers(user_id(10), 7.into(), 5);
+        followers.sort();
+        assert_eq!(followers, vec![user_id(1), user_id(2)]);
+
+        assert_eq!(delegations.followers(user_id(10), 8.into(), 6).len(), 2);
+        assert_eq!(delegations.follower_count(&user_id(10)), 3);
+    }
+
+    #[test]
+    fn changing_delegate_updates_followers() {
+        let mut delegations = ProposalVoteDelegations::default();
+        delegations.set(user_id(1), user_id(10), None, 0);
+        delegations.set_opted_out(&user_id(1), 7.into(), true);
+        delegations.set(user_id(1), user_id(11), None, 1);
+
+        assert_eq!(delegations.follower_count(&user_id(10)), 0);
+        assert_eq!(delegations.follower_count(&user_id(11)), 1);
+        assert_eq!(delegations.get(&user_id(1)).unwrap().opted_out, vec![7.into()]);
+    }
+
+    #[test]
+    fn removing_member_removes_their_delegation_and_followers() {
+        let mut delegations = ProposalVoteDelegations::default();
+        delegations.set(user_id(1), user_id(10), None, 0);
+        delegations.set(user_id(2), user_id(10), None, 0);
+        delegations.set(user_id(10), user_id(11), None, 0);
+
+        delegations.remove_member(&user_id(10));
+
+        assert!(delegations.get(&user_id(1)).is_none());
+        assert!(delegations.get(&user_id(2)).is_none());
+        assert!(delegations.get(&user_id(10)).is_none());
+        assert_eq!(delegations.follower_count(&user_id(10)), 0);
+        assert_eq!(delegations.follower_count(&user_id(11)), 0);
+    }
+
+    fn user_id(index: u8) -> UserId {
+        Principal::from_slice(&[index]).into()
+    }
+}
@@ -0,0 +1,134 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use types::{
+    ChatReport, ChatReportAction, ChatReportResolution, MemberReportCount, MessageId, MessageIndex, Milliseconds,
+    TimestampMillis, UserId,
+};
+use utils::time::DAY_IN_MS;
+
+// Reports which the chat's moderators haven't handled within this time are escalated to the
+// platform moderators
+pub const AUTO_ESCALATE_REPORTS_AFTER: Milliseconds = 3 * DAY_IN_MS;
+
+// Messages reported by members of the chat, to be handled by the chat's moderators. Each report's
+// id is its index within the list.
+#[derive(Serialize, Deserialize, Default)]
+pub struct ChatReports {
+    reports: Vec<ChatReportInternal>,
+    lookup: HashMap<(Option<MessageIndex>, MessageIndex), u32>,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct ChatReportInternal {
+    pub thread_root_message_index: Option<MessageIndex>,
+    pub message_index: MessageIndex,
+    pub message_id: MessageId,
+    pub sender: UserId,
+    pub reported_by: Vec<(UserId, TimestampMillis)>,
+    pub resolution: Option<ChatReportResolution>,
+}
+
+pub struct AddChatReportArgs {
+    pub reporter: UserId,
+    pub thread_root_message_index: Option<MessageIndex>,
+    pub message_index: MessageIndex,
+    pub message_id: MessageId,
+    pub sender: UserId,
+    pub now: TimestampMillis,
+}
+
+pub enum AddChatReportResult {
+    New(u32),
+    ExistingPending(u32),
+    ExistingResolved(u32),
+    AlreadyReportedByUser,
+}
+
+impl ChatReports {
+    pub fn add(&mut self, args: AddChatReportArgs) -> AddChatReportResult {
+        if let Some(report_id) = self
+            .lookup
+            .get(&(args.thread_root_message_index, args.message_index))
+            .copied()
+        {
+            let report = &mut self.reports[report_id as usize];
+
+            if report.reported_by.iter().any(|(u, _)| *u == args.reporter) {
+                AddChatReportResult::AlreadyReportedByUser
+            } else {
+                report.reported_by.push((args.reporter, args.now));
+                if report.resolution.is_some() {
+                    AddChatReportResult::ExistingResolved(report_id)
+                } else {
+                    AddChatReportResult::ExistingPending(report_id)
+                }
+            }
+        } else {
+            let report_id = self.reports.len() as u32;
+            self.reports.push(ChatReportInternal {
+                thread_root_message_index: args.thread_root_message_index,
+                message_index: args.message_index,
+                message_id: args.message_id,
+                sender: args.sender,
+                reported_by: vec![(args.reporter, args.now)],
+                resolution: None,
+            });
+            self.lookup
+                .insert((args.thread_root_message_index, args.message_index), report_id);
+            AddChatReportResult::New(report_id)
+        }
+    }
+
+    pub fn get(&self, report_id: u32) -> Option<&ChatReportInternal> {
+        self.reports.get(report_id as usize)
+    }
+
+    pub fn resolve(&mut self, report_id: u32, action: ChatReportAction, resolved_by: UserId, now: TimestampMillis) -> bool {
+        if let Some(report) = self.reports.get_mut(report_id as usize) {
+            if report.resolution.is_none() {
+                report.resolution = Some(ChatReportResolution {
+                    action,
+                    resolved_by,
+                    timestamp: now,
+                });
+                return true;
+            }
+        }
+        false
+    }
+
+    // Returns the most recent reports first
+    pub fn list(&self, include_resolved: bool) -> Vec<ChatReport> {
+        self.reports
+            .iter()
+            .enumerate()
+            .rev()
+            .filter(|(_, r)| include_resolved || r.resolution.is_none())
+            .map(|(i, r)| ChatReport {
+                report_id: i as u32,
+                thread_root_message_index: r.thread_root_message_index,
+                message_index: r.message_index,
+                message_id: r.message_id,
+                sender: r.sender,
+                reported_by: r.reported_by.iter().map(|(u, _)| *u).collect(),
+                first_reported: r.reported_by.first().map(|(_, t)| *t).unwrap_or_default(),
+                resolution: r.resolution.clone(),
+            })
+            .collect()
+    }
+
+    // Returns the members with the most reported messages first
+    pub fn member_report_counts(&self) -> Vec<MemberReportCount> {
+        let mut counts: HashMap<UserId, u32> = HashMap::new();
+        for report in self.reports.iter() {
+            *counts.entry(report.sender).or_default() += 1;
+        }
+
+        let mut counts: Vec<_> = counts
+            .into_iter()
+            .map(|(user_id, reports)| MemberReportCount { user_id, reports })
+            .collect();
+        counts.sort_unstable_by(|a, b| b.reports.cmp(&a.reports));
+        counts
+    }
+}
@@ -114,6 +114,8 @@ impl GroupRoleInternal {
             MessageContentInitial::Prize(_) => ps.prize.unwrap_or(ps.default),
             MessageContentInitial::MessageReminderCreated(_) => GroupPermissionRole::None,
             MessageContentInitial::MessageReminder(_) => GroupPermissionRole::Members,
+            MessageContentInitial::P2PSwap(_) => GroupPermissionRole::None,
+            MessageContentInitial::CalendarEvent(_) => ps.default,
             MessageContentInitial::Custom(mc) => ps
                 .custom
                 .iter()
@@ -129,6 +131,10 @@ impl GroupRoleInternal {
         self.is_permitted(permissions.react_to_messages)
     }
 
+    pub fn can_handle_reports(&self) -> bool {
+        self.has_moderator_rights()
+    }
+
     pub fn can_delete_group(&self) -> bool {
         self.is_owner()
     }
@@ -0,0 +1,88 @@
+use std::collections::HashMap;
+use types::{MessageIndex, Milliseconds, TimestampMillis, UserId};
+use utils::time::SECOND_IN_MS;
+
+// Clients should call `set_typing` every few seconds while the user is typing, so typing
+// notifications expire shortly after the user stops typing, even if `typing: false` is never sent
+const TYPING_TTL: Milliseconds = 5 * SECOND_IN_MS;
+
+// The members currently typing in the main chat or in each thread. Typing notifications are
+// ephemeral so are not persisted across upgrades.
+#[derive(Default)]
+pub struct TypingUsers {
+    typing: HashMap<Option<MessageIndex>, HashMap<UserId, TimestampMillis>>,
+}
+
+impl TypingUsers {
+    pub fn set(
+        &mut self,
+        user_id: UserId,
+        thread_root_message_index: Option<MessageIndex>,
+        typing: bool,
+        now: TimestampMillis,
+    ) {
+        if typing {
+            let users = self.typing.entry(thread_root_message_index).or_default();
+            users.retain(|_, expires| *expires > now);
+            users.insert(user_id, now + TYPING_TTL);
+        } else if let Some(users) = self.typing.get_mut(&thread_root_message_index) {
+            users.remove(&user_id);
+            users.retain(|_, expires| *expires > now);
+            if users.is_empty() {
+                self.typing.remove(&thread_root_message_index);
+            }
+        }
+    }
+
+    pub fn get(&self, thread_root_message_index: Option<MessageIndex>, viewer: UserId, now: TimestampMillis) -> Vec<UserId> {
+        self.typing
+            .get(&thread_root_message_index)
+            .map(|users| {
+                users
+                    .iter()
+                    .filter(|(u, expires)| **expires > now && **u != viewer)
+                    .map(|(u, _)| *u)
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use candid::Principal;
+
+    fn user(index: u8) -> UserId {
+        Principal::from_slice(&[index]).into()
+    }
+
+    #[test]
+    fn typing_expires_after_ttl() {
+        let mut typing = TypingUsers::default();
+        typing.set(user(1), None, true, 1000);
+
+        assert_eq!(typing.get(None, user(2), 1000 + TYPING_TTL - 1), vec![user(1)]);
+        assert!(typing.get(None, user(2), 1000 + TYPING_TTL).is_empty());
+    }
+
+    #[test]
+    fn typing_false_clears_user() {
+        let mut typing = TypingUsers::default();
+        typing.set(user(1), None, true, 1000);
+        typing.set(user(1), None, false, 1001);
+
+        assert!(typing.get(None, user(2), 1002).is_empty());
+    }
+
+    #[test]
+    fn viewer_excluded_and_threads_separate() {
+        let mut typing = TypingUsers::default();
+        typing.set(user(1), None, true, 1000);
+        typing.set(user(2), Some(5.into()), true, 1000);
+
+        assert!(typing.get(None, user(1), 1001).is_empty());
+        assert_eq!(typing.get(None, user(3), 1001), vec![user(1)]);
+        assert_eq!(typing.get(Some(5.into()), user(3), 1001), vec![user(2)]);
+    }
+}
@@ -25,5 +25,6 @@ pub fn build_response(body: Vec<u8>, content_type: impl Into<String>) -> HttpRes
         ],
         body: ByteBuf::from(body),
         streaming_strategy: None,
+        upgrade: None,
     }
 }
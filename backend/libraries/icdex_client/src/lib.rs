@@ -1,5 +1,10 @@
+use candid::Nat;
 use ic_cdk::api::call::{CallResult, RejectionCode};
-use icdex_canister::{ICDexOrderType, MakeOrderResponse, OrderPrice, OrderQuantity, TradingOrder};
+use icdex_canister::{
+    BalanceChange, DexSetting, ICDexOrderType, MakeOrderResponse, OrderFilled, OrderPrice, OrderQuantity, Orderbook,
+    PriceAndQuantity, TradingOrder,
+};
+use icrc_ledger_types::icrc1::account::Account;
 use icrc_ledger_types::icrc1::transfer::TransferArg;
 use types::{AggregatedOrders, CancelOrderRequest, CanisterId, MakeOrderRequest, Order, OrderType, TokenInfo};
 
@@ -45,7 +50,7 @@ impl<M: Fn(MakeOrderRequest), C: Fn(CancelOrderRequest)> ICDexClient<M, C> {
 
         let orders = icdex_canister_c2c_client::pending(self.dex_canister_id, args).await?.0;
 
-        Ok(orders.data.into_iter().map(|(_, o)| self.convert_order(o)).collect())
+        orders.data.into_iter().map(|(_, o)| self.convert_order(o)).collect()
     }
 
     pub async fn orderbook(&self) -> CallResult<AggregatedOrders> {
@@ -54,24 +59,14 @@ impl<M: Fn(MakeOrderRequest), C: Fn(CancelOrderRequest)> ICDexClient<M, C> {
         Ok(AggregatedOrders {
             bids: orderbook
                 .bid
-                .into_iter()
-                .map(|p| {
-                    (
-                        u64::try_from(p.price.0).unwrap() * self.base_token_units_per_whole() / self.smallest_order_size,
-                        u64::try_from(p.quantity.0).unwrap(),
-                    )
-                })
-                .collect(),
+                .iter()
+                .map(|p| self.convert_price_and_quantity(p))
+                .collect::<CallResult<_>>()?,
             asks: orderbook
                 .ask
-                .into_iter()
-                .map(|p| {
-                    (
-                        u64::try_from(p.price.0).unwrap() * self.base_token_units_per_whole() / self.smallest_order_size,
-                        u64::try_from(p.quantity.0).unwrap(),
-                    )
-                })
-                .collect(),
+                .iter()
+                .map(|p| self.convert_price_and_quantity(p))
+                .collect::<CallResult<_>>()?,
         })
     }
 
@@ -128,7 +123,7 @@ impl<M: Fn(MakeOrderRequest), C: Fn(CancelOrderRequest)> ICDexClient<M, C> {
     }
 
     pub async fn cancel_order(&self, order: CancelOrderRequest) -> CallResult<()> {
-        let id = hex::decode(&order.id).unwrap();
+        let id = hex::decode(&order.id).map_err(|e| invalid_response(format!("Invalid order id: {e}")))?;
 
         icdex_canister_c2c_client::cancelByTxid(self.dex_canister_id, (id, None)).await?;
 
@@ -136,18 +131,79 @@ impl<M: Fn(MakeOrderRequest), C: Fn(CancelOrderRequest)> ICDexClient<M, C> {
         Ok(())
     }
 
-    fn convert_order(&self, order: TradingOrder) -> Order {
+    // The account which tokens must be transferred into before calling `swap`
+    pub async fn deposit_account(&self) -> CallResult<Account> {
+        icdex_canister_c2c_client::getTxAccount(self.dex_canister_id, (self.this_canister_id.to_string(),))
+            .await
+            .map(|(a, ..)| a)
+    }
+
+    // Calculates the output amount by walking the orderbook, `order_type` is `Ask` when selling the
+    // base token and `Bid` when buying it. Fails if the orderbook can't fill the full amount.
+    pub async fn quote(&self, order_type: OrderType, amount: u128) -> CallResult<u128> {
+        let (config, (_, orderbook)) = futures::future::try_join(
+            icdex_canister_c2c_client::getConfig(self.dex_canister_id, ()),
+            icdex_canister_c2c_client::level100(self.dex_canister_id, ()),
+        )
+        .await?;
+        let config = config.0;
+        let unit_size = unit_size(&config)?;
+
+        let amount_out = quote_from_orderbook(order_type, amount, unit_size, &orderbook)?;
+
+        deduct_trading_fee(amount_out, &config)
+    }
+
+    // Makes a fill-or-kill order at the price which would result in exactly `min_amount_out`, this
+    // ensures that either the full amount is swapped for at least `min_amount_out`, or nothing is.
+    // The tokens must already have been transferred into the `deposit_account`. Returns the amount
+    // of the output token received, which ICDex transfers back to this canister automatically.
+    pub async fn swap(&self, order_type: OrderType, amount: u128, min_amount_out: u128) -> CallResult<u128> {
+        let (_, _, nonce, _) =
+            icdex_canister_c2c_client::getTxAccount(self.dex_canister_id, (self.this_canister_id.to_string(),)).await?;
+
+        let config = icdex_canister_c2c_client::getConfig(self.dex_canister_id, ()).await?.0;
+        let unit_size = unit_size(&config)?;
+
+        let (price, quantity) = fill_or_kill_order(order_type, amount, min_amount_out, unit_size)?;
+
+        let args = (
+            OrderPrice {
+                price: price.into(),
+                quantity,
+            },
+            ICDexOrderType::FillOrKill,
+            None,
+            Some(nonce),
+            None,
+            None,
+        );
+
+        match icdex_canister_c2c_client::trade(self.dex_canister_id, args).await?.0 {
+            MakeOrderResponse::Ok(result) => amount_received(order_type, &result.filled),
+            MakeOrderResponse::Err(e) => Err((RejectionCode::Unknown, format!("{e:?}"))),
+        }
+    }
+
+    fn convert_order(&self, order: TradingOrder) -> CallResult<Order> {
         let (order_type, amount) = match order.remaining.quantity {
             OrderQuantity::Buy(n, _) => (OrderType::Bid, n),
             OrderQuantity::Sell(n) => (OrderType::Ask, n),
         };
-        let price: u64 = order.remaining.price.0.try_into().unwrap();
-        Order {
+        let price = nat_to_u64(&order.remaining.price)?;
+        Ok(Order {
             order_type,
             id: hex::encode(order.txid),
             price: price * self.base_token_units_per_whole() / self.smallest_order_size,
-            amount: amount.0.try_into().unwrap(),
-        }
+            amount: nat_to_u64(&amount)?,
+        })
+    }
+
+    fn convert_price_and_quantity(&self, value: &PriceAndQuantity) -> CallResult<(u64, u64)> {
+        Ok((
+            nat_to_u64(&value.price)? * self.base_token_units_per_whole() / self.smallest_order_size,
+            nat_to_u64(&value.quantity)?,
+        ))
     }
 
     fn quote_token_units_per_whole(&self) -> u64 {
@@ -162,3 +218,221 @@ impl<M: Fn(MakeOrderRequest), C: Fn(CancelOrderRequest)> ICDexClient<M, C> {
         10u64.pow(decimals as u32)
     }
 }
+
+// Walks the orderbook, consuming whole units at each price level until the full amount is used.
+// Any amount too small to buy or sell a single unit is left over, but if more than that remains
+// once the orderbook is exhausted then it isn't deep enough to fill the order.
+fn quote_from_orderbook(order_type: OrderType, amount: u128, unit_size: u128, orderbook: &Orderbook) -> CallResult<u128> {
+    let mut remaining = amount;
+    let mut amount_out: u128 = 0;
+    let filled = match order_type {
+        OrderType::Ask => {
+            for level in orderbook.bid.iter() {
+                if remaining < unit_size {
+                    break;
+                }
+                let price = nat_to_u128(&level.price)?;
+                let quantity = remaining.min(nat_to_u128(&level.quantity)?) / unit_size * unit_size;
+                amount_out = checked_add(amount_out, checked_mul(quantity, price)? / unit_size)?;
+                remaining -= quantity;
+            }
+            remaining < unit_size
+        }
+        OrderType::Bid => {
+            let mut last_price = None;
+            for level in orderbook.ask.iter() {
+                let price = nat_to_u128(&level.price)?;
+                if price == 0 {
+                    continue;
+                }
+                last_price = Some(price);
+                let affordable = checked_mul(remaining, unit_size)? / price / unit_size * unit_size;
+                if affordable == 0 {
+                    break;
+                }
+                let quantity = affordable.min(nat_to_u128(&level.quantity)?);
+                amount_out = checked_add(amount_out, quantity)?;
+                remaining -= checked_mul(quantity, price)? / unit_size;
+            }
+            match last_price {
+                Some(price) => checked_mul(remaining, unit_size)? / price < unit_size,
+                None => amount == 0,
+            }
+        }
+    };
+
+    if filled {
+        Ok(amount_out)
+    } else {
+        Err(invalid_response("Insufficient liquidity".to_string()))
+    }
+}
+
+// Returns the price and quantity for a fill-or-kill order which would result in exactly `min_amount_out`
+fn fill_or_kill_order(
+    order_type: OrderType,
+    amount: u128,
+    min_amount_out: u128,
+    unit_size: u128,
+) -> CallResult<(u128, OrderQuantity)> {
+    match order_type {
+        OrderType::Ask => {
+            let quantity = amount / unit_size * unit_size;
+            if quantity == 0 {
+                return Err(invalid_response("Amount too small".to_string()));
+            }
+            Ok((
+                checked_mul(min_amount_out, unit_size)? / quantity,
+                OrderQuantity::Sell(quantity.into()),
+            ))
+        }
+        OrderType::Bid => {
+            let quantity = checked_mul(min_amount_out.max(1).div_ceil(unit_size), unit_size)?;
+            Ok((
+                checked_mul(amount, unit_size)? / quantity,
+                OrderQuantity::Buy(quantity.into(), amount.into()),
+            ))
+        }
+    }
+}
+
+fn amount_received(order_type: OrderType, filled: &[OrderFilled]) -> CallResult<u128> {
+    let mut total: u128 = 0;
+    for change in filled.iter().map(|f| match order_type {
+        OrderType::Ask => &f.token1_value,
+        OrderType::Bid => &f.token0_value,
+    }) {
+        if let BalanceChange::CreditRecord(amount) = change {
+            total = checked_add(total, nat_to_u128(amount)?)?;
+        }
+    }
+    Ok(total)
+}
+
+fn deduct_trading_fee(amount: u128, config: &DexSetting) -> CallResult<u128> {
+    let fee_ppm = nat_to_u128(&config.trading_fee)?.min(1_000_000);
+    Ok(checked_mul(amount, 1_000_000 - fee_ppm)? / 1_000_000)
+}
+
+fn unit_size(config: &DexSetting) -> CallResult<u128> {
+    match nat_to_u128(&config.unit_size)? {
+        0 => Err(invalid_response("Unit size is zero".to_string())),
+        unit_size => Ok(unit_size),
+    }
+}
+
+fn nat_to_u128(value: &Nat) -> CallResult<u128> {
+    value
+        .0
+        .clone()
+        .try_into()
+        .map_err(|_| invalid_response(format!("Value out of range: {value}")))
+}
+
+fn nat_to_u64(value: &Nat) -> CallResult<u64> {
+    value
+        .0
+        .clone()
+        .try_into()
+        .map_err(|_| invalid_response(format!("Value out of range: {value}")))
+}
+
+fn checked_add(a: u128, b: u128) -> CallResult<u128> {
+    a.checked_add(b).ok_or_else(|| invalid_response("Overflow".to_string()))
+}
+
+fn checked_mul(a: u128, b: u128) -> CallResult<u128> {
+    a.checked_mul(b).ok_or_else(|| invalid_response("Overflow".to_string()))
+}
+
+fn invalid_response(message: String) -> (RejectionCode, String) {
+    (RejectionCode::Unknown, message)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn quote_ask_walks_bids() {
+        // Selling 250 units where each unit is 10, bids of 10 @ 2 and 1000 @ 1 per unit
+        let orderbook = orderbook(vec![], vec![(20, 100), (10, 1000)]);
+
+        assert_eq!(quote_from_orderbook(OrderType::Ask, 250, 10, &orderbook).unwrap(), 200 + 150);
+    }
+
+    #[test]
+    fn quote_bid_walks_asks() {
+        // Buying with 500 where each unit is 10, asks of 100 @ 2 and 1000 @ 3 per unit
+        let orderbook = orderbook(vec![(20, 100), (30, 1000)], vec![]);
+
+        assert_eq!(quote_from_orderbook(OrderType::Bid, 500, 10, &orderbook).unwrap(), 100 + 100);
+    }
+
+    #[test]
+    fn quote_fails_if_orderbook_too_shallow() {
+        let orderbook = orderbook(vec![(20, 100)], vec![(20, 100)]);
+
+        assert!(quote_from_orderbook(OrderType::Ask, 1000, 10, &orderbook).is_err());
+        assert!(quote_from_orderbook(OrderType::Bid, 1000, 10, &orderbook).is_err());
+    }
+
+    #[test]
+    fn quote_ignores_dust_below_unit_size() {
+        let orderbook = orderbook(vec![(20, 100)], vec![(20, 100)]);
+
+        assert_eq!(quote_from_orderbook(OrderType::Ask, 105, 10, &orderbook).unwrap(), 200);
+        assert_eq!(quote_from_orderbook(OrderType::Bid, 205, 10, &orderbook).unwrap(), 100);
+    }
+
+    #[test]
+    fn zero_unit_size_is_rejected() {
+        let config = DexSetting {
+            unit_size: Nat::from(0u32),
+            trading_fee: Nat::from(0u32),
+        };
+
+        assert!(unit_size(&config).is_err());
+    }
+
+    #[test]
+    fn fill_or_kill_ask_order_too_small_is_rejected() {
+        assert!(fill_or_kill_order(OrderType::Ask, 9, 1, 10).is_err());
+    }
+
+    #[test]
+    fn fill_or_kill_prices_give_min_amount_out() {
+        let (price, quantity) = fill_or_kill_order(OrderType::Ask, 100, 250, 10).unwrap();
+        assert_eq!(price, 25);
+        assert!(matches!(quantity, OrderQuantity::Sell(q) if q == Nat::from(100u32)));
+
+        let (price, quantity) = fill_or_kill_order(OrderType::Bid, 250, 95, 10).unwrap();
+        assert_eq!(price, 25);
+        assert!(matches!(quantity, OrderQuantity::Buy(q, a) if q == Nat::from(100u32) && a == Nat::from(250u32)));
+    }
+
+    #[test]
+    fn out_of_range_values_return_errors() {
+        let orderbook = orderbook(vec![], vec![(u128::MAX, 100)]);
+
+        assert!(quote_from_orderbook(OrderType::Ask, 100, 10, &orderbook).is_err());
+        assert!(nat_to_u64(&Nat::from(u128::MAX)).is_err());
+    }
+
+    fn orderbook(ask: Vec<(u128, u128)>, bid: Vec<(u128, u128)>) -> Orderbook {
+        let convert = |levels: Vec<(u128, u128)>| {
+            levels
+                .into_iter()
+                .map(|(price, quantity)| PriceAndQuantity {
+                    price: Nat::from(price),
+                    quantity: Nat::from(quantity),
+                })
+                .collect()
+        };
+
+        Orderbook {
+            ask: convert(ask),
+            bid: convert(bid),
+        }
+    }
+}
@@ -1,4 +1,8 @@
-use icrc_ledger_types::icrc1::{account::Account, transfer::TransferArg};
+use crate::{is_transient_rejection, TransferFailure};
+use icrc_ledger_types::icrc1::{
+    account::{Account, Subaccount},
+    transfer::{TransferArg, TransferError},
+};
 use types::{
     icrc1::{CompletedCryptoTransaction, FailedCryptoTransaction, PendingCryptoTransaction},
     CanisterId,
@@ -8,10 +12,31 @@ pub async fn process_transaction(
     transaction: PendingCryptoTransaction,
     sender: CanisterId,
 ) -> Result<CompletedCryptoTransaction, FailedCryptoTransaction> {
-    let from = Account::from(sender);
+    process_transaction_from_subaccount(transaction, sender, None).await
+}
+
+pub async fn process_transaction_from_subaccount(
+    transaction: PendingCryptoTransaction,
+    sender: CanisterId,
+    from_subaccount: Option<Subaccount>,
+) -> Result<CompletedCryptoTransaction, FailedCryptoTransaction> {
+    try_process_transaction_from_subaccount(transaction, sender, from_subaccount)
+        .await
+        .map_err(|f| f.transaction)
+}
+
+pub async fn try_process_transaction_from_subaccount(
+    transaction: PendingCryptoTransaction,
+    sender: CanisterId,
+    from_subaccount: Option<Subaccount>,
+) -> Result<CompletedCryptoTransaction, TransferFailure<FailedCryptoTransaction>> {
+    let from = Account {
+        owner: sender,
+        subaccount: from_subaccount,
+    };
 
     let args = TransferArg {
-        from_subaccount: None,
+        from_subaccount,
         to: transaction.to,
         fee: Some(transaction.fee.into()),
         created_at_time: Some(transaction.created),
@@ -19,7 +44,14 @@ pub async fn process_transaction(
         amount: transaction.amount.into(),
     };
 
-    match icrc_ledger_canister_c2c_client::icrc1_transfer(transaction.ledger, &args).await {
+    let response = match icrc_ledger_canister_c2c_client::icrc1_transfer(transaction.ledger, &args).await {
+        // Retries reuse the same `created` timestamp, so if a previous attempt actually succeeded the
+        // ledger returns the block index of that transfer as a duplicate
+        Ok(Err(TransferError::Duplicate { duplicate_of })) => Ok(Ok(duplicate_of)),
+        response => response,
+    };
+
+    match response {
         Ok(Ok(block_index)) => Ok(CompletedCryptoTransaction {
             ledger: transaction.ledger,
             token: transaction.token.clone(),
@@ -33,22 +65,25 @@ pub async fn process_transaction(
         }),
         Ok(Err(transfer_error)) => {
             let error_message = format!("Transfer failed. {transfer_error:?}");
-            Err(error_message)
+            Err((error_message, matches!(transfer_error, TransferError::TemporarilyUnavailable)))
         }
         Err((code, msg)) => {
             let error_message = format!("Transfer failed. {code:?}: {msg}");
-            Err(error_message)
+            Err((error_message, is_transient_rejection(code)))
         }
     }
-    .map_err(|error| FailedCryptoTransaction {
-        ledger: transaction.ledger,
-        token: transaction.token,
-        amount: transaction.amount,
-        fee: transaction.fee,
-        from: types::icrc1::CryptoAccount::Account(from),
-        to: types::icrc1::CryptoAccount::Account(transaction.to),
-        memo: transaction.memo,
-        created: transaction.created,
-        error_message: error,
+    .map_err(|(error, transient)| TransferFailure {
+        transaction: FailedCryptoTransaction {
+            ledger: transaction.ledger,
+            token: transaction.token,
+            amount: transaction.amount,
+            fee: transaction.fee,
+            from: types::icrc1::CryptoAccount::Account(from),
+            to: types::icrc1::CryptoAccount::Account(transaction.to),
+            memo: transaction.memo,
+            created: transaction.created,
+            error_message: error,
+        },
+        transient,
     })
 }
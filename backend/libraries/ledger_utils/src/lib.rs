@@ -1,4 +1,5 @@
 use candid::{CandidType, Principal};
+use ic_cdk::api::call::RejectionCode;
 use ic_ledger_types::{AccountIdentifier, Memo, Subaccount, Timestamp, Tokens, TransferArgs, DEFAULT_SUBACCOUNT};
 use icrc_ledger_types::icrc1::account::Account;
 use serde::{Deserialize, Serialize};
@@ -52,21 +53,45 @@ pub async fn process_transaction(
     transaction: PendingCryptoTransaction,
     sender: CanisterId,
 ) -> Result<CompletedCryptoTransaction, FailedCryptoTransaction> {
+    try_process_transaction(transaction, sender).await.map_err(|f| f.transaction)
+}
+
+// Same as `process_transaction` but also reports whether a failed transfer is worth retrying
+pub async fn try_process_transaction(
+    transaction: PendingCryptoTransaction,
+    sender: CanisterId,
+) -> Result<CompletedCryptoTransaction, TransferFailure<FailedCryptoTransaction>> {
     match transaction {
-        PendingCryptoTransaction::NNS(t) => nns::process_transaction(t, sender).await,
+        PendingCryptoTransaction::NNS(t) => nns::try_process_transaction(t, sender).await,
         PendingCryptoTransaction::ICRC1(t) => {
             if t.token == Cryptocurrency::InternetComputer {
-                nns::process_transaction(t.into(), sender).await
+                nns::try_process_transaction(t.into(), sender).await
             } else {
-                match icrc1::process_transaction(t, sender).await {
+                match icrc1::try_process_transaction_from_subaccount(t, sender, None).await {
                     Ok(c) => Ok(c.into()),
-                    Err(f) => Err(f.into()),
+                    Err(f) => Err(TransferFailure {
+                        transaction: f.transaction.into(),
+                        transient: f.transient,
+                    }),
                 }
             }
         }
     }
 }
 
+pub struct TransferFailure<T> {
+    pub transaction: T,
+    // True if the ledger couldn't be reached or was temporarily unavailable, in which case the
+    // transfer can be retried (using the same `created` timestamp so that it is deduplicated)
+    pub transient: bool,
+}
+
+// A ledger which traps (`CanisterError`) is not expected to recover, so only `SysTransient` rejections
+// are worth retrying
+fn is_transient_rejection(code: RejectionCode) -> bool {
+    matches!(code, RejectionCode::SysTransient)
+}
+
 pub fn default_ledger_account(principal: Principal) -> AccountIdentifier {
     AccountIdentifier::new(&principal, &DEFAULT_SUBACCOUNT)
 }
@@ -124,6 +149,18 @@ pub fn compute_neuron_staking_subaccount_bytes(controller: Principal, nonce: u64
     hasher.finalize().into()
 }
 
+// Each P2P swap has its own escrow subaccount so that funds from different swaps are never mixed
+pub fn compute_p2p_swap_escrow_subaccount_bytes(swap_id: u32) -> [u8; 32] {
+    const DOMAIN: &[u8] = b"p2p-swap";
+    const DOMAIN_LENGTH: [u8; 1] = [0x08];
+
+    let mut hasher = Sha256::new();
+    hasher.update(DOMAIN_LENGTH);
+    hasher.update(DOMAIN);
+    hasher.update(swap_id.to_be_bytes());
+    hasher.finalize().into()
+}
+
 /// An operation which modifies account balances
 #[derive(Serialize, Deserialize, CandidType, Clone, Hash, Debug, PartialEq, Eq, PartialOrd, Ord)]
 enum Operation {
@@ -162,6 +199,7 @@ impl Transaction {
 
 #[cfg(test)]
 mod tests {
+    use ic_cdk::api::call::RejectionCode;
     use test_case::test_case;
 
     #[test_case(1000000, 8, "0.01")]
@@ -172,4 +210,12 @@ mod tests {
         let formatted = super::format_crypto_amount(units, decimals);
         assert_eq!(formatted, expected);
     }
+
+    #[test_case(RejectionCode::SysTransient, true)]
+    #[test_case(RejectionCode::CanisterError, false)]
+    #[test_case(RejectionCode::CanisterReject, false)]
+    #[test_case(RejectionCode::DestinationInvalid, false)]
+    fn transient_rejection(code: RejectionCode, expected: bool) {
+        assert_eq!(super::is_transient_rejection(code), expected);
+    }
 }
@@ -1,11 +1,18 @@
-use crate::{calculate_transaction_hash, default_ledger_account};
-use ic_ledger_types::{Memo, Timestamp, TransferArgs, DEFAULT_FEE};
+use crate::{calculate_transaction_hash, default_ledger_account, is_transient_rejection, TransferFailure};
+use ic_ledger_types::{Memo, Timestamp, TransferArgs, TransferError, DEFAULT_FEE};
 use types::{CanisterId, CompletedCryptoTransaction, FailedCryptoTransaction};
 
 pub async fn process_transaction(
     transaction: types::nns::PendingCryptoTransaction,
     sender: CanisterId,
 ) -> Result<CompletedCryptoTransaction, FailedCryptoTransaction> {
+    try_process_transaction(transaction, sender).await.map_err(|f| f.transaction)
+}
+
+pub async fn try_process_transaction(
+    transaction: types::nns::PendingCryptoTransaction,
+    sender: CanisterId,
+) -> Result<CompletedCryptoTransaction, TransferFailure<FailedCryptoTransaction>> {
     let memo = transaction.memo.unwrap_or(Memo(0));
     let fee = transaction.fee.unwrap_or(DEFAULT_FEE);
 
@@ -27,7 +34,14 @@ pub async fn process_transaction(
     };
     let transaction_hash = calculate_transaction_hash(sender, &transfer_args);
 
-    match icp_ledger_canister_c2c_client::transfer(transaction.ledger, &transfer_args).await {
+    let response = match icp_ledger_canister_c2c_client::transfer(transaction.ledger, &transfer_args).await {
+        // Retries reuse the same `created` timestamp, so if a previous attempt actually succeeded the
+        // ledger returns the block index of that transfer as a duplicate
+        Ok(Err(TransferError::TxDuplicate { duplicate_of })) => Ok(Ok(duplicate_of)),
+        response => response,
+    };
+
+    match response {
         Ok(Ok(block_index)) => Ok(CompletedCryptoTransaction::NNS(types::nns::CompletedCryptoTransaction {
             ledger: transaction.ledger,
             token: transaction.token.clone(),
@@ -41,16 +55,17 @@ pub async fn process_transaction(
             block_index,
         })),
         Ok(Err(transfer_error)) => {
+            // None of the errors returned by the ICP ledger are resolved by retrying
             let error_message = format!("Transfer failed. {transfer_error:?}");
-            Err(error_message)
+            Err((error_message, false))
         }
         Err((code, msg)) => {
             let error_message = format!("Transfer failed. {code:?}: {msg}");
-            Err(error_message)
+            Err((error_message, is_transient_rejection(code)))
         }
     }
-    .map_err(|error| {
-        FailedCryptoTransaction::NNS(types::nns::FailedCryptoTransaction {
+    .map_err(|(error, transient)| TransferFailure {
+        transaction: FailedCryptoTransaction::NNS(types::nns::FailedCryptoTransaction {
             ledger: transaction.ledger,
             token: transaction.token,
             amount: transaction.amount,
@@ -61,6 +76,7 @@ pub async fn process_transaction(
             created: transaction.created,
             transaction_hash,
             error_message: error,
-        })
+        }),
+        transient,
     })
 }
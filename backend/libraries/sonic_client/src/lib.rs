@@ -17,7 +17,6 @@ pub struct SonicClient {
     token0: TokenInfo,
     token1: TokenInfo,
     zero_for_one: bool,
-    deposit_subaccount: [u8; 32],
 }
 
 impl SonicClient {
@@ -27,7 +26,6 @@ impl SonicClient {
         token0: TokenInfo,
         token1: TokenInfo,
         zero_for_one: bool,
-        deposit_subaccount: [u8; 32],
     ) -> Self {
         SonicClient {
             this_canister_id,
@@ -35,7 +33,6 @@ impl SonicClient {
             token0,
             token1,
             zero_for_one,
-            deposit_subaccount,
         }
     }
 
@@ -46,18 +43,38 @@ impl SonicClient {
         })
     }
 
+    // Calculates the output amount based on the reserves of the pair, Sonic takes a 0.3% fee
+    pub async fn quote(&self, amount: u128) -> CallResult<u128> {
+        let input_ledger = self.input_token().ledger;
+        let output_ledger = self.output_token().ledger;
+
+        let pair = sonic_canister_c2c_client::get_pair(self.sonic_canister_id, (input_ledger, output_ledger))
+            .await?
+            .0
+            .ok_or_else(|| (RejectionCode::Unknown, "Pair not found".to_string()))?;
+
+        let (reserve_in, reserve_out) = if pair.token0 == input_ledger.to_string() {
+            (nat_to_u128(pair.reserve0)?, nat_to_u128(pair.reserve1)?)
+        } else {
+            (nat_to_u128(pair.reserve1)?, nat_to_u128(pair.reserve0)?)
+        };
+
+        Ok(amount_out(amount, reserve_in, reserve_out))
+    }
+
     pub async fn deposit(&self, amount: u128) -> CallResult<u128> {
         let args = (self.input_token().ledger, amount.into());
         match sonic_canister_c2c_client::deposit(self.sonic_canister_id, args).await?.0 {
-            SonicResult::Ok(amount_deposited) => Ok(nat_to_u128(amount_deposited)),
+            SonicResult::Ok(amount_deposited) => nat_to_u128(amount_deposited),
             SonicResult::Err(error) => Err(convert_error(error)),
         }
     }
 
-    pub async fn swap(&self, amount: u128) -> CallResult<u128> {
+    // Sonic only returns the transaction id, so the amount out is read from the swap's transaction record
+    pub async fn swap(&self, amount: u128, min_amount_out: u128) -> CallResult<u128> {
         let args = (
             Nat::from(amount),
-            Nat::from(0),
+            Nat::from(min_amount_out),
             vec![self.input_token().ledger.to_string(), self.output_token().ledger.to_string()],
             self.this_canister_id,
             Int::from(u64::MAX),
@@ -66,8 +83,9 @@ impl SonicClient {
             .await?
             .0
         {
-            SonicResult::Ok(_tx_id) => {
-                unimplemented!()
+            SonicResult::Ok(tx_id) => {
+                let (tx,) = sonic_canister_c2c_client::get_transaction(self.sonic_canister_id, (tx_id,)).await?;
+                nat_to_u128(tx.amount1)
             }
             SonicResult::Err(error) => Err(convert_error(error)),
         }
@@ -76,7 +94,7 @@ impl SonicClient {
     pub async fn withdraw(&self, amount: u128) -> CallResult<u128> {
         let args = (self.output_token().ledger, amount.into());
         match sonic_canister_c2c_client::withdraw(self.sonic_canister_id, args).await?.0 {
-            SonicResult::Ok(amount_withdrawn) => Ok(nat_to_u128(amount_withdrawn)),
+            SonicResult::Ok(amount_withdrawn) => nat_to_u128(amount_withdrawn),
             SonicResult::Err(error) => Err(convert_error(error)),
         }
     }
@@ -113,10 +131,45 @@ async fn retrieve_subaccount(sonic_canister_id: CanisterId) -> CallResult<[u8; 3
     }
 }
 
-fn nat_to_u128(value: Nat) -> u128 {
-    value.0.try_into().unwrap()
+fn amount_out(amount: u128, reserve_in: u128, reserve_out: u128) -> u128 {
+    let amount_in_with_fee = amount.saturating_mul(997);
+    let numerator = amount_in_with_fee.saturating_mul(reserve_out);
+    let denominator = reserve_in.saturating_mul(1000).saturating_add(amount_in_with_fee);
+
+    if denominator == 0 {
+        0
+    } else {
+        numerator / denominator
+    }
+}
+
+fn nat_to_u128(value: Nat) -> CallResult<u128> {
+    value
+        .0
+        .try_into()
+        .map_err(|_| (RejectionCode::Unknown, "Value out of range".to_string()))
 }
 
 fn convert_error(error: String) -> (RejectionCode, String) {
     (RejectionCode::Unknown, error)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn amount_out_deducts_fee() {
+        assert_eq!(amount_out(1_000, 1_000_000, 1_000_000), 996);
+    }
+
+    #[test]
+    fn amount_out_with_empty_pool_is_zero() {
+        assert_eq!(amount_out(1_000, 0, 0), 0);
+    }
+
+    #[test]
+    fn out_of_range_nat_returns_error() {
+        assert!(nat_to_u128(Nat::from(u128::MAX) + Nat::from(1u32)).is_err());
+    }
+}
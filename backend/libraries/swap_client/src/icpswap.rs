@@ -1,4 +1,4 @@
-use super::swap_client::SwapClient;
+use crate::SwapClient;
 use async_trait::async_trait;
 use ic_cdk::api::call::CallResult;
 use icpswap_client::ICPSwapClient;
@@ -6,6 +6,10 @@ use icrc_ledger_types::icrc1::account::Account;
 
 #[async_trait]
 impl SwapClient for ICPSwapClient {
+    async fn quote(&self, amount: u128) -> CallResult<u128> {
+        self.quote(amount).await
+    }
+
     async fn deposit_account(&self) -> CallResult<Account> {
         Ok(self.deposit_account())
     }
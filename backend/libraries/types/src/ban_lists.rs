@@ -0,0 +1,30 @@
+use crate::{CommunityId, TimestampMillis, UserId};
+use candid::CandidType;
+use serde::{Deserialize, Serialize};
+
+pub const MAX_BAN_LIST_SUBSCRIPTIONS: usize = 10;
+pub const MAX_BAN_LIST_SUBSCRIBERS: usize = 1000;
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct BanListSubscriptionSummary {
+    pub community_id: CommunityId,
+    pub remove_existing_members: bool,
+    pub subscribed: TimestampMillis,
+    pub blocked_users: u32,
+}
+
+// Records each time a user was kept out of (or removed from) a community because they were
+// blocked in a community whose ban list it subscribes to
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct BanListAuditRecord {
+    pub user_id: UserId,
+    pub ban_list: CommunityId,
+    pub action: BanListAction,
+    pub timestamp: TimestampMillis,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Copy, Debug, Eq, PartialEq)]
+pub enum BanListAction {
+    JoinPrevented,
+    MemberRemoved,
+}
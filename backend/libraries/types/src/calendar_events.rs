@@ -0,0 +1,165 @@
+use crate::{ChannelId, EventIndex, MessageIndex, Milliseconds, TimestampMillis};
+use candid::CandidType;
+use serde::{Deserialize, Serialize};
+
+const MAX_TITLE_LENGTH: usize = 100;
+const MAX_DESCRIPTION_LENGTH: usize = 5_000;
+const MAX_LOCATION_LENGTH: usize = 500;
+const REMINDER_LEAD_TIME: Milliseconds = 60 * 60 * 1000; // 1 hour
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct CalendarEventContent {
+    pub details: CalendarEventDetails,
+    pub rsvps: CalendarEventRsvps,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct CalendarEventDetails {
+    pub title: String,
+    pub description: Option<String>,
+    pub start: TimestampMillis,
+    pub end: TimestampMillis,
+    // Either a physical address or a link to join the event online
+    pub location: Option<String>,
+    pub capacity: Option<u32>,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug, Default)]
+pub struct CalendarEventRsvps {
+    pub yes: u32,
+    pub no: u32,
+    pub maybe: u32,
+    pub user: Option<Rsvp>,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Rsvp {
+    Yes,
+    No,
+    Maybe,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct UpcomingCalendarEvent {
+    pub channel_id: ChannelId,
+    pub thread_root_message_index: Option<MessageIndex>,
+    pub message_index: MessageIndex,
+    pub event_index: EventIndex,
+    pub details: CalendarEventDetails,
+    pub rsvps: CalendarEventRsvps,
+}
+
+impl CalendarEventDetails {
+    pub fn validate(&self, is_direct_chat: bool, now: TimestampMillis) -> Result<(), InvalidCalendarEventReason> {
+        if is_direct_chat {
+            Err(InvalidCalendarEventReason::EventsNotValidForDirectChats)
+        } else if self.title.chars().count() > MAX_TITLE_LENGTH {
+            Err(InvalidCalendarEventReason::TitleTooLong(MAX_TITLE_LENGTH as u32))
+        } else if self
+            .description
+            .as_ref()
+            .map_or(false, |d| d.chars().count() > MAX_DESCRIPTION_LENGTH)
+        {
+            Err(InvalidCalendarEventReason::DescriptionTooLong(MAX_DESCRIPTION_LENGTH as u32))
+        } else if self
+            .location
+            .as_ref()
+            .map_or(false, |l| l.chars().count() > MAX_LOCATION_LENGTH)
+        {
+            Err(InvalidCalendarEventReason::LocationTooLong(MAX_LOCATION_LENGTH as u32))
+        } else if self.start < now {
+            Err(InvalidCalendarEventReason::StartInThePast)
+        } else if self.end <= self.start {
+            Err(InvalidCalendarEventReason::EndNotAfterStart)
+        } else if self.capacity == Some(0) {
+            Err(InvalidCalendarEventReason::CapacityCannotBeZero)
+        } else {
+            Ok(())
+        }
+    }
+
+    // Attendees are reminded shortly before the event starts, events created too close to their
+    // start time don't get a reminder
+    pub fn reminder_time(&self, now: TimestampMillis) -> Option<TimestampMillis> {
+        self.start.checked_sub(REMINDER_LEAD_TIME).filter(|t| *t > now)
+    }
+}
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub enum InvalidCalendarEventReason {
+    TitleTooLong(u32),
+    DescriptionTooLong(u32),
+    LocationTooLong(u32),
+    StartInThePast,
+    EndNotAfterStart,
+    CapacityCannotBeZero,
+    EventsNotValidForDirectChats,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HOUR: Milliseconds = 60 * 60 * 1000;
+
+    #[test]
+    fn valid_event() {
+        assert!(details(10 * HOUR, 11 * HOUR).validate(false, 0).is_ok());
+    }
+
+    #[test]
+    fn invalid_events() {
+        assert!(matches!(
+            details(10 * HOUR, 11 * HOUR).validate(true, 0),
+            Err(InvalidCalendarEventReason::EventsNotValidForDirectChats)
+        ));
+        assert!(matches!(
+            details(10 * HOUR, 11 * HOUR).validate(false, 11 * HOUR),
+            Err(InvalidCalendarEventReason::StartInThePast)
+        ));
+        assert!(matches!(
+            details(10 * HOUR, 10 * HOUR).validate(false, 0),
+            Err(InvalidCalendarEventReason::EndNotAfterStart)
+        ));
+        assert!(matches!(
+            CalendarEventDetails {
+                title: "x".repeat(MAX_TITLE_LENGTH + 1),
+                ..details(10 * HOUR, 11 * HOUR)
+            }
+            .validate(false, 0),
+            Err(InvalidCalendarEventReason::TitleTooLong(_))
+        ));
+        assert!(matches!(
+            CalendarEventDetails {
+                capacity: Some(0),
+                ..details(10 * HOUR, 11 * HOUR)
+            }
+            .validate(false, 0),
+            Err(InvalidCalendarEventReason::CapacityCannotBeZero)
+        ));
+    }
+
+    #[test]
+    fn reminder_sent_an_hour_before_start() {
+        let details = details(10 * HOUR, 11 * HOUR);
+
+        assert_eq!(details.reminder_time(0), Some(9 * HOUR));
+        assert_eq!(details.reminder_time(9 * HOUR), None);
+    }
+
+    #[test]
+    fn no_reminder_if_event_starts_within_the_hour() {
+        assert_eq!(details(HOUR / 2, HOUR).reminder_time(0), None);
+    }
+
+    fn details(start: TimestampMillis, end: TimestampMillis) -> CalendarEventDetails {
+        CalendarEventDetails {
+            title: "Event".to_string(),
+            description: None,
+            start,
+            end,
+            location: None,
+            capacity: None,
+        }
+    }
+}
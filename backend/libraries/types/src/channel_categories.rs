@@ -0,0 +1,15 @@
+use crate::ChannelId;
+use candid::CandidType;
+use serde::{Deserialize, Serialize};
+
+pub const MAX_CHANNEL_CATEGORIES: usize = 50;
+
+// Categories are returned in display order, as are the channels within each category. Channels
+// which aren't in any category are displayed separately from the categorised channels.
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct ChannelCategory {
+    pub category_id: u32,
+    pub name: String,
+    pub collapsed_by_default: bool,
+    pub channels: Vec<ChannelId>,
+}
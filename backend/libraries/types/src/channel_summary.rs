@@ -1,6 +1,6 @@
 use crate::{
-    AccessGate, ChannelId, ChatMetrics, EventIndex, EventWrapper, GroupMembership, GroupMembershipUpdates, GroupPermissions,
-    GroupSubtype, Message, MessageIndex, Milliseconds, OptionUpdate, TimestampMillis,
+    AccessGate, ChannelId, ChannelUserGroupPermissions, ChatMetrics, EventIndex, EventWrapper, GroupMembership,
+    GroupMembershipUpdates, GroupPermissions, GroupSubtype, Message, MessageIndex, Milliseconds, OptionUpdate, TimestampMillis,
 };
 use candid::CandidType;
 use serde::{Deserialize, Serialize};
@@ -29,6 +29,12 @@ pub struct CommunityCanisterChannelSummary {
     #[serde(default)]
     pub events_ttl_last_updated: TimestampMillis,
     pub gate: Option<AccessGate>,
+    #[serde(default)]
+    pub forum_tags: Option<Vec<String>>,
+    #[serde(default)]
+    pub user_group_permissions: ChannelUserGroupPermissions,
+    #[serde(default)]
+    pub audience_user_groups: Vec<u32>,
     pub membership: Option<GroupMembership>,
 }
 
@@ -54,6 +60,12 @@ pub struct CommunityCanisterChannelSummaryUpdates {
     #[serde(default)]
     pub events_ttl_last_updated: Option<TimestampMillis>,
     pub gate: OptionUpdate<AccessGate>,
+    #[serde(default)]
+    pub forum_tags: Option<Vec<String>>,
+    #[serde(default)]
+    pub user_group_permissions: Option<ChannelUserGroupPermissions>,
+    #[serde(default)]
+    pub audience_user_groups: Option<Vec<u32>>,
     pub membership: Option<GroupMembershipUpdates>,
 }
 
@@ -0,0 +1,41 @@
+use crate::{MessageId, MessageIndex, Milliseconds, TimestampMillis, UserId};
+use candid::CandidType;
+use serde::{Deserialize, Serialize};
+
+pub const MAX_CHAT_REPORT_TIMEOUT: Milliseconds = 28 * 24 * 60 * 60 * 1000; // 28 days
+
+// A message reported by members of a group or channel, to be handled by that chat's moderators
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct ChatReport {
+    pub report_id: u32,
+    pub thread_root_message_index: Option<MessageIndex>,
+    pub message_index: MessageIndex,
+    pub message_id: MessageId,
+    pub sender: UserId,
+    pub reported_by: Vec<UserId>,
+    pub first_reported: TimestampMillis,
+    pub resolution: Option<ChatReportResolution>,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct ChatReportResolution {
+    pub action: ChatReportAction,
+    pub resolved_by: UserId,
+    pub timestamp: TimestampMillis,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ChatReportAction {
+    Dismiss,
+    DeleteMessage,
+    TimeoutMember(Milliseconds),
+    RemoveMember,
+    EscalateToPlatform,
+}
+
+// The number of distinct messages sent by a member which have been reported
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct MemberReportCount {
+    pub user_id: UserId,
+    pub reports: u32,
+}
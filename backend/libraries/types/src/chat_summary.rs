@@ -334,6 +334,10 @@ pub struct ChatMetrics {
     pub reported_messages: u64,
     pub message_reminders: u64,
     pub custom_type_messages: u64,
+    #[serde(default)]
+    pub p2p_swaps: u64,
+    #[serde(default)]
+    pub calendar_events: u64,
     pub last_active: TimestampMillis,
 }
 
@@ -0,0 +1,246 @@
+use crate::{ChannelId, UserId};
+use candid::CandidType;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+const MAX_QUESTIONS: usize = 10;
+const MAX_OPTIONS_PER_QUESTION: usize = 10;
+const MAX_QUESTION_LENGTH: usize = 200;
+const MAX_OPTION_LENGTH: usize = 100;
+const MAX_WELCOME_MESSAGE_LENGTH: usize = 1_000;
+const WELCOME_MESSAGE_MEMBER_PLACEHOLDER: &str = "{member}";
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct CommunityOnboarding {
+    pub questions: Vec<OnboardingQuestion>,
+    pub welcome_message: Option<WelcomeMessage>,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct OnboardingQuestion {
+    pub text: String,
+    pub multiple_choice: bool,
+    pub options: Vec<OnboardingOption>,
+}
+
+// Choosing an option adds the member to its user groups and joins them to its channels
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct OnboardingOption {
+    pub text: String,
+    pub user_groups: Vec<u32>,
+    pub channels: Vec<ChannelId>,
+}
+
+// Sent by the OpenChat bot when a user joins the community. Any occurrences of "{member}" in the
+// text are replaced with a mention of the new member, otherwise the mention is prepended.
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct WelcomeMessage {
+    pub channel_id: ChannelId,
+    pub text: String,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct OnboardingAnswer {
+    pub question_index: u32,
+    pub option_indexes: Vec<u32>,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub enum InvalidOnboardingReason {
+    TooManyQuestions(u32),
+    TooManyOptions(u32),
+    QuestionEmpty,
+    QuestionTooLong(u32),
+    OptionEmpty,
+    OptionTooLong(u32),
+    WelcomeMessageEmpty,
+    WelcomeMessageTooLong(u32),
+}
+
+impl CommunityOnboarding {
+    pub fn validate(&self) -> Result<(), InvalidOnboardingReason> {
+        if self.questions.len() > MAX_QUESTIONS {
+            return Err(InvalidOnboardingReason::TooManyQuestions(MAX_QUESTIONS as u32));
+        }
+
+        for question in self.questions.iter() {
+            let question_length = question.text.trim().chars().count();
+            if question_length == 0 {
+                return Err(InvalidOnboardingReason::QuestionEmpty);
+            } else if question_length > MAX_QUESTION_LENGTH {
+                return Err(InvalidOnboardingReason::QuestionTooLong(MAX_QUESTION_LENGTH as u32));
+            } else if question.options.is_empty() || question.options.len() > MAX_OPTIONS_PER_QUESTION {
+                return Err(InvalidOnboardingReason::TooManyOptions(MAX_OPTIONS_PER_QUESTION as u32));
+            }
+
+            for option in question.options.iter() {
+                let option_length = option.text.trim().chars().count();
+                if option_length == 0 {
+                    return Err(InvalidOnboardingReason::OptionEmpty);
+                } else if option_length > MAX_OPTION_LENGTH {
+                    return Err(InvalidOnboardingReason::OptionTooLong(MAX_OPTION_LENGTH as u32));
+                }
+            }
+        }
+
+        if let Some(welcome_message) = &self.welcome_message {
+            let length = welcome_message.text.trim().chars().count();
+            if length == 0 {
+                return Err(InvalidOnboardingReason::WelcomeMessageEmpty);
+            } else if length > MAX_WELCOME_MESSAGE_LENGTH {
+                return Err(InvalidOnboardingReason::WelcomeMessageTooLong(
+                    MAX_WELCOME_MESSAGE_LENGTH as u32,
+                ));
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn user_group_ids(&self) -> impl Iterator<Item = u32> + '_ {
+        self.options().flat_map(|o| o.user_groups.iter().copied())
+    }
+
+    pub fn channel_ids(&self) -> impl Iterator<Item = ChannelId> + '_ {
+        self.options()
+            .flat_map(|o| o.channels.iter().copied())
+            .chain(self.welcome_message.as_ref().map(|w| w.channel_id))
+    }
+
+    // Returns the options chosen, or None if any of the answers are invalid or a question is answered
+    // more than once
+    pub fn chosen_options(&self, answers: &[OnboardingAnswer]) -> Option<Vec<&OnboardingOption>> {
+        let mut questions_answered = HashSet::new();
+        let mut chosen = Vec::new();
+        for answer in answers {
+            let question = self.questions.get(answer.question_index as usize)?;
+            if !questions_answered.insert(answer.question_index)
+                || answer.option_indexes.is_empty()
+                || (!question.multiple_choice && answer.option_indexes.len() > 1)
+            {
+                return None;
+            }
+            let option_indexes: HashSet<_> = answer.option_indexes.iter().collect();
+            for index in option_indexes {
+                chosen.push(question.options.get(*index as usize)?);
+            }
+        }
+        Some(chosen)
+    }
+
+    fn options(&self) -> impl Iterator<Item = &OnboardingOption> {
+        self.questions.iter().flat_map(|q| q.options.iter())
+    }
+}
+
+impl WelcomeMessage {
+    pub fn text_for_member(&self, user_id: UserId) -> String {
+        let mention = format!("@UserId({user_id})");
+        if self.text.contains(WELCOME_MESSAGE_MEMBER_PLACEHOLDER) {
+            self.text.replace(WELCOME_MESSAGE_MEMBER_PLACEHOLDER, &mention)
+        } else {
+            format!("{mention} {}", self.text)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use candid::Principal;
+
+    #[test]
+    fn valid_answers_return_chosen_options() {
+        let onboarding = onboarding();
+
+        let chosen = onboarding
+            .chosen_options(&[answer(0, vec![1]), answer(1, vec![0, 2])])
+            .unwrap();
+        let texts: Vec<_> = chosen.iter().map(|o| o.text.as_str()).collect();
+
+        assert_eq!(texts.len(), 3);
+        assert!(texts.contains(&"q0o1"));
+        assert!(texts.contains(&"q1o0"));
+        assert!(texts.contains(&"q1o2"));
+    }
+
+    #[test]
+    fn multiple_options_for_single_choice_question_rejected() {
+        assert!(onboarding().chosen_options(&[answer(0, vec![0, 1])]).is_none());
+    }
+
+    #[test]
+    fn question_answered_twice_rejected() {
+        assert!(onboarding()
+            .chosen_options(&[answer(0, vec![0]), answer(0, vec![1])])
+            .is_none());
+    }
+
+    #[test]
+    fn duplicate_options_only_chosen_once() {
+        let onboarding = onboarding();
+
+        let chosen = onboarding.chosen_options(&[answer(1, vec![2, 2])]).unwrap();
+
+        assert_eq!(chosen.len(), 1);
+    }
+
+    #[test]
+    fn unknown_question_or_option_rejected() {
+        let onboarding = onboarding();
+
+        assert!(onboarding.chosen_options(&[answer(2, vec![0])]).is_none());
+        assert!(onboarding.chosen_options(&[answer(0, vec![3])]).is_none());
+        assert!(onboarding.chosen_options(&[answer(0, Vec::new())]).is_none());
+    }
+
+    #[test]
+    fn welcome_message_mentions_member() {
+        let user_id: UserId = Principal::from_slice(&[1]).into();
+        let mention = format!("@UserId({user_id})");
+
+        assert_eq!(
+            welcome_message("Welcome {member}!").text_for_member(user_id),
+            format!("Welcome {mention}!")
+        );
+        assert_eq!(
+            welcome_message("Welcome!").text_for_member(user_id),
+            format!("{mention} Welcome!")
+        );
+    }
+
+    fn onboarding() -> CommunityOnboarding {
+        CommunityOnboarding {
+            questions: vec![question(0, false), question(1, true)],
+            welcome_message: None,
+        }
+    }
+
+    fn question(index: u32, multiple_choice: bool) -> OnboardingQuestion {
+        OnboardingQuestion {
+            text: format!("q{index}"),
+            multiple_choice,
+            options: (0..3)
+                .map(|i| OnboardingOption {
+                    text: format!("q{index}o{i}"),
+                    user_groups: vec![i],
+                    channels: Vec::new(),
+                })
+                .collect(),
+        }
+    }
+
+    fn answer(question_index: u32, option_indexes: Vec<u32>) -> OnboardingAnswer {
+        OnboardingAnswer {
+            question_index,
+            option_indexes,
+        }
+    }
+
+    fn welcome_message(text: &str) -> WelcomeMessage {
+        WelcomeMessage {
+            channel_id: 1,
+            text: text.to_string(),
+        }
+    }
+}
@@ -1,7 +1,8 @@
 use crate::user_groups::UserGroupSummary;
 use crate::{
-    AccessGate, ChannelId, ChatMetrics, CommunityCanisterChannelSummary, CommunityCanisterChannelSummaryUpdates, CommunityId,
-    CommunityPermissions, CommunityRole, EventIndex, FrozenGroupInfo, OptionUpdate, TimestampMillis,
+    AccessGate, ChannelCategory, ChannelId, ChatMetrics, CommunityCanisterChannelSummary,
+    CommunityCanisterChannelSummaryUpdates, CommunityId, CommunityOnboarding, CommunityPermissions, CommunityRole, EventIndex,
+    FrozenGroupInfo, OptionUpdate, TimestampMillis,
 };
 use candid::CandidType;
 use serde::{Deserialize, Serialize};
@@ -24,6 +25,10 @@ pub struct CommunityCanisterCommunitySummary {
     pub channels: Vec<CommunityCanisterChannelSummary>,
     pub membership: Option<CommunityMembership>,
     pub user_groups: Vec<UserGroupSummary>,
+    #[serde(default)]
+    pub channel_categories: Vec<ChannelCategory>,
+    #[serde(default)]
+    pub onboarding: Option<CommunityOnboarding>,
     pub metrics: ChatMetrics,
 }
 
@@ -56,6 +61,10 @@ pub struct CommunityCanisterCommunitySummaryUpdates {
     pub membership: Option<CommunityMembershipUpdates>,
     pub user_groups: Vec<UserGroupSummary>,
     pub user_groups_deleted: Vec<u32>,
+    #[serde(default)]
+    pub channel_categories: Option<Vec<ChannelCategory>>,
+    #[serde(default)]
+    pub onboarding: OptionUpdate<CommunityOnboarding>,
     pub metrics: Option<ChatMetrics>,
 }
 
@@ -6,6 +6,7 @@ use std::fmt::{Display, Formatter};
 pub enum ExchangeId {
     ICPSwap,
     Sonic,
+    ICDex,
 }
 
 impl Display for ExchangeId {
@@ -13,6 +14,7 @@ impl Display for ExchangeId {
         match self {
             ExchangeId::ICPSwap => f.write_str("ICPSwap"),
             ExchangeId::Sonic => f.write_str("Sonic"),
+            ExchangeId::ICDex => f.write_str("ICDex"),
         }
     }
 }
@@ -0,0 +1,62 @@
+use crate::{EventIndex, MessageIndex, TimestampMillis, UserId};
+use candid::CandidType;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+pub const MAX_FORUM_TAGS: usize = 50;
+pub const MAX_FORUM_TAG_LENGTH: usize = 30;
+pub const MAX_TAGS_PER_FORUM_POST: usize = 5;
+pub const MAX_FORUM_POST_TITLE_LENGTH: usize = 200;
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct ForumPostDetails {
+    pub title: String,
+    pub tags: Vec<String>,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct ForumPost {
+    pub message_index: MessageIndex,
+    pub event_index: EventIndex,
+    pub title: String,
+    pub tags: Vec<String>,
+    pub created_by: UserId,
+    pub created: TimestampMillis,
+    pub latest_activity: TimestampMillis,
+    pub reply_count: u32,
+    pub resolved: bool,
+    pub locked: bool,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Copy, Clone, Debug, Eq, PartialEq)]
+pub enum ForumPostSortOrder {
+    LatestActivity,
+    Created,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub enum ForumTagsValidationError {
+    TooManyTags(u32),
+    TagTooLong(u32),
+    TagEmpty,
+    DuplicateTag(String),
+}
+
+pub fn validate_forum_tags(tags: &[String]) -> Result<(), ForumTagsValidationError> {
+    if tags.len() > MAX_FORUM_TAGS {
+        return Err(ForumTagsValidationError::TooManyTags(MAX_FORUM_TAGS as u32));
+    }
+
+    let mut set = HashSet::new();
+    for tag in tags {
+        if tag.trim().is_empty() {
+            return Err(ForumTagsValidationError::TagEmpty);
+        } else if tag.chars().count() > MAX_FORUM_TAG_LENGTH {
+            return Err(ForumTagsValidationError::TagTooLong(MAX_FORUM_TAG_LENGTH as u32));
+        } else if !set.insert(tag.to_lowercase()) {
+            return Err(ForumTagsValidationError::DuplicateTag(tag.clone()));
+        }
+    }
+
+    Ok(())
+}
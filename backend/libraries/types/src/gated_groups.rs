@@ -9,6 +9,7 @@ pub enum AccessGate {
     VerifiedCredential(VerifiedCredentialGate),
     SnsNeuron(SnsNeuronGate),
     Payment(PaymentGate),
+    UserGroup(UserGroupGate),
 }
 
 #[derive(CandidType, Serialize, Deserialize, Clone, Debug, Eq, PartialEq)]
@@ -31,6 +32,12 @@ pub struct PaymentGate {
     pub fee: u128,
 }
 
+// Only valid for community channels, where the community checks the user group membership
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug, Eq, PartialEq)]
+pub struct UserGroupGate {
+    pub user_group_id: u32,
+}
+
 #[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
 pub enum GateCheckFailedReason {
     NotDiamondMember,
@@ -38,4 +45,5 @@ pub enum GateCheckFailedReason {
     NoSnsNeuronsWithRequiredStakeFound,
     NoSnsNeuronsWithRequiredDissolveDelayFound,
     PaymentFailed(TransferFromError),
+    NotInUserGroup,
 }
@@ -20,6 +20,9 @@ pub struct HttpResponse {
     pub headers: Vec<HeaderField>,
     pub body: ByteBuf,
     pub streaming_strategy: Option<StreamingStrategy>,
+    // If set to true, the boundary node will retry the request as an update call via
+    // `http_request_update`
+    pub upgrade: Option<bool>,
 }
 
 #[derive(CandidType, Deserialize, Clone, Debug)]
@@ -62,6 +65,14 @@ impl HttpResponse {
             headers: Vec::new(),
             body: ByteBuf::default(),
             streaming_strategy: None,
+            upgrade: None,
+        }
+    }
+
+    pub fn upgrade() -> HttpResponse {
+        HttpResponse {
+            upgrade: Some(true),
+            ..HttpResponse::status_code(200)
         }
     }
 
@@ -94,6 +105,7 @@ impl HttpResponse {
             headers,
             body: ByteBuf::default(),
             streaming_strategy: None,
+            upgrade: None,
         }
     }
 }
@@ -4,16 +4,21 @@ use serde::{Deserialize, Serialize};
 use std::collections::{BTreeMap, HashMap, HashSet};
 
 mod avatar;
+mod ban_lists;
 mod bots;
 mod build_version;
+mod calendar_events;
 mod canister_upgrade_status;
 mod canister_wasm;
+mod channel_categories;
 mod channel_summary;
 mod chat;
 mod chat_id;
+mod chat_reports;
 mod chat_summary;
 mod community_id;
 mod community_member;
+mod community_onboarding;
 mod community_roles;
 mod community_summary;
 mod cryptocurrency;
@@ -30,6 +35,7 @@ mod exchanges;
 mod field_invalid;
 mod file;
 mod file_status;
+mod forum;
 mod frozen_group_info;
 mod gated_groups;
 mod group_activity;
@@ -67,16 +73,21 @@ mod versioned;
 
 pub use crate::range_set::*;
 pub use avatar::*;
+pub use ban_lists::*;
 pub use bots::*;
 pub use build_version::*;
+pub use calendar_events::*;
 pub use canister_upgrade_status::*;
 pub use canister_wasm::*;
+pub use channel_categories::*;
 pub use channel_summary::*;
 pub use chat::*;
 pub use chat_id::*;
+pub use chat_reports::*;
 pub use chat_summary::*;
 pub use community_id::*;
 pub use community_member::*;
+pub use community_onboarding::*;
 pub use community_roles::*;
 pub use community_summary::*;
 pub use cryptocurrency::*;
@@ -93,6 +104,7 @@ pub use exchanges::*;
 pub use field_invalid::*;
 pub use file::*;
 pub use file_status::*;
+pub use forum::*;
 pub use frozen_group_info::*;
 pub use gated_groups::*;
 pub use group_activity::*;
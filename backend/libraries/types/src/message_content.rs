@@ -1,7 +1,8 @@
-use crate::polls::{InvalidPollReason, PollConfig, PollVotes};
+use crate::polls::{InvalidPollReason, PollConfig, PollResults, PollVotes};
 use crate::{
-    CanisterId, CompletedCryptoTransaction, CryptoTransaction, CryptoTransferDetails, Cryptocurrency, MessageIndex,
-    ProposalContent, TimestampMillis, TotalVotes, User, UserId,
+    AccessGate, CalendarEventContent, CanisterId, CompletedCryptoTransaction, CryptoTransaction, CryptoTransferDetails,
+    Cryptocurrency, GateCheckFailedReason, InvalidCalendarEventReason, MessageIndex, Milliseconds, ProposalContent,
+    TimestampMillis, TokenInfo, TotalVotes, User, UserId,
 };
 use candid::{CandidType, Principal};
 use ic_ledger_types::Tokens;
@@ -28,6 +29,8 @@ pub enum MessageContentInitial {
     Prize(PrizeContentInitial),
     MessageReminderCreated(MessageReminderCreatedContent),
     MessageReminder(MessageReminderContent),
+    P2PSwap(P2PSwapContentInitial),
+    CalendarEvent(CalendarEventContent),
     Custom(CustomContent),
 }
 
@@ -48,6 +51,8 @@ pub enum MessageContent {
     MessageReminderCreated(MessageReminderCreatedContent),
     MessageReminder(MessageReminderContent),
     ReportedMessage(ReportedMessage),
+    P2PSwap(P2PSwapContent),
+    CalendarEvent(CalendarEventContent),
     Custom(CustomContent),
 }
 
@@ -56,9 +61,11 @@ pub enum ContentValidationError {
     Empty,
     TextTooLong(u32),
     InvalidPoll(InvalidPollReason),
+    InvalidCalendarEvent(InvalidCalendarEventReason),
     TransferCannotBeZero,
     InvalidTypeForForwarding,
     PrizeEndDateInThePast,
+    InvalidPrizeEligibility,
     UnauthorizedToSendProposalMessages,
     Unauthorized,
 }
@@ -102,6 +109,8 @@ impl MessageContent {
             | MessageContent::MessageReminderCreated(_)
             | MessageContent::MessageReminder(_)
             | MessageContent::ReportedMessage(_)
+            | MessageContent::P2PSwap(_)
+            | MessageContent::CalendarEvent(_)
             | MessageContent::Custom(_) => {}
         }
 
@@ -125,6 +134,8 @@ impl MessageContent {
             MessageContent::MessageReminderCreated(_) => "MessageReminderCreated",
             MessageContent::MessageReminder(_) => "MessageReminder",
             MessageContent::ReportedMessage(_) => "ReportedMessage",
+            MessageContent::P2PSwap(_) => "P2PSwap",
+            MessageContent::CalendarEvent(_) => "CalendarEvent",
             MessageContent::Custom(c) => &c.kind,
         };
 
@@ -143,6 +154,8 @@ impl MessageContent {
             MessageContent::Giphy(g) => g.caption.as_deref(),
             MessageContent::GovernanceProposal(gp) => Some(gp.proposal.title()),
             MessageContent::Prize(p) => p.caption.as_deref(),
+            MessageContent::P2PSwap(p) => p.caption.as_deref(),
+            MessageContent::CalendarEvent(e) => Some(e.details.title.as_str()),
             MessageContent::Deleted(_)
             | MessageContent::PrizeWinner(_)
             | MessageContent::MessageReminderCreated(_)
@@ -186,6 +199,8 @@ impl MessageContent {
             | MessageContent::MessageReminderCreated(_)
             | MessageContent::MessageReminder(_)
             | MessageContent::ReportedMessage(_)
+            | MessageContent::P2PSwap(_)
+            | MessageContent::CalendarEvent(_)
             | MessageContent::Custom(_) => None,
         }
     }
@@ -240,7 +255,11 @@ impl MessageContentInitial {
     ) -> Result<(), ContentValidationError> {
         if forwarding {
             match self {
-                MessageContentInitial::Poll(_) | MessageContentInitial::Crypto(_) | MessageContentInitial::Deleted(_) => {
+                MessageContentInitial::Poll(_)
+                | MessageContentInitial::Crypto(_)
+                | MessageContentInitial::Deleted(_)
+                | MessageContentInitial::P2PSwap(_)
+                | MessageContentInitial::CalendarEvent(_) => {
                     return Err(ContentValidationError::InvalidTypeForForwarding);
                 }
                 _ => {}
@@ -253,6 +272,11 @@ impl MessageContentInitial {
                     return Err(ContentValidationError::InvalidPoll(reason));
                 }
             }
+            MessageContentInitial::CalendarEvent(e) => {
+                if let Err(reason) = e.details.validate(is_direct_chat, now) {
+                    return Err(ContentValidationError::InvalidCalendarEvent(reason));
+                }
+            }
             MessageContentInitial::Crypto(c) => {
                 if c.transfer.is_zero() {
                     return Err(ContentValidationError::TransferCannotBeZero);
@@ -262,6 +286,19 @@ impl MessageContentInitial {
                 if p.end_date <= now {
                     return Err(ContentValidationError::PrizeEndDateInThePast);
                 }
+                if !p.eligibility.iter().all(|e| e.is_valid()) {
+                    return Err(ContentValidationError::InvalidPrizeEligibility);
+                }
+            }
+            MessageContentInitial::P2PSwap(p) => {
+                // The offered funds are escrowed by the sender's user canister, so P2P swaps can
+                // only be offered in direct chats
+                if !is_direct_chat {
+                    return Err(ContentValidationError::Unauthorized);
+                }
+                if p.token0_amount == 0 || p.token1_amount == 0 {
+                    return Err(ContentValidationError::TransferCannotBeZero);
+                }
             }
             MessageContentInitial::GovernanceProposal(_) => {
                 if proposals_bot_user_id.map_or(true, |u| u != sender) {
@@ -289,12 +326,14 @@ impl MessageContentInitial {
             MessageContentInitial::File(f) => f.blob_reference.is_none(),
             MessageContentInitial::Poll(p) => p.config.options.is_empty(),
             MessageContentInitial::Prize(p) => p.prizes.is_empty(),
+            MessageContentInitial::CalendarEvent(e) => e.details.title.is_empty(),
             MessageContentInitial::Deleted(_) => true,
             MessageContentInitial::Crypto(_)
             | MessageContentInitial::Giphy(_)
             | MessageContentInitial::GovernanceProposal(_)
             | MessageContentInitial::MessageReminderCreated(_)
             | MessageContentInitial::MessageReminder(_)
+            | MessageContentInitial::P2PSwap(_)
             | MessageContentInitial::Custom(_) => false,
         };
 
@@ -326,6 +365,8 @@ impl MessageContentInitial {
             MessageContentInitial::Prize(p) => p.caption.as_deref(),
             MessageContentInitial::MessageReminderCreated(r) => r.notes.as_deref(),
             MessageContentInitial::MessageReminder(r) => r.notes.as_deref(),
+            MessageContentInitial::P2PSwap(p) => p.caption.as_deref(),
+            MessageContentInitial::CalendarEvent(e) => Some(e.details.title.as_str()),
             MessageContentInitial::Deleted(_) | MessageContentInitial::Custom(_) => None,
         }
     }
@@ -349,6 +390,8 @@ impl From<MessageContent> for MessageContentInitial {
             MessageContent::MessageReminderCreated(r) => MessageContentInitial::MessageReminderCreated(r),
             MessageContent::MessageReminder(r) => MessageContentInitial::MessageReminder(r),
             MessageContent::ReportedMessage(_) => panic!("Cannot send a 'reported message' message"),
+            MessageContent::P2PSwap(_) => panic!("Cannot convert output p2p swap to initial p2p swap"),
+            MessageContent::CalendarEvent(c) => MessageContentInitial::CalendarEvent(c),
             MessageContent::Custom(c) => MessageContentInitial::Custom(c),
         }
     }
@@ -375,9 +418,14 @@ impl From<MessageContentInitial> for MessageContent {
                 caption: c.caption,
                 prizes_pending: 0,
                 diamond_only: c.diamond_only,
+                distribution: c.distribution,
+                eligibility: c.eligibility,
+                entrants: 0,
             }),
             MessageContentInitial::MessageReminderCreated(r) => MessageContent::MessageReminderCreated(r),
             MessageContentInitial::MessageReminder(r) => MessageContent::MessageReminder(r),
+            MessageContentInitial::P2PSwap(_) => panic!("Cannot convert initial p2p swap to output p2p swap"),
+            MessageContentInitial::CalendarEvent(c) => MessageContent::CalendarEvent(c),
             MessageContentInitial::Custom(c) => MessageContent::Custom(c),
         }
     }
@@ -452,6 +500,8 @@ pub struct PollContent {
     pub config: PollConfig,
     pub votes: PollVotes,
     pub ended: bool,
+    #[serde(default)]
+    pub results: Option<PollResults>,
 }
 
 impl PollContent {
@@ -468,7 +518,8 @@ impl PollContent {
         self.votes = PollVotes {
             total: total_votes,
             user: Vec::new(),
-        }
+        };
+        self.results = None;
     }
 }
 
@@ -478,6 +529,7 @@ pub enum RegisterVoteResult {
     PollEnded,
     UserCannotChangeVote,
     OptionIndexOutOfRange,
+    InvalidRanking,
 }
 
 #[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
@@ -494,6 +546,11 @@ pub struct PrizeContentInitial {
     pub end_date: TimestampMillis,
     pub caption: Option<String>,
     pub diamond_only: bool,
+    #[serde(default)]
+    pub distribution: PrizeDistribution,
+    // Users must satisfy all of these rules in order to claim the prize or enter the draw
+    #[serde(default)]
+    pub eligibility: Vec<PrizeEligibility>,
 }
 
 #[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
@@ -505,6 +562,45 @@ pub struct PrizeContent {
     pub end_date: TimestampMillis,
     pub caption: Option<String>,
     pub diamond_only: bool,
+    pub distribution: PrizeDistribution,
+    pub eligibility: Vec<PrizeEligibility>,
+    pub entrants: u32,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum PrizeDistribution {
+    // Each prize goes to the next user to claim it
+    #[default]
+    FirstComeFirstServed,
+    // Users enter the draw by claiming, then at the end date each prize is awarded to a randomly
+    // chosen entrant
+    RandomDraw,
+    // Users enter by claiming, up to one entrant per prize, then at the end date the total prize
+    // fund is split equally between the entrants
+    ProportionalSplit,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub enum PrizeEligibility {
+    // The user must have been a member of the group or channel for at least this long
+    MinMembershipDuration(Milliseconds),
+    AccessGate(AccessGate),
+    // The user must have reacted to the message, replied in its thread or quoted it in a reply
+    EngagedWithMessage(MessageIndex),
+}
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub enum PrizeIneligibilityReason {
+    MembershipTooRecent,
+    GateCheckFailed(GateCheckFailedReason),
+    NotEngagedWithMessage,
+}
+
+impl PrizeEligibility {
+    pub fn is_valid(&self) -> bool {
+        // Payment gates would charge users to enter, which is not supported
+        !matches!(self, PrizeEligibility::AccessGate(AccessGate::Payment(_)))
+    }
 }
 
 #[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
@@ -542,6 +638,63 @@ pub struct MessageReport {
     pub notes: Option<String>,
 }
 
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct P2PSwapContentInitial {
+    pub token0: TokenInfo,
+    pub token0_amount: u128,
+    pub token1: TokenInfo,
+    pub token1_amount: u128,
+    pub expires_in: Milliseconds,
+    pub caption: Option<String>,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct P2PSwapContent {
+    pub swap_id: u32,
+    pub token0: TokenInfo,
+    pub token0_amount: u128,
+    pub token1: TokenInfo,
+    pub token1_amount: u128,
+    pub expires_at: TimestampMillis,
+    pub caption: Option<String>,
+    pub token0_txn_in: u64,
+    pub status: P2PSwapStatus,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub enum P2PSwapStatus {
+    Open,
+    Cancelled(P2PSwapCancelled),
+    Expired(P2PSwapCancelled),
+    Reserved(P2PSwapReserved),
+    Accepted(P2PSwapAccepted),
+    Completed(P2PSwapCompleted),
+}
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct P2PSwapCancelled {
+    pub token0_txn_out: Option<u64>,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct P2PSwapReserved {
+    pub reserved_by: UserId,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct P2PSwapAccepted {
+    pub accepted_by: UserId,
+    pub token1_txn_in: u64,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct P2PSwapCompleted {
+    pub accepted_by: UserId,
+    pub token1_txn_in: u64,
+    pub token0_txn_out: u64,
+    pub token1_txn_out: u64,
+}
+
 #[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
 pub struct CustomContent {
     pub kind: String,
@@ -23,6 +23,10 @@ pub enum Notification {
     DirectMessageTipped(DirectMessageTipped),
     GroupMessageTipped(GroupMessageTipped),
     ChannelMessageTipped(ChannelMessageTipped),
+    GroupCalendarEventReminder(GroupCalendarEventReminder),
+    ChannelCalendarEventReminder(ChannelCalendarEventReminder),
+    GroupMessageReported(GroupMessageReported),
+    ChannelMessageReported(ChannelMessageReported),
 }
 
 #[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
@@ -176,6 +180,58 @@ pub struct ChannelMessageTipped {
     pub channel_avatar_id: Option<u128>,
 }
 
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct GroupCalendarEventReminder {
+    pub chat_id: ChatId,
+    pub thread_root_message_index: Option<MessageIndex>,
+    pub message_index: MessageIndex,
+    pub message_event_index: EventIndex,
+    pub group_name: String,
+    pub title: String,
+    pub start: TimestampMillis,
+    pub location: Option<String>,
+    pub group_avatar_id: Option<u128>,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct ChannelCalendarEventReminder {
+    pub community_id: CommunityId,
+    pub channel_id: ChannelId,
+    pub thread_root_message_index: Option<MessageIndex>,
+    pub message_index: MessageIndex,
+    pub message_event_index: EventIndex,
+    pub community_name: String,
+    pub channel_name: String,
+    pub title: String,
+    pub start: TimestampMillis,
+    pub location: Option<String>,
+    pub community_avatar_id: Option<u128>,
+    pub channel_avatar_id: Option<u128>,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct GroupMessageReported {
+    pub chat_id: ChatId,
+    pub report_id: u32,
+    pub thread_root_message_index: Option<MessageIndex>,
+    pub message_index: MessageIndex,
+    pub group_name: String,
+    pub group_avatar_id: Option<u128>,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct ChannelMessageReported {
+    pub community_id: CommunityId,
+    pub channel_id: ChannelId,
+    pub report_id: u32,
+    pub thread_root_message_index: Option<MessageIndex>,
+    pub message_index: MessageIndex,
+    pub community_name: String,
+    pub channel_name: String,
+    pub community_avatar_id: Option<u128>,
+    pub channel_avatar_id: Option<u128>,
+}
+
 #[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
 pub struct CryptoTransferDetails {
     pub recipient: UserId,
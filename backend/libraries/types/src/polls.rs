@@ -1,5 +1,5 @@
-use crate::{TimestampMillis, UserId};
-use candid::CandidType;
+use crate::{icrc1, CanisterId, Cryptocurrency, PendingCryptoTransaction, TimestampMillis, TimestampNanos, UserId};
+use candid::{CandidType, Principal};
 use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, HashSet};
 
@@ -17,14 +17,77 @@ pub struct PollConfig {
     pub allow_multiple_votes_per_user: bool,
     #[serde(default = "allow_change_default")]
     pub allow_user_to_change_vote: bool,
+    #[serde(default)]
+    pub kind: PollKind,
+    #[serde(default)]
+    pub vote_weighting: Option<PollVoteWeighting>,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug, Default, Eq, PartialEq)]
+pub enum PollKind {
+    #[default]
+    Standard,
+    // Each voter ranks the options in order of preference and the winner is decided by instant-runoff
+    RankedChoice,
+    // Holds the index of the correct option, which is hidden from each user until they have voted
+    // or the poll has ended
+    Quiz(Option<u32>),
+}
+
+// Each vote is weighted by the amount of the given ICRC-1 token the voter locks in the chat until
+// the poll ends, or by the total stake of their neurons in the given SNS which can't be dissolved
+// before the poll ends. The weight is captured when the user first votes.
+#[derive(CandidType, Serialize, Deserialize, Clone, Copy, Debug, Eq, PartialEq)]
+pub enum PollVoteWeighting {
+    TokenBalance(CanisterId),
+    NeuronStake(CanisterId),
+}
+
+// Tokens transferred from a voter into the chat canister to weight their vote, which are refunded
+// when the poll ends
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct PollVoteTokenLock {
+    pub ledger: CanisterId,
+    pub token: Cryptocurrency,
+    pub amount: u128,
+    pub fee: u128,
+}
+
+impl PollVoteTokenLock {
+    pub fn refund(&self, user_id: UserId, memo: &[u8], now_nanos: TimestampNanos) -> Option<PendingCryptoTransaction> {
+        let amount = self.amount.saturating_sub(self.fee);
+        (amount > 0).then(|| {
+            PendingCryptoTransaction::ICRC1(icrc1::PendingCryptoTransaction {
+                ledger: self.ledger,
+                token: self.token.clone(),
+                amount,
+                to: Principal::from(user_id).into(),
+                fee: self.fee,
+                memo: Some(memo.to_vec().into()),
+                created: now_nanos,
+            })
+        })
+    }
 }
 
 #[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
 pub struct PollVotes {
     pub total: TotalVotes,
+    // For ranked choice polls, this is the user's ranking of the options, most preferred first
     pub user: Vec<u32>,
 }
 
+// Only populated for weighted or ranked choice polls, and only once the votes are visible
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug, Default)]
+pub struct PollResults {
+    // The total weight of the votes for each option, or for ranked choice polls, of the first
+    // preferences for each option
+    pub option_weights: HashMap<u32, u128>,
+    // For ranked choice polls, the tallies of the remaining options in each round of instant-runoff
+    pub rounds: Vec<HashMap<u32, u128>>,
+    pub winner: Option<u32>,
+}
+
 impl PollConfig {
     pub fn validate(&self, is_direct_chat: bool, now: TimestampMillis) -> Result<(), InvalidPollReason> {
         let options = self.options.len();
@@ -40,8 +103,24 @@ impl PollConfig {
             Err(InvalidPollReason::DuplicateOptions)
         } else if self.end_date.unwrap_or(u64::MAX) < now {
             Err(InvalidPollReason::EndDateInThePast)
+        } else if self.vote_weighting.is_some() && self.end_date.is_none() {
+            // Locked tokens are refunded, and neurons must stay locked, until the poll ends
+            Err(InvalidPollReason::WeightedPollRequiresEndDate)
         } else {
-            Ok(())
+            match self.kind {
+                PollKind::Standard => Ok(()),
+                PollKind::RankedChoice if self.allow_multiple_votes_per_user => Err(InvalidPollReason::InvalidRankedChoice),
+                PollKind::RankedChoice => Ok(()),
+                // Otherwise users could vote, see the correct option and then change their vote
+                PollKind::Quiz(Some(correct_option))
+                    if (correct_option as usize) < options
+                        && !self.allow_multiple_votes_per_user
+                        && !self.allow_user_to_change_vote =>
+                {
+                    Ok(())
+                }
+                PollKind::Quiz(_) => Err(InvalidPollReason::InvalidQuiz),
+            }
         }
     }
 
@@ -76,4 +155,7 @@ pub enum InvalidPollReason {
     DuplicateOptions,
     EndDateInThePast,
     PollsNotValidForDirectChats,
+    InvalidRankedChoice,
+    InvalidQuiz,
+    WeightedPollRequiresEndDate,
 }
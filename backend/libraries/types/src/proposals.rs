@@ -1,4 +1,6 @@
-use crate::{CanisterId, MessageId, NnsNeuronId, ProposalId, SnsNeuronId, TimestampMillis};
+use crate::{
+    CanisterId, MessageId, MessageIndex, MultiUserChat, NnsNeuronId, ProposalId, SnsNeuronId, TimestampMillis, UserId,
+};
 use candid::CandidType;
 use serde::{Deserialize, Serialize};
 
@@ -24,6 +26,15 @@ impl Proposal {
         }
     }
 
+    // For NNS proposals this is the proposal's topic, for SNS proposals it is the id of the
+    // proposal's action (aka function)
+    pub fn topic(&self) -> u64 {
+        match self {
+            Proposal::NNS(p) => p.topic as u64,
+            Proposal::SNS(p) => p.action,
+        }
+    }
+
     pub fn created(&self) -> TimestampMillis {
         match self {
             Proposal::NNS(p) => p.created,
@@ -154,6 +165,24 @@ pub struct ProposalContent {
     pub my_vote: Option<bool>,
 }
 
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct UnvotedProposal {
+    pub chat: MultiUserChat,
+    pub message_index: MessageIndex,
+    pub governance_canister_id: CanisterId,
+    pub proposal_id: ProposalId,
+    pub title: String,
+    pub deadline: TimestampMillis,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct ProposalVoteDelegation {
+    pub delegate: UserId,
+    // If set, only votes on proposals with these topics are followed
+    pub topics: Option<Vec<u64>>,
+    pub opted_out: Vec<MessageIndex>,
+}
+
 #[derive(CandidType, Serialize, Deserialize, Debug, Clone)]
 pub struct ProposalUpdate {
     pub message_id: MessageId,
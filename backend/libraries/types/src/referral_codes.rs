@@ -1,3 +1,4 @@
+use crate::{ChatId, CommunityId};
 use candid::CandidType;
 use serde::{Deserialize, Serialize};
 
@@ -6,3 +7,10 @@ pub enum ReferralType {
     BtcMiami,
     User,
 }
+
+// The group or community which users who register using a referral campaign's code are joined to
+#[derive(CandidType, Serialize, Deserialize, Debug, Clone, Copy, Hash, PartialEq, Eq)]
+pub enum ReferralCampaignTarget {
+    Group(ChatId),
+    Community(CommunityId),
+}
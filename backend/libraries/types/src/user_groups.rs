@@ -15,3 +15,17 @@ pub struct UserGroupDetails {
     pub name: String,
     pub members: Vec<UserId>,
 }
+
+// When a list is non-empty, only members of at least one of the listed user groups (plus the
+// channel's moderators and above) can perform that action
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug, Default)]
+pub struct ChannelUserGroupPermissions {
+    pub send_messages: Vec<u32>,
+    pub react_to_messages: Vec<u32>,
+}
+
+impl ChannelUserGroupPermissions {
+    pub fn user_group_ids(&self) -> impl Iterator<Item = u32> + '_ {
+        self.send_messages.iter().chain(self.react_to_messages.iter()).copied()
+    }
+}
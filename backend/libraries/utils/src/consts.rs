@@ -26,6 +26,10 @@ pub const MEMO_PRIZE_CLAIM: [u8; 8] = [0x4f, 0x43, 0x5f, 0x50, 0x52, 0x5a, 0x43,
 pub const MEMO_PRIZE_REFUND: [u8; 8] = [0x4f, 0x43, 0x5f, 0x50, 0x52, 0x5a, 0x52, 0x46]; // OC_PRZRF
 pub const MEMO_SWAP: [u8; 7] = [0x4F, 0x43, 0x5F, 0x53, 0x57, 0x41, 0x50]; // OC_SWAP
 pub const MEMO_JOINING_FEE: [u8; 7] = [0x4f, 0x43, 0x5f, 0x4A, 0x4F, 0x49, 0x4E]; // OC_JOIN
+pub const MEMO_STANDING_ORDER: [u8; 6] = [0x4f, 0x43, 0x5f, 0x53, 0x54, 0x4f]; // OC_STO
+pub const MEMO_P2P_SWAP: [u8; 6] = [0x4f, 0x43, 0x5f, 0x50, 0x32, 0x50]; // OC_P2P
+pub const MEMO_POLL_VOTE_LOCK: [u8; 7] = [0x4f, 0x43, 0x5f, 0x50, 0x4f, 0x4c, 0x4c]; // OC_POLL
+pub const MEMO_POLL_VOTE_REFUND: [u8; 8] = [0x4f, 0x43, 0x5f, 0x50, 0x4f, 0x4c, 0x52, 0x46]; // OC_POLRF
 
 #[cfg(test)]
 mod tests {